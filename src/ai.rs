@@ -1,6 +1,7 @@
 //! AI commit message generation with extensible backend support.
 //!
-//! Provides `AiProvider` enum dispatch for generating commit messages from staged diffs.
+//! Provides `AiProvider` enum dispatch for generating commit messages from staged diffs
+//! and pull request descriptions from a branch's commits.
 //! Currently supports `claude -p` CLI; adding new backends requires only a new enum variant
 //! and a single `generate()` function.
 
@@ -9,6 +10,8 @@ use std::sync::mpsc::{self, Receiver};
 
 use winit::event_loop::EventLoopProxy;
 
+use crate::git::GitRepo;
+
 /// Which AI backend to use for commit message generation.
 #[derive(Clone, Debug)]
 pub enum AiProvider {
@@ -23,6 +26,16 @@ pub struct AiRequest {
     pub branch: String,
 }
 
+/// Input for AI pull request title / description generation.
+pub struct AiPullRequestRequest {
+    pub branch: String,
+    pub target: String,
+    /// Full messages of the commits being proposed, oldest first
+    /// (see [`crate::git::PullRequestDraft::messages`]).
+    pub messages: Vec<String>,
+    pub diff_stat: String,
+}
+
 /// Parsed AI response with subject and optional body.
 pub struct AiResponse {
     pub subject: String,
@@ -37,6 +50,17 @@ impl AiProvider {
         }
     }
 
+    /// Blocking call — run in a background thread. `subject` is the
+    /// pull request title and `body` its description.
+    pub fn generate_pull_request(
+        &self,
+        request: &AiPullRequestRequest,
+    ) -> Result<AiResponse, String> {
+        match self {
+            AiProvider::ClaudeCli => claude_cli::generate_pull_request(request),
+        }
+    }
+
     pub fn display_name(&self) -> &str {
         match self {
             AiProvider::ClaudeCli => "Claude CLI",
@@ -68,26 +92,15 @@ pub fn spawn_generate_async(
 ) -> Receiver<Result<AiResponse, String>> {
     let (tx, rx) = mpsc::channel();
     std::thread::spawn(move || {
-        let diff_text = match std::process::Command::new("git")
-            .args(["diff", "--cached"])
-            .current_dir(&workdir)
-            .output()
-        {
-            Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout).into_owned(),
-            Ok(o) => {
-                let _ = tx.send(Err(format!(
-                    "git diff --cached failed: {}",
-                    String::from_utf8_lossy(&o.stderr).trim()
-                )));
-                let _ = proxy.send_event(());
-                return;
-            }
-            Err(e) => {
-                let _ = tx.send(Err(format!("Failed to run git diff: {e}")));
-                let _ = proxy.send_event(());
-                return;
-            }
-        };
+        let diff_text =
+            match GitRepo::open(&workdir).and_then(|repo| repo.git_stdout(["diff", "--cached"])) {
+                Ok(diff_text) => diff_text,
+                Err(e) => {
+                    let _ = tx.send(Err(format!("git diff --cached failed: {e:#}")));
+                    let _ = proxy.send_event(());
+                    return;
+                }
+            };
         if diff_text.trim().is_empty() {
             let _ = tx.send(Err(
                 "Nothing staged to summarize. Stage some changes first.".to_string(),
@@ -103,8 +116,48 @@ pub fn spawn_generate_async(
     rx
 }
 
+/// Spawn a worker thread that collects the messages of the commits
+/// `branch` adds on top of `base` (a remote-tracking ref like
+/// `origin/main`) from [`GitRepo::pull_request_draft`] and asks
+/// `provider` for a pull request title and description targeting
+/// `target`.
+pub fn spawn_generate_pull_request_async(
+    workdir: PathBuf,
+    branch: String,
+    base: String,
+    target: String,
+    provider: AiProvider,
+    proxy: EventLoopProxy<()>,
+) -> Receiver<Result<AiResponse, String>> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let result = GitRepo::open(&workdir)
+            .and_then(|repo| {
+                let draft = repo.pull_request_draft(&base, &branch)?;
+                let diff_stat =
+                    repo.git_stdout(["diff", "--stat", &format!("{base}...{branch}")])?;
+                Ok((draft.messages, diff_stat))
+            })
+            .map_err(|e| format!("{e:#}"))
+            .and_then(|(messages, diff_stat)| {
+                if messages.is_empty() {
+                    return Err(format!("{branch} has no commits that aren't on {base}."));
+                }
+                provider.generate_pull_request(&AiPullRequestRequest {
+                    branch,
+                    target,
+                    messages,
+                    diff_stat,
+                })
+            });
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+    rx
+}
+
 mod claude_cli {
-    use super::{AiRequest, AiResponse};
+    use super::{AiPullRequestRequest, AiRequest, AiResponse};
     use std::process::Command;
 
     fn find_claude_binary() -> Option<String> {
@@ -128,10 +181,6 @@ mod claude_cli {
     }
 
     pub fn generate(req: &AiRequest) -> Result<AiResponse, String> {
-        let binary = find_claude_binary().ok_or_else(|| {
-            "Claude CLI not found. Install it from https://docs.anthropic.com/en/docs/claude-code".to_string()
-        })?;
-
         let prompt = format!(
             "Generate a git commit message for the following staged diff.\n\
              Branch: {}\n\n\
@@ -144,10 +193,36 @@ mod claude_cli {
              Diff:\n{}",
             req.branch, req.diff_text
         );
+        run(&prompt).map(|text| split_response(&text))
+    }
+
+    pub fn generate_pull_request(req: &AiPullRequestRequest) -> Result<AiResponse, String> {
+        let prompt = format!(
+            "Write a pull request title and description for merging branch {} into {}.\n\n\
+             Rules:\n\
+             - First line is the title: imperative, max 72 characters\n\
+             - Then a blank line and a description explaining what changed and why\n\
+             - The description may use markdown bullet lists\n\
+             - Output ONLY the title and description, nothing else\n\n\
+             Commits (separated by ---):\n{}\n\
+             Changed files:\n{}",
+            req.branch,
+            req.target,
+            req.messages.join("\n---\n"),
+            req.diff_stat
+        );
+        run(&prompt).map(|text| split_response(&text))
+    }
+
+    /// Run a one-shot prompt and return the model's text.
+    fn run(prompt: &str) -> Result<String, String> {
+        let binary = find_claude_binary().ok_or_else(|| {
+            "Claude CLI not found. Install it from https://docs.anthropic.com/en/docs/claude-code".to_string()
+        })?;
 
         let output = Command::new(&binary)
             .arg("-p")
-            .arg(&prompt)
+            .arg(prompt)
             .arg("--model")
             .arg("haiku")
             .arg("--output-format")
@@ -166,13 +241,14 @@ mod claude_cli {
         let parsed: serde_json::Value = serde_json::from_str(&stdout)
             .map_err(|e| format!("Failed to parse Claude response: {}", e))?;
 
-        let result_text = parsed
+        parsed
             .get("result")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| "Claude response missing 'result' field".to_string())?
-            .trim()
-            .to_string();
+            .map(|s| s.trim().to_string())
+            .ok_or_else(|| "Claude response missing 'result' field".to_string())
+    }
 
+    fn split_response(result_text: &str) -> AiResponse {
         // Split on first double-newline into subject + body
         let (subject, body) = if let Some(pos) = result_text.find("\n\n") {
             (
@@ -180,7 +256,7 @@ mod claude_cli {
                 result_text[pos + 2..].trim().to_string(),
             )
         } else {
-            (result_text, String::new())
+            (result_text.to_string(), String::new())
         };
 
         // Ensure subject doesn't exceed 72 chars
@@ -190,6 +266,6 @@ mod claude_cli {
             subject
         };
 
-        Ok(AiResponse { subject, body })
+        AiResponse { subject, body }
     }
 }
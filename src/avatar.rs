@@ -76,7 +76,9 @@ impl AvatarCache {
         }
     }
 
-    /// Spawn a download for this email if we haven't already. The
+    /// Spawn a download for this email if we haven't already. Callers
+    /// pass the mailmap-resolved email (`CommitInfo::author_email`) so
+    /// an author who changed addresses shares one avatar. The
     /// email is normalized (trimmed + lower-cased) per the Gravatar
     /// hashing contract, and used as the cache key. Without a proxy
     /// the request still spawns; the worker just won't wake the
@@ -315,6 +315,7 @@ fn build_scenes(opened: &[RepoTab]) -> Vec<(String, WhisperApp)> {
                 form: MergeForm {
                     strategy: MergeStrategy::NoFf,
                     no_ff_message: "Merge feature/aetna-port".to_string(),
+                    forecast: None,
                 },
                 source: "feature/aetna-port".to_string(),
             });
@@ -329,6 +330,7 @@ fn build_scenes(opened: &[RepoTab]) -> Vec<(String, WhisperApp)> {
                 form: RebaseForm {
                     autostash: true,
                     rebase_merges: false,
+                    forecast: None,
                 },
                 base: "origin/main".to_string(),
             });
@@ -598,16 +600,22 @@ fn inject_synthetic_ci(tab: &mut RepoTab) {
             label: "build".into(),
             state: CiState::Failure,
             url: Some("https://example.com/runs/build".into()),
+            run_id: None,
+            required: true,
         },
         CiCheckStatus {
             label: "test".into(),
             state: CiState::Success,
             url: None,
+            run_id: None,
+            required: false,
         },
         CiCheckStatus {
             label: "lint".into(),
             state: CiState::Pending,
             url: None,
+            run_id: None,
+            required: false,
         },
     ];
     let gh_counts = CiCounts::from_states(gh_runs.iter().map(|c| c.state));
@@ -636,12 +644,15 @@ fn inject_synthetic_ci(tab: &mut RepoTab) {
                     label: "build".into(),
                     state,
                     url: None,
+                    run_id: None,
+                    required: false,
                 }],
             },
         );
     }
     let github = ProviderCiResult {
         provider: CiProvider::GitHub,
+        host: String::new(),
         status: CiStatus {
             state: CiState::Failure,
             summary: "1 failed, 1 pending, 1 passed".into(),
@@ -649,6 +660,7 @@ fn inject_synthetic_ci(tab: &mut RepoTab) {
             counts: Some(gh_counts),
         },
         per_commit_rollups: gh_per_commit,
+        stale: false,
     };
 
     let mut gl_per_commit: HashMap<String, CiCommitRollup> = HashMap::new();
@@ -664,11 +676,14 @@ fn inject_synthetic_ci(tab: &mut RepoTab) {
                 label: "Pipeline #4242".into(),
                 state: CiState::Success,
                 url: Some("https://gitlab.example/pipeline/4242".into()),
+                run_id: None,
+                required: false,
             }],
         },
     );
     let gitlab = ProviderCiResult {
         provider: CiProvider::GitLab,
+        host: String::new(),
         status: CiStatus {
             state: CiState::Success,
             summary: "Pipeline passed".into(),
@@ -680,6 +695,7 @@ fn inject_synthetic_ci(tab: &mut RepoTab) {
             }),
         },
         per_commit_rollups: gl_per_commit,
+        stale: false,
     };
 
     tab.ci_results = vec![github, gitlab];
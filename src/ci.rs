@@ -3,12 +3,18 @@
 //! Shared data structures consumed by the UI to display CI results from
 //! any provider (GitHub Actions, GitLab CI, etc.).
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CiProvider {
     GitHub,
     GitLab,
+    /// Gitea and its Forgejo fork, which share an API.
+    Gitea,
+    /// Statuses read from a local JSON file by `fake_ci`, for working
+    /// on the CI UI without a network.
+    Local,
 }
 
 impl CiProvider {
@@ -17,6 +23,8 @@ impl CiProvider {
         match self {
             CiProvider::GitHub => "GH",
             CiProvider::GitLab => "GL",
+            CiProvider::Gitea => "GT",
+            CiProvider::Local => "LO",
         }
     }
 
@@ -25,11 +33,13 @@ impl CiProvider {
         match self {
             CiProvider::GitHub => 0,
             CiProvider::GitLab => 1,
+            CiProvider::Gitea => 2,
+            CiProvider::Local => 3,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum CiState {
     Success,
     Failure,
@@ -38,7 +48,7 @@ pub enum CiState {
     None,
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CiCounts {
     pub success: usize,
     pub failure: usize,
@@ -76,20 +86,94 @@ impl CiCounts {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CiCheckStatus {
     pub label: String,
     pub state: CiState,
     pub url: Option<String>,
+    /// Workflow run (GitHub) or pipeline (GitLab) ID, for listing its
+    /// jobs in the log viewer.
+    pub run_id: Option<u64>,
+    /// Required by branch protection on the default branch. Only GitHub
+    /// reports this.
+    pub required: bool,
+}
+
+/// Cap on a downloaded job log. Larger logs fail to load rather than
+/// stall the viewer; the browser link still works for them.
+pub const MAX_LOG_BYTES: u64 = 32 * 1024 * 1024;
+
+/// One job of a workflow run or pipeline, as listed in the log viewer.
+#[derive(Debug, Clone)]
+pub struct CiJob {
+    pub id: u64,
+    pub name: String,
+    /// GitLab stage; `None` on GitHub.
+    pub stage: Option<String>,
+    pub state: CiState,
+    pub url: Option<String>,
 }
 
-#[derive(Debug, Clone, Default)]
+/// A write action against a provider's CI, started from a commit's CI
+/// token or the header indicator. Run IDs are [`CiCheckStatus::run_id`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CiAction {
+    /// Re-run only the failed jobs of each run.
+    RerunFailed(Vec<u64>),
+    /// Re-run every job of each run.
+    RerunAll(Vec<u64>),
+    /// Cancel each run still in progress.
+    Cancel(Vec<u64>),
+    /// Start a run on `git_ref`: a `workflow_dispatch` of `workflow`
+    /// (file name or ID) on GitHub, a new pipeline on GitLab, where
+    /// `workflow` is unused. `inputs` become workflow inputs or
+    /// pipeline variables.
+    Trigger {
+        workflow: String,
+        git_ref: String,
+        inputs: Vec<(String, String)>,
+    },
+}
+
+impl CiAction {
+    /// Toast shown once the provider accepted the action.
+    pub fn done_message(&self) -> &'static str {
+        match self {
+            CiAction::RerunFailed(_) => "Re-running failed jobs",
+            CiAction::RerunAll(_) => "Re-running all jobs",
+            CiAction::Cancel(_) => "Cancel requested",
+            CiAction::Trigger { .. } => "Run started",
+        }
+    }
+}
+
+/// Parse `key=value` lines into workflow inputs / pipeline variables.
+/// Blank lines and `#` comments are skipped; values keep inner spaces.
+pub fn parse_inputs(text: &str) -> Result<Vec<(String, String)>, String> {
+    let mut inputs = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("Expected key=value, got \"{line}\""))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("Missing name in \"{line}\""));
+        }
+        inputs.push((key.to_string(), value.trim().to_string()));
+    }
+    Ok(inputs)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CiCommitRollup {
     pub counts: CiCounts,
     pub checks: Vec<CiCheckStatus>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CiStatus {
     pub state: CiState,
     /// Human-readable summary (e.g. "CI passed" or "2/3 checks passed")
@@ -99,22 +183,70 @@ pub struct CiStatus {
     pub counts: Option<CiCounts>,
 }
 
-#[derive(Debug, Clone)]
+/// Header summary from the default branch head's rollup, for
+/// providers that report per-commit statuses rather than runs.
+pub fn status_for_head(head: Option<&CiCommitRollup>) -> CiStatus {
+    let Some(head) = head.filter(|h| !h.checks.is_empty()) else {
+        return CiStatus {
+            state: CiState::None,
+            summary: "No CI statuses".into(),
+            url: None,
+            counts: Some(CiCounts::default()),
+        };
+    };
+    let counts = head.counts;
+    let state = counts.overall_state();
+    let summary = match state {
+        CiState::Failure => format!(
+            "{} failed, {} pending, {} passed",
+            counts.failure, counts.pending, counts.success
+        ),
+        CiState::Pending => format!("{} pending, {} passed", counts.pending, counts.success),
+        CiState::Success => format!("{}/{} checks passed", counts.success, counts.total()),
+        CiState::None => "No CI statuses".into(),
+    };
+    let url = head
+        .checks
+        .iter()
+        .find(|c| c.state == CiState::Failure && c.url.is_some())
+        .or_else(|| head.checks.iter().find(|c| c.url.is_some()))
+        .and_then(|c| c.url.clone());
+    CiStatus {
+        state,
+        summary,
+        url,
+        counts: Some(counts),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderCiResult {
     pub provider: CiProvider,
+    /// Forge host the result was polled from. Filled in by the tab
+    /// from the remote it polled, so results from github.com and an
+    /// Enterprise host sit side by side instead of replacing each
+    /// other.
+    #[serde(default)]
+    pub host: String,
     /// Branch-level summary (header bar indicator).
     pub status: CiStatus,
     /// Per-commit provider rollups for compact commit-row rendering.
     pub per_commit_rollups: HashMap<String, CiCommitRollup>,
+    /// Restored from the on-disk cache at launch rather than fetched;
+    /// shown dimmed until the first poll replaces it.
+    #[serde(skip)]
+    pub stale: bool,
 }
 
 #[derive(Debug, Clone)]
 pub struct ProviderCommitRollup {
     pub provider: CiProvider,
     pub rollup: CiCommitRollup,
+    /// Copied from [`ProviderCiResult::stale`].
+    pub stale: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CiFetchResult {
     pub providers: Vec<ProviderCiResult>,
 }
@@ -131,6 +263,7 @@ impl CiFetchResult {
                     .push(ProviderCommitRollup {
                         provider: provider.provider,
                         rollup: rollup.clone(),
+                        stale: provider.stale,
                     });
             }
         }
@@ -145,12 +278,28 @@ impl CiFetchResult {
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_inputs_skips_comments_and_rejects_bare_words() {
+        let inputs = parse_inputs("# release\nversion = 1.2.0\n\nnotes=fix a, b\nflag=").unwrap();
+        assert_eq!(
+            inputs,
+            vec![
+                ("version".to_string(), "1.2.0".to_string()),
+                ("notes".to_string(), "fix a, b".to_string()),
+                ("flag".to_string(), String::new()),
+            ]
+        );
+        assert!(parse_inputs("version").is_err());
+        assert!(parse_inputs("=1").is_err());
+    }
+
     #[test]
     fn per_commit_provider_rollups_are_sorted_by_provider() {
         let result = CiFetchResult {
             providers: vec![
                 ProviderCiResult {
                     provider: CiProvider::GitLab,
+                    host: String::new(),
                     status: CiStatus {
                         state: CiState::Failure,
                         summary: "Pipeline failed".into(),
@@ -169,9 +318,11 @@ mod tests {
                         },
                     )]
                     .into(),
+                    stale: false,
                 },
                 ProviderCiResult {
                     provider: CiProvider::GitHub,
+                    host: String::new(),
                     status: CiStatus {
                         state: CiState::Success,
                         summary: "Workflow passed".into(),
@@ -190,6 +341,7 @@ mod tests {
                         },
                     )]
                     .into(),
+                    stale: false,
                 },
             ],
         };
@@ -0,0 +1,135 @@
+//! Last-known CI results per repository, kept on disk so the tab pip
+//! and commit rows show something at launch instead of staying blank
+//! until the first poll answers.
+//!
+//! Stored at `$XDG_CACHE_HOME/whisper-git/ci/{hash}.json`, keyed by the
+//! MD5 of the repository's common directory so linked worktrees share
+//! one file. Results load with [`ProviderCiResult::stale`] set; the
+//! first fetch for each provider replaces them.
+
+use crate::ci::{CiFetchResult, ProviderCiResult};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Cached results for the repository at `common_dir`, marked stale.
+/// Empty when nothing was cached or the file doesn't parse.
+pub fn load(common_dir: &Path) -> Vec<ProviderCiResult> {
+    read(&cache_file(common_dir))
+        .map(|cached| cached.providers)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|result| ProviderCiResult {
+            stale: true,
+            ..result
+        })
+        .collect()
+}
+
+/// Record `results` for the repository at `common_dir`. Failed fetches
+/// (no per-commit rollups) keep the provider's previous entry, so going
+/// offline doesn't wipe the last good status.
+pub fn save(common_dir: &Path, results: &[ProviderCiResult]) -> Result<()> {
+    write(&cache_file(common_dir), results)
+}
+
+fn read(path: &Path) -> Option<CiFetchResult> {
+    let text = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&text).ok()
+}
+
+fn write(path: &Path, results: &[ProviderCiResult]) -> Result<()> {
+    let fresh: Vec<&ProviderCiResult> = results
+        .iter()
+        .filter(|r| !r.stale && !r.per_commit_rollups.is_empty())
+        .collect();
+    if fresh.is_empty() {
+        return Ok(());
+    }
+    let mut providers = read(path).map(|c| c.providers).unwrap_or_default();
+    for result in fresh {
+        providers.retain(|p| (p.provider, &p.host) != (result.provider, &result.host));
+        providers.push(result.clone());
+    }
+    providers.sort_by_key(|p| p.provider.sort_key());
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let json = serde_json::to_string(&CiFetchResult { providers })
+        .context("Failed to serialize CI results")?;
+    std::fs::write(path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn cache_file(common_dir: &Path) -> PathBuf {
+    let hash = format!(
+        "{:x}",
+        md5::compute(common_dir.to_string_lossy().as_bytes())
+    );
+    cache_dir().join(format!("{hash}.json"))
+}
+
+fn cache_dir() -> PathBuf {
+    let base = std::env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
+            PathBuf::from(home).join(".cache")
+        });
+    base.join("whisper-git").join("ci")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ci::{CiCommitRollup, CiProvider, CiState, CiStatus};
+    use crate::git::test_support::temp_dir;
+
+    fn result(provider: CiProvider, state: CiState, shas: &[&str]) -> ProviderCiResult {
+        ProviderCiResult {
+            provider,
+            host: String::new(),
+            status: CiStatus {
+                state,
+                summary: format!("{state:?}"),
+                url: None,
+                counts: None,
+            },
+            per_commit_rollups: shas
+                .iter()
+                .map(|sha| (sha.to_string(), CiCommitRollup::default()))
+                .collect(),
+            stale: false,
+        }
+    }
+
+    #[test]
+    fn failed_fetches_keep_the_last_good_entry() {
+        let path = temp_dir("ci-cache").join("ci.json");
+
+        write(
+            &path,
+            &[
+                result(CiProvider::GitLab, CiState::Failure, &["bbb"]),
+                result(CiProvider::GitHub, CiState::Success, &["aaa"]),
+            ],
+        )
+        .unwrap();
+        write(&path, &[result(CiProvider::GitHub, CiState::None, &[])]).unwrap();
+
+        let cached = read(&path).unwrap().providers;
+        let states: Vec<(CiProvider, CiState, bool)> = cached
+            .iter()
+            .map(|r| (r.provider, r.status.state, r.stale))
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                (CiProvider::GitHub, CiState::Success, false),
+                (CiProvider::GitLab, CiState::Failure, false),
+            ]
+        );
+        assert!(cached[0].per_commit_rollups.contains_key("aaa"));
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,507 @@
+//! CI job log parsing for the log viewer.
+//!
+//! Turns a raw job log (GitHub Actions `jobs/{id}/logs` or a GitLab job
+//! trace) into plain text lines with ANSI color spans, the collapsible
+//! groups the runner marked, and the first line that looks like an
+//! error. Pure data — `ci_log_view` renders it.
+
+use std::collections::HashSet;
+
+/// Severity the runner (or our heuristics) gave a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Normal,
+    Warning,
+    Error,
+}
+
+/// A run of `text[start..end]` in ANSI color `color` (0–15, the
+/// standard and bright palette), or the default color when `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogSpan {
+    pub start: usize,
+    pub end: usize,
+    pub color: Option<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    /// Line text with escape sequences, timestamps and runner markers
+    /// removed.
+    pub text: String,
+    /// Color runs covering `text`; empty when the line is uncolored.
+    pub spans: Vec<LogSpan>,
+    pub level: LogLevel,
+    /// Innermost group the line belongs to.
+    pub group: Option<usize>,
+}
+
+/// A collapsible section: a GitHub `##[group]` step or a GitLab
+/// `section_start` / `section_end` pair.
+#[derive(Debug, Clone)]
+pub struct LogGroup {
+    pub title: String,
+    pub parent: Option<usize>,
+    /// Index of the first line after the group header; equal to the
+    /// next group's or the line count when the group is empty.
+    pub first_line: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ParsedLog {
+    pub lines: Vec<LogLine>,
+    pub groups: Vec<LogGroup>,
+    pub first_error: Option<usize>,
+}
+
+/// A row of the rendered log once collapsed groups are folded away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogRow {
+    Group(usize),
+    Line(usize),
+}
+
+/// Parse a raw job log.
+///
+/// GitHub brackets only a step's header (`Run cargo test` plus its
+/// env) with `##[group]` / `##[endgroup]`; the output that follows
+/// belongs to the step too, so a top-level group stays open until the
+/// next top-level `##[group]`. GitLab sections nest and close by name.
+pub fn parse_log(raw: &str) -> ParsedLog {
+    let mut log = ParsedLog::default();
+    // Open groups, innermost last, with the GitLab section name (if
+    // any) and whether a GitHub step's header has been closed.
+    let mut stack: Vec<(usize, Option<String>, bool)> = Vec::new();
+
+    for raw_line in raw.trim_start_matches('\u{feff}').lines() {
+        // Text after the last carriage return wins, as on a terminal
+        // (progress bars); segments that are only markers or escapes
+        // don't count.
+        let mut visible: Option<&str> = None;
+        let mut had_marker = false;
+        for segment in raw_line.split('\r') {
+            let plain = strip_escapes(segment);
+            let plain = plain.trim_start();
+            if let Some(rest) = plain.strip_prefix("section_start:") {
+                let name = rest
+                    .split_once(':')
+                    .map(|(_, name)| name)
+                    .unwrap_or(rest)
+                    .split('[')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                open_group(&mut log, &mut stack, name.clone(), Some(name));
+                had_marker = true;
+                continue;
+            }
+            if let Some(rest) = plain.strip_prefix("section_end:") {
+                let name = rest.split_once(':').map(|(_, name)| name).unwrap_or(rest);
+                if let Some(pos) = stack
+                    .iter()
+                    .rposition(|(_, section, _)| section.as_deref() == Some(name))
+                {
+                    stack.truncate(pos);
+                }
+                had_marker = true;
+                continue;
+            }
+            if !plain.is_empty() {
+                visible = Some(segment);
+            }
+        }
+        let segment = match visible {
+            Some(segment) => segment,
+            None if had_marker => continue,
+            None => "",
+        };
+        // A GitLab section header sits on the same line as its marker;
+        // it becomes the group title rather than a line of its own.
+        let opened_here = had_marker
+            && raw_line.contains("section_start:")
+            && stack.last().is_some_and(|(g, section, _)| {
+                section.is_some() && log.groups[*g].first_line == log.lines.len()
+            });
+
+        let line = strip_timestamp(segment);
+        let (text, spans) = parse_ansi(line);
+        if opened_here {
+            let (g, _, _) = stack.last().expect("checked above");
+            log.groups[*g].title = text.trim().to_string();
+            continue;
+        }
+
+        let (text, level, spans) = match runner_marker(&text) {
+            Some(("group", title)) => {
+                let title = title.to_string();
+                if stack.len() == 1 && matches!(stack.last(), Some((_, None, true))) {
+                    stack.clear();
+                }
+                open_group(&mut log, &mut stack, title, None);
+                continue;
+            }
+            Some(("endgroup", _)) => {
+                let depth = stack.len();
+                match stack.last_mut() {
+                    // The step's header is done; its output follows.
+                    Some((_, None, header_done)) if depth == 1 => *header_done = true,
+                    Some((_, None, _)) => {
+                        stack.pop();
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+            Some((kind, msg)) => {
+                let level = match kind {
+                    "error" => LogLevel::Error,
+                    "warning" => LogLevel::Warning,
+                    _ => LogLevel::Normal,
+                };
+                let offset = text.len() - msg.len();
+                let spans = spans
+                    .into_iter()
+                    .filter(|s| s.end > offset)
+                    .map(|s| LogSpan {
+                        start: s.start.saturating_sub(offset),
+                        end: s.end - offset,
+                        color: s.color,
+                    })
+                    .collect();
+                (msg.to_string(), level, spans)
+            }
+            None => {
+                let level = if looks_like_error(&text) {
+                    LogLevel::Error
+                } else {
+                    LogLevel::Normal
+                };
+                (text, level, spans)
+            }
+        };
+        if level == LogLevel::Error && log.first_error.is_none() {
+            log.first_error = Some(log.lines.len());
+        }
+        log.lines.push(LogLine {
+            text,
+            spans,
+            level,
+            group: stack.last().map(|(g, _, _)| *g),
+        });
+    }
+    log
+}
+
+fn open_group(
+    log: &mut ParsedLog,
+    stack: &mut Vec<(usize, Option<String>, bool)>,
+    title: String,
+    section: Option<String>,
+) {
+    let idx = log.groups.len();
+    log.groups.push(LogGroup {
+        title,
+        parent: stack.last().map(|(g, _, _)| *g),
+        first_line: log.lines.len(),
+    });
+    stack.push((idx, section, false));
+}
+
+/// `##[kind]rest` → `(kind, rest)` for GitHub's workflow command
+/// markers.
+fn runner_marker(text: &str) -> Option<(&str, &str)> {
+    let rest = text.strip_prefix("##[")?;
+    let (kind, msg) = rest.split_once(']')?;
+    Some((kind, msg))
+}
+
+/// Drop the RFC 3339 timestamp GitHub (and GitLab, when enabled)
+/// prefixes every line with, and GitLab's `00O` stream tag after it.
+fn strip_timestamp(line: &str) -> &str {
+    let Some((first, rest)) = line.split_once(' ') else {
+        return line;
+    };
+    let b = first.as_bytes();
+    if b.len() < 20 || b[4] != b'-' || b[10] != b'T' || !first.ends_with('Z') {
+        return line;
+    }
+    match rest.split_once(' ') {
+        Some((tag, after))
+            if tag.len() >= 3
+                && tag.as_bytes()[..2].iter().all(u8::is_ascii_digit)
+                && matches!(&tag[2..], "O" | "E" | "O+" | "E+") =>
+        {
+            after
+        }
+        _ => rest,
+    }
+}
+
+/// Whether an unmarked line reads like a failure: compiler and tool
+/// errors, Rust panics, GitLab's closing `ERROR: Job failed`.
+fn looks_like_error(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    let lower = trimmed.get(..6).unwrap_or(trimmed).to_ascii_lowercase();
+    lower.starts_with("error") || lower.starts_with("fatal:") || trimmed.contains("panicked at")
+}
+
+/// Remove every escape sequence, keeping only printable text.
+fn strip_escapes(s: &str) -> String {
+    parse_ansi(s).0
+}
+
+/// Split ANSI SGR color codes out of `s`. Other CSI sequences (cursor
+/// movement, erase-line) are dropped. Colors don't carry across lines.
+fn parse_ansi(s: &str) -> (String, Vec<LogSpan>) {
+    let mut text = String::with_capacity(s.len());
+    let mut spans: Vec<LogSpan> = Vec::new();
+    let mut color: Option<u8> = None;
+    let mut span_start = 0;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            text.push(c);
+            continue;
+        }
+        if chars.peek() != Some(&'[') {
+            continue;
+        }
+        chars.next();
+        let mut params = String::new();
+        let mut command = None;
+        for c in chars.by_ref() {
+            if ('\u{40}'..='\u{7e}').contains(&c) {
+                command = Some(c);
+                break;
+            }
+            params.push(c);
+        }
+        if command != Some('m') {
+            continue;
+        }
+        let next = sgr_color(&params, color);
+        if next != color {
+            if text.len() > span_start {
+                spans.push(LogSpan {
+                    start: span_start,
+                    end: text.len(),
+                    color,
+                });
+            }
+            span_start = text.len();
+            color = next;
+        }
+    }
+    if text.len() > span_start && (color.is_some() || !spans.is_empty()) {
+        spans.push(LogSpan {
+            start: span_start,
+            end: text.len(),
+            color,
+        });
+    }
+    if spans.iter().all(|s| s.color.is_none()) {
+        spans.clear();
+    }
+    (text, spans)
+}
+
+/// Apply an SGR parameter list to the current foreground color.
+fn sgr_color(params: &str, mut color: Option<u8>) -> Option<u8> {
+    let codes: Vec<u16> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 | 39 => color = None,
+            c @ 30..=37 => color = Some((c - 30) as u8),
+            c @ 90..=97 => color = Some((c - 90 + 8) as u8),
+            38 => {
+                // 256-color (`38;5;n`) keeps the palette's first 16;
+                // truecolor (`38;2;r;g;b`) falls back to the default.
+                match codes.get(i + 1) {
+                    Some(5) => {
+                        color = codes.get(i + 2).and_then(|&n| (n < 16).then_some(n as u8));
+                        i += 2;
+                    }
+                    Some(2) => {
+                        color = None;
+                        i += 4;
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    color
+}
+
+impl ParsedLog {
+    /// Rows to render with the groups in `collapsed` folded to their
+    /// header.
+    pub fn visible_rows(&self, collapsed: &HashSet<usize>) -> Vec<LogRow> {
+        let hidden = |group: Option<usize>| {
+            let mut cur = group;
+            while let Some(g) = cur {
+                if collapsed.contains(&g) {
+                    return true;
+                }
+                cur = self.groups[g].parent;
+            }
+            false
+        };
+        let mut rows = Vec::new();
+        let mut next_group = 0;
+        for (i, line) in self.lines.iter().enumerate() {
+            while next_group < self.groups.len() && self.groups[next_group].first_line <= i {
+                if !hidden(self.groups[next_group].parent) {
+                    rows.push(LogRow::Group(next_group));
+                }
+                next_group += 1;
+            }
+            if !hidden(line.group) {
+                rows.push(LogRow::Line(i));
+            }
+        }
+        for g in next_group..self.groups.len() {
+            if !hidden(self.groups[g].parent) {
+                rows.push(LogRow::Group(g));
+            }
+        }
+        rows
+    }
+
+    /// Groups enclosing line `idx`, innermost first — the ones to
+    /// expand to reveal it.
+    pub fn enclosing_groups(&self, idx: usize) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut cur = self.lines.get(idx).and_then(|l| l.group);
+        while let Some(g) = cur {
+            out.push(g);
+            cur = self.groups[g].parent;
+        }
+        out
+    }
+
+    /// Lines containing `query`, case-insensitively.
+    pub fn find(&self, query: &str) -> Vec<usize> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| l.text.to_lowercase().contains(&query))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn github_steps_keep_their_output_and_flag_errors() {
+        let raw = "\u{feff}2024-05-01T10:00:00.0000000Z ##[group]Run actions/checkout@v4\n\
+                   2024-05-01T10:00:00.1000000Z with: repo\n\
+                   2024-05-01T10:00:00.2000000Z ##[endgroup]\n\
+                   2024-05-01T10:00:01.0000000Z Checked out\n\
+                   2024-05-01T10:00:02.0000000Z ##[group]Run cargo test\n\
+                   2024-05-01T10:00:02.1000000Z ##[endgroup]\n\
+                   2024-05-01T10:00:03.0000000Z \u{1b}[1m\u{1b}[91merror[E0425]\u{1b}[0m: cannot find value\n\
+                   2024-05-01T10:00:04.0000000Z ##[error]Process completed with exit code 101.\n";
+        let log = parse_log(raw);
+
+        let titles: Vec<&str> = log.groups.iter().map(|g| g.title.as_str()).collect();
+        assert_eq!(titles, ["Run actions/checkout@v4", "Run cargo test"]);
+        assert!(log.groups.iter().all(|g| g.parent.is_none()));
+        let texts: Vec<&str> = log.lines.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "with: repo",
+                "Checked out",
+                "error[E0425]: cannot find value",
+                "Process completed with exit code 101.",
+            ]
+        );
+        assert_eq!(log.lines[1].group, Some(0));
+        assert_eq!(log.lines[2].group, Some(1));
+        assert_eq!(log.first_error, Some(2));
+        assert_eq!(log.lines[3].level, LogLevel::Error);
+        assert_eq!(
+            log.lines[2].spans,
+            [
+                LogSpan {
+                    start: 0,
+                    end: 12,
+                    color: Some(9)
+                },
+                LogSpan {
+                    start: 12,
+                    end: 31,
+                    color: None
+                },
+            ]
+        );
+
+        let collapsed: HashSet<usize> = [0].into();
+        assert_eq!(
+            log.visible_rows(&collapsed),
+            [
+                LogRow::Group(0),
+                LogRow::Group(1),
+                LogRow::Line(2),
+                LogRow::Line(3)
+            ]
+        );
+        assert_eq!(log.find("EXIT CODE"), [3]);
+    }
+
+    #[test]
+    fn gitlab_sections_nest_and_take_their_header_as_title() {
+        let raw = "Running with gitlab-runner 16.0\n\
+                   \u{1b}[0Ksection_start:1700000000:prepare_script\r\u{1b}[0K\u{1b}[36;1mPreparing environment\u{1b}[0;m\n\
+                   Using docker\n\
+                   \u{1b}[0Ksection_end:1700000001:prepare_script\r\u{1b}[0K\n\
+                   \u{1b}[0Ksection_start:1700000002:step_script[collapsed=true]\r\u{1b}[0K\u{1b}[0K\u{1b}[36;1mExecuting \"step_script\"\u{1b}[0;m\n\
+                   \u{1b}[0Ksection_start:1700000003:inner\r\u{1b}[0K\n\
+                   $ make\n\
+                   \u{1b}[0Ksection_end:1700000004:inner\r\u{1b}[0K\n\
+                   progress 10%\rprogress 100%\n\
+                   \u{1b}[0Ksection_end:1700000005:step_script\r\u{1b}[0K\n\
+                   \u{1b}[31;1mERROR: Job failed: exit code 2\u{1b}[0;m\n";
+        let log = parse_log(raw);
+
+        let titles: Vec<&str> = log.groups.iter().map(|g| g.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            [
+                "Preparing environment",
+                "Executing \"step_script\"",
+                "inner"
+            ]
+        );
+        assert_eq!(log.groups[2].parent, Some(1));
+        let lines: Vec<(&str, Option<usize>)> = log
+            .lines
+            .iter()
+            .map(|l| (l.text.as_str(), l.group))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                ("Running with gitlab-runner 16.0", None),
+                ("Using docker", Some(0)),
+                ("$ make", Some(2)),
+                ("progress 100%", Some(1)),
+                ("ERROR: Job failed: exit code 2", None),
+            ]
+        );
+        assert_eq!(log.first_error, Some(4));
+        assert_eq!(log.lines[4].spans[0].color, Some(1));
+        assert_eq!(log.enclosing_groups(2), [2, 1]);
+    }
+}
@@ -0,0 +1,322 @@
+//! CI log viewer: the runs behind one commit's CI pips, the jobs of
+//! the selected run and the selected job's log. Takes over the center
+//! pane like the insights view — Close or Escape returns to the graph.
+//! Reads [`RepoTab::ci_log`]; job lists and logs are fetched off-thread
+//! through the GitHub / GitLab clients and parsed by [`crate::ci_log`].
+
+use std::sync::Arc;
+
+use aetna_core::{Color, El, IconName, Selection, prelude::*};
+
+use crate::ci::CiState;
+use crate::ci_log::{LogLevel, LogRow, ParsedLog};
+use crate::repo_tab::{CiLogState, RepoTab};
+use crate::widgets::brand_icons;
+
+/// Routed key for the search input. Events fold into
+/// `CiLogState::search`.
+pub const CI_LOG_SEARCH_KEY: &str = "ci_log:search";
+/// Key of the log's virtual list, for scroll requests.
+pub const CI_LOG_LIST_KEY: &str = "ci_log:lines";
+
+const LINENO_WIDTH: f32 = 56.0;
+const EST_ROW_HEIGHT: f32 = 20.0;
+const SCROLLBAR_GUTTER: f32 = tokens::SCROLLBAR_THUMB_WIDTH_ACTIVE + tokens::SCROLLBAR_TRACK_INSET;
+
+pub fn ci_log_view(tab: &RepoTab, selection: &Selection) -> El {
+    let Some(state) = tab.ci_log.as_ref() else {
+        return column(Vec::<El>::new());
+    };
+
+    let body = match &state.log {
+        Some(Ok(log)) => log_body(log.clone(), state),
+        Some(Err(e)) => placeholder(error_message("Couldn't load the log", e)),
+        None if state.log_rx.is_some() => placeholder(progress_row("Downloading log\u{2026}")),
+        None => match &state.jobs {
+            Some(Err(e)) => placeholder(error_message("Couldn't list jobs", e)),
+            Some(Ok(jobs)) if jobs.is_empty() => placeholder(text("This run has no jobs.").muted()),
+            Some(Ok(_)) => placeholder(text("Pick a job to show its log.").muted()),
+            None => placeholder(progress_row("Listing jobs\u{2026}")),
+        },
+    };
+
+    column([header(state, selection), separator(), body])
+        .width(Size::Fill(1.0))
+        .height(Size::Fill(1.0))
+}
+
+fn header(state: &CiLogState, selection: &Selection) -> El {
+    let short = state.sha.get(..7).unwrap_or(&state.sha);
+    let runs: Vec<El> = state
+        .checks
+        .iter()
+        .enumerate()
+        .map(|(i, check)| {
+            let b = button(format!("{} {}", state_mark(check.state), check.label))
+                .key(format!("ci_log:run:{i}"));
+            if i == state.run {
+                b.primary()
+            } else {
+                b.ghost()
+            }
+        })
+        .collect();
+
+    let mut rows = vec![
+        row([
+            icon(brand_icons::for_provider(state.provider)),
+            text(format!("CI logs \u{00b7} {short}")).label(),
+            spacer(),
+            button("Open in Browser")
+                .key("ci_log:open")
+                .ghost()
+                .tooltip("Open the selected job (or run) on the forge"),
+            button("Close").key("ci_log:close").ghost(),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+        row(runs).gap(tokens::SPACE_1).align(Align::Center),
+    ];
+    if let Some(Ok(jobs)) = &state.jobs {
+        let jobs: Vec<El> = jobs
+            .iter()
+            .map(|job| {
+                let mark = state_mark(job.state);
+                let label = match &job.stage {
+                    Some(stage) => format!("{mark} {stage} \u{00b7} {}", job.name),
+                    None => format!("{mark} {}", job.name),
+                };
+                let b = button(label).key(format!("ci_log:job:{}", job.id));
+                if state.job == Some(job.id) {
+                    b.primary()
+                } else {
+                    b.ghost()
+                }
+            })
+            .collect();
+        rows.push(row(jobs).gap(tokens::SPACE_1).align(Align::Center));
+    }
+    if let Some(Ok(log)) = &state.log {
+        rows.push(search_row(log, state, selection));
+    }
+
+    column(rows)
+        .gap(tokens::SPACE_2)
+        .padding(tokens::SPACE_3)
+        .width(Size::Fill(1.0))
+}
+
+fn search_row(log: &ParsedLog, state: &CiLogState, selection: &Selection) -> El {
+    let matches = log.find(&state.search);
+    let status = if state.search.trim().is_empty() {
+        format!("{} lines", log.lines.len())
+    } else {
+        let pos = state
+            .search_line
+            .and_then(|line| matches.iter().position(|&m| m == line))
+            .map(|p| format!("{}/", p + 1))
+            .unwrap_or_default();
+        format!("{pos}{} matches", matches.len())
+    };
+    let mut children = vec![
+        icon(IconName::Search).muted(),
+        text_input(&state.search, selection, CI_LOG_SEARCH_KEY).width(Size::Fill(1.0)),
+        text(status).caption().muted(),
+    ];
+    for (label, key) in [("Previous", "ci_log:prev"), ("Next", "ci_log:next")] {
+        let b = button(label).key(key).ghost();
+        children.push(if matches.is_empty() { b.disabled() } else { b });
+    }
+    let error = button("First Error").key("ci_log:error").ghost();
+    children.push(if log.first_error.is_some() {
+        error
+    } else {
+        error.disabled()
+    });
+    if !log.groups.is_empty() {
+        children.push(button("Expand All").key("ci_log:expand_all").ghost());
+        children.push(button("Collapse All").key("ci_log:collapse_all").ghost());
+    }
+    row(children).gap(tokens::SPACE_2).align(Align::Center)
+}
+
+fn log_body(log: Arc<ParsedLog>, state: &CiLogState) -> El {
+    if log.lines.is_empty() && log.groups.is_empty() {
+        return placeholder(text("The log is empty.").muted());
+    }
+    let rows = log.visible_rows(&state.collapsed);
+    let collapsed = state.collapsed.clone();
+    let matches = log.find(&state.search);
+    let current = state.search_line;
+    let row_keys: Vec<String> = rows
+        .iter()
+        .map(|row| match row {
+            LogRow::Group(g) => format!("ci_log:group:{g}"),
+            LogRow::Line(i) => format!("ci_log:line:{i}"),
+        })
+        .collect();
+    virtual_list_dyn(
+        rows.len(),
+        EST_ROW_HEIGHT,
+        move |i| {
+            row_keys
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("ci_log:missing:{i}"))
+        },
+        move |i| {
+            let el = match rows[i] {
+                LogRow::Group(g) => group_row(&log, g, collapsed.contains(&g)),
+                LogRow::Line(idx) => line_row(
+                    &log,
+                    idx,
+                    matches.binary_search(&idx).is_ok(),
+                    current == Some(idx),
+                ),
+            };
+            column([el]).width(Size::Fill(1.0)).padding(Sides {
+                left: 0.0,
+                right: SCROLLBAR_GUTTER,
+                top: 0.0,
+                bottom: 0.0,
+            })
+        },
+    )
+    .key(CI_LOG_LIST_KEY)
+    .height(Size::Fill(1.0))
+}
+
+/// Header of a collapsible group, indented by nesting depth. The list
+/// row is keyed `ci_log:group:{g}`, so clicking it toggles the group.
+fn group_row(log: &ParsedLog, g: usize, collapsed: bool) -> El {
+    let group = &log.groups[g];
+    let mut depth = 0;
+    let mut parent = group.parent;
+    while let Some(p) = parent {
+        depth += 1;
+        parent = log.groups[p].parent;
+    }
+    let caret = if collapsed {
+        IconName::ChevronRight
+    } else {
+        IconName::ChevronDown
+    };
+    row([
+        icon(caret).muted(),
+        text(group.title.clone()).mono().ellipsis(),
+    ])
+    .gap(tokens::SPACE_1)
+    .align(Align::Center)
+    .padding(Sides {
+        left: LINENO_WIDTH + depth as f32 * tokens::SPACE_3,
+        right: tokens::SPACE_2,
+        top: 0.0,
+        bottom: 0.0,
+    })
+    .width(Size::Fill(1.0))
+    .fill(tokens::MUTED)
+}
+
+/// One log line: number gutter then the text in its ANSI colors.
+/// Error and warning lines are tinted; search hits get a stronger tint
+/// and the hit last jumped to the strongest.
+fn line_row(log: &ParsedLog, idx: usize, matched: bool, current: bool) -> El {
+    let line = &log.lines[idx];
+    let number = text((idx + 1).to_string())
+        .mono()
+        .caption()
+        .muted()
+        .width(Size::Fixed(LINENO_WIDTH))
+        .padding(Sides::xy(tokens::SPACE_2, 0.0));
+    let content = if line.spans.is_empty() {
+        text(line.text.clone()).mono()
+    } else {
+        text_runs(
+            line.spans
+                .iter()
+                .map(|span| {
+                    let t = text(line.text[span.start..span.end].to_string()).mono();
+                    match span.color {
+                        Some(c) => t.text_color(ansi_color(c)),
+                        None => t,
+                    }
+                })
+                .collect::<Vec<El>>(),
+        )
+    };
+    let el = row([number, content.width(Size::Fill(1.0))])
+        .align(Align::Center)
+        .width(Size::Fill(1.0));
+    let bg = if current {
+        Some(tokens::PRIMARY.with_alpha(64))
+    } else if matched {
+        Some(tokens::WARNING.with_alpha(48))
+    } else {
+        match line.level {
+            LogLevel::Error => Some(tokens::DESTRUCTIVE.with_alpha(28)),
+            LogLevel::Warning => Some(tokens::WARNING.with_alpha(20)),
+            LogLevel::Normal => None,
+        }
+    };
+    match bg {
+        Some(bg) => el.fill(bg),
+        None => el,
+    }
+}
+
+/// Theme color for an ANSI palette index. Bright variants share their
+/// base color; the theme's foreground already reads as "bright".
+fn ansi_color(idx: u8) -> Color {
+    match idx % 8 {
+        0 => tokens::MUTED_FOREGROUND,
+        1 => tokens::DESTRUCTIVE,
+        2 => tokens::SUCCESS,
+        3 => tokens::WARNING,
+        4 => Color::rgb(96, 165, 250),
+        5 => Color::rgb(192, 132, 252),
+        6 => tokens::INFO,
+        _ => tokens::FOREGROUND,
+    }
+}
+
+/// Same marks as the commit row's CI tooltip.
+fn state_mark(state: CiState) -> &'static str {
+    match state {
+        CiState::Success => "\u{2713}",
+        CiState::Failure => "\u{2717}",
+        CiState::Pending => "\u{22ef}",
+        CiState::None => "\u{2014}",
+    }
+}
+
+fn error_message(title: &str, e: &str) -> El {
+    column([
+        row([
+            icon(IconName::AlertCircle).text_color(tokens::DESTRUCTIVE),
+            text(title.to_string()).label(),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+        paragraph(e.to_string()).muted(),
+    ])
+    .gap(tokens::SPACE_2)
+}
+
+fn progress_row(label: &str) -> El {
+    use aetna_core::widgets::spinner::spinner_with_color;
+    row([
+        spinner_with_color(tokens::PRIMARY),
+        text(label.to_string()).caption().muted(),
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center)
+}
+
+fn placeholder(content: El) -> El {
+    column([content])
+        .align(Align::Center)
+        .justify(Justify::Center)
+        .padding(tokens::SPACE_4)
+        .width(Size::Fill(1.0))
+        .height(Size::Fill(1.0))
+}
@@ -1,6 +1,8 @@
 //! Right-pane upper section when a commit is selected: full SHA +
-//! parents, author + timestamp, full commit message, and the list of
-//! changed files with per-file insertion / deletion stats.
+//! parents, author + timestamp, full commit message with chips for its
+//! issue references, the tags at the commit with their forge releases,
+//! any `git notes`, and the list of changed files with per-file
+//! insertion / deletion stats.
 //!
 //! Mirrors the staging well's "stack of cards in a plain scroll"
 //! shape so the right pane has one consistent rhythm regardless of
@@ -10,13 +12,31 @@
 
 use aetna_core::{El, IconName, prelude::*};
 
+use crate::commit_graph::issue_chip;
+use crate::git::format_bytes;
+use crate::issues::{Issue, IssueLink};
+use crate::releases::Release;
 use crate::repo_tab::RepoTab;
+use std::collections::HashMap;
 
 const RIGHT_PANE_EDGE_INSET: f32 = tokens::SPACE_1;
 
-pub fn commit_details_pane(tab: &RepoTab) -> El {
+/// `show_raw_author` adds the identity recorded in the commit, before
+/// `.mailmap` resolution, as a tooltip on the author line.
+pub fn commit_details_pane(tab: &RepoTab, show_raw_author: bool) -> El {
     let pane = match (tab.selected_commit, &tab.commit_detail) {
-        (Some(_), Some(detail)) => details_pane(detail),
+        (Some(oid), Some(detail)) => details_pane(
+            detail,
+            &tab.issue_linker.links(&detail.info.full_message),
+            &tab.issues,
+            &tab.tags
+                .iter()
+                .filter(|t| t.oid == oid)
+                .map(|t| (t.name.as_str(), t.release.as_ref()))
+                .collect::<Vec<_>>(),
+            tab.releases_supported(),
+            show_raw_author,
+        ),
         (Some(_), None) => placeholder_pane("Loading…"),
         (None, _) => placeholder_pane("Select a commit to inspect."),
     };
@@ -32,7 +52,14 @@ fn placeholder_pane(msg: &str) -> El {
         .width(Size::Fill(1.0))
 }
 
-fn details_pane(detail: &crate::repo_tab::CommitDetail) -> El {
+fn details_pane(
+    detail: &crate::repo_tab::CommitDetail,
+    issue_links: &[IssueLink],
+    issues: &HashMap<u64, Issue>,
+    tags: &[(&str, Option<&Release>)],
+    can_release: bool,
+    show_raw_author: bool,
+) -> El {
     let info = &detail.info;
     let parents_label = if info.parent_short_ids.is_empty() {
         "(root commit)".to_string()
@@ -40,11 +67,36 @@ fn details_pane(detail: &crate::repo_tab::CommitDetail) -> El {
         format!("Parents: {}", info.parent_short_ids.join(", "))
     };
     let (subject, body) = split_message(&info.full_message);
-    let body_children: Vec<El> = if body.trim().is_empty() {
+    let mut body_children: Vec<El> = if body.trim().is_empty() {
         Vec::new()
     } else {
         vec![paragraph(body).label()]
     };
+    // References anywhere in the message; titles and states arrive as
+    // the tab's lookups for this commit land.
+    if !issue_links.is_empty() {
+        let chips: Vec<El> = issue_links
+            .iter()
+            .map(|link| {
+                let issue = link.number.and_then(|n| issues.get(&n));
+                issue_chip(link, issue, format!("details:issue:{}", link.text))
+            })
+            .collect();
+        body_children.push(row(chips).gap(tokens::SPACE_1).align(Align::Center));
+    }
+
+    let mut author_line = text(format!(
+        "{} <{}> · {}",
+        info.author_name,
+        info.author_email,
+        info.relative_author_time(),
+    ))
+    .muted();
+    if show_raw_author && let Some(raw) = &info.raw_author {
+        author_line = author_line
+            .key("details:author")
+            .tooltip(format!("Committed as {raw}"));
+    }
 
     let identity_card = card([card_header([
         row([
@@ -62,13 +114,7 @@ fn details_pane(detail: &crate::repo_tab::CommitDetail) -> El {
         .gap(tokens::SPACE_2)
         .align(Align::Center),
         text(parents_label).muted().caption(),
-        text(format!(
-            "{} <{}> · {}",
-            info.author_name,
-            info.author_email,
-            info.relative_author_time(),
-        ))
-        .muted(),
+        author_line,
     ])
     .padding(tokens::SPACE_3)
     .gap(tokens::SPACE_1)]);
@@ -81,7 +127,13 @@ fn details_pane(detail: &crate::repo_tab::CommitDetail) -> El {
         card_header([card_title(subject).width(Size::Fill(1.0)).ellipsis()]),
         card_content(body_children),
     ]);
-    let mut cards: Vec<El> = vec![identity_card, subject_card, files_card(detail)];
+    let mut cards: Vec<El> = vec![identity_card, subject_card];
+    cards.extend(
+        tags.iter()
+            .map(|(tag, release)| tag_card(tag, *release, can_release)),
+    );
+    cards.push(notes_card(&info.notes));
+    cards.push(files_card(detail));
     if !detail.submodule_entries.is_empty() {
         cards.push(submodules_card(detail));
     }
@@ -93,6 +145,133 @@ fn details_pane(detail: &crate::repo_tab::CommitDetail) -> El {
     .height(Size::Fill(1.0))
 }
 
+/// One block per `git notes` ref holding a note for this commit, with
+/// the ref name as a caption so tool-written notes (`refs/notes/ci`)
+/// read differently from hand-written ones. Shown on every commit so
+/// the first note can be added from here too.
+fn notes_card(notes: &[crate::git::CommitNote]) -> El {
+    let title = if notes.is_empty() {
+        "Notes".to_string()
+    } else {
+        format!("Notes ({})", notes.len())
+    };
+    let summary = row([
+        icon(IconName::FileText),
+        text(title).label(),
+        spacer(),
+        button("Add")
+            .key("details:add_note")
+            .ghost()
+            .tooltip("Add a note under the default notes ref"),
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center);
+
+    if notes.is_empty() {
+        return card([card_header([summary]).padding(tokens::SPACE_3)]);
+    }
+    let body: Vec<El> = notes
+        .iter()
+        .map(|note| {
+            column([
+                row([
+                    text(note.ref_name.clone()).mono().muted().caption(),
+                    spacer(),
+                    button("Edit")
+                        .key(format!("details:edit_note:{}", note.ref_name))
+                        .ghost(),
+                    button("Remove")
+                        .key(format!("details:remove_note:{}", note.ref_name))
+                        .ghost(),
+                ])
+                .gap(tokens::SPACE_2)
+                .align(Align::Center),
+                paragraph(note.message.clone()).label(),
+            ])
+            .gap(tokens::SPACE_1)
+        })
+        .collect();
+
+    card([
+        card_header([summary]).padding(tokens::SPACE_3),
+        card_content(body)
+            .padding(tokens::SPACE_3)
+            .pt(0.0)
+            .gap(tokens::SPACE_2),
+    ])
+}
+
+/// One card per tag at this commit. A tag with a release on `origin`
+/// shows its title, badge, date, notes and assets; one without offers
+/// to create it when the forge supports releases.
+fn tag_card(tag: &str, release: Option<&Release>, can_release: bool) -> El {
+    let mut summary = vec![
+        // Same diamond as the graph's tag pills; there's no tag icon.
+        text("\u{25C6}").text_color(tokens::WARNING),
+        text(tag.to_string()).label().mono(),
+        spacer(),
+    ];
+    let Some(release) = release else {
+        if can_release {
+            summary.push(
+                button("Create Release")
+                    .key(format!("details:release_create:{tag}"))
+                    .ghost(),
+            );
+        }
+        return card([
+            card_header([row(summary).gap(tokens::SPACE_2).align(Align::Center)])
+                .padding(tokens::SPACE_3),
+        ]);
+    };
+    summary.push(
+        button("Open")
+            .key(format!("details:release_open:{tag}"))
+            .ghost()
+            .tooltip(release.url.clone()),
+    );
+
+    let mut heading = vec![text(release.title().to_string()).label().ellipsis()];
+    if let Some(badge) = release.badge() {
+        heading.push(text(badge).caption().text_color(tokens::WARNING));
+    }
+    heading.push(spacer());
+    if let Some(date) = release.published_date() {
+        heading.push(text(date.to_string()).caption().muted());
+    }
+    let mut body = vec![row(heading).gap(tokens::SPACE_2).align(Align::Center)];
+    if !release.notes.trim().is_empty() {
+        body.push(paragraph(release.notes.trim().to_string()).label());
+    }
+    for (idx, asset) in release.assets.iter().enumerate() {
+        let mut asset_row = vec![
+            icon(IconName::Download),
+            text(asset.name.clone()).mono().ellipsis(),
+            spacer(),
+        ];
+        if let Some(size) = asset.size {
+            asset_row.push(text(format_bytes(size)).caption().muted());
+        }
+        body.push(
+            row(asset_row)
+                .gap(tokens::SPACE_2)
+                .align(Align::Center)
+                .key(format!("details:release_asset:{tag}:{idx}"))
+                .focusable()
+                .tooltip(asset.url.clone()),
+        );
+    }
+
+    card([
+        card_header([row(summary).gap(tokens::SPACE_2).align(Align::Center)])
+            .padding(tokens::SPACE_3),
+        card_content(body)
+            .padding(tokens::SPACE_3)
+            .pt(0.0)
+            .gap(tokens::SPACE_2),
+    ])
+}
+
 fn files_card(detail: &crate::repo_tab::CommitDetail) -> El {
     let summary = row([
         text(format!("{} files", detail.files.len())).label(),
@@ -111,7 +290,18 @@ fn files_card(detail: &crate::repo_tab::CommitDetail) -> El {
     let body: Vec<El> = if detail.files.is_empty() {
         vec![text("No file changes.").muted()]
     } else {
-        detail.files.iter().map(file_row).collect()
+        detail
+            .files
+            .iter()
+            .map(|f| {
+                let owners = detail
+                    .file_owners
+                    .get(&f.path)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                file_row(f, owners)
+            })
+            .collect()
     };
 
     card([
@@ -123,7 +313,7 @@ fn files_card(detail: &crate::repo_tab::CommitDetail) -> El {
     ])
 }
 
-fn file_row(f: &crate::git::DiffFile) -> El {
+fn file_row(f: &crate::git::DiffFile, owners: &[String]) -> El {
     let stats = if f.additions == 0 && f.deletions == 0 {
         text("renamed").mono().muted()
     } else {
@@ -137,11 +327,20 @@ fn file_row(f: &crate::git::DiffFile) -> El {
         ])
         .gap(tokens::SPACE_2)
     };
-    row([text(f.path.clone()).mono().nowrap_text(), spacer(), stats])
+    let mut children = vec![text(f.path.clone()).mono().nowrap_text(), spacer()];
+    if let Some(label) = crate::staging::owners_label(owners) {
+        children.push(text(label).caption().muted());
+    }
+    children.push(stats);
+    let mut row_el = row(children)
         .gap(tokens::SPACE_2)
         .align(Align::Center)
         .key(format!("commit_file:{}", f.path))
-        .focusable()
+        .focusable();
+    if !owners.is_empty() {
+        row_el = row_el.tooltip(format!("Owners: {}", owners.join(", ")));
+    }
+    row_el
 }
 
 /// "Submodules (N changed)" card listing the pinned SHA at this
@@ -23,7 +23,8 @@ use aetna_core::{Color, El, Selection, prelude::*};
 use git2::Oid;
 
 use crate::ci::{CiState, ProviderCommitRollup};
-use crate::git::CommitInfo;
+use crate::git::{CommitInfo, short_notes_ref};
+use crate::issues::{Issue, IssueLink, IssueState};
 use crate::repo_tab::RepoTab;
 use crate::widgets::brand_icons;
 
@@ -131,8 +132,8 @@ fn author_color(author: &str) -> Color {
 /// and decoding for this email; `None` covers in-flight, failed (404),
 /// and not-yet-requested. `key` is required so the avatar
 /// participates in pointer hit-testing — aetna only fires tooltips on
-/// keyed elements.
-fn author_avatar(author: &str, gravatar: Option<Image>, key: String) -> El {
+/// keyed elements. `tooltip` is the author line shown on hover.
+fn author_avatar(author: &str, tooltip: String, gravatar: Option<Image>, key: String) -> El {
     if let Some(img) = gravatar {
         return image(img)
             .width(Size::Fixed(AVATAR_SIZE))
@@ -140,7 +141,7 @@ fn author_avatar(author: &str, gravatar: Option<Image>, key: String) -> El {
             .radius(AVATAR_SIZE * 0.5)
             .clip()
             .key(key)
-            .tooltip(author.to_string());
+            .tooltip(tooltip);
     }
     let initial = author
         .chars()
@@ -156,7 +157,17 @@ fn author_avatar(author: &str, gravatar: Option<Image>, key: String) -> El {
         .align(Align::Center)
         .justify(Justify::Center)
         .key(key)
-        .tooltip(author.to_string())
+        .tooltip(tooltip)
+}
+
+/// Hover text for a commit's author: the mailmap-resolved name, plus
+/// the identity recorded in the commit when `show_raw` is on and the
+/// two differ.
+fn author_tooltip(commit: &CommitInfo, show_raw: bool) -> String {
+    match commit.raw_author.as_deref() {
+        Some(raw) if show_raw => format!("{}\nCommitted as {raw}", commit.author),
+        _ => commit.author.clone(),
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -703,8 +714,7 @@ fn ci_state_color(state: CiState) -> Color {
     }
 }
 
-fn ci_check_dot(state: CiState) -> El {
-    let color = ci_state_color(state);
+fn ci_check_dot(color: Color) -> El {
     El::new(Kind::Group)
         .width(Size::Fixed(CI_DOT_SIZE))
         .height(Size::Fixed(CI_DOT_SIZE))
@@ -716,11 +726,23 @@ fn ci_check_dot(state: CiState) -> El {
 /// pre-Aetna graph treatment: provider mark first, then one small pip
 /// per workflow/pipeline check with a `+N` overflow marker. The icon
 /// gives the red/green pips a distinct CI context so they do not read
-/// as part of the adjacent `+N -M` diff-stat text.
+/// as part of the adjacent `+N -M` diff-stat text. Last session's
+/// cached status renders dimmed until the first poll replaces it.
 fn ci_provider_token(rollup: &ProviderCommitRollup, key: String) -> El {
-    let state = rollup.rollup.counts.overall_state();
-    let color = ci_state_color(state);
-    let mut tip = format!("{}: ", rollup.provider.short_label());
+    let shade = |state: CiState| {
+        let color = ci_state_color(state);
+        if rollup.stale {
+            color.with_alpha(110)
+        } else {
+            color
+        }
+    };
+    let color = shade(rollup.rollup.counts.overall_state());
+    let mut tip = format!(
+        "{}{}: ",
+        rollup.provider.short_label(),
+        if rollup.stale { " (last known)" } else { "" }
+    );
     if rollup.rollup.checks.is_empty() {
         tip.push_str("no checks");
     } else {
@@ -735,10 +757,15 @@ fn ci_provider_token(rollup: &ProviderCommitRollup, key: String) -> El {
                     CiState::Pending => "\u{22ef}", // ⋯
                     CiState::None => "\u{2014}",    // —
                 };
-                format!("{mark} {}", c.label)
+                if c.required {
+                    format!("{mark} {} (required)", c.label)
+                } else {
+                    format!("{mark} {}", c.label)
+                }
             })
             .collect();
         tip.push_str(&parts.join(", "));
+        tip.push_str(" \u{00b7} click for logs, right-click to re-run");
     }
 
     let mut children: Vec<El> = vec![
@@ -752,7 +779,7 @@ fn ci_provider_token(rollup: &ProviderCommitRollup, key: String) -> El {
             .checks
             .iter()
             .take(CI_MAX_DOTS_PER_PROVIDER)
-            .map(|check| ci_check_dot(check.state)),
+            .map(|check| ci_check_dot(shade(check.state))),
     );
     let overflow = rollup
         .rollup
@@ -944,17 +971,21 @@ fn pill(
 
 /// Decoration set for a single commit row — branch tips at this
 /// commit, tags at this commit, clean worktrees whose HEAD is this
-/// commit. Pre-computed in `history_view` so the per-row closure
-/// stays cheap.
+/// commit, open pull requests whose head is this commit.
+/// Pre-computed in `history_view` so the per-row closure stays cheap.
 #[derive(Clone, Default)]
 struct RowPills {
     /// (branch name, kind) pairs.
     branches: Vec<(String, BranchKind)>,
-    tags: Vec<String>,
+    /// (tag name, tooltip) pairs; the tooltip names the tag's forge
+    /// release when it has one.
+    tags: Vec<(String, String)>,
     /// Names of clean worktrees pointing here. Dirty worktrees show
     /// their pill on the synthetic row instead, so this list excludes
     /// them.
     clean_worktrees: Vec<String>,
+    /// (reference, key, title) of open PRs / MRs headed here.
+    pulls: Vec<(String, String, String)>,
 }
 
 #[derive(Clone, Copy)]
@@ -987,9 +1018,13 @@ fn build_row(
     ci_rollups: Option<&[ProviderCommitRollup]>,
     is_detached_head_here: bool,
     is_pinned: bool,
+    note_refs: Option<&[String]>,
+    issue_links: &[IssueLink],
+    issues: &HashMap<u64, Issue>,
     idx: usize,
     selected: bool,
     avatar: Option<Image>,
+    show_raw_author: bool,
 ) -> El {
     if commit.is_synthetic {
         return synthetic_row(commit, layout, geom, graph_width, idx, selected);
@@ -1008,9 +1043,9 @@ fn build_row(
 
     // Pills band — PINNED first so the parent-repo annotation reads
     // adjacent to the SHA on the line below, then clean worktrees,
-    // branches, tags, detached HEAD, and orphan. "Any pill at all"
-    // promotes the row to the two-row layout regardless of which kind
-    // was responsible.
+    // branches, pull requests, tags, detached HEAD, and orphan. "Any
+    // pill at all" promotes the row to the two-row layout regardless of
+    // which kind was responsible.
     let mut pill_kids: Vec<El> = Vec::new();
     if is_pinned {
         pill_kids.push(pill(
@@ -1039,13 +1074,22 @@ fn build_row(
             Some(name.clone()),
         ));
     }
-    for tag in &pills.tags {
+    for (reference, key, title) in &pills.pulls {
+        pill_kids.push(pill(
+            reference.clone(),
+            tokens::INFO,
+            28,
+            format!("commit:{idx}.pr:{key}"),
+            Some(format!("{reference} {title}")),
+        ));
+    }
+    for (tag, tooltip) in &pills.tags {
         pill_kids.push(pill(
             format!("\u{25C6} {tag}"),
             tokens::WARNING,
             40,
             format!("commit:{idx}.tag:{tag}"),
-            Some(tag.clone()),
+            Some(tooltip.clone()),
         ));
     }
     if is_detached_head_here && pills.branches.is_empty() {
@@ -1079,6 +1123,14 @@ fn build_row(
         None => summary.clone(),
     };
     let mut summary_children: Vec<El> = vec![text(summary).ellipsis()];
+    for link in issue_links {
+        let issue = link.number.and_then(|n| issues.get(&n));
+        summary_children.push(issue_chip(
+            link,
+            issue,
+            format!("commit:{idx}.issue:{}", link.text),
+        ));
+    }
     if let Some(body) = commit.body_excerpt.as_deref().filter(|s| !s.is_empty()) {
         summary_children.push(
             text(format!("\u{2014} {body}"))
@@ -1111,9 +1163,24 @@ fn build_row(
             .muted()
             .key(format!("commit:{idx}.sha"))
             .tooltip(commit.id.to_string()),
-        author_avatar(&commit.author, avatar, format!("commit:{idx}.avatar")),
+        author_avatar(
+            &commit.author,
+            author_tooltip(commit, show_raw_author),
+            avatar,
+            format!("commit:{idx}.avatar"),
+        ),
         summary_row,
     ];
+    if let Some(refs) = note_refs.filter(|r| !r.is_empty()) {
+        let names: Vec<&str> = refs.iter().map(|r| short_notes_ref(r)).collect();
+        main_children.push(
+            icon(IconName::FileText)
+                .icon_size(tokens::ICON_SM)
+                .muted()
+                .key(format!("commit:{idx}.notes"))
+                .tooltip(format!("Notes: {}", names.join(", "))),
+        );
+    }
     if let Some(rollups) = ci_rollups {
         let ci_kids: Vec<El> = rollups
             .iter()
@@ -1194,6 +1261,31 @@ fn build_row(
     outer
 }
 
+/// Chip for an issue reference in a commit message, shared with the
+/// commit details pane. Colored by the issue's state once the tab has
+/// fetched it; until then (or for template links) the tooltip shows
+/// the link target.
+pub fn issue_chip(link: &IssueLink, issue: Option<&Issue>, key: String) -> El {
+    let (color, tip) = match issue {
+        Some(issue) => (
+            match issue.state {
+                IssueState::Open => tokens::SUCCESS,
+                IssueState::Merged => tokens::PRIMARY,
+                IssueState::Closed => tokens::MUTED_FOREGROUND,
+            },
+            issue.summary(&link.text),
+        ),
+        None => (tokens::INFO, link.url.clone()),
+    };
+    row([text(link.text.clone()).caption().text_color(color)])
+        .padding(Sides::xy(tokens::SPACE_1, PILL_PAD_Y))
+        .align(Align::Center)
+        .fill(color.with_alpha(28))
+        .stroke(color.with_alpha(120))
+        .key(key)
+        .tooltip(tip)
+}
+
 /// Compact `+N -M` chip rendered to the right of the commit summary,
 /// before the author/time column. Returns `None` when both counts
 /// are zero — async diff-stats fetching may not have caught up yet,
@@ -1305,7 +1397,13 @@ pub const SEARCH_INPUT_KEY: &str = "history:search";
 /// dim to ~30% opacity so the matching set stands out without
 /// disrupting the graph's visual integrity (filtering would skip
 /// rows and break the lane verticals between adjacent commits).
-pub fn history_view(tab: &RepoTab, selection: &Selection, avatars: HashMap<String, Image>) -> El {
+/// `show_raw_author` adds the pre-mailmap identity to avatar tooltips.
+pub fn history_view(
+    tab: &RepoTab,
+    selection: &Selection,
+    avatars: HashMap<String, Image>,
+    show_raw_author: bool,
+) -> El {
     if tab.commits.is_empty() {
         return column([
             text("No commits").muted(),
@@ -1392,10 +1490,16 @@ pub fn history_view(tab: &RepoTab, selection: &Selection, avatars: HashMap<Strin
         .iter()
         .map(|c| tab.pinned_oid == Some(c.id))
         .collect();
+    let notes_per_row: Vec<Option<Vec<String>>> = tab
+        .commits
+        .iter()
+        .map(|c| tab.noted_commits.get(&c.id).cloned())
+        .collect();
 
     // Derive per-row band heights from the inputs that drive the
-    // pills band: any branch / tag / clean-worktree / detached-HEAD
-    // / orphan / PINNED pill triggers a band above the main content.
+    // pills band: any branch / tag / pull request / clean-worktree /
+    // detached-HEAD / orphan / PINNED pill triggers a band above the
+    // main content.
     // Synthetic ("uncommitted changes") rows never carry a band —
     // their WT pill stays inline.
     let band_heights: Vec<f32> = tab
@@ -1410,6 +1514,7 @@ pub fn history_view(tab: &RepoTab, selection: &Selection, avatars: HashMap<Strin
             let any_pill = !p.clean_worktrees.is_empty()
                 || !p.branches.is_empty()
                 || !p.tags.is_empty()
+                || !p.pulls.is_empty()
                 || (detached_flags[i] && p.branches.is_empty())
                 || c.is_orphaned
                 || pinned_flags[i];
@@ -1419,6 +1524,8 @@ pub fn history_view(tab: &RepoTab, selection: &Selection, avatars: HashMap<Strin
     let geom_per_row: Vec<RowGeometry> = tab
         .graph_layout
         .row_geometry_with_bands(&tab.commits, &band_heights);
+    let issue_linker = tab.issue_linker.clone();
+    let issues = tab.issues.clone();
     let commits = tab.commits.clone();
     let commit_row_keys: Vec<String> = commits.iter().map(|c| format!("commit:{}", c.id)).collect();
     let selected_oid = tab.selected_commit;
@@ -1466,6 +1573,7 @@ pub fn history_view(tab: &RepoTab, selection: &Selection, avatars: HashMap<Strin
                 let geom = geom_per_row.get(i).unwrap_or(&empty);
                 let matches = match_flags.get(i).copied().unwrap_or(true);
                 let avatar = avatars.get(&c.author_email).cloned();
+                let issue_links = issue_linker.links(&c.summary);
                 let row_el = build_row(
                     c,
                     layouts[i].as_ref(),
@@ -1475,9 +1583,13 @@ pub fn history_view(tab: &RepoTab, selection: &Selection, avatars: HashMap<Strin
                     ci_per_row[i].as_deref(),
                     detached_flags[i],
                     pinned_flags[i],
+                    notes_per_row[i].as_deref(),
+                    &issue_links,
+                    &issues,
                     i,
                     selected,
                     avatar,
+                    show_raw_author,
                 );
                 if matches { row_el } else { row_el.opacity(0.3) }
             },
@@ -1504,13 +1616,23 @@ pub fn history_view(tab: &RepoTab, selection: &Selection, avatars: HashMap<Strin
 }
 
 /// Lower-case substring match across the fields the pre-port
-/// searched: subject, author, short SHA. The full SHA's prefix is
-/// also checked so paste-a-SHA navigation still works.
+/// searched: subject, author, short SHA. The author matches on both
+/// the mailmap-resolved identity and the one recorded in the commit,
+/// so searching an old email still finds that person's commits. The
+/// full SHA's prefix is also checked so paste-a-SHA navigation still
+/// works.
 fn commit_matches_query(c: &CommitInfo, lower_query: &str) -> bool {
     if c.summary.to_lowercase().contains(lower_query) {
         return true;
     }
-    if c.author.to_lowercase().contains(lower_query) {
+    if c.author.to_lowercase().contains(lower_query)
+        || c.author_email.to_lowercase().contains(lower_query)
+    {
+        return true;
+    }
+    if let Some(raw) = &c.raw_author
+        && raw.to_lowercase().contains(lower_query)
+    {
         return true;
     }
     if c.short_id.to_lowercase().contains(lower_query) {
@@ -1523,8 +1645,9 @@ fn commit_matches_query(c: &CommitInfo, lower_query: &str) -> bool {
 }
 
 /// Pre-compute the `RowPills` for each commit: walk branch tips,
-/// tags, and clean worktree views, indexing them by their oid, then
-/// gather them onto each commit row in `tab.commits` order. Faster
+/// tags, clean worktree views, and open pull requests, indexing them
+/// by their oid, then gather them onto each commit row in
+/// `tab.commits` order. Faster
 /// than per-row filtering on big histories.
 fn build_row_pills(tab: &RepoTab) -> Vec<RowPills> {
     let mut by_oid_branches: HashMap<Oid, Vec<(String, BranchKind)>> = HashMap::new();
@@ -1548,12 +1671,19 @@ fn build_row_pills(tab: &RepoTab) -> Vec<RowPills> {
             .push((tip.name.clone(), kind));
     }
 
-    let mut by_oid_tags: HashMap<Oid, Vec<String>> = HashMap::new();
+    let mut by_oid_tags: HashMap<Oid, Vec<(String, String)>> = HashMap::new();
     for tag in &tab.tags {
+        let tooltip = match &tag.release {
+            Some(release) => match release.badge() {
+                Some(badge) => format!("{} \u{00B7} {} ({badge})", tag.name, release.title()),
+                None => format!("{} \u{00B7} {}", tag.name, release.title()),
+            },
+            None => tag.name.clone(),
+        };
         by_oid_tags
             .entry(tag.oid)
             .or_default()
-            .push(tag.name.clone());
+            .push((tag.name.clone(), tooltip));
     }
 
     // Clean worktrees: those whose status reports zero dirty files. A
@@ -1572,12 +1702,25 @@ fn build_row_pills(tab: &RepoTab) -> Vec<RowPills> {
         }
     }
 
+    // PR heads are only drawn when the head commit is already in the
+    // graph (same-repo branches, or after "Checkout PR").
+    let mut by_oid_pulls: HashMap<Oid, Vec<(String, String, String)>> = HashMap::new();
+    for (_, pr) in tab.pull_requests() {
+        if let Ok(oid) = Oid::from_str(&pr.head_sha) {
+            by_oid_pulls
+                .entry(oid)
+                .or_default()
+                .push((pr.reference(), pr.key(), pr.title.clone()));
+        }
+    }
+
     tab.commits
         .iter()
         .map(|c| RowPills {
             branches: by_oid_branches.get(&c.id).cloned().unwrap_or_default(),
             tags: by_oid_tags.get(&c.id).cloned().unwrap_or_default(),
             clean_worktrees: by_oid_clean_wts.get(&c.id).cloned().unwrap_or_default(),
+            pulls: by_oid_pulls.get(&c.id).cloned().unwrap_or_default(),
         })
         .collect()
 }
@@ -1750,6 +1893,7 @@ mod tests {
             body_full: None,
             author: String::new(),
             author_email: String::new(),
+            raw_author: None,
             time: 0,
             parent_ids: Vec::new(),
             insertions: 0,
@@ -30,6 +30,15 @@ pub struct Config {
     /// Auto-populated when a GitLab remote is detected during CI fetch.
     #[serde(default)]
     pub gitlab_hosts: Vec<String>,
+    /// Registered Gitea / Forgejo hosts. Codeberg and hosts named after
+    /// either forge are added on CI fetch; others are added by hand in
+    /// the token modal, since nothing in the URL identifies them.
+    #[serde(default)]
+    pub gitea_hosts: Vec<String>,
+    /// Registered GitHub Enterprise Server hosts, added by hand in the
+    /// token modal. github.com is implied and never listed.
+    #[serde(default)]
+    pub github_hosts: Vec<String>,
     /// User-resized left sidebar width (logical px). Persisted across
     /// restarts via this config so the layout the user worked into
     /// survives a relaunch.
@@ -42,6 +51,51 @@ pub struct Config {
     /// `true` for side-by-side diff view; `false` for unified.
     #[serde(default)]
     pub diff_split: bool,
+    /// Show the identity recorded in a commit, before `.mailmap`
+    /// resolution, in author tooltips.
+    #[serde(default)]
+    pub show_raw_author: bool,
+    /// Changelog grouping: subject prefixes instead of conventional
+    /// commit types. Remembered from the last Generate.
+    #[serde(default)]
+    pub changelog_by_prefix: bool,
+    /// Subject prefixes for prefix grouping, in section order.
+    #[serde(default)]
+    pub changelog_prefixes: Vec<String>,
+    /// Named git identities, applied to a repository's local config
+    /// when one of its remotes matches a profile's host patterns.
+    #[serde(default)]
+    pub identity_profiles: Vec<IdentityProfile>,
+}
+
+/// A reusable `user.name` / `user.email` / signing key set, e.g.
+/// "Work" for `github.com/work-org` and `*.corp.example` remotes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct IdentityProfile {
+    pub name: String,
+    pub user_name: String,
+    pub email: String,
+    #[serde(default)]
+    pub signing_key: String,
+    /// Host globs, optionally with a path prefix; see
+    /// [`crate::git::remote_matches_pattern`].
+    #[serde(default)]
+    pub host_patterns: Vec<String>,
+}
+
+impl IdentityProfile {
+    /// Local config writes that switch a repository to this identity.
+    /// An empty signing key unsets any key left by another profile.
+    pub fn config_changes(&self) -> [(&'static str, Option<&str>); 3] {
+        [
+            ("user.name", Some(self.user_name.as_str())),
+            ("user.email", Some(self.email.as_str())),
+            (
+                "user.signingkey",
+                Some(self.signing_key.as_str()).filter(|k| !k.is_empty()),
+            ),
+        ]
+    }
 }
 
 fn default_sidebar_w() -> f32 {
@@ -78,9 +132,15 @@ impl Default for Config {
             ratchet_scroll: true,
             ai_provider: default_ai_provider(),
             gitlab_hosts: Vec::new(),
+            gitea_hosts: Vec::new(),
+            github_hosts: Vec::new(),
             sidebar_w: default_sidebar_w(),
             right_pane_w: default_right_w(),
             diff_split: false,
+            show_raw_author: false,
+            changelog_by_prefix: false,
+            changelog_prefixes: Vec::new(),
+            identity_profiles: Vec::new(),
         }
     }
 }
@@ -107,6 +167,8 @@ impl Config {
         if let Err(e) = config.refresh_recent_repos() {
             eprintln!("Warning: failed to refresh recent repositories: {e}");
         }
+        crate::gitea::set_registered_hosts(&config.gitea_hosts);
+        crate::github::set_enterprise_hosts(&config.github_hosts);
         config
     }
 
@@ -134,6 +196,44 @@ impl Config {
         true
     }
 
+    /// Register a Gitea / Forgejo `host`, like
+    /// [`Self::register_gitlab_host`]. Also refreshes the host list
+    /// `gitea::parse_gitea_remote` matches against.
+    pub fn register_gitea_host(&mut self, host: &str) -> bool {
+        if self.gitea_hosts.iter().any(|h| h == host) {
+            return false;
+        }
+        self.gitea_hosts.push(host.to_string());
+        self.gitea_hosts.sort();
+        crate::gitea::set_registered_hosts(&self.gitea_hosts);
+        true
+    }
+
+    /// Register a GitHub Enterprise Server `host`, like
+    /// [`Self::register_gitlab_host`]. Also refreshes the host list
+    /// `github::parse_github_remote` matches against.
+    pub fn register_github_host(&mut self, host: &str) -> bool {
+        if host == "github.com" || self.github_hosts.iter().any(|h| h == host) {
+            return false;
+        }
+        self.github_hosts.push(host.to_string());
+        self.github_hosts.sort();
+        crate::github::set_enterprise_hosts(&self.github_hosts);
+        true
+    }
+
+    /// First identity profile with a host pattern matching any of
+    /// `remote_urls`.
+    pub fn identity_profile_for(&self, remote_urls: &[String]) -> Option<&IdentityProfile> {
+        self.identity_profiles.iter().find(|profile| {
+            profile.host_patterns.iter().any(|pattern| {
+                remote_urls
+                    .iter()
+                    .any(|url| crate::git::remote_matches_pattern(url, pattern))
+            })
+        })
+    }
+
     /// Add a repo path to the recent repos list (most recent first, deduped).
     pub fn add_recent_repo(&mut self, path: &str) -> Result<(), String> {
         self.recent_repos.retain(|p| p != path);
@@ -7,9 +7,17 @@
 
 use aetna_core::{El, IconName, Selection, prelude::*};
 
-use crate::config::Config;
-use crate::git::WorktreeInfo;
+use crate::ci::CiProvider;
+use crate::config::{Config, IdentityProfile};
+use crate::forge::TokenInfo;
+use crate::git::{
+    BundleInfo, BundlePlan, CONFIG_FIELDS, Changelog, ConfigScope, ConflictForecast,
+    IgnoreDestination, MaintenanceTask, PatchPreview, RepoHealth, WorktreeInfo, format_bytes,
+};
+use crate::pulls::{PullRequest, ReviewAction};
 use crate::recent::RecentRepoEntry;
+use crate::releases::Release;
+use crate::repo_tab::MaintenanceState;
 
 pub const MODAL_SETTINGS_KEY: &str = "modal:settings";
 pub const MODAL_CONFIRM_KEY: &str = "modal:confirm";
@@ -18,13 +26,29 @@ pub const MODAL_CLONE_KEY: &str = "modal:clone";
 pub const MODAL_TOKEN_KEY: &str = "modal:token";
 pub const MODAL_BRANCH_KEY: &str = "modal:branch";
 pub const MODAL_TAG_KEY: &str = "modal:tag";
+pub const MODAL_FETCH_KEY: &str = "modal:fetch";
 pub const MODAL_PULL_KEY: &str = "modal:pull";
 pub const MODAL_PUSH_KEY: &str = "modal:push";
 pub const MODAL_MERGE_KEY: &str = "modal:merge";
 pub const MODAL_REBASE_KEY: &str = "modal:rebase";
 pub const MODAL_WORKTREE_KEY: &str = "modal:worktree";
 pub const MODAL_WORKTREES_KEY: &str = "modal:worktrees";
+pub const MODAL_WORKTREE_LOCK_KEY: &str = "modal:worktree_lock";
+pub const MODAL_WORKTREE_MOVE_KEY: &str = "modal:worktree_move";
+pub const MODAL_PATCH_EXPORT_KEY: &str = "modal:patch_export";
+pub const MODAL_PATCH_APPLY_KEY: &str = "modal:patch_apply";
+pub const MODAL_BUNDLE_CREATE_KEY: &str = "modal:bundle_create";
+pub const MODAL_BUNDLE_IMPORT_KEY: &str = "modal:bundle_import";
+pub const MODAL_CHANGELOG_KEY: &str = "modal:changelog";
+pub const MODAL_REPO_CONFIG_KEY: &str = "modal:repo_config";
+pub const MODAL_MAINTENANCE_KEY: &str = "modal:maintenance";
+pub const MODAL_NOTE_KEY: &str = "modal:note";
+pub const MODAL_IGNORE_KEY: &str = "modal:ignore";
 pub const MODAL_OPEN_REPO_KEY: &str = "modal:open_repo";
+pub const MODAL_CREATE_PR_KEY: &str = "modal:create_pr";
+pub const MODAL_REVIEW_COMMENT_KEY: &str = "modal:review_comment";
+pub const MODAL_RUN_WORKFLOW_KEY: &str = "modal:run_workflow";
+pub const MODAL_CREATE_RELEASE_KEY: &str = "modal:create_release";
 
 /// Settings panel for application preferences. Stale pre-aetna knobs
 /// stay out of the modal until their callers exist again.
@@ -38,6 +62,10 @@ pub fn settings_modal(config: &Config, shortcut_bar_visible: bool) -> El {
                     switch(config.show_orphaned_commits).key("settings:orphans"),
                 ),
                 field_row("Row size", row_size_selector(config.row_scale)),
+                field_row(
+                    "Raw author on hover",
+                    switch(config.show_raw_author).key("settings:raw_author"),
+                ),
             ],
         ),
         settings_section(
@@ -359,6 +387,51 @@ pub fn tag_modal(state: &TagForm, selection: &Selection, target_short: &str) ->
     overlays_panel(MODAL_TAG_KEY, "Create tag", [body])
 }
 
+/// Form state for the Fetch picker modal — selected remote plus the
+/// `--prune` and notes toggles. Populated on open from the focused
+/// tab's default remote.
+#[derive(Clone, Debug, Default)]
+pub struct FetchForm {
+    pub remote: String,
+    pub prune: bool,
+    pub include_notes: bool,
+}
+
+/// Fetch-with-options modal. Lists every remote as a radio option,
+/// plus switches for `--prune` and fetching `refs/notes/*` alongside
+/// the branches. Reached via the caret next to the header Fetch button.
+pub fn fetch_modal(state: &FetchForm, remotes: &[String]) -> El {
+    let radio = radio_group(
+        "fetch:remote",
+        &state.remote,
+        remotes.iter().map(|s| (s.clone(), s.clone())),
+    );
+    let remote_field = form_item([form_label("Remote"), radio]);
+
+    let prune_field = field_row("Prune", switch(state.prune).key("fetch:prune"));
+    let notes_field = field_row(
+        "Include notes",
+        switch(state.include_notes).key("fetch:notes"),
+    );
+
+    let mut fetch_btn = button("Fetch").key("fetch:execute").primary();
+    if state.remote.trim().is_empty() {
+        fetch_btn = fetch_btn.disabled();
+    }
+
+    let actions = row([
+        spacer(),
+        button("Cancel").key("modal:fetch:cancel").ghost(),
+        fetch_btn,
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center);
+
+    let body = form([remote_field, prune_field, notes_field, actions]);
+
+    overlays_panel(MODAL_FETCH_KEY, "Fetch from remote", [body])
+}
+
 /// Form state for the Pull picker modal — one selected source label
 /// (e.g. "origin/main") plus the `--rebase` toggle. Populated on open
 /// from the active branch's upstream when present, falling back to
@@ -418,11 +491,13 @@ pub struct PushForm {
     pub force_with_lease: bool,
     pub set_upstream: bool,
     pub include_tags: bool,
+    pub include_notes: bool,
 }
 
 /// Push-with-options modal. Lists every remote as a radio option, plus
-/// a branch text input (defaulted to the current branch) and three
-/// flag switches: `--force-with-lease`, `--set-upstream`, `--tags`.
+/// a branch text input (defaulted to the current branch) and flag
+/// switches: `--force-with-lease`, `--set-upstream`, `--tags`, and
+/// pushing `refs/notes/*` alongside the branch.
 pub fn push_modal(state: &PushForm, selection: &Selection, remotes: &[String]) -> El {
     let radio = radio_group(
         "push:remote",
@@ -450,6 +525,10 @@ pub fn push_modal(state: &PushForm, selection: &Selection, remotes: &[String]) -
         switch(state.set_upstream).key("push:set_upstream"),
     );
     let tags_field = field_row("Include tags", switch(state.include_tags).key("push:tags"));
+    let notes_field = field_row(
+        "Include notes",
+        switch(state.include_notes).key("push:notes"),
+    );
 
     let mut push_btn = button("Push").key("push:execute").primary();
     if state.remote.trim().is_empty() || state.branch.trim().is_empty() {
@@ -470,6 +549,7 @@ pub fn push_modal(state: &PushForm, selection: &Selection, remotes: &[String]) -
         force_field,
         upstream_field,
         tags_field,
+        notes_field,
         actions,
     ]);
 
@@ -519,6 +599,9 @@ impl MergeStrategy {
 pub struct MergeForm {
     pub strategy: MergeStrategy,
     pub no_ff_message: String,
+    /// In-memory merge of the source into HEAD, run in the background
+    /// when the modal opens. `None` until it lands.
+    pub forecast: Option<Result<ConflictForecast, String>>,
 }
 
 /// Merge-with-options modal. Reached from the branch context menu's
@@ -547,6 +630,7 @@ pub fn merge_modal(state: &MergeForm, selection: &Selection, source: &str) -> El
     ]);
 
     let mut sections: Vec<El> = vec![strategy_field];
+    sections.extend(forecast_section(state.forecast.as_ref(), "merge"));
     if state.strategy == MergeStrategy::NoFf {
         sections.push(form_item([
             form_label("Merge commit message"),
@@ -581,6 +665,9 @@ pub fn merge_modal(state: &MergeForm, selection: &Selection, source: &str) -> El
 pub struct RebaseForm {
     pub autostash: bool,
     pub rebase_merges: bool,
+    /// In-memory replay of HEAD's commits onto the base, run in the
+    /// background when the modal opens. `None` until it lands.
+    pub forecast: Option<Result<ConflictForecast, String>>,
 }
 
 /// Rebase-with-options modal. Reached from the branch context menu's
@@ -607,8 +694,77 @@ pub fn rebase_modal(state: &RebaseForm, base: &str) -> El {
     .gap(tokens::SPACE_2)
     .align(Align::Center);
 
-    let body = form([target_caption, autostash_field, merges_field, actions]);
-    overlays_panel(MODAL_REBASE_KEY, "Rebase with options", [body])
+    let mut sections = vec![target_caption];
+    sections.extend(forecast_section(state.forecast.as_ref(), "rebase"));
+    sections.extend([autostash_field, merges_field, actions]);
+    overlays_panel(MODAL_REBASE_KEY, "Rebase with options", [form(sections)])
+}
+
+/// Conflict forecast block shared by the merge and rebase modals:
+/// a one-line verdict plus, when it would stop, the conflicting paths.
+fn forecast_section(forecast: Option<&Result<ConflictForecast, String>>, op: &str) -> Option<El> {
+    let Some(forecast) = forecast else {
+        return Some(text("Checking for conflicts…").caption().muted());
+    };
+    let forecast = match forecast {
+        Ok(forecast) => forecast,
+        Err(e) => {
+            return Some(
+                text(format!("Couldn't predict conflicts: {e}"))
+                    .caption()
+                    .muted()
+                    .ellipsis(),
+            );
+        }
+    };
+    if forecast.up_to_date {
+        return Some(row([badge("up to date").muted()]));
+    }
+    if forecast.fast_forward {
+        return Some(row([badge("fast-forward").success()]).gap(tokens::SPACE_2));
+    }
+    if forecast.is_clean() {
+        let detail = if forecast.commits > 0 {
+            format!("{} commit(s) replay cleanly.", forecast.commits)
+        } else {
+            format!("The {op} should complete without conflicts.")
+        };
+        return Some(
+            row([
+                badge("no conflicts").success(),
+                text(detail).caption().muted(),
+            ])
+            .gap(tokens::SPACE_2)
+            .align(Align::Center),
+        );
+    }
+
+    let summary = match &forecast.stopping_commit {
+        Some((oid, subject)) => format!(
+            "Stops at {} {subject} ({} commit(s) to replay).",
+            &oid.to_string()[..7],
+            forecast.commits
+        ),
+        None => format!("The {op} will stop for you to resolve these."),
+    };
+    let paths = forecast
+        .conflicts
+        .iter()
+        .map(|path| text(path.clone()).mono().caption().ellipsis());
+    Some(
+        column([
+            row([
+                badge(format!("{} conflict(s)", forecast.conflicts.len())).warning(),
+                text(summary).caption().muted().ellipsis(),
+            ])
+            .gap(tokens::SPACE_2)
+            .align(Align::Center),
+            scroll([column(paths).gap(tokens::SPACE_1)])
+                .key(format!("{op}:conflicts"))
+                .height(Size::Fixed(96.0)),
+        ])
+        .gap(tokens::SPACE_2),
+    )
 }
 
 /// Form state for the Create Worktree modal — path + source ref +
@@ -689,15 +845,16 @@ pub fn worktree_modal(state: &WorktreeForm, selection: &Selection) -> El {
     overlays_panel(MODAL_WORKTREE_KEY, "Create worktree", [body])
 }
 
+/// Worktree management dialog. One row per linked worktree with
+/// lock/unlock, move and remove actions; rows whose directory has gone
+/// missing collapse to a `missing` badge, and a Prune button appears
+/// whenever at least one such row exists.
 pub fn worktrees_modal(worktrees: &[WorktreeInfo], active_path: Option<&std::path::Path>) -> El {
+    let done = button("Done").key("modal:worktrees:close").primary();
     let body = if worktrees.is_empty() {
         column([
             paragraph("No linked worktrees.".to_string()).muted(),
-            row([
-                spacer(),
-                button("Done").key("modal:worktrees:close").primary(),
-            ])
-            .align(Align::Center),
+            row([spacer(), done]).align(Align::Center),
         ])
         .gap(tokens::SPACE_3)
     } else {
@@ -705,13 +862,23 @@ pub fn worktrees_modal(worktrees: &[WorktreeInfo], active_path: Option<&std::pat
             .iter()
             .enumerate()
             .map(|(idx, wt)| worktree_manage_row(idx, wt, active_path));
+        let stale = worktrees.iter().filter(|wt| wt.is_stale).count();
+        let mut actions: Vec<El> = Vec::new();
+        if stale > 0 {
+            actions.push(
+                text(format!("{stale} worktree(s) point at missing directories."))
+                    .caption()
+                    .muted(),
+            );
+        }
+        actions.push(spacer());
+        if stale > 0 {
+            actions.push(button("Prune stale").key("worktrees:prune").ghost());
+        }
+        actions.push(done);
         column([
             item_group(rows).width(Size::Fill(1.0)),
-            row([
-                spacer(),
-                button("Done").key("modal:worktrees:close").primary(),
-            ])
-            .align(Align::Center),
+            row(actions).gap(tokens::SPACE_2).align(Align::Center),
         ])
         .gap(tokens::SPACE_3)
     };
@@ -722,24 +889,61 @@ pub fn worktrees_modal(worktrees: &[WorktreeInfo], active_path: Option<&std::pat
 fn worktree_manage_row(idx: usize, wt: &WorktreeInfo, active_path: Option<&std::path::Path>) -> El {
     let is_active = active_path == Some(std::path::Path::new(&wt.path));
     let dirty = wt.dirty_file_count.unwrap_or(0);
-    let mut meta: Vec<El> = vec![
-        text(wt.branch.clone()).caption().muted(),
+    let mut meta: Vec<El> = Vec::new();
+    if let Some(branch) = &wt.branch {
+        meta.push(text(branch.clone()).caption().muted());
+    }
+    meta.push(
         text(wt.path.clone())
             .caption()
             .muted()
             .ellipsis()
             .width(Size::Fill(1.0))
             .tooltip(wt.path.clone()),
-    ];
-    if dirty > 0 {
+    );
+    if wt.is_stale {
+        meta.push(badge("missing").warning());
+    } else if dirty > 0 {
         meta.push(badge(format!("{dirty} dirty")).warning());
     } else if wt.is_dirty == Some(false) {
         meta.push(badge("clean").muted());
     }
+    if wt.is_locked {
+        let lock = badge("locked").muted();
+        meta.push(match &wt.lock_reason {
+            Some(reason) => lock.tooltip(reason.clone()),
+            None => lock,
+        });
+    }
     if is_active {
         meta.push(badge("active").muted());
     }
 
+    let lock_button = if wt.is_locked {
+        button("Unlock")
+            .key(format!("worktrees:unlock:{idx}"))
+            .ghost()
+    } else {
+        button("Lock\u{2026}")
+            .key(format!("worktrees:lock:{idx}"))
+            .ghost()
+    };
+    let mut actions: Vec<El> = vec![lock_button];
+    // Locked worktrees refuse move/remove until unlocked, so don't offer
+    // actions that would only fail.
+    if !wt.is_stale && !wt.is_locked {
+        actions.push(
+            button("Move\u{2026}")
+                .key(format!("worktrees:move:{idx}"))
+                .ghost(),
+        );
+        actions.push(
+            button("Remove")
+                .key(format!("worktrees:remove:{idx}"))
+                .destructive(),
+        );
+    }
+
     item([
         item_media_icon(IconName::LayoutDashboard),
         item_content([
@@ -749,139 +953,1499 @@ fn worktree_manage_row(idx: usize, wt: &WorktreeInfo, active_path: Option<&std::
                 .align(Align::Center)
                 .width(Size::Fill(1.0)),
         ]),
-        button("Remove")
-            .key(format!("worktrees:remove:{idx}"))
-            .destructive(),
+        row(actions).gap(tokens::SPACE_1).align(Align::Center),
     ])
 }
 
-/// Form state for the Token modal — one section for GitHub, one row
-/// per registered GitLab host. The Vec/HashMap shape mirrors the
-/// modal's render path: each host is independently editable, and we
-/// only carry input state for hosts the user is actively editing.
+/// Form state for the Lock Worktree modal. `path` is what gets handed
+/// to `git worktree lock`; `name` is display-only.
 #[derive(Clone, Debug, Default)]
-pub struct TokenForm {
-    /// Live text-input contents for the GitHub field. Empty when the
-    /// user hasn't started editing or just cleared the existing value.
-    pub github_input: String,
-    /// `true` while the user is actively editing the GitHub field —
-    /// drives the Save / Cancel button pair vs the Set / Clear pair.
-    pub editing_github: bool,
-    /// Per-host GitLab input buffers. A host being a key here means the
-    /// row is in editing mode; absence means the row shows status +
-    /// Set/Replace/Clear buttons.
-    pub gitlab_inputs: std::collections::HashMap<String, String>,
-}
-
-/// Token management modal. One block for GitHub, one block per
-/// registered GitLab host (sourced from `Config::gitlab_hosts`). All
-/// secrets live in the system keychain via `token_store`; this modal
-/// reads/writes through `token:*` routes that the app handles.
-///
-/// `gitlab_hosts` is `(host, configured)` for each registered GitLab
-/// host. Hosts come from `Config::gitlab_hosts` (auto-populated on
-/// CI fetch); the configured flag comes from a `token_store` lookup.
-pub fn token_modal(
-    state: &TokenForm,
-    selection: &Selection,
-    github_set: bool,
-    gitlab_hosts: &[(String, bool)],
-) -> El {
-    let github_controls: El = if state.editing_github {
-        row([
-            text_input(&state.github_input, selection, "token:github")
-                .key("token:github")
+pub struct WorktreeLockForm {
+    pub name: String,
+    pub path: String,
+    pub reason: String,
+}
+
+/// Lock-a-worktree modal. The reason is optional; git records it in
+/// `.git/worktrees/<name>/locked` and it comes back on the pill tooltip.
+pub fn worktree_lock_modal(state: &WorktreeLockForm, selection: &Selection) -> El {
+    let reason_field = form_item([
+        form_label("Reason"),
+        form_control(
+            text_input(&state.reason, selection, "worktree_lock:reason")
+                .key("worktree_lock:reason")
                 .width(Size::Fill(1.0)),
-            button("Save").key("token:github:save").primary(),
-            button("Cancel").key("token:github:cancel").ghost(),
-        ])
-        .gap(tokens::SPACE_2)
-        .align(Align::Center)
-        .width(Size::Fill(1.0))
+        ),
+        form_description(
+            "Optional. Locked worktrees are skipped by prune and can't be \
+             moved or removed until unlocked — useful for worktrees on \
+             removable or network drives."
+                .to_string(),
+        ),
+    ]);
+    let actions = row([
+        spacer(),
+        button("Cancel").key("modal:worktree_lock:cancel").ghost(),
+        button("Lock").key("worktree_lock:execute").primary(),
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center);
+
+    let body = form([
+        paragraph(format!("Lock worktree '{}' at {}.", state.name, state.path))
+            .text_color(tokens::MUTED_FOREGROUND),
+        reason_field,
+        actions,
+    ]);
+    overlays_panel(MODAL_WORKTREE_LOCK_KEY, "Lock worktree", [body])
+}
+
+/// Form state for the Move Worktree modal — the worktree being moved
+/// plus the destination path the user is typing.
+#[derive(Clone, Debug, Default)]
+pub struct WorktreeMoveForm {
+    pub name: String,
+    pub path: String,
+    pub dest: String,
+}
+
+/// Move-a-worktree modal. Routes through `git worktree move`, which
+/// rewrites the `.git` link file and the admin dir's `gitdir` in one go.
+pub fn worktree_move_modal(state: &WorktreeMoveForm, selection: &Selection) -> El {
+    let dest_field = form_item([
+        form_label("New path"),
+        form_control(
+            text_input(&state.dest, selection, "worktree_move:dest")
+                .key("worktree_move:dest")
+                .width(Size::Fill(1.0)),
+        ),
+        form_description(
+            "Must not exist yet. Worktrees containing submodules can't be moved.".to_string(),
+        ),
+    ]);
+    let mut move_btn = button("Move").key("worktree_move:execute").primary();
+    let dest = state.dest.trim();
+    if dest.is_empty() || dest == state.path {
+        move_btn = move_btn.disabled();
+    }
+    let actions = row([
+        spacer(),
+        button("Cancel").key("modal:worktree_move:cancel").ghost(),
+        move_btn,
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center);
+
+    let body = form([
+        paragraph(format!(
+            "Move worktree '{}' from {}.",
+            state.name, state.path
+        ))
+        .text_color(tokens::MUTED_FOREGROUND),
+        dest_field,
+        actions,
+    ]);
+    overlays_panel(MODAL_WORKTREE_MOVE_KEY, "Move worktree", [body])
+}
+
+/// Form state for the Export Patch modal. `tip` is the commit the menu
+/// was opened on; `base` is an optional revspec — empty exports just
+/// `tip`, otherwise every commit in `base..tip`. Pre-filled from the
+/// tab's patch range base when one was marked.
+#[derive(Clone, Debug)]
+pub struct PatchExportForm {
+    pub tip: git2::Oid,
+    pub base: String,
+}
+
+/// Export-as-patch modal. Reached from the commit context menu's
+/// "Patch Actions" group. The output goes to the clipboard, a single
+/// mbox file, or one `.patch` file per commit in a chosen folder.
+pub fn patch_export_modal(state: &PatchExportForm, selection: &Selection) -> El {
+    let tip_short = &state.tip.to_string()[..7];
+    let base = state.base.trim();
+    let description = if base.is_empty() {
+        format!("Empty exports only {tip_short}.")
     } else {
-        let status: El = if github_set {
-            badge("Configured").success()
-        } else {
-            badge("Not set").muted()
-        };
-        let mut children: Vec<El> = vec![
-            status,
-            spacer(),
-            button(if github_set {
-                "Replace\u{2026}"
-            } else {
-                "Set\u{2026}"
-            })
-            .key("token:github:edit")
+        format!("Exports every commit in {base}..{tip_short}.")
+    };
+    let base_field = form_item([
+        form_label("Range base (optional)"),
+        form_control(
+            text_input(&state.base, selection, "patch_export:base")
+                .key("patch_export:base")
+                .width(Size::Fill(1.0)),
+        ),
+        form_description(description),
+    ]);
+
+    let actions = row([
+        spacer(),
+        button("Cancel").key("modal:patch_export:cancel").ghost(),
+        button("Save to folder\u{2026}")
+            .key("patch_export:folder")
             .ghost(),
-        ];
-        if github_set {
-            children.push(button("Clear").key("token:github:clear").destructive());
-        }
-        row(children)
+        button("Save mbox\u{2026}").key("patch_export:save").ghost(),
+        button("Copy").key("patch_export:copy").primary(),
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center);
+
+    let body = form([base_field, actions]);
+    overlays_panel(MODAL_PATCH_EXPORT_KEY, "Export as patch", [body])
+}
+
+/// Form state for the Apply Patch modal. `preview` is computed once when
+/// the file is picked; `use_am` defaults on for mailboxes so the
+/// original commits are recreated.
+#[derive(Clone, Debug)]
+pub struct PatchApplyForm {
+    pub path: std::path::PathBuf,
+    pub preview: PatchPreview,
+    pub use_am: bool,
+    pub three_way: bool,
+}
+
+/// Apply-patch / mailbox modal. Lists the files the patch touches with
+/// their line counts, then lets the user pick `git am` (mailboxes only)
+/// vs `git apply` and toggle `--3way`.
+pub fn patch_apply_modal(state: &PatchApplyForm) -> El {
+    let preview = &state.preview;
+    let file_name = state
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| state.path.display().to_string());
+    let summary = if preview.is_mailbox {
+        format!(
+            "{file_name}: {} patch(es), {} file(s)",
+            preview.message_count,
+            preview.files.len()
+        )
+    } else {
+        format!("{file_name}: plain diff, {} file(s)", preview.files.len())
+    };
+
+    let files = preview.files.iter().map(|f| {
+        let stats = match (f.insertions, f.deletions) {
+            (Some(ins), Some(del)) => row([
+                text(format!("+{ins}")).mono().text_color(tokens::SUCCESS),
+                text(format!("-{del}"))
+                    .mono()
+                    .text_color(tokens::DESTRUCTIVE),
+            ])
+            .gap(tokens::SPACE_2),
+            _ => text("binary").mono().muted(),
+        };
+        row([text(f.path.clone()).mono().nowrap_text(), spacer(), stats])
             .gap(tokens::SPACE_2)
             .align(Align::Center)
-            .width(Size::Fill(1.0))
-    };
+    });
+    let file_list = scroll([column(files).gap(tokens::SPACE_1)])
+        .key("patch_apply:files")
+        .height(Size::Fixed(160.0));
+
+    let mut sections: Vec<El> = vec![
+        paragraph(summary).text_color(tokens::MUTED_FOREGROUND),
+        file_list,
+    ];
+    if preview.is_mailbox {
+        let methods = [
+            ("am".to_string(), "Create commits (git am)".to_string()),
+            (
+                "apply".to_string(),
+                "Only change files (git apply)".to_string(),
+            ),
+        ];
+        let current = if state.use_am { "am" } else { "apply" };
+        sections.push(form_item([
+            form_label("Method"),
+            radio_group("patch_apply:method", current, methods),
+        ]));
+    }
+    sections.push(field_row(
+        "Fall back to 3-way merge (--3way)",
+        switch(state.three_way).key("patch_apply:three_way"),
+    ));
+    sections.push(
+        row([
+            spacer(),
+            button("Cancel").key("modal:patch_apply:cancel").ghost(),
+            button("Apply").key("patch_apply:execute").primary(),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+    );
+
+    overlays_panel(MODAL_PATCH_APPLY_KEY, "Apply patch", [form(sections)])
+}
+
+/// Form state for the Create Bundle modal. `plan` is the result of the
+/// last "Check" — cleared whenever `revs` is edited, so Create is only
+/// enabled for a range whose prerequisites the user has seen.
+#[derive(Clone, Debug, Default)]
+pub struct BundleCreateForm {
+    pub revs: String,
+    pub plan: Option<Result<BundlePlan, String>>,
+}
 
-    let github_block = form_item([
-        form_label("GitHub"),
-        form_control(github_controls),
-        form_description("Stored in the system keychain via the `keyring` crate."),
+/// Create-bundle modal. Reached from the Remote section's context menu.
+/// Takes whitespace-separated refs / ranges exactly as `git bundle
+/// create` does, and lists the commits the receiving side must already
+/// have before the bundle can be written.
+pub fn bundle_create_modal(state: &BundleCreateForm, selection: &Selection) -> El {
+    let revs_field = form_item([
+        form_label("Refs and ranges"),
+        form_control(
+            text_input(&state.revs, selection, "bundle_create:revs")
+                .key("bundle_create:revs")
+                .width(Size::Fill(1.0)),
+        ),
+        form_description(
+            "e.g. `main`, `v1.0..main`, or `--all`. Ranges need the receiver to have the base."
+                .to_string(),
+        ),
     ]);
 
-    let mut sections: Vec<El> = vec![github_block];
+    let mut sections: Vec<El> = vec![revs_field];
+    let mut create_btn = button("Create\u{2026}")
+        .key("bundle_create:execute")
+        .primary();
+    match &state.plan {
+        None => {
+            create_btn = create_btn.disabled();
+            sections.push(
+                text("Check the range to see what the bundle will need.")
+                    .caption()
+                    .muted(),
+            );
+        }
+        Some(Err(e)) => {
+            create_btn = create_btn.disabled();
+            sections.push(paragraph(e.clone()).text_color(tokens::DESTRUCTIVE));
+        }
+        Some(Ok(plan)) => {
+            if plan.commit_count == 0 {
+                create_btn = create_btn.disabled();
+            }
+            sections.push(text(format!("{} commit(s) in bundle.", plan.commit_count)).label());
+            if plan.prerequisites.is_empty() {
+                sections.push(
+                    text("Self-contained: no prerequisite commits.")
+                        .caption()
+                        .muted(),
+                );
+            } else {
+                sections.push(
+                    text("The receiving repository must already have:")
+                        .caption()
+                        .muted(),
+                );
+                let rows = plan.prerequisites.iter().map(|p| {
+                    row([
+                        text(p.oid.to_string()[..7].to_string()).mono().muted(),
+                        text(p.summary.clone()).ellipsis().width(Size::Fill(1.0)),
+                    ])
+                    .gap(tokens::SPACE_2)
+                    .align(Align::Center)
+                });
+                sections.push(column(rows).gap(tokens::SPACE_1));
+            }
+        }
+    }
+
+    sections.push(
+        row([
+            spacer(),
+            button("Cancel").key("modal:bundle_create:cancel").ghost(),
+            button("Check").key("bundle_create:check").ghost(),
+            create_btn,
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+    );
+    overlays_panel(MODAL_BUNDLE_CREATE_KEY, "Create bundle", [form(sections)])
+}
+
+/// Form state for the Generate Changelog modal. `from` / `to` are
+/// revspecs; `prefixes` is the comma-separated prefix list used when
+/// `by_prefix` is set. `result` is cleared on any edit, so Copy and
+/// Save always act on what the preview shows.
+#[derive(Clone, Debug)]
+pub struct ChangelogForm {
+    pub from: String,
+    pub to: String,
+    pub by_prefix: bool,
+    pub prefixes: String,
+    pub result: Option<Result<Changelog, String>>,
+    /// `result` rendered as Markdown, kept alongside it for the preview.
+    pub markdown: String,
+}
+
+/// Generate-changelog modal. Reached from a tag's context menu or the
+/// commit menu's "Create Actions" group. Groups `from..to` by
+/// conventional-commit type or by subject prefix and previews the
+/// Markdown before it's copied or saved.
+pub fn changelog_modal(state: &ChangelogForm, selection: &Selection) -> El {
+    let range_row = row([
+        form_item([
+            form_label("From"),
+            form_control(
+                text_input(&state.from, selection, "changelog:from")
+                    .key("changelog:from")
+                    .width(Size::Fill(1.0)),
+            ),
+        ])
+        .width(Size::Fill(1.0)),
+        form_item([
+            form_label("To"),
+            form_control(
+                text_input(&state.to, selection, "changelog:to")
+                    .key("changelog:to")
+                    .width(Size::Fill(1.0)),
+            ),
+        ])
+        .width(Size::Fill(1.0)),
+    ])
+    .gap(tokens::SPACE_3);
 
-    if gitlab_hosts.is_empty() {
+    let mut sections: Vec<El> = vec![
+        range_row,
+        form_item([
+            form_label("Group by"),
+            radio_group(
+                "changelog:grouping",
+                if state.by_prefix {
+                    "prefix"
+                } else {
+                    "conventional"
+                },
+                [
+                    (
+                        "conventional".to_string(),
+                        "Conventional commit type (feat, fix, ...)".to_string(),
+                    ),
+                    ("prefix".to_string(), "Subject prefixes".to_string()),
+                ],
+            ),
+        ]),
+    ];
+    if state.by_prefix {
         sections.push(form_item([
-            form_label("GitLab"),
+            form_label("Prefixes"),
+            form_control(
+                text_input(&state.prefixes, selection, "changelog:prefixes")
+                    .key("changelog:prefixes")
+                    .width(Size::Fill(1.0)),
+            ),
             form_description(
-                "GitLab hosts appear here automatically when whisper-git \
-                 sees a remote that points at one (e.g. `gitlab.com` or a \
-                 self-hosted instance). Open a repo with a GitLab remote \
-                 to register the host.",
+                "Comma-separated, in section order, e.g. `ui:, git:, [docs]`.".to_string(),
             ),
         ]));
-    } else {
-        let mut rows: Vec<El> = Vec::with_capacity(gitlab_hosts.len());
-        for (host, configured) in gitlab_hosts {
-            rows.push(gitlab_host_row(state, selection, host, *configured));
-        }
-        sections.push(form_item([
-            form_label("GitLab"),
-            form_control(column(rows).gap(tokens::SPACE_2).width(Size::Fill(1.0))),
-            form_description("One token per host. Per-host secrets go in the keychain."),
-        ]));
     }
 
-    let actions =
-        row([spacer(), button("Done").key("modal:token:close").primary()]).align(Align::Center);
-    sections.push(actions);
+    let mut copy_btn = button("Copy").key("changelog:copy").primary();
+    let mut save_btn = button("Save\u{2026}").key("changelog:save").ghost();
+    match &state.result {
+        Some(Ok(log)) => {
+            sections.push(
+                text(format!(
+                    "{} commit(s) in {} section(s) from {} contributor(s).",
+                    log.commit_count,
+                    log.sections.len(),
+                    log.contributors.len()
+                ))
+                .caption()
+                .muted(),
+            );
+            let lines = state
+                .markdown
+                .lines()
+                .map(|l| text(l.to_string()).mono().caption().ellipsis());
+            sections.push(
+                scroll([column(lines).gap(tokens::SPACE_1)])
+                    .key("changelog:preview")
+                    .height(Size::Fixed(220.0)),
+            );
+        }
+        Some(Err(e)) => {
+            copy_btn = copy_btn.disabled();
+            save_btn = save_btn.disabled();
+            sections.push(paragraph(e.clone()).text_color(tokens::DESTRUCTIVE));
+        }
+        None => {
+            copy_btn = copy_btn.disabled();
+            save_btn = save_btn.disabled();
+            sections.push(
+                text("Generate to preview the release notes.")
+                    .caption()
+                    .muted(),
+            );
+        }
+    }
 
-    let body = form(sections);
+    sections.push(
+        row([
+            spacer(),
+            button("Cancel").key("modal:changelog:cancel").ghost(),
+            button("Generate").key("changelog:generate").ghost(),
+            save_btn,
+            copy_btn,
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+    );
+    overlays_panel(MODAL_CHANGELOG_KEY, "Generate changelog", [form(sections)])
+}
 
-    overlays_panel(MODAL_TOKEN_KEY, "Manage tokens", [body])
+/// Form state for the Repository Settings modal. `values` holds one
+/// entry per [`CONFIG_FIELDS`] key as set at `scope` (empty = unset);
+/// `loaded` is the same list as read, so Save only writes what changed.
+#[derive(Clone, Debug, Default)]
+pub struct RepoConfigForm {
+    pub scope: ConfigScope,
+    pub values: Vec<String>,
+    pub loaded: Vec<String>,
+    /// What git resolves each key to across every scope.
+    pub effective: Vec<Option<String>>,
+    /// New-profile inputs.
+    pub profile_name: String,
+    pub profile_hosts: String,
+    /// Issue link patterns, one `<prefix> [<url template>]` per line.
+    /// Always the repository's own, whatever `scope` says.
+    pub issue_links: String,
+    pub issue_links_loaded: String,
 }
 
-/// Render one row of the GitLab section. Mirrors the GitHub row's
-/// edit/idle split but scopes routes by host suffix (`token:gitlab:
-/// edit:gitlab.com`, etc.) so the app can dispatch them correctly.
-fn gitlab_host_row(state: &TokenForm, selection: &Selection, host: &str, configured: bool) -> El {
-    let editing = state.gitlab_inputs.contains_key(host);
-    let host_label = text(host.to_string()).label().width(Size::Fixed(180.0));
+/// Radio value standing in for "not set at this scope".
+pub const CONFIG_UNSET: &str = "unset";
 
-    let controls: El = if editing {
-        let buf = state.gitlab_inputs.get(host).cloned().unwrap_or_default();
-        row([
-            text_input(&buf, selection, &format!("token:gitlab:input:{host}"))
-                .key(format!("token:gitlab:input:{host}"))
-                .width(Size::Fill(1.0)),
-            button("Save")
-                .key(format!("token:gitlab:save:{host}"))
-                .primary(),
-            button("Cancel")
-                .key(format!("token:gitlab:cancel:{host}"))
-                .ghost(),
+/// Repository settings modal. Reached from the header's repository
+/// menu, or opened automatically when a commit fails for lack of an
+/// identity. Edits the common keys at local or global scope, the
+/// repository's issue link patterns, and manages identity profiles.
+pub fn repo_config_modal(
+    state: &RepoConfigForm,
+    profiles: &[IdentityProfile],
+    selection: &Selection,
+) -> El {
+    let scope_field = form_item([
+        form_label("Scope"),
+        radio_group(
+            "repo_config:scope",
+            match state.scope {
+                ConfigScope::Local => "local",
+                ConfigScope::Global => "global",
+            },
+            [ConfigScope::Local, ConfigScope::Global].map(|scope| {
+                let value = match scope {
+                    ConfigScope::Local => "local",
+                    ConfigScope::Global => "global",
+                };
+                (value.to_string(), scope.label().to_string())
+            }),
+        ),
+    ]);
+
+    let mut fields: Vec<El> = Vec::new();
+    for (idx, field) in CONFIG_FIELDS.iter().enumerate() {
+        let value = state.values.get(idx).map(String::as_str).unwrap_or("");
+        let hint = match state.effective.get(idx).cloned().flatten() {
+            Some(effective) if value.is_empty() => {
+                format!("{}: unset here, resolves to {effective}", field.key)
+            }
+            Some(_) => field.key.to_string(),
+            None => format!("{}: not set anywhere", field.key),
+        };
+        let control = if field.choices.is_empty() {
+            let key = format!("repo_config:field:{}", field.key);
+            form_control(
+                text_input(value, selection, &key)
+                    .key(key)
+                    .width(Size::Fill(1.0)),
+            )
+        } else {
+            let current = if value.is_empty() {
+                CONFIG_UNSET
+            } else {
+                value
+            };
+            radio_group(
+                &format!("repo_config:choice:{}", field.key),
+                current,
+                std::iter::once((CONFIG_UNSET.to_string(), "(unset)".to_string()))
+                    .chain(field.choices.iter().map(|c| (c.to_string(), c.to_string()))),
+            )
+        };
+        fields.push(form_item([
+            form_label(field.label),
+            control,
+            form_description(hint),
+        ]));
+    }
+
+    fields.push(form_item([
+        form_label("Issue links"),
+        form_control(
+            text_area(&state.issue_links, selection, "repo_config:issue_links")
+                .key("repo_config:issue_links")
+                .width(Size::Fill(1.0))
+                .height(Size::Fixed(72.0)),
+        ),
+        form_description(
+            "One per line: a prefix, then optionally a URL using {ref} or {number}, \
+             e.g. `PROJ- https://jira.example/browse/{ref}`. A bare prefix such as `#` \
+             links to the origin forge's issues. Empty uses the forge's own. Always \
+             saved to this repository.",
+        ),
+    ]));
+
+    fields.push(text("Identity profiles").label());
+    if profiles.is_empty() {
+        fields.push(
+            text("Profiles apply automatically to repositories whose remotes match their host patterns.")
+                .caption()
+                .muted(),
+        );
+    }
+    for (idx, profile) in profiles.iter().enumerate() {
+        fields.push(
+            row([
+                column([
+                    text(profile.name.clone()),
+                    text(format!(
+                        "{} <{}> \u{00b7} {}",
+                        profile.user_name,
+                        profile.email,
+                        profile.host_patterns.join(", ")
+                    ))
+                    .caption()
+                    .muted()
+                    .ellipsis(),
+                ])
+                .width(Size::Fill(1.0)),
+                button("Apply")
+                    .key(format!("repo_config:profile_apply:{idx}"))
+                    .ghost()
+                    .tooltip("Write this identity to the repository's local config"),
+                button("Remove")
+                    .key(format!("repo_config:profile_remove:{idx}"))
+                    .ghost(),
+            ])
+            .gap(tokens::SPACE_2)
+            .align(Align::Center),
+        );
+    }
+    fields.push(
+        row([
+            text_input(&state.profile_name, selection, "repo_config:profile_name")
+                .key("repo_config:profile_name")
+                .width(Size::Fill(1.0)),
+            text_input(&state.profile_hosts, selection, "repo_config:profile_hosts")
+                .key("repo_config:profile_hosts")
+                .width(Size::Fill(2.0)),
+            button("Save as Profile")
+                .key("repo_config:profile_save")
+                .ghost()
+                .tooltip("Save the name, email and signing key above as a profile"),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+    );
+    fields.push(
+        text("Profile name, then host patterns such as `github.com/work-org, *.corp.example`.")
+            .caption()
+            .muted(),
+    );
+
+    let actions = row([
+        spacer(),
+        button("Cancel").key("modal:repo_config:cancel").ghost(),
+        button("Save").key("repo_config:save").primary(),
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center);
+
+    let body = form([
+        scope_field,
+        scroll([column(fields).gap(tokens::SPACE_3)])
+            .key("repo_config:scroll")
+            .height(Size::Fixed(420.0)),
+        actions,
+    ]);
+    overlays_panel(MODAL_REPO_CONFIG_KEY, "Repository settings", [body])
+}
+
+/// Maintenance modal: the tab's health report plus buttons for the
+/// housekeeping commands and the output of recent runs. Reached from
+/// the header's repository menu.
+pub fn maintenance_modal(state: Option<&MaintenanceState>) -> El {
+    let mut sections: Vec<El> = Vec::new();
+    let busy = state.is_some_and(|s| s.op.is_some());
+    let report_pending = state.is_some_and(|s| s.health_rx.is_some());
+
+    match state.and_then(|s| s.health.as_ref()) {
+        Some(Ok(health)) => sections.extend(health_report(health)),
+        Some(Err(e)) => sections.push(
+            paragraph(format!("Couldn't build the report: {e}")).text_color(tokens::DESTRUCTIVE),
+        ),
+        None => sections.push(text("Building report\u{2026}").caption().muted()),
+    }
+
+    let mut actions: Vec<El> = MaintenanceTask::ALL
+        .into_iter()
+        .map(|task| {
+            let mut btn = button(task.label())
+                .key(format!("maintenance:run:{}", task.key()))
+                .ghost();
+            if busy {
+                btn = btn.disabled();
+            }
+            btn
+        })
+        .collect();
+    actions.push(spacer());
+    let mut refresh = button("Refresh").key("maintenance:refresh").ghost();
+    if report_pending {
+        refresh = refresh.disabled();
+    }
+    actions.push(refresh);
+    sections.push(row(actions).gap(tokens::SPACE_2).align(Align::Center));
+
+    if let Some(op) = state.and_then(|s| s.op.as_ref()) {
+        sections.push(
+            text(format!(
+                "Running {} \u{00b7} {}s",
+                op.task.label(),
+                op.started.elapsed().as_secs()
+            ))
+            .caption()
+            .muted(),
+        );
+    }
+    let log = state.map(|s| s.log.as_slice()).unwrap_or_default();
+    if !log.is_empty() {
+        let entries = log.iter().map(|run| {
+            let status = if run.success {
+                badge("ok").success()
+            } else {
+                badge("failed").destructive()
+            };
+            let output = if run.output.is_empty() {
+                "(no output)".to_string()
+            } else {
+                run.output.clone()
+            };
+            column([
+                row([
+                    status,
+                    text(run.task.label()).mono(),
+                    text(format!("{}s", run.elapsed.as_secs()))
+                        .caption()
+                        .muted(),
+                ])
+                .gap(tokens::SPACE_2)
+                .align(Align::Center),
+                paragraph(output).mono().caption(),
+            ])
+            .gap(tokens::SPACE_1)
+        });
+        sections.push(form_item([
+            form_label("Output"),
+            scroll([column(entries).gap(tokens::SPACE_3)])
+                .key("maintenance:log")
+                .height(Size::Fixed(160.0)),
+        ]));
+    }
+
+    sections.push(
+        row([
+            spacer(),
+            button("Close").key("modal:maintenance:close").ghost(),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+    );
+    overlays_panel(MODAL_MAINTENANCE_KEY, "Maintenance", [form(sections)])
+}
+
+fn health_report(health: &RepoHealth) -> Vec<El> {
+    let stat = |label: &str, value: String| {
+        column([
+            text(value).label(),
+            text(label.to_string()).caption().muted(),
+        ])
+        .gap(tokens::SPACE_1)
+        .width(Size::Fill(1.0))
+    };
+    let mut sections = vec![
+        row([
+            stat("objects", health.object_count().to_string()),
+            stat("packs", health.packs.to_string()),
+            stat(
+                "loose objects",
+                format!(
+                    "{} ({})",
+                    health.loose_objects,
+                    format_bytes(health.loose_size)
+                ),
+            ),
+            stat("repository size", format_bytes(health.repo_size)),
+            stat("dangling", health.dangling_count.to_string()),
+        ])
+        .gap(tokens::SPACE_3),
+    ];
+    if health.garbage > 0 {
+        sections.push(
+            text(format!(
+                "{} unrecognised file(s) in the object directory",
+                health.garbage
+            ))
+            .caption()
+            .text_color(tokens::WARNING),
+        );
+    }
+
+    let blobs = health.largest_blobs.iter().map(|blob| {
+        let sha = blob.oid.to_string();
+        let origin = match &blob.introduced_by {
+            Some((oid, summary)) => format!("{} {summary}", &oid.to_string()[..7]),
+            None => "no introducing commit found".to_string(),
+        };
+        row([
+            text(format_bytes(blob.size))
+                .mono()
+                .width(Size::Fixed(80.0)),
+            column([
+                text(blob.path.clone()).mono().ellipsis(),
+                text(origin).caption().muted().ellipsis(),
+            ])
+            .width(Size::Fill(1.0)),
+            button(sha[..7].to_string())
+                .key(format!("maintenance:copy:{sha}"))
+                .ghost()
+                .tooltip("Copy blob id"),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center)
+    });
+    sections.push(form_item([
+        form_label("Largest blobs in history"),
+        scroll([column(blobs).gap(tokens::SPACE_1)])
+            .key("maintenance:blobs")
+            .height(Size::Fixed(180.0)),
+    ]));
+
+    let dangling: El = if health.dangling_count == 0 {
+        text("None").caption().muted()
+    } else {
+        let mut lines: Vec<El> = health
+            .dangling
+            .iter()
+            .map(|d| {
+                row([
+                    text(d.kind.clone())
+                        .caption()
+                        .muted()
+                        .width(Size::Fixed(56.0)),
+                    text(d.oid.to_string()).mono().caption(),
+                ])
+                .gap(tokens::SPACE_2)
+            })
+            .collect();
+        if health.dangling_count > health.dangling.len() {
+            lines.push(
+                text(format!(
+                    "\u{2026} and {} more",
+                    health.dangling_count - health.dangling.len()
+                ))
+                .caption()
+                .muted(),
+            );
+        }
+        scroll([column(lines).gap(tokens::SPACE_1)])
+            .key("maintenance:dangling")
+            .height(Size::Fixed(96.0))
+    };
+    sections.push(form_item([form_label("Dangling objects"), dangling]));
+    sections
+}
+
+/// Form state for the Import Bundle modal. `info` is read when the file
+/// is picked; `remote` names the remote the bundle is registered as.
+#[derive(Clone, Debug)]
+pub struct BundleImportForm {
+    pub path: std::path::PathBuf,
+    pub info: BundleInfo,
+    pub remote: String,
+}
+
+/// Import-bundle modal: the bundle's refs, whether this repository has
+/// its prerequisites, and the remote name to fetch it under. Fetch is
+/// disabled while prerequisites are missing since git would reject it.
+pub fn bundle_import_modal(state: &BundleImportForm, selection: &Selection) -> El {
+    let info = &state.info;
+    let mut sections: Vec<El> = vec![
+        paragraph(format!(
+            "{} (v{} bundle)",
+            state.path.display(),
+            info.version
+        ))
+        .text_color(tokens::MUTED_FOREGROUND),
+    ];
+
+    let refs = info.refs.iter().map(|r| {
+        row([
+            text(r.oid.to_string()[..7].to_string()).mono().muted(),
+            text(r.name.clone()).mono().nowrap_text(),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center)
+    });
+    sections.push(form_item([
+        form_label("Refs"),
+        scroll([column(refs).gap(tokens::SPACE_1)])
+            .key("bundle_import:refs")
+            .height(Size::Fixed(120.0)),
+    ]));
+    if !info.refs.iter().any(|r| r.name.starts_with("refs/heads/")) {
+        sections.push(
+            text("No branch refs: only refs/heads/* are fetched into the remote.")
+                .caption()
+                .text_color(tokens::WARNING),
+        );
+    }
+
+    let prereq_row = if info.prerequisites.is_empty() {
+        row([badge("self-contained").muted()])
+    } else if info.missing_prerequisites.is_empty() {
+        row([badge(format!(
+            "{} prerequisite(s) present",
+            info.prerequisites.len()
+        ))
+        .success()])
+    } else {
+        let missing: Vec<String> = info
+            .missing_prerequisites
+            .iter()
+            .map(|oid| oid.to_string()[..7].to_string())
+            .collect();
+        row([
+            badge(format!("{} prerequisite(s) missing", missing.len())).warning(),
+            text(missing.join(" ")).mono().caption().muted().ellipsis(),
+        ])
+    };
+    sections.push(prereq_row.gap(tokens::SPACE_2).align(Align::Center));
+
+    sections.push(form_item([
+        form_label("Remote name"),
+        form_control(
+            text_input(&state.remote, selection, "bundle_import:remote")
+                .key("bundle_import:remote")
+                .width(Size::Fill(1.0)),
+        ),
+        form_description(
+            "The bundle is added as a remote; fetch it again after replacing the file.".to_string(),
+        ),
+    ]));
+
+    let mut fetch_btn = button("Fetch").key("bundle_import:execute").primary();
+    if state.remote.trim().is_empty() || !info.missing_prerequisites.is_empty() {
+        fetch_btn = fetch_btn.disabled();
+    }
+    sections.push(
+        row([
+            spacer(),
+            button("Cancel").key("modal:bundle_import:cancel").ghost(),
+            fetch_btn,
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+    );
+    overlays_panel(MODAL_BUNDLE_IMPORT_KEY, "Import bundle", [form(sections)])
+}
+
+/// Form state for the Add/Edit Note modal. `ref_name` is the notes ref
+/// the note is written to — the default ref for "Add Note", or the ref
+/// of the note being edited. `message` starts with the existing note.
+#[derive(Clone, Debug)]
+pub struct NoteForm {
+    pub oid: git2::Oid,
+    pub ref_name: String,
+    pub message: String,
+    pub editing: bool,
+}
+
+/// Add/Edit Note modal. Reached from the commit context menu's "Note
+/// Actions" group and the Edit button on the commit details notes card.
+/// Saving replaces any note already on the commit under that ref.
+pub fn note_modal(state: &NoteForm, selection: &Selection) -> El {
+    let short = &state.oid.to_string()[..7];
+    let ref_field = form_item([
+        form_label("Notes ref"),
+        form_control(
+            text_input(&state.ref_name, selection, "note:ref")
+                .key("note:ref")
+                .width(Size::Fill(1.0)),
+        ),
+        form_description(format!(
+            "Push or fetch refs/notes/* to share notes on {short}."
+        )),
+    ]);
+    let message_field = form_item([
+        form_label("Note"),
+        form_control(
+            text_area(&state.message, selection, "note:message")
+                .key("note:message")
+                .width(Size::Fill(1.0))
+                .height(Size::Fixed(140.0)),
+        ),
+    ]);
+
+    let can_save = !state.ref_name.trim().is_empty() && !state.message.trim().is_empty();
+    let save = button("Save Note").key("note:save").primary();
+    let actions = row([
+        spacer(),
+        button("Cancel").key("modal:note:cancel").ghost(),
+        if can_save { save } else { save.disabled() },
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center);
+
+    let title = if state.editing {
+        "Edit note"
+    } else {
+        "Add note"
+    };
+    overlays_panel(
+        MODAL_NOTE_KEY,
+        title,
+        [form([ref_field, message_field, actions])],
+    )
+}
+
+/// Form state for the Create Pull Request modal. Title and body start
+/// from the branch's commits; `reviewers` and `labels` are the raw
+/// comma-separated inputs. `created` holds the forge's answer once the
+/// request went through, and the modal switches to showing its URL.
+#[derive(Clone, Debug)]
+pub struct CreatePullForm {
+    pub provider: CiProvider,
+    pub remote: String,
+    pub source_branch: String,
+    pub target: String,
+    pub title: String,
+    pub body: String,
+    pub draft: bool,
+    pub reviewers: String,
+    pub labels: String,
+    pub generating: bool,
+    pub submitting: bool,
+    pub error: Option<String>,
+    pub created: Option<PullRequest>,
+    pub warnings: Vec<String>,
+}
+
+/// Create-pull-request modal. Reached from the local branch context
+/// menu and the post-push banner in the staging well. "Generate" asks
+/// the configured AI provider for a title and description; "Create"
+/// posts through the GitHub or GitLab client for the remote.
+pub fn create_pull_modal(state: &CreatePullForm, selection: &Selection) -> El {
+    let noun = match state.provider {
+        CiProvider::GitHub | CiProvider::Gitea | CiProvider::Local => "pull request",
+        CiProvider::GitLab => "merge request",
+    };
+    let title = match state.provider {
+        CiProvider::GitHub | CiProvider::Gitea | CiProvider::Local => "Create pull request",
+        CiProvider::GitLab => "Create merge request",
+    };
+
+    if let Some(pull) = &state.created {
+        let mut sections: Vec<El> = vec![
+            row([
+                icon(IconName::Check)
+                    .icon_size(14.0)
+                    .text_color(tokens::SUCCESS),
+                text(format!("Opened {} {}", pull.reference(), pull.title)).ellipsis(),
+            ])
+            .gap(tokens::SPACE_2)
+            .align(Align::Center),
+            text(pull.url.clone()).mono().caption().ellipsis(),
+        ];
+        for warning in &state.warnings {
+            sections.push(text(warning.clone()).caption().text_color(tokens::WARNING));
+        }
+        sections.push(
+            row([
+                spacer(),
+                button("Copy URL").key("create_pr:copy").ghost(),
+                button("Open in Browser").key("create_pr:open").ghost(),
+                button("Done").key("modal:create_pr:cancel").primary(),
+            ])
+            .gap(tokens::SPACE_2)
+            .align(Align::Center),
+        );
+        return overlays_panel(MODAL_CREATE_PR_KEY, title, [form(sections)]);
+    }
+
+    let mut sections: Vec<El> = vec![
+        text(format!("{} \u{2192} {}", state.source_branch, state.remote))
+            .caption()
+            .muted(),
+        form_item([
+            form_label("Target branch"),
+            form_control(
+                text_input(&state.target, selection, "create_pr:target")
+                    .key("create_pr:target")
+                    .width(Size::Fill(1.0)),
+            ),
+            form_description(format!(
+                "Branch on {} the {noun} merges into.",
+                state.remote
+            )),
+        ]),
+        form_item([
+            form_label("Title"),
+            form_control(
+                text_input(&state.title, selection, "create_pr:title")
+                    .key("create_pr:title")
+                    .width(Size::Fill(1.0)),
+            ),
+        ]),
+        form_item([
+            form_label("Description"),
+            form_control(
+                text_area(&state.body, selection, "create_pr:body")
+                    .key("create_pr:body")
+                    .width(Size::Fill(1.0))
+                    .height(Size::Fixed(160.0)),
+            ),
+        ]),
+        field_row("Draft", switch(state.draft).key("create_pr:draft")),
+        form_item([
+            form_label("Reviewers"),
+            form_control(
+                text_input(&state.reviewers, selection, "create_pr:reviewers")
+                    .key("create_pr:reviewers")
+                    .width(Size::Fill(1.0)),
+            ),
+            form_description("Comma-separated usernames.".to_string()),
+        ]),
+        form_item([
+            form_label("Labels"),
+            form_control(
+                text_input(&state.labels, selection, "create_pr:labels")
+                    .key("create_pr:labels")
+                    .width(Size::Fill(1.0)),
+            ),
+            form_description("Comma-separated; labels must already exist.".to_string()),
+        ]),
+    ];
+    if let Some(e) = &state.error {
+        sections.push(paragraph(e.clone()).text_color(tokens::DESTRUCTIVE));
+    }
+
+    let busy = state.generating || state.submitting;
+    let generate = if state.generating {
+        button("Generating\u{2026}").key("create_pr:ai").ghost()
+    } else {
+        button("Generate").key("create_pr:ai").ghost()
+    };
+    let create = if state.submitting {
+        button("Creating\u{2026}").key("create_pr:submit").ghost()
+    } else {
+        button("Create").key("create_pr:submit").primary()
+    };
+    let can_create = !busy && !state.title.trim().is_empty() && !state.target.trim().is_empty();
+    sections.push(
+        row([
+            spacer(),
+            button("Cancel").key("modal:create_pr:cancel").ghost(),
+            if busy { generate.disabled() } else { generate },
+            if can_create {
+                create
+            } else {
+                create.disabled()
+            },
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+    );
+    overlays_panel(MODAL_CREATE_PR_KEY, title, [form(sections)])
+}
+
+/// Form state for the create-release modal. `from` is the start of
+/// the range "Generate" writes notes for, defaulting to the previous
+/// tag; `created` switches the modal to its result view.
+#[derive(Clone, Debug)]
+pub struct CreateReleaseForm {
+    pub provider: CiProvider,
+    pub tag: String,
+    pub target_sha: String,
+    pub from: String,
+    pub name: String,
+    pub notes: String,
+    pub draft: bool,
+    pub prerelease: bool,
+    pub submitting: bool,
+    pub error: Option<String>,
+    pub created: Option<Release>,
+}
+
+/// Create-release modal. Reached from the tag context menu and the tag
+/// card in the commit details pane. "Generate" fills the notes with the
+/// changelog since `from`; "Create" posts through the GitHub or GitLab
+/// client for `origin`.
+pub fn create_release_modal(state: &CreateReleaseForm, selection: &Selection) -> El {
+    let title = "Create release";
+    if let Some(release) = &state.created {
+        let sections: Vec<El> = vec![
+            row([
+                icon(IconName::Check)
+                    .icon_size(14.0)
+                    .text_color(tokens::SUCCESS),
+                text(format!("Released {}", release.title())).ellipsis(),
+            ])
+            .gap(tokens::SPACE_2)
+            .align(Align::Center),
+            text(release.url.clone()).mono().caption().ellipsis(),
+            row([
+                spacer(),
+                button("Open in Browser").key("create_release:open").ghost(),
+                button("Done").key("modal:create_release:cancel").primary(),
+            ])
+            .gap(tokens::SPACE_2)
+            .align(Align::Center),
+        ];
+        return overlays_panel(MODAL_CREATE_RELEASE_KEY, title, [form(sections)]);
+    }
+
+    let mut sections: Vec<El> = vec![
+        text(format!("{} \u{2192} origin", state.tag))
+            .caption()
+            .muted(),
+        form_item([
+            form_label("Title"),
+            form_control(
+                text_input(&state.name, selection, "create_release:name")
+                    .key("create_release:name")
+                    .width(Size::Fill(1.0)),
+            ),
+            form_description("Leave empty to use the tag name.".to_string()),
+        ]),
+        form_item([
+            form_label("Notes since"),
+            form_control(
+                row([
+                    text_input(&state.from, selection, "create_release:from")
+                        .key("create_release:from")
+                        .width(Size::Fill(1.0)),
+                    button("Generate").key("create_release:generate").ghost(),
+                ])
+                .gap(tokens::SPACE_2)
+                .align(Align::Center),
+            ),
+            form_description(
+                "Tag or commit the generated changelog starts after; empty for \
+                 the whole history."
+                    .to_string(),
+            ),
+        ]),
+        form_item([
+            form_label("Notes"),
+            form_control(
+                text_area(&state.notes, selection, "create_release:notes")
+                    .key("create_release:notes")
+                    .width(Size::Fill(1.0))
+                    .height(Size::Fixed(200.0)),
+            ),
+        ]),
+    ];
+    // GitLab has neither drafts nor a pre-release flag; a release is
+    // only "upcoming" when dated in the future.
+    if state.provider == CiProvider::GitHub {
+        sections.push(field_row(
+            "Draft",
+            switch(state.draft).key("create_release:draft"),
+        ));
+        sections.push(field_row(
+            "Pre-release",
+            switch(state.prerelease).key("create_release:prerelease"),
+        ));
+    }
+    if let Some(e) = &state.error {
+        sections.push(paragraph(e.clone()).text_color(tokens::DESTRUCTIVE));
+    }
+
+    let create = if state.submitting {
+        button("Creating\u{2026}")
+            .key("create_release:submit")
+            .ghost()
+            .disabled()
+    } else {
+        button("Create").key("create_release:submit").primary()
+    };
+    sections.push(
+        row([
+            spacer(),
+            button("Cancel").key("modal:create_release:cancel").ghost(),
+            create,
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+    );
+    overlays_panel(MODAL_CREATE_RELEASE_KEY, title, [form(sections)])
+}
+
+/// Form state for the review comment modal. `action` is the reply or
+/// new line comment being written; `body` is copied into it on submit.
+/// `context` names what the comment attaches to.
+#[derive(Clone, Debug)]
+pub struct ReviewCommentForm {
+    pub context: String,
+    pub action: ReviewAction,
+    pub body: String,
+}
+
+/// Review comment modal. Reached from a thread's Reply button in the
+/// diff viewer and from "Comment on Line" in a diff line's context
+/// menu.
+pub fn review_comment_modal(state: &ReviewCommentForm, selection: &Selection) -> El {
+    let title = match state.action {
+        ReviewAction::Reply { .. } => "Reply to thread",
+        _ => "Comment on line",
+    };
+    let body_field = form_item([
+        form_label("Comment"),
+        form_control(
+            text_area(&state.body, selection, "review_comment:body")
+                .key("review_comment:body")
+                .width(Size::Fill(1.0))
+                .height(Size::Fixed(140.0)),
+        ),
+        form_description(state.context.clone()),
+    ]);
+
+    let submit = button("Comment").key("review_comment:submit").primary();
+    let actions = row([
+        spacer(),
+        button("Cancel").key("modal:review_comment:cancel").ghost(),
+        if state.body.trim().is_empty() {
+            submit.disabled()
+        } else {
+            submit
+        },
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center);
+
+    overlays_panel(
+        MODAL_REVIEW_COMMENT_KEY,
+        title,
+        [form([body_field, actions])],
+    )
+}
+
+/// Form state for the Run Workflow / Run Pipeline modal. `workflows`
+/// are the checkout's dispatchable GitHub workflows with their input
+/// names; picking one fills `workflow` and seeds `inputs`, which holds
+/// `key=value` lines.
+#[derive(Clone, Debug)]
+pub struct RunWorkflowForm {
+    pub provider: CiProvider,
+    pub workflows: Vec<(String, Vec<String>)>,
+    pub workflow: String,
+    pub git_ref: String,
+    pub inputs: String,
+    pub error: Option<String>,
+}
+
+/// Manual run modal. On GitHub it dispatches a workflow that declares
+/// `workflow_dispatch`; on GitLab it runs a new pipeline for a branch
+/// with the given variables. Reached from the CI context menu.
+pub fn run_workflow_modal(state: &RunWorkflowForm, selection: &Selection) -> El {
+    let github = state.provider == CiProvider::GitHub;
+    let mut sections: Vec<El> = Vec::new();
+    if github {
+        if !state.workflows.is_empty() {
+            let picks: Vec<El> = state
+                .workflows
+                .iter()
+                .enumerate()
+                .map(|(i, (name, _))| {
+                    let b = button(name.clone()).key(format!("run_workflow:pick:{i}"));
+                    if *name == state.workflow {
+                        b.primary()
+                    } else {
+                        b.ghost()
+                    }
+                })
+                .collect();
+            sections.push(row(picks).gap(tokens::SPACE_1).align(Align::Center));
+        }
+        sections.push(form_item([
+            form_label("Workflow"),
+            form_control(
+                text_input(&state.workflow, selection, "run_workflow:workflow")
+                    .key("run_workflow:workflow")
+                    .width(Size::Fill(1.0)),
+            ),
+            form_description("File name under .github/workflows, or the workflow ID.".to_string()),
+        ]));
+    }
+    sections.extend([
+        form_item([
+            form_label(if github { "Ref" } else { "Branch or tag" }),
+            form_control(
+                text_input(&state.git_ref, selection, "run_workflow:ref")
+                    .key("run_workflow:ref")
+                    .width(Size::Fill(1.0)),
+            ),
+        ]),
+        form_item([
+            form_label(if github { "Inputs" } else { "Variables" }),
+            form_control(
+                text_area(&state.inputs, selection, "run_workflow:inputs")
+                    .key("run_workflow:inputs")
+                    .width(Size::Fill(1.0))
+                    .height(Size::Fixed(120.0)),
+            ),
+            form_description("One key=value per line.".to_string()),
+        ]),
+    ]);
+    if let Some(e) = &state.error {
+        sections.push(paragraph(e.clone()).text_color(tokens::DESTRUCTIVE));
+    }
+
+    let submit = button("Run").key("run_workflow:submit").primary();
+    let ready = !state.git_ref.trim().is_empty() && (!github || !state.workflow.trim().is_empty());
+    sections.push(
+        row([
+            spacer(),
+            button("Cancel").key("modal:run_workflow:cancel").ghost(),
+            if ready { submit } else { submit.disabled() },
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+    );
+    let title = if github {
+        "Run workflow"
+    } else {
+        "Run pipeline"
+    };
+    overlays_panel(MODAL_RUN_WORKFLOW_KEY, title, [form(sections)])
+}
+
+/// State for the token modal. Rows are keyed `"{backend}:{host}"`
+/// (`"gitlab:gitlab.com"`), matching the `token:{backend}:…:{host}`
+/// routes, so every forge's block shares one shape and we only carry
+/// input state for rows the user is actively editing.
+#[derive(Clone, Debug, Default)]
+pub struct TokenForm {
+    /// Input buffers for rows in editing mode. A row being a key here
+    /// means it shows the text input + Save / Cancel; absence means it
+    /// shows status + Set/Replace/Clear buttons.
+    pub inputs: std::collections::HashMap<String, String>,
+    /// Hostname typed into a block's "add host" field, keyed by backend
+    /// id. Only forges whose self-hosted instances can't be recognised
+    /// from a URL offer the field.
+    pub new_hosts: std::collections::HashMap<String, String>,
+}
+
+/// One forge's block in the token modal, built from its
+/// [`crate::forge::ForgeBackend`].
+pub struct TokenSection {
+    /// Backend id, the route segment.
+    pub id: &'static str,
+    pub label: &'static str,
+    pub info: TokenInfo,
+    /// `(host, configured)` for each row; the configured flag comes
+    /// from a keychain lookup.
+    pub hosts: Vec<(String, bool)>,
+}
+
+/// Token management modal. One block per forge backend that takes
+/// tokens, one row per host (GitHub has `github.com` plus any Enterprise
+/// hosts added by hand; GitLab hosts are auto-registered on CI fetch). All secrets live in
+/// the system keychain via `token_store`; this modal reads/writes
+/// through `token:*` routes that the app handles.
+pub fn token_modal(state: &TokenForm, selection: &Selection, sections: &[TokenSection]) -> El {
+    let mut blocks: Vec<El> = Vec::with_capacity(sections.len() + 1);
+    for section in sections {
+        if section.hosts.is_empty() && !section.info.manual_hosts {
+            blocks.push(form_item([
+                form_label(section.label),
+                form_description(section.info.empty_help),
+            ]));
+            continue;
+        }
+        let mut rows: Vec<El> = Vec::with_capacity(section.hosts.len() + 1);
+        for (host, configured) in &section.hosts {
+            rows.push(host_token_row(
+                state,
+                selection,
+                section.id,
+                host,
+                *configured,
+            ));
+        }
+        if section.info.manual_hosts {
+            let route = format!("token:{}:new_host", section.id);
+            let buf = state.new_hosts.get(section.id).cloned().unwrap_or_default();
+            rows.push(
+                row([
+                    text_input(&buf, selection, &route)
+                        .key(route.clone())
+                        .width(Size::Fill(1.0)),
+                    button("Add host")
+                        .key(format!("token:{}:add", section.id))
+                        .ghost(),
+                ])
+                .gap(tokens::SPACE_2)
+                .align(Align::Center)
+                .width(Size::Fill(1.0)),
+            );
+        }
+        blocks.push(form_item([
+            form_label(section.label),
+            form_control(column(rows).gap(tokens::SPACE_2).width(Size::Fill(1.0))),
+            form_description(section.info.help),
+        ]));
+    }
+
+    let actions =
+        row([spacer(), button("Done").key("modal:token:close").primary()]).align(Align::Center);
+    blocks.push(actions);
+
+    let body = form(blocks);
+
+    overlays_panel(MODAL_TOKEN_KEY, "Manage tokens", [body])
+}
+
+/// Render one host row of a forge's block. Routes are scoped by
+/// backend and host suffix (`token:gitlab:edit:gitlab.com`, etc.) so
+/// the app can dispatch them correctly.
+fn host_token_row(
+    state: &TokenForm,
+    selection: &Selection,
+    forge: &str,
+    host: &str,
+    configured: bool,
+) -> El {
+    let input = state.inputs.get(&format!("{forge}:{host}"));
+    let host_label = text(host.to_string()).label().width(Size::Fixed(180.0));
+
+    let controls: El = if let Some(buf) = input {
+        row([
+            text_input(buf, selection, &format!("token:{forge}:input:{host}"))
+                .key(format!("token:{forge}:input:{host}"))
+                .width(Size::Fill(1.0)),
+            button("Save")
+                .key(format!("token:{forge}:save:{host}"))
+                .primary(),
+            button("Cancel")
+                .key(format!("token:{forge}:cancel:{host}"))
+                .ghost(),
         ])
         .gap(tokens::SPACE_2)
         .align(Align::Center)
@@ -900,13 +2464,13 @@ fn gitlab_host_row(state: &TokenForm, selection: &Selection, host: &str, configu
             } else {
                 "Set\u{2026}"
             })
-            .key(format!("token:gitlab:edit:{host}"))
+            .key(format!("token:{forge}:edit:{host}"))
             .ghost(),
         ];
         if configured {
             children.push(
                 button("Clear")
-                    .key(format!("token:gitlab:clear:{host}"))
+                    .key(format!("token:{forge}:clear:{host}"))
                     .destructive(),
             );
         }
@@ -921,3 +2485,90 @@ fn gitlab_host_row(state: &TokenForm, selection: &Selection, host: &str, configu
         .align(Align::Center)
         .width(Size::Fill(1.0))
 }
+
+/// Form state for the Ignore modal. `path` is the untracked file the
+/// staging-well menu was opened on; `pattern` starts from the menu
+/// choice (file / extension / folder) and stays editable. `preview` is
+/// the untracked files the current pattern would hide, recomputed on
+/// every edit — `Err` carries libgit2's complaint about a bad pattern.
+#[derive(Clone, Debug)]
+pub struct IgnoreForm {
+    pub path: String,
+    pub pattern: String,
+    pub destination: IgnoreDestination,
+    pub preview: Result<Vec<String>, String>,
+}
+
+/// Add-ignore-rule modal. Reached from an untracked file's context menu
+/// in the staging well. Shows the pattern, where it will be written,
+/// and every currently untracked file it would hide before anything is
+/// written.
+pub fn ignore_modal(state: &IgnoreForm, selection: &Selection) -> El {
+    let pattern_field = form_item([
+        form_label("Pattern"),
+        form_control(
+            text_input(&state.pattern, selection, "ignore:pattern")
+                .key("ignore:pattern")
+                .width(Size::Fill(1.0)),
+        ),
+        form_description(format!("Suggested from {}.", state.path)),
+    ]);
+
+    let destination = match state.destination {
+        IgnoreDestination::Gitignore => "gitignore",
+        IgnoreDestination::InfoExclude => "exclude",
+    };
+    let destination_field = form_item([
+        form_label("Write to"),
+        radio_group(
+            "ignore:destination",
+            destination,
+            [
+                (
+                    "gitignore".to_string(),
+                    ".gitignore (shared, committed)".to_string(),
+                ),
+                (
+                    "exclude".to_string(),
+                    ".git/info/exclude (this clone only)".to_string(),
+                ),
+            ],
+        ),
+    ]);
+
+    let preview: El = match &state.preview {
+        Ok(paths) if paths.is_empty() => text("No untracked files match this pattern.")
+            .caption()
+            .text_color(tokens::WARNING),
+        Ok(paths) => {
+            let rows = paths
+                .iter()
+                .map(|p| text(p.clone()).mono().caption().ellipsis());
+            column([
+                text(format!("Hides {} untracked file(s):", paths.len()))
+                    .caption()
+                    .muted(),
+                scroll([column(rows).gap(tokens::SPACE_1)])
+                    .key("ignore:preview")
+                    .height(Size::Fixed(120.0)),
+            ])
+            .gap(tokens::SPACE_1)
+        }
+        Err(e) => text(e.clone()).caption().text_color(tokens::DESTRUCTIVE),
+    };
+
+    let mut apply_btn = button("Add Rule").key("ignore:apply").primary();
+    if state.pattern.trim().is_empty() || state.preview.is_err() {
+        apply_btn = apply_btn.disabled();
+    }
+    let actions = row([
+        spacer(),
+        button("Cancel").key("modal:ignore:cancel").ghost(),
+        apply_btn,
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center);
+
+    let body = form([pattern_field, destination_field, preview, actions]);
+    overlays_panel(MODAL_IGNORE_KEY, "Ignore files", [body])
+}
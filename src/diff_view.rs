@@ -2,17 +2,26 @@
 //! [`crate::widgets::diff`] widget. Picks working-tree vs commit-diff
 //! source off `tab.selected_commit`; converts libgit2's
 //! `git::DiffHunk` / `git::DiffLine` into the widget's pure
-//! data types; routes per-hunk Stage / Unstage keys.
+//! data types; routes per-hunk Stage / Unstage keys and, for commit
+//! diffs, the file-level `restore_file:*` header buttons. Review
+//! threads from the checked-out branch's pull request are attached to
+//! the file, anchored to their lines when the diff shows the pull
+//! request's head.
 
 use aetna_core::{El, prelude::*};
 
 use crate::git::{self, FileStatus};
+use crate::pulls::{ReviewSide, ReviewThread};
 use crate::repo_tab::{RepoTab, WorktreeView};
 use crate::widgets::diff::{
-    DiffData, DiffHunk, DiffHunkAction, DiffLine, DiffLineKind, DiffMode, diff,
+    DiffComment, DiffData, DiffHunk, DiffHunkAction, DiffLine, DiffLineKind, DiffMode, DiffSide,
+    DiffThread, diff,
 };
 
 pub const DIFF_MODE_TOGGLE_KEY: &str = "diff:mode_toggle";
+/// Prefix for line keys when lines can take a new review comment; the
+/// app parses `review:line:{old}:{new}` on right-click.
+pub const REVIEW_LINE_KEY_PREFIX: &str = "review:line";
 
 pub fn diff_view(tab: &RepoTab, mode: DiffMode) -> El {
     let Some(view) = tab.active_view() else {
@@ -29,9 +38,89 @@ pub fn diff_view(tab: &RepoTab, mode: DiffMode) -> El {
     };
     data.mode = mode;
     data.mode_toggle_key = Some(DIFF_MODE_TOGGLE_KEY.to_string());
+    let anchorable = shows_pull_request_head(tab);
+    data.threads = review_threads(&tab.review_threads, path, anchorable);
+    if anchorable {
+        data.line_key_prefix = Some(REVIEW_LINE_KEY_PREFIX.to_string());
+    }
     diff(&data)
 }
 
+/// Whether the diff on screen is the checked-out pull request's head
+/// commit. Only then do the forge's line numbers match the diff's;
+/// working-tree diffs drift with every local edit.
+fn shows_pull_request_head(tab: &RepoTab) -> bool {
+    match (tab.selected_commit, tab.current_pull_request()) {
+        (Some(oid), Some((_, pr))) => oid.to_string() == pr.head_sha,
+        _ => false,
+    }
+}
+
+/// Widget threads for `path`. Index `i` in the routed keys is the
+/// thread's position in `tab.review_threads`. Threads on old-side
+/// lines stay unanchored: their numbers refer to the pull request's
+/// base, not to the parent of the diff shown.
+fn review_threads(threads: &[ReviewThread], path: &str, anchorable: bool) -> Vec<DiffThread> {
+    threads
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.path == path)
+        .map(|(i, t)| {
+            let anchor = match (anchorable, t.side, t.line) {
+                (true, ReviewSide::New, Some(line)) => Some((DiffSide::New, line)),
+                _ => None,
+            };
+            let mut badges = Vec::new();
+            if anchor.is_none() {
+                match (t.side, t.line) {
+                    (ReviewSide::New, Some(line)) => badges.push(format!("line {line}")),
+                    (ReviewSide::Old, Some(line)) => badges.push(format!("Deleted line {line}")),
+                    (_, None) => {}
+                }
+            }
+            if t.outdated {
+                badges.push("Outdated".to_string());
+            }
+            if t.resolved {
+                badges.push("Resolved".to_string());
+            }
+            let comments = t
+                .comments
+                .iter()
+                .map(|c| DiffComment {
+                    author: c.author.clone(),
+                    meta: c.created_at.get(..10).unwrap_or(&c.created_at).to_string(),
+                    body: c.body.clone(),
+                })
+                .collect();
+            let (resolve_label, resolve_key) = if t.resolved {
+                ("Unresolve", format!("review:unresolve:{i}"))
+            } else {
+                ("Resolve", format!("review:resolve:{i}"))
+            };
+            DiffThread {
+                anchor,
+                badges,
+                comments,
+                actions: vec![
+                    DiffHunkAction {
+                        label: "Reply".to_string(),
+                        key: format!("review:reply:{i}"),
+                        tooltip: None,
+                        destructive: false,
+                    },
+                    DiffHunkAction {
+                        label: resolve_label.to_string(),
+                        key: resolve_key,
+                        tooltip: None,
+                        destructive: false,
+                    },
+                ],
+            }
+        })
+        .collect()
+}
+
 fn working_diff(view: &WorktreeView, path: &str) -> DiffData {
     let staged = file_is_staged(view, path);
     let hunks = view
@@ -50,6 +139,9 @@ fn working_diff(view: &WorktreeView, path: &str) -> DiffData {
         hunks: widget_hunks,
         mode: DiffMode::Unified,
         mode_toggle_key: None,
+        header_actions: Vec::new(),
+        threads: Vec::new(),
+        line_key_prefix: None,
     }
 }
 
@@ -65,13 +157,43 @@ fn commit_diff(view: &WorktreeView, oid: git2::Oid, path: &str) -> DiffData {
     let short = oid.to_string()[..7].to_string();
     DiffData {
         title: path.to_string(),
-        badge: Some(short),
+        badge: Some(short.clone()),
         hunks: widget_hunks,
         mode: DiffMode::Unified,
         mode_toggle_key: None,
+        header_actions: restore_actions(&short),
+        threads: Vec::new(),
+        line_key_prefix: None,
     }
 }
 
+/// Restore / save buttons for a file viewed at a commit. The app
+/// resolves the commit and path from the tab's selection.
+fn restore_actions(short: &str) -> Vec<DiffHunkAction> {
+    vec![
+        DiffHunkAction {
+            label: "Restore".into(),
+            key: "restore_file:commit".into(),
+            tooltip: Some(format!("Restore the working copy to {short}'s version")),
+            destructive: false,
+        },
+        DiffHunkAction {
+            label: "Restore parent".into(),
+            key: "restore_file:parent".into(),
+            tooltip: Some(format!(
+                "Restore the working copy to the version before {short}"
+            )),
+            destructive: false,
+        },
+        DiffHunkAction {
+            label: "Save as\u{2026}".into(),
+            key: "restore_file:save".into(),
+            tooltip: Some(format!("Write the file as of {short} to disk")),
+            destructive: false,
+        },
+    ]
+}
+
 fn convert_hunk(hunk: git::DiffHunk, actions: Vec<DiffHunkAction>) -> DiffHunk {
     let lines: Vec<DiffLine> = hunk
         .lines
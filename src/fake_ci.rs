@@ -0,0 +1,292 @@
+//! Local fake forge backend: CI statuses read from a JSON file.
+//!
+//! Set `WHISPER_FAKE_CI=/path/to/ci.json` and every remote reports the
+//! file's statuses instead of talking to a forge, so the commit dots,
+//! header indicator, log viewer and CI menu can be worked on offline.
+//! The file is re-read on every poll (every couple of seconds), so
+//! editing it drives the UI live:
+//!
+//! ```json
+//! {
+//!   "head": "<sha of the branch tip, for the header indicator>",
+//!   "commits": {
+//!     "<sha>": [
+//!       { "label": "build", "state": "failure", "run_id": 1, "required": true,
+//!         "jobs": [{ "id": 10, "name": "test", "state": "failure",
+//!                    "log": "##[group]Run tests\nerror: boom\n##[endgroup]" }] }
+//!     ]
+//!   }
+//! }
+//! ```
+//!
+//! States are `success`, `failure` or `pending`. CI actions are
+//! accepted and do nothing.
+
+use crate::ci::{
+    CiAction, CiCheckStatus, CiCommitRollup, CiCounts, CiJob, CiProvider, CiState, CiStatus,
+    ProviderCiResult, status_for_head,
+};
+use crate::ci_log::{ParsedLog, parse_log};
+use crate::forge::ForgeBackend;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+use winit::event_loop::EventLoopProxy;
+
+/// Environment variable naming the statuses file.
+const ENV_VAR: &str = "WHISPER_FAKE_CI";
+
+#[derive(Debug, Deserialize, Default)]
+struct FakeFile {
+    #[serde(default)]
+    head: Option<String>,
+    #[serde(default)]
+    commits: HashMap<String, Vec<FakeCheck>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FakeCheck {
+    label: String,
+    state: String,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    run_id: Option<u64>,
+    #[serde(default)]
+    required: bool,
+    #[serde(default)]
+    jobs: Vec<FakeJob>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FakeJob {
+    id: u64,
+    name: String,
+    #[serde(default)]
+    stage: Option<String>,
+    state: String,
+    #[serde(default)]
+    log: String,
+}
+
+fn state(s: &str) -> CiState {
+    match s {
+        "success" => CiState::Success,
+        "failure" => CiState::Failure,
+        "pending" => CiState::Pending,
+        _ => CiState::None,
+    }
+}
+
+fn statuses_path() -> Option<PathBuf> {
+    std::env::var_os(ENV_VAR).map(PathBuf::from)
+}
+
+fn load(path: &Path) -> Result<FakeFile> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn ci_result(file: &FakeFile) -> ProviderCiResult {
+    let per_commit_rollups: HashMap<String, CiCommitRollup> = file
+        .commits
+        .iter()
+        .map(|(sha, checks)| {
+            let checks: Vec<CiCheckStatus> = checks
+                .iter()
+                .map(|c| CiCheckStatus {
+                    label: c.label.clone(),
+                    state: state(&c.state),
+                    url: c.url.clone(),
+                    run_id: c.run_id,
+                    required: c.required,
+                })
+                .collect();
+            let counts = CiCounts::from_states(checks.iter().map(|c| c.state));
+            (sha.clone(), CiCommitRollup { counts, checks })
+        })
+        .collect();
+    let head = file
+        .head
+        .as_deref()
+        .and_then(|sha| per_commit_rollups.get(sha));
+    ProviderCiResult {
+        provider: CiProvider::Local,
+        host: String::new(),
+        status: status_for_head(head),
+        per_commit_rollups,
+        stale: false,
+    }
+}
+
+fn jobs_for_run(file: &FakeFile, run_id: u64) -> Vec<CiJob> {
+    file.commits
+        .values()
+        .flatten()
+        .filter(|c| c.run_id == Some(run_id))
+        .flat_map(|c| &c.jobs)
+        .map(|j| CiJob {
+            id: j.id,
+            name: j.name.clone(),
+            stage: j.stage.clone(),
+            state: state(&j.state),
+            url: None,
+        })
+        .collect()
+}
+
+fn job_log(file: &FakeFile, job_id: u64) -> Option<&str> {
+    file.commits
+        .values()
+        .flatten()
+        .flat_map(|c| &c.jobs)
+        .find(|j| j.id == job_id)
+        .map(|j| j.log.as_str())
+}
+
+/// Run `work` against the statuses file on a worker thread.
+fn spawn<T: Send + 'static>(
+    proxy: EventLoopProxy<()>,
+    work: impl FnOnce(Result<FakeFile>) -> T + Send + 'static,
+) -> Option<Receiver<T>> {
+    let path = statuses_path()?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(work(load(&path)));
+        let _ = proxy.send_event(());
+    });
+    Some(rx)
+}
+
+pub struct FakeBackend;
+
+impl ForgeBackend for FakeBackend {
+    fn provider(&self) -> CiProvider {
+        CiProvider::Local
+    }
+
+    fn id(&self) -> &'static str {
+        "local"
+    }
+
+    fn label(&self) -> &'static str {
+        "Local CI"
+    }
+
+    fn detect(&self, _url: &str) -> Option<String> {
+        statuses_path().map(|_| "local".to_string())
+    }
+
+    fn requires_token(&self) -> bool {
+        false
+    }
+
+    fn poll_interval(&self, _busy: bool) -> Duration {
+        Duration::from_secs(2)
+    }
+
+    fn fetch_ci_status(
+        &self,
+        _token: Option<&str>,
+        _url: &str,
+        _pulls: &[crate::pulls::PullRequest],
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<ProviderCiResult>> {
+        spawn(proxy, |file| match file {
+            Ok(file) => ci_result(&file),
+            Err(e) => ProviderCiResult {
+                provider: CiProvider::Local,
+                host: String::new(),
+                status: CiStatus {
+                    state: CiState::None,
+                    summary: format!("Fake CI: {e:#}"),
+                    url: None,
+                    counts: None,
+                },
+                per_commit_rollups: HashMap::new(),
+                stale: false,
+            },
+        })
+    }
+
+    fn fetch_ci_jobs(
+        &self,
+        _token: Option<&str>,
+        _url: &str,
+        run_id: u64,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Vec<CiJob>, String>>> {
+        spawn(proxy, move |file| {
+            file.map(|f| jobs_for_run(&f, run_id))
+                .map_err(|e| format!("{e:#}"))
+        })
+    }
+
+    fn fetch_job_log(
+        &self,
+        _token: Option<&str>,
+        _url: &str,
+        job_id: u64,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<ParsedLog, String>>> {
+        spawn(proxy, move |file| {
+            let file = file.map_err(|e| format!("{e:#}"))?;
+            job_log(&file, job_id)
+                .map(parse_log)
+                .ok_or_else(|| format!("No job {job_id} in the fake CI file"))
+        })
+    }
+
+    fn supports_ci_actions(&self) -> bool {
+        true
+    }
+
+    fn ci_action(
+        &self,
+        _token: Option<&str>,
+        _url: &str,
+        _action: CiAction,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<(), String>>> {
+        spawn(proxy, |file| file.map(|_| ()).map_err(|e| format!("{e:#}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statuses_file_maps_to_rollups_and_jobs() {
+        let file: FakeFile = serde_json::from_str(
+            r#"{
+                "head": "aaa",
+                "commits": {
+                    "aaa": [
+                        {"label": "build", "state": "success", "run_id": 1},
+                        {"label": "test", "state": "failure", "run_id": 2,
+                         "jobs": [{"id": 20, "name": "unit", "state": "failure",
+                                   "log": "running\nerror: boom"}]}
+                    ],
+                    "bbb": [{"label": "build", "state": "pending"}]
+                }
+            }"#,
+        )
+        .unwrap();
+        let result = ci_result(&file);
+        assert_eq!(result.provider, CiProvider::Local);
+        assert_eq!(result.status.state, CiState::Failure);
+        assert_eq!(result.per_commit_rollups["aaa"].counts.total(), 2);
+        assert_eq!(result.per_commit_rollups["bbb"].counts.pending, 1);
+
+        let jobs = jobs_for_run(&file, 2);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!((jobs[0].id, jobs[0].state), (20, CiState::Failure));
+        assert!(jobs_for_run(&file, 1).is_empty());
+        assert_eq!(job_log(&file, 20), Some("running\nerror: boom"));
+    }
+}
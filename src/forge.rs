@@ -0,0 +1,900 @@
+//! Forge backends behind one trait.
+//!
+//! Each hosting provider (GitHub, GitLab, Gitea / Forgejo, plus the
+//! local fake in `fake_ci`) implements [`ForgeBackend`]: recognising its
+//! remotes, looking up tokens, fetching CI statuses and, where the forge
+//! supports them, pull request, release and CI write operations. The app walks
+//! [`backends`] instead of matching on [`CiProvider`], so adding a forge
+//! means adding an impl and a registry entry.
+
+use crate::ci::{CiAction, CiJob, CiProvider, ProviderCiResult};
+use crate::ci_log::ParsedLog;
+use crate::config::Config;
+use crate::git::ChangelogLinks;
+use crate::issues::Issue;
+use crate::pulls::{
+    CreatedPullRequest, NewPullRequest, ProviderPullResult, PullRequest, ReviewAction, ReviewThread,
+};
+use crate::releases::{NewRelease, Release};
+use crate::{fake_ci, gitea, github, gitlab, token_store};
+use std::path::Path;
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+use winit::event_loop::EventLoopProxy;
+
+/// How a backend's block in the token modal reads.
+pub struct TokenInfo {
+    /// Shown under the host rows.
+    pub help: &'static str,
+    /// Shown instead of the rows while no host is registered.
+    pub empty_help: &'static str,
+    /// Whether the block offers an "add host" field, for forges whose
+    /// self-hosted instances can't be recognised from a URL.
+    pub manual_hosts: bool,
+}
+
+/// One hosting provider. Everything past detection and tokens is
+/// optional; the defaults report the operation as unsupported by
+/// returning `None`.
+pub trait ForgeBackend: Send + Sync {
+    fn provider(&self) -> CiProvider;
+
+    /// Route and keychain segment, e.g. "gitlab".
+    fn id(&self) -> &'static str;
+
+    /// Display name, e.g. "GitLab".
+    fn label(&self) -> &'static str;
+
+    /// The host `url` points at when it's one of this forge's remotes.
+    /// Hosts key tokens and de-duplicate fetches across remotes.
+    fn detect(&self, url: &str) -> Option<String>;
+
+    /// Remember a host seen on a remote so the token modal lists it.
+    /// Returns `true` when `config` changed.
+    fn register_host(&self, _config: &mut Config, _host: &str) -> bool {
+        false
+    }
+
+    /// Hosts the token modal shows a row for.
+    fn token_hosts(&self, _config: &Config) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Add a host typed into the token modal. Returns `true` when
+    /// `config` changed.
+    fn add_token_host(&self, _config: &mut Config, _host: &str) -> bool {
+        false
+    }
+
+    /// Token modal block, or `None` for backends without tokens.
+    fn token_info(&self) -> Option<TokenInfo> {
+        None
+    }
+
+    fn token(&self, _host: &str) -> Option<String> {
+        None
+    }
+
+    fn set_token(&self, _host: &str, _token: &str) -> bool {
+        false
+    }
+
+    fn delete_token(&self, _host: &str) -> bool {
+        false
+    }
+
+    /// Whether CI fetches need a token. Forges that serve public
+    /// repositories anonymously fetch either way.
+    fn requires_token(&self) -> bool {
+        true
+    }
+
+    /// Delay between CI polls; `busy` while runs are pending or were
+    /// just pushed or acted on.
+    fn poll_interval(&self, busy: bool) -> Duration {
+        Duration::from_secs(if busy { 15 } else { 300 })
+    }
+
+    /// Rate-limited requests one CI poll makes, for pacing polls
+    /// against the remaining budget.
+    fn requests_per_poll(&self) -> u32 {
+        1
+    }
+
+    /// Host serving the API for forge `host`; rate limits are tracked
+    /// per API host.
+    fn api_host(&self, host: &str) -> String {
+        host.to_string()
+    }
+
+    /// Poll CI for the repo at `url`. `pulls` are the open pull
+    /// requests last listed for the same host, for forges whose
+    /// required checks depend on a request's base.
+    fn fetch_ci_status(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        pulls: &[PullRequest],
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<ProviderCiResult>>;
+
+    fn fetch_ci_jobs(
+        &self,
+        _token: Option<&str>,
+        _url: &str,
+        _run_id: u64,
+        _proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Vec<CiJob>, String>>> {
+        None
+    }
+
+    fn fetch_job_log(
+        &self,
+        _token: Option<&str>,
+        _url: &str,
+        _job_id: u64,
+        _proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<ParsedLog, String>>> {
+        None
+    }
+
+    /// Whether the CI menu offers re-run, cancel and run actions.
+    fn supports_ci_actions(&self) -> bool {
+        false
+    }
+
+    fn ci_action(
+        &self,
+        _token: Option<&str>,
+        _url: &str,
+        _action: CiAction,
+        _proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<(), String>>> {
+        None
+    }
+
+    /// Workflows the Run Workflow modal can dispatch, with their input
+    /// names. Empty for forges that run a whole pipeline on a ref.
+    fn dispatchable_workflows(&self, _workdir: &Path) -> Vec<(String, Vec<String>)> {
+        Vec::new()
+    }
+
+    /// Whether pull requests are listed, created and reviewed here.
+    fn supports_pull_requests(&self) -> bool {
+        false
+    }
+
+    fn fetch_pull_requests(
+        &self,
+        _token: &str,
+        _url: &str,
+        _remote: String,
+        _proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<ProviderPullResult>> {
+        None
+    }
+
+    fn create_pull_request(
+        &self,
+        _token: &str,
+        _url: &str,
+        _request: NewPullRequest,
+        _proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<CreatedPullRequest, String>>> {
+        None
+    }
+
+    fn fetch_review_threads(
+        &self,
+        _token: &str,
+        _url: &str,
+        _number: u64,
+        _head_sha: String,
+        _proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Vec<ReviewThread>, String>>> {
+        None
+    }
+
+    fn review_action(
+        &self,
+        _token: &str,
+        _url: &str,
+        _number: u64,
+        _action: ReviewAction,
+        _proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<(), String>>> {
+        None
+    }
+
+    /// Reference prefixes linked to this forge's issues when a
+    /// repository configures no patterns of its own.
+    fn issue_prefixes(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Web page of issue `number` in the repository behind `url`.
+    fn issue_url(&self, _url: &str, _number: u64) -> Option<String> {
+        None
+    }
+
+    /// Link bases for commits and references in changelogs generated
+    /// for the repository behind `url`.
+    fn changelog_links(&self, _url: &str) -> Option<ChangelogLinks> {
+        None
+    }
+
+    fn fetch_issue(
+        &self,
+        _token: Option<&str>,
+        _url: &str,
+        _number: u64,
+        _proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Issue, String>>> {
+        None
+    }
+
+    /// Whether releases are listed for tags and can be created here.
+    fn supports_releases(&self) -> bool {
+        false
+    }
+
+    fn fetch_releases(
+        &self,
+        _token: &str,
+        _url: &str,
+        _proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Vec<Release>, String>>> {
+        None
+    }
+
+    fn create_release(
+        &self,
+        _token: &str,
+        _url: &str,
+        _request: NewRelease,
+        _proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Release, String>>> {
+        None
+    }
+}
+
+/// Every backend, in detection order. The fake comes first so that,
+/// when enabled, it shadows the real forges instead of racing them.
+static BACKENDS: [&dyn ForgeBackend; 4] = [
+    &fake_ci::FakeBackend,
+    &GitHubBackend,
+    &GitLabBackend,
+    &GiteaBackend,
+];
+
+pub fn backends() -> &'static [&'static dyn ForgeBackend] {
+    &BACKENDS
+}
+
+/// The backend behind `provider`'s results.
+pub fn backend(provider: CiProvider) -> &'static dyn ForgeBackend {
+    BACKENDS
+        .iter()
+        .copied()
+        .find(|b| b.provider() == provider)
+        .expect("every CiProvider has a backend")
+}
+
+/// The first backend that claims `url`, with the host it matched.
+pub fn detect(url: &str) -> Option<(&'static dyn ForgeBackend, String)> {
+    BACKENDS
+        .iter()
+        .find_map(|b| b.detect(url).map(|host| (*b, host)))
+}
+
+/// Backend looked up by its [`ForgeBackend::id`], for token routes.
+pub fn by_id(id: &str) -> Option<&'static dyn ForgeBackend> {
+    BACKENDS.iter().copied().find(|b| b.id() == id)
+}
+
+/// Host part of an API base URL ("https://gitlab.com" -> "gitlab.com").
+fn api_host(api_base: &str) -> &str {
+    api_base
+        .strip_prefix("https://")
+        .or_else(|| api_base.strip_prefix("http://"))
+        .unwrap_or(api_base)
+}
+
+/// Repository page of a GitHub remote.
+fn github_web_url(url: &str) -> Option<String> {
+    let remote = github::parse_github_remote(url)?;
+    Some(format!(
+        "https://{}/{}/{}",
+        remote.host, remote.owner, remote.repo
+    ))
+}
+
+/// Project page of a GitLab remote.
+fn gitlab_web_url(url: &str) -> Option<String> {
+    let remote = gitlab::parse_gitlab_remote(url)?;
+    Some(format!("{}/{}", remote.api_base, remote.project_path))
+}
+
+/// Reduce what a user typed into the "add host" field to a bare host
+/// ("https://git.corp.example/" -> "git.corp.example").
+fn normalize_host(input: &str) -> String {
+    let trimmed = input.trim();
+    let rest = trimmed.split_once("://").map_or(trimmed, |(_, rest)| rest);
+    let rest = rest.rsplit_once('@').map_or(rest, |(_, host)| host);
+    rest.split(['/', ':'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+pub struct GitHubBackend;
+
+impl ForgeBackend for GitHubBackend {
+    fn provider(&self) -> CiProvider {
+        CiProvider::GitHub
+    }
+
+    fn id(&self) -> &'static str {
+        "github"
+    }
+
+    fn label(&self) -> &'static str {
+        "GitHub"
+    }
+
+    fn detect(&self, url: &str) -> Option<String> {
+        github::parse_github_remote(url).map(|remote| remote.host)
+    }
+
+    fn token_hosts(&self, config: &Config) -> Vec<String> {
+        std::iter::once("github.com".to_string())
+            .chain(config.github_hosts.iter().cloned())
+            .collect()
+    }
+
+    fn add_token_host(&self, config: &mut Config, host: &str) -> bool {
+        let host = normalize_host(host);
+        !host.is_empty() && config.register_github_host(&host)
+    }
+
+    fn token_info(&self) -> Option<TokenInfo> {
+        Some(TokenInfo {
+            help: "Add GitHub Enterprise Server instances by hostname; their API \
+                   is reached at `https://<host>/api/v3`. Stored in the system \
+                   keychain via the `keyring` crate.",
+            empty_help: "",
+            manual_hosts: true,
+        })
+    }
+
+    fn token(&self, host: &str) -> Option<String> {
+        if host == "github.com" {
+            token_store::get_github_token()
+        } else {
+            token_store::get_github_host_token(host)
+        }
+    }
+
+    fn set_token(&self, host: &str, token: &str) -> bool {
+        if host == "github.com" {
+            token_store::set_github_token(token)
+        } else {
+            token_store::set_github_host_token(host, token)
+        }
+    }
+
+    fn delete_token(&self, host: &str) -> bool {
+        if host == "github.com" {
+            token_store::delete_github_token()
+        } else {
+            token_store::delete_github_host_token(host)
+        }
+    }
+
+    fn requests_per_poll(&self) -> u32 {
+        github::CI_POLL_REQUESTS
+    }
+
+    /// github.com serves its API from a subdomain; Enterprise Server
+    /// serves it under `/api/v3` on the same host.
+    fn api_host(&self, host: &str) -> String {
+        if host == "github.com" {
+            "api.github.com".to_string()
+        } else {
+            host.to_string()
+        }
+    }
+
+    fn fetch_ci_status(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        pulls: &[PullRequest],
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<ProviderCiResult>> {
+        github::fetch_ci_status_async(token?, url, pulls.to_vec(), proxy)
+    }
+
+    fn fetch_ci_jobs(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        run_id: u64,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Vec<CiJob>, String>>> {
+        github::fetch_ci_jobs_async(token?, url, run_id, proxy)
+    }
+
+    fn fetch_job_log(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        job_id: u64,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<ParsedLog, String>>> {
+        github::fetch_job_log_async(token?, url, job_id, proxy)
+    }
+
+    fn supports_ci_actions(&self) -> bool {
+        true
+    }
+
+    fn ci_action(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        action: CiAction,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<(), String>>> {
+        github::ci_action_async(token?, url, action, proxy)
+    }
+
+    fn dispatchable_workflows(&self, workdir: &Path) -> Vec<(String, Vec<String>)> {
+        github::dispatchable_workflows(workdir)
+    }
+
+    fn supports_pull_requests(&self) -> bool {
+        true
+    }
+
+    fn fetch_pull_requests(
+        &self,
+        token: &str,
+        url: &str,
+        remote: String,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<ProviderPullResult>> {
+        github::fetch_pull_requests_async(token, url, remote, proxy)
+    }
+
+    fn create_pull_request(
+        &self,
+        token: &str,
+        url: &str,
+        request: NewPullRequest,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<CreatedPullRequest, String>>> {
+        github::create_pull_request_async(token, url, request, proxy)
+    }
+
+    fn fetch_review_threads(
+        &self,
+        token: &str,
+        url: &str,
+        number: u64,
+        _head_sha: String,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Vec<ReviewThread>, String>>> {
+        github::fetch_review_threads_async(token, url, number, proxy)
+    }
+
+    fn review_action(
+        &self,
+        token: &str,
+        url: &str,
+        number: u64,
+        action: ReviewAction,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<(), String>>> {
+        github::review_action_async(token, url, number, action, proxy)
+    }
+
+    fn issue_prefixes(&self) -> &'static [&'static str] {
+        &["#", "GH-"]
+    }
+
+    fn issue_url(&self, url: &str, number: u64) -> Option<String> {
+        Some(format!("{}/issues/{number}", github_web_url(url)?))
+    }
+
+    fn changelog_links(&self, url: &str) -> Option<ChangelogLinks> {
+        let web = github_web_url(url)?;
+        Some(ChangelogLinks {
+            commit_url: format!("{web}/commit/"),
+            issue_url: format!("{web}/issues/"),
+            merge_request_url: None,
+        })
+    }
+
+    fn fetch_issue(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        number: u64,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Issue, String>>> {
+        github::fetch_issue_async(token?, url, number, proxy)
+    }
+
+    fn supports_releases(&self) -> bool {
+        true
+    }
+
+    fn fetch_releases(
+        &self,
+        token: &str,
+        url: &str,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Vec<Release>, String>>> {
+        github::fetch_releases_async(token, url, proxy)
+    }
+
+    fn create_release(
+        &self,
+        token: &str,
+        url: &str,
+        request: NewRelease,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Release, String>>> {
+        github::create_release_async(token, url, request, proxy)
+    }
+}
+
+pub struct GitLabBackend;
+
+impl ForgeBackend for GitLabBackend {
+    fn provider(&self) -> CiProvider {
+        CiProvider::GitLab
+    }
+
+    fn id(&self) -> &'static str {
+        "gitlab"
+    }
+
+    fn label(&self) -> &'static str {
+        "GitLab"
+    }
+
+    fn detect(&self, url: &str) -> Option<String> {
+        gitlab::parse_gitlab_remote(url).map(|remote| api_host(&remote.api_base).to_string())
+    }
+
+    fn register_host(&self, config: &mut Config, host: &str) -> bool {
+        config.register_gitlab_host(host)
+    }
+
+    fn token_hosts(&self, config: &Config) -> Vec<String> {
+        config.gitlab_hosts.clone()
+    }
+
+    fn token_info(&self) -> Option<TokenInfo> {
+        Some(TokenInfo {
+            help: "One token per host. Per-host secrets go in the keychain.",
+            empty_help: "GitLab hosts appear here automatically when whisper-git \
+                         sees a remote that points at one (e.g. `gitlab.com` or a \
+                         self-hosted instance). Open a repo with a GitLab remote \
+                         to register the host.",
+            manual_hosts: false,
+        })
+    }
+
+    fn token(&self, host: &str) -> Option<String> {
+        token_store::get_gitlab_token(host)
+    }
+
+    fn set_token(&self, host: &str, token: &str) -> bool {
+        token_store::set_gitlab_token(host, token)
+    }
+
+    fn delete_token(&self, host: &str) -> bool {
+        token_store::delete_gitlab_token(host)
+    }
+
+    fn fetch_ci_status(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        _pulls: &[PullRequest],
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<ProviderCiResult>> {
+        gitlab::fetch_ci_status_async(token?, url, proxy)
+    }
+
+    fn fetch_ci_jobs(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        run_id: u64,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Vec<CiJob>, String>>> {
+        gitlab::fetch_ci_jobs_async(token?, url, run_id, proxy)
+    }
+
+    fn fetch_job_log(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        job_id: u64,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<ParsedLog, String>>> {
+        gitlab::fetch_job_log_async(token?, url, job_id, proxy)
+    }
+
+    fn supports_ci_actions(&self) -> bool {
+        true
+    }
+
+    fn ci_action(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        action: CiAction,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<(), String>>> {
+        gitlab::ci_action_async(token?, url, action, proxy)
+    }
+
+    fn supports_pull_requests(&self) -> bool {
+        true
+    }
+
+    fn fetch_pull_requests(
+        &self,
+        token: &str,
+        url: &str,
+        remote: String,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<ProviderPullResult>> {
+        gitlab::fetch_pull_requests_async(token, url, remote, proxy)
+    }
+
+    fn create_pull_request(
+        &self,
+        token: &str,
+        url: &str,
+        request: NewPullRequest,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<CreatedPullRequest, String>>> {
+        gitlab::create_pull_request_async(token, url, request, proxy)
+    }
+
+    fn fetch_review_threads(
+        &self,
+        token: &str,
+        url: &str,
+        number: u64,
+        head_sha: String,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Vec<ReviewThread>, String>>> {
+        gitlab::fetch_review_threads_async(token, url, number, head_sha, proxy)
+    }
+
+    fn review_action(
+        &self,
+        token: &str,
+        url: &str,
+        number: u64,
+        action: ReviewAction,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<(), String>>> {
+        gitlab::review_action_async(token, url, number, action, proxy)
+    }
+
+    fn issue_prefixes(&self) -> &'static [&'static str] {
+        &["#"]
+    }
+
+    fn issue_url(&self, url: &str, number: u64) -> Option<String> {
+        Some(format!("{}/-/issues/{number}", gitlab_web_url(url)?))
+    }
+
+    fn changelog_links(&self, url: &str) -> Option<ChangelogLinks> {
+        let web = gitlab_web_url(url)?;
+        Some(ChangelogLinks {
+            commit_url: format!("{web}/-/commit/"),
+            issue_url: format!("{web}/-/issues/"),
+            merge_request_url: Some(format!("{web}/-/merge_requests/")),
+        })
+    }
+
+    fn fetch_issue(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        number: u64,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Issue, String>>> {
+        gitlab::fetch_issue_async(token?, url, number, proxy)
+    }
+
+    fn supports_releases(&self) -> bool {
+        true
+    }
+
+    fn fetch_releases(
+        &self,
+        token: &str,
+        url: &str,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Vec<Release>, String>>> {
+        gitlab::fetch_releases_async(token, url, proxy)
+    }
+
+    fn create_release(
+        &self,
+        token: &str,
+        url: &str,
+        request: NewRelease,
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<Result<Release, String>>> {
+        gitlab::create_release_async(token, url, request, proxy)
+    }
+}
+
+pub struct GiteaBackend;
+
+impl ForgeBackend for GiteaBackend {
+    fn provider(&self) -> CiProvider {
+        CiProvider::Gitea
+    }
+
+    fn id(&self) -> &'static str {
+        "gitea"
+    }
+
+    fn label(&self) -> &'static str {
+        "Gitea"
+    }
+
+    fn detect(&self, url: &str) -> Option<String> {
+        gitea::parse_gitea_remote(url).map(|remote| remote.host)
+    }
+
+    /// Only hosts recognisable by name; hand-registered ones are
+    /// already in the config.
+    fn register_host(&self, config: &mut Config, host: &str) -> bool {
+        gitea::is_detected_host(host) && config.register_gitea_host(host)
+    }
+
+    fn token_hosts(&self, config: &Config) -> Vec<String> {
+        config.gitea_hosts.clone()
+    }
+
+    fn add_token_host(&self, config: &mut Config, host: &str) -> bool {
+        let host = normalize_host(host);
+        !host.is_empty() && config.register_gitea_host(&host)
+    }
+
+    fn token_info(&self) -> Option<TokenInfo> {
+        Some(TokenInfo {
+            help: "Codeberg and hosts named like `gitea.*` / `forgejo.*` register \
+                   themselves; add any other self-hosted instance by hostname. \
+                   A token is only needed for private repos.",
+            empty_help: "",
+            manual_hosts: true,
+        })
+    }
+
+    fn token(&self, host: &str) -> Option<String> {
+        token_store::get_gitea_token(host)
+    }
+
+    fn set_token(&self, host: &str, token: &str) -> bool {
+        token_store::set_gitea_token(host, token)
+    }
+
+    fn delete_token(&self, host: &str) -> bool {
+        token_store::delete_gitea_token(host)
+    }
+
+    fn requires_token(&self) -> bool {
+        false
+    }
+
+    fn requests_per_poll(&self) -> u32 {
+        gitea::CI_POLL_REQUESTS
+    }
+
+    /// Gitea numbers issues and pull requests together, so `#N` pages
+    /// redirect to whichever it is.
+    fn changelog_links(&self, url: &str) -> Option<ChangelogLinks> {
+        let remote = gitea::parse_gitea_remote(url)?;
+        let web = format!("{}/{}/{}", remote.api_base, remote.owner, remote.repo);
+        Some(ChangelogLinks {
+            commit_url: format!("{web}/commit/"),
+            issue_url: format!("{web}/issues/"),
+            merge_request_url: None,
+        })
+    }
+
+    fn fetch_ci_status(
+        &self,
+        token: Option<&str>,
+        url: &str,
+        _pulls: &[PullRequest],
+        proxy: EventLoopProxy<()>,
+    ) -> Option<Receiver<ProviderCiResult>> {
+        gitea::fetch_ci_status_async(token, url, proxy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_provider_has_one_backend() {
+        for provider in [
+            CiProvider::GitHub,
+            CiProvider::GitLab,
+            CiProvider::Gitea,
+            CiProvider::Local,
+        ] {
+            assert_eq!(backend(provider).provider(), provider);
+            assert_eq!(
+                backends()
+                    .iter()
+                    .filter(|b| b.provider() == provider)
+                    .count(),
+                1
+            );
+        }
+        assert_eq!(by_id("gitlab").map(|b| b.label()), Some("GitLab"));
+    }
+
+    #[test]
+    fn normalize_typed_hosts() {
+        assert_eq!(
+            normalize_host(" https://Git.Corp.example/ "),
+            "git.corp.example"
+        );
+        assert_eq!(normalize_host("git@gitea.local:2222"), "gitea.local");
+        assert_eq!(normalize_host("codeberg.org"), "codeberg.org");
+        assert_eq!(normalize_host(""), "");
+    }
+
+    #[test]
+    fn detect_picks_the_matching_forge() {
+        let (backend, host) = detect("git@github.com:owner/repo.git").unwrap();
+        assert_eq!(
+            (backend.provider(), host.as_str()),
+            (CiProvider::GitHub, "github.com")
+        );
+        let (backend, host) = detect("https://gitlab.example.com/group/app.git").unwrap();
+        assert_eq!(
+            (backend.provider(), host.as_str()),
+            (CiProvider::GitLab, "gitlab.example.com")
+        );
+        let (backend, host) = detect("https://codeberg.org/alice/tool").unwrap();
+        assert_eq!(
+            (backend.provider(), host.as_str()),
+            (CiProvider::Gitea, "codeberg.org")
+        );
+        assert!(detect("https://example.com/plain.git").is_none());
+    }
+
+    #[test]
+    fn changelog_links_follow_each_forge_layout() {
+        let links = |url: &str| detect(url).and_then(|(b, _)| b.changelog_links(url));
+        let github = links("git@github.com:owner/repo.git").unwrap();
+        assert_eq!(github.commit_url, "https://github.com/owner/repo/commit/");
+        let gitlab = links("https://gitlab.com/group/app.git").unwrap();
+        assert_eq!(
+            gitlab.merge_request_url.as_deref(),
+            Some("https://gitlab.com/group/app/-/merge_requests/")
+        );
+        let gitea = links("https://codeberg.org/alice/tool").unwrap();
+        assert_eq!(gitea.issue_url, "https://codeberg.org/alice/tool/issues/");
+        assert_eq!(gitea.merge_request_url, None);
+    }
+}
@@ -3,11 +3,12 @@
 //! Provides `run_git_async` and the `define_async_git_op!` macro for generating
 //! typed async wrappers, plus `classify_git_error` for user-friendly error messages.
 
-use std::path::PathBuf;
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver};
 use winit::event_loop::EventLoopProxy;
 
-use super::RemoteOpResult;
+use super::{GitRepo, RemoteOpResult};
 
 /// Spawn a background thread to run a git CLI command and send the result over a channel.
 fn run_git_async(
@@ -16,25 +17,50 @@ fn run_git_async(
     op_name: &str,
     proxy: EventLoopProxy<()>,
 ) -> Receiver<RemoteOpResult> {
-    crate::crash_log::breadcrumb(format!("git_async: {op_name} args={args:?}"));
+    run_git_sequence_async(vec![args], workdir, op_name, proxy)
+}
+
+/// Like [`run_git_async`], but runs several git commands one after the
+/// other, stopping at the first failure. The result carries the stderr
+/// of every command that ran.
+fn run_git_sequence_async(
+    commands: Vec<Vec<String>>,
+    workdir: PathBuf,
+    op_name: &str,
+    proxy: EventLoopProxy<()>,
+) -> Receiver<RemoteOpResult> {
+    crate::crash_log::breadcrumb(format!("git_async: {op_name} args={commands:?}"));
     let op_name = op_name.to_string();
     let (tx, rx) = mpsc::channel();
     std::thread::spawn(move || {
-        let result = std::process::Command::new("git")
-            .args(&args)
-            .current_dir(&workdir)
-            .env("GIT_TERMINAL_PROMPT", "0")
-            .output();
-        let op_result = match result {
-            Ok(output) => RemoteOpResult {
-                success: output.status.success(),
-                error: String::from_utf8_lossy(&output.stderr).to_string(),
-            },
-            Err(e) => RemoteOpResult {
-                success: false,
-                error: format!("Failed to run git {}: {}", op_name, e),
-            },
+        let mut op_result = RemoteOpResult {
+            success: true,
+            error: String::new(),
         };
+        for args in &commands {
+            let result = std::process::Command::new("git")
+                .args(args)
+                .current_dir(&workdir)
+                .env("GIT_TERMINAL_PROMPT", "0")
+                .output();
+            match result {
+                Ok(output) => {
+                    op_result.success = output.status.success();
+                    op_result
+                        .error
+                        .push_str(&String::from_utf8_lossy(&output.stderr));
+                }
+                Err(e) => {
+                    op_result.success = false;
+                    op_result
+                        .error
+                        .push_str(&format!("Failed to run git {}: {}", op_name, e));
+                }
+            }
+            if !op_result.success {
+                break;
+            }
+        }
         crate::crash_log::breadcrumb(format!(
             "git_async done: {op_name} success={}",
             op_result.success
@@ -75,6 +101,10 @@ define_async_git_op! {
     fetch_all_async() =>
         ["fetch", "--all", "--prune"], "fetch --all";
 
+    /// Spawn a background thread to run `git fetch <remote> refs/notes/*:refs/notes/*`
+    fetch_notes_async(remote: String) =>
+        ["fetch", remote, super::NOTES_REFSPEC], "fetch notes";
+
     /// Spawn a background thread to run `git push`
     push_remote_async(remote: String, branch: String) =>
         ["push", remote, branch], "push";
@@ -127,6 +157,18 @@ define_async_git_op! {
     remove_worktree_force_async(target: String) =>
         ["worktree", "remove", "--force", target], "worktree remove --force";
 
+    /// Spawn a background thread to unlock a locked worktree
+    unlock_worktree_async(target: String) =>
+        ["worktree", "unlock", target], "worktree unlock";
+
+    /// Spawn a background thread to move a worktree to a new directory
+    move_worktree_async(target: String, dest: String) =>
+        ["worktree", "move", target, dest], "worktree move";
+
+    /// Spawn a background thread to prune metadata of worktrees whose directory is gone
+    prune_worktrees_async() =>
+        ["worktree", "prune", "--verbose"], "worktree prune";
+
     /// Spawn a background thread to merge a branch into the current branch
     merge_branch_async(branch_name: String) =>
         ["merge", branch_name], "merge";
@@ -198,8 +240,10 @@ pub fn delete_remote_branch_async(
 /// Spawn a background thread to run `git push` with arbitrary flag combinations.
 ///
 /// Backs the push-options modal: any combination of `--force-with-lease`,
-/// `--set-upstream`, and `--tags` may be set. The macro-generated helpers
-/// can't express conditional args, so this is hand-rolled.
+/// `--set-upstream`, `--tags`, and pushing `refs/notes/*` may be set. The
+/// macro-generated helpers can't express conditional args, so this is
+/// hand-rolled.
+#[allow(clippy::too_many_arguments)]
 pub fn push_with_options_async(
     workdir: PathBuf,
     remote: String,
@@ -207,6 +251,7 @@ pub fn push_with_options_async(
     force_with_lease: bool,
     set_upstream: bool,
     include_tags: bool,
+    include_notes: bool,
     proxy: EventLoopProxy<()>,
 ) -> Receiver<RemoteOpResult> {
     let mut args: Vec<String> = vec!["push".to_string()];
@@ -221,9 +266,74 @@ pub fn push_with_options_async(
     }
     args.push(remote);
     args.push(branch);
+    if include_notes {
+        args.push(super::NOTES_REFSPEC.to_string());
+    }
     run_git_async(args, workdir, "push", proxy)
 }
 
+/// Spawn a background thread to run `git fetch [--prune] <remote>`,
+/// followed by a fetch of `refs/notes/*` when `include_notes` is set.
+/// The notes fetch is a separate command so `--prune` never deletes
+/// local-only notes refs.
+pub fn fetch_with_options_async(
+    workdir: PathBuf,
+    remote: String,
+    prune: bool,
+    include_notes: bool,
+    proxy: EventLoopProxy<()>,
+) -> Receiver<RemoteOpResult> {
+    let mut fetch: Vec<String> = vec!["fetch".to_string()];
+    if prune {
+        fetch.push("--prune".to_string());
+    }
+    fetch.push(remote.clone());
+    let mut commands = vec![fetch];
+    if include_notes {
+        commands.push(vec![
+            "fetch".to_string(),
+            remote,
+            super::NOTES_REFSPEC.to_string(),
+        ]);
+    }
+    run_git_sequence_async(commands, workdir, "fetch", proxy)
+}
+
+/// Spawn a background thread to run `git worktree lock [--reason <reason>] <target>`.
+/// The reason is optional and only passed when non-empty, so the
+/// macro-generated helpers can't express it.
+pub fn lock_worktree_async(
+    workdir: PathBuf,
+    target: String,
+    reason: Option<String>,
+    proxy: EventLoopProxy<()>,
+) -> Receiver<RemoteOpResult> {
+    let mut args: Vec<String> = vec!["worktree".to_string(), "lock".to_string()];
+    if let Some(reason) = reason.filter(|r| !r.trim().is_empty()) {
+        args.push("--reason".to_string());
+        args.push(reason);
+    }
+    args.push(target);
+    run_git_async(args, workdir, "worktree lock", proxy)
+}
+
+/// Spawn a background thread to write a bundle of `revs` (refs and
+/// `base..tip` ranges) to `dest` with `git bundle create`.
+pub fn create_bundle_async(
+    workdir: PathBuf,
+    dest: PathBuf,
+    revs: Vec<String>,
+    proxy: EventLoopProxy<()>,
+) -> Receiver<RemoteOpResult> {
+    let mut args: Vec<String> = vec![
+        "bundle".into(),
+        "create".into(),
+        dest.to_string_lossy().into_owned(),
+    ];
+    args.extend(revs);
+    run_git_async(args, workdir, "bundle create", proxy)
+}
+
 /// Spawn a background thread to run `git clone [--bare] <url> <dest>`.
 /// Unlike the other async ops in this module, clone has no `workdir` —
 /// it *creates* one — so it returns its own result type carrying either
@@ -291,6 +401,38 @@ define_async_git_op! {
     /// Spawn a background thread to revert a commit
     revert_commit_async(sha: String) =>
         ["revert", "--no-edit", sha], "revert";
+
+    /// Spawn a background thread to resume a stopped `git am` after conflicts are resolved
+    am_continue_async() =>
+        ["am", "--continue"], "am --continue";
+
+    /// Spawn a background thread to skip the patch a stopped `git am` is stuck on
+    am_skip_async() =>
+        ["am", "--skip"], "am --skip";
+
+    /// Spawn a background thread to abort a stopped `git am` and restore the original branch
+    am_abort_async() =>
+        ["am", "--abort"], "am --abort";
+}
+
+/// Spawn a background thread to apply a patch file: `git am` to recreate
+/// the commits of a mailbox, or `git apply` to only change the worktree.
+/// With `three_way`, conflicting hunks fall back to a 3-way merge and are
+/// left as conflicts instead of failing the whole patch.
+pub fn apply_patch_async(
+    workdir: PathBuf,
+    patch_file: PathBuf,
+    use_am: bool,
+    three_way: bool,
+    proxy: EventLoopProxy<()>,
+) -> Receiver<RemoteOpResult> {
+    let mut args: Vec<String> = vec![if use_am { "am" } else { "apply" }.into()];
+    if three_way {
+        args.push("--3way".into());
+    }
+    args.push(patch_file.to_string_lossy().into_owned());
+    let op = if use_am { "am" } else { "apply" };
+    run_git_async(args, workdir, op, proxy)
 }
 
 /// Spawn a background thread to apply a stash entry (without removing it)
@@ -498,6 +640,106 @@ pub fn create_worktree_with_post_steps_async(
     rx
 }
 
+/// Spawn a background thread to check out a pull / merge request head:
+/// fetch `head_ref` from `remote`, then point `branch` at it, either in
+/// `workdir` or in a new worktree at `worktree_path`. An existing
+/// `branch` from an earlier checkout is reset to the new head, so a
+/// force-pushed request updates too, unless it carries local commits.
+pub fn checkout_pull_request_async(
+    workdir: PathBuf,
+    remote: String,
+    head_ref: String,
+    branch: String,
+    worktree_path: Option<String>,
+    proxy: EventLoopProxy<()>,
+) -> Receiver<RemoteOpResult> {
+    crate::crash_log::breadcrumb(format!(
+        "git_async: checkout pull request {remote} {head_ref} -> {branch}"
+    ));
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let op_result = match checkout_pull_request(
+            &workdir,
+            &remote,
+            &head_ref,
+            &branch,
+            worktree_path.as_deref(),
+        ) {
+            Ok(()) => RemoteOpResult {
+                success: true,
+                error: String::new(),
+            },
+            Err(e) => RemoteOpResult {
+                success: false,
+                error: format!("{e:#}"),
+            },
+        };
+        let _ = tx.send(op_result);
+        let _ = proxy.send_event(());
+    });
+    rx
+}
+
+/// Ref holding the head last fetched for pull request branch `branch`.
+/// Lets a later checkout tell the request's own commits, including
+/// ones a force-push dropped, from commits made locally on `branch`.
+fn pull_head_ref(remote: &str, branch: &str) -> String {
+    format!("refs/pull-heads/{remote}/{branch}")
+}
+
+impl GitRepo {
+    /// Delete the [`pull_head_ref`]s under `remote` for requests no
+    /// longer open, i.e. whose local branch isn't in `open`.
+    pub fn prune_pull_heads(&self, remote: &str, open: &[String]) -> Result<()> {
+        let prefix = pull_head_ref(remote, "");
+        let refs = self.git_stdout(["for-each-ref", "--format=%(refname)", &prefix])?;
+        for name in refs.lines() {
+            let branch = name.strip_prefix(&prefix).unwrap_or(name);
+            if !open.iter().any(|b| b == branch) {
+                self.git_stdout(["update-ref", "-d", name])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn checkout_pull_request(
+    workdir: &Path,
+    remote: &str,
+    head_ref: &str,
+    branch: &str,
+    worktree_path: Option<&str>,
+) -> Result<()> {
+    let repo = GitRepo::open(workdir)?;
+    let local_ref = format!("refs/heads/{branch}");
+    let tracking = pull_head_ref(remote, branch);
+    let rev = |name: &str| {
+        repo.git_stdout(["rev-parse", "--verify", "--quiet", name])
+            .ok()
+            .map(|oid| oid.trim().to_string())
+    };
+    let exists = rev(&local_ref).is_some();
+    let previous = rev(&tracking);
+    repo.git_stdout(["fetch", remote, &format!("+{head_ref}:{tracking}")])?;
+    let head = rev(&tracking).context("Fetched pull request head is missing")?;
+    if exists {
+        let mut args = vec!["rev-list", "--count", &local_ref, "--not", &head];
+        args.extend(previous.as_deref());
+        let local_only: usize = repo.git_stdout(args)?.trim().parse().unwrap_or(0);
+        if local_only > 0 {
+            bail!(
+                "{branch} has {local_only} commit(s) that aren't on the pull request; \
+                 push or move them before checking it out again"
+            );
+        }
+    }
+    match worktree_path {
+        None => repo.git_stdout(["checkout", "-B", branch, &head])?,
+        Some(path) => repo.git_stdout(["worktree", "add", "-B", branch, path, &head])?,
+    };
+    Ok(())
+}
+
 /// Classify a git CLI stderr message into a user-friendly error string.
 /// Returns `(friendly_message, is_rejected)` where `is_rejected` indicates
 /// the remote rejected the push (e.g. non-fast-forward).
@@ -609,3 +851,64 @@ pub fn classify_git_error(op: &str, stderr: &str) -> (String, bool) {
 
     (friendly, is_rejected)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+
+    fn commit_file(repo_dir: &Path, path: &str, contents: &str) -> String {
+        fs::write(repo_dir.join(path), contents).expect("write file");
+        run_git(repo_dir, &["add", path]);
+        run_git(repo_dir, &["commit", "-m", path]);
+        run_git(repo_dir, &["rev-parse", "HEAD"])
+    }
+
+    #[test]
+    fn pull_request_checkout_follows_force_pushes_but_keeps_local_commits() {
+        let upstream = temp_dir("pr-upstream");
+        run_git(&upstream, &["init", "-b", "main"]);
+        commit_file(&upstream, "base.txt", "base\n");
+        let first = commit_file(&upstream, "pr.txt", "v1\n");
+        run_git(&upstream, &["update-ref", "refs/pull/1/head", &first]);
+
+        let clone = temp_dir("pr-clone");
+        run_git(
+            &upstream,
+            &[
+                "clone",
+                "-q",
+                upstream.to_str().unwrap(),
+                clone.to_str().unwrap(),
+            ],
+        );
+        let checkout = || checkout_pull_request(&clone, "origin", "refs/pull/1/head", "pr/1", None);
+        checkout().expect("first checkout");
+        assert_eq!(run_git(&clone, &["rev-parse", "pr/1"]), first);
+
+        // Force-push: rewrite the request's commit.
+        run_git(&upstream, &["reset", "-q", "--hard", "HEAD~1"]);
+        let rewritten = commit_file(&upstream, "pr.txt", "v2\n");
+        run_git(&upstream, &["update-ref", "refs/pull/1/head", &rewritten]);
+        checkout().expect("checkout after force-push");
+        assert_eq!(run_git(&clone, &["rev-parse", "pr/1"]), rewritten);
+
+        // A local commit on the branch blocks the reset.
+        let local = commit_file(&clone, "local.txt", "mine\n");
+        assert!(checkout().is_err());
+        assert_eq!(run_git(&clone, &["rev-parse", "pr/1"]), local);
+
+        // Once the request is no longer listed, its head ref goes.
+        let repo = GitRepo::open(&clone).expect("open clone");
+        let heads = || run_git(&clone, &["for-each-ref", "refs/pull-heads/"]);
+        repo.prune_pull_heads("origin", &["pr/1".to_string()])
+            .expect("prune open");
+        assert!(!heads().is_empty());
+        repo.prune_pull_heads("origin", &[]).expect("prune closed");
+        assert!(heads().is_empty());
+
+        let _ = fs::remove_dir_all(&upstream);
+        let _ = fs::remove_dir_all(&clone);
+    }
+}
@@ -0,0 +1,274 @@
+//! `git bundle` support for moving history between machines without a
+//! network path.
+//!
+//! Bundles are read by parsing their text header directly (refs and
+//! prerequisite commits), so inspecting one never touches the pack data.
+//! Creating one runs `git bundle create` through `create_bundle_async`;
+//! importing registers the file as a remote so the regular fetch path
+//! can pull from it.
+
+use anyhow::{Context, Result, bail};
+use git2::Oid;
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use super::GitRepo;
+
+/// One ref advertised by a bundle, e.g. `refs/heads/main`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BundleRef {
+    pub name: String,
+    pub oid: Oid,
+}
+
+/// Parsed bundle header plus what this repository is missing to
+/// unbundle it.
+#[derive(Clone, Debug, Default)]
+pub struct BundleInfo {
+    /// Bundle format version (2 or 3).
+    pub version: u8,
+    pub refs: Vec<BundleRef>,
+    /// Commits the receiving repository must already have. Empty for a
+    /// full-history bundle.
+    pub prerequisites: Vec<Oid>,
+    /// Subset of `prerequisites` not present in this repository — the
+    /// same check `git bundle verify` performs. Fetching fails until
+    /// this is empty.
+    pub missing_prerequisites: Vec<Oid>,
+}
+
+/// A commit a bundle being created will require on the receiving side.
+#[derive(Clone, Debug)]
+pub struct BundlePrerequisite {
+    pub oid: Oid,
+    pub summary: String,
+}
+
+/// Dry run of `git bundle create` for a set of revisions.
+#[derive(Clone, Debug, Default)]
+pub struct BundlePlan {
+    /// Commits the bundle will carry. Zero means git would refuse to
+    /// create it.
+    pub commit_count: usize,
+    /// Boundary commits the bundle will depend on.
+    pub prerequisites: Vec<BundlePrerequisite>,
+}
+
+impl GitRepo {
+    /// Read the bundle at `path` and check its prerequisites against
+    /// this repository's object database.
+    pub fn inspect_bundle(&self, path: &Path) -> Result<BundleInfo> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut info = parse_bundle_header(BufReader::new(file))
+            .with_context(|| format!("{} is not a git bundle", path.display()))?;
+        let odb = self.repo.odb().context("Failed to open object database")?;
+        info.missing_prerequisites = info
+            .prerequisites
+            .iter()
+            .copied()
+            .filter(|oid| !odb.exists(*oid))
+            .collect();
+        Ok(info)
+    }
+
+    /// Work out what `git bundle create <revs>` would contain without
+    /// writing anything: the commit count and the boundary commits the
+    /// receiver needs (`git rev-list --boundary`).
+    pub fn bundle_plan(&self, revs: &[String]) -> Result<BundlePlan> {
+        if revs.is_empty() {
+            bail!("No refs or ranges given");
+        }
+        let mut args = vec!["rev-list".to_string(), "--boundary".to_string()];
+        args.extend(revs.iter().cloned());
+        args.push("--".to_string());
+        let out = self.git_stdout(&args)?;
+        let mut plan = BundlePlan::default();
+        for line in out.lines() {
+            match line.strip_prefix('-') {
+                Some(boundary) => {
+                    let oid = Oid::from_str(boundary.trim())
+                        .with_context(|| format!("Unexpected rev-list output: {line}"))?;
+                    let summary = self
+                        .repo
+                        .find_commit(oid)
+                        .ok()
+                        .and_then(|c| c.summary().map(str::to_string))
+                        .unwrap_or_default();
+                    plan.prerequisites.push(BundlePrerequisite { oid, summary });
+                }
+                None if !line.trim().is_empty() => plan.commit_count += 1,
+                None => {}
+            }
+        }
+        Ok(plan)
+    }
+
+    /// Names of the remotes whose URL is a bundle file, resolved the way
+    /// `git fetch` would: relative to [`GitRepo::git_command_dir`].
+    pub fn bundle_remotes(&self) -> HashSet<String> {
+        let base = self.git_command_dir();
+        self.remote_names()
+            .into_iter()
+            .filter(|name| {
+                self.remote_url(name)
+                    .is_some_and(|url| is_bundle_url(&url, &base))
+            })
+            .collect()
+    }
+}
+
+/// `true` when a remote URL points at a bundle file rather than a
+/// repository. Git only accepts a plain file as a remote if it's a
+/// bundle, so a local path to a regular file is enough to tell.
+/// Relative paths resolve against `base`, the directory git runs in;
+/// `file://` URLs are read as the path they name. Stats the filesystem,
+/// so callers compute it once per refresh.
+pub fn is_bundle_url(url: &str, base: &Path) -> bool {
+    let path = url.strip_prefix("file://").unwrap_or(url);
+    !path.contains("://") && base.join(path).is_file()
+}
+
+/// Parse the text header of a v2/v3 bundle:
+///
+/// ```text
+/// # v2 git bundle
+/// -<prerequisite oid> <comment>
+/// <oid> <refname>
+/// <blank line, then pack data>
+/// ```
+///
+/// v3 adds `@key=value` capability lines after the signature.
+fn parse_bundle_header(mut reader: impl BufRead) -> Result<BundleInfo> {
+    let mut line = Vec::new();
+    reader.read_until(b'\n', &mut line)?;
+    let version = match line.as_slice() {
+        b"# v2 git bundle\n" => 2,
+        b"# v3 git bundle\n" => 3,
+        _ => bail!("Missing bundle signature"),
+    };
+    let mut info = BundleInfo {
+        version,
+        ..Default::default()
+    };
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            bail!("Truncated bundle header");
+        }
+        let text = String::from_utf8_lossy(&line);
+        let text = text.trim_end_matches('\n');
+        if text.is_empty() {
+            break;
+        }
+        if text.starts_with('@') {
+            continue;
+        }
+        if let Some(rest) = text.strip_prefix('-') {
+            let sha = rest.split_whitespace().next().unwrap_or_default();
+            let oid =
+                Oid::from_str(sha).with_context(|| format!("Bad prerequisite line: {text}"))?;
+            info.prerequisites.push(oid);
+            continue;
+        }
+        let Some((sha, name)) = text.split_once(' ') else {
+            bail!("Bad ref line: {text}");
+        };
+        let oid = Oid::from_str(sha).with_context(|| format!("Bad ref line: {text}"))?;
+        info.refs.push(BundleRef {
+            name: name.to_string(),
+            oid,
+        });
+    }
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_bundle_header;
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn header_parses_refs_prerequisites_and_capabilities() {
+        let a = "1111111111111111111111111111111111111111";
+        let b = "2222222222222222222222222222222222222222";
+        let header = format!(
+            "# v3 git bundle\n@object-format=sha1\n-{a} base commit\n{b} refs/heads/main\n\nPACK"
+        );
+        let info = parse_bundle_header(header.as_bytes()).expect("parse header");
+        assert_eq!(info.version, 3);
+        assert_eq!(info.prerequisites.len(), 1);
+        assert_eq!(info.prerequisites[0].to_string(), a);
+        assert_eq!(info.refs.len(), 1);
+        assert_eq!(info.refs[0].name, "refs/heads/main");
+        assert_eq!(info.refs[0].oid.to_string(), b);
+
+        assert!(parse_bundle_header(&b"PACK\n"[..]).is_err());
+    }
+
+    #[test]
+    fn incremental_bundle_reports_prerequisites() {
+        let root = temp_dir("bundle");
+        let src = root.join("src");
+        let empty = root.join("empty");
+        fs::create_dir_all(&src).expect("create src repo dir");
+        fs::create_dir_all(&empty).expect("create empty repo dir");
+
+        run_git(&src, &["init", "-b", "main"]);
+        fs::write(src.join("a.txt"), "one\n").expect("write a.txt");
+        run_git(&src, &["add", "a.txt"]);
+        run_git(&src, &["commit", "-m", "init"]);
+        fs::write(src.join("a.txt"), "one\ntwo\n").expect("write a.txt");
+        run_git(&src, &["commit", "-am", "second"]);
+        fs::write(src.join("a.txt"), "one\ntwo\nthree\n").expect("write a.txt");
+        run_git(&src, &["commit", "-am", "third"]);
+
+        let repo = GitRepo::open(&src).expect("open src repo");
+        let plan = repo
+            .bundle_plan(&["main~1..main".to_string()])
+            .expect("bundle plan");
+        assert_eq!(plan.commit_count, 1);
+        assert_eq!(plan.prerequisites.len(), 1);
+        assert_eq!(plan.prerequisites[0].summary, "second");
+
+        let bundle = root.join("inc.bundle");
+        run_git(
+            &src,
+            &[
+                "bundle",
+                "create",
+                &bundle.to_string_lossy(),
+                "main~1..main",
+            ],
+        );
+        let info = repo.inspect_bundle(&bundle).expect("inspect in src");
+        assert_eq!(info.refs.len(), 1);
+        assert_eq!(info.refs[0].name, "refs/heads/main");
+        assert!(info.missing_prerequisites.is_empty());
+
+        run_git(&empty, &["init"]);
+        let empty_repo = GitRepo::open(&empty).expect("open empty repo");
+        let info = empty_repo
+            .inspect_bundle(&bundle)
+            .expect("inspect in empty");
+        assert_eq!(info.missing_prerequisites, info.prerequisites);
+        assert!(super::is_bundle_url(&bundle.to_string_lossy(), &empty));
+        assert!(super::is_bundle_url(
+            &format!("file://{}", bundle.to_string_lossy()),
+            &empty
+        ));
+        assert!(!super::is_bundle_url("../src", &empty));
+
+        run_git(&empty, &["remote", "add", "usb", "../inc.bundle"]);
+        run_git(&empty, &["remote", "add", "origin", "../src"]);
+        let empty_repo = GitRepo::open(&empty).expect("reopen empty repo");
+        let bundles = empty_repo.bundle_remotes();
+        assert!(bundles.contains("usb"));
+        assert!(!bundles.contains("origin"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,478 @@
+//! Changelog / release-notes generation between two refs.
+//!
+//! Commits in `from..to` (merges skipped) are grouped either by their
+//! conventional-commit type (`feat(ui)!: ...`) or by a user-configured
+//! list of subject prefixes, then rendered as Markdown with PR / issue
+//! references linked and authors credited.
+
+use anyhow::{Context, Result};
+use git2::{DescribeFormatOptions, DescribeOptions, Oid, Sort};
+use std::collections::HashMap;
+
+use super::{GitRepo, format_absolute_time, resolve_author};
+
+/// Heading for commits that match no group.
+const OTHER_TITLE: &str = "Other";
+
+/// Conventional-commit types in the order their sections appear.
+const CONVENTIONAL_TYPES: [(&str, &str); 11] = [
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("refactor", "Refactoring"),
+    ("docs", "Documentation"),
+    ("test", "Tests"),
+    ("build", "Build"),
+    ("ci", "CI"),
+    ("chore", "Chores"),
+    ("style", "Style"),
+    ("revert", "Reverts"),
+];
+
+/// How commits are sorted into sections.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangelogGrouping {
+    /// `type(scope)!: summary`; breaking changes get their own section.
+    Conventional,
+    /// Subject prefixes such as `ui:` or `[git]`, matched
+    /// case-insensitively in order. The section title is the prefix
+    /// without its punctuation.
+    Prefixes(Vec<String>),
+}
+
+/// URL bases for linking references, derived from the forge remote.
+/// Each is joined directly with the number or SHA.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangelogLinks {
+    pub commit_url: String,
+    /// `#N` references.
+    pub issue_url: String,
+    /// `!N` references (GitLab merge requests).
+    pub merge_request_url: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangelogEntry {
+    pub oid: Oid,
+    pub scope: Option<String>,
+    /// Subject without the type / prefix and trailing `(#N)`.
+    pub summary: String,
+    pub author: String,
+    /// `#N` / `!N` references from the subject and closing trailers.
+    pub refs: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangelogSection {
+    pub title: String,
+    pub entries: Vec<ChangelogEntry>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Changelog {
+    /// Base ref as given; empty when the changelog covers all history.
+    pub from: String,
+    pub to: String,
+    /// Commit time of `to`.
+    pub date: i64,
+    pub sections: Vec<ChangelogSection>,
+    /// Mailmap-resolved authors with commit counts, most commits first.
+    pub contributors: Vec<(String, usize)>,
+    pub commit_count: usize,
+}
+
+impl GitRepo {
+    /// Collect the non-merge commits in `from..to` into sections. An
+    /// empty `from` covers everything reachable from `to`.
+    pub fn changelog(
+        &self,
+        from: &str,
+        to: &str,
+        grouping: &ChangelogGrouping,
+    ) -> Result<Changelog> {
+        let resolve = |rev: &str| {
+            self.repo
+                .revparse_single(rev)
+                .and_then(|obj| obj.peel_to_commit())
+                .with_context(|| format!("Unknown ref {rev}"))
+        };
+        let to_commit = resolve(to)?;
+        let mut walk = self.repo.revwalk()?;
+        walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        walk.push(to_commit.id())?;
+        if !from.trim().is_empty() {
+            walk.hide(resolve(from.trim())?.id())?;
+        }
+
+        let mailmap = self.mailmap();
+        let mut sections: Vec<ChangelogSection> = Vec::new();
+        let mut contributors: HashMap<String, usize> = HashMap::new();
+        let mut commit_count = 0;
+        for oid in walk {
+            let commit = self.repo.find_commit(oid?)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            commit_count += 1;
+            let author = resolve_author(&commit, mailmap.as_ref()).name;
+            *contributors.entry(author.clone()).or_default() += 1;
+
+            let message = commit.message().unwrap_or("");
+            let subject = message.lines().next().unwrap_or("").trim();
+            let (title, scope, summary) = classify(subject, message, grouping);
+            let (summary, mut refs) = split_trailing_refs(summary);
+            for r in closing_refs(message) {
+                if !refs.contains(&r) {
+                    refs.push(r);
+                }
+            }
+            let entry = ChangelogEntry {
+                oid: commit.id(),
+                scope,
+                summary,
+                author,
+                refs,
+            };
+            match sections.iter_mut().find(|s| s.title == title) {
+                Some(section) => section.entries.push(entry),
+                None => sections.push(ChangelogSection {
+                    title,
+                    entries: vec![entry],
+                }),
+            }
+        }
+
+        let order = section_order(grouping);
+        sections.sort_by_key(|s| {
+            order
+                .iter()
+                .position(|t| *t == s.title)
+                .unwrap_or(order.len())
+        });
+        let mut contributors: Vec<(String, usize)> = contributors.into_iter().collect();
+        contributors.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        Ok(Changelog {
+            from: from.trim().to_string(),
+            to: to.to_string(),
+            date: to_commit.time().seconds(),
+            sections,
+            contributors,
+            commit_count,
+        })
+    }
+
+    /// Nearest tag reachable from `oid`'s first parent — the natural
+    /// base for a changelog ending at `oid`.
+    pub fn previous_tag(&self, oid: Oid) -> Option<String> {
+        let parent = self.repo.find_commit(oid).ok()?.parent(0).ok()?;
+        let describe = parent
+            .as_object()
+            .describe(DescribeOptions::new().describe_tags())
+            .ok()?;
+        describe
+            .format(Some(DescribeFormatOptions::new().abbreviated_size(0)))
+            .ok()
+    }
+}
+
+impl Changelog {
+    pub fn to_markdown(&self, links: Option<&ChangelogLinks>) -> String {
+        let mut out = format!(
+            "## {} ({})\n\n",
+            self.to,
+            &format_absolute_time(self.date)[..10]
+        );
+        if !self.from.is_empty() {
+            out.push_str(&format!("Changes since {}.\n\n", self.from));
+        }
+        for section in &self.sections {
+            out.push_str(&format!("### {}\n\n", section.title));
+            for entry in &section.entries {
+                out.push_str("- ");
+                if let Some(scope) = &entry.scope {
+                    out.push_str(&format!("**{scope}:** "));
+                }
+                out.push_str(&entry.summary);
+                for r in &entry.refs {
+                    out.push_str(&format!(" ({})", link_ref(r, links)));
+                }
+                let short = &entry.oid.to_string()[..7];
+                let sha = match links {
+                    Some(l) => format!("[{short}]({}{})", l.commit_url, entry.oid),
+                    None => short.to_string(),
+                };
+                out.push_str(&format!(" ({sha}) by {}\n", entry.author));
+            }
+            out.push('\n');
+        }
+        if !self.contributors.is_empty() {
+            out.push_str("### Contributors\n\n");
+            for (name, commits) in &self.contributors {
+                let noun = if *commits == 1 { "commit" } else { "commits" };
+                out.push_str(&format!("- {name} ({commits} {noun})\n"));
+            }
+        }
+        out
+    }
+}
+
+/// Markdown for one `#N` / `!N` reference, linked when the forge is
+/// known.
+fn link_ref(r: &str, links: Option<&ChangelogLinks>) -> String {
+    let base = match (r.as_bytes().first(), links) {
+        (Some(b'#'), Some(l)) => Some(l.issue_url.as_str()),
+        (Some(b'!'), Some(l)) => l.merge_request_url.as_deref(),
+        _ => None,
+    };
+    match base {
+        Some(base) => format!("[{r}]({base}{})", &r[1..]),
+        None => r.to_string(),
+    }
+}
+
+/// Section titles in display order for `grouping`.
+fn section_order(grouping: &ChangelogGrouping) -> Vec<String> {
+    let mut order: Vec<String> = match grouping {
+        ChangelogGrouping::Conventional => std::iter::once("Breaking Changes".to_string())
+            .chain(CONVENTIONAL_TYPES.iter().map(|(_, t)| t.to_string()))
+            .collect(),
+        ChangelogGrouping::Prefixes(prefixes) => prefixes.iter().map(|p| prefix_title(p)).collect(),
+    };
+    order.push(OTHER_TITLE.to_string());
+    order
+}
+
+fn prefix_title(prefix: &str) -> String {
+    prefix
+        .trim()
+        .trim_matches(|c: char| matches!(c, '[' | ']' | '(' | ')' | ':'))
+        .trim()
+        .to_string()
+}
+
+/// `text` after a case-insensitive `prefix`, compared char by char so
+/// the split lands on a boundary of `text` itself even when lowercasing
+/// changes a character's byte length.
+fn strip_prefix_ignore_case<'a>(text: &'a str, prefix: &str) -> Option<&'a str> {
+    let mut chars = text.chars();
+    for p in prefix.chars() {
+        let c = chars.next()?;
+        if !c.to_lowercase().eq(p.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.as_str())
+}
+
+/// `(section title, scope, summary)` for one commit.
+fn classify(
+    subject: &str,
+    message: &str,
+    grouping: &ChangelogGrouping,
+) -> (String, Option<String>, String) {
+    match grouping {
+        ChangelogGrouping::Conventional => match parse_conventional(subject) {
+            Some(cc) => {
+                let breaking = cc.breaking
+                    || message.lines().any(|l| {
+                        l.starts_with("BREAKING CHANGE:") || l.starts_with("BREAKING-CHANGE:")
+                    });
+                let title = if breaking {
+                    "Breaking Changes".to_string()
+                } else {
+                    CONVENTIONAL_TYPES
+                        .iter()
+                        .find(|(ty, _)| ty.eq_ignore_ascii_case(cc.kind))
+                        .map(|(_, t)| t.to_string())
+                        .unwrap_or_else(|| OTHER_TITLE.to_string())
+                };
+                (title, cc.scope.map(str::to_string), cc.summary.to_string())
+            }
+            None => (OTHER_TITLE.to_string(), None, subject.to_string()),
+        },
+        ChangelogGrouping::Prefixes(prefixes) => prefixes
+            .iter()
+            .filter(|p| !p.trim().is_empty())
+            .find_map(|p| {
+                let rest = strip_prefix_ignore_case(subject, p.trim())?;
+                let rest = rest.trim_start_matches([':', '-', ' ']).to_string();
+                Some((prefix_title(p), None, rest))
+            })
+            .unwrap_or_else(|| (OTHER_TITLE.to_string(), None, subject.to_string())),
+    }
+}
+
+struct Conventional<'a> {
+    kind: &'a str,
+    scope: Option<&'a str>,
+    breaking: bool,
+    summary: &'a str,
+}
+
+/// `type(scope)!: summary`, or `None` when the subject doesn't follow
+/// the convention.
+fn parse_conventional(subject: &str) -> Option<Conventional<'_>> {
+    let (head, summary) = subject.split_once(':')?;
+    let summary = summary.trim();
+    let (head, breaking) = match head.strip_suffix('!') {
+        Some(h) => (h, true),
+        None => (head, false),
+    };
+    let (kind, scope) = match head.split_once('(') {
+        Some((kind, rest)) => (kind, Some(rest.strip_suffix(')')?)),
+        None => (head, None),
+    };
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphabetic()) || summary.is_empty() {
+        return None;
+    }
+    Some(Conventional {
+        kind,
+        scope: scope.filter(|s| !s.is_empty()),
+        breaking,
+        summary,
+    })
+}
+
+/// Split `Add thing (#12) (!4)` into the summary and its trailing
+/// references, the form squash-merges leave behind.
+fn split_trailing_refs(summary: String) -> (String, Vec<String>) {
+    let mut text = summary.trim_end();
+    let mut refs = Vec::new();
+    while let Some(open) = text.rfind(" (")
+        && let Some(inner) = text[open + 2..].strip_suffix(')')
+        && is_ref(inner)
+    {
+        refs.insert(0, inner.to_string());
+        text = text[..open].trim_end();
+    }
+    (text.to_string(), refs)
+}
+
+/// References in closing trailers (`Closes #4`, `Fixes: #5, #6`).
+fn closing_refs(message: &str) -> Vec<String> {
+    const KEYWORDS: [&str; 5] = ["closes", "fixes", "resolves", "refs", "see"];
+    let mut refs = Vec::new();
+    for line in message.lines().skip(1) {
+        let lower = line.trim().to_lowercase();
+        if !KEYWORDS.iter().any(|k| lower.starts_with(k)) {
+            continue;
+        }
+        for word in line.split(|c: char| c.is_whitespace() || c == ',') {
+            let word = word.trim_end_matches(['.', ')']);
+            if is_ref(word) && !refs.iter().any(|r| r == word) {
+                refs.push(word.to_string());
+            }
+        }
+    }
+    refs
+}
+
+fn is_ref(s: &str) -> bool {
+    s.len() > 1
+        && (s.starts_with('#') || s.starts_with('!'))
+        && s[1..].bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChangelogGrouping, ChangelogLinks};
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn prefix_grouping_handles_non_ascii_subjects() {
+        let grouping = ChangelogGrouping::Prefixes(vec!["ß".to_string(), "Ärger:".to_string()]);
+        // 'ẞ' lowercases to the shorter 'ß'; the split must still land on
+        // a char boundary of the original subject.
+        assert_eq!(
+            classify("ẞ fix the thing", "", &grouping),
+            ("ß".to_string(), None, "fix the thing".to_string())
+        );
+        assert_eq!(
+            classify("ärger: über alles", "", &grouping),
+            ("Ärger".to_string(), None, "über alles".to_string())
+        );
+        assert_eq!(classify("Ü only", "", &grouping).0, OTHER_TITLE.to_string());
+    }
+
+    #[test]
+    fn changelog_groups_conventional_commits_between_tags() {
+        let repo_dir = temp_dir("changelog");
+
+        run_git(&repo_dir, &["init"]);
+        run_git(&repo_dir, &["commit", "--allow-empty", "-m", "initial"]);
+        run_git(&repo_dir, &["tag", "v1.0.0"]);
+        run_git(
+            &repo_dir,
+            &[
+                "commit",
+                "--allow-empty",
+                "-m",
+                "feat(ui): add dark mode (#12)",
+            ],
+        );
+        run_git(
+            &repo_dir,
+            &[
+                "commit",
+                "--allow-empty",
+                "-m",
+                "fix: crash on empty repo",
+                "-m",
+                "Closes #7",
+            ],
+        );
+        run_git(
+            &repo_dir,
+            &["commit", "--allow-empty", "-m", "refactor!: drop v1 API"],
+        );
+        run_git(&repo_dir, &["commit", "--allow-empty", "-m", "tidy up"]);
+        run_git(&repo_dir, &["tag", "v1.1.0"]);
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let tip = repo.head_oid().expect("head");
+        assert_eq!(repo.previous_tag(tip).as_deref(), Some("v1.0.0"));
+
+        let log = repo
+            .changelog("v1.0.0", "v1.1.0", &ChangelogGrouping::Conventional)
+            .expect("changelog");
+        assert_eq!(log.commit_count, 4);
+        let titles: Vec<&str> = log.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["Breaking Changes", "Features", "Bug Fixes", "Other"]
+        );
+        let feat = &log.sections[1].entries[0];
+        assert_eq!(feat.scope.as_deref(), Some("ui"));
+        assert_eq!(feat.summary, "add dark mode");
+        assert_eq!(feat.refs, vec!["#12"]);
+        assert_eq!(log.sections[2].entries[0].refs, vec!["#7"]);
+        assert_eq!(log.contributors, vec![("Test".to_string(), 4)]);
+
+        let links = ChangelogLinks {
+            commit_url: "https://example.com/c/".to_string(),
+            issue_url: "https://example.com/i/".to_string(),
+            merge_request_url: None,
+        };
+        let md = log.to_markdown(Some(&links));
+        assert!(md.starts_with("## v1.1.0 ("));
+        assert!(md.contains("- **ui:** add dark mode ([#12](https://example.com/i/12))"));
+
+        let by_prefix = repo
+            .changelog(
+                "v1.0.0",
+                "v1.1.0",
+                &ChangelogGrouping::Prefixes(vec!["fix:".to_string()]),
+            )
+            .expect("changelog");
+        assert_eq!(by_prefix.sections[0].title, "fix");
+        assert_eq!(
+            by_prefix.sections[0].entries[0].summary,
+            "crash on empty repo"
+        );
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
@@ -0,0 +1,432 @@
+//! `CODEOWNERS` parsing and matching, for showing who owns each changed
+//! file and who a change needs review from.
+//!
+//! Both dialects are understood: GitHub's flat list (last matching
+//! rule wins) and GitLab's `[Section]` syntax, where each section is
+//! matched independently and a file collects owners from every section
+//! that covers it. `^[Section]` marks a section whose approval is
+//! optional; `[Section] @owner` gives entries without owners of their
+//! own a default. Headers repeating a name (in any case) continue the
+//! earlier section.
+
+use git2::Oid;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use super::GitRepo;
+
+/// Where hosts look for the file, in lookup order. GitHub stops at the
+/// first one it finds; so do we.
+pub const CODEOWNERS_PATHS: [&str; 4] = [
+    ".github/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+];
+
+/// One pattern line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct OwnerRule {
+    pattern: String,
+    owners: Vec<String>,
+    /// Index into `CodeOwners::sections`; `None` before the first
+    /// section header (and always, for GitHub files).
+    section: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Section {
+    /// Lowercased; GitLab merges sections that share a name.
+    name: String,
+    /// `^[Name]`: approval from this section isn't required.
+    optional: bool,
+}
+
+/// A parsed `CODEOWNERS` file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeOwners {
+    /// Worktree-relative path the rules were read from.
+    pub source: String,
+    rules: Vec<OwnerRule>,
+    sections: Vec<Section>,
+}
+
+/// Owners a set of changed files needs review from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReviewSummary {
+    /// Required owners with the number of files each covers, most
+    /// files first. Owners only from optional sections aren't listed.
+    pub owners: Vec<(String, usize)>,
+    /// Files no rule assigns an owner to.
+    pub unowned: usize,
+}
+
+impl CodeOwners {
+    pub fn parse(source: &str, text: &str) -> Self {
+        let mut rules = Vec::new();
+        let mut sections: Vec<Section> = Vec::new();
+        let mut section_defaults: Vec<String> = Vec::new();
+        let mut section: Option<usize> = None;
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((header, defaults)) = parse_section_header(line) {
+                // A repeated `[Name]` continues the earlier section.
+                section = match sections.iter().position(|s| s.name == header.name) {
+                    Some(idx) => Some(idx),
+                    None => {
+                        sections.push(header);
+                        Some(sections.len() - 1)
+                    }
+                };
+                section_defaults = defaults;
+                continue;
+            }
+            let mut tokens = split_unescaped(line).into_iter();
+            let Some(pattern) = tokens.next() else {
+                continue;
+            };
+            let mut owners: Vec<String> = tokens.take_while(|t| !t.starts_with('#')).collect();
+            if owners.is_empty() && section.is_some() {
+                owners = section_defaults.clone();
+            }
+            rules.push(OwnerRule {
+                pattern,
+                owners,
+                section,
+            });
+        }
+        Self {
+            source: source.to_string(),
+            rules,
+            sections,
+        }
+    }
+
+    /// Last matching rule per section for `path`, in section order.
+    fn matching_rules(&self, path: &str) -> Vec<&OwnerRule> {
+        let mut last: BTreeMap<Option<usize>, &OwnerRule> = BTreeMap::new();
+        for rule in &self.rules {
+            if pattern_matches(&rule.pattern, path) {
+                last.insert(rule.section, rule);
+            }
+        }
+        last.into_values().collect()
+    }
+
+    /// Everyone who owns `path` (worktree-relative), deduplicated in
+    /// file order. Empty when no rule matches or the matching rule
+    /// clears ownership.
+    pub fn owners_for(&self, path: &str) -> Vec<String> {
+        let mut owners: Vec<String> = Vec::new();
+        for rule in self.matching_rules(path) {
+            for owner in &rule.owners {
+                if !owners.contains(owner) {
+                    owners.push(owner.clone());
+                }
+            }
+        }
+        owners
+    }
+
+    /// [`Self::owners_for`] for each of `paths`, keyed by path. Paths
+    /// nobody owns are left out. Computed once per refresh so file rows
+    /// can look their owners up instead of re-matching every frame.
+    pub fn owners_by_path<'a>(
+        &self,
+        paths: impl IntoIterator<Item = &'a str>,
+    ) -> HashMap<String, Vec<String>> {
+        paths
+            .into_iter()
+            .filter_map(|path| {
+                let owners = self.owners_for(path);
+                (!owners.is_empty()).then(|| (path.to_string(), owners))
+            })
+            .collect()
+    }
+
+    fn required_owners_for(&self, path: &str) -> Vec<&str> {
+        let mut owners: Vec<&str> = Vec::new();
+        for rule in self.matching_rules(path) {
+            let optional = rule
+                .section
+                .and_then(|idx| self.sections.get(idx))
+                .is_some_and(|s| s.optional);
+            if optional {
+                continue;
+            }
+            for owner in &rule.owners {
+                if !owners.contains(&owner.as_str()) {
+                    owners.push(owner);
+                }
+            }
+        }
+        owners
+    }
+
+    /// Who a change touching `paths` needs review from.
+    pub fn review_summary<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> ReviewSummary {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut unowned = 0;
+        for path in paths {
+            if self.owners_for(path).is_empty() {
+                unowned += 1;
+            }
+            for owner in self.required_owners_for(path) {
+                *counts.entry(owner).or_default() += 1;
+            }
+        }
+        let mut owners: Vec<(String, usize)> = counts
+            .into_iter()
+            .map(|(owner, n)| (owner.to_string(), n))
+            .collect();
+        owners.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        ReviewSummary { owners, unowned }
+    }
+}
+
+/// The first `CODEOWNERS` file present under `workdir`.
+pub fn codeowners_in_workdir(workdir: &Path) -> Option<CodeOwners> {
+    CODEOWNERS_PATHS.iter().find_map(|rel| {
+        let text = std::fs::read_to_string(workdir.join(rel)).ok()?;
+        Some(CodeOwners::parse(rel, &text))
+    })
+}
+
+impl GitRepo {
+    /// `CODEOWNERS` as it is in the working tree, including unstaged
+    /// edits. `None` for bare repositories or when there is no file.
+    pub fn codeowners(&self) -> Option<CodeOwners> {
+        codeowners_in_workdir(self.workdir()?)
+    }
+
+    /// `CODEOWNERS` as of commit `oid`.
+    pub fn codeowners_at(&self, oid: Oid) -> Option<CodeOwners> {
+        let tree = self.repo.find_commit(oid).ok()?.tree().ok()?;
+        CODEOWNERS_PATHS.iter().find_map(|rel| {
+            let entry = tree.get_path(Path::new(rel)).ok()?;
+            let blob = self.repo.find_blob(entry.id()).ok()?;
+            let text = std::str::from_utf8(blob.content()).ok()?;
+            Some(CodeOwners::parse(rel, text))
+        })
+    }
+}
+
+/// `[Name]`, `^[Name]`, `[Name][2]`, each optionally followed by
+/// default owners. Returns the section and its default owners.
+fn parse_section_header(line: &str) -> Option<(Section, Vec<String>)> {
+    let (optional, rest) = match line.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+    let rest = rest.strip_prefix('[')?;
+    let (name, mut rest) = rest.split_once(']')?;
+    // Required-approval count, e.g. `[Docs][2]`.
+    if let Some(after) = rest.strip_prefix('[') {
+        rest = after.split_once(']').map(|(_, r)| r).unwrap_or("");
+    }
+    let defaults = rest
+        .split_whitespace()
+        .take_while(|t| !t.starts_with('#'))
+        .map(str::to_string)
+        .collect();
+    Some((
+        Section {
+            name: name.trim().to_lowercase(),
+            optional,
+        },
+        defaults,
+    ))
+}
+
+/// Split on whitespace, keeping backslash-escaped spaces inside a
+/// token (`docs/My\ File.md`). A leading `\#` is a literal `#`.
+fn split_unescaped(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(next @ (' ' | '#')) => current.push(next),
+                Some(next) => {
+                    current.push('\\');
+                    current.push(next);
+                }
+                None => current.push('\\'),
+            },
+            c if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// gitignore-style match of a `CODEOWNERS` pattern against a
+/// worktree-relative file path. A pattern with a slash before its end
+/// is anchored to the root; one without matches at any depth. A
+/// pattern naming a directory owns everything below it, except that
+/// `dir/*` covers only direct children, as on GitHub.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let dir_only = pattern.ends_with('/');
+    let pat = pattern.trim_end_matches('/');
+    if pat.is_empty() {
+        return false;
+    }
+    let anchored = pat.starts_with('/') || pat.contains('/');
+    let pat = pat.trim_start_matches('/');
+    let direct_children_only = pat.ends_with("/*");
+    let segments: Vec<&str> = path.split('/').collect();
+    let last = segments.len() - 1;
+
+    if anchored {
+        (0..=last).any(|i| {
+            let is_file = i == last;
+            if (is_file && dir_only) || (!is_file && direct_children_only) {
+                return false;
+            }
+            glob_match(pat.as_bytes(), segments[..=i].join("/").as_bytes())
+        })
+    } else {
+        segments.iter().enumerate().any(|(i, segment)| {
+            if i == last && dir_only {
+                return false;
+            }
+            glob_match(pat.as_bytes(), segment.as_bytes())
+        })
+    }
+}
+
+/// `*` and `?` stay within a path segment; `**` crosses them, and
+/// `**/` also matches zero directories.
+fn glob_match(pat: &[u8], text: &[u8]) -> bool {
+    match pat {
+        [] => text.is_empty(),
+        [b'*', b'*', b'/', rest @ ..] => {
+            glob_match(rest, text)
+                || text
+                    .iter()
+                    .enumerate()
+                    .any(|(i, c)| *c == b'/' && glob_match(rest, &text[i + 1..]))
+        }
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+        [b'*', rest @ ..] => {
+            for i in 0..=text.len() {
+                if glob_match(rest, &text[i..]) {
+                    return true;
+                }
+                if text.get(i) == Some(&b'/') {
+                    break;
+                }
+            }
+            false
+        }
+        [b'?', rest @ ..] => {
+            matches!(text.first(), Some(c) if *c != b'/') && glob_match(rest, &text[1..])
+        }
+        [b'\\', c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+        [c, rest @ ..] => text.first() == Some(c) && glob_match(rest, &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CodeOwners, pattern_matches};
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn patterns_and_sections_resolve_owners() {
+        assert!(pattern_matches("*", "src/main.rs"));
+        assert!(pattern_matches("*.rs", "src/main.rs"));
+        assert!(pattern_matches("/docs/", "docs/a/b.md"));
+        assert!(pattern_matches("apps/", "web/apps/x.js"));
+        assert!(!pattern_matches("apps/", "web/apps"));
+        assert!(pattern_matches("docs/*", "docs/a.md"));
+        assert!(!pattern_matches("docs/*", "docs/sub/a.md"));
+        assert!(pattern_matches("**/logs", "deep/logs/x.log"));
+        assert!(!pattern_matches("/build/", "src/build/x"));
+
+        let github = CodeOwners::parse(
+            "CODEOWNERS",
+            "# comment\n* @everyone\n*.rs @rustaceans @lead # inline\n/vendor/\n",
+        );
+        assert_eq!(github.owners_for("README.md"), vec!["@everyone"]);
+        assert_eq!(github.owners_for("src/a.rs"), vec!["@rustaceans", "@lead"]);
+        assert!(github.owners_for("vendor/lib.c").is_empty());
+        let by_path = github.owners_by_path(["README.md", "vendor/lib.c"]);
+        assert_eq!(by_path.len(), 1);
+        assert_eq!(by_path["README.md"], vec!["@everyone"]);
+
+        let gitlab = CodeOwners::parse(
+            ".gitlab/CODEOWNERS",
+            "*.md @writer\n[Backend] @backend-team\n/src/\n/src/api/ @api\n^[Docs][2] @docs\n*.md\n",
+        );
+        assert_eq!(
+            gitlab.owners_for("src/api/x.md"),
+            vec!["@writer", "@api", "@docs"]
+        );
+        let summary = gitlab.review_summary(["src/api/x.md", "src/lib.rs", "Makefile"]);
+        assert_eq!(
+            summary.owners,
+            vec![
+                ("@api".to_string(), 1),
+                ("@backend-team".to_string(), 1),
+                ("@writer".to_string(), 1)
+            ]
+        );
+        assert_eq!(summary.unowned, 1);
+    }
+
+    #[test]
+    fn sections_sharing_a_name_are_merged() {
+        let owners = CodeOwners::parse(
+            "CODEOWNERS",
+            "[Docs] @docs\n*.md\n[Backend] @backend\n/src/\n[docs]\n/src/*.md @api-docs\n",
+        );
+        // The second `[docs]` block overrides the first within one
+        // section instead of adding a third owner set.
+        assert_eq!(
+            owners.owners_for("src/guide.md"),
+            vec!["@api-docs", "@backend"]
+        );
+        assert_eq!(owners.owners_for("README.md"), vec!["@docs"]);
+        let summary = owners.review_summary(["src/guide.md"]);
+        assert_eq!(
+            summary.owners,
+            vec![("@api-docs".to_string(), 1), ("@backend".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn codeowners_found_in_worktree_and_history() {
+        let repo_dir = temp_dir("codeowners");
+        fs::create_dir_all(repo_dir.join(".github")).expect("create .github dir");
+
+        run_git(&repo_dir, &["init"]);
+        fs::write(repo_dir.join(".github/CODEOWNERS"), "* @old\n").expect("write CODEOWNERS");
+        run_git(&repo_dir, &["add", "."]);
+        run_git(&repo_dir, &["commit", "-m", "owners"]);
+        fs::write(repo_dir.join(".github/CODEOWNERS"), "* @new\n").expect("write CODEOWNERS");
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let head = repo.head_oid().expect("head oid");
+        let worktree = repo.codeowners().expect("worktree codeowners");
+        assert_eq!(worktree.source, ".github/CODEOWNERS");
+        assert_eq!(worktree.owners_for("a.txt"), vec!["@new"]);
+        let committed = repo.codeowners_at(head).expect("committed codeowners");
+        assert_eq!(committed.owners_for("a.txt"), vec!["@old"]);
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
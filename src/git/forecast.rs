@@ -0,0 +1,332 @@
+//! Conflict forecasts for merges and rebases, computed without touching
+//! the working tree or the object store.
+//!
+//! Each forecast opens its own handle on the repository and puts an
+//! in-memory (mempack) backend in front of the object database, so the
+//! trees and merged blobs libgit2 writes while merging are dropped with
+//! the handle instead of landing as loose objects.
+
+use anyhow::{Context, Result};
+use git2::{Commit, Index, Oid, Repository};
+use std::sync::mpsc::{self, Receiver};
+use winit::event_loop::EventLoopProxy;
+
+use super::GitRepo;
+
+/// What running a merge or rebase would do.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConflictForecast {
+    /// Nothing to merge / replay.
+    pub up_to_date: bool,
+    /// HEAD would just move forward; nothing can conflict.
+    pub fast_forward: bool,
+    /// Commits a rebase would replay (0 for merges).
+    pub commits: usize,
+    /// Paths left conflicted. For a rebase, those of the first commit
+    /// that stops, since later commits depend on how it's resolved.
+    pub conflicts: Vec<String>,
+    /// The rebase commit that stops, with its summary.
+    pub stopping_commit: Option<(Oid, String)>,
+}
+
+impl ConflictForecast {
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+impl GitRepo {
+    /// Forecast `git merge <source>` into HEAD.
+    pub fn forecast_merge(&self, source: &str) -> Result<ConflictForecast> {
+        let repo = scratch_repo(&self.repo)?;
+        let head = repo
+            .head()?
+            .peel_to_commit()
+            .context("HEAD has no commit")?;
+        let theirs = repo
+            .revparse_single(source)
+            .and_then(|o| o.peel_to_commit())
+            .with_context(|| format!("Unknown revision {source}"))?;
+        if let Some(trivial) = trivial_forecast(&repo, head.id(), theirs.id())? {
+            return Ok(trivial);
+        }
+        let index = repo
+            .merge_commits(&head, &theirs, None)
+            .context("In-memory merge failed")?;
+        Ok(ConflictForecast {
+            conflicts: conflict_paths(&index),
+            ..ConflictForecast::default()
+        })
+    }
+
+    /// Forecast `git rebase <base>` of HEAD: replay each commit the
+    /// rebase would pick onto `base` in order, stopping at the first
+    /// that conflicts. Merge commits are skipped, as a plain rebase
+    /// drops them.
+    pub fn forecast_rebase(&self, base: &str) -> Result<ConflictForecast> {
+        let repo = scratch_repo(&self.repo)?;
+        let head = repo
+            .head()?
+            .peel_to_commit()
+            .context("HEAD has no commit")?;
+        let onto = repo
+            .revparse_single(base)
+            .and_then(|o| o.peel_to_commit())
+            .with_context(|| format!("Unknown revision {base}"))?;
+        // Rebasing onto a descendant fast-forwards; onto an ancestor
+        // is a no-op.
+        if let Some(trivial) = trivial_forecast(&repo, head.id(), onto.id())? {
+            return Ok(trivial);
+        }
+
+        // Like `git rebase`, skip commits whose patch is already upstream.
+        let range = format!("{}...{}", onto.id(), head.id());
+        let listed = self.git_stdout([
+            "rev-list",
+            "--cherry-pick",
+            "--right-only",
+            "--no-merges",
+            "--topo-order",
+            "--reverse",
+            range.as_str(),
+        ])?;
+        let mut picks: Vec<Commit> = Vec::new();
+        for line in listed.lines().filter(|l| !l.is_empty()) {
+            let oid = Oid::from_str(line.trim()).context("Unexpected rev-list output")?;
+            picks.push(repo.find_commit(oid)?);
+        }
+
+        let mut forecast = ConflictForecast {
+            commits: picks.len(),
+            ..ConflictForecast::default()
+        };
+        let mut tip = onto;
+        for commit in &picks {
+            let mut index = repo
+                .cherrypick_commit(commit, &tip, 0, None)
+                .with_context(|| format!("In-memory pick of {} failed", commit.id()))?;
+            if index.has_conflicts() {
+                forecast.conflicts = conflict_paths(&index);
+                forecast.stopping_commit =
+                    Some((commit.id(), commit.summary().unwrap_or("").to_string()));
+                break;
+            }
+            let tree = repo.find_tree(index.write_tree_to(&repo)?)?;
+            let sig = commit.committer();
+            let oid = repo.commit(
+                None,
+                &commit.author(),
+                &sig,
+                commit.message().unwrap_or(""),
+                &tree,
+                &[&tip],
+            )?;
+            tip = repo.find_commit(oid)?;
+        }
+        Ok(forecast)
+    }
+
+    /// Number of paths left conflicted by merging `branch` into
+    /// `head`. Zero when one already contains the other.
+    pub fn merge_conflict_count(&self, head: Oid, branch: Oid) -> Result<usize> {
+        let repo = scratch_repo(&self.repo)?;
+        if trivial_forecast(&repo, head, branch)?.is_some() {
+            return Ok(0);
+        }
+        let index = repo
+            .merge_commits(&repo.find_commit(head)?, &repo.find_commit(branch)?, None)
+            .context("In-memory merge failed")?;
+        Ok(conflict_paths(&index).len())
+    }
+
+    /// [`Self::forecast_merge`] on a background thread.
+    pub fn forecast_merge_async(
+        &self,
+        source: String,
+        proxy: EventLoopProxy<()>,
+    ) -> Receiver<Result<ConflictForecast, String>> {
+        self.spawn_forecast(proxy, move |repo| repo.forecast_merge(&source))
+    }
+
+    /// [`Self::forecast_rebase`] on a background thread.
+    pub fn forecast_rebase_async(
+        &self,
+        base: String,
+        proxy: EventLoopProxy<()>,
+    ) -> Receiver<Result<ConflictForecast, String>> {
+        self.spawn_forecast(proxy, move |repo| repo.forecast_rebase(&base))
+    }
+
+    /// [`Self::merge_conflict_count`] on a background thread.
+    pub fn merge_conflict_count_async(
+        &self,
+        head: Oid,
+        branch: Oid,
+        proxy: EventLoopProxy<()>,
+    ) -> Receiver<Result<usize, String>> {
+        self.spawn_forecast(proxy, move |repo| repo.merge_conflict_count(head, branch))
+    }
+
+    fn spawn_forecast<T: Send + 'static>(
+        &self,
+        proxy: EventLoopProxy<()>,
+        op: impl FnOnce(&GitRepo) -> Result<T> + Send + 'static,
+    ) -> Receiver<Result<T, String>> {
+        let git_dir = self.repo.path().to_path_buf();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = GitRepo::open(&git_dir)
+                .and_then(|repo| op(&repo))
+                .map_err(|e| format!("{e:#}"));
+            let _ = tx.send(result);
+            let _ = proxy.send_event(());
+        });
+        rx
+    }
+}
+
+/// A second handle on `repo` whose object writes go to memory.
+fn scratch_repo(repo: &Repository) -> Result<Repository> {
+    let scratch = Repository::open(repo.path()).context("Failed to open repository")?;
+    scratch
+        .odb()?
+        .add_new_mempack_backend(1000)
+        .context("Failed to set up in-memory object store")?;
+    Ok(scratch)
+}
+
+/// Up-to-date / fast-forward forecasts, which need no merge. `None`
+/// when the histories diverged.
+fn trivial_forecast(repo: &Repository, head: Oid, other: Oid) -> Result<Option<ConflictForecast>> {
+    if head == other || repo.graph_descendant_of(head, other)? {
+        return Ok(Some(ConflictForecast {
+            up_to_date: true,
+            ..ConflictForecast::default()
+        }));
+    }
+    if repo.graph_descendant_of(other, head)? {
+        return Ok(Some(ConflictForecast {
+            fast_forward: true,
+            ..ConflictForecast::default()
+        }));
+    }
+    Ok(None)
+}
+
+fn conflict_paths(index: &Index) -> Vec<String> {
+    let Ok(conflicts) = index.conflicts() else {
+        return Vec::new();
+    };
+    let mut paths: Vec<String> = conflicts
+        .flatten()
+        .filter_map(|c| c.our.or(c.their).or(c.ancestor))
+        .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+        .collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use git2::Oid;
+    use std::fs;
+    use std::path::Path;
+
+    fn commit_file(repo_dir: &Path, path: &str, contents: &str, message: &str) {
+        fs::write(repo_dir.join(path), contents).expect("write file");
+        run_git(repo_dir, &["add", path]);
+        run_git(repo_dir, &["commit", "-m", message]);
+    }
+
+    fn repo_oid(repo_dir: &Path, rev: &str) -> Oid {
+        Oid::from_str(&run_git(repo_dir, &["rev-parse", rev])).expect("oid")
+    }
+
+    #[test]
+    fn forecasts_merge_and_rebase_conflicts_without_writing_objects() {
+        let repo_dir = temp_dir("forecast");
+        run_git(&repo_dir, &["init", "-b", "main"]);
+        run_git(&repo_dir, &["config", "user.name", "Test"]);
+        run_git(&repo_dir, &["config", "user.email", "test@example.com"]);
+
+        commit_file(&repo_dir, "shared.txt", "base\n", "Base");
+        run_git(&repo_dir, &["branch", "feature"]);
+        run_git(&repo_dir, &["branch", "clean"]);
+        commit_file(&repo_dir, "shared.txt", "main\n", "Main edit");
+
+        run_git(&repo_dir, &["checkout", "-q", "clean"]);
+        commit_file(&repo_dir, "other.txt", "other\n", "Unrelated");
+        run_git(&repo_dir, &["checkout", "-q", "feature"]);
+        commit_file(&repo_dir, "notes.txt", "first\n", "Add notes");
+        commit_file(&repo_dir, "shared.txt", "feature\n", "Feature edit");
+        let stopping = run_git(&repo_dir, &["rev-parse", "HEAD"]);
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let objects_before = run_git(&repo_dir, &["count-objects"]);
+
+        let merge = repo.forecast_merge("main").expect("merge forecast");
+        assert_eq!(merge.conflicts, vec!["shared.txt"]);
+        assert!(
+            repo.forecast_merge("clean")
+                .expect("clean forecast")
+                .is_clean()
+        );
+        assert!(
+            repo.forecast_merge("feature~1")
+                .expect("ancestor")
+                .up_to_date
+        );
+
+        let rebase = repo.forecast_rebase("main").expect("rebase forecast");
+        assert_eq!(rebase.commits, 2);
+        assert_eq!(rebase.conflicts, vec!["shared.txt"]);
+        let (oid, summary) = rebase.stopping_commit.expect("stopping commit");
+        assert_eq!(oid.to_string(), stopping);
+        assert_eq!(summary, "Feature edit");
+        assert!(
+            repo.forecast_rebase("clean")
+                .expect("clean rebase")
+                .is_clean()
+        );
+
+        let head = repo_oid(&repo_dir, "HEAD");
+        assert_eq!(
+            repo.merge_conflict_count(head, repo_oid(&repo_dir, "main"))
+                .expect("main risk"),
+            1
+        );
+        assert_eq!(
+            repo.merge_conflict_count(head, repo_oid(&repo_dir, "clean"))
+                .expect("clean risk"),
+            0
+        );
+
+        assert_eq!(run_git(&repo_dir, &["count-objects"]), objects_before);
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+
+    #[test]
+    fn rebase_forecast_skips_commits_already_upstream() {
+        let repo_dir = temp_dir("forecast-upstream");
+        run_git(&repo_dir, &["init", "-b", "main"]);
+        commit_file(&repo_dir, "shared.txt", "base\n", "Base");
+        run_git(&repo_dir, &["checkout", "-q", "-b", "feature"]);
+        commit_file(&repo_dir, "shared.txt", "fixed\n", "Fix shared");
+        commit_file(&repo_dir, "notes.txt", "notes\n", "Add notes");
+
+        // main picks up the fix, then builds on the same line.
+        run_git(&repo_dir, &["checkout", "-q", "main"]);
+        run_git(&repo_dir, &["cherry-pick", "feature~1"]);
+        commit_file(&repo_dir, "shared.txt", "fixed again\n", "Refine fix");
+        run_git(&repo_dir, &["checkout", "-q", "feature"]);
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let rebase = repo.forecast_rebase("main").expect("rebase forecast");
+        assert!(rebase.is_clean());
+        assert_eq!(rebase.commits, 1);
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
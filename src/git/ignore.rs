@@ -0,0 +1,300 @@
+//! Ignore-rule editing for the staging well: building `.gitignore` /
+//! `.git/info/exclude` patterns from an untracked path, previewing which
+//! untracked files a pattern would hide, and explaining why a path is
+//! ignored (`git check-ignore -v`).
+
+use anyhow::{Context, Result, bail};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+use super::GitRepo;
+
+/// Which pattern to derive from an untracked path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IgnoreKind {
+    /// Just this file: `/path/to/file.log`.
+    File,
+    /// Every file with this extension, anywhere: `*.log`.
+    Extension,
+    /// The file's parent directory: `/path/to/`.
+    Folder,
+}
+
+/// Where a new ignore rule is written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IgnoreDestination {
+    /// The worktree-root `.gitignore` — committed and shared.
+    #[default]
+    Gitignore,
+    /// `.git/info/exclude` — local to this clone, never committed.
+    InfoExclude,
+}
+
+/// The rule that ignores a path, as reported by `git check-ignore -v`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IgnoreReason {
+    /// File the rule lives in, relative to the worktree when inside it
+    /// (`.gitignore`, `build/.gitignore`, `.git/info/exclude`) or
+    /// absolute for `core.excludesFile`.
+    pub source: String,
+    pub line: usize,
+    pub pattern: String,
+}
+
+/// Root-anchored pattern for `path` (a worktree-relative path as
+/// reported by status). `None` when the kind doesn't apply — no
+/// extension, or a file at the root for `Folder`.
+pub fn ignore_pattern(path: &str, kind: IgnoreKind) -> Option<String> {
+    let trimmed = path.trim_end_matches('/');
+    match kind {
+        // A status path ending in `/` is a whole untracked directory.
+        IgnoreKind::File if path.ends_with('/') => Some(format!("/{}/", escape_literal(trimmed))),
+        IgnoreKind::File => Some(format!("/{}", escape_literal(trimmed))),
+        IgnoreKind::Extension => {
+            if path.ends_with('/') {
+                return None;
+            }
+            let ext = Path::new(trimmed).extension()?.to_str()?;
+            Some(format!("*.{}", escape_literal(ext)))
+        }
+        IgnoreKind::Folder => {
+            let (parent, _) = trimmed.rsplit_once('/')?;
+            Some(format!("/{}/", escape_literal(parent)))
+        }
+    }
+}
+
+/// `text` with everything gitignore would read as syntax
+/// backslash-escaped: glob characters, a leading `#` or `!`, and
+/// trailing spaces, which git otherwise strips.
+fn escape_literal(text: &str) -> String {
+    let kept = text.trim_end_matches(' ');
+    let mut out = String::with_capacity(text.len());
+    for (i, c) in kept.chars().enumerate() {
+        if matches!(c, '\\' | '*' | '?' | '[') || (i == 0 && matches!(c, '#' | '!')) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    for _ in kept.len()..text.len() {
+        out.push_str("\\ ");
+    }
+    out
+}
+
+/// `pattern` without surrounding whitespace, except for a trailing
+/// space escaped as `\ `, which is part of the pattern.
+fn trim_pattern(pattern: &str) -> &str {
+    let pattern = pattern.trim_start();
+    let end = pattern.trim_end();
+    match pattern[end.len()..].chars().next() {
+        Some(c) if end.ends_with('\\') => &pattern[..end.len() + c.len_utf8()],
+        _ => end,
+    }
+}
+
+impl GitRepo {
+    /// Which of `untracked` (worktree-relative paths) a new root-level
+    /// `pattern` would hide. Evaluated on a throwaway libgit2 handle
+    /// so the in-memory rule never leaks into this repository's status.
+    pub fn ignore_preview(&self, pattern: &str, untracked: &[String]) -> Result<Vec<String>> {
+        let pattern = trim_pattern(pattern);
+        if pattern.is_empty() {
+            return Ok(Vec::new());
+        }
+        let scratch = git2::Repository::open(self.repo.path())
+            .context("Failed to open repository for ignore preview")?;
+        scratch
+            .add_ignore_rule(pattern)
+            .with_context(|| format!("Invalid ignore pattern: {pattern}"))?;
+        Ok(untracked
+            .iter()
+            .filter(|path| {
+                scratch
+                    .is_path_ignored(path.trim_end_matches('/'))
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Append `pattern` to the chosen ignore file, creating it if
+    /// needed. Returns the file written, or `None` when the exact
+    /// pattern was already present.
+    pub fn add_ignore_pattern(
+        &self,
+        pattern: &str,
+        destination: IgnoreDestination,
+    ) -> Result<Option<PathBuf>> {
+        let pattern = trim_pattern(pattern);
+        if pattern.is_empty() {
+            bail!("Empty ignore pattern");
+        }
+        let file = match destination {
+            IgnoreDestination::Gitignore => self
+                .workdir()
+                .context("Bare repositories have no .gitignore")?
+                .join(".gitignore"),
+            IgnoreDestination::InfoExclude => self.repo.commondir().join("info").join("exclude"),
+        };
+        let existing = match std::fs::read_to_string(&file) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", file.display())),
+        };
+        if existing.lines().any(|line| trim_pattern(line) == pattern) {
+            return Ok(None);
+        }
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut out = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&file)
+            .with_context(|| format!("Failed to open {}", file.display()))?;
+        let separator = if existing.is_empty() || existing.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        writeln!(out, "{separator}{pattern}")
+            .with_context(|| format!("Failed to write {}", file.display()))?;
+        Ok(Some(file))
+    }
+
+    /// Which rule ignores `path`, via `git check-ignore -v`. `None` when
+    /// the path isn't ignored.
+    pub fn ignore_reason(&self, path: &str) -> Result<Option<IgnoreReason>> {
+        let output = self.git_output(["check-ignore", "-v", "--no-index", "--", path])?;
+        // Exit 1 means "not ignored"; anything else is a real failure.
+        match output.status.code() {
+            Some(0) => {}
+            Some(1) => return Ok(None),
+            _ => bail!("{}", String::from_utf8_lossy(&output.stderr).trim()),
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout.lines().next().and_then(parse_check_ignore_line))
+    }
+}
+
+/// Parse one `git check-ignore -v` line: `<source>:<line>:<pattern>\t<path>`.
+/// The pattern may itself contain `:`, so split the source and line
+/// off the front and leave the rest intact.
+fn parse_check_ignore_line(line: &str) -> Option<IgnoreReason> {
+    let (rule, _path) = line.split_once('\t')?;
+    let (source, rest) = rule.split_once(':')?;
+    let (line_no, pattern) = rest.split_once(':')?;
+    Some(IgnoreReason {
+        source: source.to_string(),
+        line: line_no.parse().ok()?,
+        pattern: pattern.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IgnoreDestination, IgnoreKind, ignore_pattern, parse_check_ignore_line};
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn patterns_derive_from_untracked_paths() {
+        assert_eq!(
+            ignore_pattern("logs/run.log", IgnoreKind::File).as_deref(),
+            Some("/logs/run.log")
+        );
+        assert_eq!(
+            ignore_pattern("logs/run.log", IgnoreKind::Extension).as_deref(),
+            Some("*.log")
+        );
+        assert_eq!(
+            ignore_pattern("logs/run.log", IgnoreKind::Folder).as_deref(),
+            Some("/logs/")
+        );
+        assert_eq!(ignore_pattern("Makefile", IgnoreKind::Extension), None);
+        assert_eq!(ignore_pattern("Makefile", IgnoreKind::Folder), None);
+
+        let reason = parse_check_ignore_line(".gitignore:3:*.o\tbuild/a.o").expect("parse");
+        assert_eq!(reason.source, ".gitignore");
+        assert_eq!(reason.line, 3);
+        assert_eq!(reason.pattern, "*.o");
+    }
+
+    #[test]
+    fn added_pattern_previews_and_explains() {
+        let repo_dir = temp_dir("ignore");
+        fs::create_dir_all(repo_dir.join("logs")).expect("create logs dir");
+
+        run_git(&repo_dir, &["init"]);
+        fs::write(repo_dir.join("logs/run.log"), "x\n").expect("write run.log");
+        fs::write(repo_dir.join("keep.txt"), "x\n").expect("write keep.txt");
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let untracked = vec!["keep.txt".to_string(), "logs/run.log".to_string()];
+        let hidden = repo.ignore_preview("*.log", &untracked).expect("preview");
+        assert_eq!(hidden, vec!["logs/run.log".to_string()]);
+        // The preview rule must not leak into the real handle.
+        assert!(repo.ignore_reason("logs/run.log").expect("check").is_none());
+
+        let written = repo
+            .add_ignore_pattern("*.log", IgnoreDestination::InfoExclude)
+            .expect("add pattern");
+        assert!(written.is_some());
+        assert!(
+            repo.add_ignore_pattern("*.log", IgnoreDestination::InfoExclude)
+                .expect("add again")
+                .is_none()
+        );
+        let reason = repo
+            .ignore_reason("logs/run.log")
+            .expect("check")
+            .expect("ignored");
+        assert!(reason.source.ends_with("info/exclude"));
+        assert_eq!(reason.pattern, "*.log");
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+
+    #[test]
+    fn special_characters_in_paths_are_matched_literally() {
+        let repo_dir = temp_dir("ignore");
+        run_git(&repo_dir, &["init"]);
+        let names = [
+            "foo[1].log",
+            "foo1.log",
+            "#notes",
+            "!keep",
+            "a*b",
+            "a-long-b",
+            "q?",
+            "qx",
+            "back\\slash",
+            "backslash",
+            "trail ",
+            "trail",
+        ];
+        for name in names {
+            fs::write(repo_dir.join(name), "x\n").expect("write file");
+        }
+        let untracked: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        for name in names {
+            let pattern = ignore_pattern(name, IgnoreKind::File).expect("pattern");
+            let hidden = repo.ignore_preview(&pattern, &untracked).expect("preview");
+            assert_eq!(hidden, vec![name.to_string()], "pattern {pattern:?}");
+        }
+        assert_eq!(
+            ignore_pattern("dir[2]/x.log", IgnoreKind::Folder).as_deref(),
+            Some("/dir\\[2]/")
+        );
+        assert_eq!(
+            ignore_pattern("weird.l?g", IgnoreKind::Extension).as_deref(),
+            Some("*.l\\?g")
+        );
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
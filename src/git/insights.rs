@@ -0,0 +1,521 @@
+//! Repository insights: who changed what, when, and where — per-author
+//! commit and line counts over time, a commit calendar, churn
+//! hotspots, and per-directory ownership for one ref over a date range.
+//!
+//! Built on the same time-sorted revwalk as the history graph and the
+//! same first-parent diff as the per-commit `+N -M` stats. A full pass
+//! diffs every commit in range, so it runs on a worker thread through
+//! [`GitRepo::compute_insights_async`]; callers cache the result per
+//! tip commit.
+
+use anyhow::{Context, Result};
+use git2::{Oid, Patch, Repository};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc::{self, Receiver};
+use winit::event_loop::EventLoopProxy;
+
+use super::{GitRepo, first_parent_diff, resolve_author};
+
+/// Walk at most this many commits per pass so "All time" on a huge
+/// history still finishes in reasonable time.
+const MAX_INSIGHTS_COMMITS: usize = 20_000;
+/// Number of equal-width slices each author's timeline is split into.
+pub const TIMELINE_BUCKETS: usize = 12;
+const MAX_HOTSPOTS: usize = 25;
+const MAX_OWNERSHIP_DIRS: usize = 20;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Date range an insights pass covers. The presets end now; `Custom`
+/// covers fixed dates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InsightsRange {
+    Last30Days,
+    Last90Days,
+    #[default]
+    LastYear,
+    AllTime,
+    /// Days since the unix epoch (UTC), both ends inclusive.
+    Custom {
+        since_day: i64,
+        until_day: i64,
+    },
+}
+
+impl InsightsRange {
+    pub const ALL: [InsightsRange; 4] = [
+        InsightsRange::Last30Days,
+        InsightsRange::Last90Days,
+        InsightsRange::LastYear,
+        InsightsRange::AllTime,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            InsightsRange::Last30Days => "30 days",
+            InsightsRange::Last90Days => "90 days",
+            InsightsRange::LastYear => "1 year",
+            InsightsRange::AllTime => "All time",
+            InsightsRange::Custom { .. } => "Custom",
+        }
+    }
+
+    /// Stable id used in route keys (`insights:range:<key>`).
+    pub fn key(self) -> &'static str {
+        match self {
+            InsightsRange::Last30Days => "30d",
+            InsightsRange::Last90Days => "90d",
+            InsightsRange::LastYear => "1y",
+            InsightsRange::AllTime => "all",
+            InsightsRange::Custom { .. } => "custom",
+        }
+    }
+
+    /// Preset for `key`; `Custom` carries dates, so it has no key form.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|r| r.key() == key)
+    }
+
+    /// Earliest commit time included (`None` for no lower bound) and
+    /// the end of the covered period, as of `now`.
+    fn bounds(self, now: i64) -> (Option<i64>, i64) {
+        let days = match self {
+            InsightsRange::Last30Days => 30,
+            InsightsRange::Last90Days => 90,
+            InsightsRange::LastYear => 365,
+            InsightsRange::AllTime => return (None, now),
+            InsightsRange::Custom {
+                since_day,
+                until_day,
+            } => {
+                let end = (until_day + 1) * SECONDS_PER_DAY - 1;
+                return (Some(since_day * SECONDS_PER_DAY), end.min(now));
+            }
+        };
+        (Some(now - days * SECONDS_PER_DAY), now)
+    }
+
+    /// Day (since the unix epoch) the range starts on today. The presets
+    /// slide with the clock, so caches key on this alongside the range.
+    pub fn start_day(self) -> Option<i64> {
+        self.bounds(unix_now())
+            .0
+            .map(|since| since.div_euclid(SECONDS_PER_DAY))
+    }
+}
+
+/// Parse a `YYYY-MM-DD` date into days since the unix epoch, using
+/// Hinnant's `days_from_civil` like [`super::format_absolute_time`].
+pub fn parse_day(text: &str) -> Option<i64> {
+    let mut parts = text.trim().splitn(3, '-');
+    let y: i64 = parts.next()?.parse().ok()?;
+    let m: i64 = parts.next()?.parse().ok()?;
+    let d: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+        return None;
+    }
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146097 + doe - 719468)
+}
+
+/// One (mailmap-resolved) author's activity in range.
+#[derive(Clone, Debug, Default)]
+pub struct AuthorStats {
+    pub name: String,
+    pub email: String,
+    pub commits: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Commits per slice of the range, oldest first;
+    /// [`TIMELINE_BUCKETS`] entries.
+    pub timeline: Vec<usize>,
+}
+
+/// How often a file changed in range.
+#[derive(Clone, Debug, Default)]
+pub struct FileChurn {
+    pub path: String,
+    pub commits: usize,
+    /// Lines added plus lines removed.
+    pub lines: usize,
+}
+
+/// Who wrote most of the lines added under a top-level directory.
+#[derive(Clone, Debug, Default)]
+pub struct DirOwnership {
+    /// Top-level directory with a trailing `/`, or `(root)` for files
+    /// at the repository root.
+    pub dir: String,
+    pub owner: String,
+    pub owner_lines: usize,
+    pub total_lines: usize,
+    pub contributors: usize,
+}
+
+impl DirOwnership {
+    /// Owner's share of added lines, 0-100.
+    pub fn owner_percent(&self) -> usize {
+        (self.owner_lines * 100)
+            .checked_div(self.total_lines)
+            .unwrap_or(0)
+    }
+}
+
+/// Result of one insights pass.
+#[derive(Clone, Debug)]
+pub struct RepoInsights {
+    /// Commit the walk started from.
+    pub tip: Oid,
+    pub range: InsightsRange,
+    /// Start and end of the covered period (unix seconds). For
+    /// `AllTime` the start is the oldest commit walked.
+    pub start: i64,
+    pub end: i64,
+    pub commit_count: usize,
+    /// Merge commits are counted but not diffed, matching
+    /// `git log --no-merges --numstat` for line totals.
+    pub merge_count: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Most commits first.
+    pub authors: Vec<AuthorStats>,
+    /// Commits per day, keyed by days since the unix epoch in each
+    /// commit's own timezone.
+    pub calendar: BTreeMap<i64, usize>,
+    /// Most frequently changed files first.
+    pub hotspots: Vec<FileChurn>,
+    /// Most added lines first.
+    pub ownership: Vec<DirOwnership>,
+    /// `true` when the walk stopped at the commit cap before reaching
+    /// the start of the range.
+    pub truncated: bool,
+}
+
+impl RepoInsights {
+    /// Start time of timeline slice `idx`.
+    pub fn bucket_start(&self, idx: usize) -> i64 {
+        self.start + bucket_width(self.start, self.end) * idx as i64
+    }
+}
+
+fn bucket_width(start: i64, end: i64) -> i64 {
+    ((end - start) / TIMELINE_BUCKETS as i64).max(1)
+}
+
+impl GitRepo {
+    /// Resolve `rev` to the commit an insights pass would start from,
+    /// for cache lookups before spawning a worker.
+    pub fn insights_tip(&self, rev: &str) -> Result<Oid> {
+        let commit = self
+            .repo
+            .revparse_single(rev)
+            .and_then(|obj| obj.peel_to_commit())
+            .with_context(|| format!("Unknown ref {rev}"))?;
+        Ok(commit.id())
+    }
+
+    /// Spawn a worker computing insights for `tip` over `range`. The
+    /// receiver yields exactly once; the proxy wakes the UI when it
+    /// does.
+    pub fn compute_insights_async(
+        &self,
+        tip: Oid,
+        range: InsightsRange,
+        proxy: EventLoopProxy<()>,
+    ) -> Receiver<Result<RepoInsights, String>> {
+        crate::crash_log::breadcrumb(format!("insights_async: {tip} {}", range.key()));
+        let repo_path = self.repo.path().to_path_buf();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = Repository::open(&repo_path)
+                .context("Failed to open repository")
+                .and_then(|repo| compute_insights_for(&repo, tip, range, unix_now()))
+                .map_err(|e| format!("{e:#}"));
+            let _ = tx.send(result);
+            let _ = proxy.send_event(());
+        });
+        rx
+    }
+
+    /// Synchronous variant of [`Self::compute_insights_async`].
+    pub fn compute_insights_sync(&self, tip: Oid, range: InsightsRange) -> Result<RepoInsights> {
+        compute_insights_for(&self.repo, tip, range, unix_now())
+    }
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Per-author accumulator; commit times are kept so the timeline can
+/// be bucketed once the actual start of the range is known.
+#[derive(Default)]
+struct AuthorAccum {
+    stats: AuthorStats,
+    times: Vec<i64>,
+}
+
+fn compute_insights_for(
+    repo: &Repository,
+    tip: Oid,
+    range: InsightsRange,
+    now: i64,
+) -> Result<RepoInsights> {
+    let (since, end) = range.bounds(now);
+    let mut revwalk = repo.revwalk().context("Failed to create revwalk")?;
+    revwalk.push(tip).context("Failed to start revwalk")?;
+    revwalk.set_sorting(git2::Sort::TIME)?;
+    let mailmap = repo.mailmap().ok();
+
+    let mut insights = RepoInsights {
+        tip,
+        range,
+        start: end,
+        end,
+        commit_count: 0,
+        merge_count: 0,
+        insertions: 0,
+        deletions: 0,
+        authors: Vec::new(),
+        calendar: BTreeMap::new(),
+        hotspots: Vec::new(),
+        ownership: Vec::new(),
+        truncated: false,
+    };
+    let mut authors: HashMap<String, AuthorAccum> = HashMap::new();
+    let mut files: HashMap<String, FileChurn> = HashMap::new();
+    // dir -> author key -> lines added
+    let mut dirs: HashMap<String, HashMap<String, usize>> = HashMap::new();
+    let mut oldest = end;
+
+    for oid in revwalk {
+        if insights.commit_count >= MAX_INSIGHTS_COMMITS {
+            insights.truncated = true;
+            break;
+        }
+        let Ok(oid) = oid else { continue };
+        let Ok(commit) = repo.find_commit(oid) else {
+            continue;
+        };
+        let time = commit.time();
+        // TIME sorting walks newest-first, so the first commit before
+        // the range ends it; commits after a custom end are skipped.
+        if since.is_some_and(|s| time.seconds() < s) {
+            break;
+        }
+        if time.seconds() > end {
+            continue;
+        }
+        insights.commit_count += 1;
+        oldest = oldest.min(time.seconds());
+        let day = (time.seconds() + time.offset_minutes() as i64 * 60).div_euclid(SECONDS_PER_DAY);
+        *insights.calendar.entry(day).or_default() += 1;
+
+        let author = resolve_author(&commit, mailmap.as_ref());
+        let author_key = author.email.to_lowercase();
+        let accum = authors.entry(author_key.clone()).or_default();
+        if accum.stats.commits == 0 {
+            accum.stats.name = author.name;
+            accum.stats.email = author.email;
+        }
+        accum.stats.commits += 1;
+        accum.times.push(time.seconds());
+
+        if commit.parent_count() > 1 {
+            insights.merge_count += 1;
+            continue;
+        }
+        let Some(diff) = first_parent_diff(repo, &commit) else {
+            continue;
+        };
+        for idx in 0..diff.deltas().len() {
+            let Some(delta) = diff.get_delta(idx) else {
+                continue;
+            };
+            let Some(path) = delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .map(|p| p.to_string_lossy().into_owned())
+            else {
+                continue;
+            };
+            let (added, removed) = match Patch::from_diff(&diff, idx) {
+                Ok(Some(patch)) => patch
+                    .line_stats()
+                    .map(|(_, added, removed)| (added, removed))
+                    .unwrap_or((0, 0)),
+                _ => (0, 0),
+            };
+            accum.stats.insertions += added;
+            accum.stats.deletions += removed;
+            insights.insertions += added;
+            insights.deletions += removed;
+
+            let dir = match path.split_once('/') {
+                Some((top, _)) => format!("{top}/"),
+                None => "(root)".to_string(),
+            };
+            *dirs
+                .entry(dir)
+                .or_default()
+                .entry(author_key.clone())
+                .or_default() += added;
+            let churn = files.entry(path.clone()).or_insert_with(|| FileChurn {
+                path,
+                ..Default::default()
+            });
+            churn.commits += 1;
+            churn.lines += added + removed;
+        }
+    }
+
+    insights.start = since.unwrap_or(oldest);
+    let width = bucket_width(insights.start, insights.end);
+    let mut author_list: Vec<AuthorStats> = Vec::with_capacity(authors.len());
+    let mut names: HashMap<String, String> = HashMap::new();
+    for (key, accum) in authors {
+        let mut stats = accum.stats;
+        stats.timeline = vec![0; TIMELINE_BUCKETS];
+        for t in accum.times {
+            let idx = ((t - insights.start) / width).clamp(0, TIMELINE_BUCKETS as i64 - 1);
+            stats.timeline[idx as usize] += 1;
+        }
+        names.insert(key, stats.name.clone());
+        author_list.push(stats);
+    }
+    author_list.sort_by(|a, b| b.commits.cmp(&a.commits).then(a.name.cmp(&b.name)));
+    insights.authors = author_list;
+
+    let mut hotspots: Vec<FileChurn> = files.into_values().collect();
+    hotspots.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then(b.lines.cmp(&a.lines))
+            .then(a.path.cmp(&b.path))
+    });
+    hotspots.truncate(MAX_HOTSPOTS);
+    insights.hotspots = hotspots;
+
+    let mut ownership: Vec<DirOwnership> = dirs
+        .into_iter()
+        .filter_map(|(dir, by_author)| {
+            let total_lines: usize = by_author.values().sum();
+            let (owner_key, owner_lines) = by_author
+                .iter()
+                .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))?;
+            Some(DirOwnership {
+                dir,
+                owner: names.get(owner_key).cloned().unwrap_or_default(),
+                owner_lines: *owner_lines,
+                total_lines,
+                contributors: by_author.values().filter(|n| **n > 0).count(),
+            })
+        })
+        .filter(|d| d.total_lines > 0)
+        .collect();
+    ownership.sort_by(|a, b| b.total_lines.cmp(&a.total_lines).then(a.dir.cmp(&b.dir)));
+    ownership.truncate(MAX_OWNERSHIP_DIRS);
+    insights.ownership = ownership;
+
+    Ok(insights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InsightsRange, parse_day};
+    use crate::git::test_support::{run_git, temp_dir};
+    use crate::git::{GitRepo, format_absolute_time};
+    use std::fs;
+    use std::path::Path;
+
+    fn commit_as(dir: &Path, name: &str, message: &str) {
+        let email = format!("user.email={}@example.com", name.to_lowercase());
+        let name = format!("user.name={name}");
+        run_git(dir, &["-c", &name, "-c", &email, "commit", "-qam", message]);
+    }
+
+    #[test]
+    fn parse_day_round_trips_through_absolute_time() {
+        assert_eq!(parse_day("1970-01-01"), Some(0));
+        assert_eq!(parse_day("1970-01-02"), Some(1));
+        let day = parse_day("2024-02-29").expect("leap day");
+        assert!(format_absolute_time(day * 86_400).starts_with("2024-02-29"));
+        assert_eq!(parse_day("2024-13-01"), None);
+        assert_eq!(parse_day("yesterday"), None);
+    }
+
+    #[test]
+    fn insights_count_authors_churn_and_ownership() {
+        let repo_dir = temp_dir("insights");
+        fs::create_dir_all(repo_dir.join("src")).expect("create src dir");
+
+        run_git(&repo_dir, &["init"]);
+        fs::write(repo_dir.join("src/lib.rs"), "a\nb\nc\n").expect("write lib.rs");
+        fs::write(repo_dir.join("README"), "hi\n").expect("write README");
+        run_git(&repo_dir, &["add", "."]);
+        commit_as(&repo_dir, "Alice", "init");
+        fs::write(repo_dir.join("src/lib.rs"), "a\nb\nc\nd\n").expect("write lib.rs");
+        commit_as(&repo_dir, "Bob", "add d");
+        fs::write(repo_dir.join("src/lib.rs"), "a\nB\nc\nd\n").expect("write lib.rs");
+        commit_as(&repo_dir, "Alice", "fix b");
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let tip = repo.insights_tip("HEAD").expect("tip");
+        let insights = repo
+            .compute_insights_sync(tip, InsightsRange::Last30Days)
+            .expect("insights");
+
+        assert_eq!(insights.commit_count, 3);
+        assert_eq!(insights.insertions, 6);
+        assert_eq!(insights.deletions, 1);
+        assert_eq!(insights.calendar.values().sum::<usize>(), 3);
+
+        assert_eq!(insights.authors.len(), 2);
+        assert_eq!(insights.authors[0].name, "Alice");
+        assert_eq!(insights.authors[0].commits, 2);
+        assert_eq!(insights.authors[0].timeline.iter().sum::<usize>(), 2);
+
+        assert_eq!(insights.hotspots[0].path, "src/lib.rs");
+        assert_eq!(insights.hotspots[0].commits, 3);
+
+        let src = insights
+            .ownership
+            .iter()
+            .find(|d| d.dir == "src/")
+            .expect("src ownership");
+        assert_eq!(src.owner, "Alice");
+        assert_eq!(src.total_lines, 5);
+        assert_eq!(src.contributors, 2);
+        assert_eq!(src.owner_percent(), 80);
+
+        let today = InsightsRange::Last30Days.start_day().expect("start day") + 30;
+        let this_week = InsightsRange::Custom {
+            since_day: today - 7,
+            until_day: today,
+        };
+        let insights = repo
+            .compute_insights_sync(tip, this_week)
+            .expect("custom insights");
+        assert_eq!(insights.commit_count, 3);
+        let long_ago = InsightsRange::Custom {
+            since_day: parse_day("1999-01-01").expect("parse since"),
+            until_day: parse_day("1999-12-31").expect("parse until"),
+        };
+        let insights = repo
+            .compute_insights_sync(tip, long_ago)
+            .expect("past insights");
+        assert_eq!(insights.commit_count, 0);
+
+        assert!(repo.insights_tip("no-such-ref").is_err());
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
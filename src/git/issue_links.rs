@@ -0,0 +1,145 @@
+//! Issue references in commit messages (`#123`, `GH-45`, `PROJ-42`)
+//! and the per-repository patterns that turn them into links.
+//!
+//! Patterns live in the repository's local config as a multi-valued
+//! `whisper.issueLink` key, one `<prefix> [<url template>]` per value:
+//!
+//! ```text
+//! [whisper]
+//!     issueLink = PROJ- https://jira.corp.example/browse/{ref}
+//!     issueLink = #
+//! ```
+//!
+//! A prefix without a template links to the `origin` forge's issues.
+
+/// Multi-valued config key holding the patterns.
+pub const ISSUE_LINK_KEY: &str = "whisper.issuelink";
+
+/// One reference shape: a literal prefix followed by a number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IssueLinkPattern {
+    /// Text before the number, e.g. "#", "GH-" or "PROJ-".
+    pub prefix: String,
+    /// Link with `{ref}` (the whole reference) and `{number}`
+    /// placeholders. `None` links to the origin forge's issue, whose
+    /// title and state can then be fetched.
+    pub url: Option<String>,
+}
+
+impl IssueLinkPattern {
+    /// Parse one config value or modal line. Blank lines yield `None`.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut parts = line.split_whitespace();
+        let prefix = parts.next()?.to_string();
+        let url = parts.next().map(str::to_string);
+        Some(Self { prefix, url })
+    }
+
+    /// Inverse of [`Self::parse`].
+    pub fn to_line(&self) -> String {
+        match &self.url {
+            Some(url) => format!("{} {url}", self.prefix),
+            None => self.prefix.clone(),
+        }
+    }
+
+    /// Fill this pattern's template for `reference`, if it has one.
+    pub fn url_for(&self, reference: &IssueRef) -> Option<String> {
+        let template = self.url.as_ref()?;
+        Some(
+            template
+                .replace("{ref}", &reference.text)
+                .replace("{number}", &reference.number.to_string()),
+        )
+    }
+}
+
+/// A reference found in a message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IssueRef {
+    /// As written, e.g. "PROJ-42".
+    pub text: String,
+    pub number: u64,
+    /// Index of the pattern it matched.
+    pub pattern: usize,
+}
+
+/// Every distinct reference to one of `patterns` in `message`, in order
+/// of first appearance. A reference must stand on its own: `abc#1` and
+/// `#12a` don't count. Where prefixes overlap the longest one wins.
+pub fn find_issue_refs(message: &str, patterns: &[IssueLinkPattern]) -> Vec<IssueRef> {
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let bytes = message.as_bytes();
+    let mut refs: Vec<IssueRef> = Vec::new();
+    for (start, _) in message.char_indices() {
+        if start > 0 && is_word(bytes[start - 1]) {
+            continue;
+        }
+        let rest = &message[start..];
+        let Some((idx, pattern)) = patterns
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| !p.prefix.is_empty() && rest.starts_with(&p.prefix))
+            .max_by_key(|(_, p)| p.prefix.len())
+        else {
+            continue;
+        };
+        let digits_start = pattern.prefix.len();
+        let digits = rest[digits_start..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        let end = digits_start + digits;
+        if digits == 0 || rest.as_bytes().get(end).is_some_and(|&b| is_word(b)) {
+            continue;
+        }
+        let Ok(number) = rest[digits_start..end].parse() else {
+            continue;
+        };
+        let text = &rest[..end];
+        if !refs.iter().any(|r| r.text == text) {
+            refs.push(IssueRef {
+                text: text.to_string(),
+                number,
+                pattern: idx,
+            });
+        }
+    }
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_standalone_references() {
+        let patterns: Vec<IssueLinkPattern> =
+            ["#", "GH-", "PROJ- https://jira.example/browse/{ref}"]
+                .into_iter()
+                .filter_map(IssueLinkPattern::parse)
+                .collect();
+        let refs = find_issue_refs(
+            "Fix crash (#12), see GH-45 and PROJ-7; not abc#3, #4a or XPROJ-9. Again #12",
+            &patterns,
+        );
+        let found: Vec<(&str, u64, usize)> = refs
+            .iter()
+            .map(|r| (r.text.as_str(), r.number, r.pattern))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("#12", 12, 0), ("GH-45", 45, 1), ("PROJ-7", 7, 2)]
+        );
+        assert_eq!(
+            patterns[2].url_for(&refs[2]).as_deref(),
+            Some("https://jira.example/browse/PROJ-7")
+        );
+        assert_eq!(patterns[0].url_for(&refs[0]), None);
+        assert_eq!(
+            patterns[2].to_line(),
+            "PROJ- https://jira.example/browse/{ref}"
+        );
+        assert_eq!(IssueLinkPattern::parse("   "), None);
+    }
+}
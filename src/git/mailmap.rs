@@ -0,0 +1,116 @@
+//! `.mailmap` resolution for commit authors, so people who changed
+//! names or emails show up as one author (one identicon color, one
+//! Gravatar) across history.
+//!
+//! libgit2 loads the same sources `git log --use-mailmap` does: the
+//! worktree `.mailmap`, `mailmap.file`, and `mailmap.blob` (which
+//! defaults to `HEAD:.mailmap` in bare repositories).
+
+use git2::{Commit, Mailmap};
+
+use super::GitRepo;
+
+/// A commit author after mailmap resolution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolvedAuthor {
+    pub name: String,
+    pub email: String,
+    /// `Name <email>` exactly as recorded in the commit, when the
+    /// mailmap rewrote it. `None` when the recorded identity is
+    /// already canonical.
+    pub raw: Option<String>,
+}
+
+impl GitRepo {
+    /// The repository's mailmap, or `None` when it can't be loaded
+    /// (e.g. a malformed `mailmap.blob`). An absent `.mailmap` still
+    /// yields an empty map that resolves every identity to itself.
+    pub fn mailmap(&self) -> Option<Mailmap> {
+        self.repo.mailmap().ok()
+    }
+}
+
+/// Resolve `commit`'s author through `mailmap`, falling back to the
+/// recorded identity when there is no mailmap or it has no entry.
+pub fn resolve_author(commit: &Commit, mailmap: Option<&Mailmap>) -> ResolvedAuthor {
+    let recorded = commit.author();
+    let raw_name = recorded.name().unwrap_or("Unknown");
+    let raw_email = recorded.email().unwrap_or("");
+    let mapped = mailmap.and_then(|m| commit.author_with_mailmap(m).ok());
+    let (name, email) = match &mapped {
+        Some(sig) => (
+            sig.name().unwrap_or(raw_name),
+            sig.email().unwrap_or(raw_email),
+        ),
+        None => (raw_name, raw_email),
+    };
+    let raw = (name != raw_name || email != raw_email).then(|| format!("{raw_name} <{raw_email}>"));
+    ResolvedAuthor {
+        name: name.to_string(),
+        email: email.to_string(),
+        raw,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn mailmap_merges_old_identity_into_canonical() {
+        let repo_dir = temp_dir("mailmap");
+
+        run_git(&repo_dir, &["init"]);
+        fs::write(repo_dir.join("a.txt"), "one\n").expect("write a.txt");
+        run_git(&repo_dir, &["add", "a.txt"]);
+        run_git(
+            &repo_dir,
+            &[
+                "-c",
+                "user.name=Old Name",
+                "-c",
+                "user.email=old@example.com",
+                "commit",
+                "-m",
+                "old",
+            ],
+        );
+        fs::write(
+            repo_dir.join(".mailmap"),
+            "New Name <new@example.com> <old@example.com>\n",
+        )
+        .expect("write .mailmap");
+        run_git(&repo_dir, &["add", ".mailmap"]);
+        run_git(
+            &repo_dir,
+            &[
+                "-c",
+                "user.name=New Name",
+                "-c",
+                "user.email=new@example.com",
+                "commit",
+                "-m",
+                "new",
+            ],
+        );
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let commits = repo.commit_graph(10).expect("commit graph");
+        assert_eq!(commits.len(), 2);
+        assert!(commits.iter().all(|c| c.author == "New Name"));
+        assert!(commits.iter().all(|c| c.author_email == "new@example.com"));
+        assert_eq!(commits[0].raw_author, None);
+        assert_eq!(
+            commits[1].raw_author.as_deref(),
+            Some("Old Name <old@example.com>")
+        );
+
+        let info = repo.full_commit_info(commits[1].id).expect("full info");
+        assert_eq!(info.author_email, "new@example.com");
+        assert!(info.raw_author.is_some());
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
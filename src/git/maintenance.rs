@@ -0,0 +1,377 @@
+//! Repository health report and housekeeping commands.
+//!
+//! The report combines `git count-objects -v`, the on-disk size of the
+//! git directory, the largest blobs reachable from any ref (with the
+//! commit and path that introduced each), and the dangling objects
+//! `git fsck` finds. Building it walks every object, so the UI runs it
+//! through [`GitRepo::repo_health_async`]. The housekeeping commands
+//! (`gc`, `maintenance run`, `fsck`, `prune`) likewise run on a worker
+//! thread with their combined output captured for the panel's log.
+
+use anyhow::{Context, Result};
+use git2::{ObjectType, Oid};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+use winit::event_loop::EventLoopProxy;
+
+use super::GitRepo;
+
+/// Blobs listed in the report's "largest blobs" table.
+const MAX_LARGE_BLOBS: usize = 10;
+/// Dangling objects listed individually; the rest are only counted.
+const MAX_DANGLING_LISTED: usize = 200;
+
+/// Snapshot of a repository's object store.
+#[derive(Clone, Debug, Default)]
+pub struct RepoHealth {
+    pub loose_objects: u64,
+    /// Disk used by loose objects, in bytes.
+    pub loose_size: u64,
+    pub packed_objects: u64,
+    pub packs: u64,
+    /// Disk used by packs, in bytes.
+    pub pack_size: u64,
+    /// Files in the object directory git doesn't recognise.
+    pub garbage: u64,
+    /// Everything under the git directory, in bytes.
+    pub repo_size: u64,
+    /// Largest blobs reachable from any ref, biggest first.
+    pub largest_blobs: Vec<LargeBlob>,
+    /// Up to [`MAX_DANGLING_LISTED`] dangling objects.
+    pub dangling: Vec<DanglingObject>,
+    /// All dangling objects, including those not listed.
+    pub dangling_count: usize,
+}
+
+impl RepoHealth {
+    pub fn object_count(&self) -> u64 {
+        self.loose_objects + self.packed_objects
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LargeBlob {
+    pub oid: Oid,
+    pub size: u64,
+    /// A path the blob is reachable under.
+    pub path: String,
+    /// Oldest commit that added the blob, with its summary.
+    pub introduced_by: Option<(Oid, String)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DanglingObject {
+    /// `commit`, `tree`, `blob` or `tag`.
+    pub kind: String,
+    pub oid: Oid,
+}
+
+/// A housekeeping command the maintenance panel can run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaintenanceTask {
+    Gc,
+    Maintenance,
+    Fsck,
+    Prune,
+}
+
+impl MaintenanceTask {
+    pub const ALL: [Self; 4] = [Self::Gc, Self::Maintenance, Self::Fsck, Self::Prune];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Gc => "gc",
+            Self::Maintenance => "maintenance",
+            Self::Fsck => "fsck",
+            Self::Prune => "prune",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.key() == key)
+    }
+
+    /// Command line as shown to the user.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Gc => "git gc",
+            Self::Maintenance => "git maintenance run",
+            Self::Fsck => "git fsck",
+            Self::Prune => "git prune",
+        }
+    }
+
+    fn args(self) -> &'static [&'static str] {
+        match self {
+            Self::Gc => &["gc"],
+            Self::Maintenance => &["maintenance", "run"],
+            Self::Fsck => &["fsck", "--no-progress"],
+            Self::Prune => &["prune", "--verbose"],
+        }
+    }
+}
+
+/// Result of one housekeeping run.
+#[derive(Clone, Debug)]
+pub struct MaintenanceOutput {
+    pub task: MaintenanceTask,
+    pub success: bool,
+    /// stdout followed by stderr, trimmed.
+    pub output: String,
+    pub elapsed: Duration,
+}
+
+impl GitRepo {
+    /// Build the health report on a background thread.
+    pub fn repo_health_async(
+        &self,
+        proxy: EventLoopProxy<()>,
+    ) -> Receiver<Result<RepoHealth, String>> {
+        crate::crash_log::breadcrumb("repo_health_async".to_string());
+        let repo_path = self.repo.path().to_path_buf();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = GitRepo::open(&repo_path)
+                .and_then(|repo| repo_health_for(&repo))
+                .map_err(|e| format!("{e:#}"));
+            let _ = tx.send(result);
+            let _ = proxy.send_event(());
+        });
+        rx
+    }
+
+    /// Synchronous variant of [`Self::repo_health_async`].
+    pub fn repo_health_sync(&self) -> Result<RepoHealth> {
+        repo_health_for(self)
+    }
+
+    /// Run `task` on a background thread, capturing its output.
+    pub fn run_maintenance_async(
+        &self,
+        task: MaintenanceTask,
+        proxy: EventLoopProxy<()>,
+    ) -> Receiver<MaintenanceOutput> {
+        crate::crash_log::breadcrumb(format!("maintenance_async: {}", task.key()));
+        let git_dir = self.repo.path().to_path_buf();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let output = run_maintenance(&git_dir, task);
+            crate::crash_log::breadcrumb(format!(
+                "maintenance_async done: {} success={}",
+                task.key(),
+                output.success
+            ));
+            let _ = tx.send(output);
+            let _ = proxy.send_event(());
+        });
+        rx
+    }
+}
+
+fn run_maintenance(git_dir: &Path, task: MaintenanceTask) -> MaintenanceOutput {
+    let started = Instant::now();
+    let result = Command::new("git")
+        .arg("--git-dir")
+        .arg(git_dir)
+        .args(task.args())
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output();
+    let (success, output) = match result {
+        Ok(out) => {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            let combined = [stdout.trim(), stderr.trim()]
+                .into_iter()
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n");
+            (out.status.success(), combined)
+        }
+        Err(e) => (false, format!("Failed to run {}: {e}", task.label())),
+    };
+    MaintenanceOutput {
+        task,
+        success,
+        output,
+        elapsed: started.elapsed(),
+    }
+}
+
+fn repo_health_for(repo: &GitRepo) -> Result<RepoHealth> {
+    let mut health = RepoHealth::default();
+
+    // `count-objects -v` reports sizes in KiB.
+    for line in repo.git_stdout(["count-objects", "-v"])?.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value: u64 = value.trim().parse().unwrap_or(0);
+        match key {
+            "count" => health.loose_objects = value,
+            "size" => health.loose_size = value * 1024,
+            "in-pack" => health.packed_objects = value,
+            "packs" => health.packs = value,
+            "size-pack" => health.pack_size = value * 1024,
+            "garbage" => health.garbage = value,
+            _ => {}
+        }
+    }
+    health.repo_size = dir_size(repo.repo.commondir());
+    health.largest_blobs = largest_blobs(repo)?;
+
+    let fsck = repo.git_stdout(["fsck", "--dangling", "--connectivity-only", "--no-progress"])?;
+    for line in fsck.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some("dangling"), Some(kind), Some(oid)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let Ok(oid) = Oid::from_str(oid) else {
+            continue;
+        };
+        health.dangling_count += 1;
+        if health.dangling.len() < MAX_DANGLING_LISTED {
+            health.dangling.push(DanglingObject {
+                kind: kind.to_string(),
+                oid,
+            });
+        }
+    }
+    Ok(health)
+}
+
+/// Largest blobs reachable from any ref, using object headers so the
+/// blob contents are never inflated.
+fn largest_blobs(repo: &GitRepo) -> Result<Vec<LargeBlob>> {
+    let odb = repo.repo.odb().context("Failed to open object database")?;
+    let objects = repo.git_stdout(["rev-list", "--objects", "--all"])?;
+    let mut blobs: Vec<LargeBlob> = Vec::new();
+    for line in objects.lines() {
+        // Commits are listed without a path; trees and blobs with one.
+        let Some((oid, path)) = line.split_once(' ') else {
+            continue;
+        };
+        let Ok(oid) = Oid::from_str(oid) else {
+            continue;
+        };
+        let Ok((size, ObjectType::Blob)) = odb.read_header(oid) else {
+            continue;
+        };
+        let size = size as u64;
+        if blobs.len() == MAX_LARGE_BLOBS && blobs.last().is_some_and(|b| b.size >= size) {
+            continue;
+        }
+        let at = blobs.partition_point(|b| b.size >= size);
+        blobs.insert(
+            at,
+            LargeBlob {
+                oid,
+                size,
+                path: path.to_string(),
+                introduced_by: None,
+            },
+        );
+        blobs.truncate(MAX_LARGE_BLOBS);
+    }
+    for blob in &mut blobs {
+        let oid = blob.oid.to_string();
+        let find = format!("--find-object={oid}");
+        let log = repo
+            .git_stdout(["log", "--all", "--reverse", "--format=%H%x00%s", &find])
+            .unwrap_or_default();
+        blob.introduced_by = log.lines().next().and_then(|line| {
+            let (sha, summary) = line.split_once('\0')?;
+            Some((Oid::from_str(sha).ok()?, summary.to_string()))
+        });
+    }
+    Ok(blobs)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let mut total = 0;
+    let mut stack: Vec<PathBuf> = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                stack.push(entry.path());
+            } else {
+                total += meta.len();
+            }
+        }
+    }
+    total
+}
+
+/// `1536` → `"1.5 KiB"`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MaintenanceTask, format_bytes, run_maintenance};
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn health_reports_large_blobs_and_dangling_objects() {
+        let repo_dir = temp_dir("maintenance");
+        run_git(&repo_dir, &["init"]);
+        run_git(&repo_dir, &["config", "user.name", "Test"]);
+        run_git(&repo_dir, &["config", "user.email", "test@example.com"]);
+
+        fs::write(repo_dir.join("small.txt"), "small\n").expect("write small");
+        run_git(&repo_dir, &["add", "small.txt"]);
+        run_git(&repo_dir, &["commit", "-m", "Add small file"]);
+        fs::write(repo_dir.join("big.bin"), "x".repeat(64 * 1024)).expect("write big");
+        run_git(&repo_dir, &["add", "big.bin"]);
+        run_git(&repo_dir, &["commit", "-m", "Add big file"]);
+        let big_commit = run_git(&repo_dir, &["rev-parse", "HEAD"]);
+        fs::write(repo_dir.join("orphan.txt"), "nobody points here\n").expect("write orphan");
+        let orphan = run_git(&repo_dir, &["hash-object", "-w", "orphan.txt"]);
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let health = repo.repo_health_sync().expect("health");
+        assert!(health.object_count() >= 7);
+        assert!(health.repo_size > 0);
+        let biggest = &health.largest_blobs[0];
+        assert_eq!(biggest.path, "big.bin");
+        assert_eq!(biggest.size, 64 * 1024);
+        let (introduced, summary) = biggest.introduced_by.clone().expect("introducing commit");
+        assert_eq!(introduced.to_string(), big_commit);
+        assert_eq!(summary, "Add big file");
+        assert!(
+            health
+                .dangling
+                .iter()
+                .any(|d| d.kind == "blob" && d.oid.to_string() == orphan)
+        );
+
+        let fsck = run_maintenance(&repo_dir.join(".git"), MaintenanceTask::Fsck);
+        assert!(fsck.success, "{}", fsck.output);
+        assert_eq!(MaintenanceTask::from_key("gc"), Some(MaintenanceTask::Gc));
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
@@ -4,14 +4,53 @@
 //! and synthetic commit entries for visualizing dirty worktrees in the commit graph.
 
 mod async_ops;
+mod bundle;
+mod changelog;
+mod codeowners;
 mod diff;
+mod forecast;
 mod hunk;
+mod ignore;
+mod insights;
+mod issue_links;
+mod mailmap;
+mod maintenance;
+mod notes;
+mod patch;
+mod pull_draft;
 mod refs;
+mod repo_config;
+mod restore;
 mod status;
+mod tags;
+#[cfg(test)]
+pub(crate) mod test_support;
 
 pub use async_ops::*;
+pub use bundle::{BundleInfo, BundlePlan, BundlePrerequisite, BundleRef};
+pub use changelog::{
+    Changelog, ChangelogEntry, ChangelogGrouping, ChangelogLinks, ChangelogSection,
+};
+pub use codeowners::{CodeOwners, ReviewSummary, codeowners_in_workdir};
 pub use diff::{DiffFile, DiffHunk, DiffLine};
+pub use forecast::ConflictForecast;
+pub use ignore::{IgnoreDestination, IgnoreKind, IgnoreReason, ignore_pattern};
+pub use insights::{
+    AuthorStats, DirOwnership, FileChurn, InsightsRange, RepoInsights, TIMELINE_BUCKETS, parse_day,
+};
+pub use issue_links::{ISSUE_LINK_KEY, IssueLinkPattern, IssueRef, find_issue_refs};
+pub use mailmap::{ResolvedAuthor, resolve_author};
+pub use maintenance::{
+    DanglingObject, LargeBlob, MaintenanceOutput, MaintenanceTask, RepoHealth, format_bytes,
+};
+pub use notes::{CommitNote, DEFAULT_NOTES_REF, NOTES_REFSPEC, short_notes_ref};
+pub use patch::{PatchFileStat, PatchPreview, PatchRange};
+pub use pull_draft::PullRequestDraft;
+pub use repo_config::{
+    CONFIG_FIELDS, ConfigField, ConfigScope, remote_host_path, remote_matches_pattern,
+};
 pub use status::{FileStatus, FileStatusKind, WorkingDirStatus, working_dir_status_from_statuses};
+pub use tags::{TagSort, TagVersion, sort_tags};
 
 use anyhow::{Context, Result};
 use git2::{Commit, Oid, Repository, RepositoryState};
@@ -251,8 +290,12 @@ pub struct CommitInfo {
     pub body_excerpt: Option<String>,
     /// Full body text (all lines after summary), for tooltips.
     pub body_full: Option<String>,
+    /// Author name and email after `.mailmap` resolution.
     pub author: String,
     pub author_email: String,
+    /// `Name <email>` as recorded in the commit, when the mailmap
+    /// rewrote it.
+    pub raw_author: Option<String>,
     pub time: i64,
     pub parent_ids: Vec<Oid>,
     /// Number of lines inserted in this commit (0 if not computed)
@@ -270,7 +313,7 @@ pub struct CommitInfo {
 }
 
 impl CommitInfo {
-    fn from_commit(commit: &Commit) -> Self {
+    fn from_commit(commit: &Commit, mailmap: Option<&git2::Mailmap>) -> Self {
         // Extract the first non-empty body line after the summary
         let (body_excerpt, body_full) = match commit.message() {
             Some(msg) => {
@@ -295,14 +338,16 @@ impl CommitInfo {
             }
             None => (None, None),
         };
+        let author = resolve_author(commit, mailmap);
         Self {
             id: commit.id(),
             short_id: commit.id().to_string().get(..7).unwrap_or("").to_string(),
             summary: commit.summary().unwrap_or("").to_string(),
             body_excerpt,
             body_full,
-            author: commit.author().name().unwrap_or("Unknown").to_string(),
-            author_email: commit.author().email().unwrap_or("").to_string(),
+            author: author.name,
+            author_email: author.email,
+            raw_author: author.raw,
             time: commit.time().seconds(),
             parent_ids: commit.parent_ids().collect(),
             insertions: 0,
@@ -363,6 +408,7 @@ impl CommitInfo {
             body_full: None,
             author: String::new(),
             author_email: String::new(),
+            raw_author: None,
             time,
             parent_ids: vec![head],
             insertions: 0,
@@ -407,6 +453,7 @@ impl CommitInfo {
             body_full: None,
             author: String::new(),
             author_email: String::new(),
+            raw_author: None,
             time,
             parent_ids: vec![head_oid],
             insertions: 0,
@@ -583,12 +630,13 @@ impl GitRepo {
         // Sort topologically for better graph layout
         revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
 
+        let mailmap = self.mailmap();
         let commits: Vec<CommitInfo> = revwalk
             .take(max_commits)
             .filter_map(|oid| {
                 let oid = oid.ok()?;
                 let commit = self.repo.find_commit(oid).ok()?;
-                Some(CommitInfo::from_commit(&commit))
+                Some(CommitInfo::from_commit(&commit, mailmap.as_ref()))
             })
             .collect();
 
@@ -701,6 +749,7 @@ impl GitRepo {
         });
 
         // Validate each candidate still exists (not GC'd) and build CommitInfo
+        let mailmap = self.mailmap();
         let mut orphans: Vec<CommitInfo> = Vec::new();
         let mut orphan_oids: HashSet<Oid> = HashSet::new();
 
@@ -708,7 +757,7 @@ impl GitRepo {
             let Ok(commit) = self.repo.find_commit(*oid) else {
                 continue;
             };
-            let mut info = CommitInfo::from_commit(&commit);
+            let mut info = CommitInfo::from_commit(&commit, mailmap.as_ref());
             info.is_orphaned = true;
             info.orphan_source = Some(label.clone());
             orphan_oids.insert(*oid);
@@ -740,7 +789,7 @@ impl GitRepo {
             {
                 continue;
             }
-            let mut info = CommitInfo::from_commit(&commit);
+            let mut info = CommitInfo::from_commit(&commit, mailmap.as_ref());
             info.is_orphaned = true;
             info.orphan_source = Some(format!("parent of {}", source_sha));
             orphan_oids.insert(pid);
@@ -826,25 +875,24 @@ fn compute_diff_stats_for(repo: &Repository, oids: &[Oid]) -> Vec<(Oid, usize, u
         let Ok(commit) = repo.find_commit(oid) else {
             continue;
         };
-        let (ins, del) = if let Ok(tree) = commit.tree() {
-            let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
-            if let Ok(diff) = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None) {
-                if let Ok(stats) = diff.stats() {
-                    (stats.insertions(), stats.deletions())
-                } else {
-                    (0, 0)
-                }
-            } else {
-                (0, 0)
-            }
-        } else {
-            (0, 0)
-        };
+        let (ins, del) = first_parent_diff(repo, &commit)
+            .and_then(|diff| diff.stats().ok())
+            .map(|stats| (stats.insertions(), stats.deletions()))
+            .unwrap_or((0, 0));
         results.push((oid, ins, del));
     }
     results
 }
 
+/// Diff of `commit` against its first parent, or against the empty
+/// tree for root commits. `None` when either tree fails to load.
+fn first_parent_diff<'r>(repo: &'r Repository, commit: &Commit) -> Option<git2::Diff<'r>> {
+    let tree = commit.tree().ok()?;
+    let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+    repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+        .ok()
+}
+
 impl GitRepo {
     /// Get the repository name (basename of workdir or bare repo path)
     pub fn repo_name(&self) -> String {
@@ -1025,9 +1073,13 @@ impl GitRepo {
 
     /// Get worktree metadata (lightweight — no dirty checks).
     ///
-    /// Returns names, paths, branch info, and HEAD OIDs without running
-    /// status scans.  Dirty state is computed asynchronously via
-    /// per-worktree background checks.
+    /// Returns names, paths, branch info, HEAD OIDs and lock state
+    /// without running status scans.  Dirty state is computed
+    /// asynchronously via per-worktree background checks.
+    ///
+    /// Worktrees whose directory has gone missing are still returned,
+    /// flagged `is_stale`, so the UI can offer `git worktree prune`
+    /// instead of silently hiding the leftover metadata.
     pub fn worktrees(&self) -> Result<Vec<WorktreeInfo>> {
         let worktrees = self.repo.worktrees().context("Failed to get worktrees")?;
 
@@ -1037,10 +1089,31 @@ impl GitRepo {
                 && let Ok(wt) = self.repo.find_worktree(name)
             {
                 let wt_path = wt.path();
+                let path = wt_path.to_string_lossy().to_string();
+                let (is_locked, lock_reason) = match wt.is_locked() {
+                    Ok(git2::WorktreeLockStatus::Locked(reason)) => (
+                        true,
+                        reason
+                            .map(|r| r.trim().to_string())
+                            .filter(|r| !r.is_empty()),
+                    ),
+                    _ => (false, None),
+                };
+
                 if !wt_path.is_dir() {
+                    infos.push(WorktreeInfo {
+                        name: name.to_string(),
+                        path,
+                        branch: None,
+                        head_oid: None,
+                        is_dirty: None,
+                        dirty_file_count: None,
+                        is_locked,
+                        lock_reason,
+                        is_stale: true,
+                    });
                     continue;
                 }
-                let path = wt_path.to_string_lossy().to_string();
 
                 // Read branch and HEAD OID — cheap (no status scan).
                 let (branch, head_oid) = if let Ok(wt_repo) = Repository::open(wt_path) {
@@ -1050,9 +1123,9 @@ impl GitRepo {
                         .and_then(|h| h.shorthand().map(|s| s.to_string()))
                         .unwrap_or_else(|| "detached".to_string());
                     let head_oid = head_ref.and_then(|h| h.target());
-                    (branch, head_oid)
+                    (Some(branch), head_oid)
                 } else {
-                    ("unknown".to_string(), None)
+                    (Some("unknown".to_string()), None)
                 };
 
                 infos.push(WorktreeInfo {
@@ -1062,6 +1135,9 @@ impl GitRepo {
                     head_oid,
                     is_dirty: None,         // computed asynchronously
                     dirty_file_count: None, // computed asynchronously
+                    is_locked,
+                    lock_reason,
+                    is_stale: false,
                 });
             }
         }
@@ -1137,6 +1213,7 @@ impl GitRepo {
             tags.push(TagInfo {
                 name,
                 oid: commit_oid,
+                release: None,
             });
             true
         })?;
@@ -1173,11 +1250,20 @@ pub struct CommitSubmoduleEntry {
 pub struct WorktreeInfo {
     pub name: String,
     pub path: String,
-    pub branch: String,
+    /// Checked-out branch (`"detached"` for a detached HEAD). `None` when
+    /// the worktree directory is missing and there's no HEAD to read.
+    pub branch: Option<String>,
     pub head_oid: Option<Oid>,
     /// None = not yet checked (async dirty check pending), Some(bool) = known state
     pub is_dirty: Option<bool>,
     pub dirty_file_count: Option<usize>,
+    /// `git worktree lock` state; locked worktrees are skipped by prune
+    /// and refuse move/remove without `--force`.
+    pub is_locked: bool,
+    pub lock_reason: Option<String>,
+    /// Metadata under `.git/worktrees/<name>` whose directory no longer
+    /// exists — what `git worktree prune` would clean up.
+    pub is_stale: bool,
 }
 
 /// Stash entry information
@@ -1204,6 +1290,9 @@ pub struct BranchTip {
 pub struct TagInfo {
     pub name: String,
     pub oid: Oid,
+    /// Forge release cut from this tag. Never set here; the tab
+    /// attaches `origin`'s releases once they're listed.
+    pub release: Option<crate::releases::Release>,
 }
 
 /// Result of a remote git operation (fetch, push, pull)
@@ -1219,10 +1308,16 @@ pub struct FullCommitInfo {
     pub id: Oid,
     pub short_id: String,
     pub full_message: String,
+    /// Author name and email after `.mailmap` resolution.
     pub author_name: String,
     pub author_email: String,
+    /// `Name <email>` as recorded in the commit, when the mailmap
+    /// rewrote it.
+    pub raw_author: Option<String>,
     pub author_time: i64,
     pub parent_short_ids: Vec<String>,
+    /// Notes attached to this commit, one per notes ref.
+    pub notes: Vec<CommitNote>,
 }
 
 impl FullCommitInfo {
@@ -0,0 +1,150 @@
+//! `git notes`: extra text attached to commits without rewriting them
+//! (review sign-offs, build provenance, ...).
+//!
+//! Notes live on their own refs under `refs/notes/`. Every notes ref is
+//! read so provenance written by tooling under e.g. `refs/notes/ci` shows
+//! up next to hand-written notes on the default `refs/notes/commits`.
+
+use anyhow::{Context, Result};
+use git2::Oid;
+use std::collections::HashMap;
+
+use super::GitRepo;
+
+/// Default notes ref used by `git notes` when `core.notesRef` is unset.
+pub const DEFAULT_NOTES_REF: &str = "refs/notes/commits";
+
+/// Refspec covering every notes ref, for both fetch and push. Git moves
+/// notes only when asked to. Deliberately not forced: a notes ref that
+/// diverged from the remote's is rejected rather than overwritten.
+pub const NOTES_REFSPEC: &str = "refs/notes/*:refs/notes/*";
+
+/// One note attached to a commit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommitNote {
+    /// Full notes ref, e.g. `refs/notes/commits`.
+    pub ref_name: String,
+    pub message: String,
+}
+
+impl CommitNote {
+    /// Ref name without the `refs/notes/` prefix, for display.
+    pub fn short_ref(&self) -> &str {
+        short_notes_ref(&self.ref_name)
+    }
+}
+
+/// `refs/notes/ci` -> `ci`.
+pub fn short_notes_ref(ref_name: &str) -> &str {
+    ref_name.strip_prefix("refs/notes/").unwrap_or(ref_name)
+}
+
+impl GitRepo {
+    /// The ref new notes are written to: `core.notesRef`, falling back to
+    /// `refs/notes/commits`.
+    pub fn default_notes_ref(&self) -> String {
+        self.repo
+            .note_default_ref()
+            .unwrap_or_else(|_| DEFAULT_NOTES_REF.to_string())
+    }
+
+    /// Every notes ref in the repository, default ref first.
+    pub fn notes_refs(&self) -> Vec<String> {
+        let default = self.default_notes_ref();
+        let mut refs = vec![default.clone()];
+        if let Ok(iter) = self.repo.references_glob("refs/notes/*") {
+            for name in iter.flatten().filter_map(|r| r.name().map(str::to_string)) {
+                if name != default {
+                    refs.push(name);
+                }
+            }
+        }
+        refs
+    }
+
+    /// Notes attached to `oid` across all notes refs.
+    pub fn notes_for(&self, oid: Oid) -> Vec<CommitNote> {
+        self.notes_refs()
+            .into_iter()
+            .filter_map(|ref_name| {
+                let note = self.repo.find_note(Some(&ref_name), oid).ok()?;
+                let message = note.message()?.trim_end().to_string();
+                Some(CommitNote { ref_name, message })
+            })
+            .collect()
+    }
+
+    /// Map of annotated commit -> names of the notes refs holding a note
+    /// for it. Used to flag commits in the history graph.
+    pub fn noted_commits(&self) -> HashMap<Oid, Vec<String>> {
+        let mut noted: HashMap<Oid, Vec<String>> = HashMap::new();
+        for ref_name in self.notes_refs() {
+            let Ok(notes) = self.repo.notes(Some(&ref_name)) else {
+                continue;
+            };
+            for (_, annotated) in notes.flatten() {
+                noted.entry(annotated).or_default().push(ref_name.clone());
+            }
+        }
+        noted
+    }
+
+    /// Add or replace the note on `oid` under `ref_name`.
+    pub fn set_note(&self, ref_name: &str, oid: Oid, message: &str) -> Result<()> {
+        let sig = self.repo.signature().context("Failed to get signature")?;
+        self.repo
+            .note(&sig, &sig, Some(ref_name), oid, message, true)
+            .with_context(|| format!("Failed to write note on {ref_name}"))?;
+        Ok(())
+    }
+
+    /// Remove the note on `oid` under `ref_name`.
+    pub fn remove_note(&self, ref_name: &str, oid: Oid) -> Result<()> {
+        let sig = self.repo.signature().context("Failed to get signature")?;
+        self.repo
+            .note_delete(oid, Some(ref_name), &sig, &sig)
+            .with_context(|| format!("Failed to remove note from {ref_name}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn notes_are_read_from_every_notes_ref() {
+        let repo_dir = temp_dir("notes");
+
+        run_git(&repo_dir, &["init"]);
+        run_git(&repo_dir, &["config", "user.name", "Test"]);
+        run_git(&repo_dir, &["config", "user.email", "test@example.com"]);
+        fs::write(repo_dir.join("a.txt"), "one\n").expect("write a.txt");
+        run_git(&repo_dir, &["add", "a.txt"]);
+        run_git(&repo_dir, &["commit", "-m", "init"]);
+        run_git(
+            &repo_dir,
+            &["notes", "--ref", "ci", "add", "-m", "built by ci"],
+        );
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let head = repo.head_oid().expect("head oid");
+        repo.set_note("refs/notes/commits", head, "reviewed\n")
+            .expect("set note");
+
+        let notes = repo.notes_for(head);
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].ref_name, "refs/notes/commits");
+        assert_eq!(notes[0].message, "reviewed");
+        assert_eq!(notes[1].short_ref(), "ci");
+        assert_eq!(notes[1].message, "built by ci");
+        assert_eq!(repo.noted_commits().get(&head).map(Vec::len), Some(2));
+
+        repo.remove_note("refs/notes/commits", head)
+            .expect("remove note");
+        assert_eq!(repo.notes_for(head).len(), 1);
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
@@ -0,0 +1,213 @@
+//! Patch exchange: `git format-patch` export and `git am` / `git apply`
+//! previews.
+//!
+//! Export and preview run synchronously through the git CLI — libgit2
+//! has no format-patch / mailbox support — and are cheap enough for the
+//! UI thread. Applying a patch mutates the worktree and goes through
+//! `apply_patch_async` instead.
+
+use anyhow::{Context, Result, bail};
+use git2::Oid;
+use std::path::{Path, PathBuf};
+
+use super::GitRepo;
+
+/// Which commits a patch export covers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatchRange {
+    /// Just this commit (`format-patch -1 <oid>`).
+    Single(Oid),
+    /// Everything reachable from `tip` but not from `base`
+    /// (`format-patch <base>..<tip>`). `base` is any revspec.
+    Since { base: String, tip: Oid },
+}
+
+impl PatchRange {
+    fn args(&self) -> Vec<String> {
+        match self {
+            Self::Single(oid) => vec!["-1".to_string(), oid.to_string()],
+            Self::Since { base, tip } => vec![format!("{base}..{tip}")],
+        }
+    }
+}
+
+/// One file a patch would touch, from `git apply --numstat`. Counts are
+/// `None` for binary files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatchFileStat {
+    pub path: String,
+    pub insertions: Option<usize>,
+    pub deletions: Option<usize>,
+}
+
+/// What a patch file contains, for the apply dialog.
+#[derive(Clone, Debug, Default)]
+pub struct PatchPreview {
+    /// Files touched, in patch order; a path touched by several
+    /// mailbox messages is listed once with summed counts.
+    pub files: Vec<PatchFileStat>,
+    /// `true` when the file looks like `format-patch` / mbox output, so
+    /// `git am` can recreate the commits. Plain diffs only `git apply`.
+    pub is_mailbox: bool,
+    /// Number of mailbox messages (0 for a plain diff).
+    pub message_count: usize,
+}
+
+impl GitRepo {
+    /// Render `range` as a single mbox stream (`format-patch --stdout`),
+    /// ready for the clipboard or a `.mbox` file.
+    pub fn format_patch(&self, range: &PatchRange) -> Result<String> {
+        let mut args = vec!["format-patch".to_string(), "--stdout".to_string()];
+        args.extend(range.args());
+        let out = self.git_stdout(&args)?;
+        if out.trim().is_empty() {
+            bail!("No commits in range");
+        }
+        Ok(out)
+    }
+
+    /// Write one `NNNN-subject.patch` file per commit in `range` into
+    /// `dir`. Returns the paths git reports having written.
+    pub fn format_patch_to_dir(&self, range: &PatchRange, dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut args = vec![
+            "format-patch".to_string(),
+            "-o".to_string(),
+            dir.to_string_lossy().to_string(),
+        ];
+        args.extend(range.args());
+        let out = self.git_stdout(&args)?;
+        let files: Vec<PathBuf> = out
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(PathBuf::from)
+            .collect();
+        if files.is_empty() {
+            bail!("No commits in range");
+        }
+        Ok(files)
+    }
+
+    /// Summarize `patch_file` without touching the worktree: the files
+    /// it changes (`git apply --numstat`) and whether it's a mailbox.
+    pub fn patch_preview(&self, patch_file: &Path) -> Result<PatchPreview> {
+        let content = std::fs::read(patch_file)
+            .with_context(|| format!("Failed to read {}", patch_file.display()))?;
+        let content = String::from_utf8_lossy(&content);
+        let message_count = mailbox_message_count(&content);
+        let numstat = self.git_stdout(["apply", "--numstat", &patch_file.to_string_lossy()])?;
+        let files = parse_numstat(&numstat);
+        if files.is_empty() {
+            bail!("No changes found in {}", patch_file.display());
+        }
+        Ok(PatchPreview {
+            files,
+            is_mailbox: message_count > 0,
+            message_count,
+        })
+    }
+}
+
+/// Count `format-patch` messages. Each one opens with an mbox
+/// `From <sha> <date>` separator; hand-written mails without one still
+/// count once if they carry `From:` and `Subject:` headers up top.
+fn mailbox_message_count(content: &str) -> usize {
+    let separators = content
+        .lines()
+        .filter_map(|l| l.strip_prefix("From "))
+        .filter(|rest| {
+            rest.split_whitespace()
+                .next()
+                .is_some_and(|sha| sha.len() == 40 && sha.bytes().all(|b| b.is_ascii_hexdigit()))
+        })
+        .count();
+    if separators > 0 {
+        return separators;
+    }
+    let headers: Vec<&str> = content.lines().take_while(|l| !l.is_empty()).collect();
+    let has = |name: &str| headers.iter().any(|l| l.starts_with(name));
+    usize::from(has("From: ") && has("Subject: "))
+}
+
+/// Parse `git apply --numstat` output (`<ins>\t<del>\t<path>`, `-` for
+/// binary) into per-path stats, merging repeated paths.
+fn parse_numstat(out: &str) -> Vec<PatchFileStat> {
+    let mut files: Vec<PatchFileStat> = Vec::new();
+    for line in out.lines() {
+        let mut parts = line.splitn(3, '\t');
+        let (Some(ins), Some(del), Some(path)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let ins = ins.parse::<usize>().ok();
+        let del = del.parse::<usize>().ok();
+        match files.iter_mut().find(|f| f.path == path) {
+            Some(existing) => {
+                existing.insertions = existing.insertions.zip(ins).map(|(a, b)| a + b);
+                existing.deletions = existing.deletions.zip(del).map(|(a, b)| a + b);
+            }
+            None => files.push(PatchFileStat {
+                path: path.to_string(),
+                insertions: ins,
+                deletions: del,
+            }),
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PatchRange, parse_numstat};
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn numstat_merges_repeated_paths_and_keeps_binary_unknown() {
+        let files = parse_numstat("3\t1\tsrc/a.rs\n-\t-\tlogo.png\n2\t0\tsrc/a.rs\n");
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/a.rs");
+        assert_eq!(files[0].insertions, Some(5));
+        assert_eq!(files[0].deletions, Some(1));
+        assert_eq!(files[1].insertions, None);
+    }
+
+    #[test]
+    fn exported_range_previews_as_mailbox() {
+        let repo_dir = temp_dir("patch");
+
+        run_git(&repo_dir, &["init"]);
+        fs::write(repo_dir.join("a.txt"), "one\n").expect("write a.txt");
+        run_git(&repo_dir, &["add", "a.txt"]);
+        run_git(&repo_dir, &["commit", "-m", "init"]);
+        fs::write(repo_dir.join("a.txt"), "one\ntwo\n").expect("write a.txt");
+        run_git(&repo_dir, &["commit", "-am", "second"]);
+        fs::write(repo_dir.join("b.txt"), "b\n").expect("write b.txt");
+        run_git(&repo_dir, &["add", "b.txt"]);
+        run_git(&repo_dir, &["commit", "-m", "third"]);
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let tip = repo.head_oid().expect("head oid");
+        let mbox = repo
+            .format_patch(&PatchRange::Since {
+                base: "HEAD~2".to_string(),
+                tip,
+            })
+            .expect("format-patch range");
+        let patch_file = repo_dir.join("series.mbox");
+        fs::write(&patch_file, mbox).expect("write mbox");
+
+        let preview = repo.patch_preview(&patch_file).expect("preview");
+        assert!(preview.is_mailbox);
+        assert_eq!(preview.message_count, 2);
+        let paths: Vec<&str> = preview.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["a.txt", "b.txt"]);
+
+        let single = repo
+            .format_patch(&PatchRange::Single(tip))
+            .expect("format-patch single");
+        assert!(single.contains("Subject: [PATCH] third"));
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
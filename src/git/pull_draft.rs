@@ -0,0 +1,147 @@
+//! Defaults for a new pull / merge request, drawn from the branch and
+//! the commits it would introduce.
+
+use anyhow::{Context, Result};
+use git2::Sort;
+
+use super::GitRepo;
+
+/// Prefilled title and description plus the commit messages they were
+/// drawn from (oldest first), which also seed the AI prompt.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PullRequestDraft {
+    pub title: String,
+    pub body: String,
+    pub messages: Vec<String>,
+}
+
+impl GitRepo {
+    /// The branch `remote/HEAD` points at, falling back to whichever of
+    /// `main` / `master` the remote has.
+    pub fn remote_default_branch(&self, remote: &str) -> Option<String> {
+        let prefix = format!("refs/remotes/{remote}/");
+        if let Ok(head) = self.repo.find_reference(&format!("{prefix}HEAD"))
+            && let Some(target) = head.symbolic_target()
+            && let Some(branch) = target.strip_prefix(&prefix)
+        {
+            return Some(branch.to_string());
+        }
+        ["main", "master"]
+            .into_iter()
+            .find(|b| self.repo.find_reference(&format!("{prefix}{b}")).is_ok())
+            .map(str::to_string)
+    }
+
+    /// Remote `branch` tracks, else [`Self::default_remote`].
+    pub fn branch_remote(&self, branch: &str) -> Option<String> {
+        self.repo
+            .branch_upstream_remote(&format!("refs/heads/{branch}"))
+            .ok()
+            .and_then(|buf| buf.as_str().map(str::to_string))
+            .or_else(|| self.default_remote().ok())
+    }
+
+    /// Draft for merging `head` into `base`. A single commit lends its
+    /// subject and body; several give a title from the branch name and
+    /// a bulleted list of subjects. Merge commits are skipped.
+    pub fn pull_request_draft(&self, base: &str, head: &str) -> Result<PullRequestDraft> {
+        let resolve = |rev: &str| {
+            self.repo
+                .revparse_single(rev)
+                .and_then(|obj| obj.peel_to_commit())
+                .with_context(|| format!("Unknown ref {rev}"))
+        };
+        let mut walk = self.repo.revwalk()?;
+        walk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+        walk.push(resolve(head)?.id())?;
+        walk.hide(resolve(base)?.id())?;
+
+        let mut messages = Vec::new();
+        for oid in walk {
+            let commit = self.repo.find_commit(oid?)?;
+            if commit.parent_count() <= 1 {
+                messages.push(commit.message().unwrap_or("").trim().to_string());
+            }
+        }
+
+        let (title, body) = match messages.as_slice() {
+            [only] => match only.split_once("\n\n") {
+                Some((subject, body)) => (subject.trim().to_string(), body.trim().to_string()),
+                None => (only.trim().to_string(), String::new()),
+            },
+            _ => (
+                title_from_branch(head),
+                messages
+                    .iter()
+                    .map(|m| format!("- {}", m.lines().next().unwrap_or("")))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ),
+        };
+        Ok(PullRequestDraft {
+            title,
+            body,
+            messages,
+        })
+    }
+}
+
+/// `feature/fix-login_timeout` → `Fix login timeout`.
+fn title_from_branch(branch: &str) -> String {
+    let name = branch.rsplit('/').next().unwrap_or(branch);
+    let words = name.replace(['-', '_'], " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+    use std::path::Path;
+
+    fn commit_file(repo_dir: &Path, path: &str, message: &str) {
+        fs::write(repo_dir.join(path), message).expect("write file");
+        run_git(repo_dir, &["add", path]);
+        run_git(repo_dir, &["commit", "-m", message]);
+    }
+
+    #[test]
+    fn drafts_from_single_and_multiple_commits() {
+        let repo_dir = temp_dir("pull-draft");
+        run_git(&repo_dir, &["init", "-b", "main"]);
+        run_git(&repo_dir, &["config", "user.name", "Test"]);
+        run_git(&repo_dir, &["config", "user.email", "test@example.com"]);
+        commit_file(&repo_dir, "base.txt", "Base");
+
+        run_git(
+            &repo_dir,
+            &["checkout", "-q", "-b", "feature/fix-login_timeout"],
+        );
+        commit_file(
+            &repo_dir,
+            "a.txt",
+            "Raise login timeout\n\nSlow links gave up.",
+        );
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let single = repo
+            .pull_request_draft("main", "feature/fix-login_timeout")
+            .expect("single draft");
+        assert_eq!(single.title, "Raise login timeout");
+        assert_eq!(single.body, "Slow links gave up.");
+
+        commit_file(&repo_dir, "b.txt", "Log retries");
+        let multiple = repo
+            .pull_request_draft("main", "feature/fix-login_timeout")
+            .expect("multiple draft");
+        assert_eq!(multiple.title, "Fix login timeout");
+        assert_eq!(multiple.body, "- Raise login timeout\n- Log retries");
+        assert_eq!(multiple.messages.len(), 2);
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
@@ -9,7 +9,7 @@ use git2::{Oid, Status};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use super::{CommitSubmoduleEntry, FullCommitInfo, GitRepo, StashEntry};
+use super::{CommitSubmoduleEntry, FullCommitInfo, GitRepo, StashEntry, resolve_author};
 
 impl GitRepo {
     /// Check if the working directory has any uncommitted changes (staged or unstaged).
@@ -30,6 +30,37 @@ impl GitRepo {
             .to_path_buf()
     }
 
+    /// Run a git CLI command in [`Self::git_command_dir`] and return its
+    /// raw output, whatever the exit status. For commands whose non-zero
+    /// exits carry meaning (`check-ignore`, `merge-base --is-ancestor`);
+    /// everything else wants [`Self::git_stdout`].
+    pub fn git_output<I, S>(&self, args: I) -> Result<std::process::Output>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<std::ffi::OsStr>,
+    {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(self.git_command_dir())
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output()
+            .context("Failed to run git")
+    }
+
+    /// Run a git CLI command in [`Self::git_command_dir`] and return its
+    /// stdout. Fails with git's trimmed stderr on a non-zero exit.
+    pub fn git_stdout<I, S>(&self, args: I) -> Result<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let output = self.git_output(args)?;
+        if !output.status.success() {
+            anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
     /// Check if git user.name and user.email are configured.
     /// Returns true if `repo.signature()` would succeed (needed for commits).
     pub fn has_user_config(&self) -> bool {
@@ -406,7 +437,7 @@ impl GitRepo {
             .find_commit(oid)
             .with_context(|| format!("Failed to find commit {}", oid))?;
 
-        let author = commit.author();
+        let author = resolve_author(&commit, self.mailmap().as_ref());
 
         let parent_short_ids: Vec<String> = commit
             .parent_ids()
@@ -417,10 +448,12 @@ impl GitRepo {
             id: commit.id(),
             short_id: commit.id().to_string().get(..7).unwrap_or("").to_string(),
             full_message: commit.message().unwrap_or("").to_string(),
-            author_name: author.name().unwrap_or("Unknown").to_string(),
-            author_email: author.email().unwrap_or("").to_string(),
-            author_time: author.when().seconds(),
+            author_name: author.name,
+            author_email: author.email,
+            raw_author: author.raw,
+            author_time: commit.author().when().seconds(),
             parent_short_ids,
+            notes: self.notes_for(oid),
         })
     }
 
@@ -657,35 +690,13 @@ impl GitRepo {
 #[cfg(test)]
 mod tests {
     use super::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
     use git2::Oid;
     use std::fs;
-    use std::path::Path;
-    use std::process::Command;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    fn run_git(dir: &Path, args: &[&str]) -> String {
-        let out = Command::new("git")
-            .args(args)
-            .current_dir(dir)
-            .output()
-            .expect("failed to run git");
-        assert!(
-            out.status.success(),
-            "git {:?} failed: {}",
-            args,
-            String::from_utf8_lossy(&out.stderr)
-        );
-        String::from_utf8_lossy(&out.stdout).trim().to_string()
-    }
 
     #[test]
     fn submodules_at_commit_includes_nested_gitlink_paths() {
-        let unique = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("clock before epoch")
-            .as_nanos();
-        let repo_dir = std::env::temp_dir().join(format!("whisper-git-refs-test-{unique}"));
-        fs::create_dir_all(&repo_dir).expect("create temp repo dir");
+        let repo_dir = temp_dir("refs");
 
         run_git(&repo_dir, &["init"]);
         fs::write(repo_dir.join("README.md"), "root\n").expect("write README");
@@ -756,12 +767,7 @@ mod tests {
 
     #[test]
     fn local_branches_with_submodule_pin_matches_branch_tips() {
-        let unique = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("clock before epoch")
-            .as_nanos();
-        let repo_dir = std::env::temp_dir().join(format!("whisper-git-refs-test-{unique}"));
-        fs::create_dir_all(&repo_dir).expect("create temp repo dir");
+        let repo_dir = temp_dir("refs");
 
         run_git(&repo_dir, &["init"]);
         fs::write(repo_dir.join("README.md"), "root\n").expect("write README");
@@ -873,4 +879,66 @@ mod tests {
 
         let _ = fs::remove_dir_all(&repo_dir);
     }
+
+    #[test]
+    fn worktrees_report_lock_reason_and_stale_directories() {
+        let repo_dir = temp_dir("refs");
+        let locked_dir = repo_dir.with_extension("locked");
+        let gone_dir = repo_dir.with_extension("gone");
+
+        run_git(&repo_dir, &["init"]);
+        fs::write(repo_dir.join("README.md"), "root\n").expect("write README");
+        run_git(&repo_dir, &["add", "README.md"]);
+        run_git(
+            &repo_dir,
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "init",
+            ],
+        );
+        let locked_path = locked_dir.to_string_lossy().to_string();
+        let gone_path = gone_dir.to_string_lossy().to_string();
+        run_git(
+            &repo_dir,
+            &["worktree", "add", "-b", "locked", &locked_path],
+        );
+        run_git(&repo_dir, &["worktree", "add", "-b", "gone", &gone_path]);
+        run_git(
+            &repo_dir,
+            &["worktree", "lock", "--reason", "on usb drive", &locked_path],
+        );
+        fs::remove_dir_all(&gone_dir).expect("remove worktree dir");
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let worktrees = repo.worktrees().expect("list worktrees");
+        let locked = worktrees
+            .iter()
+            .find(|wt| wt.branch.as_deref() == Some("locked"))
+            .expect("locked worktree listed");
+        assert!(locked.is_locked);
+        assert_eq!(locked.lock_reason.as_deref(), Some("on usb drive"));
+        assert!(!locked.is_stale);
+
+        let gone = worktrees
+            .iter()
+            .find(|wt| wt.path == gone_path)
+            .expect("stale worktree still listed");
+        assert!(gone.is_stale);
+        assert_eq!(gone.branch, None);
+        assert!(!gone.is_locked);
+
+        run_git(&repo_dir, &["worktree", "prune"]);
+        let repo = GitRepo::open(&repo_dir).expect("reopen repo");
+        let worktrees = repo.worktrees().expect("list worktrees after prune");
+        assert_eq!(worktrees.len(), 1);
+        assert!(worktrees.iter().all(|wt| !wt.is_stale));
+
+        let _ = fs::remove_dir_all(&locked_dir);
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
 }
@@ -0,0 +1,317 @@
+//! Reading and writing the handful of git config keys the repository
+//! settings panel exposes, at local (`.git/config`) or global
+//! (`~/.gitconfig`) scope, the repository's issue link patterns, plus
+//! remote-URL matching for identity profiles.
+
+use anyhow::{Context, Result};
+use git2::{Config, ConfigLevel, ErrorCode};
+use std::path::PathBuf;
+
+use super::GitRepo;
+use super::issue_links::{ISSUE_LINK_KEY, IssueLinkPattern};
+
+/// Which config file an edit targets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConfigScope {
+    /// This repository's `.git/config`.
+    #[default]
+    Local,
+    /// The user's `~/.gitconfig`.
+    Global,
+}
+
+impl ConfigScope {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Local => "This repository",
+            Self::Global => "Global (~/.gitconfig)",
+        }
+    }
+}
+
+/// One key the settings panel edits. Empty `choices` means free text;
+/// otherwise the value is one of them (or unset).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfigField {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub choices: &'static [&'static str],
+}
+
+pub const CONFIG_FIELDS: [ConfigField; 7] = [
+    ConfigField {
+        key: "user.name",
+        label: "Name",
+        choices: &[],
+    },
+    ConfigField {
+        key: "user.email",
+        label: "Email",
+        choices: &[],
+    },
+    ConfigField {
+        key: "user.signingkey",
+        label: "Signing key",
+        choices: &[],
+    },
+    ConfigField {
+        key: "commit.gpgsign",
+        label: "Sign commits",
+        choices: &["true", "false"],
+    },
+    ConfigField {
+        key: "pull.rebase",
+        label: "Pull strategy (pull.rebase)",
+        choices: &["false", "true", "merges", "interactive"],
+    },
+    ConfigField {
+        key: "push.default",
+        label: "Push default",
+        choices: &["simple", "current", "upstream", "matching", "nothing"],
+    },
+    ConfigField {
+        key: "core.autocrlf",
+        label: "Line endings (core.autocrlf)",
+        choices: &["false", "true", "input"],
+    },
+];
+
+impl GitRepo {
+    fn scoped_config(&self, scope: ConfigScope) -> Result<Config> {
+        match scope {
+            ConfigScope::Local => self
+                .repo
+                .config()
+                .and_then(|c| c.open_level(ConfigLevel::Local))
+                .context("Failed to open repository config"),
+            ConfigScope::Global => {
+                // `find_global` fails when there's no ~/.gitconfig yet;
+                // opening the default path creates it on first write.
+                let path = Config::find_global().or_else(|_| {
+                    std::env::var("HOME")
+                        .map(|home| PathBuf::from(home).join(".gitconfig"))
+                        .context("Could not locate ~/.gitconfig")
+                })?;
+                Config::open(&path).with_context(|| format!("Failed to open {}", path.display()))
+            }
+        }
+    }
+
+    /// Values of [`CONFIG_FIELDS`] set at `scope`, in field order.
+    /// `None` where the key isn't set in that file.
+    pub fn config_values(&self, scope: ConfigScope) -> Vec<Option<String>> {
+        let config = self.scoped_config(scope).ok();
+        CONFIG_FIELDS
+            .iter()
+            .map(|f| config.as_ref().and_then(|c| c.get_string(f.key).ok()))
+            .collect()
+    }
+
+    /// Values of [`CONFIG_FIELDS`] as git resolves them across every
+    /// scope, for showing what an unset key falls back to.
+    pub fn effective_config_values(&self) -> Vec<Option<String>> {
+        let config = self.repo.config().ok();
+        CONFIG_FIELDS
+            .iter()
+            .map(|f| config.as_ref().and_then(|c| c.get_string(f.key).ok()))
+            .collect()
+    }
+
+    /// Write `changes` at `scope`. A `None` value unsets the key.
+    pub fn set_config_values(
+        &self,
+        scope: ConfigScope,
+        changes: &[(&str, Option<&str>)],
+    ) -> Result<()> {
+        let mut config = self.scoped_config(scope)?;
+        for (key, value) in changes {
+            match value {
+                Some(value) => config
+                    .set_str(key, value)
+                    .with_context(|| format!("Failed to set {key}"))?,
+                None => match config.remove(key) {
+                    Err(e) if e.code() != ErrorCode::NotFound => {
+                        return Err(e).with_context(|| format!("Failed to unset {key}"));
+                    }
+                    _ => {}
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// Issue link patterns from this repository's local config.
+    pub fn issue_link_patterns(&self) -> Vec<IssueLinkPattern> {
+        let mut patterns = Vec::new();
+        if let Ok(config) = self.scoped_config(ConfigScope::Local)
+            && let Ok(entries) = config.multivar(ISSUE_LINK_KEY, None)
+        {
+            let _ = entries.for_each(|entry| {
+                if let Some(pattern) = entry.value().and_then(IssueLinkPattern::parse) {
+                    patterns.push(pattern);
+                }
+            });
+        }
+        patterns
+    }
+
+    /// Replace the local issue link patterns with `patterns`.
+    pub fn set_issue_link_patterns(&self, patterns: &[IssueLinkPattern]) -> Result<()> {
+        let mut config = self.scoped_config(ConfigScope::Local)?;
+        match config.remove_multivar(ISSUE_LINK_KEY, ".*") {
+            Err(e) if e.code() != ErrorCode::NotFound => {
+                return Err(e).context("Failed to clear issue link patterns");
+            }
+            _ => {}
+        }
+        for pattern in patterns {
+            // A regexp no value matches appends rather than replaces.
+            config
+                .set_multivar(ISSUE_LINK_KEY, "^$", &pattern.to_line())
+                .context("Failed to save issue link pattern")?;
+        }
+        Ok(())
+    }
+
+    /// `true` when this repository sets its own `user.email`, so an
+    /// identity profile shouldn't overwrite it.
+    pub fn has_local_identity(&self) -> bool {
+        self.scoped_config(ConfigScope::Local)
+            .is_ok_and(|c| c.get_string("user.email").is_ok())
+    }
+
+    /// Fetch URLs of every remote.
+    pub fn remote_urls(&self) -> Vec<String> {
+        self.remote_names()
+            .iter()
+            .filter_map(|name| self.remote_url(name))
+            .collect()
+    }
+}
+
+/// `(host, path)` of a remote URL: `https://host/a/b.git`,
+/// `ssh://git@host:22/a/b`, or scp-style `git@host:a/b.git`. The
+/// host is lowercased; the path loses its `.git` suffix.
+pub fn remote_host_path(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let (authority, path) = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').unwrap_or((rest, "")),
+        None => url.split_once(':')?,
+    };
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?.to_lowercase();
+    if host.is_empty() {
+        return None;
+    }
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    Some((host, path.to_string()))
+}
+
+/// Whether an identity profile's host pattern covers `url`. A pattern
+/// is a host glob (`github.com`, `*.corp.example`) optionally followed
+/// by a path prefix (`github.com/work-org`); `*` matches any run of
+/// characters.
+pub fn remote_matches_pattern(url: &str, pattern: &str) -> bool {
+    let pattern = pattern.trim().trim_end_matches('/').to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+    let Some((host, path)) = remote_host_path(url) else {
+        return false;
+    };
+    if pattern.contains('/') {
+        let target = format!("{host}/{}", path.to_lowercase());
+        wildcard_match(&pattern, &target) || wildcard_match(&format!("{pattern}/*"), &target)
+    } else {
+        wildcard_match(&pattern, &host)
+    }
+}
+
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((head, rest)) => {
+            let Some(text) = text.strip_prefix(head) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|&i| text.is_char_boundary(i))
+                .any(|i| wildcard_match(rest, &text[i..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CONFIG_FIELDS, ConfigScope, remote_matches_pattern};
+    use crate::git::test_support::{run_git, temp_dir};
+    use crate::git::{GitRepo, IssueLinkPattern};
+    use std::fs;
+
+    #[test]
+    fn host_patterns_match_remote_urls() {
+        let work = "git@github.com:work-org/service.git";
+        let personal = "https://github.com/me/dotfiles.git";
+        let corp = "ssh://git@git.corp.example:2222/team/app";
+        assert!(remote_matches_pattern(work, "github.com/work-org"));
+        assert!(!remote_matches_pattern(personal, "github.com/work-org"));
+        assert!(remote_matches_pattern(personal, "GitHub.com"));
+        assert!(remote_matches_pattern(corp, "*.corp.example"));
+        assert!(remote_matches_pattern(corp, "*.corp.example/team/"));
+        assert!(!remote_matches_pattern(corp, "corp.example"));
+    }
+
+    #[test]
+    fn local_config_round_trips() {
+        let repo_dir = temp_dir("repo-config");
+        run_git(&repo_dir, &["init"]);
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        assert!(!repo.has_local_identity());
+        repo.set_config_values(
+            ConfigScope::Local,
+            &[
+                ("user.email", Some("me@work.example")),
+                ("pull.rebase", Some("true")),
+            ],
+        )
+        .expect("set values");
+        assert!(repo.has_local_identity());
+        let values = repo.config_values(ConfigScope::Local);
+        let idx = |key: &str| {
+            CONFIG_FIELDS
+                .iter()
+                .position(|f| f.key == key)
+                .expect("known key")
+        };
+        assert_eq!(
+            values[idx("user.email")].as_deref(),
+            Some("me@work.example")
+        );
+        assert_eq!(values[idx("pull.rebase")].as_deref(), Some("true"));
+
+        repo.set_config_values(
+            ConfigScope::Local,
+            &[("pull.rebase", None), ("push.default", None)],
+        )
+        .expect("unset values");
+        assert_eq!(
+            repo.config_values(ConfigScope::Local)[idx("pull.rebase")],
+            None
+        );
+
+        let patterns: Vec<IssueLinkPattern> = ["#", "PROJ- https://jira.example/browse/{ref}"]
+            .into_iter()
+            .filter_map(IssueLinkPattern::parse)
+            .collect();
+        repo.set_issue_link_patterns(&patterns)
+            .expect("set patterns");
+        assert_eq!(repo.issue_link_patterns(), patterns);
+        repo.set_issue_link_patterns(&patterns[1..])
+            .expect("replace patterns");
+        assert_eq!(repo.issue_link_patterns(), patterns[1..]);
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
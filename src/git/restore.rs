@@ -0,0 +1,106 @@
+//! Restoring worktree paths to a historical revision
+//! (`git restore --source=<rev> --worktree`) and reading a file's
+//! contents at a revision for "Save as…".
+
+use anyhow::{Context, Result, bail};
+use git2::{Oid, StatusOptions};
+
+use super::GitRepo;
+
+impl GitRepo {
+    /// First parent of `oid`, the source for "restore to parent
+    /// version". Fails for root commits.
+    pub fn first_parent(&self, oid: Oid) -> Result<Oid> {
+        let commit = self
+            .repo
+            .find_commit(oid)
+            .with_context(|| format!("Failed to find commit {oid}"))?;
+        match commit.parent_ids().next() {
+            Some(parent) => Ok(parent),
+            None => bail!("{} is a root commit", &oid.to_string()[..7]),
+        }
+    }
+
+    /// `true` when `path` (a file or directory, worktree-relative) has
+    /// staged, unstaged or untracked changes that a restore would
+    /// overwrite.
+    pub fn path_has_local_changes(&self, path: &str) -> Result<bool> {
+        let mut opts = StatusOptions::new();
+        opts.pathspec(path.trim_end_matches('/'))
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .exclude_submodules(true);
+        let statuses = self
+            .repo
+            .statuses(Some(&mut opts))
+            .context("Failed to get status")?;
+        Ok(statuses
+            .iter()
+            .any(|e| !e.status().intersects(git2::Status::IGNORED)))
+    }
+
+    /// Make the worktree copy of `path` match `source`, leaving the
+    /// index alone — `git restore --source=<source> --worktree`. Files
+    /// that don't exist at `source` are removed from the worktree.
+    pub fn restore_path(&self, source: Oid, path: &str) -> Result<()> {
+        let source = format!("--source={source}");
+        self.git_stdout(["restore", "--worktree", &source, "--", path])?;
+        Ok(())
+    }
+
+    /// Raw bytes of the file at `path` in commit `oid`.
+    pub fn file_at_revision(&self, oid: Oid, path: &str) -> Result<Vec<u8>> {
+        let commit = self
+            .repo
+            .find_commit(oid)
+            .with_context(|| format!("Failed to find commit {oid}"))?;
+        let entry = commit
+            .tree()
+            .context("Failed to read commit tree")?
+            .get_path(std::path::Path::new(path))
+            .with_context(|| format!("{path} does not exist at {}", &oid.to_string()[..7]))?;
+        let blob = self
+            .repo
+            .find_blob(entry.id())
+            .with_context(|| format!("{path} is not a file"))?;
+        Ok(blob.content().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::git::GitRepo;
+    use crate::git::test_support::{run_git, temp_dir};
+    use std::fs;
+
+    #[test]
+    fn restore_path_from_parent_and_read_blob() {
+        let repo_dir = temp_dir("restore");
+
+        run_git(&repo_dir, &["init"]);
+        fs::write(repo_dir.join("a.txt"), "one\n").expect("write a.txt");
+        run_git(&repo_dir, &["add", "a.txt"]);
+        run_git(&repo_dir, &["commit", "-m", "init"]);
+        fs::write(repo_dir.join("a.txt"), "two\n").expect("write a.txt");
+        run_git(&repo_dir, &["commit", "-am", "second"]);
+
+        let repo = GitRepo::open(&repo_dir).expect("open repo");
+        let head = repo.head_oid().expect("head oid");
+        let parent = repo.first_parent(head).expect("parent");
+        assert!(repo.first_parent(parent).is_err());
+        assert_eq!(
+            repo.file_at_revision(parent, "a.txt").expect("blob"),
+            b"one\n"
+        );
+
+        assert!(!repo.path_has_local_changes("a.txt").expect("status"));
+        repo.restore_path(parent, "a.txt").expect("restore");
+        assert_eq!(
+            fs::read_to_string(repo_dir.join("a.txt")).expect("read"),
+            "one\n"
+        );
+        assert!(repo.path_has_local_changes("a.txt").expect("status"));
+
+        let _ = fs::remove_dir_all(&repo_dir);
+    }
+}
@@ -14,6 +14,10 @@ pub struct WorkingDirStatus {
     /// Untracked (new) files not yet known to git
     pub untracked: Vec<FileStatus>,
     pub conflicted: Vec<FileStatus>,
+    /// Ignored paths. Only populated when the status walk asked for
+    /// them (the staging well's "show ignored" toggle); ignored
+    /// directories appear once with a trailing `/`.
+    pub ignored: Vec<FileStatus>,
 }
 
 impl WorkingDirStatus {
@@ -77,11 +81,20 @@ pub fn working_dir_status_from_statuses(statuses: &git2::Statuses<'_>) -> Workin
     let mut unstaged = Vec::new();
     let mut untracked = Vec::new();
     let mut conflicted = Vec::new();
+    let mut ignored = Vec::new();
 
     for entry in statuses.iter() {
         let path = entry.path().unwrap_or("").to_string();
         let status = entry.status();
 
+        if status.contains(Status::IGNORED) {
+            ignored.push(FileStatus {
+                path,
+                status: FileStatusKind::New,
+            });
+            continue;
+        }
+
         if status.contains(Status::CONFLICTED) {
             conflicted.push(FileStatus {
                 path,
@@ -123,6 +136,7 @@ pub fn working_dir_status_from_statuses(statuses: &git2::Statuses<'_>) -> Workin
         unstaged,
         untracked,
         conflicted,
+        ignored,
     }
 }
 
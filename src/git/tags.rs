@@ -0,0 +1,178 @@
+//! Version-aware tag ordering for the sidebar's TAGS section.
+//!
+//! libgit2 hands tags back in refname order, which puts `v1.10.0`
+//! between `v1.1.0` and `v1.2.0`. Tags that parse as a (possibly
+//! prefixed) semantic version sort newest first instead, by semver
+//! precedence; everything else follows alphabetically.
+
+use std::cmp::Ordering;
+
+use super::TagInfo;
+
+/// How the TAGS section orders its entries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TagSort {
+    /// Semver precedence, newest first; non-version tags after.
+    #[default]
+    Version,
+    /// Plain refname order.
+    Name,
+}
+
+/// A tag name read as `<prefix><major>[.<minor>[.<patch>]][-<pre>][+<build>]`,
+/// e.g. `v1.2.3`, `1.4`, `release-2.0.0-rc.1`, `app/v0.3.0`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagVersion {
+    /// Everything before the version, minus a trailing `v`, so `v1.0`
+    /// and `1.1` sort together while `app/v1.0` and `lib/v1.0` don't.
+    pub prefix: String,
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Dot-separated pre-release identifiers (`rc.1` → `["rc", "1"]`).
+    /// Empty for a release.
+    pub pre: Vec<String>,
+}
+
+impl TagVersion {
+    pub fn parse(name: &str) -> Option<Self> {
+        let start = name.find(|c: char| c.is_ascii_digit())?;
+        let prefix = name[..start].trim_end_matches(['v', 'V']).to_string();
+        let rest = &name[start..];
+        // Build metadata never affects precedence.
+        let rest = rest.split_once('+').map_or(rest, |(v, _)| v);
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (rest, None),
+        };
+        let mut numbers = [0u64; 3];
+        let parts: Vec<&str> = core.split('.').collect();
+        if parts.len() > 3 {
+            return None;
+        }
+        for (slot, part) in numbers.iter_mut().zip(&parts) {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            *slot = part.parse().ok()?;
+        }
+        let pre = match pre {
+            Some(pre) if pre.is_empty() || pre.split('.').any(str::is_empty) => return None,
+            Some(pre) => pre.split('.').map(str::to_string).collect(),
+            None => Vec::new(),
+        };
+        Some(Self {
+            prefix,
+            major: numbers[0],
+            minor: numbers[1],
+            patch: numbers[2],
+            pre,
+        })
+    }
+
+    /// Semver precedence, ignoring the prefix.
+    fn cmp_precedence(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                // A release outranks its own pre-releases.
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => cmp_pre(&self.pre, &other.pre),
+            })
+    }
+}
+
+/// Compare pre-release identifiers per semver: numeric identifiers
+/// numerically and below alphanumeric ones, and a shorter list below a
+/// longer one it prefixes.
+fn cmp_pre(a: &[String], b: &[String]) -> Ordering {
+    for (x, y) in a.iter().zip(b) {
+        let ord = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+impl TagInfo {
+    /// This tag's version, when its name reads as one.
+    pub fn version(&self) -> Option<TagVersion> {
+        TagVersion::parse(&self.name)
+    }
+}
+
+/// Order `tags` in place for the TAGS section.
+pub fn sort_tags(tags: &mut [TagInfo], sort: TagSort) {
+    match sort {
+        TagSort::Name => tags.sort_by(|a, b| a.name.cmp(&b.name)),
+        TagSort::Version => {
+            tags.sort_by(|a, b| match (a.version(), b.version()) {
+                (Some(va), Some(vb)) => va
+                    .prefix
+                    .cmp(&vb.prefix)
+                    .then_with(|| vb.cmp_precedence(&va))
+                    .then_with(|| a.name.cmp(&b.name)),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => a.name.cmp(&b.name),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TagSort, TagVersion, sort_tags};
+    use crate::git::TagInfo;
+
+    #[test]
+    fn version_sort_orders_by_semver_precedence() {
+        assert!(TagVersion::parse("nightly").is_none());
+        assert!(TagVersion::parse("v1.2.3.4").is_none());
+        let rc = TagVersion::parse("release-2.0.0-rc.1+build.5").expect("parse");
+        assert_eq!(rc.prefix, "release-");
+        assert_eq!(rc.pre, vec!["rc", "1"]);
+
+        let names = [
+            "v1.2.0",
+            "nightly",
+            "v1.10.0",
+            "v1.10.0-rc.2",
+            "v1.10.0-rc.10",
+            "1.9",
+            "v1.10.0-beta",
+            "app/v0.1.0",
+        ];
+        let mut tags: Vec<TagInfo> = names
+            .iter()
+            .map(|name| TagInfo {
+                name: name.to_string(),
+                oid: git2::Oid::zero(),
+                release: None,
+            })
+            .collect();
+        sort_tags(&mut tags, TagSort::Version);
+        let sorted: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            sorted,
+            vec![
+                "v1.10.0",
+                "v1.10.0-rc.10",
+                "v1.10.0-rc.2",
+                "v1.10.0-beta",
+                "1.9",
+                "v1.2.0",
+                "app/v0.1.0",
+                "nightly",
+            ]
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Scaffolding shared by the repo-backed and temp-dir tests.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Run `git` in `dir` under a fixed test identity and return its trimmed
+/// stdout. Panics with git's stderr on a non-zero exit. Later `-c` flags in
+/// `args` override the identity, so tests can still commit as other authors.
+pub(crate) fn run_git(dir: &Path, args: &[&str]) -> String {
+    let out = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run git");
+    assert!(
+        out.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&out.stderr)
+    );
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+}
+
+/// Create a fresh, uniquely named directory under the system temp dir.
+/// `label` keeps leftovers from different test modules recognisable.
+pub(crate) fn temp_dir(label: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let unique = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock before epoch")
+        .as_nanos();
+    let seq = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let dir = std::env::temp_dir().join(format!("whisper-git-{label}-test-{unique}-{seq}"));
+    fs::create_dir_all(&dir).expect("create temp repo dir");
+    dir
+}
@@ -31,7 +31,7 @@
 //! routing. Each worker uses [`StatusOptions::exclude_submodules`] so
 //! the per-entity check never recurses into nested submodules.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, Sender};
 
@@ -39,8 +39,8 @@ use git2::Oid;
 use winit::event_loop::EventLoopProxy;
 
 use crate::git::{
-    BranchTip, CommitInfo, GitRepo, StashEntry, SubmoduleInfo, TagInfo, WorkingDirStatus,
-    WorktreeInfo, working_dir_status_from_statuses,
+    BranchTip, CodeOwners, CommitInfo, GitRepo, StashEntry, SubmoduleInfo, TagInfo,
+    WorkingDirStatus, WorktreeInfo, codeowners_in_workdir, working_dir_status_from_statuses,
 };
 
 /// Maximum commits walked per refresh. The legacy used the same cap;
@@ -69,15 +69,20 @@ pub struct StatusResult {
     pub staging_status: Option<WorkingDirStatus>,
     /// Staging repo state (merge / rebase / cherry-pick in progress).
     pub staging_repo_state: git2::RepositoryState,
+    /// `CODEOWNERS` from the staging worktree, re-read on every status
+    /// refresh so edits to the file itself show up immediately.
+    pub staging_codeowners: Option<CodeOwners>,
 }
 
 /// Spawn a worker that computes working-directory status off-thread.
 /// `is_bare` short-circuits the status walk for bare repos that have
-/// no working tree at all.
+/// no working tree at all. `include_ignored` adds ignored paths (not
+/// recursed into) for the staging well's Ignored section.
 pub(crate) fn spawn_status_refresh(
     repo_context_path: PathBuf,
     staging_context_path: Option<PathBuf>,
     is_bare: bool,
+    include_ignored: bool,
     proxy: EventLoopProxy<()>,
 ) -> Receiver<StatusResult> {
     let (tx, rx) = std::sync::mpsc::channel();
@@ -94,6 +99,7 @@ pub(crate) fn spawn_status_refresh(
                 // `spawn_dirty_checks`.
                 opts.include_untracked(true)
                     .recurse_untracked_dirs(true)
+                    .include_ignored(include_ignored)
                     .exclude_submodules(true);
                 let statuses = repo.statuses(Some(&mut opts)).ok()?;
                 Some(working_dir_status_from_statuses(&statuses))
@@ -113,6 +119,7 @@ pub(crate) fn spawn_status_refresh(
                     let mut opts = git2::StatusOptions::new();
                     opts.include_untracked(true)
                         .recurse_untracked_dirs(true)
+                        .include_ignored(include_ignored)
                         .exclude_submodules(true);
                     repo.statuses(Some(&mut opts))
                         .ok()
@@ -131,6 +138,7 @@ pub(crate) fn spawn_status_refresh(
         let staging_path = staging_repo
             .as_ref()
             .and_then(|repo| repo.workdir().map(|p| p.to_path_buf()));
+        let staging_codeowners = staging_path.as_deref().and_then(codeowners_in_workdir);
 
         let _ = tx.send(StatusResult {
             main_path,
@@ -138,6 +146,7 @@ pub(crate) fn spawn_status_refresh(
             staging_path,
             staging_status,
             staging_repo_state,
+            staging_codeowners,
         });
         let _ = proxy.send_event(());
     });
@@ -165,10 +174,14 @@ pub struct RepoStateResult {
     pub worktrees: Vec<WorktreeInfo>,
     pub remote_names: Vec<String>,
     pub remote_urls: HashMap<String, String>,
+    /// Remotes whose URL is a local bundle file.
+    pub bundle_remotes: HashSet<String>,
     pub is_bare: bool,
     pub submodules: Vec<SubmoduleInfo>,
     pub stashes: Vec<StashEntry>,
     pub ahead_behind: HashMap<String, (usize, usize)>,
+    /// Commits carrying a `git notes` entry -> the notes refs holding it.
+    pub noted_commits: HashMap<Oid, Vec<String>>,
     /// Cheap hash of the contents of `git_dir/refs/`. Compared against
     /// the last-seen fingerprint by the reconciliation timer; a
     /// divergence triggers `repo.reopen()` + a full state refresh.
@@ -210,10 +223,12 @@ pub(crate) fn spawn_repo_state_refresh(
                     worktrees: Vec::new(),
                     remote_names: Vec::new(),
                     remote_urls: HashMap::new(),
+                    bundle_remotes: HashSet::new(),
                     is_bare: false,
                     submodules: Vec::new(),
                     stashes: Vec::new(),
                     ahead_behind: HashMap::new(),
+                    noted_commits: HashMap::new(),
                     ref_fingerprint: 0,
                     real_oids: Vec::new(),
                     worktree_repos: HashMap::new(),
@@ -271,6 +286,7 @@ pub(crate) fn spawn_repo_state_refresh(
         // why this can't live on the main thread.
         let worktree_repos: HashMap<PathBuf, GitRepo> = worktrees
             .iter()
+            .filter(|wt| !wt.is_stale)
             .filter_map(|wt| {
                 let path = PathBuf::from(&wt.path);
                 GitRepo::open(&path).ok().map(|r| (path, r))
@@ -283,6 +299,7 @@ pub(crate) fn spawn_repo_state_refresh(
             .iter()
             .filter_map(|name| repo.remote_url(name).map(|url| (name.clone(), url)))
             .collect();
+        let bundle_remotes = repo.bundle_remotes();
 
         let submodules = staging.submodules().unwrap_or_else(|e| {
             errors.push(format!("Failed to load submodules: {e}"));
@@ -291,6 +308,7 @@ pub(crate) fn spawn_repo_state_refresh(
 
         let stashes = repo.stash_list();
         let ahead_behind = repo.all_branches_ahead_behind();
+        let noted_commits = repo.noted_commits();
         let ref_fingerprint = crate::git::ref_fingerprint(repo.git_dir());
 
         let real_oids: Vec<Oid> = commits
@@ -308,10 +326,12 @@ pub(crate) fn spawn_repo_state_refresh(
             worktrees,
             remote_names,
             remote_urls,
+            bundle_remotes,
             is_bare,
             submodules,
             stashes,
             ahead_behind,
+            noted_commits,
             ref_fingerprint,
             real_oids,
             worktree_repos,
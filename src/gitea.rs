@@ -0,0 +1,467 @@
+//! Gitea / Forgejo REST client: commit statuses and Actions tasks for
+//! the header CI indicator and the per-commit dots in the graph.
+//!
+//! Unlike github.com and GitLab, a Gitea host can't be recognised from
+//! its name. Codeberg and hostnames containing "gitea" or "forgejo" are
+//! detected; any other host has to be registered in
+//! `Config::gitea_hosts` (from the token modal), which the app mirrors
+//! here through [`set_registered_hosts`].
+
+use crate::ci::{
+    CiCheckStatus, CiCommitRollup, CiCounts, CiProvider, CiState, CiStatus, ProviderCiResult,
+};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{LazyLock, RwLock};
+use winit::event_loop::EventLoopProxy;
+
+/// Commits on the default branch whose combined status is fetched per
+/// poll. Older commits only get dots from Actions tasks.
+const STATUS_COMMITS: u32 = 10;
+
+/// Requests one CI poll makes: the commit list, a combined status for
+/// each of the `STATUS_COMMITS`, then the Actions tasks.
+pub const CI_POLL_REQUESTS: u32 = 2 + STATUS_COMMITS;
+
+/// Hosts from `Config::gitea_hosts`, so URL parsing doesn't need the
+/// config threaded through every caller.
+static REGISTERED_HOSTS: LazyLock<RwLock<Vec<String>>> = LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// Replace the registered host list. Called at startup and whenever the
+/// token modal adds a host.
+pub fn set_registered_hosts(hosts: &[String]) {
+    if let Ok(mut registered) = REGISTERED_HOSTS.write() {
+        *registered = hosts.to_vec();
+    }
+}
+
+/// Whether `host` is a Gitea / Forgejo instance we know of. Also used
+/// to decide which detected hosts get auto-registered.
+pub fn is_detected_host(host: &str) -> bool {
+    host == "codeberg.org" || host.contains("gitea") || host.contains("forgejo")
+}
+
+fn is_gitea_host(host: &str, registered: &[String]) -> bool {
+    is_detected_host(host) || registered.iter().any(|h| h == host)
+}
+
+/// Parsed Gitea remote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiteaRemote {
+    /// e.g. "codeberg.org"; the key for tokens and host registration.
+    pub host: String,
+    /// e.g. "https://codeberg.org"
+    pub api_base: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+/// Extract Gitea remote info from a remote URL whose host is detected
+/// or registered. SSH ports are dropped; an HTTPS port is kept.
+pub fn parse_gitea_remote(url: &str) -> Option<GiteaRemote> {
+    let registered = REGISTERED_HOSTS.read().ok()?;
+    parse_remote_with_hosts(url, &registered)
+}
+
+/// [`parse_gitea_remote`] against an explicit list of registered hosts.
+fn parse_remote_with_hosts(url: &str, registered: &[String]) -> Option<GiteaRemote> {
+    let url = url.trim();
+    let (host, path) = if let Some(rest) = url.strip_prefix("git@") {
+        // SSH: git@codeberg.org:owner/repo.git
+        rest.split_once(':')?
+    } else if let Some(rest) = url.strip_prefix("ssh://") {
+        // SSH: ssh://git@git.example.com:2222/owner/repo.git
+        let rest = rest.split_once('@').map(|(_, r)| r).unwrap_or(rest);
+        let (host, path) = rest.split_once('/')?;
+        (host.split(':').next()?, path)
+    } else {
+        // HTTPS: https://git.example.com/owner/repo.git
+        url.strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))?
+            .split_once('/')?
+    };
+    if !is_gitea_host(host, registered) {
+        return None;
+    }
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path.split_once('/')?;
+    let repo = repo.split('/').next()?;
+    if owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+    Some(GiteaRemote {
+        host: host.to_string(),
+        api_base: format!("https://{host}"),
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct GiteaCommit {
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CombinedStatus {
+    #[serde(default)]
+    statuses: Vec<CommitStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitStatus {
+    id: u64,
+    context: String,
+    status: String,
+    target_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ActionTasksResponse {
+    #[serde(default)]
+    workflow_runs: Vec<ActionTask>,
+}
+
+/// One Actions job. Gitea calls them tasks and lists them under the
+/// misleading `workflow_runs` key.
+#[derive(Debug, Clone, Deserialize)]
+struct ActionTask {
+    id: u64,
+    name: String,
+    head_sha: String,
+    status: String,
+    #[serde(default)]
+    workflow_id: String,
+    url: Option<String>,
+}
+
+pub struct GiteaClient {
+    token: Option<String>,
+    api_base: String,
+}
+
+impl GiteaClient {
+    /// Without a token only public repositories can be read.
+    pub fn new(token: Option<String>, api_base: String) -> Self {
+        Self { token, api_base }
+    }
+
+    /// GET without checking the status, for callers that treat some
+    /// error statuses as answers.
+    fn request(&self, path: &str) -> Result<ureq::http::Response<ureq::Body>> {
+        let mut req = ureq::get(&format!("{}/api/v1{path}", self.api_base))
+            .header("Accept", "application/json")
+            .header("User-Agent", "whisper-git");
+        if let Some(token) = &self.token {
+            req = req.header("Authorization", &format!("token {token}"));
+        }
+        req.config()
+            .http_status_as_error(false)
+            .build()
+            .call()
+            .context("Gitea API request failed")
+    }
+
+    fn get(&self, path: &str) -> Result<ureq::http::Response<ureq::Body>> {
+        let mut resp = self.request(path)?;
+        Self::ensure_success(&mut resp)?;
+        Ok(resp)
+    }
+
+    fn ensure_success(resp: &mut ureq::http::Response<ureq::Body>) -> Result<()> {
+        let status = resp.status().as_u16();
+        if !(200..300).contains(&status) {
+            let body = resp.body_mut().read_to_string().unwrap_or_default();
+            let message = match status {
+                401 | 403 => format!("Gitea API refused access (HTTP {status}): check the token"),
+                404 => "Gitea API returned 404: the repository is private or the token \
+                        lacks read access"
+                    .to_string(),
+                _ if body.trim().is_empty() => format!("Gitea API request failed (HTTP {status})"),
+                _ => format!("Gitea API request failed (HTTP {status}): {}", body.trim()),
+            };
+            anyhow::bail!(message);
+        }
+        Ok(())
+    }
+
+    /// Latest commits on the default branch, newest first.
+    fn recent_commits(&self, owner: &str, repo: &str, limit: u32) -> Result<Vec<String>> {
+        let mut resp = self
+            .get(&format!(
+                "/repos/{owner}/{repo}/commits?limit={limit}&stat=false&verification=false&files=false"
+            ))
+            .with_context(|| format!("Failed to fetch commits for {owner}/{repo}"))?;
+        let commits: Vec<GiteaCommit> = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse commits")?;
+        Ok(commits.into_iter().map(|c| c.sha).collect())
+    }
+
+    /// Statuses reported on `sha`, one per context.
+    fn commit_statuses(&self, owner: &str, repo: &str, sha: &str) -> Result<Vec<CommitStatus>> {
+        let mut resp = self
+            .get(&format!("/repos/{owner}/{repo}/commits/{sha}/status"))
+            .with_context(|| format!("Failed to fetch statuses for {sha}"))?;
+        let combined: CombinedStatus = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse commit status")?;
+        Ok(combined.statuses)
+    }
+
+    /// Recent Actions tasks, newest first. Instances with Actions
+    /// disabled, or too old to have the endpoint, answer 404; that
+    /// reads as "no tasks" rather than an error.
+    fn action_tasks(&self, owner: &str, repo: &str, limit: u32) -> Result<Vec<ActionTask>> {
+        let mut resp = self.request(&format!(
+            "/repos/{owner}/{repo}/actions/tasks?limit={limit}"
+        ))?;
+        if resp.status().as_u16() == 404 {
+            return Ok(Vec::new());
+        }
+        Self::ensure_success(&mut resp).context("Failed to fetch Actions tasks")?;
+        let body: ActionTasksResponse = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse Actions tasks")?;
+        Ok(body.workflow_runs)
+    }
+}
+
+/// State of a commit status (`pending` / `success` / `error` /
+/// `failure` / `warning`) or an Actions task.
+fn status_state(status: &str) -> CiState {
+    match status {
+        "success" | "warning" | "skipped" => CiState::Success,
+        "failure" | "error" | "cancelled" => CiState::Failure,
+        "pending" | "running" | "waiting" | "blocked" => CiState::Pending,
+        _ => CiState::None,
+    }
+}
+
+/// Per-commit rollups. Commit statuses win for the commits they were
+/// fetched for, since Actions reports its jobs there too; older commits
+/// fall back to the latest task per workflow job.
+fn per_commit_rollups(
+    statuses: &[(String, Vec<CommitStatus>)],
+    tasks: &[ActionTask],
+) -> HashMap<String, CiCommitRollup> {
+    let mut result: HashMap<String, CiCommitRollup> = HashMap::new();
+    for (sha, statuses) in statuses {
+        if statuses.is_empty() {
+            continue;
+        }
+        // Re-reported contexts keep only their latest status.
+        let mut latest: HashMap<&str, &CommitStatus> = HashMap::new();
+        for status in statuses {
+            let entry = latest.entry(status.context.as_str()).or_insert(status);
+            if status.id > entry.id {
+                *entry = status;
+            }
+        }
+        let mut checks: Vec<CiCheckStatus> = latest
+            .into_values()
+            .map(|s| CiCheckStatus {
+                label: s.context.clone(),
+                state: status_state(&s.status),
+                url: s.target_url.clone().filter(|u| !u.is_empty()),
+                run_id: None,
+                required: false,
+            })
+            .collect();
+        checks.sort_by(|a, b| a.label.cmp(&b.label));
+        let counts = CiCounts::from_states(checks.iter().map(|c| c.state));
+        result.insert(sha.clone(), CiCommitRollup { counts, checks });
+    }
+
+    let mut by_sha: HashMap<&str, HashMap<(&str, &str), &ActionTask>> = HashMap::new();
+    for task in tasks {
+        if result.contains_key(&task.head_sha) {
+            continue;
+        }
+        let jobs = by_sha.entry(task.head_sha.as_str()).or_default();
+        let entry = jobs
+            .entry((task.workflow_id.as_str(), task.name.as_str()))
+            .or_insert(task);
+        if task.id > entry.id {
+            *entry = task;
+        }
+    }
+    for (sha, jobs) in by_sha {
+        let mut checks: Vec<CiCheckStatus> = jobs
+            .into_values()
+            .map(|t| CiCheckStatus {
+                label: if t.workflow_id.is_empty() {
+                    t.name.clone()
+                } else {
+                    format!("{} / {}", t.workflow_id, t.name)
+                },
+                state: status_state(&t.status),
+                url: t.url.clone(),
+                run_id: None,
+                required: false,
+            })
+            .collect();
+        checks.sort_by(|a, b| a.label.cmp(&b.label));
+        let counts = CiCounts::from_states(checks.iter().map(|c| c.state));
+        result.insert(sha.to_string(), CiCommitRollup { counts, checks });
+    }
+    result
+}
+
+fn fetch_ci_result(token: Option<String>, remote: &GiteaRemote) -> ProviderCiResult {
+    let client = GiteaClient::new(token, remote.api_base.clone());
+    let (owner, repo) = (&remote.owner, &remote.repo);
+    let fetched = client
+        .recent_commits(owner, repo, STATUS_COMMITS)
+        .and_then(|shas| {
+            let statuses = shas
+                .into_iter()
+                .map(|sha| {
+                    let statuses = client.commit_statuses(owner, repo, &sha)?;
+                    Ok((sha, statuses))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let tasks = client.action_tasks(owner, repo, 50)?;
+            Ok((statuses, tasks))
+        });
+    match fetched {
+        Ok((statuses, tasks)) => {
+            let per_commit_rollups = per_commit_rollups(&statuses, &tasks);
+            let head = statuses
+                .first()
+                .and_then(|(sha, _)| per_commit_rollups.get(sha));
+            ProviderCiResult {
+                provider: CiProvider::Gitea,
+                host: String::new(),
+                status: crate::ci::status_for_head(head),
+                per_commit_rollups,
+                stale: false,
+            }
+        }
+        Err(e) => ProviderCiResult {
+            provider: CiProvider::Gitea,
+            host: String::new(),
+            status: CiStatus {
+                state: CiState::None,
+                summary: format!("Gitea CI fetch failed: {e:#}"),
+                url: None,
+                counts: None,
+            },
+            per_commit_rollups: HashMap::new(),
+            stale: false,
+        },
+    }
+}
+
+/// Fetch CI status for a Gitea / Forgejo repository asynchronously.
+/// Returns None if the URL isn't a known Gitea host.
+pub fn fetch_ci_status_async(
+    token: Option<&str>,
+    origin_url: &str,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<ProviderCiResult>> {
+    let remote = parse_gitea_remote(origin_url)?;
+    let token = token.map(str::to_string);
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let result = fetch_ci_result(token, &remote);
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_detected_and_registered_hosts() {
+        assert_eq!(
+            parse_gitea_remote("git@codeberg.org:alice/tool.git"),
+            Some(GiteaRemote {
+                host: "codeberg.org".into(),
+                api_base: "https://codeberg.org".into(),
+                owner: "alice".into(),
+                repo: "tool".into(),
+            })
+        );
+        let remote = parse_gitea_remote("ssh://git@forgejo.example.com:2222/bob/app.git").unwrap();
+        assert_eq!(remote.api_base, "https://forgejo.example.com");
+        assert_eq!(
+            (remote.owner.as_str(), remote.repo.as_str()),
+            ("bob", "app")
+        );
+
+        assert_eq!(
+            parse_remote_with_hosts("https://git.corp.example/team/svc", &[]),
+            None
+        );
+        let registered = ["git.corp.example".to_string()];
+        let remote =
+            parse_remote_with_hosts("https://git.corp.example/team/svc", &registered).unwrap();
+        assert_eq!(remote.host, "git.corp.example");
+        assert_eq!(remote.repo, "svc");
+        assert_eq!(parse_gitea_remote("https://github.com/a/b.git"), None);
+    }
+
+    fn status(id: u64, context: &str, state: &str) -> CommitStatus {
+        CommitStatus {
+            id,
+            context: context.into(),
+            status: state.into(),
+            target_url: Some(format!("https://ci.example/{id}")),
+        }
+    }
+
+    fn task(id: u64, sha: &str, name: &str, state: &str) -> ActionTask {
+        ActionTask {
+            id,
+            name: name.into(),
+            head_sha: sha.into(),
+            status: state.into(),
+            workflow_id: "ci.yml".into(),
+            url: None,
+        }
+    }
+
+    #[test]
+    fn statuses_win_over_tasks_and_keep_latest_per_context() {
+        let statuses = vec![(
+            "head".to_string(),
+            vec![
+                status(1, "ci/woodpecker", "failure"),
+                status(3, "ci/woodpecker", "success"),
+                status(2, "ci.yml / test (push)", "pending"),
+            ],
+        )];
+        let tasks = vec![
+            task(10, "head", "test", "running"),
+            task(7, "old", "test", "failure"),
+            task(9, "old", "test", "success"),
+            task(8, "old", "lint", "failure"),
+        ];
+        let rollups = per_commit_rollups(&statuses, &tasks);
+
+        let head = &rollups["head"];
+        assert_eq!(head.checks.len(), 2);
+        assert_eq!(head.counts.success, 1);
+        assert_eq!(head.counts.pending, 1);
+
+        let old = &rollups["old"];
+        assert_eq!(old.checks.len(), 2);
+        assert_eq!(old.checks[0].label, "ci.yml / lint");
+        assert_eq!(old.checks[1].state, CiState::Success);
+
+        let summary = crate::ci::status_for_head(Some(head));
+        assert_eq!(summary.state, CiState::Pending);
+        assert_eq!(summary.summary, "1 pending, 1 passed");
+    }
+}
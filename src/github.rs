@@ -1,47 +1,129 @@
 //! GitHub Actions REST client — feeds CI status into the header bar
-//! and per-commit dots in the graph.
+//! and per-commit dots in the graph, with third-party check runs and
+//! commit statuses folded in for recent commits — plus the GraphQL pull request
+//! listing behind the sidebar's Pull Requests section and pull request
+//! creation. Job lists and raw job logs feed the CI log viewer; re-runs,
+//! cancels and `workflow_dispatch` back the CI actions menu. Releases
+//! are listed for the tag card and created from a tag.
+//!
+//! GitHub Enterprise Server instances use the same client against
+//! `https://<host>/api/v3`; their hosts are registered in
+//! `Config::github_hosts` and mirrored here through
+//! [`set_enterprise_hosts`].
 
 use crate::ci::{
-    CiCheckStatus, CiCommitRollup, CiCounts, CiProvider, CiState, CiStatus, ProviderCiResult,
+    CiAction, CiCheckStatus, CiCommitRollup, CiCounts, CiJob, CiProvider, CiState, CiStatus,
+    MAX_LOG_BYTES, ProviderCiResult,
 };
+use crate::ci_log::{ParsedLog, parse_log};
+use crate::http_cache;
+use crate::issues::{Issue, IssueState};
+use crate::pulls::{
+    CreatedPullRequest, NewPullRequest, ProviderPullResult, PullRequest, ReviewAction,
+    ReviewComment, ReviewSide, ReviewState, ReviewThread,
+};
+use crate::releases::{NewRelease, Release, ReleaseAsset};
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::mpsc::{self, Receiver};
+use std::sync::{LazyLock, RwLock};
 use winit::event_loop::EventLoopProxy;
 
 const API_BASE: &str = "https://api.github.com";
 
+/// Commits per poll whose check runs and commit statuses are fetched
+/// on top of the workflow runs: the default branch tip, heads of open
+/// pull requests into it, then the most recent run heads. Two requests
+/// each, so kept small.
+const CHECK_COMMITS: usize = 5;
+
+/// Page size for release listings, the most GitHub serves at once.
+const RELEASES_PER_PAGE: usize = 100;
+
+/// REST requests one CI poll makes at most: the workflow run list, the
+/// repository and its default branch, then check runs and statuses for
+/// each of the `CHECK_COMMITS`.
+pub const CI_POLL_REQUESTS: u32 = 3 + 2 * CHECK_COMMITS as u32;
+
 pub struct GitHubClient {
     token: String,
+    /// "https://api.github.com", or "https://{host}/api/v3" for
+    /// GitHub Enterprise Server.
+    api_base: String,
 }
 
-/// Extract (owner, repo) from a GitHub remote URL.
-/// Supports both HTTPS and SSH formats:
-///   https://github.com/owner/repo.git
-///   git@github.com:owner/repo.git
-pub fn parse_github_remote(url: &str) -> Option<(String, String)> {
-    let url = url.trim();
+/// GitHub Enterprise Server hosts registered in the token modal. Unlike
+/// github.com nothing in a GHE URL identifies the forge, so only these
+/// hosts are matched.
+static ENTERPRISE_HOSTS: LazyLock<RwLock<Vec<String>>> = LazyLock::new(|| RwLock::new(Vec::new()));
 
-    if let Some(path) = url.strip_prefix("git@github.com:") {
-        let path = path.strip_suffix(".git").unwrap_or(path);
-        let (owner, repo) = path.split_once('/')?;
-        if !owner.is_empty() && !repo.is_empty() {
-            return Some((owner.to_string(), repo.to_string()));
-        }
+/// Replace the registered Enterprise host list. Called at startup and
+/// whenever the token modal adds a host.
+pub fn set_enterprise_hosts(hosts: &[String]) {
+    if let Ok(mut registered) = ENTERPRISE_HOSTS.write() {
+        *registered = hosts.to_vec();
     }
+}
+
+/// Parsed GitHub remote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubRemote {
+    /// "github.com" or a registered Enterprise host; the key for tokens.
+    pub host: String,
+    /// e.g. "https://api.github.com" or "https://ghe.corp.example/api/v3"
+    pub api_base: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+/// Extract remote info from a github.com or registered GitHub
+/// Enterprise URL. Supports HTTPS and SSH formats:
+///   https://github.com/owner/repo.git
+///   git@github.com:owner/repo.git
+///   ssh://git@ghe.corp.example/owner/repo.git
+pub fn parse_github_remote(url: &str) -> Option<GitHubRemote> {
+    let hosts = ENTERPRISE_HOSTS
+        .read()
+        .map(|hosts| hosts.clone())
+        .unwrap_or_default();
+    parse_github_remote_with(url, &hosts)
+}
 
-    let path = url
-        .strip_prefix("https://github.com/")
-        .or_else(|| url.strip_prefix("http://github.com/"))?;
+/// [`parse_github_remote`] against an explicit list of Enterprise hosts.
+fn parse_github_remote_with(url: &str, enterprise_hosts: &[String]) -> Option<GitHubRemote> {
+    let url = url.trim();
+    let (host, path) = if let Some(rest) = url.strip_prefix("git@") {
+        rest.split_once(':')?
+    } else if let Some(rest) = url.strip_prefix("ssh://") {
+        let rest = rest.split_once('@').map(|(_, r)| r).unwrap_or(rest);
+        let (host, path) = rest.split_once('/')?;
+        (host.split(':').next()?, path)
+    } else {
+        url.strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))?
+            .split_once('/')?
+    };
+    let api_base = if host == "github.com" {
+        API_BASE.to_string()
+    } else if enterprise_hosts.iter().any(|h| h == host) {
+        format!("https://{host}/api/v3")
+    } else {
+        return None;
+    };
     let path = path.strip_suffix(".git").unwrap_or(path);
     let (owner, repo) = path.split_once('/')?;
     let repo = repo.split('/').next()?;
-    if !owner.is_empty() && !repo.is_empty() {
-        Some((owner.to_string(), repo.to_string()))
-    } else {
-        None
+    if owner.is_empty() || repo.is_empty() {
+        return None;
     }
+    Some(GitHubRemote {
+        host: host.to_string(),
+        api_base,
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+    })
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -54,6 +136,9 @@ pub struct WorkflowRun {
     pub status: String,
     pub conclusion: Option<String>,
     pub html_url: String,
+    /// Check suite holding the run's jobs as check runs.
+    #[serde(default)]
+    pub check_suite_id: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -61,15 +146,371 @@ struct WorkflowRunsResponse {
     workflow_runs: Vec<WorkflowRun>,
 }
 
+#[derive(Debug, Deserialize)]
+struct WorkflowJob {
+    id: u64,
+    name: String,
+    status: String,
+    conclusion: Option<String>,
+    html_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkflowJobsResponse {
+    jobs: Vec<WorkflowJob>,
+}
+
+/// A check run on a commit. GitHub Actions jobs show up here too, in
+/// their workflow run's check suite.
+#[derive(Debug, Deserialize)]
+struct CheckRun {
+    id: u64,
+    name: String,
+    status: String,
+    conclusion: Option<String>,
+    html_url: Option<String>,
+    details_url: Option<String>,
+    #[serde(default)]
+    app: Option<CheckApp>,
+    #[serde(default)]
+    check_suite: Option<CheckSuiteRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckApp {
+    slug: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckSuiteRef {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRunsResponse {
+    check_runs: Vec<CheckRun>,
+}
+
+/// A legacy commit status. The combined-status endpoint already keeps
+/// only the latest per context.
+#[derive(Debug, Deserialize)]
+struct CommitStatus {
+    context: String,
+    state: String,
+    target_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CombinedStatusResponse {
+    statuses: Vec<CommitStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepoResponse {
+    default_branch: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchResponse {
+    commit: BranchCommit,
+    #[serde(default)]
+    protection: Option<BranchProtection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchCommit {
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BranchProtection {
+    #[serde(default)]
+    required_status_checks: Option<RequiredStatusChecks>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RequiredStatusChecks {
+    #[serde(default)]
+    contexts: Vec<String>,
+    #[serde(default)]
+    checks: Vec<RequiredCheck>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RequiredCheck {
+    context: String,
+}
+
+/// The default branch with the checks its protection requires.
+struct DefaultBranch {
+    name: String,
+    tip: String,
+    required: Vec<String>,
+}
+
+/// Open PRs with everything the sidebar row shows, in one round trip.
+/// The CI rollup comes from the head commit's `statusCheckRollup`,
+/// which covers both check runs and legacy commit statuses.
+const PULL_REQUESTS_QUERY: &str = r#"
+query($owner: String!, $name: String!) {
+  repository(owner: $owner, name: $name) {
+    pullRequests(states: OPEN, first: 50, orderBy: {field: UPDATED_AT, direction: DESC}) {
+      nodes {
+        number
+        title
+        url
+        isDraft
+        reviewDecision
+        headRefName
+        baseRefName
+        headRefOid
+        author { login }
+        commits(last: 1) { nodes { commit { statusCheckRollup { state } } } }
+      }
+    }
+  }
+}
+"#;
+
+/// Review threads of one PR with their comments. `line` is null for
+/// outdated threads GitHub can no longer place in the current diff.
+const REVIEW_THREADS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100) {
+        nodes {
+          id
+          isResolved
+          isOutdated
+          path
+          line
+          diffSide
+          comments(first: 100) {
+            nodes { databaseId body createdAt author { login } }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+const RESOLVE_THREAD_MUTATION: &str = r#"
+mutation($id: ID!) { resolveReviewThread(input: {threadId: $id}) { thread { id } } }
+"#;
+
+const UNRESOLVE_THREAD_MUTATION: &str = r#"
+mutation($id: ID!) { unresolveReviewThread(input: {threadId: $id}) { thread { id } } }
+"#;
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestsData {
+    repository: Option<PullRequestsRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestsRepository {
+    pull_requests: Nodes<PullRequestNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Nodes<T> {
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestNode {
+    number: u64,
+    title: String,
+    url: String,
+    is_draft: bool,
+    review_decision: Option<String>,
+    head_ref_name: String,
+    base_ref_name: String,
+    head_ref_oid: String,
+    /// `null` for PRs from deleted accounts.
+    author: Option<PullRequestAuthor>,
+    commits: Nodes<PullRequestCommitNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestAuthor {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestCommitNode {
+    commit: PullRequestCommit,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestCommit {
+    status_check_rollup: Option<StatusCheckRollup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusCheckRollup {
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsData {
+    repository: Option<ReviewThreadsRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThreadsRepository {
+    pull_request: Option<ReviewThreadsPull>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThreadsPull {
+    review_threads: Nodes<ReviewThreadNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThreadNode {
+    id: String,
+    is_resolved: bool,
+    is_outdated: bool,
+    path: String,
+    line: Option<u32>,
+    diff_side: String,
+    comments: Nodes<ReviewCommentNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewCommentNode {
+    database_id: Option<u64>,
+    body: String,
+    created_at: String,
+    author: Option<PullRequestAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreatedPullResponse {
+    number: u64,
+    title: String,
+    html_url: String,
+    #[serde(default)]
+    draft: bool,
+    user: Option<PullRequestAuthor>,
+    head: PullRefResponse,
+    base: PullRefResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRefResponse {
+    #[serde(rename = "ref")]
+    ref_name: String,
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueResponse {
+    number: u64,
+    title: String,
+    state: String,
+    html_url: String,
+    /// Present when the number belongs to a pull request.
+    pull_request: Option<IssuePullRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssuePullRef {
+    merged_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseResponse {
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    draft: bool,
+    prerelease: bool,
+    html_url: String,
+    published_at: Option<String>,
+    #[serde(default)]
+    assets: Vec<ReleaseAssetResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseAssetResponse {
+    name: String,
+    size: u64,
+    browser_download_url: String,
+}
+
+impl From<ReleaseResponse> for Release {
+    fn from(r: ReleaseResponse) -> Self {
+        Release {
+            provider: CiProvider::GitHub,
+            tag: r.tag_name,
+            name: r.name.unwrap_or_default(),
+            notes: r.body.unwrap_or_default(),
+            draft: r.draft,
+            prerelease: r.prerelease,
+            published_at: r.published_at,
+            url: r.html_url,
+            assets: r
+                .assets
+                .into_iter()
+                .map(|a| ReleaseAsset {
+                    name: a.name,
+                    size: Some(a.size),
+                    url: a.browser_download_url,
+                })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct GitHubApiErrorBody {
     message: Option<String>,
     documentation_url: Option<String>,
+    /// Validation details on 422s, e.g. "A pull request already exists".
+    #[serde(default)]
+    errors: Vec<GitHubApiErrorDetail>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubApiErrorDetail {
+    message: Option<String>,
 }
 
 impl GitHubClient {
-    pub fn new(token: String) -> Self {
-        Self { token }
+    pub fn new(token: String, api_base: String) -> Self {
+        Self { token, api_base }
+    }
+
+    /// Absolute URL for an API `path`. GitHub Enterprise Server serves
+    /// GraphQL from `/api/graphql`, beside rather than under `/api/v3`.
+    fn url(&self, path: &str) -> String {
+        match (path, self.api_base.strip_suffix("/api/v3")) {
+            ("/graphql", Some(root)) => format!("{root}/api/graphql"),
+            _ => format!("{}{path}", self.api_base),
+        }
     }
 
     fn classify_http_error(status: u16, body: &str) -> String {
@@ -121,6 +562,14 @@ impl GitHubClient {
         {
             message.push_str(&format!(" GitHub says: {api_message}"));
         }
+        let details: Vec<&str> = parsed
+            .iter()
+            .flat_map(|p| &p.errors)
+            .filter_map(|e| e.message.as_deref())
+            .collect();
+        if !details.is_empty() {
+            message.push_str(&format!(" ({})", details.join("; ")));
+        }
         if let Some(url) = docs_url {
             message.push_str(&format!(" See: {url}"));
         }
@@ -140,8 +589,29 @@ impl GitHubClient {
         anyhow::bail!(Self::classify_http_error(status, &body));
     }
 
+    /// Conditional through [`http_cache::get`], so re-polling an
+    /// unchanged list costs a 304 rather than a download.
     fn get(&self, path: &str) -> Result<ureq::http::Response<ureq::Body>> {
-        let mut resp = ureq::get(&format!("{API_BASE}{path}"))
+        let url = self.url(path);
+        let request = ureq::get(&url)
+            .header("Authorization", &format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "whisper-git")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .config()
+            .http_status_as_error(false)
+            .build();
+        let mut resp = http_cache::get(&url, request).map_err(Self::map_ureq_error)?;
+        Self::ensure_success(&mut resp)?;
+        Ok(resp)
+    }
+
+    fn post(
+        &self,
+        path: &str,
+        body: serde_json::Value,
+    ) -> Result<ureq::http::Response<ureq::Body>> {
+        let mut resp = ureq::post(&self.url(path))
             .header("Authorization", &format!("Bearer {}", self.token))
             .header("Accept", "application/vnd.github+json")
             .header("User-Agent", "whisper-git")
@@ -149,43 +619,642 @@ impl GitHubClient {
             .config()
             .http_status_as_error(false)
             .build()
-            .call()
+            .send_json(body)
             .map_err(Self::map_ureq_error)?;
         Self::ensure_success(&mut resp)?;
         Ok(resp)
     }
-
-    pub fn workflow_runs(
-        &self,
-        owner: &str,
-        repo: &str,
-        branch: Option<&str>,
-        per_page: u32,
-    ) -> Result<Vec<WorkflowRun>> {
-        let mut path = format!("/repos/{owner}/{repo}/actions/runs?per_page={per_page}");
-        if let Some(branch) = branch {
-            path.push_str(&format!("&branch={branch}"));
+
+    /// Run a GraphQL query. GraphQL-level errors arrive with HTTP 200,
+    /// so they're checked here rather than by `ensure_success`.
+    fn graphql<T: serde::de::DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T> {
+        let mut resp = self.post(
+            "/graphql",
+            serde_json::json!({ "query": query, "variables": variables }),
+        )?;
+        let body: GraphQlResponse<T> = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse GitHub GraphQL response")?;
+        graphql_data(body)
+    }
+
+    /// Review threads on pull request `number`, oldest first.
+    pub fn review_threads(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<ReviewThread>> {
+        let data: ReviewThreadsData = self
+            .graphql(
+                REVIEW_THREADS_QUERY,
+                serde_json::json!({ "owner": owner, "name": repo, "number": number }),
+            )
+            .with_context(|| format!("Failed to fetch review threads for #{number}"))?;
+        threads_from_graphql(data)
+    }
+
+    /// Reply to, resolve or start a review thread on pull request
+    /// `number`.
+    pub fn review_action(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        action: &ReviewAction,
+    ) -> Result<()> {
+        match action {
+            ReviewAction::Reply { reply_to, body, .. } => {
+                self.post(
+                    &format!("/repos/{owner}/{repo}/pulls/{number}/comments/{reply_to}/replies"),
+                    serde_json::json!({ "body": body }),
+                )
+                .context("Failed to post reply")?;
+            }
+            ReviewAction::SetResolved {
+                thread_id,
+                resolved,
+            } => {
+                let mutation = if *resolved {
+                    RESOLVE_THREAD_MUTATION
+                } else {
+                    UNRESOLVE_THREAD_MUTATION
+                };
+                self.graphql::<serde_json::Value>(mutation, serde_json::json!({ "id": thread_id }))
+                    .context("Failed to update review thread")?;
+            }
+            ReviewAction::Comment {
+                path,
+                old_line,
+                new_line,
+                commit_sha,
+                body,
+            } => {
+                let (side, line) = match (new_line, old_line) {
+                    (Some(line), _) => ("RIGHT", line),
+                    (None, Some(line)) => ("LEFT", line),
+                    (None, None) => anyhow::bail!("Review comment has no line"),
+                };
+                self.post(
+                    &format!("/repos/{owner}/{repo}/pulls/{number}/comments"),
+                    serde_json::json!({
+                        "body": body,
+                        "commit_id": commit_sha,
+                        "path": path,
+                        "line": line,
+                        "side": side,
+                    }),
+                )
+                .context("Failed to post review comment")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Open pull requests, most recently updated first.
+    pub fn pull_requests(&self, owner: &str, repo: &str) -> Result<Vec<PullRequest>> {
+        let data: PullRequestsData = self
+            .graphql(
+                PULL_REQUESTS_QUERY,
+                serde_json::json!({ "owner": owner, "name": repo }),
+            )
+            .with_context(|| format!("Failed to fetch pull requests for {owner}/{repo}"))?;
+        pulls_from_graphql(data)
+    }
+
+    /// Open a pull request from a branch of this repository, then
+    /// request reviewers and add labels.
+    pub fn create_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        request: &NewPullRequest,
+    ) -> Result<CreatedPullRequest> {
+        let mut resp = self
+            .post(
+                &format!("/repos/{owner}/{repo}/pulls"),
+                serde_json::json!({
+                    "title": request.title,
+                    "head": request.source_branch,
+                    "base": request.target_branch,
+                    "body": request.body,
+                    "draft": request.draft,
+                }),
+            )
+            .with_context(|| format!("Failed to create pull request for {owner}/{repo}"))?;
+        let created: CreatedPullResponse = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse created pull request")?;
+
+        let mut warnings = Vec::new();
+        let mut review = ReviewState::None;
+        if !request.reviewers.is_empty() {
+            match self.post(
+                &format!(
+                    "/repos/{owner}/{repo}/pulls/{}/requested_reviewers",
+                    created.number
+                ),
+                serde_json::json!({ "reviewers": request.reviewers }),
+            ) {
+                Ok(_) => review = ReviewState::ReviewRequired,
+                Err(e) => warnings.push(format!("Couldn't request reviewers: {e:#}")),
+            }
+        }
+        if !request.labels.is_empty()
+            && let Err(e) = self.post(
+                &format!("/repos/{owner}/{repo}/issues/{}/labels", created.number),
+                serde_json::json!({ "labels": request.labels }),
+            )
+        {
+            warnings.push(format!("Couldn't add labels: {e:#}"));
+        }
+
+        Ok(CreatedPullRequest {
+            pull: PullRequest {
+                provider: CiProvider::GitHub,
+                number: created.number,
+                title: created.title,
+                author: created.user.map(|u| u.login).unwrap_or_default(),
+                draft: created.draft,
+                review,
+                source_branch: created.head.ref_name,
+                target_branch: created.base.ref_name,
+                head_sha: created.head.sha,
+                url: created.html_url,
+                ci: None,
+            },
+            warnings,
+        })
+    }
+
+    /// Issue or pull request `number`, through the issues endpoint
+    /// that serves both.
+    pub fn issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
+        let mut resp = self
+            .get(&format!("/repos/{owner}/{repo}/issues/{number}"))
+            .with_context(|| format!("Failed to fetch issue #{number}"))?;
+        let issue: IssueResponse = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse issue")?;
+        let merged = issue
+            .pull_request
+            .as_ref()
+            .is_some_and(|p| p.merged_at.is_some());
+        Ok(Issue {
+            number: issue.number,
+            title: issue.title,
+            state: match issue.state.as_str() {
+                "open" => IssueState::Open,
+                _ if merged => IssueState::Merged,
+                _ => IssueState::Closed,
+            },
+            pull_request: issue.pull_request.is_some(),
+            url: issue.html_url,
+        })
+    }
+
+    /// Every release, newest first, read page by page until a short
+    /// page. Drafts are included for tokens with push access.
+    pub fn releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>> {
+        let mut releases = Vec::new();
+        for page in 1u32.. {
+            let batch: Vec<ReleaseResponse> = self
+                .get(&format!(
+                    "/repos/{owner}/{repo}/releases?per_page={RELEASES_PER_PAGE}&page={page}"
+                ))
+                .with_context(|| format!("Failed to fetch releases for {owner}/{repo}"))?
+                .body_mut()
+                .read_json()
+                .context("Failed to parse releases")?;
+            let last = batch.len() < RELEASES_PER_PAGE;
+            releases.extend(batch.into_iter().map(Release::from));
+            if last {
+                break;
+            }
+        }
+        Ok(releases)
+    }
+
+    /// Publish a release for an existing tag.
+    pub fn create_release(&self, owner: &str, repo: &str, request: &NewRelease) -> Result<Release> {
+        let mut resp = self
+            .post(
+                &format!("/repos/{owner}/{repo}/releases"),
+                serde_json::json!({
+                    "tag_name": request.tag,
+                    "target_commitish": request.target_sha,
+                    "name": request.name,
+                    "body": request.notes,
+                    "draft": request.draft,
+                    "prerelease": request.prerelease,
+                }),
+            )
+            .with_context(|| format!("Failed to create release {}", request.tag))?;
+        let created: ReleaseResponse = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse created release")?;
+        Ok(created.into())
+    }
+
+    pub fn workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: Option<&str>,
+        per_page: u32,
+    ) -> Result<Vec<WorkflowRun>> {
+        let mut path = format!("/repos/{owner}/{repo}/actions/runs?per_page={per_page}");
+        if let Some(branch) = branch {
+            path.push_str(&format!("&branch={branch}"));
+        }
+        let mut resp = self
+            .get(&path)
+            .with_context(|| format!("Failed to fetch workflow runs for {owner}/{repo}"))?;
+        let body: WorkflowRunsResponse = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse runs")?;
+        Ok(body.workflow_runs)
+    }
+
+    /// Check runs on commit `sha`, Actions jobs included.
+    fn check_runs(&self, owner: &str, repo: &str, sha: &str) -> Result<Vec<CheckRun>> {
+        let mut resp = self
+            .get(&format!(
+                "/repos/{owner}/{repo}/commits/{sha}/check-runs?per_page=100"
+            ))
+            .with_context(|| format!("Failed to fetch check runs for {sha}"))?;
+        let body: CheckRunsResponse = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse check runs")?;
+        Ok(body.check_runs)
+    }
+
+    /// Latest commit status per context on commit `sha`.
+    fn commit_statuses(&self, owner: &str, repo: &str, sha: &str) -> Result<Vec<CommitStatus>> {
+        let mut resp = self
+            .get(&format!(
+                "/repos/{owner}/{repo}/commits/{sha}/status?per_page=100"
+            ))
+            .with_context(|| format!("Failed to fetch commit statuses for {sha}"))?;
+        let body: CombinedStatusResponse = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse commit statuses")?;
+        Ok(body.statuses)
+    }
+
+    /// The default branch and the check names its branch protection
+    /// requires. The branch endpoint reports protection to anyone who
+    /// can read the repo, unlike the protection endpoint itself, which
+    /// needs admin rights.
+    fn default_branch_checks(&self, owner: &str, repo: &str) -> Result<DefaultBranch> {
+        let repo_info: RepoResponse = self
+            .get(&format!("/repos/{owner}/{repo}"))
+            .with_context(|| format!("Failed to fetch {owner}/{repo}"))?
+            .body_mut()
+            .read_json()
+            .context("Failed to parse repository")?;
+        let branch: BranchResponse = self
+            .get(&format!(
+                "/repos/{owner}/{repo}/branches/{}",
+                repo_info.default_branch
+            ))
+            .with_context(|| format!("Failed to fetch branch {}", repo_info.default_branch))?
+            .body_mut()
+            .read_json()
+            .context("Failed to parse branch")?;
+        let mut required: Vec<String> = branch
+            .protection
+            .and_then(|p| p.required_status_checks)
+            .map(|r| {
+                r.contexts
+                    .into_iter()
+                    .chain(r.checks.into_iter().map(|c| c.context))
+                    .collect()
+            })
+            .unwrap_or_default();
+        required.sort();
+        required.dedup();
+        Ok(DefaultBranch {
+            name: repo_info.default_branch,
+            tip: branch.commit.sha,
+            required,
+        })
+    }
+
+    /// Jobs of workflow run `run_id`, latest attempt only.
+    pub fn run_jobs(&self, owner: &str, repo: &str, run_id: u64) -> Result<Vec<CiJob>> {
+        let mut resp = self
+            .get(&format!(
+                "/repos/{owner}/{repo}/actions/runs/{run_id}/jobs?per_page=100"
+            ))
+            .with_context(|| format!("Failed to fetch jobs for run {run_id}"))?;
+        let body: WorkflowJobsResponse = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse jobs")?;
+        Ok(body
+            .jobs
+            .into_iter()
+            .map(|job| CiJob {
+                state: conclusion_state(&job.status, job.conclusion.as_deref()),
+                id: job.id,
+                name: job.name,
+                stage: None,
+                url: job.html_url,
+            })
+            .collect())
+    }
+
+    /// Raw log of job `job_id`. GitHub redirects to a short-lived
+    /// download URL; ureq follows it and drops our token on the way.
+    pub fn job_log(&self, owner: &str, repo: &str, job_id: u64) -> Result<String> {
+        let mut resp = self
+            .get(&format!("/repos/{owner}/{repo}/actions/jobs/{job_id}/logs"))
+            .with_context(|| format!("Failed to fetch the log for job {job_id}"))?;
+        resp.body_mut()
+            .with_config()
+            .limit(MAX_LOG_BYTES)
+            .read_to_string()
+            .context("Failed to read job log")
+    }
+
+    /// Apply a [`CiAction`]. Multi-run actions go run by run and stop
+    /// at the first one GitHub refuses.
+    pub fn ci_action(&self, owner: &str, repo: &str, action: &CiAction) -> Result<()> {
+        let runs = format!("/repos/{owner}/{repo}/actions/runs");
+        let (run_ids, verb) = match action {
+            CiAction::RerunFailed(ids) => (ids, "rerun-failed-jobs"),
+            CiAction::RerunAll(ids) => (ids, "rerun"),
+            CiAction::Cancel(ids) => (ids, "cancel"),
+            CiAction::Trigger {
+                workflow,
+                git_ref,
+                inputs,
+            } => {
+                let inputs: serde_json::Map<String, serde_json::Value> = inputs
+                    .iter()
+                    .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+                    .collect();
+                self.post(
+                    &format!("/repos/{owner}/{repo}/actions/workflows/{workflow}/dispatches"),
+                    serde_json::json!({ "ref": git_ref, "inputs": inputs }),
+                )
+                .with_context(|| format!("Failed to dispatch {workflow} on {git_ref}"))?;
+                return Ok(());
+            }
+        };
+        for id in run_ids {
+            self.post(&format!("{runs}/{id}/{verb}"), serde_json::json!({}))
+                .with_context(|| format!("Failed to {verb} run {id}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Workflows under `.github/workflows` in `workdir` that accept
+/// `workflow_dispatch`, as `(file name, input names)` sorted by name.
+/// Read from the checkout, so a workflow only on another branch won't
+/// be listed.
+pub fn dispatchable_workflows(workdir: &Path) -> Vec<(String, Vec<String>)> {
+    let Ok(entries) = std::fs::read_dir(workdir.join(".github/workflows")) else {
+        return Vec::new();
+    };
+    let mut workflows: Vec<(String, Vec<String>)> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            if !(name.ends_with(".yml") || name.ends_with(".yaml")) {
+                return None;
+            }
+            let yaml = std::fs::read_to_string(e.path()).ok()?;
+            Some((name, dispatch_inputs(&yaml)?))
+        })
+        .collect();
+    workflows.sort();
+    workflows
+}
+
+/// Input names of a workflow's `workflow_dispatch` trigger, or `None`
+/// when it has none (or the file isn't valid YAML). `on:` may be a
+/// single event, a list of events or a map keyed by event.
+fn dispatch_inputs(yaml: &str) -> Option<Vec<String>> {
+    use serde_yaml::Value;
+    const TRIGGER: &str = "workflow_dispatch";
+    let workflow: Value = serde_yaml::from_str(yaml).ok()?;
+    match workflow.get("on")? {
+        Value::String(event) => (event == TRIGGER).then(Vec::new),
+        Value::Sequence(events) => events
+            .iter()
+            .any(|e| e.as_str() == Some(TRIGGER))
+            .then(Vec::new),
+        Value::Mapping(events) => {
+            let trigger = events.get(TRIGGER)?;
+            let inputs = trigger
+                .get("inputs")
+                .and_then(Value::as_mapping)
+                .map(|inputs| {
+                    inputs
+                        .keys()
+                        .filter_map(|k| k.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(inputs)
+        }
+        _ => None,
+    }
+}
+
+/// State of a commit status.
+fn commit_status_state(state: &str) -> CiState {
+    match state {
+        "success" => CiState::Success,
+        "failure" | "error" => CiState::Failure,
+        _ => CiState::Pending,
+    }
+}
+
+/// Importance order for compact dot strips: failures first.
+fn check_sort_key(check: &CiCheckStatus) -> u8 {
+    match check.state {
+        CiState::Failure => 0,
+        CiState::Pending => 1,
+        CiState::Success => 2,
+        CiState::None => 3,
+    }
+}
+
+/// Fold a commit's check runs and commit statuses into `rollup`, which
+/// holds its workflow runs. Actions check runs belong to a run's check
+/// suite and only mark that run required; other check runs (latest per
+/// name) and then statuses whose context no check already covers are
+/// added as checks of their own. A required context nothing reported
+/// is added as pending, the way GitHub waits on it before merging.
+fn merge_commit_checks(
+    rollup: &mut CiCommitRollup,
+    runs: &[WorkflowRun],
+    check_runs: &[CheckRun],
+    statuses: &[CommitStatus],
+    required: &[String],
+) {
+    let is_required = |name: &str| required.iter().any(|r| r == name);
+    let suite_runs: HashMap<u64, u64> = runs
+        .iter()
+        .filter_map(|r| Some((r.check_suite_id?, r.id)))
+        .collect();
+
+    let mut third_party: HashMap<&str, &CheckRun> = HashMap::new();
+    for check in check_runs {
+        let run_id = check
+            .check_suite
+            .as_ref()
+            .and_then(|suite| suite_runs.get(&suite.id));
+        if let Some(run_id) = run_id {
+            if is_required(&check.name)
+                && let Some(c) = rollup.checks.iter_mut().find(|c| c.run_id == Some(*run_id))
+            {
+                c.required = true;
+            }
+            continue;
+        }
+        let actions = check
+            .app
+            .as_ref()
+            .and_then(|a| a.slug.as_deref())
+            .is_some_and(|slug| slug == "github-actions");
+        if actions {
+            // A job of a run too old to be in the runs page.
+            continue;
+        }
+        let entry = third_party.entry(check.name.as_str()).or_insert(check);
+        if check.id > entry.id {
+            *entry = check;
+        }
+    }
+
+    for check in &mut rollup.checks {
+        check.required |= is_required(&check.label);
+    }
+    for check in third_party.into_values() {
+        if rollup.checks.iter().any(|c| c.label == check.name) {
+            continue;
+        }
+        rollup.checks.push(CiCheckStatus {
+            label: check.name.clone(),
+            state: conclusion_state(&check.status, check.conclusion.as_deref()),
+            url: check.html_url.clone().or_else(|| check.details_url.clone()),
+            run_id: None,
+            required: is_required(&check.name),
+        });
+    }
+    for status in statuses {
+        if rollup.checks.iter().any(|c| c.label == status.context) {
+            continue;
+        }
+        rollup.checks.push(CiCheckStatus {
+            label: status.context.clone(),
+            state: commit_status_state(&status.state),
+            url: status.target_url.clone().filter(|u| !u.is_empty()),
+            run_id: None,
+            required: is_required(&status.context),
+        });
+    }
+    for name in required {
+        let reported = check_runs.iter().any(|c| c.name == *name)
+            || rollup.checks.iter().any(|c| c.label == *name);
+        if !reported {
+            rollup.checks.push(CiCheckStatus {
+                label: name.clone(),
+                state: CiState::Pending,
+                url: None,
+                run_id: None,
+                required: true,
+            });
         }
-        let mut resp = self
-            .get(&path)
-            .with_context(|| format!("Failed to fetch workflow runs for {owner}/{repo}"))?;
-        let body: WorkflowRunsResponse = resp
-            .body_mut()
-            .read_json()
-            .context("Failed to parse runs")?;
-        Ok(body.workflow_runs)
     }
+    rollup.checks.sort_by_key(check_sort_key);
+    rollup.counts = CiCounts::from_states(rollup.checks.iter().map(|c| c.state));
 }
 
 fn run_state(run: &WorkflowRun) -> CiState {
-    match run.conclusion.as_deref() {
+    conclusion_state(&run.status, run.conclusion.as_deref())
+}
+
+/// State of a run or job from its `status` / `conclusion` pair.
+fn conclusion_state(status: &str, conclusion: Option<&str>) -> CiState {
+    match conclusion {
         Some("success") => CiState::Success,
         Some("failure" | "timed_out" | "cancelled") => CiState::Failure,
-        _ if run.status == "completed" => CiState::Success,
+        _ if status == "completed" => CiState::Success,
         _ => CiState::Pending,
     }
 }
 
+/// The data of a GraphQL response, or its first error.
+fn graphql_data<T>(body: GraphQlResponse<T>) -> Result<T> {
+    if let Some(err) = body.errors.first() {
+        anyhow::bail!("GitHub GraphQL error: {}", err.message);
+    }
+    body.data.context("GitHub GraphQL response had no data")
+}
+
+fn pulls_from_graphql(data: PullRequestsData) -> Result<Vec<PullRequest>> {
+    let repository = data
+        .repository
+        .context("GitHub returned no repository for this remote")?;
+    Ok(repository
+        .pull_requests
+        .nodes
+        .into_iter()
+        .map(|node| {
+            let ci = node
+                .commits
+                .nodes
+                .first()
+                .and_then(|c| c.commit.status_check_rollup.as_ref())
+                .map(|rollup| match rollup.state.as_str() {
+                    "SUCCESS" => CiState::Success,
+                    "FAILURE" | "ERROR" => CiState::Failure,
+                    "PENDING" | "EXPECTED" => CiState::Pending,
+                    _ => CiState::None,
+                });
+            let review = match node.review_decision.as_deref() {
+                Some("APPROVED") => ReviewState::Approved,
+                Some("CHANGES_REQUESTED") => ReviewState::ChangesRequested,
+                Some("REVIEW_REQUIRED") => ReviewState::ReviewRequired,
+                _ => ReviewState::None,
+            };
+            PullRequest {
+                provider: CiProvider::GitHub,
+                number: node.number,
+                title: node.title,
+                author: node
+                    .author
+                    .map(|a| a.login)
+                    .unwrap_or_else(|| "ghost".to_string()),
+                draft: node.is_draft,
+                review,
+                source_branch: node.head_ref_name,
+                target_branch: node.base_ref_name,
+                head_sha: node.head_ref_oid,
+                url: node.url,
+                ci,
+            }
+        })
+        .collect())
+}
+
 /// Summarize workflow runs into a single CI status.
 ///
 /// Runs arrive sorted by ID descending (most recent first). We only
@@ -222,6 +1291,8 @@ fn ci_status_from_runs(runs: &[WorkflowRun]) -> CiStatus {
             label: run.name.clone(),
             state: run_state(run),
             url: Some(run.html_url.clone()),
+            run_id: Some(run.id),
+            required: false,
         })
         .collect();
     checks.sort_by(|a, b| a.label.cmp(&b.label));
@@ -286,15 +1357,12 @@ fn per_commit_rollups(runs: &[WorkflowRun]) -> HashMap<String, CiCommitRollup> {
                 label: run.name.clone(),
                 state: run_state(run),
                 url: Some(run.html_url.clone()),
+                run_id: Some(run.id),
+                required: false,
             })
             .collect();
         // Most important states first in compact dot strips.
-        checks.sort_by_key(|c| match c.state {
-            CiState::Failure => 0,
-            CiState::Pending => 1,
-            CiState::Success => 2,
-            CiState::None => 3,
-        });
+        checks.sort_by_key(check_sort_key);
 
         let counts = CiCounts::from_states(checks.iter().map(|c| c.state));
         result.insert(sha.to_string(), CiCommitRollup { counts, checks });
@@ -303,39 +1371,481 @@ fn per_commit_rollups(runs: &[WorkflowRun]) -> HashMap<String, CiCommitRollup> {
     result
 }
 
+/// Commits the default branch's protection binds: its tip and the
+/// heads of open pull requests into it.
+fn protected_heads<'a>(branch: &'a DefaultBranch, pulls: &'a [PullRequest]) -> Vec<&'a str> {
+    let mut heads = vec![branch.tip.as_str()];
+    for pr in pulls {
+        if pr.target_branch == branch.name && !heads.contains(&pr.head_sha.as_str()) {
+            heads.push(&pr.head_sha);
+        }
+    }
+    heads
+}
+
+/// Workflow runs for the repo, with check runs, commit statuses and
+/// required checks merged in for the [`CHECK_COMMITS`] most relevant
+/// commits. Only the runs are essential; a failed check run or status
+/// request is noted in the header summary. `pulls` are the open pull
+/// requests, whose heads into the default branch owe its required
+/// checks.
+fn fetch_ci_result(
+    client: &GitHubClient,
+    owner: &str,
+    repo: &str,
+    pulls: &[PullRequest],
+) -> ProviderCiResult {
+    let runs = match client.workflow_runs(owner, repo, None, 50) {
+        Ok(runs) => runs,
+        Err(e) => {
+            return ProviderCiResult {
+                provider: CiProvider::GitHub,
+                host: String::new(),
+                status: CiStatus {
+                    state: CiState::None,
+                    summary: format!("CI fetch failed: {e}"),
+                    url: None,
+                    counts: None,
+                },
+                per_commit_rollups: HashMap::new(),
+                stale: false,
+            };
+        }
+    };
+    let mut per_commit_rollups = per_commit_rollups(&runs);
+    let branch = client.default_branch_checks(owner, repo).ok();
+    let protected = branch
+        .as_ref()
+        .map(|b| protected_heads(b, pulls))
+        .unwrap_or_default();
+
+    let mut shas: Vec<&str> = Vec::with_capacity(CHECK_COMMITS);
+    for sha in protected
+        .iter()
+        .copied()
+        .chain(runs.iter().map(|r| r.head_sha.as_str()))
+    {
+        if shas.len() == CHECK_COMMITS {
+            break;
+        }
+        if !shas.contains(&sha) {
+            shas.push(sha);
+        }
+    }
+    let mut lookup_errors = Vec::new();
+    for sha in shas {
+        let check_runs = client.check_runs(owner, repo, sha).unwrap_or_else(|e| {
+            lookup_errors.push(format!("{e:#}"));
+            Vec::new()
+        });
+        let statuses = client
+            .commit_statuses(owner, repo, sha)
+            .unwrap_or_else(|e| {
+                lookup_errors.push(format!("{e:#}"));
+                Vec::new()
+            });
+        let sha_runs: Vec<WorkflowRun> =
+            runs.iter().filter(|r| r.head_sha == sha).cloned().collect();
+        let required: &[String] = match &branch {
+            Some(b) if protected.contains(&sha) => &b.required,
+            _ => &[],
+        };
+        merge_commit_checks(
+            per_commit_rollups.entry(sha.to_string()).or_default(),
+            &sha_runs,
+            &check_runs,
+            &statuses,
+            required,
+        );
+    }
+    per_commit_rollups.retain(|_, rollup| !rollup.checks.is_empty());
+
+    // The header follows the newest run's commit (the tip when nothing
+    // ran on Actions); once other checks joined it, summarise them all.
+    let tip = branch.as_ref().map(|b| b.tip.as_str());
+    let head = runs.first().map(|r| r.head_sha.as_str()).or(tip);
+    let mut status = match head.and_then(|sha| per_commit_rollups.get(sha)) {
+        Some(rollup) if rollup.checks.iter().any(|c| c.run_id.is_none()) => {
+            crate::ci::status_for_head(Some(rollup))
+        }
+        _ => ci_status_from_runs(&runs),
+    };
+    if let Some(first) = lookup_errors.first() {
+        status.summary = match lookup_errors.len() - 1 {
+            0 => format!("{} (check lookup failed: {first})", status.summary),
+            more => format!(
+                "{} (check lookup failed: {first}, and {more} more)",
+                status.summary
+            ),
+        };
+    }
+    ProviderCiResult {
+        provider: CiProvider::GitHub,
+        host: String::new(),
+        status,
+        per_commit_rollups,
+        stale: false,
+    }
+}
+
 /// Fetch CI status for a GitHub repo asynchronously. Returns `None` when
 /// `origin_url` doesn't parse as a GitHub URL.
 pub fn fetch_ci_status_async(
     token: &str,
     origin_url: &str,
+    pulls: Vec<PullRequest>,
     proxy: EventLoopProxy<()>,
 ) -> Option<Receiver<ProviderCiResult>> {
-    let (owner, repo) = parse_github_remote(origin_url)?;
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
     let token = token.to_string();
     let (tx, rx) = mpsc::channel();
 
     std::thread::spawn(move || {
-        let client = GitHubClient::new(token);
-        let result = match client.workflow_runs(&owner, &repo, None, 50) {
-            Ok(runs) => {
-                let per_commit_rollups = per_commit_rollups(&runs);
-                ProviderCiResult {
-                    provider: CiProvider::GitHub,
-                    status: ci_status_from_runs(&runs),
-                    per_commit_rollups,
-                }
-            }
-            Err(e) => ProviderCiResult {
-                provider: CiProvider::GitHub,
-                status: CiStatus {
-                    state: CiState::None,
-                    summary: format!("CI fetch failed: {e}"),
-                    url: None,
-                    counts: None,
-                },
-                per_commit_rollups: HashMap::new(),
+        let client = GitHubClient::new(token, api_base);
+        let result = fetch_ci_result(&client, &owner, &repo, &pulls);
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Fetch open pull requests for a GitHub repo asynchronously. Returns
+/// `None` when `origin_url` doesn't parse as a GitHub URL.
+pub fn fetch_pull_requests_async(
+    token: &str,
+    origin_url: &str,
+    remote: String,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<ProviderPullResult>> {
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitHubClient::new(token, api_base);
+        let pulls = client
+            .pull_requests(&owner, &repo)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(ProviderPullResult {
+            provider: CiProvider::GitHub,
+            host: String::new(),
+            remote,
+            pulls,
+        });
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Open a pull request on a GitHub repo asynchronously. Returns `None`
+/// when `origin_url` doesn't parse as a GitHub URL.
+pub fn create_pull_request_async(
+    token: &str,
+    origin_url: &str,
+    request: NewPullRequest,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<CreatedPullRequest, String>>> {
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitHubClient::new(token, api_base);
+        let result = client
+            .create_pull_request(&owner, &repo, &request)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+fn threads_from_graphql(data: ReviewThreadsData) -> Result<Vec<ReviewThread>> {
+    let pull = data
+        .repository
+        .and_then(|r| r.pull_request)
+        .context("GitHub returned no pull request")?;
+    Ok(pull
+        .review_threads
+        .nodes
+        .into_iter()
+        .map(|node| ReviewThread {
+            reply_to: node
+                .comments
+                .nodes
+                .first()
+                .and_then(|c| c.database_id)
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            id: node.id,
+            path: node.path,
+            side: if node.diff_side == "LEFT" {
+                ReviewSide::Old
+            } else {
+                ReviewSide::New
             },
-        };
+            line: node.line,
+            outdated: node.is_outdated,
+            resolved: node.is_resolved,
+            comments: node
+                .comments
+                .nodes
+                .into_iter()
+                .map(|c| ReviewComment {
+                    author: c
+                        .author
+                        .map(|a| a.login)
+                        .unwrap_or_else(|| "ghost".to_string()),
+                    body: c.body,
+                    created_at: c.created_at,
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// Fetch the jobs of workflow run `run_id` asynchronously. Returns
+/// `None` when `origin_url` doesn't parse as a GitHub URL.
+pub fn fetch_ci_jobs_async(
+    token: &str,
+    origin_url: &str,
+    run_id: u64,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<Vec<CiJob>, String>>> {
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitHubClient::new(token, api_base);
+        let result = client
+            .run_jobs(&owner, &repo, run_id)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Download and parse the log of job `job_id` asynchronously. Returns
+/// `None` when `origin_url` doesn't parse as a GitHub URL.
+pub fn fetch_job_log_async(
+    token: &str,
+    origin_url: &str,
+    job_id: u64,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<ParsedLog, String>>> {
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitHubClient::new(token, api_base);
+        let result = client
+            .job_log(&owner, &repo, job_id)
+            .map(|raw| parse_log(&raw))
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Apply a [`CiAction`] asynchronously. Returns `None` when
+/// `origin_url` doesn't parse as a GitHub URL.
+pub fn ci_action_async(
+    token: &str,
+    origin_url: &str,
+    action: CiAction,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<(), String>>> {
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitHubClient::new(token, api_base);
+        let result = client
+            .ci_action(&owner, &repo, &action)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Fetch review threads for pull request `number` asynchronously.
+/// Returns `None` when `origin_url` doesn't parse as a GitHub URL.
+pub fn fetch_review_threads_async(
+    token: &str,
+    origin_url: &str,
+    number: u64,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<Vec<ReviewThread>, String>>> {
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitHubClient::new(token, api_base);
+        let result = client
+            .review_threads(&owner, &repo, number)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Apply a [`ReviewAction`] to pull request `number` asynchronously.
+/// Returns `None` when `origin_url` doesn't parse as a GitHub URL.
+pub fn review_action_async(
+    token: &str,
+    origin_url: &str,
+    number: u64,
+    action: ReviewAction,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<(), String>>> {
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitHubClient::new(token, api_base);
+        let result = client
+            .review_action(&owner, &repo, number, &action)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Fetch issue `number` asynchronously. Returns `None` when
+/// `origin_url` doesn't parse as a GitHub URL.
+pub fn fetch_issue_async(
+    token: &str,
+    origin_url: &str,
+    number: u64,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<Issue, String>>> {
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitHubClient::new(token, api_base);
+        let result = client
+            .issue(&owner, &repo, number)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Fetch the releases of a GitHub repo asynchronously. Returns `None`
+/// when `origin_url` doesn't parse as a GitHub URL.
+pub fn fetch_releases_async(
+    token: &str,
+    origin_url: &str,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<Vec<Release>, String>>> {
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitHubClient::new(token, api_base);
+        let result = client.releases(&owner, &repo).map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Create a release on a GitHub repo asynchronously. Returns `None`
+/// when `origin_url` doesn't parse as a GitHub URL.
+pub fn create_release_async(
+    token: &str,
+    origin_url: &str,
+    request: NewRelease,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<Release, String>>> {
+    let GitHubRemote {
+        api_base,
+        owner,
+        repo,
+        ..
+    } = parse_github_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitHubClient::new(token, api_base);
+        let result = client
+            .create_release(&owner, &repo, &request)
+            .map_err(|e| format!("{e:#}"));
         let _ = tx.send(result);
         let _ = proxy.send_event(());
     });
@@ -349,23 +1859,47 @@ mod tests {
 
     #[test]
     fn parse_ssh_url() {
-        let (owner, repo) = parse_github_remote("git@github.com:user/project.git").unwrap();
-        assert_eq!(owner, "user");
-        assert_eq!(repo, "project");
+        let remote = parse_github_remote("git@github.com:user/project.git").unwrap();
+        assert_eq!(remote.owner, "user");
+        assert_eq!(remote.repo, "project");
     }
 
     #[test]
     fn parse_https_url() {
-        let (owner, repo) = parse_github_remote("https://github.com/user/project.git").unwrap();
-        assert_eq!(owner, "user");
-        assert_eq!(repo, "project");
+        let remote = parse_github_remote("https://github.com/user/project.git").unwrap();
+        assert_eq!(remote.owner, "user");
+        assert_eq!(remote.repo, "project");
     }
 
     #[test]
     fn parse_https_no_dotgit() {
-        let (owner, repo) = parse_github_remote("https://github.com/user/project").unwrap();
-        assert_eq!(owner, "user");
-        assert_eq!(repo, "project");
+        let remote = parse_github_remote("https://github.com/user/project").unwrap();
+        assert_eq!(remote.owner, "user");
+        assert_eq!(remote.repo, "project");
+    }
+
+    #[test]
+    fn dispatch_inputs_lists_keys_under_inputs() {
+        let yaml = "name: Release\non:\n  push:\n    branches: [main]\n  workflow_dispatch:\n    inputs:\n      version:\n        description: Version\n        required: true\n      # comment\n      dry_run:\n        type: boolean\njobs:\n  build:\n    runs-on: ubuntu-latest\n";
+        assert_eq!(
+            dispatch_inputs(yaml),
+            Some(vec!["version".to_string(), "dry_run".to_string()])
+        );
+        assert_eq!(
+            dispatch_inputs("on: [push, workflow_dispatch]\n"),
+            Some(Vec::new())
+        );
+        assert_eq!(dispatch_inputs("on: workflow_dispatch\n"), Some(Vec::new()));
+        assert_eq!(
+            dispatch_inputs("on:\n  workflow_dispatch:\n  push:\n"),
+            Some(Vec::new())
+        );
+        // Only the trigger itself counts, not the word elsewhere.
+        assert_eq!(
+            dispatch_inputs("name: workflow_dispatch helper\non: push\n"),
+            None
+        );
+        assert_eq!(dispatch_inputs("on: push\n"), None);
     }
 
     #[test]
@@ -373,6 +1907,35 @@ mod tests {
         assert!(parse_github_remote("https://gitlab.com/user/project").is_none());
     }
 
+    #[test]
+    fn parse_enterprise_url_once_registered() {
+        let url = "ssh://git@ghe.corp.example/team/service.git";
+        assert!(parse_github_remote_with(url, &[]).is_none());
+        let remote = parse_github_remote_with(url, &["ghe.corp.example".to_string()]).unwrap();
+        assert_eq!(remote.host, "ghe.corp.example");
+        assert_eq!(remote.api_base, "https://ghe.corp.example/api/v3");
+        assert_eq!(
+            (remote.owner.as_str(), remote.repo.as_str()),
+            ("team", "service")
+        );
+        assert_eq!(
+            parse_github_remote("git@github.com:user/project.git").map(|r| r.api_base),
+            Some(API_BASE.to_string())
+        );
+
+        let client = GitHubClient::new(String::new(), remote.api_base);
+        assert_eq!(
+            client.url("/repos/team/service"),
+            "https://ghe.corp.example/api/v3/repos/team/service"
+        );
+        assert_eq!(
+            client.url("/graphql"),
+            "https://ghe.corp.example/api/graphql"
+        );
+        let client = GitHubClient::new(String::new(), API_BASE.to_string());
+        assert_eq!(client.url("/graphql"), "https://api.github.com/graphql");
+    }
+
     fn make_run(id: u64, name: &str, status: &str, conclusion: Option<&str>) -> WorkflowRun {
         WorkflowRun {
             id,
@@ -382,9 +1945,105 @@ mod tests {
             status: status.to_string(),
             conclusion: conclusion.map(|s| s.to_string()),
             html_url: format!("https://github.com/test/repo/actions/runs/{id}"),
+            check_suite_id: None,
         }
     }
 
+    #[test]
+    fn merge_commit_checks_dedupes_and_marks_required() {
+        let mut ci = make_run(1, "CI", "completed", Some("success"));
+        ci.check_suite_id = Some(100);
+        let runs = vec![ci];
+        let mut rollup = per_commit_rollups(&runs).remove("abc123").unwrap();
+        let check_runs: Vec<CheckRun> = serde_json::from_str(
+            r#"[
+                {"id": 1, "name": "test", "status": "completed", "conclusion": "success",
+                 "html_url": "https://github.com/o/r/runs/1", "details_url": null,
+                 "app": {"slug": "github-actions"}, "check_suite": {"id": 100}},
+                {"id": 2, "name": "old", "status": "completed", "conclusion": "failure",
+                 "html_url": null, "details_url": null,
+                 "app": {"slug": "github-actions"}, "check_suite": {"id": 99}},
+                {"id": 3, "name": "buildkite/app", "status": "completed", "conclusion": "failure",
+                 "html_url": null, "details_url": "https://buildkite.com/o/app/builds/1",
+                 "app": {"slug": "buildkite"}, "check_suite": {"id": 200}},
+                {"id": 4, "name": "buildkite/app", "status": "in_progress", "conclusion": null,
+                 "html_url": null, "details_url": null,
+                 "app": {"slug": "buildkite"}, "check_suite": {"id": 201}}
+            ]"#,
+        )
+        .unwrap();
+        let statuses: Vec<CommitStatus> = serde_json::from_str(
+            r#"[
+                {"context": "ci/circleci", "state": "error", "target_url": ""},
+                {"context": "buildkite/app", "state": "success", "target_url": null}
+            ]"#,
+        )
+        .unwrap();
+        let required = vec!["test".to_string(), "ci/circleci".to_string()];
+        merge_commit_checks(&mut rollup, &runs, &check_runs, &statuses, &required);
+
+        let labels: Vec<(&str, CiState, bool)> = rollup
+            .checks
+            .iter()
+            .map(|c| (c.label.as_str(), c.state, c.required))
+            .collect();
+        assert_eq!(labels.len(), 3);
+        assert!(labels.contains(&("CI", CiState::Success, true)));
+        assert!(labels.contains(&("buildkite/app", CiState::Pending, false)));
+        assert!(labels.contains(&("ci/circleci", CiState::Failure, true)));
+        assert_eq!(rollup.checks[0].label, "ci/circleci");
+        assert_eq!(rollup.checks[0].url, None);
+        assert_eq!(rollup.counts.total(), 3);
+    }
+
+    #[test]
+    fn required_checks_bind_pull_request_heads_into_the_default_branch() {
+        let branch = DefaultBranch {
+            name: "main".to_string(),
+            tip: "tip".to_string(),
+            required: vec!["build".to_string(), "lint".to_string()],
+        };
+        let pull = |number, target: &str, head: &str| PullRequest {
+            provider: CiProvider::GitHub,
+            number,
+            title: String::new(),
+            author: String::new(),
+            draft: false,
+            review: ReviewState::None,
+            source_branch: format!("feature-{number}"),
+            target_branch: target.to_string(),
+            head_sha: head.to_string(),
+            url: String::new(),
+            ci: None,
+        };
+        let pulls = vec![pull(1, "main", "pr1"), pull(2, "release", "pr2")];
+        assert_eq!(protected_heads(&branch, &pulls), vec!["tip", "pr1"]);
+
+        // The PR head reported `build` but never `lint`.
+        let mut rollup = CiCommitRollup::default();
+        let statuses: Vec<CommitStatus> = serde_json::from_str(
+            r#"[{"context": "build", "state": "success", "target_url": null}]"#,
+        )
+        .unwrap();
+        merge_commit_checks(&mut rollup, &[], &[], &statuses, &branch.required);
+        let labels: Vec<(&str, CiState, bool)> = rollup
+            .checks
+            .iter()
+            .map(|c| (c.label.as_str(), c.state, c.required))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                ("lint", CiState::Pending, true),
+                ("build", CiState::Success, true)
+            ]
+        );
+        assert_eq!(
+            crate::ci::status_for_head(Some(&rollup)).state,
+            CiState::Pending
+        );
+    }
+
     #[test]
     fn ci_status_all_passed() {
         let runs = vec![
@@ -458,4 +2117,81 @@ mod tests {
         let msg = GitHubClient::classify_http_error(403, body);
         assert!(msg.contains("organization SSO"));
     }
+
+    #[test]
+    fn pulls_from_graphql_maps_review_and_ci() {
+        let body = r#"{"data":{"repository":{"pullRequests":{"nodes":[
+            {"number":12,"title":"Add widgets","url":"https://github.com/o/r/pull/12",
+             "isDraft":true,"reviewDecision":"CHANGES_REQUESTED",
+             "headRefName":"widgets","baseRefName":"main","headRefOid":"abc123",
+             "author":{"login":"alice"},
+             "commits":{"nodes":[{"commit":{"statusCheckRollup":{"state":"FAILURE"}}}]}},
+            {"number":13,"title":"Fix docs","url":"https://github.com/o/r/pull/13",
+             "isDraft":false,"reviewDecision":null,
+             "headRefName":"docs","baseRefName":"main","headRefOid":"def456",
+             "author":null,
+             "commits":{"nodes":[{"commit":{"statusCheckRollup":null}}]}}
+        ]}}}}"#;
+        let parsed: GraphQlResponse<PullRequestsData> = serde_json::from_str(body).unwrap();
+        let pulls = pulls_from_graphql(graphql_data(parsed).unwrap()).unwrap();
+        assert_eq!(pulls.len(), 2);
+        assert!(pulls[0].draft);
+        assert_eq!(pulls[0].review, ReviewState::ChangesRequested);
+        assert_eq!(pulls[0].ci, Some(CiState::Failure));
+        assert_eq!(pulls[0].head_ref(), "refs/pull/12/head");
+        assert_eq!(pulls[0].local_branch(), "pr/12");
+        assert_eq!(pulls[1].author, "ghost");
+        assert_eq!(pulls[1].review, ReviewState::None);
+        assert_eq!(pulls[1].ci, None);
+    }
+
+    #[test]
+    fn pulls_from_graphql_surfaces_errors() {
+        let body = r#"{"data":{"repository":null},"errors":[{"message":"Could not resolve to a Repository"}]}"#;
+        let parsed: GraphQlResponse<PullRequestsData> = serde_json::from_str(body).unwrap();
+        let err = graphql_data(parsed).unwrap_err();
+        assert!(err.to_string().contains("Could not resolve"));
+    }
+
+    #[test]
+    fn classify_422_includes_validation_details() {
+        let body = r#"{"message":"Validation Failed","errors":[{"resource":"PullRequest","code":"custom","message":"A pull request already exists for o:feature."}]}"#;
+        let msg = GitHubClient::classify_http_error(422, body);
+        assert!(msg.contains("Validation Failed"));
+        assert!(msg.contains("A pull request already exists"));
+    }
+
+    #[test]
+    fn review_threads_map_side_and_reply_target() {
+        let body = r#"{"data":{"repository":{"pullRequest":{"reviewThreads":{"nodes":[
+            {"id":"PRRT_1","isResolved":false,"isOutdated":true,"path":"src/lib.rs",
+             "line":null,"diffSide":"LEFT","comments":{"nodes":[
+                {"databaseId":99,"body":"Why?","createdAt":"2024-05-01T10:00:00Z","author":{"login":"amy"}},
+                {"databaseId":100,"body":"Because.","createdAt":"2024-05-01T11:00:00Z","author":null}]}}]}}}}}"#;
+        let parsed: GraphQlResponse<ReviewThreadsData> = serde_json::from_str(body).unwrap();
+        let threads = threads_from_graphql(parsed.data.unwrap()).unwrap();
+        assert_eq!(threads.len(), 1);
+        let thread = &threads[0];
+        assert_eq!(thread.side, ReviewSide::Old);
+        assert_eq!(thread.line, None);
+        assert!(thread.outdated);
+        assert_eq!(thread.reply_to, "99");
+        assert_eq!(thread.comments[1].author, "ghost");
+    }
+
+    #[test]
+    fn releases_map_null_names_and_assets() {
+        let body = r#"[{"tag_name":"v1.0.0","name":null,"body":null,"draft":false,
+            "prerelease":true,"html_url":"https://github.com/o/r/releases/tag/v1.0.0",
+            "published_at":"2026-01-31T09:30:00Z",
+            "assets":[{"name":"app.tar.gz","size":2048,
+                       "browser_download_url":"https://github.com/o/r/releases/download/v1.0.0/app.tar.gz"}]}]"#;
+        let parsed: Vec<ReleaseResponse> = serde_json::from_str(body).unwrap();
+        let release = Release::from(parsed.into_iter().next().unwrap());
+        assert_eq!(release.title(), "v1.0.0");
+        assert_eq!(release.notes, "");
+        assert!(release.prerelease);
+        assert_eq!(release.assets[0].size, Some(2048));
+        assert!(release.assets[0].url.ends_with("/app.tar.gz"));
+    }
 }
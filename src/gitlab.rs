@@ -1,17 +1,33 @@
-//! GitLab pipelines REST client.
+//! GitLab pipelines, job traces, merge requests and releases REST
+//! client, plus pipeline retries, cancels and new pipelines for the CI
+//! actions menu.
 //!
 //! Supports both gitlab.com and self-hosted instances; the API base is
 //! derived from the remote URL so any hostname containing "gitlab" works.
 
 use crate::ci::{
-    CiCheckStatus, CiCommitRollup, CiCounts, CiProvider, CiState, CiStatus, ProviderCiResult,
+    CiAction, CiCheckStatus, CiCommitRollup, CiCounts, CiJob, CiProvider, CiState, CiStatus,
+    MAX_LOG_BYTES, ProviderCiResult,
 };
-use anyhow::{Context, Result};
+use crate::ci_log::{ParsedLog, parse_log};
+use crate::http_cache;
+use crate::issues::{Issue, IssueState};
+use crate::pulls::{
+    CreatedPullRequest, NewPullRequest, ProviderPullResult, PullRequest, ReviewAction,
+    ReviewComment, ReviewSide, ReviewState, ReviewThread,
+};
+use crate::releases::{NewRelease, Release, ReleaseAsset};
+use anyhow::{Context, Result, bail};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::mpsc::{self, Receiver};
 use winit::event_loop::EventLoopProxy;
 
+/// Page sizes for release and merge request diff listings, the most
+/// GitLab serves at once.
+const RELEASES_PER_PAGE: usize = 100;
+const DIFFS_PER_PAGE: usize = 100;
+
 /// Parsed GitLab remote: base URL and project path.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GitLabRemote {
@@ -72,6 +88,161 @@ pub struct Pipeline {
     pub web_url: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct CommitId {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PipelineJob {
+    id: u64,
+    name: String,
+    stage: String,
+    status: String,
+    web_url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeRequest {
+    pub iid: u64,
+    pub title: String,
+    pub author: MergeRequestAuthor,
+    #[serde(default)]
+    pub draft: bool,
+    /// Pre-14.0 spelling of `draft`; newer instances send both.
+    #[serde(default)]
+    pub work_in_progress: bool,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub sha: String,
+    pub web_url: String,
+    pub detailed_merge_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergeRequestAuthor {
+    pub username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabUser {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct Discussion {
+    id: String,
+    notes: Vec<DiscussionNote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscussionNote {
+    #[serde(rename = "type")]
+    note_type: Option<String>,
+    body: String,
+    author: MergeRequestAuthor,
+    created_at: String,
+    #[serde(default)]
+    resolved: bool,
+    position: Option<NotePosition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotePosition {
+    head_sha: String,
+    new_path: Option<String>,
+    old_path: Option<String>,
+    new_line: Option<u32>,
+    old_line: Option<u32>,
+}
+
+/// One entry of `merge_requests/:iid/versions`, newest first.
+#[derive(Debug, Deserialize)]
+struct MergeRequestVersion {
+    base_commit_sha: String,
+    head_commit_sha: String,
+    start_commit_sha: String,
+}
+
+/// One file of `merge_requests/:iid/diffs`.
+#[derive(Debug, Deserialize)]
+struct MergeRequestDiff {
+    old_path: String,
+    new_path: String,
+    diff: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseResponse {
+    tag_name: String,
+    name: Option<String>,
+    description: Option<String>,
+    released_at: Option<String>,
+    /// Dated in the future; shown as a pre-release.
+    #[serde(default)]
+    upcoming_release: bool,
+    #[serde(rename = "_links")]
+    links: Option<ReleaseLinks>,
+    #[serde(default)]
+    assets: ReleaseAssets,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseLinks {
+    #[serde(rename = "self")]
+    web_url: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ReleaseAssets {
+    #[serde(default)]
+    links: Vec<ReleaseAssetLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseAssetLink {
+    name: String,
+    url: String,
+    /// Permalink that survives the target URL changing.
+    direct_asset_url: Option<String>,
+}
+
+fn release_from(r: ReleaseResponse, api_base: &str, project_path: &str) -> Release {
+    let url = r
+        .links
+        .and_then(|l| l.web_url)
+        .unwrap_or_else(|| format!("{api_base}/{project_path}/-/releases/{}", r.tag_name));
+    Release {
+        provider: CiProvider::GitLab,
+        tag: r.tag_name,
+        name: r.name.unwrap_or_default(),
+        notes: r.description.unwrap_or_default(),
+        draft: false,
+        prerelease: r.upcoming_release,
+        published_at: r.released_at,
+        url,
+        assets: r
+            .assets
+            .links
+            .into_iter()
+            .map(|l| ReleaseAsset {
+                name: l.name,
+                size: None,
+                url: l.direct_asset_url.unwrap_or(l.url),
+            })
+            .collect(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueResponse {
+    iid: u64,
+    title: String,
+    /// "opened" or "closed".
+    state: String,
+    web_url: String,
+}
+
 pub struct GitLabClient {
     token: String,
     api_base: String,
@@ -82,15 +253,51 @@ impl GitLabClient {
         Self { token, api_base }
     }
 
+    /// Conditional through [`http_cache::get`], like GitHub's.
     fn get(&self, path: &str) -> Result<ureq::http::Response<ureq::Body>> {
-        let mut resp = ureq::get(&format!("{}{path}", self.api_base))
+        let url = format!("{}{path}", self.api_base);
+        let request = ureq::get(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .header("User-Agent", "whisper-git")
+            .config()
+            .http_status_as_error(false)
+            .build();
+        let mut resp = http_cache::get(&url, request).context("GitLab API request failed")?;
+        Self::ensure_success(&mut resp)?;
+        Ok(resp)
+    }
+
+    fn post(
+        &self,
+        path: &str,
+        body: serde_json::Value,
+    ) -> Result<ureq::http::Response<ureq::Body>> {
+        let mut resp = ureq::post(&format!("{}{path}", self.api_base))
             .header("PRIVATE-TOKEN", &self.token)
             .header("User-Agent", "whisper-git")
             .config()
             .http_status_as_error(false)
             .build()
-            .call()
+            .send_json(body)
             .context("GitLab API request failed")?;
+        Self::ensure_success(&mut resp)?;
+        Ok(resp)
+    }
+
+    fn put(&self, path: &str) -> Result<ureq::http::Response<ureq::Body>> {
+        let mut resp = ureq::put(&format!("{}{path}", self.api_base))
+            .header("PRIVATE-TOKEN", &self.token)
+            .header("User-Agent", "whisper-git")
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .send_empty()
+            .context("GitLab API request failed")?;
+        Self::ensure_success(&mut resp)?;
+        Ok(resp)
+    }
+
+    fn ensure_success(resp: &mut ureq::http::Response<ureq::Body>) -> Result<()> {
         let status = resp.status().as_u16();
         if !(200..300).contains(&status) {
             let body = resp.body_mut().read_to_string().unwrap_or_default();
@@ -101,7 +308,7 @@ impl GitLabClient {
             };
             return Err(anyhow::anyhow!(message));
         }
-        Ok(resp)
+        Ok(())
     }
 
     pub fn pipelines(
@@ -124,6 +331,437 @@ impl GitLabClient {
             .context("Failed to parse pipelines")?;
         Ok(pipelines)
     }
+
+    /// Issue `iid` of the project.
+    pub fn issue(&self, project_path: &str, iid: u64) -> Result<Issue> {
+        let encoded = url_encode_path(project_path);
+        let mut resp = self
+            .get(&format!("/api/v4/projects/{encoded}/issues/{iid}"))
+            .with_context(|| format!("Failed to fetch issue #{iid}"))?;
+        let issue: IssueResponse = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse issue")?;
+        Ok(Issue {
+            number: issue.iid,
+            title: issue.title,
+            state: if issue.state == "opened" {
+                IssueState::Open
+            } else {
+                IssueState::Closed
+            },
+            pull_request: false,
+            url: issue.web_url,
+        })
+    }
+
+    /// Every release, newest first, read page by page until a short
+    /// page.
+    pub fn releases(&self, project_path: &str) -> Result<Vec<Release>> {
+        let encoded = url_encode_path(project_path);
+        let mut releases = Vec::new();
+        for page in 1u32.. {
+            let batch: Vec<ReleaseResponse> = self
+                .get(&format!(
+                    "/api/v4/projects/{encoded}/releases?per_page={RELEASES_PER_PAGE}&page={page}"
+                ))
+                .with_context(|| format!("Failed to fetch releases for {project_path}"))?
+                .body_mut()
+                .read_json()
+                .context("Failed to parse releases")?;
+            let last = batch.len() < RELEASES_PER_PAGE;
+            releases.extend(
+                batch
+                    .into_iter()
+                    .map(|r| release_from(r, &self.api_base, project_path)),
+            );
+            if last {
+                break;
+            }
+        }
+        Ok(releases)
+    }
+
+    /// Create a release for a tag. GitLab has no drafts, so
+    /// `request.draft` is ignored.
+    pub fn create_release(&self, project_path: &str, request: &NewRelease) -> Result<Release> {
+        let encoded = url_encode_path(project_path);
+        let mut resp = self
+            .post(
+                &format!("/api/v4/projects/{encoded}/releases"),
+                serde_json::json!({
+                    "tag_name": request.tag,
+                    "ref": request.target_sha,
+                    "name": request.name,
+                    "description": request.notes,
+                }),
+            )
+            .with_context(|| format!("Failed to create release {}", request.tag))?;
+        let created: ReleaseResponse = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse created release")?;
+        Ok(release_from(created, &self.api_base, project_path))
+    }
+
+    /// Jobs of pipeline `pipeline_id` in creation order, which follows
+    /// the stages.
+    pub fn pipeline_jobs(&self, project_path: &str, pipeline_id: u64) -> Result<Vec<CiJob>> {
+        let encoded = url_encode_path(project_path);
+        let mut resp = self
+            .get(&format!(
+                "/api/v4/projects/{encoded}/pipelines/{pipeline_id}/jobs?per_page=100"
+            ))
+            .with_context(|| format!("Failed to fetch jobs for pipeline {pipeline_id}"))?;
+        let mut jobs: Vec<PipelineJob> = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse jobs")?;
+        jobs.sort_by_key(|j| j.id);
+        Ok(jobs
+            .into_iter()
+            .map(|job| CiJob {
+                state: pipeline_state(&job.status),
+                id: job.id,
+                name: job.name,
+                stage: Some(job.stage),
+                url: Some(job.web_url),
+            })
+            .collect())
+    }
+
+    /// Raw trace (console log) of job `job_id`.
+    pub fn job_trace(&self, project_path: &str, job_id: u64) -> Result<String> {
+        let encoded = url_encode_path(project_path);
+        let mut resp = self
+            .get(&format!("/api/v4/projects/{encoded}/jobs/{job_id}/trace"))
+            .with_context(|| format!("Failed to fetch the trace for job {job_id}"))?;
+        resp.body_mut()
+            .with_config()
+            .limit(MAX_LOG_BYTES)
+            .read_to_string()
+            .context("Failed to read job trace")
+    }
+
+    /// Apply a [`CiAction`]. GitLab has no "re-run everything" call, so
+    /// [`CiAction::RerunAll`] starts a fresh pipeline on each pipeline's
+    /// ref; retry only reaches failed and canceled jobs.
+    pub fn ci_action(&self, project_path: &str, action: &CiAction) -> Result<()> {
+        let encoded = url_encode_path(project_path);
+        let pipelines = format!("/api/v4/projects/{encoded}/pipelines");
+        match action {
+            CiAction::RerunFailed(ids) => {
+                for id in ids {
+                    self.post(&format!("{pipelines}/{id}/retry"), serde_json::json!({}))
+                        .with_context(|| format!("Failed to retry pipeline {id}"))?;
+                }
+            }
+            CiAction::Cancel(ids) => {
+                for id in ids {
+                    self.post(&format!("{pipelines}/{id}/cancel"), serde_json::json!({}))
+                        .with_context(|| format!("Failed to cancel pipeline {id}"))?;
+                }
+            }
+            CiAction::RerunAll(ids) => {
+                // GitLab's pipeline retry only reruns failed jobs, so a
+                // full rerun is a new pipeline on the ref. That only
+                // reruns the same commit while the ref hasn't moved.
+                for id in ids {
+                    let pipeline: Pipeline = self
+                        .get(&format!("{pipelines}/{id}"))
+                        .with_context(|| format!("Failed to look up pipeline {id}"))?
+                        .body_mut()
+                        .read_json()
+                        .context("Failed to parse pipeline")?;
+                    let tip = self.ref_tip(project_path, &pipeline.ref_name)?;
+                    if tip != pipeline.sha {
+                        bail!(
+                            "{} has moved on since pipeline {id} ran; \
+                             re-run failed jobs or start a new pipeline instead",
+                            pipeline.ref_name
+                        );
+                    }
+                    self.create_pipeline(project_path, &pipeline.ref_name, &[])?;
+                }
+            }
+            CiAction::Trigger {
+                git_ref, inputs, ..
+            } => self.create_pipeline(project_path, git_ref, inputs)?,
+        }
+        Ok(())
+    }
+
+    /// Commit `git_ref` (a branch or tag name) points at.
+    fn ref_tip(&self, project_path: &str, git_ref: &str) -> Result<String> {
+        let encoded = url_encode_path(project_path);
+        let commit: CommitId = self
+            .get(&format!(
+                "/api/v4/projects/{encoded}/repository/commits/{}",
+                url_encode_path(git_ref)
+            ))
+            .with_context(|| format!("Failed to look up {git_ref}"))?
+            .body_mut()
+            .read_json()
+            .context("Failed to parse commit")?;
+        Ok(commit.id)
+    }
+
+    /// Run a new pipeline on `git_ref` with `variables` set.
+    fn create_pipeline(
+        &self,
+        project_path: &str,
+        git_ref: &str,
+        variables: &[(String, String)],
+    ) -> Result<()> {
+        let encoded = url_encode_path(project_path);
+        let variables: Vec<serde_json::Value> = variables
+            .iter()
+            .map(|(key, value)| serde_json::json!({ "key": key, "value": value }))
+            .collect();
+        self.post(
+            &format!("/api/v4/projects/{encoded}/pipeline"),
+            serde_json::json!({ "ref": git_ref, "variables": variables }),
+        )
+        .with_context(|| format!("Failed to run a pipeline on {git_ref}"))?;
+        Ok(())
+    }
+
+    /// Open merge requests, most recently updated first.
+    pub fn merge_requests(&self, project_path: &str, per_page: u32) -> Result<Vec<MergeRequest>> {
+        let encoded = url_encode_path(project_path);
+        let path = format!(
+            "/api/v4/projects/{encoded}/merge_requests?state=opened&per_page={per_page}&order_by=updated_at&sort=desc"
+        );
+        let mut resp = self.get(&path)?;
+        let merge_requests: Vec<MergeRequest> = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse merge requests")?;
+        Ok(merge_requests)
+    }
+
+    /// Diff threads on merge request `iid`. Threads left on an older
+    /// head than `head_sha` are outdated.
+    pub fn review_threads(
+        &self,
+        project_path: &str,
+        iid: u64,
+        head_sha: &str,
+    ) -> Result<Vec<ReviewThread>> {
+        let encoded = url_encode_path(project_path);
+        let mut resp = self.get(&format!(
+            "/api/v4/projects/{encoded}/merge_requests/{iid}/discussions?per_page=100"
+        ))?;
+        let discussions: Vec<Discussion> = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse merge request discussions")?;
+        Ok(threads_from_discussions(discussions, head_sha))
+    }
+
+    /// Reply to, resolve or start a discussion on merge request `iid`.
+    pub fn review_action(&self, project_path: &str, iid: u64, action: &ReviewAction) -> Result<()> {
+        let encoded = url_encode_path(project_path);
+        let base = format!("/api/v4/projects/{encoded}/merge_requests/{iid}");
+        match action {
+            ReviewAction::Reply {
+                thread_id, body, ..
+            } => {
+                self.post(
+                    &format!("{base}/discussions/{thread_id}/notes"),
+                    serde_json::json!({ "body": body }),
+                )
+                .context("Failed to post reply")?;
+            }
+            ReviewAction::SetResolved {
+                thread_id,
+                resolved,
+            } => {
+                self.put(&format!(
+                    "{base}/discussions/{thread_id}?resolved={resolved}"
+                ))
+                .context("Failed to update discussion")?;
+            }
+            ReviewAction::Comment {
+                path,
+                old_line,
+                new_line,
+                body,
+                ..
+            } => {
+                // Positions are relative to the MR's own base..head
+                // diff, not the commit the line was picked in, so the
+                // old-side line is looked up there.
+                if new_line.is_none() && old_line.is_none() {
+                    bail!("Review comment has no line");
+                }
+                let refs = self.latest_diff_refs(&base)?;
+                let file = self
+                    .merge_request_diff(&base, path)?
+                    .with_context(|| format!("{path} isn't part of the merge request's diff"))?;
+                let old_line = match new_line {
+                    Some(line) => old_line_in_diff(&file.diff, *line),
+                    None => *old_line,
+                };
+                self.post(
+                    &format!("{base}/discussions"),
+                    serde_json::json!({
+                        "body": body,
+                        "position": {
+                            "position_type": "text",
+                            "base_sha": refs.base_commit_sha,
+                            "start_sha": refs.start_commit_sha,
+                            "head_sha": refs.head_commit_sha,
+                            "old_path": file.old_path,
+                            "new_path": file.new_path,
+                            "old_line": old_line,
+                            "new_line": new_line,
+                        },
+                    }),
+                )
+                .context("Failed to post review comment")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Diff refs of the newest version of the merge request at `base`.
+    fn latest_diff_refs(&self, base: &str) -> Result<MergeRequestVersion> {
+        let versions: Vec<MergeRequestVersion> = self
+            .get(&format!("{base}/versions"))?
+            .body_mut()
+            .read_json()
+            .context("Failed to parse merge request versions")?;
+        versions
+            .into_iter()
+            .next()
+            .context("Merge request has no diff versions")
+    }
+
+    /// The diff of `path` in the merge request at `base`, read page by
+    /// page until found or a short page.
+    fn merge_request_diff(&self, base: &str, path: &str) -> Result<Option<MergeRequestDiff>> {
+        for page in 1u32.. {
+            let batch: Vec<MergeRequestDiff> = self
+                .get(&format!(
+                    "{base}/diffs?per_page={DIFFS_PER_PAGE}&page={page}"
+                ))
+                .context("Failed to fetch merge request diffs")?
+                .body_mut()
+                .read_json()
+                .context("Failed to parse merge request diffs")?;
+            let last = batch.len() < DIFFS_PER_PAGE;
+            if let Some(file) = batch
+                .into_iter()
+                .find(|d| d.new_path == path || d.old_path == path)
+            {
+                return Ok(Some(file));
+            }
+            if last {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    /// Numeric user ID for a username; reviewers are assigned by ID.
+    fn user_id(&self, username: &str) -> Result<Option<u64>> {
+        let mut resp = self.get(&format!("/api/v4/users?username={username}"))?;
+        let users: Vec<GitLabUser> = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse users")?;
+        Ok(users.first().map(|u| u.id))
+    }
+
+    /// Open a merge request. Reviewers GitLab doesn't know are skipped
+    /// and reported as warnings.
+    pub fn create_merge_request(
+        &self,
+        project_path: &str,
+        request: &NewPullRequest,
+    ) -> Result<CreatedPullRequest> {
+        let mut warnings = Vec::new();
+        let mut reviewer_ids = Vec::new();
+        for name in &request.reviewers {
+            match self.user_id(name) {
+                Ok(Some(id)) => reviewer_ids.push(id),
+                Ok(None) => warnings.push(format!("Unknown reviewer {name}")),
+                Err(e) => warnings.push(format!("Couldn't look up reviewer {name}: {e:#}")),
+            }
+        }
+
+        let title = if request.draft {
+            format!("Draft: {}", request.title)
+        } else {
+            request.title.clone()
+        };
+        let encoded = url_encode_path(project_path);
+        let mut resp = self
+            .post(
+                &format!("/api/v4/projects/{encoded}/merge_requests"),
+                serde_json::json!({
+                    "source_branch": request.source_branch,
+                    "target_branch": request.target_branch,
+                    "title": title,
+                    "description": request.body,
+                    "labels": request.labels.join(","),
+                    "reviewer_ids": reviewer_ids,
+                }),
+            )
+            .with_context(|| format!("Failed to create merge request for {project_path}"))?;
+        let mr: MergeRequest = resp
+            .body_mut()
+            .read_json()
+            .context("Failed to parse created merge request")?;
+        Ok(CreatedPullRequest {
+            pull: pull_request_from_mr(mr),
+            warnings,
+        })
+    }
+}
+
+/// Keep only discussions on diff lines. The thread's anchor and
+/// resolved state come from its first note.
+fn threads_from_discussions(discussions: Vec<Discussion>, head_sha: &str) -> Vec<ReviewThread> {
+    discussions
+        .into_iter()
+        .filter_map(|d| {
+            let first = d.notes.first()?;
+            if first.note_type.as_deref() != Some("DiffNote") {
+                return None;
+            }
+            let position = first.position.as_ref()?;
+            let outdated = position.head_sha != head_sha;
+            let (side, line) = match (position.new_line, position.old_line) {
+                (Some(line), _) => (ReviewSide::New, line),
+                (None, Some(line)) => (ReviewSide::Old, line),
+                (None, None) => return None,
+            };
+            let path = position
+                .new_path
+                .clone()
+                .or_else(|| position.old_path.clone())?;
+            Some(ReviewThread {
+                reply_to: d.id.clone(),
+                id: d.id,
+                path,
+                side,
+                line: (!outdated).then_some(line),
+                outdated,
+                resolved: first.resolved,
+                comments: d
+                    .notes
+                    .into_iter()
+                    .map(|n| ReviewComment {
+                        author: n.author.username,
+                        body: n.body,
+                        created_at: n.created_at,
+                    })
+                    .collect(),
+            })
+        })
+        .collect()
 }
 
 fn url_encode_path(path: &str) -> String {
@@ -146,6 +784,30 @@ fn pipeline_state(status: &str) -> CiState {
     }
 }
 
+/// The list endpoint carries no pipeline state, so `ci` is left for the
+/// caller to fill from the per-commit pipeline results.
+fn pull_request_from_mr(mr: MergeRequest) -> PullRequest {
+    let review = match mr.detailed_merge_status.as_deref() {
+        Some("not_approved") => ReviewState::ReviewRequired,
+        Some("requested_changes") => ReviewState::ChangesRequested,
+        Some("mergeable") => ReviewState::Approved,
+        _ => ReviewState::None,
+    };
+    PullRequest {
+        provider: CiProvider::GitLab,
+        number: mr.iid,
+        title: mr.title,
+        author: mr.author.username,
+        draft: mr.draft || mr.work_in_progress,
+        review,
+        source_branch: mr.source_branch,
+        target_branch: mr.target_branch,
+        head_sha: mr.sha,
+        url: mr.web_url,
+        ci: None,
+    }
+}
+
 /// Summarize pipelines into a CiStatus (branch-level summary).
 ///
 /// Pipelines arrive sorted by ID descending (most recent first). We only
@@ -229,6 +891,8 @@ fn per_commit_rollups(pipelines: &[Pipeline]) -> HashMap<String, CiCommitRollup>
                 label: format!("Pipeline #{}", latest.id),
                 state: check_state,
                 url: Some(latest.web_url.clone()),
+                run_id: Some(latest.id),
+                required: false,
             }];
             let counts = CiCounts::from_states(checks.iter().map(|c| c.state));
             result.insert(sha.to_string(), CiCommitRollup { counts, checks });
@@ -244,12 +908,15 @@ fn fetch_ci_result(token: &str, remote: &GitLabRemote) -> ProviderCiResult {
             let per_commit_rollups = per_commit_rollups(&pipelines);
             ProviderCiResult {
                 provider: CiProvider::GitLab,
+                host: String::new(),
                 status: ci_status_from_pipelines(&pipelines),
                 per_commit_rollups,
+                stale: false,
             }
         }
         Err(e) => ProviderCiResult {
             provider: CiProvider::GitLab,
+            host: String::new(),
             status: CiStatus {
                 state: CiState::None,
                 summary: format!("GitLab CI fetch failed: {e}"),
@@ -257,6 +924,7 @@ fn fetch_ci_result(token: &str, remote: &GitLabRemote) -> ProviderCiResult {
                 counts: None,
             },
             per_commit_rollups: HashMap::new(),
+            stale: false,
         },
     }
 }
@@ -281,10 +949,325 @@ pub fn fetch_ci_status_async(
     Some(rx)
 }
 
+/// Fetch open merge requests for a GitLab project asynchronously.
+/// Returns None if the URL isn't a GitLab URL.
+pub fn fetch_pull_requests_async(
+    token: &str,
+    origin_url: &str,
+    remote: String,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<ProviderPullResult>> {
+    let gitlab = parse_gitlab_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitLabClient::new(token, gitlab.api_base.clone());
+        let pulls = client
+            .merge_requests(&gitlab.project_path, 50)
+            .map(|mrs| mrs.into_iter().map(pull_request_from_mr).collect())
+            .map_err(|e| format!("GitLab merge request fetch failed: {e:#}"));
+        let _ = tx.send(ProviderPullResult {
+            provider: CiProvider::GitLab,
+            host: String::new(),
+            remote,
+            pulls,
+        });
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Open a merge request on a GitLab project asynchronously.
+/// Returns None if the URL isn't a GitLab URL.
+pub fn create_pull_request_async(
+    token: &str,
+    origin_url: &str,
+    request: NewPullRequest,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<CreatedPullRequest, String>>> {
+    let gitlab = parse_gitlab_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitLabClient::new(token, gitlab.api_base.clone());
+        let result = client
+            .create_merge_request(&gitlab.project_path, &request)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Fetch the jobs of pipeline `pipeline_id` asynchronously.
+/// Returns None if the URL isn't a GitLab URL.
+pub fn fetch_ci_jobs_async(
+    token: &str,
+    origin_url: &str,
+    pipeline_id: u64,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<Vec<CiJob>, String>>> {
+    let gitlab = parse_gitlab_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitLabClient::new(token, gitlab.api_base.clone());
+        let result = client
+            .pipeline_jobs(&gitlab.project_path, pipeline_id)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Download and parse the trace of job `job_id` asynchronously.
+/// Returns None if the URL isn't a GitLab URL.
+pub fn fetch_job_log_async(
+    token: &str,
+    origin_url: &str,
+    job_id: u64,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<ParsedLog, String>>> {
+    let gitlab = parse_gitlab_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitLabClient::new(token, gitlab.api_base.clone());
+        let result = client
+            .job_trace(&gitlab.project_path, job_id)
+            .map(|raw| parse_log(&raw))
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Apply a [`CiAction`] asynchronously.
+/// Returns None if the URL isn't a GitLab URL.
+pub fn ci_action_async(
+    token: &str,
+    origin_url: &str,
+    action: CiAction,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<(), String>>> {
+    let gitlab = parse_gitlab_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitLabClient::new(token, gitlab.api_base.clone());
+        let result = client
+            .ci_action(&gitlab.project_path, &action)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Fetch diff threads for merge request `iid` asynchronously.
+/// Returns None if the URL isn't a GitLab URL.
+pub fn fetch_review_threads_async(
+    token: &str,
+    origin_url: &str,
+    iid: u64,
+    head_sha: String,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<Vec<ReviewThread>, String>>> {
+    let gitlab = parse_gitlab_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitLabClient::new(token, gitlab.api_base.clone());
+        let result = client
+            .review_threads(&gitlab.project_path, iid, &head_sha)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Apply a [`ReviewAction`] to merge request `iid` asynchronously.
+/// Returns None if the URL isn't a GitLab URL.
+pub fn review_action_async(
+    token: &str,
+    origin_url: &str,
+    iid: u64,
+    action: ReviewAction,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<(), String>>> {
+    let gitlab = parse_gitlab_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitLabClient::new(token, gitlab.api_base.clone());
+        let result = client
+            .review_action(&gitlab.project_path, iid, &action)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Fetch issue `iid` asynchronously. Returns None if the URL isn't a
+/// GitLab URL.
+pub fn fetch_issue_async(
+    token: &str,
+    origin_url: &str,
+    iid: u64,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<Issue, String>>> {
+    let gitlab = parse_gitlab_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitLabClient::new(token, gitlab.api_base.clone());
+        let result = client
+            .issue(&gitlab.project_path, iid)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Fetch the releases of a GitLab project asynchronously.
+/// Returns None if the URL isn't a GitLab URL.
+pub fn fetch_releases_async(
+    token: &str,
+    origin_url: &str,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<Vec<Release>, String>>> {
+    let gitlab = parse_gitlab_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitLabClient::new(token, gitlab.api_base.clone());
+        let result = client
+            .releases(&gitlab.project_path)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Create a release on a GitLab project asynchronously.
+/// Returns None if the URL isn't a GitLab URL.
+pub fn create_release_async(
+    token: &str,
+    origin_url: &str,
+    request: NewRelease,
+    proxy: EventLoopProxy<()>,
+) -> Option<Receiver<Result<Release, String>>> {
+    let gitlab = parse_gitlab_remote(origin_url)?;
+    let token = token.to_string();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let client = GitLabClient::new(token, gitlab.api_base.clone());
+        let result = client
+            .create_release(&gitlab.project_path, &request)
+            .map_err(|e| format!("{e:#}"));
+        let _ = tx.send(result);
+        let _ = proxy.send_event(());
+    });
+
+    Some(rx)
+}
+
+/// Where new-side line `new_line` of unified `diff` sits on the old
+/// side: `None` for an added line, else the old line number. Lines
+/// outside every hunk are shifted by the hunks above them.
+fn old_line_in_diff(diff: &str, new_line: u32) -> Option<u32> {
+    let (mut old, mut new) = (1i64, 1i64);
+    let mut offset = 0i64;
+    let mut in_hunk = false;
+    let target = i64::from(new_line);
+    for line in diff.lines() {
+        if let Some(header) = line.strip_prefix("@@ ") {
+            let Some((old_start, new_start)) = hunk_starts(header) else {
+                continue;
+            };
+            if target < new_start {
+                break;
+            }
+            (old, new) = (old_start, new_start);
+            in_hunk = true;
+            continue;
+        }
+        if !in_hunk {
+            continue;
+        }
+        match line.as_bytes().first() {
+            Some(b'+') => {
+                if new == target {
+                    return None;
+                }
+                new += 1;
+            }
+            Some(b'-') => old += 1,
+            Some(b'\\') => {}
+            _ => {
+                if new == target {
+                    return u32::try_from(old).ok();
+                }
+                old += 1;
+                new += 1;
+            }
+        }
+        offset = old - new;
+    }
+    u32::try_from(target + offset).ok()
+}
+
+/// Old and new start lines of a `-a,b +c,d @@` hunk header.
+fn hunk_starts(header: &str) -> Option<(i64, i64)> {
+    let mut parts = header.split_whitespace();
+    let start = |range: &str| range.split(',').next()?.parse::<i64>().ok();
+    let old = start(parts.next()?.strip_prefix('-')?)?;
+    let new = start(parts.next()?.strip_prefix('+')?)?;
+    Some((old, new))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn old_lines_follow_the_merge_request_diff() {
+        // Old 1-3 kept, old 4 replaced by two lines, old 5.. shifted by one.
+        let diff = "@@ -2,4 +2,5 @@ fn main() {\n a\n b\n-c\n+C\n+D\n e\n";
+        assert_eq!(old_line_in_diff(diff, 1), Some(1));
+        assert_eq!(old_line_in_diff(diff, 3), Some(3));
+        assert_eq!(old_line_in_diff(diff, 4), None);
+        assert_eq!(old_line_in_diff(diff, 5), None);
+        assert_eq!(old_line_in_diff(diff, 6), Some(5));
+        assert_eq!(old_line_in_diff(diff, 20), Some(19));
+        assert_eq!(old_line_in_diff("@@ -0,0 +1,2 @@\n+x\n+y\n", 2), None);
+    }
+
     #[test]
     fn parse_ssh_gitlab_com() {
         let r = parse_gitlab_remote("git@gitlab.com:user/project.git").unwrap();
@@ -377,4 +1360,61 @@ mod tests {
         let rollups = per_commit_rollups(&pipelines);
         assert_eq!(rollups["abc"].counts.overall_state(), CiState::Success);
     }
+
+    #[test]
+    fn merge_request_maps_to_pull_request() {
+        let body = r#"[{"iid":7,"title":"Draft: Tidy CI","author":{"username":"bob"},
+            "work_in_progress":true,"source_branch":"tidy","target_branch":"main",
+            "sha":"abc","web_url":"https://gitlab.com/o/r/-/merge_requests/7",
+            "detailed_merge_status":"not_approved"}]"#;
+        let mrs: Vec<MergeRequest> = serde_json::from_str(body).unwrap();
+        let pr = pull_request_from_mr(mrs.into_iter().next().unwrap());
+        assert!(pr.draft);
+        assert_eq!(pr.review, ReviewState::ReviewRequired);
+        assert_eq!(pr.author, "bob");
+        assert_eq!(pr.reference(), "!7");
+        assert_eq!(pr.head_ref(), "refs/merge-requests/7/head");
+        assert_eq!(pr.local_branch(), "mr/7");
+    }
+
+    #[test]
+    fn releases_prefer_direct_asset_links() {
+        let body = r#"{"tag_name":"v2.0","name":null,"description":"Notes",
+            "released_at":"2026-03-01T12:00:00Z","upcoming_release":true,
+            "assets":{"links":[{"name":"app.tar.gz","url":"https://cdn.example/app.tar.gz",
+                "direct_asset_url":"https://gitlab.com/o/r/-/releases/v2.0/downloads/app.tar.gz"}]}}"#;
+        let response: ReleaseResponse = serde_json::from_str(body).unwrap();
+        let release = release_from(response, "https://gitlab.com", "o/r");
+        assert_eq!(release.title(), "v2.0");
+        assert!(release.prerelease && !release.draft);
+        assert_eq!(release.url, "https://gitlab.com/o/r/-/releases/v2.0");
+        assert_eq!(
+            release.assets[0].url,
+            "https://gitlab.com/o/r/-/releases/v2.0/downloads/app.tar.gz"
+        );
+        assert_eq!(release.assets[0].size, None);
+    }
+
+    #[test]
+    fn discussions_keep_diff_notes_and_flag_outdated() {
+        let body = r#"[
+            {"id":"d1","notes":[{"type":null,"body":"LGTM","author":{"username":"amy"},
+                "created_at":"2024-05-01T10:00:00Z"}]},
+            {"id":"d2","notes":[{"type":"DiffNote","body":"Off by one","author":{"username":"bob"},
+                "created_at":"2024-05-01T10:00:00Z","resolved":true,
+                "position":{"head_sha":"new","new_path":"a.rs","old_path":"a.rs","new_line":12,"old_line":null}}]},
+            {"id":"d3","notes":[{"type":"DiffNote","body":"Gone","author":{"username":"bob"},
+                "created_at":"2024-05-01T10:00:00Z",
+                "position":{"head_sha":"old","new_path":"a.rs","old_path":"a.rs","new_line":null,"old_line":4}}]}
+        ]"#;
+        let discussions: Vec<Discussion> = serde_json::from_str(body).unwrap();
+        let threads = threads_from_discussions(discussions, "new");
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].line, Some(12));
+        assert_eq!(threads[0].side, ReviewSide::New);
+        assert!(threads[0].resolved && !threads[0].outdated);
+        assert_eq!(threads[1].side, ReviewSide::Old);
+        assert_eq!(threads[1].line, None);
+        assert!(threads[1].outdated);
+    }
 }
@@ -0,0 +1,230 @@
+//! Conditional GETs and rate-limit tracking for the forge REST clients.
+//!
+//! `GitHubClient::get` and `GitLabClient::get` go through [`get`]. A
+//! JSON response carrying an `ETag` is remembered by URL, and the next
+//! request for that URL sends it back as `If-None-Match`; the forge
+//! answers `304 Not Modified` when nothing changed (GitHub doesn't
+//! count those against the rate limit) and the remembered body is
+//! handed back as if it had been downloaded again.
+//!
+//! Every response's rate-limit headers are recorded per API host (each
+//! GitHub Enterprise or GitLab instance has its own budget), and
+//! [`paced`] stretches the CI poll interval so polling can't spend the
+//! budget before the window resets.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, SystemTime};
+use ureq::http::{Response, StatusCode};
+use ureq::typestate::WithoutBody;
+use ureq::{Body, RequestBuilder};
+
+/// Bodies larger than this are passed through without being cached.
+const MAX_CACHED_BODY: usize = 1024 * 1024;
+
+/// Remembered responses before the cache starts over. Each CI poll
+/// touches a handful of URLs per repository.
+const MAX_CACHED_URLS: usize = 256;
+
+/// Share of the rate limit (1/N) CI polling leaves untouched for
+/// interactive requests: job logs, pull requests, CI actions.
+const RESERVE_DIVISOR: u64 = 5;
+
+struct Cached {
+    etag: String,
+    body: Vec<u8>,
+}
+
+static RESPONSES: LazyLock<Mutex<HashMap<String, Cached>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The rate-limit window as of the latest response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+    /// When the window resets and `remaining` returns to `limit`.
+    pub reset: SystemTime,
+}
+
+/// Keyed by API host, e.g. "api.github.com" or "gitlab.example.com".
+static RATE_LIMITS: LazyLock<Mutex<HashMap<String, RateLimit>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Send `request` for `url`, conditionally when a previous response is
+/// remembered. A 304 comes back as a 200 with the remembered body;
+/// other non-2xx statuses are returned untouched for the caller's error
+/// mapping.
+pub fn get(url: &str, request: RequestBuilder<WithoutBody>) -> Result<Response<Body>, ureq::Error> {
+    let cached = RESPONSES
+        .lock()
+        .ok()
+        .and_then(|responses| responses.get(url).map(|c| (c.etag.clone(), c.body.clone())));
+    let request = match &cached {
+        Some((etag, _)) => request.header("If-None-Match", etag),
+        None => request,
+    };
+    let mut resp = request.call()?;
+    record_rate_limit(url_host(url), &resp);
+
+    if resp.status() == StatusCode::NOT_MODIFIED
+        && let Some((_, body)) = cached
+    {
+        return Ok(with_body(resp, body));
+    }
+    let etag = resp
+        .headers()
+        .get("etag")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let is_json = resp
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("json"));
+    let (Some(etag), true, true) = (etag, is_json, resp.status() == StatusCode::OK) else {
+        return Ok(resp);
+    };
+    let body = resp.body_mut().read_to_vec()?;
+    if body.len() <= MAX_CACHED_BODY
+        && let Ok(mut responses) = RESPONSES.lock()
+    {
+        if responses.len() >= MAX_CACHED_URLS {
+            responses.clear();
+        }
+        responses.insert(
+            url.to_string(),
+            Cached {
+                etag,
+                body: body.clone(),
+            },
+        );
+    }
+    Ok(with_body(resp, body))
+}
+
+/// `resp` as a 200 whose body is `body`, for callers that read it as
+/// they would a fresh response.
+fn with_body(resp: Response<Body>, body: Vec<u8>) -> Response<Body> {
+    let (mut parts, _) = resp.into_parts();
+    parts.status = StatusCode::OK;
+    Response::from_parts(
+        parts,
+        Body::builder().mime_type("application/json").data(body),
+    )
+}
+
+/// GitHub sends `x-ratelimit-*`, GitLab `ratelimit-*`; both give the
+/// reset as a Unix timestamp. GitHub's GraphQL and search budgets are
+/// separate from the REST one CI polling draws on, so only the `core`
+/// resource is recorded.
+fn record_rate_limit(host: &str, resp: &Response<Body>) {
+    let header = |name: &str| {
+        [format!("x-ratelimit-{name}"), format!("ratelimit-{name}")]
+            .iter()
+            .find_map(|key| resp.headers().get(key.as_str()))
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    if header("resource").is_some_and(|r| r != "core") {
+        return;
+    }
+    let number = |name: &str| header(name).and_then(|v| v.trim().parse::<u64>().ok());
+    let (Some(limit), Some(remaining), Some(reset)) =
+        (number("limit"), number("remaining"), number("reset"))
+    else {
+        return;
+    };
+    if let Ok(mut limits) = RATE_LIMITS.lock() {
+        limits.insert(
+            host.to_string(),
+            RateLimit {
+                limit,
+                remaining,
+                reset: SystemTime::UNIX_EPOCH + Duration::from_secs(reset),
+            },
+        );
+    }
+}
+
+/// Host (with any port) of an absolute URL.
+fn url_host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split('/').next().unwrap_or(rest)
+}
+
+/// Latest rate-limit window reported by `api_host`, if any.
+fn rate_limit(api_host: &str) -> Option<RateLimit> {
+    RATE_LIMITS.lock().ok()?.get(api_host).copied()
+}
+
+/// Poll interval against `api_host`: `base`, stretched when polls
+/// costing `requests_per_poll` each at that rate would run out of
+/// budget before the rate-limit window resets.
+pub fn paced(api_host: &str, base: Duration, requests_per_poll: u32) -> Duration {
+    match rate_limit(api_host) {
+        Some(limit) => pace(limit, base, requests_per_poll, SystemTime::now()),
+        None => base,
+    }
+}
+
+fn pace(limit: RateLimit, base: Duration, requests_per_poll: u32, now: SystemTime) -> Duration {
+    let Ok(window) = limit.reset.duration_since(now) else {
+        // Already reset; the next response brings a fresh window.
+        return base;
+    };
+    let budget = limit
+        .remaining
+        .saturating_sub(limit.limit / RESERVE_DIVISOR);
+    let polls = budget / u64::from(requests_per_poll.max(1));
+    if polls == 0 {
+        return base.max(window);
+    }
+    base.max(window / u32::try_from(polls).unwrap_or(u32::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pace_spreads_the_remaining_budget_over_the_window() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let base = Duration::from_secs(15);
+        let window = |limit, remaining| RateLimit {
+            limit,
+            remaining,
+            reset: now + Duration::from_secs(3600),
+        };
+        // Plenty left: the provider's own cadence applies.
+        assert_eq!(pace(window(5000, 4900), base, 13, now), base);
+        // 1000 + 130 left of 5000: ten 13-request polls for the hour.
+        assert_eq!(
+            pace(window(5000, 1130), base, 13, now),
+            Duration::from_secs(360)
+        );
+        // Into the reserve: wait for the reset.
+        assert_eq!(
+            pace(window(5000, 900), base, 13, now),
+            Duration::from_secs(3600)
+        );
+        // A window that has already reset doesn't hold polling back.
+        let expired = RateLimit {
+            reset: now - Duration::from_secs(1),
+            ..window(5000, 0)
+        };
+        assert_eq!(pace(expired, base, 13, now), base);
+    }
+
+    #[test]
+    fn url_host_keeps_the_port() {
+        assert_eq!(
+            url_host("https://api.github.com/repos/o/r/actions/runs"),
+            "api.github.com"
+        );
+        assert_eq!(
+            url_host("https://gitlab.example.com:8443/api/v4/projects"),
+            "gitlab.example.com:8443"
+        );
+    }
+}
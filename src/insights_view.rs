@@ -0,0 +1,415 @@
+//! Insights view: per-author activity over time, a commit calendar,
+//! churn hotspots and per-directory ownership for one ref over a date
+//! range. Takes over the center pane like the diff view — Close or
+//! Escape returns to the graph. Reads [`RepoTab::insights`]; the pass
+//! itself runs off-thread through `GitRepo::compute_insights_async`
+//! and is cached per tip commit on the tab.
+
+use aetna_core::{El, IconName, prelude::*};
+
+use crate::git::{
+    AuthorStats, DirOwnership, FileChurn, InsightsRange, RepoInsights, TIMELINE_BUCKETS,
+    format_absolute_time,
+};
+use crate::repo_tab::{InsightsState, RepoTab};
+
+/// Routed key for the ref input. Events fold into
+/// `InsightsState::rev`; Apply (`insights:apply`) re-runs the pass.
+pub const INSIGHTS_REF_KEY: &str = "insights:ref";
+/// Routed keys for the custom range's `YYYY-MM-DD` inputs.
+pub const INSIGHTS_SINCE_KEY: &str = "insights:since";
+pub const INSIGHTS_UNTIL_KEY: &str = "insights:until";
+
+/// Authors listed before collapsing the rest into an "and N more"
+/// caption.
+const MAX_AUTHOR_ROWS: usize = 30;
+/// Calendar columns (weeks) at most — one year, GitHub-style.
+const CALENDAR_WEEKS: i64 = 53;
+const CALENDAR_CELL: f32 = 10.0;
+const CALENDAR_GAP: f32 = 2.0;
+const TIMELINE_BAR_WIDTH: f32 = 6.0;
+const TIMELINE_HEIGHT: f32 = 16.0;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+pub fn insights_view(tab: &RepoTab, selection: &Selection) -> El {
+    let Some(state) = tab.insights.as_ref() else {
+        return column(Vec::<El>::new());
+    };
+
+    let body = match &state.result {
+        Some(Ok(insights)) => insights_body(insights),
+        Some(Err(e)) => placeholder(
+            column([
+                row([
+                    icon(IconName::AlertCircle).text_color(tokens::DESTRUCTIVE),
+                    text("Couldn't compute insights").label(),
+                ])
+                .gap(tokens::SPACE_2)
+                .align(Align::Center),
+                paragraph(e.clone()).muted(),
+            ])
+            .gap(tokens::SPACE_2),
+        ),
+        None => placeholder(progress_row("Walking history\u{2026}")),
+    };
+
+    column([header(state, selection), separator(), body])
+        .width(Size::Fill(1.0))
+        .height(Size::Fill(1.0))
+}
+
+fn header(state: &InsightsState, selection: &Selection) -> El {
+    let mut ranges: Vec<El> = InsightsRange::ALL
+        .into_iter()
+        .map(|range| {
+            let b = button(range.label()).key(format!("insights:range:{}", range.key()));
+            if range == state.range {
+                b.primary()
+            } else {
+                b.ghost()
+            }
+        })
+        .collect();
+    let custom = matches!(state.range, InsightsRange::Custom { .. });
+    let custom_button = button("Custom\u{2026}")
+        .key("insights:range:custom")
+        .tooltip("Cover the dates entered below");
+    ranges.push(if custom {
+        custom_button.primary()
+    } else {
+        custom_button.ghost()
+    });
+    if state.pending.is_some() && state.result.is_some() {
+        ranges.push(progress_row("Updating\u{2026}"));
+    }
+    let dates = row([
+        text("From").caption().muted(),
+        text_input(&state.custom_since, selection, INSIGHTS_SINCE_KEY).width(Size::Fixed(110.0)),
+        text("to").caption().muted(),
+        text_input(&state.custom_until, selection, INSIGHTS_UNTIL_KEY).width(Size::Fixed(110.0)),
+        text("YYYY-MM-DD, inclusive").caption().muted(),
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center);
+
+    column([
+        row([
+            icon(IconName::Activity),
+            text("Insights").label(),
+            spacer(),
+            button("Close").key("insights:close").ghost(),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+        row([
+            text_input(&state.rev, selection, INSIGHTS_REF_KEY).width(Size::Fill(1.0)),
+            button("Apply")
+                .key("insights:apply")
+                .tooltip("Compute insights for this ref"),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center),
+        row(ranges).gap(tokens::SPACE_1).align(Align::Center),
+        dates,
+    ])
+    .gap(tokens::SPACE_2)
+    .padding(tokens::SPACE_3)
+    .width(Size::Fill(1.0))
+}
+
+fn insights_body(insights: &RepoInsights) -> El {
+    let mut summary = format!(
+        "{} commits \u{00b7} {} authors \u{00b7} +{} -{} \u{00b7} {} \u{2192} {}",
+        insights.commit_count,
+        insights.authors.len(),
+        insights.insertions,
+        insights.deletions,
+        date(insights.start),
+        date(insights.end),
+    );
+    if insights.merge_count > 0 {
+        summary.push_str(&format!(" \u{00b7} {} merges", insights.merge_count));
+    }
+    let mut top: Vec<El> = vec![text(summary).caption().muted()];
+    if insights.truncated {
+        top.push(
+            text("Stopped at the commit limit; older history isn't counted.")
+                .caption()
+                .text_color(tokens::WARNING),
+        );
+    }
+
+    let cards: Vec<El> = vec![
+        column(top).gap(tokens::SPACE_1),
+        calendar_card(insights),
+        authors_card(insights),
+        hotspots_card(&insights.hotspots),
+        ownership_card(&insights.ownership),
+    ];
+    scroll([column(cards).gap(tokens::SPACE_3).padding(tokens::SPACE_3)])
+        .key("insights:scroll")
+        .height(Size::Fill(1.0))
+}
+
+/// One square per day, one column per week (Sunday on top), shaded by
+/// commit count relative to the busiest day in view.
+fn calendar_card(insights: &RepoInsights) -> El {
+    let end_day = insights.end.div_euclid(SECONDS_PER_DAY);
+    let start_day = insights.start.div_euclid(SECONDS_PER_DAY);
+    // Day 0 (1970-01-01) was a Thursday; shift so weeks start Sunday.
+    let first_week_start = {
+        let earliest = start_day.max(end_day - CALENDAR_WEEKS * 7 + 1);
+        earliest - (earliest + 4).rem_euclid(7)
+    };
+    let busiest = insights
+        .calendar
+        .range(first_week_start..=end_day)
+        .map(|(_, n)| *n)
+        .max()
+        .unwrap_or(0);
+
+    let mut weeks: Vec<El> = Vec::new();
+    let mut week_start = first_week_start;
+    while week_start <= end_day {
+        let days: Vec<El> = (week_start..week_start + 7)
+            .map(|day| {
+                let in_range = day >= start_day && day <= end_day;
+                let count = insights.calendar.get(&day).copied().unwrap_or(0);
+                calendar_cell(day, count, busiest, in_range)
+            })
+            .collect();
+        weeks.push(column(days).gap(CALENDAR_GAP));
+        week_start += 7;
+    }
+    let active_days = insights.calendar.range(first_week_start..=end_day).count();
+
+    card([
+        card_header([row([
+            text("Commit calendar").label(),
+            spacer(),
+            text(format!("{active_days} active days")).caption().muted(),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center)])
+        .padding(tokens::SPACE_3),
+        // A year of weeks is ~640px; narrower panes clip the grid's
+        // right edge rather than wrapping it.
+        card_content([row(weeks).gap(CALENDAR_GAP).width(Size::Fill(1.0)).clip()])
+            .padding(tokens::SPACE_3)
+            .pt(0.0),
+    ])
+}
+
+fn calendar_cell(day: i64, count: usize, busiest: usize, in_range: bool) -> El {
+    let fill = if !in_range {
+        tokens::MUTED.with_alpha(12)
+    } else if count == 0 {
+        tokens::MUTED.with_alpha(40)
+    } else {
+        // Four shades, GitHub-style: quartiles of the busiest day.
+        let level = (count * 4).div_ceil(busiest.max(1)).clamp(1, 4);
+        tokens::SUCCESS.with_alpha(55 + 50 * level as u8)
+    };
+    let cell = column(Vec::<El>::new())
+        .width(Size::Fixed(CALENDAR_CELL))
+        .height(Size::Fixed(CALENDAR_CELL))
+        .radius(2.0)
+        .fill(fill);
+    if !in_range {
+        return cell;
+    }
+    let noun = if count == 1 { "commit" } else { "commits" };
+    cell.key(format!("insights:day:{day}"))
+        .tooltip(format!("{count} {noun} on {}", date(day * SECONDS_PER_DAY)))
+}
+
+fn authors_card(insights: &RepoInsights) -> El {
+    let mut rows: Vec<El> = insights
+        .authors
+        .iter()
+        .take(MAX_AUTHOR_ROWS)
+        .enumerate()
+        .map(|(idx, a)| author_row(idx, a, insights))
+        .collect();
+    if insights.authors.len() > MAX_AUTHOR_ROWS {
+        rows.push(
+            text(format!(
+                "and {} more",
+                insights.authors.len() - MAX_AUTHOR_ROWS
+            ))
+            .caption()
+            .muted(),
+        );
+    }
+    if rows.is_empty() {
+        rows.push(text("No commits in this range.").muted());
+    }
+    section_card(
+        &format!("Authors ({})", insights.authors.len()),
+        "commits \u{00b7} lines \u{00b7} activity",
+        rows,
+    )
+}
+
+fn author_row(idx: usize, author: &AuthorStats, insights: &RepoInsights) -> El {
+    row([
+        text(author.name.clone())
+            .ellipsis()
+            .width(Size::Fill(1.0))
+            .key(format!("insights:author:{idx}"))
+            .tooltip(author.email.clone()),
+        text(author.commits.to_string()).mono().muted(),
+        line_counts(author.insertions, author.deletions),
+        timeline(idx, &author.timeline, insights),
+    ])
+    .gap(tokens::SPACE_3)
+    .align(Align::Center)
+}
+
+/// Bars for an author's commits per timeline slice, scaled to their
+/// own busiest slice so quieter contributors still show a shape.
+fn timeline(idx: usize, buckets: &[usize], insights: &RepoInsights) -> El {
+    let busiest = buckets.iter().copied().max().unwrap_or(0).max(1);
+    let bars: Vec<El> = buckets
+        .iter()
+        .enumerate()
+        .map(|(bucket, &count)| {
+            let height = if count == 0 {
+                1.0
+            } else {
+                (TIMELINE_HEIGHT * count as f32 / busiest as f32).max(2.0)
+            };
+            let color = if count == 0 {
+                tokens::MUTED.with_alpha(60)
+            } else {
+                tokens::PRIMARY
+            };
+            column(Vec::<El>::new())
+                .width(Size::Fixed(TIMELINE_BAR_WIDTH))
+                .height(Size::Fixed(height))
+                .fill(color)
+                .key(format!("insights:timeline:{idx}:{bucket}"))
+                .tooltip(format!(
+                    "{count} commits from {}",
+                    date(insights.bucket_start(bucket))
+                ))
+        })
+        .collect();
+    row(bars)
+        .gap(1.0)
+        .align(Align::End)
+        .height(Size::Fixed(TIMELINE_HEIGHT))
+        .width(Size::Fixed(
+            TIMELINE_BUCKETS as f32 * (TIMELINE_BAR_WIDTH + 1.0),
+        ))
+}
+
+fn hotspots_card(hotspots: &[FileChurn]) -> El {
+    let rows: Vec<El> = if hotspots.is_empty() {
+        vec![text("No file changes in this range.").muted()]
+    } else {
+        hotspots
+            .iter()
+            .map(|f| {
+                row([
+                    text(f.path.clone())
+                        .mono()
+                        .ellipsis()
+                        .width(Size::Fill(1.0))
+                        .key(format!("insights:file:{}", f.path))
+                        .tooltip(f.path.clone()),
+                    text(format!("{} commits", f.commits)).caption().muted(),
+                    text(format!("\u{00b1}{}", f.lines)).mono().muted(),
+                ])
+                .gap(tokens::SPACE_3)
+                .align(Align::Center)
+            })
+            .collect()
+    };
+    section_card("Hotspots", "most frequently changed files", rows)
+}
+
+fn ownership_card(dirs: &[DirOwnership]) -> El {
+    let rows: Vec<El> = if dirs.is_empty() {
+        vec![text("No added lines in this range.").muted()]
+    } else {
+        dirs.iter()
+            .map(|d| {
+                let others = d.contributors.saturating_sub(1);
+                let tooltip = if others == 0 {
+                    format!("{} wrote every added line", d.owner)
+                } else {
+                    format!(
+                        "{} of {} added lines; {others} other contributors",
+                        d.owner_lines, d.total_lines
+                    )
+                };
+                row([
+                    text(d.dir.clone()).mono().ellipsis().width(Size::Fill(1.0)),
+                    text(d.owner.clone())
+                        .ellipsis()
+                        .key(format!("insights:dir:{}", d.dir))
+                        .tooltip(tooltip),
+                    badge(format!("{}%", d.owner_percent())).muted(),
+                    text(format!("+{}", d.total_lines)).mono().muted(),
+                ])
+                .gap(tokens::SPACE_3)
+                .align(Align::Center)
+            })
+            .collect()
+    };
+    section_card("Ownership", "top author by added lines", rows)
+}
+
+fn section_card(title: &str, caption: &str, rows: Vec<El>) -> El {
+    card([
+        card_header([row([
+            text(title.to_string()).label(),
+            spacer(),
+            text(caption.to_string()).caption().muted(),
+        ])
+        .gap(tokens::SPACE_2)
+        .align(Align::Center)])
+        .padding(tokens::SPACE_3),
+        card_content(rows)
+            .padding(tokens::SPACE_3)
+            .pt(0.0)
+            .gap(tokens::SPACE_1),
+    ])
+}
+
+fn line_counts(insertions: usize, deletions: usize) -> El {
+    row([
+        text(format!("+{insertions}"))
+            .mono()
+            .text_color(tokens::SUCCESS),
+        text(format!("-{deletions}"))
+            .mono()
+            .text_color(tokens::DESTRUCTIVE),
+    ])
+    .gap(tokens::SPACE_2)
+}
+
+fn progress_row(label: &str) -> El {
+    use aetna_core::widgets::spinner::spinner_with_color;
+    row([
+        spinner_with_color(tokens::PRIMARY),
+        text(label.to_string()).caption().muted(),
+    ])
+    .gap(tokens::SPACE_2)
+    .align(Align::Center)
+}
+
+fn placeholder(content: El) -> El {
+    column([content])
+        .align(Align::Center)
+        .justify(Justify::Center)
+        .padding(tokens::SPACE_4)
+        .width(Size::Fill(1.0))
+        .height(Size::Fill(1.0))
+}
+
+/// `YYYY-MM-DD` for a unix timestamp.
+fn date(ts: i64) -> String {
+    format_absolute_time(ts).chars().take(10).collect()
+}
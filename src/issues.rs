@@ -0,0 +1,163 @@
+//! Provider-agnostic issue types and commit message link resolution.
+//!
+//! [`IssueLinker`] turns the references [`crate::git::find_issue_refs`]
+//! finds into [`IssueLink`]s: through a pattern's URL template, or to
+//! the `origin` forge's issue page. Forge links carry the issue number
+//! so the tab can fetch the title and state lazily for tooltips.
+
+use crate::forge::{self, ForgeBackend};
+use crate::git::{IssueLinkPattern, find_issue_refs};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueState {
+    Open,
+    Closed,
+    /// A pull request that was merged; GitHub numbers them alongside
+    /// issues, so `#12` may be either.
+    Merged,
+}
+
+impl IssueState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IssueState::Open => "Open",
+            IssueState::Closed => "Closed",
+            IssueState::Merged => "Merged",
+        }
+    }
+}
+
+/// An issue as the forge reports it.
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub state: IssueState,
+    /// `true` when the number belongs to a pull request.
+    pub pull_request: bool,
+    pub url: String,
+}
+
+impl Issue {
+    /// Tooltip text: "#12 · Open issue\nTitle".
+    pub fn summary(&self, reference: &str) -> String {
+        let kind = if self.pull_request {
+            "pull request"
+        } else {
+            "issue"
+        };
+        format!(
+            "{reference} \u{00b7} {} {kind}\n{}",
+            self.state.label(),
+            self.title
+        )
+    }
+}
+
+/// A reference in a commit message, resolved to where it points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueLink {
+    /// As written, e.g. "#12" or "PROJ-42".
+    pub text: String,
+    pub url: String,
+    /// Forge issue number, for links whose details can be fetched.
+    pub number: Option<u64>,
+}
+
+/// Resolves references for one repository. Built from its configured
+/// patterns and `origin` remote; without configured patterns the
+/// forge's own reference shapes (`#12`, plus `GH-12` on GitHub) apply.
+#[derive(Clone, Default)]
+pub struct IssueLinker {
+    patterns: Vec<IssueLinkPattern>,
+    /// Forge behind `origin`, with the remote URL.
+    forge: Option<(&'static dyn ForgeBackend, String)>,
+}
+
+impl IssueLinker {
+    pub fn new(mut patterns: Vec<IssueLinkPattern>, origin_url: Option<&str>) -> Self {
+        let forge = origin_url
+            .and_then(|url| forge::detect(url).map(|(backend, _)| (backend, url.to_string())));
+        if patterns.is_empty()
+            && let Some((backend, _)) = forge
+        {
+            patterns = backend
+                .issue_prefixes()
+                .iter()
+                .map(|prefix| IssueLinkPattern {
+                    prefix: prefix.to_string(),
+                    url: None,
+                })
+                .collect();
+        }
+        Self { patterns, forge }
+    }
+
+    /// Links for every reference in `message`. References to forge
+    /// patterns are dropped when `origin` isn't on a forge that links
+    /// issues.
+    pub fn links(&self, message: &str) -> Vec<IssueLink> {
+        if self.patterns.is_empty() {
+            return Vec::new();
+        }
+        find_issue_refs(message, &self.patterns)
+            .into_iter()
+            .filter_map(|reference| {
+                let pattern = &self.patterns[reference.pattern];
+                let (url, number) = match pattern.url_for(&reference) {
+                    Some(url) => (url, None),
+                    None => {
+                        let (backend, remote) = self.forge.as_ref()?;
+                        let url = backend.issue_url(remote, reference.number)?;
+                        (url, Some(reference.number))
+                    }
+                };
+                Some(IssueLink {
+                    text: reference.text,
+                    url,
+                    number,
+                })
+            })
+            .collect()
+    }
+
+    /// The forge issue numbers are fetched from, when `origin` has one.
+    pub fn forge(&self) -> Option<(&'static dyn ForgeBackend, &str)> {
+        self.forge
+            .as_ref()
+            .map(|(backend, url)| (*backend, url.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_default_to_the_origin_forge() {
+        let linker = IssueLinker::new(Vec::new(), Some("git@github.com:owner/repo.git"));
+        let links = linker.links("Fix panic (#12), closes GH-3");
+        assert_eq!(
+            links,
+            vec![
+                IssueLink {
+                    text: "#12".into(),
+                    url: "https://github.com/owner/repo/issues/12".into(),
+                    number: Some(12),
+                },
+                IssueLink {
+                    text: "GH-3".into(),
+                    url: "https://github.com/owner/repo/issues/3".into(),
+                    number: Some(3),
+                },
+            ]
+        );
+
+        let jira = IssueLinkPattern::parse("PROJ- https://jira.example/browse/{ref}").unwrap();
+        let linker = IssueLinker::new(vec![jira], Some("https://example.com/plain.git"));
+        let links = linker.links("PROJ-42: tidy up (#12)");
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://jira.example/browse/PROJ-42");
+        assert_eq!(links[0].number, None);
+    }
+}
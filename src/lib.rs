@@ -7,18 +7,29 @@
 pub mod ai;
 pub mod avatar;
 pub mod ci;
+pub mod ci_cache;
+pub mod ci_log;
+pub mod ci_log_view;
 pub mod commit_details;
 pub mod commit_graph;
 pub mod config;
 pub mod crash_log;
 pub mod dialogs;
 pub mod diff_view;
+pub mod fake_ci;
+pub mod forge;
 pub mod git;
 pub mod git_async;
+pub mod gitea;
 pub mod github;
 pub mod gitlab;
 pub mod host;
+pub mod http_cache;
+pub mod insights_view;
+pub mod issues;
+pub mod pulls;
 pub mod recent;
+pub mod releases;
 pub mod repo_tab;
 pub mod screenshot_mode;
 pub mod sidebar;
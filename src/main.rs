@@ -198,11 +198,9 @@ fn apply_screenshot_state(app: &mut WhisperApp, state: Option<&str>) {
             app.active_modal = Some(ActiveModal::Token(TokenForm::default()));
         }
         "token-edit" => {
-            let form = TokenForm {
-                editing_github: true,
-                github_input: "ghp_demo123".to_string(),
-                ..Default::default()
-            };
+            let mut form = TokenForm::default();
+            form.inputs
+                .insert("github:github.com".to_string(), "ghp_demo123".to_string());
             app.active_modal = Some(ActiveModal::Token(form));
         }
         "context-menu" => {
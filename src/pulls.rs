@@ -0,0 +1,186 @@
+//! Provider-agnostic pull request / merge request types.
+//!
+//! GitHub pull requests and GitLab merge requests are both surfaced as
+//! [`PullRequest`]s so the sidebar, graph pills and checkout flow don't
+//! care which forge the remote points at.
+
+use crate::ci::{CiProvider, CiState};
+
+/// Review state as reported by the forge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReviewState {
+    #[default]
+    None,
+    ReviewRequired,
+    Approved,
+    ChangesRequested,
+}
+
+impl ReviewState {
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            ReviewState::None => None,
+            ReviewState::ReviewRequired => Some("Review required"),
+            ReviewState::Approved => Some("Approved"),
+            ReviewState::ChangesRequested => Some("Changes requested"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PullRequest {
+    pub provider: CiProvider,
+    /// PR number (GitHub) or MR iid (GitLab).
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub draft: bool,
+    pub review: ReviewState,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub head_sha: String,
+    pub url: String,
+    /// CI rollup reported alongside the PR. `None` when the provider's
+    /// listing doesn't include one; callers fall back to the per-commit
+    /// CI results for `head_sha`.
+    pub ci: Option<CiState>,
+}
+
+impl PullRequest {
+    /// Display reference: `#12` for PRs, `!12` for MRs.
+    pub fn reference(&self) -> String {
+        match self.provider {
+            CiProvider::GitHub | CiProvider::Gitea | CiProvider::Local => {
+                format!("#{}", self.number)
+            }
+            CiProvider::GitLab => format!("!{}", self.number),
+        }
+    }
+
+    /// Remote ref the forge publishes the head under.
+    pub fn head_ref(&self) -> String {
+        match self.provider {
+            CiProvider::GitHub | CiProvider::Gitea | CiProvider::Local => {
+                format!("refs/pull/{}/head", self.number)
+            }
+            CiProvider::GitLab => format!("refs/merge-requests/{}/head", self.number),
+        }
+    }
+
+    /// Local branch name a checkout creates.
+    pub fn local_branch(&self) -> String {
+        match self.provider {
+            CiProvider::GitHub | CiProvider::Gitea | CiProvider::Local => {
+                format!("pr/{}", self.number)
+            }
+            CiProvider::GitLab => format!("mr/{}", self.number),
+        }
+    }
+
+    /// Stable identifier used in UI keys, e.g. `GH-12`.
+    pub fn key(&self) -> String {
+        format!("{}-{}", self.provider.short_label(), self.number)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProviderPullResult {
+    pub provider: CiProvider,
+    /// Forge host the listing came from; see
+    /// [`crate::ci::ProviderCiResult::host`].
+    pub host: String,
+    /// Git remote the listing was fetched for; checkouts fetch from it.
+    pub remote: String,
+    pub pulls: Result<Vec<PullRequest>, String>,
+}
+
+/// A pull / merge request to open from `source_branch`.
+#[derive(Debug, Clone, Default)]
+pub struct NewPullRequest {
+    pub source_branch: String,
+    pub target_branch: String,
+    pub title: String,
+    pub body: String,
+    pub draft: bool,
+    /// Usernames to request review from.
+    pub reviewers: Vec<String>,
+    pub labels: Vec<String>,
+}
+
+/// Result of opening a pull request. The request itself may succeed
+/// while assigning reviewers or labels fails; those failures are
+/// reported as `warnings` rather than failing the whole operation.
+#[derive(Debug, Clone)]
+pub struct CreatedPullRequest {
+    pub pull: PullRequest,
+    pub warnings: Vec<String>,
+}
+
+/// Side of the diff a review comment is anchored to: the base
+/// version (`Old`, deleted lines) or the head version (`New`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewSide {
+    Old,
+    New,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReviewComment {
+    pub author: String,
+    pub body: String,
+    pub created_at: String,
+}
+
+/// A review thread on one line of one file in a pull request.
+#[derive(Debug, Clone)]
+pub struct ReviewThread {
+    /// GitHub review thread node ID or GitLab discussion ID.
+    pub id: String,
+    /// What replies attach to: the first comment's REST ID on GitHub,
+    /// the discussion ID on GitLab.
+    pub reply_to: String,
+    pub path: String,
+    pub side: ReviewSide,
+    /// Line in the pull request's current head (or base, for `Old`).
+    /// `None` when the forge can no longer place an outdated thread.
+    pub line: Option<u32>,
+    /// The code the thread was left on has changed since.
+    pub outdated: bool,
+    pub resolved: bool,
+    pub comments: Vec<ReviewComment>,
+}
+
+/// A write to a pull request's review threads.
+#[derive(Debug, Clone)]
+pub enum ReviewAction {
+    Reply {
+        thread_id: String,
+        reply_to: String,
+        body: String,
+    },
+    SetResolved {
+        thread_id: String,
+        resolved: bool,
+    },
+    /// Start a new thread on a line of `path` as of `commit_sha`.
+    /// Added lines carry only `new_line`, deleted lines only
+    /// `old_line`, context lines both.
+    Comment {
+        path: String,
+        old_line: Option<u32>,
+        new_line: Option<u32>,
+        commit_sha: String,
+        body: String,
+    },
+}
+
+/// Split a comma separated list of usernames or labels, dropping `@`
+/// prefixes. Commas only, since labels may contain spaces.
+pub fn parse_name_list(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(|s| s.trim().trim_start_matches('@'))
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,107 @@
+//! Provider-agnostic release types.
+//!
+//! GitHub releases and GitLab releases are both surfaced as
+//! [`Release`]s, keyed by the tag they were cut from, so the tag card
+//! in the commit details pane and the tag context menu don't care
+//! which forge `origin` points at.
+
+use crate::ci::CiProvider;
+
+/// A file attached to a release (GitHub upload, GitLab release link).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseAsset {
+    pub name: String,
+    /// Size in bytes. GitLab links don't report one.
+    pub size: Option<u64>,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    pub provider: CiProvider,
+    pub tag: String,
+    /// Release title; empty when the forge falls back to the tag.
+    pub name: String,
+    /// Markdown release notes.
+    pub notes: String,
+    /// Unpublished. Only GitHub has drafts.
+    pub draft: bool,
+    /// Marked as not production-ready. GitLab's closest notion is an
+    /// upcoming release, dated in the future.
+    pub prerelease: bool,
+    /// ISO 8601 publish time as the forge reports it.
+    pub published_at: Option<String>,
+    pub url: String,
+    pub assets: Vec<ReleaseAsset>,
+}
+
+impl Release {
+    /// Heading for the release: its name, or the tag when unnamed.
+    pub fn title(&self) -> &str {
+        if self.name.trim().is_empty() {
+            &self.tag
+        } else {
+            &self.name
+        }
+    }
+
+    /// Badge for releases that aren't plain published ones.
+    pub fn badge(&self) -> Option<&'static str> {
+        if self.draft {
+            Some("Draft")
+        } else if self.prerelease {
+            Some("Pre-release")
+        } else {
+            None
+        }
+    }
+
+    /// Publish date, `2026-01-31`, without the time of day.
+    pub fn published_date(&self) -> Option<&str> {
+        self.published_at
+            .as_deref()
+            .map(|at| at.split_once('T').map_or(at, |(date, _)| date))
+    }
+}
+
+/// A release to create from an existing tag.
+#[derive(Debug, Clone, Default)]
+pub struct NewRelease {
+    pub tag: String,
+    /// Commit the tag points at. Forges only use it when the tag isn't
+    /// on the remote yet, so an unpushed tag is created in the right
+    /// place rather than on the default branch.
+    pub target_sha: String,
+    pub name: String,
+    pub notes: String,
+    pub draft: bool,
+    pub prerelease: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_badge_and_date_fall_back_sensibly() {
+        let mut release = Release {
+            provider: CiProvider::GitHub,
+            tag: "v1.2.0".into(),
+            name: " ".into(),
+            notes: String::new(),
+            draft: false,
+            prerelease: true,
+            published_at: Some("2026-01-31T09:30:00Z".into()),
+            url: String::new(),
+            assets: Vec::new(),
+        };
+        assert_eq!(release.title(), "v1.2.0");
+        assert_eq!(release.badge(), Some("Pre-release"));
+        assert_eq!(release.published_date(), Some("2026-01-31"));
+
+        release.name = "Spring release".into();
+        release.draft = true;
+        assert_eq!(release.title(), "Spring release");
+        assert_eq!(release.badge(), Some("Draft"));
+    }
+}
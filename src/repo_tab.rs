@@ -19,23 +19,35 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use anyhow::{Context, Result};
 
+use std::sync::Arc;
 use std::sync::mpsc::Receiver;
 use std::time::Instant;
 
 use winit::event_loop::EventLoopProxy;
 
-use crate::ci::{CiFetchResult, ProviderCiResult, ProviderCommitRollup};
+use crate::ci::{
+    CiAction, CiCheckStatus, CiCounts, CiFetchResult, CiJob, CiProvider, CiState, ProviderCiResult,
+    ProviderCommitRollup,
+};
+use crate::ci_cache;
+use crate::ci_log::{LogRow, ParsedLog};
 use crate::commit_graph::GraphLayout;
 use crate::config::Config;
+use crate::forge;
 use crate::git::{
-    BranchTip, CommitInfo, CommitSubmoduleEntry, DiffFile, FullCommitInfo, GitRepo, RemoteOpResult,
-    StashEntry, SubmoduleInfo, TagInfo, WorkingDirStatus, WorktreeInfo, insert_synthetics_sorted,
+    BranchTip, CodeOwners, CommitInfo, CommitSubmoduleEntry, DiffFile, FullCommitInfo, GitRepo,
+    InsightsRange, MaintenanceOutput, MaintenanceTask, RemoteOpResult, RepoHealth, RepoInsights,
+    StashEntry, SubmoduleInfo, TagInfo, TagSort, WorkingDirStatus, WorktreeInfo,
+    format_absolute_time, insert_synthetics_sorted, sort_tags,
 };
 use crate::git_async::{
     DirtyCheckResult, RepoStateResult, StatusResult, spawn_repo_state_refresh, spawn_status_refresh,
 };
+use crate::http_cache;
+use crate::issues::{Issue, IssueLinker};
+use crate::pulls::{CreatedPullRequest, ProviderPullResult, PullRequest, ReviewThread};
+use crate::releases::Release;
 use crate::watcher::{FsChangeKind, RepoWatcher, WatcherInitResult};
-use crate::{github, gitlab, token_store};
 
 /// Unique id allocator for [`RepoTab`]. Used by per-entity dirty-check
 /// results (which flow over a global channel) to route back to the
@@ -87,6 +99,12 @@ pub struct AiOp {
     pub target_path: PathBuf,
 }
 
+/// Forge behind a remote URL and the stored API token for it, if any.
+pub fn remote_forge(url: &str) -> Option<(CiProvider, Option<String>)> {
+    let (backend, host) = forge::detect(url)?;
+    Some((backend.provider(), backend.token(&host)))
+}
+
 /// Cached detail for the currently selected commit. Loaded once per
 /// selection change so the History details pane doesn't hit libgit2 on
 /// every frame.
@@ -98,8 +116,114 @@ pub struct CommitDetail {
     /// changed-first by `submodules_at_commit` so callers can render
     /// straight through.
     pub submodule_entries: Vec<CommitSubmoduleEntry>,
+    /// Owners of each changed file under `CODEOWNERS` as of this
+    /// commit, so ownership reflects the rules the change was made
+    /// under. Files nobody owns are absent.
+    pub file_owners: HashMap<String, Vec<String>>,
+}
+
+/// Insights view state for one tab. `Some` on [`RepoTab::insights`]
+/// while the view owns the center pane.
+pub struct InsightsState {
+    /// Ref typed into the view's input; resolved when applied.
+    pub rev: String,
+    pub range: InsightsRange,
+    /// `YYYY-MM-DD` inputs for [`InsightsRange::Custom`], applied with
+    /// `insights:range:custom`.
+    pub custom_since: String,
+    pub custom_until: String,
+    /// Latest pass for the applied ref and range, or the reason there
+    /// isn't one (unknown ref, failed walk).
+    pub result: Option<Result<Arc<RepoInsights>, String>>,
+    /// In-flight pass with the cache key it fills.
+    pub pending: Option<PendingInsights>,
+}
+
+/// CI log viewer state for one tab. `Some` on [`RepoTab::ci_log`]
+/// while the viewer owns the center pane.
+pub struct CiLogState {
+    pub provider: CiProvider,
+    /// Remote the checks were reported for; API calls go through it.
+    pub remote_url: String,
+    pub sha: String,
+    /// The commit's checks from `provider` that name a run.
+    pub checks: Vec<CiCheckStatus>,
+    /// Index into `checks` of the run whose jobs are listed.
+    pub run: usize,
+    pub jobs: Option<Result<Vec<CiJob>, String>>,
+    pub jobs_rx: Option<Receiver<Result<Vec<CiJob>, String>>>,
+    pub job: Option<u64>,
+    pub log: Option<Result<Arc<ParsedLog>, String>>,
+    /// In-flight download with the job it's for.
+    pub log_rx: Option<(u64, Receiver<Result<ParsedLog, String>>)>,
+    /// Folded group indices in `log`.
+    pub collapsed: HashSet<usize>,
+    /// Search box text; `ci_log:next` / `ci_log:prev` step through the
+    /// matching lines.
+    pub search: String,
+    /// Line of the last search hit jumped to.
+    pub search_line: Option<usize>,
+}
+
+impl CiLogState {
+    pub fn selected_job(&self) -> Option<&CiJob> {
+        let jobs = self.jobs.as_ref()?.as_ref().ok()?;
+        jobs.iter().find(|j| Some(j.id) == self.job)
+    }
+
+    /// Unfold the groups hiding line `idx` and return its row in the
+    /// rendered log.
+    pub fn reveal_line(&mut self, idx: usize) -> Option<usize> {
+        let Some(Ok(log)) = self.log.as_ref() else {
+            return None;
+        };
+        for g in log.enclosing_groups(idx) {
+            self.collapsed.remove(&g);
+        }
+        log.visible_rows(&self.collapsed)
+            .iter()
+            .position(|r| *r == LogRow::Line(idx))
+    }
+}
+
+/// Cache key for one insights pass: tip commit, range, and the day the
+/// range starts on (see [`InsightsRange::start_day`]).
+pub type InsightsKey = (git2::Oid, InsightsRange, Option<i64>);
+
+pub struct PendingInsights {
+    pub key: InsightsKey,
+    pub rx: Receiver<Result<RepoInsights, String>>,
+}
+
+pub struct PendingConflictRisk {
+    pub key: (git2::Oid, git2::Oid),
+    pub rx: Receiver<Result<usize, String>>,
 }
 
+/// Maintenance panel state for one tab: the latest health report
+/// and the housekeeping commands run from the panel.
+#[derive(Default)]
+pub struct MaintenanceState {
+    /// Latest report, or why it couldn't be built.
+    pub health: Option<Result<RepoHealth, String>>,
+    /// In-flight report.
+    pub health_rx: Option<Receiver<Result<RepoHealth, String>>>,
+    /// In-flight command. One at a time per repo — `gc` and `prune`
+    /// both rewrite the object store.
+    pub op: Option<MaintenanceOp>,
+    /// Finished commands, newest first, capped at
+    /// [`MAINTENANCE_LOG_LIMIT`].
+    pub log: Vec<MaintenanceOutput>,
+}
+
+pub struct MaintenanceOp {
+    pub task: MaintenanceTask,
+    pub rx: Receiver<MaintenanceOutput>,
+    pub started: Instant,
+}
+
+const MAINTENANCE_LOG_LIMIT: usize = 10;
+
 /// Cap for `commit_graph()` — first cut, no infinite-scroll. Plenty for
 /// the visible viewport even on big repos. Lifted later if needed.
 const COMMIT_LIMIT: usize = 1000;
@@ -119,6 +243,7 @@ const DIFF_STATS_FETCH_LIMIT: usize = COMMIT_LIMIT;
 pub enum SidebarSection {
     Local,
     Remote,
+    PullRequests,
     Tags,
     Stashes,
 }
@@ -128,6 +253,7 @@ impl SidebarSection {
         match self {
             Self::Local => "LOCAL",
             Self::Remote => "REMOTE",
+            Self::PullRequests => "PULLS",
             Self::Tags => "TAGS",
             Self::Stashes => "STASHES",
         }
@@ -137,12 +263,19 @@ impl SidebarSection {
         match self {
             Self::Local => "Local",
             Self::Remote => "Remote",
+            Self::PullRequests => "Pull Requests",
             Self::Tags => "Tags",
             Self::Stashes => "Stashes",
         }
     }
 
-    pub const ALL: [SidebarSection; 4] = [Self::Local, Self::Remote, Self::Tags, Self::Stashes];
+    pub const ALL: [SidebarSection; 5] = [
+        Self::Local,
+        Self::Remote,
+        Self::PullRequests,
+        Self::Tags,
+        Self::Stashes,
+    ];
 }
 
 /// A logical entry in the sidebar — the keyboard cursor lands on these.
@@ -162,6 +295,8 @@ pub struct SidebarState {
     /// expanded; entries here are remotes the user has collapsed.
     pub collapsed_remotes: HashSet<String>,
     pub selected: Option<SidebarSelection>,
+    /// TAGS section order. Applied whenever `RepoTab::tags` is loaded.
+    pub tag_sort: TagSort,
 }
 
 impl SidebarState {
@@ -211,11 +346,20 @@ pub struct WorktreeView {
     pub current_branch: String,
     /// HEAD OID for this worktree.
     pub head_oid: Option<git2::Oid>,
+    /// In-progress operation (merge, rebase, `git am`, ...) for this
+    /// worktree. Drives the Continue / Abort banner in the staging well.
+    pub repo_state: git2::RepositoryState,
     /// Submodules registered in *this worktree*. Submodules are a
     /// property of the working tree (they live at paths inside it),
     /// not the repo — so each worktree carries its own list. Refreshed
     /// alongside `status` whenever the active view re-runs.
     pub submodules: Vec<SubmoduleInfo>,
+    /// `CODEOWNERS` rules as they are in this working tree. Refreshed
+    /// with `status`, so the staging well follows edits to the file.
+    pub codeowners: Option<CodeOwners>,
+    /// Owners of each path in `status`, matched against `codeowners`
+    /// whenever either changes. Paths nobody owns are absent.
+    pub file_owners: HashMap<String, Vec<String>>,
     /// Commit-message subject draft (controlled).
     pub commit_subject: String,
     /// Commit-message body draft (controlled).
@@ -238,7 +382,10 @@ impl WorktreeView {
             status: WorkingDirStatus::default(),
             current_branch: String::new(),
             head_oid: None,
+            repo_state: git2::RepositoryState::Clean,
             submodules: Vec::new(),
+            codeowners: None,
+            file_owners: HashMap::new(),
             commit_subject: String::new(),
             commit_body: String::new(),
             selected_diff_file: None,
@@ -261,7 +408,10 @@ impl WorktreeView {
             status: WorkingDirStatus::default(),
             current_branch: String::new(),
             head_oid: None,
+            repo_state: git2::RepositoryState::Clean,
             submodules: Vec::new(),
+            codeowners: None,
+            file_owners: HashMap::new(),
             commit_subject: String::new(),
             commit_body: String::new(),
             selected_diff_file: None,
@@ -271,10 +421,31 @@ impl WorktreeView {
     /// Re-query worktree-scoped state (status + branch + HEAD + submodules).
     pub fn refresh(&mut self) {
         self.status = self.repo.status().unwrap_or_default();
+        self.codeowners = self.repo.codeowners();
+        self.refresh_file_owners();
         self.refresh_ref_state();
         self.submodules = self.repo.submodules().unwrap_or_default();
     }
 
+    /// Re-match every path in `status` against `codeowners`.
+    pub fn refresh_file_owners(&mut self) {
+        self.file_owners = match &self.codeowners {
+            Some(owners) => {
+                let status = &self.status;
+                owners.owners_by_path(
+                    status
+                        .staged
+                        .iter()
+                        .chain(&status.unstaged)
+                        .chain(&status.untracked)
+                        .chain(&status.conflicted)
+                        .map(|f| f.path.as_str()),
+                )
+            }
+            None => HashMap::new(),
+        };
+    }
+
     /// Re-query only ref metadata for this worktree. This deliberately
     /// skips status scans, so repo-state refreshes can keep inactive
     /// worktree HEAD/branch data current without losing cached dirty
@@ -282,6 +453,7 @@ impl WorktreeView {
     fn refresh_ref_state(&mut self) {
         self.current_branch = self.repo.current_branch().unwrap_or_default();
         self.head_oid = self.repo.head_oid().ok();
+        self.repo_state = self.repo.repo_state();
     }
 }
 
@@ -304,6 +476,9 @@ pub struct RepoTab {
     // ---- Repo-level metadata ----
     pub branch_tips: Vec<BranchTip>,
     pub remotes: Vec<String>,
+    /// Remotes pointing at a bundle file. Resolved on refresh so the
+    /// sidebar and menus never stat the filesystem per frame.
+    pub bundle_remotes: HashSet<String>,
     pub tags: Vec<TagInfo>,
     /// Linked-worktree metadata. The main worktree is *not* in this
     /// list — libgit2 only enumerates linked worktrees here.
@@ -313,6 +488,17 @@ pub struct RepoTab {
     /// Reachable commit history, refreshed alongside repo metadata.
     /// Capped at `COMMIT_LIMIT` until infinite-scroll comes back.
     pub commits: Vec<CommitInfo>,
+    /// Commits with `git notes` attached -> notes refs holding them.
+    /// Drives the note marker on history rows.
+    pub noted_commits: HashMap<git2::Oid, Vec<String>>,
+    /// Conflicting path counts for merging a local branch into the
+    /// checked-out one, keyed by `(HEAD oid, branch oid)`. Filled lazily
+    /// for the branch the user selects (see
+    /// [`Self::request_conflict_risk`]); drives the sidebar's warning
+    /// marker.
+    pub conflict_risk: HashMap<(git2::Oid, git2::Oid), usize>,
+    /// In-flight conflict-risk merge for one `(HEAD, branch)` pair.
+    pub pending_conflict_risk: Option<PendingConflictRisk>,
     /// Lane / color assignment for `commits`. Rebuilt each refresh.
     pub graph_layout: GraphLayout,
 
@@ -326,6 +512,9 @@ pub struct RepoTab {
     /// Cached detail for `selected_commit`, refreshed via
     /// [`Self::select_commit`] when the selection changes.
     pub commit_detail: Option<CommitDetail>,
+    /// Start of a patch-export range, marked from the commit context
+    /// menu. The export modal pre-fills it as the `base` of `base..tip`.
+    pub patch_range_base: Option<git2::Oid>,
 
     // ---- Per-worktree state ----
     /// One entry per known worktree (main + linked), keyed by working-dir
@@ -357,23 +546,71 @@ pub struct RepoTab {
     pub ai_op: Option<AiOp>,
 
     // ---- CI status ----
-    /// Latest results, one per provider. The header bar reads these for
+    /// Latest results, one per provider and host. The header bar reads these for
     /// the branch-level summary; the graph rows index `ci_per_commit`
-    /// (derived) by SHA for per-commit dots.
+    /// (derived) by SHA for per-commit dots. Seeded at open from
+    /// `ci_cache` (marked stale) and written back as results land.
     pub ci_results: Vec<ProviderCiResult>,
-    /// In-flight CI fetches — one per provider per fetch attempt. Drained
-    /// each frame; on Ready, the matching `ci_results` entry is replaced.
-    pub ci_receivers: Vec<Receiver<ProviderCiResult>>,
-    /// When the last CI fetch was kicked off, regardless of outcome.
-    /// Drives the dynamic poll cadence in `WhisperApp::poll_ci_refresh`.
-    pub last_ci_fetch: Option<Instant>,
+    /// In-flight CI fetches — one per forge host per fetch attempt,
+    /// tagged with that host. Drained each frame; on Ready, the matching
+    /// `ci_results` entry is replaced.
+    pub ci_receivers: Vec<(String, Receiver<ProviderCiResult>)>,
+    /// When each forge host's CI fetch was last kicked off, regardless
+    /// of outcome. Each host polls on its own cadence; see
+    /// [`Self::ci_next_due`].
+    pub last_ci_fetch: HashMap<(CiProvider, String), Instant>,
     /// When the most recent successful push completed. Within 5 minutes
     /// the CI poll cadence boosts to 15 s so users see new runs appear
     /// quickly after they push.
     pub last_push_time: Option<Instant>,
+    /// When a re-run / cancel / dispatch was last accepted. Boosts the
+    /// poll cadence the same way a push does.
+    pub last_ci_action: Option<Instant>,
+    /// In-flight CI action, kept for its toast once it finishes.
+    pub ci_action_rx: Option<(CiAction, Receiver<Result<(), String>>)>,
     /// Per-commit rollups derived from `ci_results`. Recomputed whenever
     /// a new provider result lands.
     pub ci_per_commit: HashMap<String, Vec<ProviderCommitRollup>>,
+    /// Open pull / merge requests, one listing per provider and host.
+    pub pull_results: Vec<ProviderPullResult>,
+    /// In-flight listings, tagged with their host like `ci_receivers`.
+    pub pull_receivers: Vec<(String, Receiver<ProviderPullResult>)>,
+    /// Set when pull requests should be listed again: when the tab
+    /// opens, on a manual refresh and after a push. Listings change
+    /// far less often than CI, so they stay off the CI poll.
+    pub pulls_due: bool,
+    /// `(branch, remote)` just pushed with no pull request open for it.
+    /// Shown as a "Create Pull Request" banner until acted on.
+    pub pr_offer: Option<(String, String)>,
+    /// In-flight AI title / description for the Create Pull Request
+    /// modal.
+    pub pr_draft_rx: Option<Receiver<Result<crate::ai::AiResponse, String>>>,
+    /// In-flight pull request creation, with the remote it was opened on.
+    pub pr_create_rx: Option<(String, Receiver<Result<CreatedPullRequest, String>>)>,
+    /// Review threads on the checked-out branch's pull request, shown
+    /// in the diff viewer. `review_for` is the `(key, head_sha)` they
+    /// were fetched for, so a new push or branch switch refetches.
+    pub review_threads: Vec<ReviewThread>,
+    pub review_for: Option<(String, String)>,
+    pub review_rx: Option<Receiver<Result<Vec<ReviewThread>, String>>>,
+    /// In-flight reply / resolve / new comment.
+    pub review_action_rx: Option<Receiver<Result<(), String>>>,
+    /// Turns `#12` / `PROJ-42` in commit messages into links. Rebuilt
+    /// on every state refresh, since `origin` may have moved.
+    pub issue_linker: IssueLinker,
+    /// Forge issues referenced by commits the user has selected,
+    /// fetched once each for the link tooltips. `issues_requested`
+    /// includes in-flight and failed lookups so neither repeats.
+    pub issues: HashMap<u64, Issue>,
+    pub issues_requested: HashSet<u64>,
+    pub issue_receivers: Vec<Receiver<Result<Issue, String>>>,
+    /// Set when `origin`'s releases should be listed again: when the
+    /// tab opens, on a manual refresh and after a fetch or push, which
+    /// may bring new tags. Listings land on [`TagInfo::release`].
+    pub releases_due: bool,
+    pub release_rx: Option<Receiver<Result<Vec<Release>, String>>>,
+    /// In-flight release creation, with the tag it's cut from.
+    pub release_create_rx: Option<(String, Receiver<Result<Release, String>>)>,
 
     // ---- Async refresh slots ----
     /// `true` once the tab's first state-refresh has been spawned.
@@ -446,6 +683,25 @@ pub struct RepoTab {
     /// query). The query persists across tab switches but the bar
     /// visibility is per-tab.
     pub history_search_open: bool,
+    /// Staging well's "show ignored files" toggle. When on, status
+    /// refreshes also collect ignored paths for the Ignored section.
+    pub show_ignored_files: bool,
+
+    // ---- Insights ----
+    /// Insights view, when open. Escape closes it.
+    pub insights: Option<InsightsState>,
+    /// Finished insights passes keyed by tip commit, range and the
+    /// range's resolved start day. A new commit on the ref changes the
+    /// tip and a new day moves the start of "last N days", so stale
+    /// entries are simply never hit again; lives as long as the tab.
+    pub insights_cache: HashMap<InsightsKey, Arc<RepoInsights>>,
+
+    // ---- CI logs ----
+    /// CI log viewer, when open. Escape closes it.
+    pub ci_log: Option<CiLogState>,
+
+    // ---- Maintenance ----
+    pub maintenance: MaintenanceState,
 
     // ---- Submodule drill-down ----
     /// Stack of drilled-in submodule views. Each entry is a fully
@@ -508,19 +764,31 @@ impl RepoTab {
     /// [`Self::refresh`] right after `open` to do the work synchronously.
     pub fn open(path: impl AsRef<Path>) -> Result<Self> {
         let repo = GitRepo::open(&path).context("open repository")?;
+        // Last-known CI from the previous session, shown stale until
+        // the first poll lands.
+        let ci_results = ci_cache::load(repo.common_dir());
+        let ci_per_commit = CiFetchResult {
+            providers: ci_results.clone(),
+        }
+        .per_commit_provider_rollups();
         let tab = Self {
             id: next_tab_id(),
             repo_name: repo.repo_name(),
             branch_tips: Vec::new(),
             remotes: Vec::new(),
+            bundle_remotes: HashSet::new(),
             tags: Vec::new(),
             worktrees: Vec::new(),
             stashes: Vec::new(),
+            noted_commits: HashMap::new(),
+            conflict_risk: HashMap::new(),
+            pending_conflict_risk: None,
             sidebar: SidebarState::default(),
             commits: Vec::new(),
             graph_layout: GraphLayout::new(),
             selected_commit: None,
             commit_detail: None,
+            patch_range_base: None,
             worktree_views: HashMap::new(),
             active_worktree: None,
             worktree_order: Vec::new(),
@@ -530,11 +798,30 @@ impl RepoTab {
             push_op: None,
             mutation_op: None,
             ai_op: None,
-            ci_results: Vec::new(),
+            ci_results,
             ci_receivers: Vec::new(),
-            last_ci_fetch: None,
+            last_ci_fetch: HashMap::new(),
             last_push_time: None,
-            ci_per_commit: HashMap::new(),
+            last_ci_action: None,
+            ci_action_rx: None,
+            ci_per_commit,
+            pull_results: Vec::new(),
+            pull_receivers: Vec::new(),
+            pulls_due: true,
+            pr_offer: None,
+            pr_draft_rx: None,
+            pr_create_rx: None,
+            review_threads: Vec::new(),
+            review_for: None,
+            review_rx: None,
+            review_action_rx: None,
+            issue_linker: IssueLinker::default(),
+            issues: HashMap::new(),
+            issues_requested: HashSet::new(),
+            issue_receivers: Vec::new(),
+            releases_due: true,
+            release_rx: None,
+            release_create_rx: None,
             state_refresh_attempted: false,
             state_refresh_rx: None,
             status_rx: None,
@@ -547,6 +834,11 @@ impl RepoTab {
             diff_stats_fetched: false,
             search_query: String::new(),
             history_search_open: false,
+            show_ignored_files: false,
+            insights: None,
+            ci_log: None,
+            insights_cache: HashMap::new(),
+            maintenance: MaintenanceState::default(),
             nav_stack: Vec::new(),
             pinned_oid: None,
             pinned_path: None,
@@ -569,9 +861,11 @@ impl RepoTab {
         self.state_refresh_attempted = true;
         self.branch_tips = self.repo.branch_tips().unwrap_or_default();
         self.remotes = self.repo.remote_names();
-        self.tags = self.repo.tags().unwrap_or_default();
+        self.bundle_remotes = self.repo.bundle_remotes();
+        self.set_tags(self.repo.tags().unwrap_or_default());
         self.worktrees = self.repo.worktrees().unwrap_or_default();
         self.stashes = self.repo.stash_list();
+        self.noted_commits = self.repo.noted_commits();
         // Commit list might change — let the polling loop re-trigger
         // the diff-stats fetch on the next pass.
         self.diff_stats_fetched = false;
@@ -769,6 +1063,7 @@ impl RepoTab {
             repo_context_path,
             staging_context_path,
             is_bare,
+            self.show_ignored_files,
             proxy.clone(),
         ));
     }
@@ -817,10 +1112,13 @@ impl RepoTab {
         }
         self.commits = commits;
         self.branch_tips = result.branch_tips;
-        self.tags = result.tags;
+        self.set_tags(result.tags);
         self.worktrees = result.worktrees.clone();
         self.remotes = result.remote_names;
+        self.bundle_remotes = result.bundle_remotes;
+        self.refresh_issue_linker();
         self.stashes = result.stashes;
+        self.noted_commits = result.noted_commits;
         self.ref_fingerprint = result.ref_fingerprint;
         // Clear cached diff-stats marker so the polling loop re-runs the
         // fetch against the new commit set.
@@ -900,16 +1198,30 @@ impl RepoTab {
             main_status,
             staging_path,
             staging_status,
-            staging_repo_state: _,
+            staging_repo_state,
+            staging_codeowners,
         } = result;
 
         let mut changed = false;
         if let (Some(path), Some(status)) = (main_path.as_deref(), main_status) {
             changed |= self.set_worktree_status(path, status);
         }
+        if let Some(view) = staging_path
+            .as_deref()
+            .and_then(|path| self.worktree_views.get_mut(path))
+        {
+            view.repo_state = staging_repo_state;
+            view.codeowners = staging_codeowners;
+        }
         if let (Some(path), Some(status)) = (staging_path.as_deref(), staging_status) {
             changed |= self.set_worktree_status(path, status);
         }
+        if let Some(view) = staging_path
+            .as_deref()
+            .and_then(|path| self.worktree_views.get_mut(path))
+        {
+            view.refresh_file_owners();
+        }
 
         if changed {
             self.rebuild_synthetic_entries();
@@ -938,6 +1250,13 @@ impl RepoTab {
                 path,
                 status,
             } => {
+                // Dirty checks never walk ignored paths; keep the ones
+                // the last status refresh found so the Ignored section
+                // doesn't blink empty between refreshes.
+                let mut status = status;
+                if let Some(view) = self.worktree_views.get(&path) {
+                    status.ignored = view.status.ignored.clone();
+                }
                 let dirty_file_count = status.total_files();
                 let is_dirty = dirty_file_count > 0;
                 if let Some(wt) = self
@@ -963,6 +1282,9 @@ impl RepoTab {
         };
         let changed = view.status != status;
         view.status = status;
+        if changed {
+            view.refresh_file_owners();
+        }
         changed
     }
 
@@ -1022,7 +1344,7 @@ impl RepoTab {
         // Linked worktrees from the worker's worktrees list.
         for wt in &self.worktrees {
             let path = PathBuf::from(&wt.path);
-            if new_views.contains_key(&path) {
+            if wt.is_stale || new_views.contains_key(&path) {
                 continue;
             }
             let view = if let Some(mut existing) = self.worktree_views.remove(&path) {
@@ -1098,10 +1420,13 @@ impl RepoTab {
             let wt_info = WorktreeInfo {
                 name: view.name.clone(),
                 path: path.to_string_lossy().to_string(),
-                branch: view.current_branch.clone(),
+                branch: Some(view.current_branch.clone()),
                 head_oid: view.head_oid,
                 is_dirty: Some(true),
                 dirty_file_count: Some(count),
+                is_locked: false,
+                lock_reason: None,
+                is_stale: false,
             };
             if let Some(mut entry) = CommitInfo::synthetic_for_worktree(&wt_info, parent_time) {
                 let (ins, del) = view.repo.working_tree_diff_stats();
@@ -1144,7 +1469,7 @@ impl RepoTab {
         // Linked worktrees from libgit2.
         for wt in &self.worktrees {
             let path = PathBuf::from(&wt.path);
-            if new_views.contains_key(&path) {
+            if wt.is_stale || new_views.contains_key(&path) {
                 continue;
             }
             let entry = self
@@ -1289,10 +1614,16 @@ impl RepoTab {
         };
         let files = self.repo.diff_for_commit(oid).unwrap_or_default();
         let submodule_entries = self.repo.submodules_at_commit(oid).unwrap_or_default();
+        let file_owners = self
+            .repo
+            .codeowners_at(oid)
+            .map(|o| o.owners_by_path(files.iter().map(|f| f.path.as_str())))
+            .unwrap_or_default();
         self.commit_detail = Some(CommitDetail {
             info,
             files,
             submodule_entries,
+            file_owners,
         });
     }
 
@@ -1419,68 +1750,90 @@ impl RepoTab {
         out
     }
 
+    /// When CI for `host` on `provider` is next due: that forge's own
+    /// poll interval after its last fetch, paced against its API
+    /// host's rate limit. The fast cadence applies while its runs are
+    /// pending or within 5 minutes of a push or CI action. `None`
+    /// before the host's first fetch.
+    pub fn ci_next_due(&self, provider: CiProvider, host: &str, now: Instant) -> Option<Instant> {
+        let last = self.last_ci_fetch.get(&(provider, host.to_string()))?;
+        let pending = self.ci_results.iter().any(|r| {
+            r.provider == provider && r.host == host && r.status.state == CiState::Pending
+        });
+        let recent = |t: Option<Instant>| t.is_some_and(|t| now.duration_since(t).as_secs() < 300);
+        let busy = pending || recent(self.last_push_time) || recent(self.last_ci_action);
+        let backend = forge::backend(provider);
+        let interval = http_cache::paced(
+            &backend.api_host(host),
+            backend.poll_interval(busy),
+            backend.requests_per_poll(),
+        );
+        Some(*last + interval)
+    }
+
     /// Kick off CI fetches for every configured remote that maps to a
-    /// known provider. One worker thread per (provider, remote) — each
-    /// thread sends its result back via `ci_receivers` and wakes the
-    /// event loop through `proxy`. `last_ci_fetch` is only stamped when
-    /// at least one fetch actually launched: on the first frame after
-    /// `RepoTab::open` the async state refresh hasn't populated
-    /// `self.remotes` yet, so the loop has nothing to iterate. Stamping
-    /// regardless would hide that empty-remotes race behind the 5-min
-    /// dynamic interval (CI badges would show 5 minutes late on every
-    /// open). Leaving the stamp untouched lets the next poll retry
-    /// once the state-refresh worker fills the field.
+    /// known provider and is due (see [`Self::ci_next_due`]) with no
+    /// fetch already in flight. One worker thread per (provider, host)
+    /// — each thread sends its result back via `ci_receivers` and
+    /// wakes the event loop through `proxy`. Only hosts whose fetch
+    /// actually launched are stamped in `last_ci_fetch`, so a host
+    /// skipped for lack of a token, or remotes not loaded yet on the
+    /// first frames after `RepoTab::open`, are retried on the next
+    /// poll instead of waiting out the interval.
     ///
-    /// Tokens come from the system keychain via `token_store`. GitLab
-    /// hosts seen here are also auto-registered into `config.gitlab_hosts`
-    /// (and the config persisted) so the token modal has something to
-    /// enumerate even before the user has set a token for the host.
+    /// Each remote goes to the first [`forge::backends`] entry that
+    /// claims it, once per (forge, host). Tokens come from the backend
+    /// (the system keychain for the real forges). Hosts seen here are
+    /// also offered to [`forge::ForgeBackend::register_host`] (and the
+    /// config persisted) so the token modal has something to enumerate
+    /// even before the user has set a token for the host.
     pub fn trigger_ci_fetch(&mut self, config: &mut Config, proxy: EventLoopProxy<()>) {
         if self.remotes.is_empty() {
             // Early-out so the keychain query below doesn't fire 60×/s
             // while we wait for the state-refresh worker to populate
-            // `self.remotes`. The `launched`-gated stamp at the bottom
-            // would also leave `last_ci_fetch` correctly unset, but the
-            // keychain hit isn't free.
+            // `self.remotes`.
             return;
         }
-        let github_token = token_store::get_github_token();
-        let mut seen_github = false;
-        let mut seen_gitlab: HashSet<String> = HashSet::new();
+        let now = Instant::now();
+        let mut seen: HashSet<(&'static str, String)> = HashSet::new();
         let mut config_dirty = false;
-        let mut launched = false;
         for remote in &self.remotes {
-            let url = match self.repo.remote_url(remote) {
-                Some(u) => u,
-                None => continue,
+            let Some(url) = self.repo.remote_url(remote) else {
+                continue;
             };
-            if !seen_github
-                && let Some(token) = github_token.as_deref()
-                && let Some(rx) = github::fetch_ci_status_async(token, &url, proxy.clone())
-            {
-                self.ci_receivers.push(rx);
-                seen_github = true;
-                launched = true;
+            let Some((backend, host)) = forge::detect(&url) else {
+                continue;
+            };
+            if !seen.insert((backend.id(), host.clone())) {
                 continue;
             }
-            if let Some(parsed) = gitlab::parse_gitlab_remote(&url) {
-                if !seen_gitlab.insert(parsed.api_base.clone()) {
-                    continue;
-                }
-                let host = parsed
-                    .api_base
-                    .strip_prefix("https://")
-                    .or_else(|| parsed.api_base.strip_prefix("http://"))
-                    .unwrap_or(&parsed.api_base);
-                if config.register_gitlab_host(host) {
-                    config_dirty = true;
-                }
-                if let Some(token) = token_store::get_gitlab_token(host)
-                    && let Some(rx) = gitlab::fetch_ci_status_async(&token, &url, proxy.clone())
-                {
-                    self.ci_receivers.push(rx);
-                    launched = true;
-                }
+            if backend.register_host(config, &host) {
+                config_dirty = true;
+            }
+            let in_flight = self.ci_receivers.iter().any(|(h, _)| *h == host);
+            let not_due = self
+                .ci_next_due(backend.provider(), &host, now)
+                .is_some_and(|due| due > now);
+            if in_flight || not_due {
+                continue;
+            }
+            let token = backend.token(&host);
+            if token.is_none() && backend.requires_token() {
+                continue;
+            }
+            let pulls: Vec<PullRequest> = self
+                .pull_results
+                .iter()
+                .filter(|r| r.provider == backend.provider() && r.host == host)
+                .filter_map(|r| r.pulls.as_ref().ok())
+                .flatten()
+                .cloned()
+                .collect();
+            if let Some(rx) = backend.fetch_ci_status(token.as_deref(), &url, &pulls, proxy.clone())
+            {
+                self.last_ci_fetch
+                    .insert((backend.provider(), host.clone()), now);
+                self.ci_receivers.push((host, rx));
             }
         }
         if config_dirty {
@@ -1490,8 +1843,52 @@ impl RepoTab {
             // path since it'd be noise on every CI poll.
             let _ = config.save();
         }
-        if launched {
-            self.last_ci_fetch = Some(Instant::now());
+    }
+
+    /// List `origin`'s releases when [`Self::releases_due`] asks for it
+    /// and no listing is already in flight. Waits for the first state
+    /// refresh, which loads the tags the releases attach to.
+    pub fn trigger_release_fetch(&mut self, proxy: EventLoopProxy<()>) {
+        if !self.releases_due || self.remotes.is_empty() || self.release_rx.is_some() {
+            return;
+        }
+        self.releases_due = false;
+        let Some(url) = self.repo.remote_url("origin") else {
+            return;
+        };
+        let Some((backend, host)) = forge::detect(&url) else {
+            return;
+        };
+        if let Some(token) = backend.token(&host) {
+            self.release_rx = backend.fetch_releases(&token, &url, proxy);
+        }
+    }
+
+    /// List open pull requests on every forge host the remotes point
+    /// at, when [`Self::pulls_due`] asks for it and no listing is
+    /// already in flight.
+    pub fn trigger_pull_fetch(&mut self, proxy: EventLoopProxy<()>) {
+        if !self.pulls_due || self.remotes.is_empty() || !self.pull_receivers.is_empty() {
+            return;
+        }
+        self.pulls_due = false;
+        let mut seen: HashSet<(&'static str, String)> = HashSet::new();
+        for remote in &self.remotes {
+            let Some(url) = self.repo.remote_url(remote) else {
+                continue;
+            };
+            let Some((backend, host)) = forge::detect(&url) else {
+                continue;
+            };
+            if !backend.supports_pull_requests() || !seen.insert((backend.id(), host.clone())) {
+                continue;
+            }
+            if let Some(token) = backend.token(&host)
+                && let Some(rx) =
+                    backend.fetch_pull_requests(&token, &url, remote.clone(), proxy.clone())
+            {
+                self.pull_receivers.push((host, rx));
+            }
         }
     }
 
@@ -1555,6 +1952,464 @@ impl RepoTab {
         self.diff_stats_rx = Some(self.repo.compute_diff_stats_async(oids, proxy));
     }
 
+    /// Open the insights view on the checked-out branch (or `HEAD`
+    /// when detached) and start a pass. Re-opening keeps the previous
+    /// ref and range.
+    pub fn open_insights(&mut self, proxy: Option<&EventLoopProxy<()>>) {
+        if self.insights.is_none() {
+            let branch = self.current_branch();
+            let rev = if branch.is_empty() || branch.starts_with('(') {
+                "HEAD".to_string()
+            } else {
+                branch.to_string()
+            };
+            let today = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0);
+            let month_ago = today - 30 * 86_400;
+            self.insights = Some(InsightsState {
+                rev,
+                range: InsightsRange::default(),
+                custom_since: format_absolute_time(month_ago)[..10].to_string(),
+                custom_until: format_absolute_time(today)[..10].to_string(),
+                result: None,
+                pending: None,
+            });
+        }
+        self.request_insights(proxy);
+    }
+
+    /// Resolve the insights ref and show the cached pass for its tip
+    /// and range, spawning a worker on a miss. Without a proxy
+    /// (headless callers) the pass runs inline.
+    pub fn request_insights(&mut self, proxy: Option<&EventLoopProxy<()>>) {
+        let Some(state) = self.insights.as_mut() else {
+            return;
+        };
+        let tip = match self.repo.insights_tip(state.rev.trim()) {
+            Ok(tip) => tip,
+            Err(e) => {
+                state.pending = None;
+                state.result = Some(Err(format!("{e:#}")));
+                return;
+            }
+        };
+        let key = (tip, state.range, state.range.start_day());
+        if let Some(cached) = self.insights_cache.get(&key) {
+            state.pending = None;
+            state.result = Some(Ok(cached.clone()));
+            return;
+        }
+        if state.pending.as_ref().is_some_and(|p| p.key == key) {
+            return;
+        }
+        match proxy {
+            Some(proxy) => {
+                let rx = self
+                    .repo
+                    .compute_insights_async(tip, state.range, proxy.clone());
+                state.pending = Some(PendingInsights { key, rx });
+            }
+            None => {
+                let result = self
+                    .repo
+                    .compute_insights_sync(tip, state.range)
+                    .map(Arc::new)
+                    .map_err(|e| format!("{e:#}"));
+                if let Ok(insights) = &result {
+                    self.insights_cache.insert(key, insights.clone());
+                }
+                state.pending = None;
+                state.result = Some(result);
+            }
+        }
+    }
+
+    /// Fold a finished insights pass into the cache, and into the view
+    /// when it's still the one being shown. Returns true when a result
+    /// landed.
+    pub fn drain_insights(&mut self) -> bool {
+        use std::sync::mpsc::TryRecvError;
+        let Some(state) = self.insights.as_mut() else {
+            return false;
+        };
+        let Some(pending) = state.pending.as_ref() else {
+            return false;
+        };
+        let result = match pending.rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => Err("Insights worker exited".to_string()),
+        };
+        let key = pending.key;
+        state.pending = None;
+        let result = result.map(Arc::new);
+        if let Ok(insights) = &result {
+            self.insights_cache.insert(key, insights.clone());
+        }
+        state.result = Some(result);
+        true
+    }
+
+    /// `(HEAD oid, branch oid)` for merging local `branch` into the
+    /// active worktree's HEAD.
+    fn conflict_risk_key(&self, branch: &str) -> Option<(git2::Oid, git2::Oid)> {
+        let head = self.active_view()?.head_oid?;
+        let tip = self
+            .branch_tips
+            .iter()
+            .find(|b| !b.is_remote && b.name == branch)?;
+        Some((head, tip.oid))
+    }
+
+    /// Conflicting path count for merging `branch` into HEAD, when it
+    /// has been computed for the current tips and is non-zero.
+    pub fn conflict_risk_for(&self, branch: &str) -> Option<usize> {
+        let key = self.conflict_risk_key(branch)?;
+        self.conflict_risk.get(&key).copied().filter(|&n| n > 0)
+    }
+
+    /// Merge `branch` into HEAD in memory unless the pair is already
+    /// cached or in flight. Without a proxy (headless callers) the
+    /// merge runs inline.
+    pub fn request_conflict_risk(&mut self, branch: &str, proxy: Option<&EventLoopProxy<()>>) {
+        let Some(key) = self.conflict_risk_key(branch) else {
+            return;
+        };
+        if key.0 == key.1
+            || self.conflict_risk.contains_key(&key)
+            || self
+                .pending_conflict_risk
+                .as_ref()
+                .is_some_and(|p| p.key == key)
+        {
+            return;
+        }
+        match proxy {
+            Some(proxy) => {
+                let rx = self
+                    .active_repo()
+                    .merge_conflict_count_async(key.0, key.1, proxy.clone());
+                self.pending_conflict_risk = Some(PendingConflictRisk { key, rx });
+            }
+            None => {
+                let count = self
+                    .active_repo()
+                    .merge_conflict_count(key.0, key.1)
+                    .unwrap_or(0);
+                self.conflict_risk.insert(key, count);
+            }
+        }
+    }
+
+    /// Fold a finished conflict-risk merge into the cache. A failed
+    /// merge is cached as no risk so it isn't retried every frame.
+    /// Returns true when a result landed.
+    pub fn drain_conflict_risk(&mut self) -> bool {
+        use std::sync::mpsc::TryRecvError;
+        let Some(pending) = self.pending_conflict_risk.as_ref() else {
+            return false;
+        };
+        let count = match pending.rx.try_recv() {
+            Ok(result) => result.unwrap_or(0),
+            Err(TryRecvError::Empty) => return false,
+            Err(TryRecvError::Disconnected) => 0,
+        };
+        let key = pending.key;
+        self.pending_conflict_risk = None;
+        self.conflict_risk.insert(key, count);
+        true
+    }
+
+    /// Open the CI log viewer on commit `sha`'s checks from the
+    /// `provider_idx`th provider in [`Self::ci_per_commit`], starting
+    /// at the first failing run.
+    pub fn open_ci_log(
+        &mut self,
+        sha: &str,
+        provider_idx: usize,
+        proxy: EventLoopProxy<()>,
+    ) -> Result<(), String> {
+        let rollup = self
+            .ci_per_commit
+            .get(sha)
+            .and_then(|r| r.get(provider_idx))
+            .ok_or("No CI results for this commit")?;
+        let provider = rollup.provider;
+        let checks: Vec<CiCheckStatus> = rollup
+            .rollup
+            .checks
+            .iter()
+            .filter(|c| c.run_id.is_some())
+            .cloned()
+            .collect();
+        if checks.is_empty() {
+            return Err("No CI runs to show logs for".to_string());
+        }
+        let remote_url = self
+            .ci_remote_url(provider)
+            .ok_or("No remote for this CI provider")?;
+        let run = checks
+            .iter()
+            .position(|c| c.state == CiState::Failure)
+            .unwrap_or(0);
+        self.insights = None;
+        self.ci_log = Some(CiLogState {
+            provider,
+            remote_url,
+            sha: sha.to_string(),
+            checks,
+            run,
+            jobs: None,
+            jobs_rx: None,
+            job: None,
+            log: None,
+            log_rx: None,
+            collapsed: HashSet::new(),
+            search: String::new(),
+            search_line: None,
+        });
+        self.request_ci_jobs(proxy);
+        Ok(())
+    }
+
+    /// URL of the first remote hosted on `provider`'s forge.
+    fn ci_remote_url(&self, provider: CiProvider) -> Option<String> {
+        self.remotes
+            .iter()
+            .filter_map(|r| self.repo.remote_url(r))
+            .find(|url| remote_forge(url).is_some_and(|(p, _)| p == provider))
+    }
+
+    /// Send `action` to `provider`'s CI through the first remote on that
+    /// forge. Finished in [`Self::drain_ci_action`].
+    pub fn start_ci_action(
+        &mut self,
+        provider: CiProvider,
+        action: CiAction,
+        proxy: EventLoopProxy<()>,
+    ) -> Result<(), String> {
+        if self.ci_action_rx.is_some() {
+            return Err("Still sending the previous CI action".to_string());
+        }
+        let url = self
+            .ci_remote_url(provider)
+            .ok_or("No remote for this CI provider")?;
+        let backend = forge::backend(provider);
+        if !backend.supports_ci_actions() {
+            return Err(format!(
+                "CI actions aren't supported on {} yet",
+                backend.label()
+            ));
+        }
+        let Some((_, token)) =
+            remote_forge(&url).filter(|(_, t)| t.is_some() || !backend.requires_token())
+        else {
+            return Err("No API token set for this remote. Add one under Manage tokens.".into());
+        };
+        let rx = backend.ci_action(token.as_deref(), &url, action.clone(), proxy);
+        self.ci_action_rx = Some((action, rx.ok_or("Couldn't parse the remote URL")?));
+        Ok(())
+    }
+
+    /// Poll the in-flight CI action. Once accepted, the next CI poll is
+    /// due immediately and the fast cadence holds for a while so the
+    /// re-run or new run shows up.
+    pub fn drain_ci_action(&mut self) -> Option<(CiAction, Result<(), String>)> {
+        use std::sync::mpsc::TryRecvError;
+        let (_, rx) = self.ci_action_rx.as_ref()?;
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err("CI action worker exited".to_string()),
+        };
+        let (action, _) = self.ci_action_rx.take()?;
+        if result.is_ok() {
+            self.last_ci_action = Some(Instant::now());
+            self.last_ci_fetch.clear();
+        }
+        Some((action, result))
+    }
+
+    /// List the jobs of the log viewer's selected run, dropping the
+    /// job and log shown for the previous one.
+    pub fn request_ci_jobs(&mut self, proxy: EventLoopProxy<()>) {
+        let Some(state) = self.ci_log.as_mut() else {
+            return;
+        };
+        state.jobs = None;
+        state.job = None;
+        state.log = None;
+        state.log_rx = None;
+        let Some(run_id) = state.checks.get(state.run).and_then(|c| c.run_id) else {
+            return;
+        };
+        let backend = forge::backend(state.provider);
+        let Some((_, token)) = remote_forge(&state.remote_url)
+            .filter(|(_, t)| t.is_some() || !backend.requires_token())
+        else {
+            state.jobs = Some(Err("No API token set for this remote".to_string()));
+            return;
+        };
+        state.jobs_rx = backend.fetch_ci_jobs(token.as_deref(), &state.remote_url, run_id, proxy);
+        if state.jobs_rx.is_none() {
+            state.jobs = Some(Err(format!(
+                "{} job logs aren't supported",
+                backend.label()
+            )));
+        }
+    }
+
+    /// Show job `job_id` in the log viewer and start downloading its
+    /// log.
+    pub fn select_ci_job(&mut self, job_id: u64, proxy: EventLoopProxy<()>) {
+        let Some(state) = self.ci_log.as_mut() else {
+            return;
+        };
+        state.job = Some(job_id);
+        state.log = None;
+        state.collapsed.clear();
+        state.search_line = None;
+        let backend = forge::backend(state.provider);
+        let Some((_, token)) = remote_forge(&state.remote_url)
+            .filter(|(_, t)| t.is_some() || !backend.requires_token())
+        else {
+            state.log = Some(Err("No API token set for this remote".to_string()));
+            return;
+        };
+        let rx = backend.fetch_job_log(token.as_deref(), &state.remote_url, job_id, proxy);
+        state.log_rx = rx.map(|rx| (job_id, rx));
+    }
+
+    /// Fold finished job lists and log downloads into the log viewer.
+    /// A job list selects its first failed job; a log folds every group
+    /// except the ones around its first error. Returns that error's
+    /// row when a log landed, for the caller to scroll to.
+    pub fn drain_ci_log(&mut self, proxy: Option<&EventLoopProxy<()>>) -> Option<usize> {
+        use std::sync::mpsc::TryRecvError;
+        let state = self.ci_log.as_mut()?;
+        if let Some(rx) = state.jobs_rx.as_ref() {
+            let result = match rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(TryRecvError::Empty) => None,
+                Err(TryRecvError::Disconnected) => Some(Err("Job list worker exited".to_string())),
+            };
+            if let Some(result) = result {
+                state.jobs_rx = None;
+                let first = result.as_ref().ok().and_then(|jobs| {
+                    jobs.iter()
+                        .find(|j| j.state == CiState::Failure)
+                        .or_else(|| jobs.first())
+                        .map(|j| j.id)
+                });
+                state.jobs = Some(result);
+                if let (Some(job_id), Some(proxy)) = (first, proxy) {
+                    self.select_ci_job(job_id, proxy.clone());
+                }
+            }
+        }
+
+        let state = self.ci_log.as_mut()?;
+        let (job_id, rx) = state.log_rx.as_ref()?;
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err("Log download worker exited".to_string()),
+        };
+        let job_id = *job_id;
+        state.log_rx = None;
+        if state.job != Some(job_id) {
+            return None;
+        }
+        match result {
+            Ok(log) => {
+                let error = log.first_error;
+                let keep: HashSet<usize> = error
+                    .map(|idx| log.enclosing_groups(idx).into_iter().collect())
+                    .unwrap_or_default();
+                if error.is_some() {
+                    state.collapsed = (0..log.groups.len())
+                        .filter(|g| !keep.contains(g))
+                        .collect();
+                }
+                state.log = Some(Ok(Arc::new(log)));
+                error.and_then(|idx| state.reveal_line(idx))
+            }
+            Err(e) => {
+                state.log = Some(Err(e));
+                None
+            }
+        }
+    }
+
+    /// Rebuild the maintenance panel's health report, unless one is
+    /// already in flight. Without a proxy the report is built inline.
+    pub fn request_repo_health(&mut self, proxy: Option<&EventLoopProxy<()>>) {
+        if self.maintenance.health_rx.is_some() {
+            return;
+        }
+        match proxy {
+            Some(proxy) => {
+                self.maintenance.health_rx = Some(self.repo.repo_health_async(proxy.clone()));
+            }
+            None => {
+                self.maintenance.health =
+                    Some(self.repo.repo_health_sync().map_err(|e| format!("{e:#}")));
+            }
+        }
+    }
+
+    /// Start a housekeeping command. Returns false when one is already
+    /// running.
+    pub fn start_maintenance(&mut self, task: MaintenanceTask, proxy: EventLoopProxy<()>) -> bool {
+        if self.maintenance.op.is_some() {
+            return false;
+        }
+        self.maintenance.op = Some(MaintenanceOp {
+            task,
+            rx: self.repo.run_maintenance_async(task, proxy),
+            started: Instant::now(),
+        });
+        true
+    }
+
+    /// Fold a finished health report into the panel, and hand back a
+    /// finished housekeeping command (after logging it) so the caller
+    /// can toast and refresh.
+    pub fn drain_maintenance(&mut self) -> Option<MaintenanceOutput> {
+        use std::sync::mpsc::TryRecvError;
+        let state = &mut self.maintenance;
+        if let Some(rx) = &state.health_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    state.health = Some(result);
+                    state.health_rx = None;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    state.health = Some(Err("Health report worker exited".to_string()));
+                    state.health_rx = None;
+                }
+            }
+        }
+        let op = state.op.as_ref()?;
+        let output = match op.rx.try_recv() {
+            Ok(output) => output,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => MaintenanceOutput {
+                task: op.task,
+                success: false,
+                output: format!("{} terminated unexpectedly", op.task.label()),
+                elapsed: op.started.elapsed(),
+            },
+        };
+        state.op = None;
+        state.log.insert(0, output.clone());
+        state.log.truncate(MAINTENANCE_LOG_LIMIT);
+        Some(output)
+    }
+
     /// Drain in-flight diff-stats fetches and apply per-commit
     /// `(insertions, deletions)` onto matching `commits` entries.
     /// The worker emits in chunks (see `DIFF_STATS_CHUNK_SIZE`); we
@@ -1602,11 +2457,14 @@ impl RepoTab {
     pub fn drain_ci_receivers(&mut self) -> bool {
         use std::sync::mpsc::TryRecvError;
         let mut changed = false;
-        self.ci_receivers.retain(|rx| match rx.try_recv() {
-            Ok(result) => {
-                self.ci_results.retain(|r| r.provider != result.provider);
+        self.ci_receivers.retain(|(host, rx)| match rx.try_recv() {
+            Ok(mut result) => {
+                result.host = host.clone();
+                self.ci_results
+                    .retain(|r| (r.provider, &r.host) != (result.provider, host));
                 self.ci_results.push(result);
-                self.ci_results.sort_by_key(|r| r.provider.sort_key());
+                self.ci_results
+                    .sort_by_key(|r| (r.provider.sort_key(), r.host.clone()));
                 changed = true;
                 false
             }
@@ -1618,10 +2476,292 @@ impl RepoTab {
                 providers: self.ci_results.clone(),
             };
             self.ci_per_commit = merged.per_commit_provider_rollups();
+            // Best-effort, like the config save after a CI poll: a
+            // failed write only costs the next launch its head start.
+            let _ = ci_cache::save(self.repo.common_dir(), &self.ci_results);
         }
         changed
     }
 
+    /// Drain in-flight pull request listings, replacing the previous
+    /// listing from the same provider and host. Returns true if any
+    /// landed.
+    pub fn drain_pull_receivers(&mut self) -> bool {
+        use std::sync::mpsc::TryRecvError;
+        let mut changed = false;
+        self.pull_receivers
+            .retain(|(host, rx)| match rx.try_recv() {
+                Ok(mut result) => {
+                    result.host = host.clone();
+                    if let Ok(pulls) = &result.pulls {
+                        let open: Vec<String> = pulls.iter().map(|pr| pr.local_branch()).collect();
+                        // Best-effort: a stale ref only costs disk space.
+                        let _ = self.repo.prune_pull_heads(&result.remote, &open);
+                    }
+                    self.pull_results
+                        .retain(|r| (r.provider, &r.host) != (result.provider, host));
+                    self.pull_results.push(result);
+                    self.pull_results
+                        .sort_by_key(|r| (r.provider.sort_key(), r.host.clone()));
+                    changed = true;
+                    false
+                }
+                Err(TryRecvError::Empty) => true,
+                Err(TryRecvError::Disconnected) => false,
+            });
+        changed
+    }
+
+    /// Every fetched pull request with the remote it was listed for.
+    pub fn pull_requests(&self) -> impl Iterator<Item = (&str, &PullRequest)> {
+        self.pull_results.iter().flat_map(|r| {
+            r.pulls
+                .iter()
+                .flatten()
+                .map(move |pr| (r.remote.as_str(), pr))
+        })
+    }
+
+    /// The open pull request whose source is local branch `branch`.
+    pub fn pull_request_for_branch(&self, branch: &str) -> Option<&PullRequest> {
+        self.pull_requests()
+            .map(|(_, pr)| pr)
+            .find(|pr| pr.source_branch == branch)
+    }
+
+    /// The open pull request for the checked-out branch, with the
+    /// remote it was listed for.
+    pub fn current_pull_request(&self) -> Option<(&str, &PullRequest)> {
+        let branch = self.current_branch();
+        self.pull_requests()
+            .find(|(_, pr)| !branch.is_empty() && pr.source_branch == branch)
+    }
+
+    /// Fetch review threads for [`Self::current_pull_request`] unless
+    /// they're already loaded for its current head. `force` refetches
+    /// regardless, e.g. after posting a comment. Clears the threads when
+    /// the branch has no pull request.
+    pub fn trigger_review_fetch(&mut self, proxy: EventLoopProxy<()>, force: bool) {
+        let Some((remote, pr)) = self
+            .current_pull_request()
+            .map(|(remote, pr)| (remote.to_string(), pr.clone()))
+        else {
+            if self.review_for.take().is_some() {
+                self.review_threads.clear();
+            }
+            return;
+        };
+        let wanted = (pr.key(), pr.head_sha.clone());
+        if self.review_rx.is_some() || (!force && self.review_for.as_ref() == Some(&wanted)) {
+            return;
+        }
+        if self
+            .review_for
+            .as_ref()
+            .is_some_and(|(key, _)| *key != wanted.0)
+        {
+            self.review_threads.clear();
+        }
+        // Stamped before launching so a missing token doesn't send us
+        // back to the keychain every frame.
+        self.review_for = Some(wanted);
+        let Some(url) = self.repo.remote_url(&remote) else {
+            return;
+        };
+        let Some((_, Some(token))) = remote_forge(&url) else {
+            return;
+        };
+        self.review_rx = forge::backend(pr.provider).fetch_review_threads(
+            &token,
+            &url,
+            pr.number,
+            pr.head_sha.clone(),
+            proxy,
+        );
+    }
+
+    /// Drain an in-flight review thread fetch. A failed fetch keeps the
+    /// threads already shown and is reported to the caller.
+    pub fn drain_review_receiver(&mut self) -> Option<String> {
+        use std::sync::mpsc::TryRecvError;
+        let result = match self.review_rx.as_ref()?.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err("Review fetch worker disconnected".to_string()),
+        };
+        self.review_rx = None;
+        match result {
+            Ok(threads) => {
+                self.review_threads = threads;
+                None
+            }
+            Err(e) => Some(e),
+        }
+    }
+
+    /// Rebuild [`Self::issue_linker`] from the repository's patterns and
+    /// `origin` remote.
+    pub fn refresh_issue_linker(&mut self) {
+        let origin = self.repo.remote_url("origin");
+        self.issue_linker = IssueLinker::new(self.repo.issue_link_patterns(), origin.as_deref());
+    }
+
+    /// Fetch the forge issues the selected commit's message references,
+    /// skipping any already looked up.
+    pub fn request_selected_issues(&mut self, proxy: EventLoopProxy<()>) {
+        let Some(detail) = &self.commit_detail else {
+            return;
+        };
+        let Some((backend, url)) = self.issue_linker.forge() else {
+            return;
+        };
+        let numbers: Vec<u64> = self
+            .issue_linker
+            .links(&detail.info.full_message)
+            .iter()
+            .filter_map(|link| link.number)
+            .filter(|n| !self.issues_requested.contains(n))
+            .collect();
+        if numbers.is_empty() {
+            return;
+        }
+        let token = forge::detect(url).and_then(|(backend, host)| backend.token(&host));
+        for number in numbers {
+            self.issues_requested.insert(number);
+            if let Some(rx) = backend.fetch_issue(token.as_deref(), url, number, proxy.clone()) {
+                self.issue_receivers.push(rx);
+            }
+        }
+    }
+
+    /// Fold finished issue lookups into [`Self::issues`]. Failures just
+    /// leave the link without details.
+    pub fn drain_issue_receivers(&mut self) {
+        use std::sync::mpsc::TryRecvError;
+        let mut issues = Vec::new();
+        self.issue_receivers.retain(|rx| match rx.try_recv() {
+            Ok(result) => {
+                issues.extend(result.ok());
+                false
+            }
+            Err(TryRecvError::Empty) => true,
+            Err(TryRecvError::Disconnected) => false,
+        });
+        for issue in issues {
+            self.issues.insert(issue.number, issue);
+        }
+    }
+
+    /// Whether `origin`'s forge backend has releases.
+    pub fn releases_supported(&self) -> bool {
+        self.repo
+            .remote_url("origin")
+            .and_then(|url| forge::detect(&url))
+            .is_some_and(|(backend, _)| backend.supports_releases())
+    }
+
+    /// The release cut from `tag`, once listed.
+    pub fn release(&self, tag: &str) -> Option<&Release> {
+        self.tags.iter().find(|t| t.name == tag)?.release.as_ref()
+    }
+
+    /// Attach a release we just created to its tag.
+    pub fn record_release(&mut self, release: Release) {
+        if let Some(tag) = self.tags.iter_mut().find(|t| t.name == release.tag) {
+            tag.release = Some(release);
+        }
+    }
+
+    /// Replace `tags` with a fresh listing from git, keeping the
+    /// releases attached to tags that are still there.
+    fn set_tags(&mut self, mut tags: Vec<TagInfo>) {
+        let mut releases: HashMap<String, Release> = self
+            .tags
+            .drain(..)
+            .filter_map(|t| Some((t.name, t.release?)))
+            .collect();
+        for tag in &mut tags {
+            tag.release = releases.remove(&tag.name);
+        }
+        sort_tags(&mut tags, self.sidebar.tag_sort);
+        self.tags = tags;
+    }
+
+    /// Attach a finished listing to [`Self::tags`]. A failed fetch
+    /// keeps the previous releases, like the CI cache does.
+    pub fn drain_release_receiver(&mut self) {
+        use std::sync::mpsc::TryRecvError;
+        let result = match self.release_rx.as_ref().map(|rx| rx.try_recv()) {
+            Some(Ok(result)) => result,
+            Some(Err(TryRecvError::Disconnected)) => Err("Worker thread disconnected".to_string()),
+            Some(Err(TryRecvError::Empty)) | None => return,
+        };
+        self.release_rx = None;
+        if let Ok(releases) = result {
+            let mut by_tag: HashMap<String, Release> = releases
+                .into_iter()
+                .map(|release| (release.tag.clone(), release))
+                .collect();
+            for tag in &mut self.tags {
+                tag.release = by_tag.remove(&tag.name);
+            }
+        }
+    }
+
+    /// Add a pull request we just opened to its provider's listing so
+    /// the sidebar and graph show it before the next poll.
+    pub fn record_created_pull(&mut self, remote: String, pull: PullRequest) {
+        let host = self
+            .repo
+            .remote_url(&remote)
+            .and_then(|url| forge::detect(&url))
+            .map(|(_, host)| host)
+            .unwrap_or_default();
+        match self
+            .pull_results
+            .iter_mut()
+            .find(|r| r.provider == pull.provider && r.host == host)
+        {
+            Some(ProviderPullResult {
+                pulls: Ok(pulls), ..
+            }) => pulls.insert(0, pull),
+            Some(result) => result.pulls = Ok(vec![pull]),
+            None => {
+                self.pull_results.push(ProviderPullResult {
+                    provider: pull.provider,
+                    host,
+                    remote,
+                    pulls: Ok(vec![pull]),
+                });
+                self.pull_results
+                    .sort_by_key(|r| (r.provider.sort_key(), r.host.clone()));
+            }
+        }
+    }
+
+    /// Look up a pull request by its [`PullRequest::key`].
+    pub fn pull_request(&self, key: &str) -> Option<(&str, &PullRequest)> {
+        self.pull_requests().find(|(_, pr)| pr.key() == key)
+    }
+
+    /// CI rollup for a pull request's head: the provider-reported one
+    /// when the listing carried it, else the per-commit CI results.
+    pub fn pull_ci_state(&self, pr: &PullRequest) -> CiState {
+        if let Some(state) = pr.ci {
+            return state;
+        }
+        let Some(rollups) = self.ci_per_commit.get(&pr.head_sha) else {
+            return CiState::None;
+        };
+        let mut counts = CiCounts::default();
+        for r in rollups {
+            counts.success += r.rollup.counts.success;
+            counts.failure += r.rollup.counts.failure;
+            counts.pending += r.rollup.counts.pending;
+        }
+        counts.overall_state()
+    }
+
     /// Remote branches grouped by remote name. Within each remote, the
     /// branch list is sorted alphabetically. `origin/HEAD` and similar
     /// symref aliases are filtered out — git2 surfaces them as branches
@@ -1703,10 +2843,12 @@ mod tests {
             worktrees,
             remote_names: repo.remote_names(),
             remote_urls: HashMap::new(),
+            bundle_remotes: repo.bundle_remotes(),
             is_bare: repo.is_effectively_bare(),
             submodules: repo.submodules().unwrap_or_default(),
             stashes: repo.stash_list(),
             ahead_behind: repo.all_branches_ahead_behind(),
+            noted_commits: repo.noted_commits(),
             ref_fingerprint: crate::git::ref_fingerprint(repo.git_dir()),
             real_oids,
             worktree_repos,
@@ -1742,6 +2884,7 @@ mod tests {
             staging_path: None,
             staging_status: None,
             staging_repo_state: git2::RepositoryState::Clean,
+            staging_codeowners: None,
         });
 
         assert_eq!(tab.worktree_views[&main].status.total_files(), 1);
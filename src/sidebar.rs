@@ -1,9 +1,10 @@
 //! Branch sidebar composition.
 //!
-//! Renders the four collapsible sections (Local / Remote / Tags /
-//! Stashes) as plain aetna primitives. Toggle keys: `section:<KEY>`.
-//! Item keys: `branch:<name>`, `remote:<remote>/<branch>`,
-//! `tag:<name>`, `stash:<idx>`.
+//! Renders the collapsible sections (Local / Remote / Pull Requests /
+//! Tags / Stashes) as plain aetna primitives. Toggle keys:
+//! `section:<KEY>`. Item keys: `branch:<name>`,
+//! `remote:<remote>/<branch>`, `pr:<key>`, `tag:<name>`,
+//! `stash:<idx>`.
 //!
 //! Worktrees and submodules deliberately don't live here — see
 //! `staging::worktree_selector` for the worktree pill bar (top of
@@ -13,12 +14,20 @@
 
 use aetna_core::{El, IconName, prelude::*, widgets::sidebar::sidebar as sidebar_panel};
 
+use crate::ci::CiState;
+use crate::git::TagSort;
+use crate::pulls::{PullRequest, ReviewState};
 use crate::repo_tab::{RepoTab, SidebarSection, SidebarSelection};
+use crate::widgets::brand_icons;
 
 pub fn sidebar(tab: &RepoTab) -> El {
     let sections: Vec<El> = SidebarSection::ALL
         .iter()
         .copied()
+        // Pull Requests only appears once a forge has answered, so
+        // repos without a GitHub/GitLab remote (or token) don't grow
+        // an always-empty section.
+        .filter(|&s| s != SidebarSection::PullRequests || !tab.pull_results.is_empty())
         .map(|s| section_block(tab, s))
         .collect();
 
@@ -77,6 +86,46 @@ fn section_header(tab: &RepoTab, section: SidebarSection, collapsed: bool) -> El
         SidebarSection::Tags => Some(("new_tag", "Create tag")),
         _ => None,
     };
+    // Tags toggle between version and name order; the label names
+    // the current order.
+    if section == SidebarSection::Tags {
+        let (label, tooltip) = match tab.sidebar.tag_sort {
+            TagSort::Version => ("ver", "Sorted by version \u{2014} click to sort by name"),
+            TagSort::Name => (
+                "a\u{2013}z",
+                "Sorted by name \u{2014} click to sort by version",
+            ),
+        };
+        children.push(
+            text(label)
+                .caption()
+                .muted()
+                .key("tags:sort")
+                .focusable()
+                .cursor(Cursor::Pointer)
+                .tooltip(tooltip),
+        );
+    }
+    if section == SidebarSection::PullRequests {
+        children.push(
+            icon(IconName::RefreshCw)
+                .muted()
+                .key("pulls:refresh")
+                .focusable()
+                .cursor(Cursor::Pointer)
+                .tooltip("Refresh pull requests"),
+        );
+    }
+    if section == SidebarSection::Tags && tab.releases_supported() {
+        children.push(
+            icon(IconName::RefreshCw)
+                .muted()
+                .key("releases:refresh")
+                .focusable()
+                .cursor(Cursor::Pointer)
+                .tooltip("Refresh releases"),
+        );
+    }
     if let Some((key, tooltip)) = create {
         children.push(
             icon(IconName::Plus)
@@ -95,6 +144,7 @@ fn section_count(tab: &RepoTab, section: SidebarSection) -> usize {
     match section {
         SidebarSection::Local => tab.local_branches().len(),
         SidebarSection::Remote => remote_names(tab).len(),
+        SidebarSection::PullRequests => tab.pull_requests().count(),
         SidebarSection::Tags => tab.tags.len(),
         SidebarSection::Stashes => tab.stashes.len(),
     }
@@ -104,6 +154,7 @@ fn section_body(tab: &RepoTab, section: SidebarSection) -> Option<El> {
     match section {
         SidebarSection::Local => Some(local_body(tab)),
         SidebarSection::Remote => remote_body(tab),
+        SidebarSection::PullRequests => Some(pulls_body(tab)),
         SidebarSection::Tags => tags_body(tab),
         SidebarSection::Stashes => stashes_body(tab),
     }
@@ -128,14 +179,42 @@ fn local_body(tab: &RepoTab) -> El {
             } else {
                 IconName::GitBranch
             };
-            item_row(
+            // Branches whose merge into the checked-out one would
+            // conflict (see `RepoTab::request_conflict_risk`).
+            let risk = tab.conflict_risk_for(name).filter(|_| !is_head);
+            // The open pull request this branch is the source of.
+            let pull = tab.pull_request_for_branch(name);
+            let mut markers: Vec<El> = Vec::new();
+            if let Some(pr) = pull {
+                markers.push(text(pr.reference()).caption().text_color(tokens::INFO));
+            }
+            if let Some(paths) = risk {
+                markers.extend([
+                    icon(IconName::AlertCircle)
+                        .icon_size(12.0)
+                        .text_color(tokens::WARNING),
+                    text(paths.to_string())
+                        .caption()
+                        .text_color(tokens::WARNING),
+                ]);
+            }
+            let marker = (!markers.is_empty())
+                .then(|| row(markers).gap(tokens::SPACE_1).align(Align::Center));
+            let row_el = item_row(
                 leading,
                 name,
-                None,
+                marker,
                 is_head,
                 is_selected,
                 format!("branch:{}", name),
-            )
+            );
+            match (risk, pull) {
+                (Some(paths), _) => row_el.tooltip(format!(
+                    "Merging into {current} would conflict in {paths} file(s)"
+                )),
+                (None, Some(pr)) => row_el.tooltip(format!("{} {}", pr.reference(), pr.title)),
+                (None, None) => row_el,
+            }
         })
         .collect();
     column(rows)
@@ -161,16 +240,22 @@ fn remote_body(tab: &RepoTab) -> Option<El> {
         // for github.com, gitlab tanuki for gitlab.* — so the user can
         // tell at a glance where each remote points. Unknown hosts get
         // no decorator and just show the bare name.
+        // Bundle-file remotes (air-gapped imports) get a file glyph
+        // instead, since they have no host to brand.
+        let is_bundle = tab.bundle_remotes.contains(&remote);
         let provider_icon = tab
             .repo
             .remote_url(&remote)
-            .and_then(|url| crate::widgets::brand_icons::for_remote_url(&url));
+            .as_deref()
+            .and_then(crate::widgets::brand_icons::for_remote_url);
         // Sub-group header within Remote section. `tree_row` makes
         // the whole row a focusable click target so the caret toggles
         // collapse like the section_header above.
         let mut header: Vec<El> = vec![icon(caret).muted()];
         if let Some(brand) = provider_icon {
             header.push(icon(brand));
+        } else if is_bundle {
+            header.push(icon(IconName::FileText).muted());
         }
         header.extend([
             text(remote.clone()).caption(),
@@ -222,6 +307,93 @@ fn empty_remote_row() -> El {
     .height(Size::Fixed(28.0))
 }
 
+fn pulls_body(tab: &RepoTab) -> El {
+    let mut rows: Vec<El> = Vec::new();
+    for result in &tab.pull_results {
+        match &result.pulls {
+            Ok(pulls) => rows.extend(pulls.iter().map(|pr| pull_row(tab, pr))),
+            Err(e) => rows.push(
+                empty_pull_row(format!("{} fetch failed", result.provider.short_label()))
+                    .key(format!("pr_error:{}", result.provider.short_label()))
+                    .tooltip(e.clone()),
+            ),
+        }
+    }
+    if rows.is_empty() {
+        rows.push(empty_pull_row("No open pull requests".to_string()));
+    }
+    column(rows)
+}
+
+/// One open PR / MR: provider mark, reference and title, then review
+/// state, CI rollup and target branch. The author and source branch
+/// ride in the tooltip to keep the dense row readable.
+fn pull_row(tab: &RepoTab, pr: &PullRequest) -> El {
+    let ci = tab.pull_ci_state(pr);
+    let mut children: Vec<El> = vec![
+        icon(brand_icons::for_provider(pr.provider)).muted(),
+        text(pr.reference()).caption().muted(),
+    ];
+    let title = text(pr.title.clone()).ellipsis().width(Size::Fill(1.0));
+    children.push(if pr.draft { title.muted() } else { title });
+    let review = match pr.review {
+        ReviewState::Approved => Some((IconName::Check, tokens::SUCCESS)),
+        ReviewState::ChangesRequested => Some((IconName::X, tokens::DESTRUCTIVE)),
+        ReviewState::ReviewRequired | ReviewState::None => None,
+    };
+    if let Some((name, color)) = review {
+        children.push(icon(name).icon_size(12.0).text_color(color));
+    }
+    let ci_icon = match ci {
+        CiState::Success => Some((IconName::Check, tokens::SUCCESS)),
+        CiState::Failure => Some((IconName::AlertCircle, tokens::DESTRUCTIVE)),
+        CiState::Pending => Some((IconName::Activity, tokens::WARNING)),
+        CiState::None => None,
+    };
+    if let Some((name, color)) = ci_icon {
+        children.push(icon(name).icon_size(12.0).text_color(color));
+    }
+    children.push(
+        text(format!("\u{2192} {}", pr.target_branch))
+            .caption()
+            .muted(),
+    );
+
+    let mut details = vec![format!(
+        "{} wants to merge {} into {}",
+        pr.author, pr.source_branch, pr.target_branch
+    )];
+    if pr.draft {
+        details.push("Draft".to_string());
+    }
+    if let Some(label) = pr.review.label() {
+        details.push(label.to_string());
+    }
+    match ci {
+        CiState::Success => details.push("CI passing".to_string()),
+        CiState::Failure => details.push("CI failing".to_string()),
+        CiState::Pending => details.push("CI running".to_string()),
+        CiState::None => {}
+    }
+    tree_row(children, format!("pr:{}", pr.key())).tooltip(format!(
+        "{} {}\n{}",
+        pr.reference(),
+        pr.title,
+        details.join(" \u{00b7} ")
+    ))
+}
+
+fn empty_pull_row(label: String) -> El {
+    row([
+        icon(IconName::GitBranch).muted(),
+        text(label).caption().muted(),
+    ])
+    .padding(Sides::xy(tokens::SPACE_2, tokens::SPACE_1))
+    .gap(tokens::SPACE_1)
+    .align(Align::Center)
+    .height(Size::Fixed(28.0))
+}
+
 fn tags_body(tab: &RepoTab) -> Option<El> {
     if tab.tags.is_empty() {
         return None;
@@ -297,7 +469,7 @@ where
 fn item_row(
     leading: IconName,
     label: &str,
-    detail: Option<String>,
+    trailing: Option<El>,
     is_head: bool,
     is_selected: bool,
     key: String,
@@ -313,9 +485,9 @@ fn item_row(
         text(label.to_string())
     };
     let mut content: Vec<El> = vec![leading_icon, label_el];
-    if let Some(d) = detail {
+    if let Some(el) = trailing {
         content.push(spacer());
-        content.push(text(d).caption().muted());
+        content.push(el);
     }
     let row_el = tree_row(content, key);
 
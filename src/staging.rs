@@ -11,10 +11,16 @@
 //! - `stage_file:{path}` / `unstage_file:{path}` — per-file toggle
 //! - `discard_file:{path}` — destructive working-tree discard
 //! - `diff:{path}` — preview file's diff
+//! - `am:continue` / `am:skip` / `am:abort` — stopped `git am` controls
+//! - `toggle_ignored` — show / hide the ignored-files section
+//! - `why_ignored:{path}` — explain which rule ignores a path
+
+use std::collections::HashMap;
+use std::path::Path;
 
 use aetna_core::{El, IconName, Selection, prelude::*};
 
-use crate::git::{FileStatus, FileStatusKind, SubmoduleInfo};
+use crate::git::{CodeOwners, FileStatus, FileStatusKind, SubmoduleInfo};
 use crate::repo_tab::{RepoTab, WorktreeView};
 
 /// Worktree count above which the pill bar gives way to a dropdown
@@ -121,11 +127,16 @@ pub fn worktree_picker_overlay(tab: &RepoTab) -> Option<El> {
             } else {
                 "  "
             };
-            let label = if dirty > 0 {
+            let mut label = if dirty > 0 {
                 format!("{prefix}{label}  ·  {dirty} dirty")
             } else {
                 format!("{prefix}{label}")
             };
+            match worktree_lock(tab, path) {
+                Some(Some(reason)) => label.push_str(&format!("  ·  locked ({reason})")),
+                Some(None) => label.push_str("  ·  locked"),
+                None => {}
+            }
             Some(menu_item(label).key(format!("wt_select:option:{}", path.to_string_lossy())))
         })
         .collect();
@@ -162,10 +173,18 @@ fn worktree_pill_bar(tab: &RepoTab) -> El {
             if dirty > 0 {
                 children.push(badge(format!("{dirty}")).warning());
             }
-            Some(
+            let lock = worktree_lock(tab, path);
+            if lock.is_some() {
+                children.push(badge("locked").muted());
+            }
+            let trigger =
                 tab_trigger_content("wt_select", path.to_string_lossy(), children, is_active)
-                    .height(Size::Fixed(WORKTREE_PILL_TRIGGER_HEIGHT)),
-            )
+                    .height(Size::Fixed(WORKTREE_PILL_TRIGGER_HEIGHT));
+            Some(match lock {
+                Some(Some(reason)) => trigger.tooltip(format!("Locked: {reason}")),
+                Some(None) => trigger.tooltip("Locked"),
+                None => trigger,
+            })
         })
         .collect();
 
@@ -219,6 +238,17 @@ fn worktree_dropdown_trigger(tab: &RepoTab) -> El {
     select_trigger("wt_select", trigger_label)
 }
 
+/// Lock state for the linked worktree at `path`: `None` when unlocked
+/// (or the main worktree, which can't be locked), `Some(reason)` when
+/// locked. The reason is whatever was passed to `git worktree lock
+/// --reason`, if anything.
+fn worktree_lock<'a>(tab: &'a RepoTab, path: &Path) -> Option<Option<&'a str>> {
+    tab.worktrees
+        .iter()
+        .find(|wt| wt.is_locked && Path::new(&wt.path) == path)
+        .map(|wt| wt.lock_reason.as_deref())
+}
+
 fn dirty_count(view: &WorktreeView) -> usize {
     view.status.unstaged.len()
         + view.status.untracked.len()
@@ -261,19 +291,34 @@ fn compute_display_names(names: &[String]) -> Vec<String> {
     }
 }
 
-pub fn staging_well(view: &WorktreeView, selection: &Selection, ai_in_flight: bool) -> El {
+pub fn staging_well(
+    view: &WorktreeView,
+    selection: &Selection,
+    ai_in_flight: bool,
+    show_ignored: bool,
+    pr_offer: Option<(&str, &str)>,
+) -> El {
     let staged = &view.status.staged;
     let unstaged = &view.status.unstaged;
     let untracked = &view.status.untracked;
     let conflicted = &view.status.conflicted;
+    let owners = view.codeowners.as_ref();
+    let file_owners = Some(&view.file_owners);
 
     let mut sections: Vec<El> = Vec::new();
+    if let Some(banner) = in_progress_banner(view) {
+        sections.push(banner);
+    }
+    if let Some((branch, remote)) = pr_offer {
+        sections.push(pull_request_offer(branch, remote));
+    }
     sections.push(commit_message(view, selection, ai_in_flight));
     if !conflicted.is_empty() {
         sections.push(file_section(
             "Conflicted",
             conflicted.iter().collect::<Vec<_>>().as_slice(),
             None,
+            file_owners,
             FileRowMode::Conflicted,
             SurfaceRole::Danger,
         ));
@@ -282,13 +327,20 @@ pub fn staging_well(view: &WorktreeView, selection: &Selection, ai_in_flight: bo
         "Staged",
         staged.iter().collect::<Vec<_>>().as_slice(),
         Some(("Unstage all", "unstage_all", false)),
+        file_owners,
         FileRowMode::Staged,
         SurfaceRole::Sunken,
     ));
+    if let Some(owners) = owners
+        && !staged.is_empty()
+    {
+        sections.push(review_summary_card(owners, staged));
+    }
     sections.push(file_section(
         "Unstaged",
         unstaged.iter().collect::<Vec<_>>().as_slice(),
         Some(("Stage all", "stage_all", true)),
+        file_owners,
         FileRowMode::Unstaged,
         SurfaceRole::Sunken,
     ));
@@ -297,10 +349,26 @@ pub fn staging_well(view: &WorktreeView, selection: &Selection, ai_in_flight: bo
             "Untracked",
             untracked.iter().collect::<Vec<_>>().as_slice(),
             Some(("Track all", "stage_untracked_all", true)),
+            file_owners,
             FileRowMode::Untracked,
             SurfaceRole::Sunken,
         ));
     }
+    if show_ignored {
+        sections.push(file_section(
+            "Ignored",
+            view.status.ignored.iter().collect::<Vec<_>>().as_slice(),
+            Some(("Hide", "toggle_ignored", false)),
+            None,
+            FileRowMode::Ignored,
+            SurfaceRole::Sunken,
+        ));
+    } else {
+        sections.push(row([
+            spacer(),
+            button("Show ignored files").key("toggle_ignored").ghost(),
+        ]));
+    }
     if !view.submodules.is_empty() {
         sections.push(submodules_section(&view.submodules));
     }
@@ -319,6 +387,70 @@ pub fn staging_well(view: &WorktreeView, selection: &Selection, ai_in_flight: bo
     .height(Size::Fill(1.0))
 }
 
+/// Banner naming the operation this worktree is in the middle of. A
+/// stopped `git am` also gets Continue / Skip / Abort, since the
+/// mailbox can't be finished from the commit box the way a merge can.
+fn in_progress_banner(view: &WorktreeView) -> Option<El> {
+    let label = crate::git::repo_state_label(view.repo_state)?;
+    let mut children: Vec<El> = vec![
+        icon(IconName::AlertCircle).text_color(tokens::WARNING),
+        text(label).caption().text_color(tokens::WARNING),
+        spacer(),
+    ];
+    if matches!(
+        view.repo_state,
+        git2::RepositoryState::ApplyMailbox | git2::RepositoryState::ApplyMailboxOrRebase
+    ) {
+        let mut continue_btn = button("Continue")
+            .key("am:continue")
+            .primary()
+            .tooltip("git am --continue");
+        if !view.status.conflicted.is_empty() {
+            continue_btn = continue_btn.disabled();
+        }
+        children.extend([
+            continue_btn,
+            button("Skip")
+                .key("am:skip")
+                .ghost()
+                .tooltip("git am --skip"),
+            button("Abort")
+                .key("am:abort")
+                .destructive()
+                .tooltip("git am --abort"),
+        ]);
+    }
+    Some(
+        card([
+            card_content([row(children).align(Align::Center).gap(tokens::SPACE_2)])
+                .padding(tokens::SPACE_3),
+        ])
+        .fill(tokens::WARNING.with_alpha(40)),
+    )
+}
+
+/// Shown after pushing a branch that has no pull request yet, in place
+/// of making the user go to the forge to open one.
+fn pull_request_offer(branch: &str, remote: &str) -> El {
+    card([card_content([row([
+        icon(IconName::Upload).text_color(tokens::INFO),
+        text(format!("Pushed {branch} to {remote}"))
+            .caption()
+            .ellipsis()
+            .width(Size::Fill(1.0)),
+        button("Create Pull Request")
+            .key("pr_offer:create")
+            .primary(),
+        icon_button(IconName::X)
+            .key("pr_offer:dismiss")
+            .tooltip("Dismiss"),
+    ])
+    .align(Align::Center)
+    .gap(tokens::SPACE_2)])
+    .padding(tokens::SPACE_3)])
+    .fill(tokens::INFO.with_alpha(40))
+}
+
 fn commit_message(view: &WorktreeView, selection: &Selection, ai_in_flight: bool) -> El {
     let staged_count = view.status.staged.len();
     // Generate is gated on having something to summarize and on no
@@ -373,6 +505,7 @@ fn file_section(
     title: &str,
     files: &[&FileStatus],
     bulk_action: Option<(&str, &str, bool)>,
+    owners: Option<&HashMap<String, Vec<String>>>,
     row_mode: FileRowMode,
     role: SurfaceRole,
 ) -> El {
@@ -403,7 +536,16 @@ fn file_section(
                 .padding(Sides::xy(tokens::SPACE_4, tokens::SPACE_3)),
         ]
     } else {
-        files.iter().map(|f| file_row(f, row_mode)).collect()
+        files
+            .iter()
+            .map(|f| {
+                let file_owners = owners
+                    .and_then(|o| o.get(&f.path))
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                file_row(f, file_owners, row_mode)
+            })
+            .collect()
     };
 
     let header_fill = if is_danger {
@@ -435,16 +577,18 @@ enum FileRowMode {
     Unstaged,
     Untracked,
     Conflicted,
+    Ignored,
 }
 
-fn file_row(file: &FileStatus, mode: FileRowMode) -> El {
-    let (status_char, status_color) = match file.status {
-        FileStatusKind::New => ('A', tokens::SUCCESS),
-        FileStatusKind::Modified => ('M', tokens::WARNING),
-        FileStatusKind::Deleted => ('D', tokens::DESTRUCTIVE),
-        FileStatusKind::Renamed => ('R', tokens::INFO),
-        FileStatusKind::TypeChange => ('T', tokens::INFO),
-        FileStatusKind::Conflicted => ('!', tokens::DESTRUCTIVE),
+fn file_row(file: &FileStatus, owners: &[String], mode: FileRowMode) -> El {
+    let (status_char, status_color) = match (mode, file.status) {
+        (FileRowMode::Ignored, _) => ('I', tokens::MUTED_FOREGROUND),
+        (_, FileStatusKind::New) => ('A', tokens::SUCCESS),
+        (_, FileStatusKind::Modified) => ('M', tokens::WARNING),
+        (_, FileStatusKind::Deleted) => ('D', tokens::DESTRUCTIVE),
+        (_, FileStatusKind::Renamed) => ('R', tokens::INFO),
+        (_, FileStatusKind::TypeChange) => ('T', tokens::INFO),
+        (_, FileStatusKind::Conflicted) => ('!', tokens::DESTRUCTIVE),
     };
     let mut children = vec![
         text(status_char.to_string())
@@ -452,6 +596,9 @@ fn file_row(file: &FileStatus, mode: FileRowMode) -> El {
             .text_color(status_color),
         text(file.path.clone()).ellipsis().width(Size::Fill(1.0)),
     ];
+    if let Some(label) = owners_label(owners) {
+        children.push(text(label).caption().muted());
+    }
     match mode {
         FileRowMode::Staged => {
             children.push(
@@ -486,10 +633,17 @@ fn file_row(file: &FileStatus, mode: FileRowMode) -> El {
                     .tooltip("Resolve conflicts before staging"),
             );
         }
+        FileRowMode::Ignored => {}
     }
 
-    row(children)
-        .key(format!("diff:{}", file.path))
+    // Ignored files have no diff to preview; clicking one explains the
+    // rule that hides it instead.
+    let key = match mode {
+        FileRowMode::Ignored => format!("why_ignored:{}", file.path),
+        _ => format!("diff:{}", file.path),
+    };
+    let mut row_el = row(children)
+        .key(key)
         .focusable()
         .style_profile(StyleProfile::Surface)
         .metrics_role(MetricsRole::ListItem)
@@ -500,7 +654,76 @@ fn file_row(file: &FileStatus, mode: FileRowMode) -> El {
         .padding(tokens::SPACE_3)
         .gap(tokens::SPACE_2)
         .align(Align::Center)
-        .height(Size::Fixed(48.0))
+        .height(Size::Fixed(48.0));
+    if !owners.is_empty() {
+        row_el = row_el.tooltip(format!("Owners: {}", owners.join(", ")));
+    }
+    row_el
+}
+
+/// Compact owner label for a file row: the first owner, plus a count
+/// of the rest (full list is in the row tooltip).
+pub(crate) fn owners_label(owners: &[String]) -> Option<String> {
+    let first = owners.first()?;
+    Some(match owners.len() {
+        1 => first.clone(),
+        n => format!("{first} +{}", n - 1),
+    })
+}
+
+/// "Needs review from" over the staged set: every required
+/// `CODEOWNERS` owner with how many staged files they cover, and how
+/// many staged files nobody owns.
+fn review_summary_card(owners: &CodeOwners, staged: &[FileStatus]) -> El {
+    let summary = owners.review_summary(staged.iter().map(|f| f.path.as_str()));
+    let mut body: Vec<El> = summary
+        .owners
+        .iter()
+        .map(|(owner, files)| {
+            row([
+                text(owner.clone()).ellipsis().width(Size::Fill(1.0)),
+                text(if *files == 1 {
+                    "1 file".to_string()
+                } else {
+                    format!("{files} files")
+                })
+                .caption()
+                .muted(),
+            ])
+            .align(Align::Center)
+            .gap(tokens::SPACE_2)
+        })
+        .collect();
+    if summary.owners.is_empty() {
+        body.push(text("No required owners").caption().muted());
+    }
+    if summary.unowned > 0 {
+        body.push(
+            text(format!(
+                "{} staged file{} without an owner",
+                summary.unowned,
+                if summary.unowned == 1 { "" } else { "s" }
+            ))
+            .caption()
+            .text_color(tokens::WARNING),
+        );
+    }
+
+    card([
+        card_header([row([
+            text("Needs review from").caption().muted(),
+            badge(summary.owners.len().to_string()).muted(),
+            spacer(),
+            text(owners.source.clone()).caption().muted().mono(),
+        ])
+        .align(Align::Center)
+        .gap(tokens::SPACE_2)])
+        .padding(tokens::SPACE_3)
+        .fill(tokens::MUTED),
+        card_content(body)
+            .padding(Sides::xy(tokens::SPACE_3, tokens::SPACE_2))
+            .gap(tokens::SPACE_1),
+    ])
 }
 
 fn staging_row_button(icon_name: IconName) -> El {
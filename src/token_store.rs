@@ -18,6 +18,14 @@ const GITHUB_USER: &str = "github-token";
 /// Full username is "gitlab-token:{hostname}" e.g. "gitlab-token:gitlab.com".
 const GITLAB_PREFIX: &str = "gitlab-token:";
 
+/// Prefix for GitHub Enterprise Server token entries, e.g.
+/// "github-token:ghe.corp.example". github.com keeps the plain
+/// `GITHUB_USER` entry.
+const GITHUB_HOST_PREFIX: &str = "github-token:";
+
+/// Prefix for Gitea / Forgejo token entries, e.g. "gitea-token:codeberg.org".
+const GITEA_PREFIX: &str = "gitea-token:";
+
 /// Cached token lookups keyed by keychain username. A present entry with
 /// value `None` means "we've checked and there is no usable token" — we still
 /// return `None` without touching the keychain again.
@@ -99,6 +107,21 @@ pub fn delete_github_token() -> bool {
     cached_delete(GITHUB_USER)
 }
 
+/// Read a GitHub Enterprise Server token for a specific host.
+pub fn get_github_host_token(host: &str) -> Option<String> {
+    cached_get(&format!("{GITHUB_HOST_PREFIX}{host}"))
+}
+
+/// Store a GitHub Enterprise Server token for a specific host.
+pub fn set_github_host_token(host: &str, token: &str) -> bool {
+    cached_set(&format!("{GITHUB_HOST_PREFIX}{host}"), token)
+}
+
+/// Delete a GitHub Enterprise Server token for a specific host.
+pub fn delete_github_host_token(host: &str) -> bool {
+    cached_delete(&format!("{GITHUB_HOST_PREFIX}{host}"))
+}
+
 /// Read a GitLab token for a specific host from the keychain.
 pub fn get_gitlab_token(host: &str) -> Option<String> {
     cached_get(&format!("{GITLAB_PREFIX}{host}"))
@@ -114,6 +137,21 @@ pub fn delete_gitlab_token(host: &str) -> bool {
     cached_delete(&format!("{GITLAB_PREFIX}{host}"))
 }
 
+/// Read a Gitea / Forgejo token for a specific host from the keychain.
+pub fn get_gitea_token(host: &str) -> Option<String> {
+    cached_get(&format!("{GITEA_PREFIX}{host}"))
+}
+
+/// Store a Gitea / Forgejo token for a specific host in the keychain.
+pub fn set_gitea_token(host: &str, token: &str) -> bool {
+    cached_set(&format!("{GITEA_PREFIX}{host}"), token)
+}
+
+/// Delete a Gitea / Forgejo token for a specific host from the keychain.
+pub fn delete_gitea_token(host: &str) -> bool {
+    cached_delete(&format!("{GITEA_PREFIX}{host}"))
+}
+
 /// Whether the system keychain is available at all. Cached after first probe.
 pub fn is_available() -> bool {
     let Ok(mut cached) = BACKEND_AVAILABLE.lock() else {
@@ -20,6 +20,7 @@ use aetna_core::{
         text_area, text_input,
     },
 };
+use anyhow::Context as _;
 
 const KM_CTRL: KeyModifiers = KeyModifiers {
     shift: false,
@@ -59,22 +60,32 @@ fn apply_routed_text_area(
     text_area::apply_event(value, selection, key, event)
 }
 
+use crate::ci::{CiAction, CiCheckStatus, CiProvider, CiState};
+use crate::ci_log_view;
 use crate::commit_details;
 use crate::commit_graph;
 use crate::config::Config;
 use crate::dialogs;
 use crate::dialogs::{
-    BranchForm, CloneForm, MergeForm, MergeStrategy, PullForm, PushForm, RebaseForm, TagForm,
-    TokenForm, WorktreeForm,
+    BranchForm, BundleCreateForm, BundleImportForm, ChangelogForm, CloneForm, CreatePullForm,
+    CreateReleaseForm, FetchForm, IgnoreForm, MergeForm, MergeStrategy, NoteForm, PatchApplyForm,
+    PatchExportForm, PullForm, PushForm, RebaseForm, RepoConfigForm, ReviewCommentForm,
+    RunWorkflowForm, TagForm, TokenForm, WorktreeForm, WorktreeLockForm, WorktreeMoveForm,
 };
 use crate::diff_view;
-use crate::git::{RemoteOpResult, classify_git_error};
+use crate::forge;
+use crate::git::{
+    CONFIG_FIELDS, ChangelogGrouping, ChangelogLinks, ConfigScope, ConflictForecast,
+    IgnoreDestination, IgnoreKind, InsightsRange, IssueLinkPattern, MaintenanceTask, PatchRange,
+    RemoteOpResult, TagSort, classify_git_error,
+};
 use crate::host::HostApp;
+use crate::insights_view;
+use crate::pulls::ReviewAction;
 use crate::recent;
-use crate::repo_tab::{RepoTab, SidebarSection, TimedOp};
+use crate::repo_tab::{RepoTab, SidebarSection, SidebarSelection, TimedOp, remote_forge};
 use crate::sidebar;
 use crate::staging;
-use crate::token_store;
 use crate::welcome;
 
 /// Resolve `(outer_idx, depth)` to a `&mut RepoTab`. `depth = None`
@@ -88,50 +99,35 @@ fn resolve_tab_mut(tabs: &mut [RepoTab], idx: usize, depth: Option<usize>) -> Op
     }
 }
 
-/// One level's CI poll: checks the dynamic interval against
-/// `last_ci_fetch`, kicks off a refresh when due. Pulled to a free fn
-/// so `poll_ci_refresh` can apply it to the outermost tab and to every
-/// drilled-in level uniformly.
+/// One level's CI poll: kicks off a refresh when any forge host is
+/// due. Pulled to a free fn so `poll_ci_refresh` can apply it to the
+/// outermost tab and to every drilled-in level uniformly.
 fn poll_ci_refresh_for(
     tab: &mut RepoTab,
     config: &mut Config,
     proxy: &winit::event_loop::EventLoopProxy<()>,
     now: Instant,
 ) {
-    if !tab.ci_receivers.is_empty() {
-        return;
-    }
-    let interval = ci_poll_interval(tab, now);
-    let due = match tab.last_ci_fetch {
-        None => true,
-        Some(last) => now.duration_since(last) >= interval,
+    tab.trigger_pull_fetch(proxy.clone());
+    tab.trigger_release_fetch(proxy.clone());
+    let due = if tab.last_ci_fetch.is_empty() {
+        tab.ci_receivers.is_empty()
+    } else {
+        next_ci_wake_for(tab, now).is_some_and(|due| due <= now)
     };
     if due {
         tab.trigger_ci_fetch(config, proxy.clone());
     }
 }
 
-fn ci_poll_interval(tab: &RepoTab, now: Instant) -> Duration {
-    let any_pending = tab
-        .ci_results
-        .iter()
-        .any(|r| r.status.state == crate::ci::CiState::Pending);
-    let recently_pushed = tab
-        .last_push_time
-        .is_some_and(|t| now.duration_since(t).as_secs() < 300);
-    Duration::from_secs(if any_pending || recently_pushed {
-        15
-    } else {
-        300
-    })
-}
-
+/// Earliest next-due time among the forge hosts `tab` has polled and
+/// isn't waiting on.
 fn next_ci_wake_for(tab: &RepoTab, now: Instant) -> Option<Instant> {
-    if !tab.ci_receivers.is_empty() || tab.ci_results.is_empty() {
-        return None;
-    }
     tab.last_ci_fetch
-        .map(|last| last + ci_poll_interval(tab, now))
+        .keys()
+        .filter(|(_, host)| !tab.ci_receivers.iter().any(|(h, _)| h == host))
+        .filter_map(|(provider, host)| tab.ci_next_due(*provider, host, now))
+        .min()
 }
 
 /// Discriminator for the four per-tab async slots. Carries the
@@ -199,6 +195,12 @@ pub enum ConfirmAction {
         remote: String,
         branch: String,
     },
+    /// `git restore --source=<source> --worktree -- <path>` over a
+    /// worktree copy with local changes.
+    RestorePath {
+        source: git2::Oid,
+        path: String,
+    },
     /// Stage the parent's submodule pointer at `sm_path` to the new
     /// commit, then pop back to the parent view. Triggered by the
     /// post-commit coordination dialog when the user commits in a
@@ -206,6 +208,19 @@ pub enum ConfirmAction {
     UpdateSubmodulePin {
         sm_path: String,
     },
+    /// Housekeeping command from the maintenance panel that deletes
+    /// objects (`git prune`).
+    RunMaintenance(MaintenanceTask),
+    /// `git notes --ref <ref_name> remove <oid>`.
+    RemoveNote {
+        oid: git2::Oid,
+        ref_name: String,
+    },
+    /// Cancel in-progress CI runs from the CI context menu.
+    CancelCi {
+        provider: CiProvider,
+        runs: Vec<u64>,
+    },
 }
 
 /// Per-section right-click target. Carries the exact identity needed to
@@ -221,6 +236,8 @@ pub enum ContextTarget {
     },
     Tag(String),
     Stash(usize),
+    /// An open pull / merge request, by [`crate::pulls::PullRequest::key`].
+    PullRequest(String),
     /// A row in the commit history view.
     Commit(git2::Oid),
     /// A linked-worktree selector pill.
@@ -231,6 +248,26 @@ pub enum ContextTarget {
         staged: bool,
         untracked: bool,
     },
+    /// The header bar's repository menu (the `...` button).
+    RepoMenu,
+    /// A file row in the commit details pane, at the selected commit.
+    CommitFile {
+        oid: git2::Oid,
+        path: String,
+    },
+    /// A line of the diff viewer that can take a review comment: the
+    /// pull request head's version of `path`.
+    ReviewLine {
+        path: String,
+        old_line: Option<u32>,
+        new_line: u32,
+    },
+    /// One provider's CI runs on a commit: a commit row's CI token, or
+    /// a header badge (at HEAD).
+    Ci {
+        sha: String,
+        provider: CiProvider,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -266,8 +303,8 @@ pub enum ActiveModal {
     /// Clone-a-remote dialog. Carries the live form state so the
     /// inputs persist while the user is editing.
     Clone(CloneForm),
-    /// Manage Tokens dialog. Holds the inline-edit buffer for the
-    /// GitHub token field; persistence goes through `token_store`.
+    /// Manage Tokens dialog. Holds the inline-edit buffers for each
+    /// forge's token rows; persistence goes through the forge backends.
     Token(TokenForm),
     /// Create-branch dialog. Carries the live form state (name +
     /// checkout toggle) plus the OID this branch will be created at —
@@ -282,6 +319,13 @@ pub enum ActiveModal {
         form: TagForm,
         target: git2::Oid,
     },
+    /// Fetch-with-options picker. Lets the user choose a remote and
+    /// toggle `--prune` and notes. Reached via the caret next to the
+    /// header Fetch button.
+    FetchPicker {
+        form: FetchForm,
+        remotes: Vec<String>,
+    },
     /// Pull-with-options picker. Lets the user pick a non-tracking
     /// source and toggle `--rebase`. Reached via the caret next to
     /// the header Pull button — the bare Pull button keeps its
@@ -319,6 +363,80 @@ pub enum ActiveModal {
     /// Worktree management dialog. Reached via the more button beside
     /// the worktree selector and owns destructive removal affordances.
     Worktrees,
+    /// Lock-worktree dialog with an optional reason. Reached from the
+    /// worktree context menu or the management dialog's Lock button.
+    WorktreeLock {
+        form: WorktreeLockForm,
+    },
+    /// Move-worktree dialog. Same entry points as `WorktreeLock`.
+    WorktreeMove {
+        form: WorktreeMoveForm,
+    },
+    /// Export-as-patch dialog. Reached from the commit context menu's
+    /// "Patch Actions" group.
+    PatchExport {
+        form: PatchExportForm,
+    },
+    /// Apply-patch / mailbox dialog. Opened after the user picks a file
+    /// from the header repository menu; carries the parsed preview.
+    PatchApply {
+        form: PatchApplyForm,
+    },
+    /// Create-bundle dialog. Reached from the Remote section's context
+    /// menu.
+    BundleCreate {
+        form: BundleCreateForm,
+    },
+    /// Import-bundle dialog. Opened after picking a bundle file, or from
+    /// a bundle-backed remote's "Inspect Bundle…" item.
+    BundleImport {
+        form: BundleImportForm,
+    },
+    /// Generate-changelog dialog. Reached from a tag's context menu or
+    /// the commit menu's "Create Actions" group.
+    Changelog {
+        form: ChangelogForm,
+    },
+    /// Repository settings: common git config keys at local or global
+    /// scope, plus identity profiles. Reached from the header's
+    /// repository menu or a commit that lacks an identity.
+    RepoConfig {
+        form: RepoConfigForm,
+    },
+    /// Repository health report and housekeeping commands. Carries no
+    /// state — the report and command log live on the tab, so they
+    /// survive the modal being closed while a command runs.
+    Maintenance,
+    /// Add/Edit git note dialog. Reached from the commit context menu
+    /// and the notes card in the commit details pane.
+    Note {
+        form: NoteForm,
+    },
+    /// Add-ignore-rule dialog. Reached from an untracked file's context
+    /// menu in the staging well; carries the live pattern preview.
+    Ignore {
+        form: IgnoreForm,
+    },
+    /// Create-pull-request dialog. Reached from the local branch context
+    /// menu and the post-push banner; stays open to show the new URL.
+    CreatePullRequest {
+        form: CreatePullForm,
+    },
+    /// Reply to a review thread or comment on a diff line. Reached
+    /// from a thread's Reply button and a diff line's context menu.
+    ReviewComment {
+        form: ReviewCommentForm,
+    },
+    /// Dispatch a workflow or run a pipeline by hand. Reached from the
+    /// CI context menu.
+    RunWorkflow {
+        form: RunWorkflowForm,
+    },
+    /// Create a release on `origin` from a tag. Reached from the tag
+    /// context menu and the tag card in the commit details pane.
+    CreateRelease {
+        form: CreateReleaseForm,
+    },
 }
 
 pub struct WhisperApp {
@@ -356,6 +474,10 @@ pub struct WhisperApp {
     /// In-flight `git clone`. App-scoped (not per-tab) since the new
     /// repo doesn't have a tab yet — on success we open it as one.
     pub clone_op: Option<CloneOp>,
+    /// In-flight conflict forecast for the Merge / Rebase options
+    /// modal. Replaced (dropping the old receiver) whenever either
+    /// modal opens, so a late result can't land in the wrong one.
+    pub forecast_op: Option<std::sync::mpsc::Receiver<Result<ConflictForecast, String>>>,
     /// Left-sidebar pixel width. Initialised from `Config::sidebar_w`,
     /// re-saved when the user releases a drag of the left handle.
     pub sidebar_w: f32,
@@ -433,7 +555,7 @@ impl WhisperApp {
         let right_pane_w = config.right_pane_w;
         let (dirty_check_tx, dirty_check_rx) = std::sync::mpsc::channel();
         let now = std::time::Instant::now();
-        Self {
+        let mut app = Self {
             tabs,
             active_tab: 0,
             shortcut_bar_visible: config.shortcut_bar_visible,
@@ -446,6 +568,7 @@ impl WhisperApp {
             context_menu: None,
             proxy: None,
             clone_op: None,
+            forecast_op: None,
             sidebar_w,
             right_pane_w,
             sidebar_drag: ResizeDrag::default(),
@@ -456,7 +579,13 @@ impl WhisperApp {
             dirty_checks_in_flight: 0,
             last_status_refresh: now,
             last_ref_check: now,
+        };
+        // Restored tabs get the same identity-profile treatment as
+        // ones opened mid-session.
+        for idx in 0..app.tabs.len() {
+            app.apply_identity_profile(idx);
         }
+        app
     }
 
     /// Construct with already-built tabs. Used by `dump_bundles` which
@@ -481,6 +610,7 @@ impl WhisperApp {
             context_menu: None,
             proxy: None,
             clone_op: None,
+            forecast_op: None,
             sidebar_w,
             right_pane_w,
             sidebar_drag: ResizeDrag::default(),
@@ -603,6 +733,10 @@ impl App for WhisperApp {
                         };
                         diff_view::diff_view(tab, mode)
                     }
+                    _ if tab.ci_log.is_some() => ci_log_view::ci_log_view(tab, &self.selection),
+                    _ if tab.insights.is_some() => {
+                        insights_view::insights_view(tab, &self.selection)
+                    }
                     _ => {
                         // Snapshot loaded Gravatars for the rows
                         // we're about to render. Cheap clone (Image
@@ -621,7 +755,12 @@ impl App for WhisperApp {
                                     .collect::<std::collections::HashMap<_, _>>()
                             })
                             .unwrap_or_default();
-                        commit_graph::history_view(tab, &self.selection, avatars)
+                        commit_graph::history_view(
+                            tab,
+                            &self.selection,
+                            avatars,
+                            self.config.show_raw_author,
+                        )
                     }
                 };
 
@@ -630,9 +769,20 @@ impl App for WhisperApp {
                 // dirty count), then either the commit detail (when a
                 // commit is selected) or the staging well (default).
                 let right_upper = if tab.selected_commit.is_some() {
-                    commit_details::commit_details_pane(tab)
+                    commit_details::commit_details_pane(tab, self.config.show_raw_author)
                 } else if let Some(view) = tab.active_view() {
-                    staging::staging_well(view, &self.selection, tab.ai_op.is_some())
+                    staging::staging_well(
+                        view,
+                        &self.selection,
+                        tab.ai_op.is_some(),
+                        tab.show_ignored_files,
+                        // A listing that lands after the push may show
+                        // the pull request was opened elsewhere.
+                        tab.pr_offer
+                            .as_ref()
+                            .filter(|(branch, _)| tab.pull_request_for_branch(branch).is_none())
+                            .map(|(branch, remote)| (branch.as_str(), remote.as_str())),
+                    )
                 } else {
                     no_worktree_placeholder()
                 };
@@ -692,14 +842,27 @@ impl App for WhisperApp {
                 dialogs::clone_modal(form, &self.selection, self.clone_op.is_some())
             }
             ActiveModal::Token(form) => {
-                let github_set = token_store::get_github_token().is_some();
-                let gitlab_hosts: Vec<(String, bool)> = self
-                    .config
-                    .gitlab_hosts
+                let sections: Vec<dialogs::TokenSection> = forge::backends()
                     .iter()
-                    .map(|h| (h.clone(), token_store::get_gitlab_token(h).is_some()))
+                    .filter_map(|backend| {
+                        let info = backend.token_info()?;
+                        let hosts = backend
+                            .token_hosts(&self.config)
+                            .into_iter()
+                            .map(|h| {
+                                let configured = backend.token(&h).is_some();
+                                (h, configured)
+                            })
+                            .collect();
+                        Some(dialogs::TokenSection {
+                            id: backend.id(),
+                            label: backend.label(),
+                            info,
+                            hosts,
+                        })
+                    })
                     .collect();
-                dialogs::token_modal(form, &self.selection, github_set, &gitlab_hosts)
+                dialogs::token_modal(form, &self.selection, &sections)
             }
             ActiveModal::Branch { form, target } => {
                 let target_short = target.to_string()[..7].to_string();
@@ -709,6 +872,7 @@ impl App for WhisperApp {
                 let target_short = target.to_string()[..7].to_string();
                 dialogs::tag_modal(form, &self.selection, &target_short)
             }
+            ActiveModal::FetchPicker { form, remotes } => dialogs::fetch_modal(form, remotes),
             ActiveModal::PullPicker { form, sources } => dialogs::pull_modal(form, sources),
             ActiveModal::PushPicker { form, remotes } => {
                 dialogs::push_modal(form, &self.selection, remotes)
@@ -724,6 +888,39 @@ impl App for WhisperApp {
                 }
                 None => dialogs::worktrees_modal(&[], None),
             },
+            ActiveModal::WorktreeLock { form } => {
+                dialogs::worktree_lock_modal(form, &self.selection)
+            }
+            ActiveModal::WorktreeMove { form } => {
+                dialogs::worktree_move_modal(form, &self.selection)
+            }
+            ActiveModal::PatchExport { form } => dialogs::patch_export_modal(form, &self.selection),
+            ActiveModal::PatchApply { form } => dialogs::patch_apply_modal(form),
+            ActiveModal::BundleCreate { form } => {
+                dialogs::bundle_create_modal(form, &self.selection)
+            }
+            ActiveModal::BundleImport { form } => {
+                dialogs::bundle_import_modal(form, &self.selection)
+            }
+            ActiveModal::Changelog { form } => dialogs::changelog_modal(form, &self.selection),
+            ActiveModal::RepoConfig { form } => {
+                dialogs::repo_config_modal(form, &self.config.identity_profiles, &self.selection)
+            }
+            ActiveModal::Maintenance => {
+                dialogs::maintenance_modal(self.active_focus().map(|t| &t.maintenance))
+            }
+            ActiveModal::Note { form } => dialogs::note_modal(form, &self.selection),
+            ActiveModal::Ignore { form } => dialogs::ignore_modal(form, &self.selection),
+            ActiveModal::CreatePullRequest { form } => {
+                dialogs::create_pull_modal(form, &self.selection)
+            }
+            ActiveModal::ReviewComment { form } => {
+                dialogs::review_comment_modal(form, &self.selection)
+            }
+            ActiveModal::RunWorkflow { form } => dialogs::run_workflow_modal(form, &self.selection),
+            ActiveModal::CreateRelease { form } => {
+                dialogs::create_release_modal(form, &self.selection)
+            }
         });
         let menu_layer = self
             .context_menu
@@ -742,9 +939,9 @@ impl App for WhisperApp {
     fn on_event(&mut self, event: UiEvent) {
         // Escape unwinds the deepest active state, one step at a time:
         // (1) close any open modal, (2) clear the focused view's diff
-        // (returns center to graph), (3) clear the focused view's
-        // selected commit (returns right pane to staging well), (4) pop
-        // one level of submodule drill-down. Aetna emits an Escape
+        // (returns center to graph), (3) close the CI log or insights view, (4)
+        // clear the focused view's selected commit (returns right pane
+        // to staging well), (5) pop one level of submodule drill-down. Aetna emits an Escape
         // event when the key is pressed and no widget consumes it; our
         // text inputs don't consume Escape, so it always reaches us.
         if matches!(event.kind, UiEventKind::Escape) {
@@ -783,6 +980,14 @@ impl App for WhisperApp {
                 if cleared_diff {
                     return;
                 }
+                if focus.ci_log.is_some() {
+                    focus.ci_log = None;
+                    return;
+                }
+                if focus.insights.is_some() {
+                    focus.insights = None;
+                    return;
+                }
                 if focus.selected_commit.is_some() {
                     focus.select_commit(None);
                     return;
@@ -850,6 +1055,42 @@ impl App for WhisperApp {
                 &event,
             );
         }
+        if let Some(state) = self
+            .tabs
+            .get_mut(active_idx)
+            .and_then(|t| t.active_view_tab_mut().ci_log.as_mut())
+        {
+            apply_routed_text_input(
+                &mut state.search,
+                &mut self.selection,
+                ci_log_view::CI_LOG_SEARCH_KEY,
+                &event,
+            );
+        }
+        if let Some(state) = self
+            .tabs
+            .get_mut(active_idx)
+            .and_then(|t| t.active_view_tab_mut().insights.as_mut())
+        {
+            apply_routed_text_input(
+                &mut state.rev,
+                &mut self.selection,
+                insights_view::INSIGHTS_REF_KEY,
+                &event,
+            );
+            apply_routed_text_input(
+                &mut state.custom_since,
+                &mut self.selection,
+                insights_view::INSIGHTS_SINCE_KEY,
+                &event,
+            );
+            apply_routed_text_input(
+                &mut state.custom_until,
+                &mut self.selection,
+                insights_view::INSIGHTS_UNTIL_KEY,
+                &event,
+            );
+        }
         if let Some(view) = self
             .tabs
             .get_mut(active_idx)
@@ -867,26 +1108,28 @@ impl App for WhisperApp {
         // Modal text fields. Routed by key — only the active modal's
         // fields are present in the tree, so non-matching events are
         // ignored harmlessly.
+        let mut ignore_pattern_edited = false;
+        let mut config_scope_changed = false;
         match &mut self.active_modal {
             Some(ActiveModal::Clone(form)) => {
                 apply_routed_text_input(&mut form.url, &mut self.selection, "clone:url", &event);
                 apply_routed_text_input(&mut form.dest, &mut self.selection, "clone:dest", &event);
             }
             Some(ActiveModal::Token(form)) => {
-                apply_routed_text_input(
-                    &mut form.github_input,
-                    &mut self.selection,
-                    "token:github",
-                    &event,
-                );
-                // Each in-edit GitLab host owns a routed text input
-                // keyed `token:gitlab:input:<host>`. Iterate the form's
-                // editing set so we don't pay a per-host fold for hosts
+                // Each in-edit row owns a routed text input keyed
+                // `token:<backend>:input:<host>`. Iterate the form's
+                // editing set so we don't pay a per-host fold for rows
                 // the user isn't currently editing.
-                let host_keys: Vec<String> = form.gitlab_inputs.keys().cloned().collect();
-                for host in host_keys {
-                    let route = format!("token:gitlab:input:{host}");
-                    if let Some(buf) = form.gitlab_inputs.get_mut(&host) {
+                for (row_key, buf) in form.inputs.iter_mut() {
+                    if let Some((id, host)) = row_key.split_once(':') {
+                        let route = format!("token:{id}:input:{host}");
+                        apply_routed_text_input(buf, &mut self.selection, &route, &event);
+                    }
+                }
+                for backend in forge::backends() {
+                    if backend.token_info().is_some_and(|info| info.manual_hosts) {
+                        let route = format!("token:{}:new_host", backend.id());
+                        let buf = form.new_hosts.entry(backend.id().to_string()).or_default();
                         apply_routed_text_input(buf, &mut self.selection, &route, &event);
                     }
                 }
@@ -897,6 +1140,14 @@ impl App for WhisperApp {
             Some(ActiveModal::Tag { form, .. }) => {
                 apply_routed_text_input(&mut form.name, &mut self.selection, "tag:name", &event);
             }
+            Some(ActiveModal::FetchPicker { form, .. }) => {
+                aetna_core::widgets::radio::apply_event(
+                    &mut form.remote,
+                    &event,
+                    "fetch:remote",
+                    |raw| Some(raw.to_string()),
+                );
+            }
             Some(ActiveModal::PullPicker { form, .. }) => {
                 aetna_core::widgets::radio::apply_event(
                     &mut form.source,
@@ -947,13 +1198,246 @@ impl App for WhisperApp {
                     &event,
                 );
             }
+            Some(ActiveModal::WorktreeLock { form }) => {
+                apply_routed_text_input(
+                    &mut form.reason,
+                    &mut self.selection,
+                    "worktree_lock:reason",
+                    &event,
+                );
+            }
+            Some(ActiveModal::WorktreeMove { form }) => {
+                apply_routed_text_input(
+                    &mut form.dest,
+                    &mut self.selection,
+                    "worktree_move:dest",
+                    &event,
+                );
+            }
+            Some(ActiveModal::PatchExport { form }) => {
+                apply_routed_text_input(
+                    &mut form.base,
+                    &mut self.selection,
+                    "patch_export:base",
+                    &event,
+                );
+            }
+            Some(ActiveModal::PatchApply { form }) => {
+                aetna_core::widgets::radio::apply_event(
+                    &mut form.use_am,
+                    &event,
+                    "patch_apply:method",
+                    |raw| match raw {
+                        "am" => Some(true),
+                        "apply" => Some(false),
+                        _ => None,
+                    },
+                );
+            }
+            Some(ActiveModal::BundleCreate { form }) => {
+                if apply_routed_text_input(
+                    &mut form.revs,
+                    &mut self.selection,
+                    "bundle_create:revs",
+                    &event,
+                ) {
+                    form.plan = None;
+                }
+            }
+            Some(ActiveModal::Changelog { form }) => {
+                let by_prefix = form.by_prefix;
+                aetna_core::widgets::radio::apply_event(
+                    &mut form.by_prefix,
+                    &event,
+                    "changelog:grouping",
+                    |raw| match raw {
+                        "conventional" => Some(false),
+                        "prefix" => Some(true),
+                        _ => None,
+                    },
+                );
+                let mut edited = form.by_prefix != by_prefix;
+                for (value, key) in [
+                    (&mut form.from, "changelog:from"),
+                    (&mut form.to, "changelog:to"),
+                    (&mut form.prefixes, "changelog:prefixes"),
+                ] {
+                    edited |= apply_routed_text_input(value, &mut self.selection, key, &event);
+                }
+                if edited {
+                    form.result = None;
+                }
+            }
+            Some(ActiveModal::RepoConfig { form }) => {
+                let scope = form.scope;
+                aetna_core::widgets::radio::apply_event(
+                    &mut form.scope,
+                    &event,
+                    "repo_config:scope",
+                    |raw| match raw {
+                        "local" => Some(ConfigScope::Local),
+                        "global" => Some(ConfigScope::Global),
+                        _ => None,
+                    },
+                );
+                config_scope_changed = form.scope != scope;
+                for (field, value) in CONFIG_FIELDS.iter().zip(form.values.iter_mut()) {
+                    if field.choices.is_empty() {
+                        apply_routed_text_input(
+                            value,
+                            &mut self.selection,
+                            &format!("repo_config:field:{}", field.key),
+                            &event,
+                        );
+                    } else {
+                        aetna_core::widgets::radio::apply_event(
+                            value,
+                            &event,
+                            &format!("repo_config:choice:{}", field.key),
+                            |raw| match raw {
+                                dialogs::CONFIG_UNSET => Some(String::new()),
+                                raw => Some(raw.to_string()),
+                            },
+                        );
+                    }
+                }
+                apply_routed_text_input(
+                    &mut form.profile_name,
+                    &mut self.selection,
+                    "repo_config:profile_name",
+                    &event,
+                );
+                apply_routed_text_input(
+                    &mut form.profile_hosts,
+                    &mut self.selection,
+                    "repo_config:profile_hosts",
+                    &event,
+                );
+                apply_routed_text_area(
+                    &mut form.issue_links,
+                    &mut self.selection,
+                    "repo_config:issue_links",
+                    &event,
+                );
+            }
+            Some(ActiveModal::BundleImport { form }) => {
+                apply_routed_text_input(
+                    &mut form.remote,
+                    &mut self.selection,
+                    "bundle_import:remote",
+                    &event,
+                );
+            }
+            Some(ActiveModal::Ignore { form }) => {
+                ignore_pattern_edited = apply_routed_text_input(
+                    &mut form.pattern,
+                    &mut self.selection,
+                    "ignore:pattern",
+                    &event,
+                );
+                aetna_core::widgets::radio::apply_event(
+                    &mut form.destination,
+                    &event,
+                    "ignore:destination",
+                    |raw| match raw {
+                        "gitignore" => Some(IgnoreDestination::Gitignore),
+                        "exclude" => Some(IgnoreDestination::InfoExclude),
+                        _ => None,
+                    },
+                );
+            }
+            Some(ActiveModal::Note { form }) => {
+                apply_routed_text_input(
+                    &mut form.ref_name,
+                    &mut self.selection,
+                    "note:ref",
+                    &event,
+                );
+                apply_routed_text_area(
+                    &mut form.message,
+                    &mut self.selection,
+                    "note:message",
+                    &event,
+                );
+            }
+            Some(ActiveModal::CreatePullRequest { form }) => {
+                for (value, key) in [
+                    (&mut form.target, "create_pr:target"),
+                    (&mut form.title, "create_pr:title"),
+                    (&mut form.reviewers, "create_pr:reviewers"),
+                    (&mut form.labels, "create_pr:labels"),
+                ] {
+                    apply_routed_text_input(value, &mut self.selection, key, &event);
+                }
+                apply_routed_text_area(
+                    &mut form.body,
+                    &mut self.selection,
+                    "create_pr:body",
+                    &event,
+                );
+            }
+            Some(ActiveModal::ReviewComment { form }) => {
+                apply_routed_text_area(
+                    &mut form.body,
+                    &mut self.selection,
+                    "review_comment:body",
+                    &event,
+                );
+            }
+            Some(ActiveModal::RunWorkflow { form }) => {
+                for (value, key) in [
+                    (&mut form.workflow, "run_workflow:workflow"),
+                    (&mut form.git_ref, "run_workflow:ref"),
+                ] {
+                    apply_routed_text_input(value, &mut self.selection, key, &event);
+                }
+                apply_routed_text_area(
+                    &mut form.inputs,
+                    &mut self.selection,
+                    "run_workflow:inputs",
+                    &event,
+                );
+            }
+            Some(ActiveModal::CreateRelease { form }) => {
+                for (value, key) in [
+                    (&mut form.name, "create_release:name"),
+                    (&mut form.from, "create_release:from"),
+                ] {
+                    apply_routed_text_input(value, &mut self.selection, key, &event);
+                }
+                apply_routed_text_area(
+                    &mut form.notes,
+                    &mut self.selection,
+                    "create_release:notes",
+                    &event,
+                );
+            }
             _ => {}
         }
+        if ignore_pattern_edited {
+            self.refresh_ignore_preview();
+        }
+        if config_scope_changed {
+            self.reload_repo_config_form();
+        }
 
         if matches!(event.kind, UiEventKind::SecondaryClick) && self.handle_secondary_click(&event)
         {
             return;
         }
+        // The header repository menu is a context menu anchored at the
+        // click, so it needs the pointer position `handle_action` lacks.
+        if matches!(event.kind, UiEventKind::Click)
+            && event.route() == Some("repo_menu")
+            && let Some(pos) = event.pointer_pos()
+        {
+            self.context_menu = Some(ContextMenuState {
+                pos,
+                target: ContextTarget::RepoMenu,
+                expanded_groups: Vec::new(),
+            });
+            return;
+        }
 
         let route = event.route().map(str::to_string);
         match event.kind {
@@ -1058,6 +1542,134 @@ impl WhisperApp {
             return;
         }
 
+        // pr_offer:create / pr_offer:dismiss — the staging well's banner
+        // after pushing a branch with no pull request.
+        if let Some(action) = key.strip_prefix("pr_offer:") {
+            let offer = self.active_focus_mut().and_then(|t| t.pr_offer.take());
+            if action == "create"
+                && let Some((branch, _)) = offer
+            {
+                self.open_create_pull_request(branch);
+            }
+            return;
+        }
+
+        // review:reply|resolve|unresolve:{idx} — buttons on a review
+        // thread in the diff viewer, indexing `review_threads`. Line
+        // keys (`review:line:*`) only matter on right-click.
+        if let Some(rest) = key.strip_prefix("review:") {
+            if let Some((action, idx)) = rest.split_once(':')
+                && let Ok(idx) = idx.parse::<usize>()
+            {
+                match action {
+                    "reply" => self.open_review_reply(idx),
+                    "resolve" => self.set_review_thread_resolved(idx, true),
+                    "unresolve" => self.set_review_thread_resolved(idx, false),
+                    _ => {}
+                }
+            }
+            return;
+        }
+
+        // am:continue / am:skip / am:abort — the staging well's banner
+        // while a `git am` is stopped.
+        if let Some(action) = key.strip_prefix("am:") {
+            self.am_step(action);
+            return;
+        }
+
+        // restore_file:commit|parent|save — header buttons on a commit
+        // file's diff. The commit and path come from the selection.
+        if let Some(which) = key.strip_prefix("restore_file:") {
+            let selected = self.active_focus().and_then(|t| {
+                let oid = t.selected_commit?;
+                let path = t.active_view()?.selected_diff_file.clone()?;
+                Some((oid, path))
+            });
+            if let Some((oid, path)) = selected {
+                match which {
+                    "commit" => self.restore_file_at(oid, path, false),
+                    "parent" => self.restore_file_at(oid, path, true),
+                    "save" => self.save_file_at_revision(oid, path),
+                    _ => {}
+                }
+            }
+            return;
+        }
+
+        // ci_log:* — the CI log viewer's header, group rows and lines.
+        if let Some(action) = key.strip_prefix("ci_log:") {
+            self.handle_ci_log_route(action);
+            return;
+        }
+
+        // insights:close / insights:apply / insights:range:<key> — the
+        // insights view header.
+        if let Some(action) = key.strip_prefix("insights:") {
+            self.handle_insights_route(action);
+            return;
+        }
+
+        // why_ignored:<path> — an Ignored row in the staging well.
+        if let Some(path) = key.strip_prefix("why_ignored:") {
+            self.explain_ignored(path.to_string());
+            return;
+        }
+
+        // details:edit_note:<ref> / details:remove_note:<ref> — the
+        // notes card in the commit details pane.
+        if let Some(oid) = self.active_focus().and_then(|t| t.selected_commit) {
+            if let Some(ref_name) = key.strip_prefix("details:edit_note:") {
+                self.open_note_modal(oid, ref_name.to_string());
+                return;
+            }
+            if let Some(ref_name) = key.strip_prefix("details:remove_note:") {
+                self.confirm_remove_note(oid, ref_name.to_string());
+                return;
+            }
+        }
+
+        // details:release_open:<tag> / details:release_asset:<tag>:<idx> /
+        // details:release_create:<tag> — the tag cards in the commit
+        // details pane.
+        if let Some(tag) = key.strip_prefix("details:release_create:") {
+            self.open_create_release(tag.to_string());
+            return;
+        }
+        if let Some(tag) = key.strip_prefix("details:release_open:") {
+            self.open_release(tag);
+            return;
+        }
+        if let Some((tag, idx)) = key
+            .strip_prefix("details:release_asset:")
+            .and_then(|rest| rest.rsplit_once(':'))
+        {
+            let url = self.active_focus().and_then(|t| {
+                let release = t.release(tag)?;
+                Some(release.assets.get(idx.parse::<usize>().ok()?)?.url.clone())
+            });
+            if let Some(url) = url {
+                let _ = std::process::Command::new("xdg-open").arg(&url).spawn();
+            }
+            return;
+        }
+
+        // details:issue:<ref> — an issue chip in the commit details pane.
+        if let Some(reference) = key.strip_prefix("details:issue:") {
+            if let Some(link) = self.active_focus().and_then(|t| {
+                let detail = t.commit_detail.as_ref()?;
+                t.issue_linker
+                    .links(&detail.info.full_message)
+                    .into_iter()
+                    .find(|l| l.text == reference)
+            }) {
+                let _ = std::process::Command::new("xdg-open")
+                    .arg(&link.url)
+                    .spawn();
+            }
+            return;
+        }
+
         // section:LOCAL etc.
         if let Some(section_key) = key.strip_prefix("section:") {
             if let Some(section) = parse_section(section_key)
@@ -1155,9 +1767,12 @@ impl WhisperApp {
         // upper swap from happening with stale center-pane state.
         // A trailing `.suffix` (e.g., `commit:3.sha`, `commit:3.time`)
         // identifies a tooltip-bearing leaf inside the commit row;
-        // the leaf still routes click selection to the commit.
+        // the leaf still routes click selection to the commit. The CI
+        // strip (`commit:3.ci0`) also opens that provider's logs, and
+        // an issue chip (`commit:3.issue:#12`) its link.
         if let Some(idx_str) = key.strip_prefix("commit:") {
-            let idx_str = idx_str.split_once('.').map(|(a, _)| a).unwrap_or(idx_str);
+            let (idx_str, leaf) = idx_str.split_once('.').unwrap_or((idx_str, ""));
+            let proxy = self.proxy.clone();
             if let Ok(idx) = idx_str.parse::<usize>()
                 && let Some(tab) = self.active_focus_mut()
             {
@@ -1166,6 +1781,24 @@ impl WhisperApp {
                 if let Some(view) = tab.active_view_mut() {
                     view.selected_diff_file = None;
                 }
+                if let Some(reference) = leaf.strip_prefix("issue:")
+                    && let Some(link) = tab.commits.get(idx).and_then(|c| {
+                        tab.issue_linker
+                            .links(&c.summary)
+                            .into_iter()
+                            .find(|l| l.text == reference)
+                    })
+                {
+                    let _ = std::process::Command::new("xdg-open")
+                        .arg(&link.url)
+                        .spawn();
+                }
+                if let Some(provider_idx) = leaf.strip_prefix("ci").and_then(|i| i.parse().ok())
+                    && let (Some(oid), Some(proxy)) = (oid, proxy)
+                    && let Err(e) = tab.open_ci_log(&oid.to_string(), provider_idx, proxy)
+                {
+                    self.toasts.push(ToastSpec::info(e));
+                }
             }
             return;
         }
@@ -1243,6 +1876,7 @@ impl WhisperApp {
         // pane on its details, and pushes a scroll-to-row request so
         // off-viewport rows come into view.
         if let Some(name) = key.strip_prefix("branch:") {
+            self.select_sidebar_branch(name);
             let oid = self.active_focus().and_then(|t| {
                 t.branch_tips
                     .iter()
@@ -1269,6 +1903,27 @@ impl WhisperApp {
             self.jump_to_commit(oid, name);
             return;
         }
+        // Pull requests jump to their head when it's in the graph;
+        // checkout is right-click only.
+        if let Some(pr_key) = key.strip_prefix("pr:") {
+            let Some((reference, head)) = self.active_focus().and_then(|t| {
+                let (_, pr) = t.pull_request(pr_key)?;
+                let head = git2::Oid::from_str(&pr.head_sha)
+                    .ok()
+                    .filter(|oid| t.commits.iter().any(|c| c.id == *oid));
+                Some((pr.reference(), head))
+            }) else {
+                return;
+            };
+            if head.is_some() {
+                self.jump_to_commit(head, &reference);
+            } else {
+                self.toasts.push(ToastSpec::info(format!(
+                    "{reference}'s head isn't fetched \u{2014} right-click to check it out"
+                )));
+            }
+            return;
+        }
         if key.starts_with("stash:") {
             // Stash actions (Apply / Pop / Drop) are right-click only.
             // `StashEntry` doesn't carry the WIP commit OID, so a row
@@ -1334,8 +1989,28 @@ impl WhisperApp {
             "open_repo" => self.open_repo_dialog(),
             "new_branch" => self.open_branch_modal(),
             "new_tag" => self.open_tag_modal(),
+            "pulls:refresh" => {
+                if let Some(tab) = self.active_focus_mut() {
+                    tab.pulls_due = true;
+                }
+            }
+            "releases:refresh" => {
+                if let Some(tab) = self.active_focus_mut() {
+                    tab.releases_due = true;
+                }
+            }
+            "tags:sort" => {
+                if let Some(tab) = self.active_focus_mut() {
+                    tab.sidebar.tag_sort = match tab.sidebar.tag_sort {
+                        TagSort::Version => TagSort::Name,
+                        TagSort::Name => TagSort::Version,
+                    };
+                    crate::git::sort_tags(&mut tab.tags, tab.sidebar.tag_sort);
+                }
+            }
             "new_worktree" => self.open_worktree_modal(),
             "manage_worktrees" => self.open_worktrees_modal(),
+            "fetch_options" => self.open_fetch_picker(),
             "pull_options" => self.open_pull_picker(),
             "push_options" => self.open_push_picker(),
             "ai_generate" => self.generate_commit_message_via_ai(),
@@ -1363,6 +2038,23 @@ impl WhisperApp {
                     tab.history_search_open = true;
                 }
             }
+            "toggle_ignored" => {
+                let proxy = self.proxy.clone();
+                if let Some(tab) = self.active_focus_mut() {
+                    tab.show_ignored_files = !tab.show_ignored_files;
+                    if let Some(proxy) = proxy.as_ref() {
+                        tab.trigger_status_refresh(proxy);
+                    }
+                }
+            }
+            "details:add_note" => {
+                if let Some(tab) = self.active_focus()
+                    && let Some(oid) = tab.selected_commit
+                {
+                    let ref_name = tab.repo.default_notes_ref();
+                    self.open_note_modal(oid, ref_name);
+                }
+            }
             "details:copy_sha" => {
                 if let Some(oid) = self.active_focus().and_then(|t| t.selected_commit) {
                     let sha = oid.to_string();
@@ -1419,6 +2111,7 @@ impl WhisperApp {
             Ok(tab) => {
                 self.tabs.push(tab);
                 self.active_tab = self.tabs.len() - 1;
+                self.apply_identity_profile(self.active_tab);
                 let recent_path = recent::recent_repo_entry(&path)
                     .map(|entry| entry.path)
                     .unwrap_or_else(|| path.clone());
@@ -1445,7 +2138,17 @@ impl WhisperApp {
         let Some(route) = event.route() else {
             return false;
         };
-        let target = if let Some(idx_str) = route.strip_prefix("commit:") {
+        // PR pills in a commit row get the pull request menu rather
+        // than the commit one.
+        let pill_pull = route
+            .strip_prefix("commit:")
+            .and_then(|rest| rest.split_once(".pr:"))
+            .map(|(_, key)| key.to_string());
+        let target = if let Some(target) = self.parse_ci_context_target(route) {
+            target
+        } else if let Some(key) = pill_pull {
+            ContextTarget::PullRequest(key)
+        } else if let Some(idx_str) = route.strip_prefix("commit:") {
             let idx_str = idx_str.split_once('.').map(|(a, _)| a).unwrap_or(idx_str);
             let Ok(idx) = idx_str.parse::<usize>() else {
                 return false;
@@ -1461,12 +2164,47 @@ impl WhisperApp {
                 view.selected_diff_file = None;
             }
             ContextTarget::Commit(oid)
+        } else if let Some(path) = route.strip_prefix("commit_file:") {
+            let Some(oid) = self.active_focus().and_then(|t| t.selected_commit) else {
+                return false;
+            };
+            ContextTarget::CommitFile {
+                oid,
+                path: path.to_string(),
+            }
+        } else if let Some(lines) = route
+            .strip_prefix(diff_view::REVIEW_LINE_KEY_PREFIX)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            // `{old}:{new}` with `-` for a missing side. Deleted lines
+            // have no place in the head to comment on.
+            let Some((old, new)) = lines.split_once(':') else {
+                return false;
+            };
+            let Ok(new_line) = new.parse::<u32>() else {
+                return false;
+            };
+            let Some(path) = self
+                .active_focus()
+                .and_then(|t| t.active_view())
+                .and_then(|v| v.selected_diff_file.clone())
+            else {
+                return false;
+            };
+            ContextTarget::ReviewLine {
+                path,
+                old_line: old.parse().ok(),
+                new_line,
+            }
         } else if let Some(target) = self.parse_staging_context_target(route) {
             target
         } else {
             let Some(t) = parse_sidebar_target(route) else {
                 return false;
             };
+            if let ContextTarget::LocalBranch(name) = &t {
+                self.select_sidebar_branch(name);
+            }
             t
         };
         let Some(pos) = event.pointer_pos() else {
@@ -1480,6 +2218,35 @@ impl WhisperApp {
         true
     }
 
+    /// CI tokens in a commit row (`commit:3.ci0`, which also selects the
+    /// commit) and the header's CI badges (`ci:open:0`, at HEAD).
+    fn parse_ci_context_target(&mut self, route: &str) -> Option<ContextTarget> {
+        if let Some(idx) = route.strip_prefix("ci:open:") {
+            let tab = self.active_focus()?;
+            let provider = tab.ci_results.get(idx.parse::<usize>().ok()?)?.provider;
+            if !forge::backend(provider).supports_ci_actions() {
+                return None;
+            }
+            let sha = tab.active_view()?.head_oid?.to_string();
+            return Some(ContextTarget::Ci { sha, provider });
+        }
+        let (idx, leaf) = route.strip_prefix("commit:")?.split_once('.')?;
+        let ci_idx: usize = leaf.strip_prefix("ci")?.parse().ok()?;
+        let tab = self.active_focus_mut()?;
+        let oid = tab.commits.get(idx.parse::<usize>().ok()?)?.id;
+        let sha = oid.to_string();
+        let provider = tab.ci_per_commit.get(&sha)?.get(ci_idx)?.provider;
+        if !forge::backend(provider).supports_ci_actions() {
+            // Nothing to offer; fall back to the commit menu.
+            return None;
+        }
+        tab.select_commit(Some(oid));
+        if let Some(view) = tab.active_view_mut() {
+            view.selected_diff_file = None;
+        }
+        Some(ContextTarget::Ci { sha, provider })
+    }
+
     fn parse_staging_context_target(&self, route: &str) -> Option<ContextTarget> {
         if route == diff_view::DIFF_MODE_TOGGLE_KEY {
             return None;
@@ -1577,6 +2344,14 @@ impl WhisperApp {
             self.handle_tag_route(key);
             return true;
         }
+        // `fetch:` keys only exist inside the picker; the bare "fetch"
+        // header-button route doesn't carry the colon.
+        if matches!(self.active_modal, Some(ActiveModal::FetchPicker { .. }))
+            && key.starts_with("fetch:")
+        {
+            self.handle_fetch_route(key);
+            return true;
+        }
         // `pull:` keys (rebase toggle, execute, source radio fallthrough)
         // are only meaningful while the picker is open. The bare "pull"
         // header-button route stays intact since it doesn't carry the colon.
@@ -1622,6 +2397,94 @@ impl WhisperApp {
             self.handle_worktrees_route(key);
             return true;
         }
+        if matches!(self.active_modal, Some(ActiveModal::WorktreeLock { .. }))
+            && key == "worktree_lock:execute"
+        {
+            self.lock_worktree_from_modal();
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::WorktreeMove { .. }))
+            && key == "worktree_move:execute"
+        {
+            self.move_worktree_from_modal();
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::PatchExport { .. }))
+            && key.starts_with("patch_export:")
+        {
+            self.handle_patch_export_route(key);
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::PatchApply { .. }))
+            && key.starts_with("patch_apply:")
+        {
+            self.handle_patch_apply_route(key);
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::BundleCreate { .. }))
+            && key.starts_with("bundle_create:")
+        {
+            self.handle_bundle_create_route(key);
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::Changelog { .. }))
+            && key.starts_with("changelog:")
+        {
+            self.handle_changelog_route(key);
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::Maintenance))
+            && key.starts_with("maintenance:")
+        {
+            self.handle_maintenance_route(key);
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::RepoConfig { .. }))
+            && key.starts_with("repo_config:")
+        {
+            self.handle_repo_config_route(key);
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::BundleImport { .. }))
+            && key == "bundle_import:execute"
+        {
+            self.import_bundle_from_modal();
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::Ignore { .. })) && key == "ignore:apply" {
+            self.add_ignore_rule_from_modal();
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::Note { .. })) && key == "note:save" {
+            self.save_note_from_modal();
+            return true;
+        }
+        if matches!(
+            self.active_modal,
+            Some(ActiveModal::CreatePullRequest { .. })
+        ) && key.starts_with("create_pr:")
+        {
+            self.handle_create_pull_route(key);
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::ReviewComment { .. }))
+            && key == "review_comment:submit"
+        {
+            self.submit_review_comment();
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::RunWorkflow { .. }))
+            && key.starts_with("run_workflow:")
+        {
+            self.handle_run_workflow_route(key);
+            return true;
+        }
+        if matches!(self.active_modal, Some(ActiveModal::CreateRelease { .. }))
+            && key.starts_with("create_release:")
+        {
+            self.handle_create_release_route(key);
+            return true;
+        }
 
         match key {
             "modal:confirm:cancel" => {
@@ -1654,6 +2517,10 @@ impl WhisperApp {
                 self.active_modal = None;
                 true
             }
+            "modal:fetch:cancel" => {
+                self.active_modal = None;
+                true
+            }
             "modal:pull:cancel" => {
                 self.active_modal = None;
                 true
@@ -1678,6 +2545,66 @@ impl WhisperApp {
                 self.active_modal = None;
                 true
             }
+            "modal:worktree_lock:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:worktree_move:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:patch_export:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:patch_apply:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:bundle_create:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:bundle_import:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:changelog:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:repo_config:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:maintenance:close" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:note:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:ignore:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:create_pr:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:review_comment:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:run_workflow:cancel" => {
+                self.active_modal = None;
+                true
+            }
+            "modal:create_release:cancel" => {
+                self.active_modal = None;
+                true
+            }
             _ => false,
         }
     }
@@ -1838,6 +2765,90 @@ impl WhisperApp {
         });
     }
 
+    fn handle_fetch_route(&mut self, key: &str) {
+        match key {
+            "fetch:prune" => {
+                if let Some(ActiveModal::FetchPicker { form, .. }) = &mut self.active_modal {
+                    form.prune = !form.prune;
+                }
+            }
+            "fetch:notes" => {
+                if let Some(ActiveModal::FetchPicker { form, .. }) = &mut self.active_modal {
+                    form.include_notes = !form.include_notes;
+                }
+            }
+            "fetch:execute" => self.fetch_from_modal(),
+            // `fetch:remote:radio:<value>` is folded by `radio::apply_event`
+            // up in `on_event`; nothing to do here for those.
+            _ => {}
+        }
+    }
+
+    /// Apply the fetch-picker form. Parks the receiver on the same
+    /// `fetch_op` slot as the bare Fetch button.
+    fn fetch_from_modal(&mut self) {
+        let (remote, prune, notes) = match &self.active_modal {
+            Some(ActiveModal::FetchPicker { form, .. }) => (
+                form.remote.trim().to_string(),
+                form.prune,
+                form.include_notes,
+            ),
+            _ => return,
+        };
+        if remote.is_empty() {
+            return;
+        }
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Fetch, true) else {
+            return;
+        };
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        if tab.repo.remote_missing_fetch_refspec(&remote) {
+            let _ = tab.repo.add_default_fetch_refspec(&remote);
+        }
+        let rx = crate::git::fetch_with_options_async(wd, remote.clone(), prune, notes, proxy);
+        let label = if notes {
+            format!("from {remote} (notes)")
+        } else {
+            format!("from {remote}")
+        };
+        tab.fetch_op = Some(TimedOp::new(rx, label.clone()));
+        self.toasts
+            .push(ToastSpec::info(format!("Fetching {label}…")));
+        self.active_modal = None;
+    }
+
+    /// Open the fetch picker, pre-selecting the focused tab's default
+    /// remote. Prune starts on to match the bare Fetch button.
+    fn open_fetch_picker(&mut self) {
+        let Some(focus) = self.active_focus() else {
+            return;
+        };
+        let mut remotes = focus.repo.remote_names();
+        remotes.sort();
+        if remotes.is_empty() {
+            self.toasts.push(ToastSpec::error(
+                "No remotes configured for this repository",
+            ));
+            return;
+        }
+        let remote = focus
+            .repo
+            .default_remote()
+            .ok()
+            .filter(|r| remotes.contains(r))
+            .unwrap_or_else(|| remotes[0].clone());
+        self.active_modal = Some(ActiveModal::FetchPicker {
+            form: FetchForm {
+                remote,
+                prune: true,
+                include_notes: false,
+            },
+            remotes,
+        });
+    }
+
     fn handle_pull_route(&mut self, key: &str) {
         match key {
             "pull:rebase" => {
@@ -1966,6 +2977,11 @@ impl WhisperApp {
                     form.include_tags = !form.include_tags;
                 }
             }
+            "push:notes" => {
+                if let Some(ActiveModal::PushPicker { form, .. }) = &mut self.active_modal {
+                    form.include_notes = !form.include_notes;
+                }
+            }
             "push:execute" => self.push_from_modal(),
             // `push:remote:radio:<value>` and `push:branch` text edits are
             // folded by `radio::apply_event` / `text_input::apply_event`
@@ -1980,13 +2996,14 @@ impl WhisperApp {
     /// `push_op` slot as the bare Push button so the header progress
     /// affordance and post-op refresh don't need a separate code path.
     fn push_from_modal(&mut self) {
-        let (remote, branch, force, upstream, tags) = match &self.active_modal {
+        let (remote, branch, force, upstream, tags, notes) = match &self.active_modal {
             Some(ActiveModal::PushPicker { form, .. }) => (
                 form.remote.trim().to_string(),
                 form.branch.trim().to_string(),
                 form.force_with_lease,
                 form.set_upstream,
                 form.include_tags,
+                form.include_notes,
             ),
             _ => return,
         };
@@ -2005,6 +3022,7 @@ impl WhisperApp {
             force,
             upstream,
             tags,
+            notes,
             proxy,
         );
         let Some(tab) = self.active_focus_mut() else {
@@ -2020,6 +3038,9 @@ impl WhisperApp {
         if tags {
             suffix.push("tags");
         }
+        if notes {
+            suffix.push("notes");
+        }
         let label = if suffix.is_empty() {
             format!("{branch} → {remote}")
         } else {
@@ -2067,6 +3088,7 @@ impl WhisperApp {
                 force_with_lease: false,
                 set_upstream: false,
                 include_tags: false,
+                include_notes: false,
             },
             remotes,
         });
@@ -2090,10 +3112,25 @@ impl WhisperApp {
     }
 
     fn handle_worktrees_route(&mut self, key: &str) {
-        if let Some(idx_str) = key.strip_prefix("worktrees:remove:")
-            && let Ok(idx) = idx_str.parse::<usize>()
-        {
-            self.confirm_remove_worktree(idx);
+        if key == "worktrees:prune" {
+            self.prune_worktrees();
+            return;
+        }
+        let Some((action, idx_str)) = key
+            .strip_prefix("worktrees:")
+            .and_then(|rest| rest.split_once(':'))
+        else {
+            return;
+        };
+        let Ok(idx) = idx_str.parse::<usize>() else {
+            return;
+        };
+        match action {
+            "remove" => self.confirm_remove_worktree(idx),
+            "lock" => self.open_worktree_lock_modal(idx),
+            "unlock" => self.unlock_worktree(idx),
+            "move" => self.open_worktree_move_modal(idx),
+            _ => {}
         }
     }
 
@@ -2147,6 +3184,12 @@ impl WhisperApp {
                 .push(ToastSpec::warning("Worktree is no longer present"));
             return;
         };
+        if info.is_locked {
+            self.toasts.push(ToastSpec::warning(
+                "Worktree is locked; unlock it before removing",
+            ));
+            return;
+        }
         let path = PathBuf::from(&info.path);
         let dirty_files = tab
             .worktree_views
@@ -2177,47 +3220,171 @@ impl WhisperApp {
     }
 
     fn confirm_remove_worktree_by_key(&mut self, key: &str) {
-        let Some(tab) = self.active_focus() else {
-            return;
-        };
-        let Some(idx) = tab
-            .worktrees
-            .iter()
-            .position(|wt| wt.name == key || wt.path == key)
-        else {
+        if let Some(idx) = self.worktree_index_by_key(key) {
+            self.confirm_remove_worktree(idx);
+        }
+    }
+
+    /// Resolve a context-menu worktree key (name or path) to its index
+    /// in the focused tab's `worktrees`. Toasts and returns `None` when
+    /// the worktree has vanished since the menu opened.
+    fn worktree_index_by_key(&mut self, key: &str) -> Option<usize> {
+        let idx = self.active_focus().and_then(|tab| {
+            tab.worktrees
+                .iter()
+                .position(|wt| wt.name == key || wt.path == key)
+        });
+        if idx.is_none() {
             self.toasts
                 .push(ToastSpec::warning("Worktree is no longer present"));
+        }
+        idx
+    }
+
+    fn open_worktree_lock_modal(&mut self, idx: usize) {
+        let Some(info) = self
+            .active_focus()
+            .and_then(|tab| tab.worktrees.get(idx).cloned())
+        else {
             return;
         };
-        self.confirm_remove_worktree(idx);
+        self.active_modal = Some(ActiveModal::WorktreeLock {
+            form: WorktreeLockForm {
+                name: info.name,
+                path: info.path,
+                reason: String::new(),
+            },
+        });
     }
 
-    fn select_worktree_by_key(&mut self, key: &str) {
-        let Some(tab) = self.active_focus_mut() else {
+    fn open_worktree_move_modal(&mut self, idx: usize) {
+        let Some(info) = self
+            .active_focus()
+            .and_then(|tab| tab.worktrees.get(idx).cloned())
+        else {
             return;
         };
-        let path = tab
-            .worktrees
-            .iter()
-            .find(|wt| wt.name == key || wt.path == key)
-            .map(|wt| std::path::PathBuf::from(&wt.path))
-            .or_else(|| {
-                let path = std::path::PathBuf::from(key);
-                path.exists().then_some(path)
-            });
-        let Some(path) = path else {
-            self.toasts
-                .push(ToastSpec::warning("Worktree is no longer present"));
+        if info.is_locked {
+            self.toasts.push(ToastSpec::warning(format!(
+                "Worktree '{}' is locked — unlock it before moving",
+                info.name
+            )));
             return;
-        };
-        tab.select_worktree(path);
-        tab.select_commit(None);
-        if let Some(view) = tab.active_view_mut() {
-            view.selected_diff_file = None;
         }
+        self.active_modal = Some(ActiveModal::WorktreeMove {
+            form: WorktreeMoveForm {
+                name: info.name,
+                dest: info.path.clone(),
+                path: info.path,
+            },
+        });
     }
 
-    fn jump_to_worktree_branch(&mut self, key: &str) {
+    fn lock_worktree_from_modal(&mut self) {
+        let Some(ActiveModal::WorktreeLock { form }) = &self.active_modal else {
+            return;
+        };
+        let (name, path) = (form.name.clone(), form.path.clone());
+        let reason = Some(form.reason.trim().to_string()).filter(|r| !r.is_empty());
+        self.active_modal = None;
+        let target = path.clone();
+        self.spawn_worktree_op(
+            &path,
+            format!("lock worktree {name}"),
+            format!("Locking worktree {name}…"),
+            move |dir, proxy| crate::git::lock_worktree_async(dir, target, reason, proxy),
+        );
+    }
+
+    fn unlock_worktree(&mut self, idx: usize) {
+        let Some(info) = self
+            .active_focus()
+            .and_then(|tab| tab.worktrees.get(idx).cloned())
+        else {
+            return;
+        };
+        let target = info.path.clone();
+        self.spawn_worktree_op(
+            &info.path,
+            format!("unlock worktree {}", info.name),
+            format!("Unlocking worktree {}…", info.name),
+            move |dir, proxy| crate::git::unlock_worktree_async(dir, target, proxy),
+        );
+    }
+
+    fn move_worktree_from_modal(&mut self) {
+        let Some(ActiveModal::WorktreeMove { form }) = &self.active_modal else {
+            return;
+        };
+        let (name, path) = (form.name.clone(), form.path.clone());
+        let dest = form.dest.trim().to_string();
+        if dest.is_empty() || dest == path {
+            self.toasts
+                .push(ToastSpec::warning("Pick a new path for the worktree"));
+            return;
+        }
+        self.active_modal = None;
+        let target = path.clone();
+        self.spawn_worktree_op(
+            &path,
+            format!("move worktree {name}"),
+            format!("Moving worktree {name} to {dest}…"),
+            move |dir, proxy| crate::git::move_worktree_async(dir, target, dest, proxy),
+        );
+    }
+
+    /// `git worktree prune` — drops admin dirs for worktrees whose
+    /// directory is gone. Locked entries survive by design, so the
+    /// toast says so when any stale worktree is also locked.
+    fn prune_worktrees(&mut self) {
+        let locked_stale = self.active_focus().map_or(0, |tab| {
+            tab.worktrees
+                .iter()
+                .filter(|wt| wt.is_stale && wt.is_locked)
+                .count()
+        });
+        if matches!(self.active_modal, Some(ActiveModal::Worktrees)) {
+            self.active_modal = None;
+        }
+        let toast = if locked_stale > 0 {
+            format!("Pruning stale worktrees ({locked_stale} locked, kept)…")
+        } else {
+            "Pruning stale worktrees…".to_string()
+        };
+        self.spawn_worktree_op(
+            Path::new(""),
+            "prune worktrees".to_string(),
+            toast,
+            crate::git::prune_worktrees_async,
+        );
+    }
+
+    fn select_worktree_by_key(&mut self, key: &str) {
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        let path = tab
+            .worktrees
+            .iter()
+            .find(|wt| wt.name == key || wt.path == key)
+            .map(|wt| std::path::PathBuf::from(&wt.path))
+            .or_else(|| {
+                let path = std::path::PathBuf::from(key);
+                path.exists().then_some(path)
+            });
+        let Some(path) = path else {
+            self.toasts
+                .push(ToastSpec::warning("Worktree is no longer present"));
+            return;
+        };
+        tab.select_worktree(path);
+        tab.select_commit(None);
+        if let Some(view) = tab.active_view_mut() {
+            view.selected_diff_file = None;
+        }
+    }
+
+    fn jump_to_worktree_branch(&mut self, key: &str) {
         let Some(tab) = self.active_focus() else {
             return;
         };
@@ -2231,7 +3398,13 @@ impl WhisperApp {
                 .push(ToastSpec::warning("Worktree is no longer present"));
             return;
         };
-        if worktree.branch.is_empty() {
+        let Some(branch) = worktree.branch.filter(|b| !b.is_empty()) else {
+            self.toasts.push(ToastSpec::warning(
+                "Worktree directory is missing; prune it from Worktrees",
+            ));
+            return;
+        };
+        if branch == "detached" {
             self.toasts.push(ToastSpec::warning(
                 "Worktree is detached and has no branch to jump to",
             ));
@@ -2240,10 +3413,10 @@ impl WhisperApp {
         let oid = tab
             .branch_tips
             .iter()
-            .find(|tip| !tip.is_remote && tip.name == worktree.branch)
+            .find(|tip| !tip.is_remote && tip.name == branch)
             .map(|tip| tip.oid)
             .or(worktree.head_oid);
-        self.jump_to_commit(oid, &worktree.branch);
+        self.jump_to_commit(oid, &branch);
     }
 
     /// Apply the worktree-creation form: spawn `git worktree add` (with
@@ -2296,14 +3469,46 @@ impl WhisperApp {
     }
 
     fn remove_worktree(&mut self, name: String, path: String, force: bool) {
+        let toast = format!(
+            "Removing worktree {name}{}…",
+            if force { " with --force" } else { "" }
+        );
+        let target = path.clone();
+        self.spawn_worktree_op(
+            Path::new(&path),
+            format!("remove worktree {name}"),
+            toast,
+            move |dir, proxy| {
+                if force {
+                    crate::git::remove_worktree_force_async(dir, target, proxy)
+                } else {
+                    crate::git::remove_worktree_async(dir, target, proxy)
+                }
+            },
+        );
+    }
+
+    /// Run a `git worktree …` subcommand on the focused tab's
+    /// mutation slot. The command runs from a worktree other than
+    /// `target_path` (git refuses to remove or move the worktree it is
+    /// running in), falling back to the common dir when the target is
+    /// the only checkout left.
+    fn spawn_worktree_op(
+        &mut self,
+        target_path: &Path,
+        label: String,
+        toast: String,
+        spawn: impl FnOnce(
+            PathBuf,
+            winit::event_loop::EventLoopProxy<()>,
+        ) -> std::sync::mpsc::Receiver<RemoteOpResult>,
+    ) {
         let Some(proxy) = self.proxy.clone() else {
-            self.toasts.push(ToastSpec::error(
-                "Remove worktree unavailable: event loop proxy missing",
-            ));
+            self.toasts.push(ToastSpec::error(format!(
+                "{label} unavailable: event loop proxy missing"
+            )));
             return;
         };
-        let target_path = PathBuf::from(&path);
-        let label = format!("remove worktree {name}");
         let Some(tab) = self.active_focus_mut() else {
             return;
         };
@@ -2316,7 +3521,7 @@ impl WhisperApp {
         let command_dir = tab
             .worktree_order
             .iter()
-            .find(|p| **p != target_path && p.is_dir())
+            .find(|p| p.as_path() != target_path && p.is_dir())
             .cloned()
             .unwrap_or_else(|| {
                 let wd = tab.repo.git_command_dir();
@@ -2326,480 +3531,1125 @@ impl WhisperApp {
                     wd
                 }
             });
-        let rx = if force {
-            crate::git::remove_worktree_force_async(command_dir, path, proxy)
-        } else {
-            crate::git::remove_worktree_async(command_dir, path, proxy)
-        };
-        tab.mutation_op = Some(TimedOp::new(rx, label.clone()));
-        self.toasts.push(ToastSpec::info(format!(
-            "Removing worktree {name}{}…",
-            if force { " with --force" } else { "" }
-        )));
+        let rx = spawn(command_dir, proxy);
+        tab.mutation_op = Some(TimedOp::new(rx, label));
+        self.toasts.push(ToastSpec::info(toast));
     }
 
-    /// Open the create-worktree modal. Pre-fills the path with a
-    /// sibling-of-repo default named after the source ref, and the
-    /// source with the current branch when checked out.
-    fn open_worktree_modal(&mut self) {
-        let Some(focus) = self.active_focus() else {
+    /// Check out a pull / merge request as its local `pr/N` / `mr/N`
+    /// branch — in place, or in a new worktree beside the repo named
+    /// after that branch, like the create-worktree modal's default.
+    fn checkout_pull_request(&mut self, key: &str, in_worktree: bool) {
+        let Some((remote, pr)) = self
+            .active_focus()
+            .and_then(|t| t.pull_request(key))
+            .map(|(remote, pr)| (remote.to_string(), pr.clone()))
+        else {
+            self.toasts
+                .push(ToastSpec::warning("Pull request is no longer listed"));
             return;
         };
-        let parent = focus
-            .repo
-            .git_command_dir()
-            .parent()
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| std::path::PathBuf::from("."));
-        let source = focus.current_branch().to_string();
-        let path_default = if source.is_empty() {
-            String::new()
-        } else {
-            // Sanitize slashes so a branch like "feature/x" doesn't
-            // accidentally create a nested directory under parent.
-            let dir_name = source.replace('/', "-");
-            parent.join(dir_name).to_string_lossy().to_string()
-        };
-        self.active_modal = Some(ActiveModal::Worktree {
-            form: WorktreeForm {
-                path: path_default,
-                source,
-                detached: false,
-                init_submodules: false,
-            },
+        let branch = pr.local_branch();
+        let worktree_path = in_worktree.then(|| {
+            self.active_focus()
+                .and_then(|t| t.repo.git_command_dir().parent().map(|p| p.to_path_buf()))
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(branch.replace('/', "-"))
+                .to_string_lossy()
+                .to_string()
         });
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Mutation, false) else {
+            return;
+        };
+        let rx = crate::git::checkout_pull_request_async(
+            wd,
+            remote,
+            pr.head_ref(),
+            branch.clone(),
+            worktree_path.clone(),
+            proxy,
+        );
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        let label = match &worktree_path {
+            Some(path) => format!("{} into worktree {path} ({branch})", pr.reference()),
+            None => format!("{} as {branch}", pr.reference()),
+        };
+        tab.mutation_op = Some(TimedOp::new(rx, format!("checkout {label}")));
+        self.toasts
+            .push(ToastSpec::info(format!("Checking out {label}\u{2026}")));
     }
 
-    fn open_worktree_modal_for_source(&mut self, source: String) {
-        let Some(focus) = self.active_focus() else {
+    /// Open the Create Pull Request modal for local `branch` on the
+    /// remote it tracks. Title and description start from the commits
+    /// not yet on the remote's default branch.
+    fn open_create_pull_request(&mut self, branch: String) {
+        let Some(tab) = self.active_focus() else {
             return;
         };
-        let parent = focus
+        if let Some(pr) = tab.pull_request_for_branch(&branch) {
+            let msg = format!("{branch} already has {} open", pr.reference());
+            self.toasts.push(ToastSpec::info(msg));
+            return;
+        }
+        let Some(remote) = tab.repo.branch_remote(&branch) else {
+            self.toasts
+                .push(ToastSpec::warning("No remote to open a pull request on"));
+            return;
+        };
+        let Some((provider, _)) = tab
             .repo
-            .git_command_dir()
-            .parent()
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| std::path::PathBuf::from("."));
-        let dir_name = source.replace('/', "-");
-        let init_submodules = focus
-            .active_view()
-            .is_some_and(|view| !view.submodules.is_empty());
-        self.active_modal = Some(ActiveModal::Worktree {
-            form: WorktreeForm {
-                path: parent.join(dir_name).to_string_lossy().to_string(),
-                source,
-                detached: false,
-                init_submodules,
-            },
-        });
-    }
-
-    fn open_worktree_modal_at_commit(&mut self, oid: git2::Oid) {
-        let Some(focus) = self.active_focus() else {
+            .remote_url(&remote)
+            .and_then(|u| remote_forge(&u))
+            .filter(|(p, _)| forge::backend(*p).supports_pull_requests())
+        else {
+            self.toasts.push(ToastSpec::warning(format!(
+                "{remote} isn't a GitHub or GitLab remote"
+            )));
             return;
         };
-        let short = oid.to_string()[..7].to_string();
-        let parent = focus
+        let target = tab
             .repo
-            .git_command_dir()
-            .parent()
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| std::path::PathBuf::from("."));
-        let init_submodules = focus
-            .active_view()
-            .is_some_and(|view| !view.submodules.is_empty());
-        self.active_modal = Some(ActiveModal::Worktree {
-            form: WorktreeForm {
-                path: parent
-                    .join(format!("wt-{short}"))
-                    .to_string_lossy()
-                    .to_string(),
-                source: short,
-                detached: true,
-                init_submodules,
+            .remote_default_branch(&remote)
+            .unwrap_or_else(|| "main".to_string());
+        let draft = tab
+            .repo
+            .pull_request_draft(&format!("{remote}/{target}"), &branch)
+            .unwrap_or_default();
+        self.active_modal = Some(ActiveModal::CreatePullRequest {
+            form: CreatePullForm {
+                provider,
+                remote,
+                source_branch: branch,
+                target,
+                title: draft.title,
+                body: draft.body,
+                draft: false,
+                reviewers: String::new(),
+                labels: String::new(),
+                generating: false,
+                submitting: false,
+                error: None,
+                created: None,
+                warnings: Vec::new(),
             },
         });
+        // Drop a generation still running for an earlier modal.
+        if let Some(tab) = self.active_focus_mut() {
+            tab.pr_draft_rx = None;
+        }
     }
 
-    fn open_worktrees_modal(&mut self) {
-        match self.active_focus() {
-            Some(tab) if !tab.worktrees.is_empty() => {
-                self.active_modal = Some(ActiveModal::Worktrees);
+    fn handle_create_pull_route(&mut self, key: &str) {
+        let Some(ActiveModal::CreatePullRequest { form }) = &mut self.active_modal else {
+            return;
+        };
+        match key {
+            "create_pr:draft" => form.draft = !form.draft,
+            "create_pr:ai" => {
+                if form.generating || form.submitting {
+                    return;
+                }
+                let Some(proxy) = self.proxy.clone() else {
+                    return;
+                };
+                let provider = crate::ai::AiProvider::from_config(&self.config.ai_provider);
+                let (branch, target) = (form.source_branch.clone(), form.target.trim().to_string());
+                let base = format!("{}/{target}", form.remote);
+                form.generating = true;
+                form.error = None;
+                let Some(tab) = self.active_focus_mut() else {
+                    return;
+                };
+                let workdir = tab.active_repo().git_command_dir();
+                tab.pr_draft_rx = Some(crate::ai::spawn_generate_pull_request_async(
+                    workdir, branch, base, target, provider, proxy,
+                ));
+            }
+            "create_pr:submit" => {
+                if !form.generating && !form.submitting {
+                    self.submit_create_pull_request();
+                }
             }
-            Some(_) => {
-                self.toasts
-                    .push(ToastSpec::info("No linked worktrees to manage"));
+            "create_pr:open" => {
+                if let Some(pr) = &form.created {
+                    let _ = std::process::Command::new("xdg-open").arg(&pr.url).spawn();
+                }
             }
-            None => {}
+            "create_pr:copy" => {
+                let Some(url) = form.created.as_ref().map(|pr| pr.url.clone()) else {
+                    return;
+                };
+                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(url)) {
+                    Ok(()) => self.toasts.push(ToastSpec::success("Copied URL")),
+                    Err(e) => self
+                        .toasts
+                        .push(ToastSpec::error(format!("Clipboard: {e}"))),
+                }
+            }
+            _ => {}
         }
     }
 
-    /// Spawn the AI-commit-message worker for the focused tab's
-    /// active worktree. Bails with a toast if no proxy / no active
-    /// worktree / generation already running. The Generate button
-    /// in the staging well disables on those same conditions, so
-    /// the toasts here cover only edge cases (e.g. losing the proxy).
-    fn generate_commit_message_via_ai(&mut self) {
+    /// Post the Create Pull Request modal's form through the GitHub or
+    /// GitLab client for its remote.
+    fn submit_create_pull_request(&mut self) {
+        let Some(ActiveModal::CreatePullRequest { form }) = &self.active_modal else {
+            return;
+        };
         let Some(proxy) = self.proxy.clone() else {
-            self.toasts.push(ToastSpec::error(
-                "AI generate unavailable: event loop proxy missing",
-            ));
             return;
         };
-        let provider = crate::ai::AiProvider::from_config(&self.config.ai_provider);
-        let Some(tab) = self.active_focus_mut() else {
+        let request = crate::pulls::NewPullRequest {
+            source_branch: form.source_branch.clone(),
+            target_branch: form.target.trim().to_string(),
+            title: form.title.trim().to_string(),
+            body: form.body.trim().to_string(),
+            draft: form.draft,
+            reviewers: crate::pulls::parse_name_list(&form.reviewers),
+            labels: crate::pulls::parse_name_list(&form.labels),
+        };
+        let remote = form.remote.clone();
+        let url = self.active_focus().and_then(|t| t.repo.remote_url(&remote));
+        let rx = match url
+            .as_deref()
+            .and_then(|url| Some((url, remote_forge(url)?)))
+        {
+            Some((url, (provider, Some(token)))) => forge::backend(provider)
+                .create_pull_request(&token, url, request, proxy)
+                .ok_or_else(|| format!("{remote} doesn't support pull requests")),
+            Some((_, (_, None))) => Err(format!(
+                "No API token set for {remote}. Add one under Manage tokens."
+            )),
+            None => Err(format!("{remote} isn't a GitHub or GitLab remote")),
+        };
+        let Some(ActiveModal::CreatePullRequest { form }) = &mut self.active_modal else {
             return;
         };
-        if tab.ai_op.is_some() {
-            self.toasts
-                .push(ToastSpec::info("AI generate already in progress"));
+        match rx {
+            Ok(rx) => {
+                form.submitting = true;
+                form.error = None;
+                if let Some(tab) = self.active_focus_mut() {
+                    tab.pr_create_rx = Some((remote, rx));
+                }
+            }
+            Err(e) => form.error = Some(e),
+        }
+    }
+
+    /// Open the Create Release modal for `tag` on `origin`, with notes
+    /// generated from the changelog since the previous tag.
+    fn open_create_release(&mut self, tag: String) {
+        let Some(tab) = self.active_focus() else {
+            return;
+        };
+        if let Some(release) = tab.release(&tag) {
+            let msg = format!("{tag} is already released as {}", release.title());
+            self.toasts.push(ToastSpec::info(msg));
             return;
         }
-        let Some(view) = tab.active_view() else {
-            self.toasts.push(ToastSpec::warning(
-                "No active worktree to generate a message for",
-            ));
+        let Some(oid) = tab.tags.iter().find(|t| t.name == tag).map(|t| t.oid) else {
             return;
         };
-        let target_path = view.path.clone();
-        let workdir = view.repo.git_command_dir();
-        let branch = tab.current_branch().to_string();
-        let rx = crate::ai::spawn_generate_async(workdir, branch, provider, proxy);
-        tab.ai_op = Some(crate::repo_tab::AiOp {
-            rx,
-            started: std::time::Instant::now(),
-            target_path,
+        let Some((provider, _)) = tab
+            .repo
+            .remote_url("origin")
+            .and_then(|u| remote_forge(&u))
+            .filter(|(p, _)| forge::backend(*p).supports_releases())
+        else {
+            self.toasts
+                .push(ToastSpec::warning("origin isn't a GitHub or GitLab remote"));
+            return;
+        };
+        let from = tab.active_repo().previous_tag(oid).unwrap_or_default();
+        self.active_modal = Some(ActiveModal::CreateRelease {
+            form: CreateReleaseForm {
+                provider,
+                tag,
+                target_sha: oid.to_string(),
+                from,
+                name: String::new(),
+                notes: String::new(),
+                draft: false,
+                prerelease: false,
+                submitting: false,
+                error: None,
+                created: None,
+            },
         });
-        self.toasts
-            .push(ToastSpec::info("Generating commit message…"));
+        self.handle_create_release_route("create_release:generate");
     }
 
-    /// Routes for the Open-repo modal opened by the tab-bar `+`. Each
-    /// path closes the modal first so a new modal (Clone, Error from
-    /// `open_repo_path`) can replace it cleanly without a one-frame gap
-    /// of two dialogs being live.
-    fn handle_open_repo_route(&mut self, key: &str) {
-        match key {
-            "modal:open_repo:cancel" => {
-                self.active_modal = None;
-            }
-            "modal:open_repo:browse" => {
-                self.active_modal = None;
-                self.open_repo_dialog();
-            }
-            "modal:open_repo:clone" => {
-                let form = CloneForm {
-                    dest: std::env::var("HOME").unwrap_or_default(),
-                    ..Default::default()
-                };
-                self.active_modal = Some(ActiveModal::Clone(form));
-            }
-            other => {
-                if let Some(idx_str) = other.strip_prefix("modal:open_repo:recent:")
-                    && let Ok(idx) = idx_str.parse::<usize>()
-                    && let Some(path) = self.recent_repo_path(idx)
-                {
-                    self.active_modal = None;
-                    self.open_repo_path(path);
-                }
-            }
-        }
-    }
-
-    fn handle_clone_route(&mut self, key: &str) {
-        match key {
-            "clone:bare" => {
-                if let Some(ActiveModal::Clone(form)) = &mut self.active_modal {
-                    form.bare = !form.bare;
-                }
-            }
-            "clone:browse" => {
-                let picked = rfd::FileDialog::new()
-                    .set_title("Choose destination")
-                    .pick_folder();
-                if let Some(p) = picked
-                    && let Some(ActiveModal::Clone(form)) = &mut self.active_modal
-                {
-                    form.dest = p.to_string_lossy().to_string();
-                }
-            }
-            "clone:start" => self.start_clone(),
-            _ => {}
+    /// Open `tag`'s release page on the forge.
+    fn open_release(&mut self, tag: &str) {
+        let url = self
+            .active_focus()
+            .and_then(|t| t.release(tag))
+            .map(|r| r.url.clone());
+        if let Some(url) = url {
+            let _ = std::process::Command::new("xdg-open").arg(&url).spawn();
         }
     }
 
-    fn handle_token_route(&mut self, key: &str) {
+    fn handle_create_release_route(&mut self, key: &str) {
+        let Some(ActiveModal::CreateRelease { form }) = &mut self.active_modal else {
+            return;
+        };
         match key {
-            "token:github:edit" => {
-                if let Some(ActiveModal::Token(form)) = &mut self.active_modal {
-                    form.editing_github = true;
-                    form.github_input.clear();
-                }
-            }
-            "token:github:cancel" => {
-                if let Some(ActiveModal::Token(form)) = &mut self.active_modal {
-                    form.editing_github = false;
-                    form.github_input.clear();
-                }
-            }
-            "token:github:save" => {
-                let value = if let Some(ActiveModal::Token(form)) = &self.active_modal {
-                    form.github_input.trim().to_string()
+            "create_release:draft" => form.draft = !form.draft,
+            "create_release:prerelease" => form.prerelease = !form.prerelease,
+            "create_release:generate" => {
+                let (from, to) = (form.from.trim().to_string(), form.tag.clone());
+                let grouping = if self.config.changelog_by_prefix {
+                    ChangelogGrouping::Prefixes(self.config.changelog_prefixes.clone())
                 } else {
+                    ChangelogGrouping::Conventional
+                };
+                let Some(tab) = self.active_focus() else {
                     return;
                 };
-                if value.is_empty() {
-                    self.toasts
-                        .push(ToastSpec::warning("Token is empty — leaving unchanged"));
+                let notes = tab
+                    .active_repo()
+                    .changelog(&from, &to, &grouping)
+                    .map(|log| log.to_markdown(changelog_links(tab).as_ref()))
+                    .map_err(|e| format!("Generate notes: {e:#}"));
+                let Some(ActiveModal::CreateRelease { form }) = &mut self.active_modal else {
                     return;
-                }
-                if token_store::set_github_token(&value) {
-                    self.toasts.push(ToastSpec::success("GitHub token saved"));
-                    if let Some(ActiveModal::Token(form)) = &mut self.active_modal {
-                        form.editing_github = false;
-                        form.github_input.clear();
+                };
+                match notes {
+                    Ok(notes) => {
+                        form.notes = notes;
+                        form.error = None;
                     }
-                } else {
-                    self.toasts
-                        .push(ToastSpec::error("Couldn't write to keychain"));
+                    Err(e) => form.error = Some(e),
                 }
             }
-            "token:github:clear" => {
-                if token_store::delete_github_token() {
-                    self.toasts.push(ToastSpec::success("GitHub token cleared"));
-                } else {
-                    self.toasts
-                        .push(ToastSpec::error("Couldn't clear keychain entry"));
+            "create_release:submit" => {
+                if !form.submitting {
+                    self.submit_create_release();
                 }
             }
-            other => {
-                if let Some(host) = other.strip_prefix("token:gitlab:edit:") {
-                    if let Some(ActiveModal::Token(form)) = &mut self.active_modal {
-                        form.gitlab_inputs.insert(host.to_string(), String::new());
-                    }
-                } else if let Some(host) = other.strip_prefix("token:gitlab:cancel:") {
-                    if let Some(ActiveModal::Token(form)) = &mut self.active_modal {
-                        form.gitlab_inputs.remove(host);
-                    }
-                } else if let Some(host) = other.strip_prefix("token:gitlab:save:") {
-                    let value = if let Some(ActiveModal::Token(form)) = &self.active_modal {
-                        form.gitlab_inputs
-                            .get(host)
-                            .map(|s| s.trim().to_string())
-                            .unwrap_or_default()
-                    } else {
-                        return;
-                    };
-                    if value.is_empty() {
-                        self.toasts
-                            .push(ToastSpec::warning("Token is empty — leaving unchanged"));
-                        return;
-                    }
-                    if token_store::set_gitlab_token(host, &value) {
-                        self.toasts
-                            .push(ToastSpec::success(format!("GitLab token saved ({host})")));
-                        if let Some(ActiveModal::Token(form)) = &mut self.active_modal {
-                            form.gitlab_inputs.remove(host);
-                        }
-                    } else {
-                        self.toasts
-                            .push(ToastSpec::error("Couldn't write to keychain"));
-                    }
-                } else if let Some(host) = other.strip_prefix("token:gitlab:clear:") {
-                    if token_store::delete_gitlab_token(host) {
-                        self.toasts
-                            .push(ToastSpec::success(format!("GitLab token cleared ({host})")));
-                    } else {
-                        self.toasts
-                            .push(ToastSpec::error("Couldn't clear keychain entry"));
-                    }
+            "create_release:open" => {
+                if let Some(release) = &form.created {
+                    let _ = std::process::Command::new("xdg-open")
+                        .arg(&release.url)
+                        .spawn();
                 }
             }
+            _ => {}
         }
     }
 
-    /// Spawn a `git clone` thread for the URL/dest in the current
-    /// Clone modal. Validates the inputs first, surfaces errors as
-    /// toasts (rather than blocking the modal flow), and parks the
-    /// receiver on `clone_op` for `poll_async_ops` to drain.
-    fn start_clone(&mut self) {
+    /// Post the Create Release modal's form through the GitHub or
+    /// GitLab client for `origin`.
+    fn submit_create_release(&mut self) {
+        let Some(ActiveModal::CreateRelease { form }) = &self.active_modal else {
+            return;
+        };
         let Some(proxy) = self.proxy.clone() else {
-            self.toasts.push(ToastSpec::error(
-                "Clone unavailable: event loop proxy missing",
-            ));
             return;
         };
-        if self.clone_op.is_some() {
-            self.toasts
-                .push(ToastSpec::info("A clone is already in progress"));
+        let request = crate::releases::NewRelease {
+            tag: form.tag.clone(),
+            target_sha: form.target_sha.clone(),
+            name: form.name.trim().to_string(),
+            notes: form.notes.trim().to_string(),
+            draft: form.draft,
+            prerelease: form.prerelease,
+        };
+        let tag = form.tag.clone();
+        let url = self
+            .active_focus()
+            .and_then(|t| t.repo.remote_url("origin"));
+        let rx = match url
+            .as_deref()
+            .and_then(|url| Some((url, remote_forge(url)?)))
+        {
+            Some((url, (provider, Some(token)))) => forge::backend(provider)
+                .create_release(&token, url, request, proxy)
+                .ok_or_else(|| "origin doesn't support releases".to_string()),
+            Some((_, (_, None))) => {
+                Err("No API token set for origin. Add one under Manage tokens.".to_string())
+            }
+            None => Err("origin isn't a GitHub or GitLab remote".to_string()),
+        };
+        let Some(ActiveModal::CreateRelease { form }) = &mut self.active_modal else {
             return;
+        };
+        match rx {
+            Ok(rx) => {
+                form.submitting = true;
+                form.error = None;
+                if let Some(tab) = self.active_focus_mut() {
+                    tab.release_create_rx = Some((tag, rx));
+                }
+            }
+            Err(e) => form.error = Some(e),
         }
-        let Some(ActiveModal::Clone(form)) = &self.active_modal else {
+    }
+
+    /// Run IDs of `provider`'s runs on `sha` whose state passes `keep`.
+    fn ci_run_ids(&self, sha: &str, provider: CiProvider, keep: fn(CiState) -> bool) -> Vec<u64> {
+        self.active_focus()
+            .map(|t| ci_checks(t, sha, provider))
+            .unwrap_or_default()
+            .iter()
+            .filter(|c| keep(c.state))
+            .filter_map(|c| c.run_id)
+            .collect()
+    }
+
+    /// Send `action` through the focused tab; the result toasts from
+    /// [`Self::drain_ci_actions`].
+    fn start_ci_action(&mut self, provider: CiProvider, action: CiAction) {
+        let Some(proxy) = self.proxy.clone() else {
             return;
         };
-        let url = form.url.trim().to_string();
-        let dest = form.dest.trim().to_string();
-        let bare = form.bare;
-        if url.is_empty() {
-            self.toasts
-                .push(ToastSpec::warning("Repository URL is required"));
+        let Some(tab) = self.active_focus_mut() else {
             return;
+        };
+        if let Err(e) = tab.start_ci_action(provider, action, proxy) {
+            self.toasts.push(ToastSpec::error(e));
         }
-        if dest.is_empty() {
-            self.toasts
-                .push(ToastSpec::warning("Destination directory is required"));
+    }
+
+    /// Open the Run Workflow / Run Pipeline modal on the current
+    /// branch, with the checkout's first dispatchable workflow picked.
+    fn open_run_workflow(&mut self, provider: CiProvider) {
+        let Some(tab) = self.active_focus() else {
             return;
-        }
-        let dest_path = std::path::PathBuf::from(&dest);
-        let dest_label = dest_path.display().to_string();
-        let rx = crate::git::clone_async(url.clone(), dest_path, bare, proxy);
-        self.clone_op = Some(CloneOp {
-            rx,
-            started: std::time::Instant::now(),
-            dest_label: dest_label.clone(),
-        });
-        self.toasts.push(ToastSpec::info(format!(
-            "Cloning into {dest_label}\u{2026}"
-        )));
+        };
+        let workflows = tab
+            .repo
+            .workdir()
+            .map(|workdir| forge::backend(provider).dispatchable_workflows(workdir))
+            .unwrap_or_default();
+        let (workflow, inputs) = workflows
+            .first()
+            .map(|(name, inputs)| (name.clone(), seed_inputs(inputs)))
+            .unwrap_or_default();
+        let form = RunWorkflowForm {
+            provider,
+            workflows,
+            workflow,
+            git_ref: tab.current_branch().to_string(),
+            inputs,
+            error: None,
+        };
+        self.active_modal = Some(ActiveModal::RunWorkflow { form });
     }
 
-    fn handle_context_action(&mut self, action: &str) {
-        let Some(mut state) = self.context_menu.take() else {
+    fn handle_run_workflow_route(&mut self, key: &str) {
+        let Some(ActiveModal::RunWorkflow { form }) = &mut self.active_modal else {
             return;
         };
-        if let Some(group) = action.strip_prefix("toggle_group:") {
-            if let Some(idx) = state.expanded_groups.iter().position(|g| g == group) {
-                state.expanded_groups.remove(idx);
-            } else {
-                state.expanded_groups.push(group.to_string());
+        if let Some(i) = key
+            .strip_prefix("run_workflow:pick:")
+            .and_then(|i| i.parse::<usize>().ok())
+        {
+            if let Some((name, inputs)) = form.workflows.get(i) {
+                form.workflow = name.clone();
+                form.inputs = seed_inputs(inputs);
             }
-            self.context_menu = Some(state);
             return;
         }
-        if self.handle_dynamic_commit_context_action(action, state.target.clone()) {
+        if key != "run_workflow:submit" {
             return;
         }
-        match (action, state.target) {
-            ("checkout", ContextTarget::LocalBranch(name)) => {
-                self.run_op("Checkout", |t| t.repo.checkout_branch(&name));
-            }
-            ("create_worktree", ContextTarget::LocalBranch(name)) => {
-                self.open_worktree_modal_for_source(name);
-            }
-            ("fetch_remote", ContextTarget::Remote(remote)) => {
-                self.fetch_remote(remote);
-            }
-            ("delete_remote", ContextTarget::Remote(remote)) => {
-                self.active_modal = Some(ActiveModal::Confirm {
-                    title: "Delete remote".to_string(),
-                    body: format!("Delete remote '{remote}' from this repository?"),
-                    ok_label: "Delete".to_string(),
-                    destructive: true,
-                    action: ConfirmAction::DeleteRemote(remote),
-                });
-            }
-            ("fetch_all", ContextTarget::RemoteSection) => {
-                self.fetch_all();
-            }
-            ("checkout", ContextTarget::RemoteBranch { remote, branch }) => {
-                self.run_op("Checkout", |t| {
-                    t.repo.checkout_remote_branch(&remote, &branch)
-                });
-            }
-            ("merge", ContextTarget::LocalBranch(name)) => {
-                self.merge_branch(name);
-            }
-            ("merge", ContextTarget::RemoteBranch { remote, branch }) => {
-                self.merge_branch(format!("{remote}/{branch}"));
-            }
-            ("merge_options", ContextTarget::LocalBranch(name)) => {
-                self.open_merge_options(name);
+        let inputs = match crate::ci::parse_inputs(&form.inputs) {
+            Ok(inputs) => inputs,
+            Err(e) => {
+                form.error = Some(e);
+                return;
             }
-            ("merge_options", ContextTarget::RemoteBranch { remote, branch }) => {
-                self.open_merge_options(format!("{remote}/{branch}"));
+        };
+        let provider = form.provider;
+        let action = CiAction::Trigger {
+            workflow: form.workflow.trim().to_string(),
+            git_ref: form.git_ref.trim().to_string(),
+            inputs,
+        };
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+        let result = self
+            .active_focus_mut()
+            .ok_or_else(|| "No repository open".to_string())
+            .and_then(|tab| tab.start_ci_action(provider, action, proxy));
+        match result {
+            Ok(()) => self.active_modal = None,
+            Err(e) => {
+                if let Some(ActiveModal::RunWorkflow { form }) = &mut self.active_modal {
+                    form.error = Some(e);
+                }
             }
-            ("rebase", ContextTarget::LocalBranch(name)) => {
-                self.rebase_onto(name);
+        }
+    }
+
+    /// Open the review comment modal as a reply to thread `idx` of the
+    /// focused tab's `review_threads`.
+    fn open_review_reply(&mut self, idx: usize) {
+        let Some(thread) = self.active_focus().and_then(|t| t.review_threads.get(idx)) else {
+            return;
+        };
+        let author = thread
+            .comments
+            .first()
+            .map(|c| c.author.as_str())
+            .unwrap_or("unknown");
+        let form = ReviewCommentForm {
+            context: format!("Replying to {author} on {}", thread.path),
+            action: ReviewAction::Reply {
+                thread_id: thread.id.clone(),
+                reply_to: thread.reply_to.clone(),
+                body: String::new(),
+            },
+            body: String::new(),
+        };
+        self.active_modal = Some(ActiveModal::ReviewComment { form });
+    }
+
+    /// Open the review comment modal for a new thread on a line of the
+    /// pull request's head commit.
+    fn open_review_line_comment(&mut self, path: String, old_line: Option<u32>, new_line: u32) {
+        let Some((_, pr)) = self.active_focus().and_then(|t| t.current_pull_request()) else {
+            return;
+        };
+        let form = ReviewCommentForm {
+            context: format!("{path} line {new_line} on {}", pr.reference()),
+            action: ReviewAction::Comment {
+                path,
+                old_line,
+                new_line: Some(new_line),
+                commit_sha: pr.head_sha.clone(),
+                body: String::new(),
+            },
+            body: String::new(),
+        };
+        self.active_modal = Some(ActiveModal::ReviewComment { form });
+    }
+
+    fn submit_review_comment(&mut self) {
+        let Some(ActiveModal::ReviewComment { form }) = &self.active_modal else {
+            return;
+        };
+        let body_text = form.body.trim().to_string();
+        if body_text.is_empty() {
+            return;
+        }
+        let mut action = form.action.clone();
+        match &mut action {
+            ReviewAction::Reply { body, .. } | ReviewAction::Comment { body, .. } => {
+                *body = body_text;
             }
-            ("rebase", ContextTarget::RemoteBranch { remote, branch }) => {
-                self.rebase_onto(format!("{remote}/{branch}"));
+            ReviewAction::SetResolved { .. } => {}
+        }
+        match self.start_review_action(action) {
+            Ok(()) => {
+                self.active_modal = None;
+                self.toasts.push(ToastSpec::info("Posting comment\u{2026}"));
             }
-            ("rebase_options", ContextTarget::LocalBranch(name)) => {
-                self.open_rebase_options(name);
+            Err(e) => self.toasts.push(ToastSpec::error(e)),
+        }
+    }
+
+    fn set_review_thread_resolved(&mut self, idx: usize, resolved: bool) {
+        let Some(thread_id) = self
+            .active_focus()
+            .and_then(|t| t.review_threads.get(idx))
+            .map(|t| t.id.clone())
+        else {
+            return;
+        };
+        let action = ReviewAction::SetResolved {
+            thread_id,
+            resolved,
+        };
+        if let Err(e) = self.start_review_action(action) {
+            self.toasts.push(ToastSpec::error(e));
+        }
+    }
+
+    /// Post `action` to the checked-out branch's pull request through
+    /// the forge client for its remote. The result is drained in
+    /// [`Self::poll_pull_request_ops_at`], which refetches the threads.
+    fn start_review_action(&mut self, action: ReviewAction) -> Result<(), String> {
+        let proxy = self.proxy.clone().ok_or("No event loop")?;
+        let tab = self.active_focus_mut().ok_or("No repository open")?;
+        if tab.review_action_rx.is_some() {
+            return Err("Still posting the previous review change".to_string());
+        }
+        let (remote, number) = tab
+            .current_pull_request()
+            .map(|(remote, pr)| (remote.to_string(), pr.number))
+            .ok_or("This branch has no open pull request")?;
+        let url = tab
+            .repo
+            .remote_url(&remote)
+            .ok_or_else(|| format!("{remote} has no URL"))?;
+        let rx = match remote_forge(&url) {
+            Some((provider, Some(token))) => {
+                forge::backend(provider).review_action(&token, &url, number, action, proxy)
             }
-            ("rebase_options", ContextTarget::RemoteBranch { remote, branch }) => {
-                self.open_rebase_options(format!("{remote}/{branch}"));
+            Some((_, None)) => {
+                return Err(format!(
+                    "No API token set for {remote}. Add one under Manage tokens."
+                ));
             }
-            ("delete", ContextTarget::LocalBranch(name)) => {
-                self.active_modal = Some(ActiveModal::Confirm {
-                    title: "Delete branch".to_string(),
-                    body: format!("Delete local branch '{name}' permanently?"),
-                    ok_label: "Delete".to_string(),
-                    destructive: true,
-                    action: ConfirmAction::DeleteBranch(name),
-                });
-            }
-            ("delete", ContextTarget::RemoteBranch { remote, branch }) => {
-                self.active_modal = Some(ActiveModal::Confirm {
-                    title: "Delete remote branch".to_string(),
-                    body: format!(
-                        "Delete branch '{branch}' from remote '{remote}'? This cannot be undone."
-                    ),
-                    ok_label: "Delete".to_string(),
-                    destructive: true,
-                    action: ConfirmAction::DeleteRemoteBranch { remote, branch },
-                });
-            }
-            ("delete", ContextTarget::Tag(name)) => {
-                self.active_modal = Some(ActiveModal::Confirm {
-                    title: "Delete tag".to_string(),
-                    body: format!("Delete tag '{name}' permanently?"),
-                    ok_label: "Delete".to_string(),
-                    destructive: true,
-                    action: ConfirmAction::DeleteTag(name),
-                });
+            None => None,
+        };
+        tab.review_action_rx =
+            Some(rx.ok_or_else(|| format!("{remote} isn't a GitHub or GitLab remote"))?);
+        Ok(())
+    }
+
+    /// Open the create-worktree modal. Pre-fills the path with a
+    /// sibling-of-repo default named after the source ref, and the
+    /// source with the current branch when checked out.
+    fn open_worktree_modal(&mut self) {
+        let Some(focus) = self.active_focus() else {
+            return;
+        };
+        let parent = focus
+            .repo
+            .git_command_dir()
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| std::path::PathBuf::from("."));
+        let source = focus.current_branch().to_string();
+        let path_default = if source.is_empty() {
+            String::new()
+        } else {
+            // Sanitize slashes so a branch like "feature/x" doesn't
+            // accidentally create a nested directory under parent.
+            let dir_name = source.replace('/', "-");
+            parent.join(dir_name).to_string_lossy().to_string()
+        };
+        self.active_modal = Some(ActiveModal::Worktree {
+            form: WorktreeForm {
+                path: path_default,
+                source,
+                detached: false,
+                init_submodules: false,
+            },
+        });
+    }
+
+    fn open_worktree_modal_for_source(&mut self, source: String) {
+        let Some(focus) = self.active_focus() else {
+            return;
+        };
+        let parent = focus
+            .repo
+            .git_command_dir()
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| std::path::PathBuf::from("."));
+        let dir_name = source.replace('/', "-");
+        let init_submodules = focus
+            .active_view()
+            .is_some_and(|view| !view.submodules.is_empty());
+        self.active_modal = Some(ActiveModal::Worktree {
+            form: WorktreeForm {
+                path: parent.join(dir_name).to_string_lossy().to_string(),
+                source,
+                detached: false,
+                init_submodules,
+            },
+        });
+    }
+
+    fn open_worktree_modal_at_commit(&mut self, oid: git2::Oid) {
+        let Some(focus) = self.active_focus() else {
+            return;
+        };
+        let short = oid.to_string()[..7].to_string();
+        let parent = focus
+            .repo
+            .git_command_dir()
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| std::path::PathBuf::from("."));
+        let init_submodules = focus
+            .active_view()
+            .is_some_and(|view| !view.submodules.is_empty());
+        self.active_modal = Some(ActiveModal::Worktree {
+            form: WorktreeForm {
+                path: parent
+                    .join(format!("wt-{short}"))
+                    .to_string_lossy()
+                    .to_string(),
+                source: short,
+                detached: true,
+                init_submodules,
+            },
+        });
+    }
+
+    fn open_worktrees_modal(&mut self) {
+        match self.active_focus() {
+            Some(tab) if !tab.worktrees.is_empty() => {
+                self.active_modal = Some(ActiveModal::Worktrees);
             }
-            ("apply", ContextTarget::Stash(idx)) => {
-                self.stash_apply(idx);
+            Some(_) => {
+                self.toasts
+                    .push(ToastSpec::info("No linked worktrees to manage"));
             }
-            ("pop", ContextTarget::Stash(idx)) => {
-                self.stash_pop(idx);
+            None => {}
+        }
+    }
+
+    /// Spawn the AI-commit-message worker for the focused tab's
+    /// active worktree. Bails with a toast if no proxy / no active
+    /// worktree / generation already running. The Generate button
+    /// in the staging well disables on those same conditions, so
+    /// the toasts here cover only edge cases (e.g. losing the proxy).
+    fn generate_commit_message_via_ai(&mut self) {
+        let Some(proxy) = self.proxy.clone() else {
+            self.toasts.push(ToastSpec::error(
+                "AI generate unavailable: event loop proxy missing",
+            ));
+            return;
+        };
+        let provider = crate::ai::AiProvider::from_config(&self.config.ai_provider);
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        if tab.ai_op.is_some() {
+            self.toasts
+                .push(ToastSpec::info("AI generate already in progress"));
+            return;
+        }
+        let Some(view) = tab.active_view() else {
+            self.toasts.push(ToastSpec::warning(
+                "No active worktree to generate a message for",
+            ));
+            return;
+        };
+        let target_path = view.path.clone();
+        let workdir = view.repo.git_command_dir();
+        let branch = tab.current_branch().to_string();
+        let rx = crate::ai::spawn_generate_async(workdir, branch, provider, proxy);
+        tab.ai_op = Some(crate::repo_tab::AiOp {
+            rx,
+            started: std::time::Instant::now(),
+            target_path,
+        });
+        self.toasts
+            .push(ToastSpec::info("Generating commit message…"));
+    }
+
+    /// Routes for the Open-repo modal opened by the tab-bar `+`. Each
+    /// path closes the modal first so a new modal (Clone, Error from
+    /// `open_repo_path`) can replace it cleanly without a one-frame gap
+    /// of two dialogs being live.
+    fn handle_open_repo_route(&mut self, key: &str) {
+        match key {
+            "modal:open_repo:cancel" => {
+                self.active_modal = None;
             }
-            ("drop", ContextTarget::Stash(idx)) => {
-                self.active_modal = Some(ActiveModal::Confirm {
-                    title: "Drop stash".to_string(),
-                    body: format!("Drop stash @{{{idx}}} permanently?"),
-                    ok_label: "Drop".to_string(),
-                    destructive: true,
-                    action: ConfirmAction::DropStash(idx),
-                });
+            "modal:open_repo:browse" => {
+                self.active_modal = None;
+                self.open_repo_dialog();
             }
-            ("switch_worktree", ContextTarget::Worktree(key)) => {
-                self.select_worktree_by_key(&key);
+            "modal:open_repo:clone" => {
+                let form = CloneForm {
+                    dest: std::env::var("HOME").unwrap_or_default(),
+                    ..Default::default()
+                };
+                self.active_modal = Some(ActiveModal::Clone(form));
             }
-            ("jump_to_worktree", ContextTarget::Worktree(key)) => {
-                self.jump_to_worktree_branch(&key);
+            other => {
+                if let Some(idx_str) = other.strip_prefix("modal:open_repo:recent:")
+                    && let Ok(idx) = idx_str.parse::<usize>()
+                    && let Some(path) = self.recent_repo_path(idx)
+                {
+                    self.active_modal = None;
+                    self.open_repo_path(path);
+                }
             }
-            ("remove_worktree", ContextTarget::Worktree(key)) => {
-                self.confirm_remove_worktree_by_key(&key);
+        }
+    }
+
+    fn handle_clone_route(&mut self, key: &str) {
+        match key {
+            "clone:bare" => {
+                if let Some(ActiveModal::Clone(form)) = &mut self.active_modal {
+                    form.bare = !form.bare;
+                }
             }
-            ("stage", ContextTarget::StagingFile { path, .. }) => {
-                self.run_op("Stage", move |t| t.active_repo().stage_file(&path));
+            "clone:browse" => {
+                let picked = rfd::FileDialog::new()
+                    .set_title("Choose destination")
+                    .pick_folder();
+                if let Some(p) = picked
+                    && let Some(ActiveModal::Clone(form)) = &mut self.active_modal
+                {
+                    form.dest = p.to_string_lossy().to_string();
+                }
             }
-            ("unstage", ContextTarget::StagingFile { path, .. }) => {
-                self.run_op("Unstage", move |t| t.active_repo().unstage_file(&path));
+            "clone:start" => self.start_clone(),
+            _ => {}
+        }
+    }
+
+    fn handle_token_route(&mut self, key: &str) {
+        let Some((backend, rest)) = key
+            .strip_prefix("token:")
+            .and_then(|rest| rest.split_once(':'))
+            .and_then(|(id, rest)| Some((forge::by_id(id)?, rest)))
+        else {
+            return;
+        };
+        let id = backend.id();
+        if rest == "add" {
+            let Some(ActiveModal::Token(form)) = &mut self.active_modal else {
+                return;
+            };
+            let host = form.new_hosts.remove(id).unwrap_or_default();
+            if backend.add_token_host(&mut self.config, &host) {
+                self.persist_config();
+                // Re-poll so commits on the new host pick up CI dots.
+                for tab in &mut self.tabs {
+                    tab.last_ci_fetch.clear();
+                }
             }
-            ("view_diff", ContextTarget::StagingFile { path, .. }) => {
-                if let Some(view) = self.active_focus_mut().and_then(|t| t.active_view_mut()) {
-                    view.selected_diff_file = Some(path);
+            return;
+        }
+        let Some((verb, host)) = rest.split_once(':') else {
+            return;
+        };
+        let row = format!("{id}:{host}");
+        let label = backend.label();
+        match verb {
+            "edit" => {
+                if let Some(ActiveModal::Token(form)) = &mut self.active_modal {
+                    form.inputs.insert(row, String::new());
                 }
             }
-            ("discard", ContextTarget::StagingFile { path, .. }) => {
-                self.confirm_discard_file(path);
+            "cancel" => {
+                if let Some(ActiveModal::Token(form)) = &mut self.active_modal {
+                    form.inputs.remove(&row);
+                }
             }
-            ("copy_sha", ContextTarget::Commit(oid)) => {
-                let sha = oid.to_string();
-                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(sha.clone())) {
+            "save" => {
+                let value = if let Some(ActiveModal::Token(form)) = &self.active_modal {
+                    form.inputs
+                        .get(&row)
+                        .map(|s| s.trim().to_string())
+                        .unwrap_or_default()
+                } else {
+                    return;
+                };
+                if value.is_empty() {
+                    self.toasts
+                        .push(ToastSpec::warning("Token is empty — leaving unchanged"));
+                    return;
+                }
+                if backend.set_token(host, &value) {
+                    self.toasts
+                        .push(ToastSpec::success(format!("{label} token saved ({host})")));
+                    if let Some(ActiveModal::Token(form)) = &mut self.active_modal {
+                        form.inputs.remove(&row);
+                    }
+                    for tab in &mut self.tabs {
+                        tab.pulls_due = true;
+                        tab.releases_due = true;
+                    }
+                } else {
+                    self.toasts
+                        .push(ToastSpec::error("Couldn't write to keychain"));
+                }
+            }
+            "clear" => {
+                if backend.delete_token(host) {
+                    self.toasts.push(ToastSpec::success(format!(
+                        "{label} token cleared ({host})"
+                    )));
+                } else {
+                    self.toasts
+                        .push(ToastSpec::error("Couldn't clear keychain entry"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Spawn a `git clone` thread for the URL/dest in the current
+    /// Clone modal. Validates the inputs first, surfaces errors as
+    /// toasts (rather than blocking the modal flow), and parks the
+    /// receiver on `clone_op` for `poll_async_ops` to drain.
+    fn start_clone(&mut self) {
+        let Some(proxy) = self.proxy.clone() else {
+            self.toasts.push(ToastSpec::error(
+                "Clone unavailable: event loop proxy missing",
+            ));
+            return;
+        };
+        if self.clone_op.is_some() {
+            self.toasts
+                .push(ToastSpec::info("A clone is already in progress"));
+            return;
+        }
+        let Some(ActiveModal::Clone(form)) = &self.active_modal else {
+            return;
+        };
+        let url = form.url.trim().to_string();
+        let dest = form.dest.trim().to_string();
+        let bare = form.bare;
+        if url.is_empty() {
+            self.toasts
+                .push(ToastSpec::warning("Repository URL is required"));
+            return;
+        }
+        if dest.is_empty() {
+            self.toasts
+                .push(ToastSpec::warning("Destination directory is required"));
+            return;
+        }
+        let dest_path = std::path::PathBuf::from(&dest);
+        let dest_label = dest_path.display().to_string();
+        let rx = crate::git::clone_async(url.clone(), dest_path, bare, proxy);
+        self.clone_op = Some(CloneOp {
+            rx,
+            started: std::time::Instant::now(),
+            dest_label: dest_label.clone(),
+        });
+        self.toasts.push(ToastSpec::info(format!(
+            "Cloning into {dest_label}\u{2026}"
+        )));
+    }
+
+    fn handle_context_action(&mut self, action: &str) {
+        let Some(mut state) = self.context_menu.take() else {
+            return;
+        };
+        if let Some(group) = action.strip_prefix("toggle_group:") {
+            if let Some(idx) = state.expanded_groups.iter().position(|g| g == group) {
+                state.expanded_groups.remove(idx);
+            } else {
+                state.expanded_groups.push(group.to_string());
+            }
+            self.context_menu = Some(state);
+            return;
+        }
+        if self.handle_dynamic_commit_context_action(action, state.target.clone()) {
+            return;
+        }
+        match (action, state.target) {
+            ("checkout", ContextTarget::LocalBranch(name)) => {
+                self.run_op("Checkout", |t| t.repo.checkout_branch(&name));
+            }
+            ("create_worktree", ContextTarget::LocalBranch(name)) => {
+                self.open_worktree_modal_for_source(name);
+            }
+            ("create_pr", ContextTarget::LocalBranch(name)) => {
+                self.open_create_pull_request(name);
+            }
+            ("fetch_remote", ContextTarget::Remote(remote)) => {
+                self.fetch_remote(remote);
+            }
+            ("fetch_notes", ContextTarget::Remote(remote)) => {
+                self.fetch_notes(remote);
+            }
+            ("delete_remote", ContextTarget::Remote(remote)) => {
+                self.active_modal = Some(ActiveModal::Confirm {
+                    title: "Delete remote".to_string(),
+                    body: format!("Delete remote '{remote}' from this repository?"),
+                    ok_label: "Delete".to_string(),
+                    destructive: true,
+                    action: ConfirmAction::DeleteRemote(remote),
+                });
+            }
+            ("fetch_all", ContextTarget::RemoteSection) => {
+                self.fetch_all();
+            }
+            ("create_bundle", ContextTarget::RemoteSection) => {
+                self.open_bundle_create_modal();
+            }
+            ("import_bundle", ContextTarget::RemoteSection) => {
+                self.open_bundle_import_picker();
+            }
+            ("inspect_bundle", ContextTarget::Remote(remote)) => {
+                self.inspect_bundle_remote(remote);
+            }
+            ("checkout", ContextTarget::RemoteBranch { remote, branch }) => {
+                self.run_op("Checkout", |t| {
+                    t.repo.checkout_remote_branch(&remote, &branch)
+                });
+            }
+            ("merge", ContextTarget::LocalBranch(name)) => {
+                self.merge_branch(name);
+            }
+            ("merge", ContextTarget::RemoteBranch { remote, branch }) => {
+                self.merge_branch(format!("{remote}/{branch}"));
+            }
+            ("merge_options", ContextTarget::LocalBranch(name)) => {
+                self.open_merge_options(name);
+            }
+            ("merge_options", ContextTarget::RemoteBranch { remote, branch }) => {
+                self.open_merge_options(format!("{remote}/{branch}"));
+            }
+            ("rebase", ContextTarget::LocalBranch(name)) => {
+                self.rebase_onto(name);
+            }
+            ("rebase", ContextTarget::RemoteBranch { remote, branch }) => {
+                self.rebase_onto(format!("{remote}/{branch}"));
+            }
+            ("rebase_options", ContextTarget::LocalBranch(name)) => {
+                self.open_rebase_options(name);
+            }
+            ("rebase_options", ContextTarget::RemoteBranch { remote, branch }) => {
+                self.open_rebase_options(format!("{remote}/{branch}"));
+            }
+            ("delete", ContextTarget::LocalBranch(name)) => {
+                self.active_modal = Some(ActiveModal::Confirm {
+                    title: "Delete branch".to_string(),
+                    body: format!("Delete local branch '{name}' permanently?"),
+                    ok_label: "Delete".to_string(),
+                    destructive: true,
+                    action: ConfirmAction::DeleteBranch(name),
+                });
+            }
+            ("delete", ContextTarget::RemoteBranch { remote, branch }) => {
+                self.active_modal = Some(ActiveModal::Confirm {
+                    title: "Delete remote branch".to_string(),
+                    body: format!(
+                        "Delete branch '{branch}' from remote '{remote}'? This cannot be undone."
+                    ),
+                    ok_label: "Delete".to_string(),
+                    destructive: true,
+                    action: ConfirmAction::DeleteRemoteBranch { remote, branch },
+                });
+            }
+            ("changelog", ContextTarget::Tag(name)) => {
+                let oid = self
+                    .active_focus()
+                    .and_then(|t| t.tags.iter().find(|t| t.name == name).map(|t| t.oid));
+                if let Some(oid) = oid {
+                    self.open_changelog_modal(name, oid);
+                }
+            }
+            ("create_release", ContextTarget::Tag(name)) => {
+                self.open_create_release(name);
+            }
+            ("release_open", ContextTarget::Tag(name)) => {
+                self.open_release(&name);
+            }
+            ("set_patch_base", ContextTarget::Tag(name)) => {
+                let oid = self
+                    .active_focus()
+                    .and_then(|t| t.tags.iter().find(|t| t.name == name).map(|t| t.oid));
+                let Some(oid) = oid else {
+                    return;
+                };
+                if let Some(tab) = self.active_focus_mut() {
+                    tab.patch_range_base = Some(oid);
+                }
+                self.toasts.push(ToastSpec::info(format!(
+                    "Range base set to {name}; right-click a later tag or commit to use it"
+                )));
+            }
+            ("delete", ContextTarget::Tag(name)) => {
+                self.active_modal = Some(ActiveModal::Confirm {
+                    title: "Delete tag".to_string(),
+                    body: format!("Delete tag '{name}' permanently?"),
+                    ok_label: "Delete".to_string(),
+                    destructive: true,
+                    action: ConfirmAction::DeleteTag(name),
+                });
+            }
+            ("pr_checkout", ContextTarget::PullRequest(key)) => {
+                self.checkout_pull_request(&key, false);
+            }
+            ("pr_worktree", ContextTarget::PullRequest(key)) => {
+                self.checkout_pull_request(&key, true);
+            }
+            ("pr_open", ContextTarget::PullRequest(key)) => {
+                if let Some(url) = self
+                    .active_focus()
+                    .and_then(|t| t.pull_request(&key))
+                    .map(|(_, pr)| pr.url.clone())
+                {
+                    let _ = std::process::Command::new("xdg-open").arg(&url).spawn();
+                }
+            }
+            ("pr_copy_url", ContextTarget::PullRequest(key)) => {
+                let Some(url) = self
+                    .active_focus()
+                    .and_then(|t| t.pull_request(&key))
+                    .map(|(_, pr)| pr.url.clone())
+                else {
+                    return;
+                };
+                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(url)) {
+                    Ok(()) => self.toasts.push(ToastSpec::success("Copied URL")),
+                    Err(e) => self
+                        .toasts
+                        .push(ToastSpec::error(format!("Clipboard: {e}"))),
+                }
+            }
+            ("apply", ContextTarget::Stash(idx)) => {
+                self.stash_apply(idx);
+            }
+            ("pop", ContextTarget::Stash(idx)) => {
+                self.stash_pop(idx);
+            }
+            ("drop", ContextTarget::Stash(idx)) => {
+                self.active_modal = Some(ActiveModal::Confirm {
+                    title: "Drop stash".to_string(),
+                    body: format!("Drop stash @{{{idx}}} permanently?"),
+                    ok_label: "Drop".to_string(),
+                    destructive: true,
+                    action: ConfirmAction::DropStash(idx),
+                });
+            }
+            ("switch_worktree", ContextTarget::Worktree(key)) => {
+                self.select_worktree_by_key(&key);
+            }
+            ("jump_to_worktree", ContextTarget::Worktree(key)) => {
+                self.jump_to_worktree_branch(&key);
+            }
+            ("remove_worktree", ContextTarget::Worktree(key)) => {
+                self.confirm_remove_worktree_by_key(&key);
+            }
+            ("lock_worktree", ContextTarget::Worktree(key)) => {
+                if let Some(idx) = self.worktree_index_by_key(&key) {
+                    self.open_worktree_lock_modal(idx);
+                }
+            }
+            ("unlock_worktree", ContextTarget::Worktree(key)) => {
+                if let Some(idx) = self.worktree_index_by_key(&key) {
+                    self.unlock_worktree(idx);
+                }
+            }
+            ("move_worktree", ContextTarget::Worktree(key)) => {
+                if let Some(idx) = self.worktree_index_by_key(&key) {
+                    self.open_worktree_move_modal(idx);
+                }
+            }
+            ("prune_worktrees", ContextTarget::Worktree(_)) => {
+                self.prune_worktrees();
+            }
+            ("stage", ContextTarget::StagingFile { path, .. }) => {
+                self.run_op("Stage", move |t| t.active_repo().stage_file(&path));
+            }
+            ("unstage", ContextTarget::StagingFile { path, .. }) => {
+                self.run_op("Unstage", move |t| t.active_repo().unstage_file(&path));
+            }
+            ("view_diff", ContextTarget::StagingFile { path, .. }) => {
+                if let Some(view) = self.active_focus_mut().and_then(|t| t.active_view_mut()) {
+                    view.selected_diff_file = Some(path);
+                }
+            }
+            ("discard", ContextTarget::StagingFile { path, .. }) => {
+                self.confirm_discard_file(path);
+            }
+            ("copy_sha", ContextTarget::Commit(oid)) => {
+                let sha = oid.to_string();
+                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(sha.clone())) {
                     Ok(()) => self
                         .toasts
                         .push(ToastSpec::success(format!("Copied {}", &sha[..7]))),
@@ -2837,1213 +4687,2775 @@ impl WhisperApp {
             ("reset_hard", ContextTarget::Commit(oid)) => {
                 self.confirm_reset_to_commit(oid, git2::ResetType::Hard);
             }
-            ("cherry_pick", ContextTarget::Commit(oid)) => {
-                self.cherry_pick(oid);
+            ("cherry_pick", ContextTarget::Commit(oid)) => {
+                self.cherry_pick(oid);
+            }
+            ("revert", ContextTarget::Commit(oid)) => {
+                self.revert(oid);
+            }
+            ("changelog", ContextTarget::Commit(oid)) => {
+                let to = self
+                    .active_focus()
+                    .and_then(|t| t.tags.iter().find(|t| t.oid == oid).map(|t| t.name.clone()))
+                    .unwrap_or_else(|| oid.to_string()[..7].to_string());
+                self.open_changelog_modal(to, oid);
+            }
+            ("export_patch", ContextTarget::Commit(oid)) => {
+                self.open_patch_export_modal(oid, None);
+            }
+            ("export_patch_range", ContextTarget::Commit(oid)) => {
+                let base = self.active_focus().and_then(|t| t.patch_range_base);
+                self.open_patch_export_modal(oid, base);
+            }
+            ("set_patch_base", ContextTarget::Commit(oid)) => {
+                if let Some(tab) = self.active_focus_mut() {
+                    tab.patch_range_base = Some(oid);
+                }
+                let short = &oid.to_string()[..7];
+                self.toasts.push(ToastSpec::info(format!(
+                    "Patch range base set to {short}; right-click a later commit to export"
+                )));
+            }
+            ("restore_commit", ContextTarget::CommitFile { oid, path }) => {
+                self.restore_file_at(oid, path, false);
+            }
+            ("restore_parent", ContextTarget::CommitFile { oid, path }) => {
+                self.restore_file_at(oid, path, true);
+            }
+            ("restore_dir_commit", ContextTarget::CommitFile { oid, path }) => {
+                if let Some((dir, _)) = path.rsplit_once('/') {
+                    self.restore_file_at(oid, dir.to_string(), false);
+                }
+            }
+            ("restore_dir_parent", ContextTarget::CommitFile { oid, path }) => {
+                if let Some((dir, _)) = path.rsplit_once('/') {
+                    self.restore_file_at(oid, dir.to_string(), true);
+                }
+            }
+            ("restore_selected_commit", ContextTarget::Commit(oid)) => {
+                self.restore_selected_file_at(oid, false);
+            }
+            ("restore_selected_parent", ContextTarget::Commit(oid)) => {
+                self.restore_selected_file_at(oid, true);
+            }
+            ("save_at_revision", ContextTarget::CommitFile { oid, path }) => {
+                self.save_file_at_revision(oid, path);
+            }
+            ("apply_patch", ContextTarget::RepoMenu) => {
+                self.open_patch_apply_picker();
+            }
+            ("repo_config", ContextTarget::RepoMenu) => {
+                self.open_repo_config_modal();
+            }
+            ("maintenance", ContextTarget::RepoMenu) => {
+                self.open_maintenance_modal();
+            }
+            ("insights", ContextTarget::RepoMenu) => {
+                let proxy = self.proxy.clone();
+                if let Some(tab) = self.active_focus_mut() {
+                    tab.open_insights(proxy.as_ref());
+                }
+            }
+            ("ignore_file", ContextTarget::StagingFile { path, .. }) => {
+                self.open_ignore_modal(path, IgnoreKind::File, IgnoreDestination::Gitignore);
+            }
+            ("ignore_extension", ContextTarget::StagingFile { path, .. }) => {
+                self.open_ignore_modal(path, IgnoreKind::Extension, IgnoreDestination::Gitignore);
+            }
+            ("ignore_folder", ContextTarget::StagingFile { path, .. }) => {
+                self.open_ignore_modal(path, IgnoreKind::Folder, IgnoreDestination::Gitignore);
+            }
+            ("ignore_local", ContextTarget::StagingFile { path, .. }) => {
+                self.open_ignore_modal(path, IgnoreKind::File, IgnoreDestination::InfoExclude);
+            }
+            (
+                "review_comment",
+                ContextTarget::ReviewLine {
+                    path,
+                    old_line,
+                    new_line,
+                },
+            ) => {
+                self.open_review_line_comment(path, old_line, new_line);
+            }
+            ("ci_logs", ContextTarget::Ci { sha, provider }) => {
+                let proxy = self.proxy.clone();
+                if let (Some(tab), Some(proxy)) = (self.active_focus_mut(), proxy) {
+                    let idx = tab
+                        .ci_per_commit
+                        .get(&sha)
+                        .and_then(|r| r.iter().position(|r| r.provider == provider))
+                        .unwrap_or(0);
+                    if let Err(e) = tab.open_ci_log(&sha, idx, proxy) {
+                        self.toasts.push(ToastSpec::info(e));
+                    }
+                }
+            }
+            ("ci_rerun_failed", ContextTarget::Ci { sha, provider }) => {
+                let runs = self.ci_run_ids(&sha, provider, |s| s == CiState::Failure);
+                self.start_ci_action(provider, CiAction::RerunFailed(runs));
+            }
+            ("ci_rerun_all", ContextTarget::Ci { sha, provider }) => {
+                let runs = self.ci_run_ids(&sha, provider, |s| s != CiState::Pending);
+                self.start_ci_action(provider, CiAction::RerunAll(runs));
+            }
+            ("ci_cancel", ContextTarget::Ci { sha, provider }) => {
+                let runs = self.ci_run_ids(&sha, provider, |s| s == CiState::Pending);
+                let noun = if runs.len() == 1 { "run" } else { "runs" };
+                self.active_modal = Some(ActiveModal::Confirm {
+                    title: "Cancel CI".to_string(),
+                    body: format!(
+                        "Cancel {} in-progress {noun} on {}?",
+                        runs.len(),
+                        sha.get(..7).unwrap_or(&sha)
+                    ),
+                    ok_label: "Cancel Runs".to_string(),
+                    destructive: true,
+                    action: ConfirmAction::CancelCi { provider, runs },
+                });
+            }
+            ("ci_run", ContextTarget::Ci { provider, .. }) => {
+                self.open_run_workflow(provider);
+            }
+            ("add_note", ContextTarget::Commit(oid)) => {
+                let ref_name = self
+                    .active_focus()
+                    .map(|t| t.repo.default_notes_ref())
+                    .unwrap_or_else(|| crate::git::DEFAULT_NOTES_REF.to_string());
+                self.open_note_modal(oid, ref_name);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_dynamic_commit_context_action(
+        &mut self,
+        action: &str,
+        target: ContextTarget,
+    ) -> bool {
+        if !matches!(target, ContextTarget::Commit(_)) {
+            return false;
+        }
+        if let Some(branch) = action.strip_prefix("checkout_branch:") {
+            let branch = branch.to_string();
+            self.run_op("Checkout", move |t| t.repo.checkout_branch(&branch));
+            return true;
+        }
+        if let Some(source) = action.strip_prefix("merge_ref:") {
+            self.merge_branch(source.to_string());
+            return true;
+        }
+        if let Some(base) = action.strip_prefix("rebase_ref:") {
+            self.rebase_onto(base.to_string());
+            return true;
+        }
+        if let ContextTarget::Commit(oid) = target {
+            if let Some(ref_name) = action.strip_prefix("edit_note:") {
+                self.open_note_modal(oid, ref_name.to_string());
+                return true;
+            }
+            if let Some(ref_name) = action.strip_prefix("remove_note:") {
+                self.confirm_remove_note(oid, ref_name.to_string());
+                return true;
+            }
+        }
+        false
+    }
+
+    fn handle_settings_route(&mut self, sub: &str) {
+        match sub {
+            "close" => self.active_modal = None,
+            "avatars" => {
+                self.config.avatars_enabled = !self.config.avatars_enabled;
+                self.persist_config();
+            }
+            "shortcut_bar" => {
+                self.shortcut_bar_visible = !self.shortcut_bar_visible;
+                self.config.shortcut_bar_visible = self.shortcut_bar_visible;
+                self.persist_config();
+            }
+            "orphans" => {
+                self.config.show_orphaned_commits = !self.config.show_orphaned_commits;
+                self.refresh_all_repo_state();
+                self.persist_config();
+            }
+            "diff_split" => {
+                self.config.diff_split = !self.config.diff_split;
+                self.persist_config();
+            }
+            "raw_author" => {
+                self.config.show_raw_author = !self.config.show_raw_author;
+                self.persist_config();
+            }
+            "clone" => {
+                // Clone-from-Settings: pre-fill destination with $HOME so
+                // first-time users land in a sensible default location.
+                let form = CloneForm {
+                    dest: std::env::var("HOME").unwrap_or_default(),
+                    ..Default::default()
+                };
+                self.active_modal = Some(ActiveModal::Clone(form));
+            }
+            "tokens" => {
+                self.active_modal = Some(ActiveModal::Token(TokenForm::default()));
+            }
+            other => {
+                if let Some(scale_str) = other.strip_prefix("row_size:")
+                    && let Ok(scale) = scale_str.parse::<f32>()
+                {
+                    self.config.row_scale = scale;
+                    self.persist_config();
+                }
+            }
+        }
+    }
+
+    fn refresh_all_repo_state(&mut self) {
+        let show_orphans = self.config.show_orphaned_commits;
+        let proxy = self.proxy.clone();
+        for outer in &mut self.tabs {
+            outer.request_state_refresh(proxy.as_ref(), show_orphans);
+            for sub in &mut outer.nav_stack {
+                sub.request_state_refresh(proxy.as_ref(), show_orphans);
+            }
+        }
+    }
+
+    fn persist_config(&mut self) {
+        if let Err(e) = self.config.save() {
+            self.toasts
+                .push(ToastSpec::error(format!("Save settings failed: {e}")));
+        }
+    }
+
+    fn run_confirm_action(&mut self, action: ConfirmAction) {
+        match action {
+            ConfirmAction::CloseTab(idx) => self.close_tab(idx),
+            ConfirmAction::DeleteBranch(name) => {
+                self.run_op("Delete branch", |t| t.repo.delete_branch(&name));
+            }
+            ConfirmAction::DeleteRemote(name) => {
+                self.run_op("Delete remote", |t| t.repo.delete_remote(&name));
+            }
+            ConfirmAction::DeleteRemoteBranch { remote, branch } => {
+                self.delete_remote_branch(remote, branch);
+            }
+            ConfirmAction::DeleteTag(name) => {
+                self.run_op("Delete tag", |t| t.repo.delete_tag(&name));
+            }
+            ConfirmAction::DropStash(idx) => {
+                self.stash_drop(idx);
+            }
+            ConfirmAction::DiscardFile(path) => {
+                self.run_op("Discard", move |t| t.active_repo().discard_file(&path));
+            }
+            ConfirmAction::DiscardHunk { path, idx } => {
+                self.run_op("Discard hunk", move |t| {
+                    t.active_repo().discard_hunk(&path, idx)
+                });
+            }
+            ConfirmAction::RemoveWorktree { name, path, force } => {
+                self.remove_worktree(name, path, force);
+            }
+            ConfirmAction::ResetToCommit { oid, mode } => {
+                self.run_op(reset_label(mode), move |t| {
+                    t.repo.reset_to_commit(oid, mode)
+                });
+            }
+            ConfirmAction::ForcePush { remote, branch } => {
+                self.force_push(remote, branch);
+            }
+            ConfirmAction::RestorePath { source, path } => {
+                self.restore_path(source, path);
+            }
+            ConfirmAction::UpdateSubmodulePin { sm_path } => {
+                self.stage_submodule_pin_update(&sm_path);
+            }
+            ConfirmAction::RunMaintenance(task) => {
+                self.open_maintenance_modal();
+                self.run_maintenance(task);
+            }
+            ConfirmAction::RemoveNote { oid, ref_name } => {
+                self.remove_note(oid, ref_name);
+            }
+            ConfirmAction::CancelCi { provider, runs } => {
+                self.start_ci_action(provider, CiAction::Cancel(runs));
+            }
+        }
+    }
+
+    /// Stage the parent's submodule pointer at `sm_path`, then pop the
+    /// drill-down stack one level so the user lands on the parent
+    /// view with the pointer change ready to commit. `sm_path` is
+    /// relative to the parent's worktree — exactly the form
+    /// `index.add_path` expects.
+    ///
+    /// No-op if not drilled in (no parent to stage against). On
+    /// success we surface a toast naming the submodule + new short
+    /// SHA so the user sees the pointer landed.
+    fn stage_submodule_pin_update(&mut self, sm_path: &str) {
+        let Some(outer) = self.active_mut() else {
+            return;
+        };
+        // Capture the new HEAD before we exit (the focused tab carries
+        // it via active_view_tab().active_view()).
+        let new_short = outer
+            .active_view_tab()
+            .active_view()
+            .and_then(|v| v.head_oid)
+            .map(|o| o.to_string()[..7].to_string())
+            .unwrap_or_else(|| "?".to_string());
+        // Pop first so the parent becomes the focused tab. Now stage
+        // through the parent's active worktree's repo.
+        if !outer.exit_submodule() {
+            return;
+        }
+        let Some(parent_view) = outer.active_view_tab_mut().active_view_mut() else {
+            self.toasts.push(ToastSpec::error(
+                "Parent view has no active worktree — cannot stage pointer update",
+            ));
+            return;
+        };
+        match parent_view.repo.stage_file(sm_path) {
+            Ok(()) => {
+                // Refresh the parent so the staged pointer change shows
+                // up in its staging well. Refresh on the OUTERMOST
+                // (which is now the focused view since we popped).
+                let proxy = self.proxy.clone();
+                let show_orphans = self.config.show_orphaned_commits;
+                if let Some(outer) = self.active_mut() {
+                    outer.request_state_refresh(proxy.as_ref(), show_orphans);
+                }
+                self.toasts.push(ToastSpec::success(format!(
+                    "Staged {sm_path} \u{2192} {new_short}"
+                )));
+            }
+            Err(e) => {
+                self.toasts
+                    .push(ToastSpec::error(format!("Couldn't stage {sm_path}: {e}")));
+            }
+        }
+    }
+
+    /// `git push --force-with-lease <remote> <branch>`. Reached only via
+    /// the rejected-push Confirm modal, so the user has explicitly opted
+    /// in. Writes through the same `push_op` slot as a regular push, so
+    /// the header progress affordance and the failure-handling path
+    /// don't need a separate code path.
+    fn force_push(&mut self, remote: String, branch: String) {
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Push, true) else {
+            return;
+        };
+        let rx = crate::git::push_force_async(wd, remote.clone(), branch.clone(), proxy);
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        tab.push_op = Some(TimedOp::new(
+            rx,
+            format!("{branch} \u{2192} {remote} (force)"),
+        ));
+        self.toasts.push(ToastSpec::info(format!(
+            "Force-pushing {branch} to {remote}…"
+        )));
+    }
+
+    // -------------------------------------------------------------
+    // Async op infrastructure.
+    //
+    // Each remote op spawns a `git` CLI thread (see `git/async_ops.rs`)
+    // that sends a `RemoteOpResult` back over a channel and wakes the
+    // event loop via `EventLoopProxy`. We poll the channels in
+    // `before_build`; on completion, refresh the tab and surface
+    // success via toast / failure via error modal.
+    // -------------------------------------------------------------
+
+    /// Drain all per-tab async slots plus the app-scoped clone slot;
+    /// called once per frame from `before_build`. Visits every tab and
+    /// every drilled-in level inside it, so background work in a
+    /// non-foreground tab — or on a parent repo while the user has
+    /// drilled into a submodule — still completes cleanly.
+    pub fn poll_async_ops(&mut self) {
+        for idx in 0..self.tabs.len() {
+            // Outermost first.
+            self.poll_async_ops_at(idx, None);
+            // Then each drilled-in submodule level. Re-read depth each
+            // iteration since the outer poll may have refreshed and
+            // exit_submodule could (in principle) shrink the stack.
+            let mut d = 0;
+            while let Some(t) = self.tabs.get(idx)
+                && d < t.nav_stack.len()
+            {
+                self.poll_async_ops_at(idx, Some(d));
+                d += 1;
+            }
+        }
+        self.poll_clone_op();
+        self.poll_state_refreshes();
+        self.poll_status_refreshes();
+        self.poll_dirty_checks();
+        self.poll_watcher_inits();
+        self.poll_watcher_events();
+        self.poll_status_safety_net();
+        self.poll_ref_reconciliation();
+        self.drain_ci_receivers();
+        self.poll_ci_refresh();
+        self.drain_diff_stats();
+        self.trigger_diff_stats_fetches();
+        self.drain_insights();
+        self.poll_conflict_risk();
+        self.drain_forecast();
+        self.drain_ci_logs();
+        self.drain_ci_actions();
+        self.drain_maintenance();
+        self.drain_avatar_completions();
+        self.request_visible_avatars();
+    }
+
+    /// Kick off the very first state refresh for any tab that hasn't
+    /// had one attempted yet. Runs every frame from `before_build` —
+    /// the per-tab `state_refresh_attempted` flag short-circuits after
+    /// the first call so this is cheap on subsequent frames.
+    ///
+    /// Tabs constructed before the proxy was set (the startup path
+    /// through `from_paths`) sit in an empty state until the first
+    /// frame where `proxy` is available; that's also the first frame
+    /// this runs. Tabs created later (via `open_repo_path`) trigger
+    /// their refresh inline at construction time and skip this.
+    fn trigger_initial_state_refreshes(&mut self) {
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+        let show_orphans = self.config.show_orphaned_commits;
+        for tab in &mut self.tabs {
+            if !tab.state_refresh_attempted {
+                tab.trigger_state_refresh(&proxy, show_orphans);
+            }
+            for sub in &mut tab.nav_stack {
+                if !sub.state_refresh_attempted {
+                    sub.trigger_state_refresh(&proxy, show_orphans);
+                }
+            }
+        }
+    }
+
+    /// Drain finished `RepoStateResult`s for every tab + drilled-in
+    /// level. On a result, fold via [`RepoTab::apply_state_result`],
+    /// surface any errors as toasts, then dispatch the produced effects
+    /// (kick off diff-stats fanout, kick off per-entity dirty checks,
+    /// flag the watcher for path updates).
+    fn poll_state_refreshes(&mut self) {
+        for tab_idx in 0..self.tabs.len() {
+            // Outermost first, then each drilled-in level.
+            self.poll_state_refresh_at(tab_idx, None);
+            let mut d = 0;
+            while let Some(t) = self.tabs.get(tab_idx)
+                && d < t.nav_stack.len()
+            {
+                self.poll_state_refresh_at(tab_idx, Some(d));
+                d += 1;
+            }
+        }
+    }
+
+    fn poll_state_refresh_at(&mut self, tab_idx: usize, depth: Option<usize>) {
+        let result = {
+            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
+                return;
+            };
+            let Some(rx) = tab.state_refresh_rx.take() else {
+                return;
+            };
+            match rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    // Worker still running — put the receiver back.
+                    tab.state_refresh_rx = Some(rx);
+                    None
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
+            }
+        };
+        let Some(result) = result else { return };
+
+        // Apply + collect effects in a scoped borrow.
+        let effects = {
+            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
+                return;
+            };
+            tab.apply_state_result(result)
+        };
+
+        for err in effects.errors {
+            self.toasts.push(ToastSpec::error(err));
+        }
+
+        // Diff-stats fetch for the new commit set. The existing
+        // `trigger_diff_stats_fetches` runs every poll anyway, so this
+        // is informational — but kicking it now starts the fetch in
+        // the same frame instead of waiting for the next.
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+        if let Some(tab) = self.tab_at_mut(tab_idx, depth) {
+            tab.trigger_diff_stats_fetch(proxy.clone());
+        }
+
+        // Per-entity dirty-check fanout — one worker per submodule, one
+        // per worktree. `tab_id` on each result lets the global drain
+        // route back here even after a tab close / reopen.
+        let (tab_id, repo_workdir) = {
+            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
+                return;
+            };
+            (tab.id, tab.repo.workdir().map(|p| p.to_path_buf()))
+        };
+        self.dirty_checks_in_flight += crate::git_async::spawn_dirty_checks(
+            tab_id,
+            &effects.dirty_checks_submodules,
+            &effects.dirty_checks_worktree_paths,
+            repo_workdir,
+            &self.dirty_check_tx,
+            &proxy,
+        );
+
+        // Update the watcher's per-worktree watch set if the resolved
+        // worktree list changed, and refresh its submodule exclusion
+        // list so submodules added/removed mid-session are reflected
+        // without rebuilding the watcher.
+        if effects.watcher_paths_changed
+            && let Some(tab) = self.tab_at_mut(tab_idx, depth)
+        {
+            let common_dir = tab.repo.common_dir().to_path_buf();
+            let worktrees = tab.worktrees.clone();
+            let submodule_paths: Vec<PathBuf> = tab
+                .worktree_views
+                .values()
+                .flat_map(|v| v.submodules.iter().map(|sm| v.path.join(&sm.path)))
+                .collect();
+            if let Some(w) = tab.watcher.as_mut() {
+                w.update_worktree_watches(&worktrees, &common_dir);
+                w.update_submodule_paths(submodule_paths);
+            }
+        }
+
+        // Trigger watcher init once we have submodule paths from the
+        // first state-refresh result. Subsequent calls short-circuit
+        // (idempotent on `watcher.is_some() || watcher_init_rx.is_some()`).
+        if let Some(tab) = self.tab_at_mut(tab_idx, depth) {
+            tab.trigger_watcher_init(&proxy);
+        }
+    }
+
+    /// Drain finished watcher init results for every tab + drilled-in
+    /// level. On success, populate `watcher` + `watcher_rx`; on
+    /// failure, surface a toast and leave the slots `None` (the safety
+    /// nets still cover refresh).
+    fn poll_watcher_inits(&mut self) {
+        for tab_idx in 0..self.tabs.len() {
+            self.poll_watcher_init_at(tab_idx, None);
+            let mut d = 0;
+            while let Some(t) = self.tabs.get(tab_idx)
+                && d < t.nav_stack.len()
+            {
+                self.poll_watcher_init_at(tab_idx, Some(d));
+                d += 1;
+            }
+        }
+    }
+
+    fn poll_watcher_init_at(&mut self, tab_idx: usize, depth: Option<usize>) {
+        let result = {
+            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
+                return;
+            };
+            let Some(rx) = tab.watcher_init_rx.take() else {
+                return;
+            };
+            match rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    tab.watcher_init_rx = Some(rx);
+                    None
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
+            }
+        };
+        let Some(result) = result else { return };
+        match result {
+            Ok((watcher, watcher_rx)) => {
+                if let Some(tab) = self.tab_at_mut(tab_idx, depth) {
+                    tab.watcher = Some(watcher);
+                    tab.watcher_rx = Some(watcher_rx);
+                }
+            }
+            Err(e) => {
+                self.toasts.push(ToastSpec::error(format!(
+                    "Filesystem watcher failed to initialize: {e}"
+                )));
+            }
+        }
+    }
+
+    /// Drain debounced `FsChangeKind` events for every tab + drilled-in
+    /// level. Coalesces a burst of events to its highest-priority kind
+    /// (so a stream of working-tree edits + a single git-commit event
+    /// dispatches to GitMetadata, not WorkingTree). Per-kind dispatch:
+    ///
+    /// - `WorkingTree` → trigger working-dir status refresh + per-
+    ///   worktree dirty check fanout
+    /// - `GitMetadata` → reopen repo handles (libgit2 cache bypass) +
+    ///   trigger full state refresh
+    /// - `WorktreeStructure` → same as `GitMetadata` plus the next
+    ///   state refresh's `watcher_paths_changed` effect updates the
+    ///   watch set
+    fn poll_watcher_events(&mut self) {
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+        let show_orphans = self.config.show_orphaned_commits;
+        for tab_idx in 0..self.tabs.len() {
+            self.dispatch_watcher_events_at(tab_idx, None, &proxy, show_orphans);
+            let mut d = 0;
+            while let Some(t) = self.tabs.get(tab_idx)
+                && d < t.nav_stack.len()
+            {
+                self.dispatch_watcher_events_at(tab_idx, Some(d), &proxy, show_orphans);
+                d += 1;
+            }
+        }
+    }
+
+    /// 30 s safety-net status refresh on the active tab. Catches the
+    /// case where a watcher event was dropped (inotify queue overflow,
+    /// filesystem races, NFS-style fakery) by re-querying status
+    /// unconditionally on a long interval. Cheap relative to a full
+    /// state refresh — just the working-dir status walk on the active
+    /// view's repo.
+    ///
+    /// Background tabs aren't covered here; they get refreshed when
+    /// the user switches to them or via their own watcher when one
+    /// fires for that tab. The legacy used the same shape — the
+    /// active tab is what the user is staring at, the safety net's
+    /// job is to keep that tab from going stale silently.
+    fn poll_status_safety_net(&mut self) {
+        let now = std::time::Instant::now();
+        let expired = now.duration_since(self.last_status_refresh).as_secs() >= 30;
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+        if let Some(tab) = self.active_focus_mut() {
+            if expired {
+                tab.status_dirty = true;
+            }
+            if tab.status_dirty {
+                tab.trigger_status_refresh(&proxy);
+            }
+        }
+        // `trigger_status_refresh` clears the tab's dirty bit only
+        // when it successfully spawns a worker.
+    }
+
+    /// 5 s ref_fingerprint reconciliation on the active tab. Cheap
+    /// hash of `git_dir/refs/` content; if it diverges from the last
+    /// cached value (something updated the refdb that the watcher
+    /// didn't surface) → reopen + trigger a full state refresh.
+    /// Belt-and-braces against missed `GitMetadata` events; the
+    /// reopen specifically handles libgit2's refdb cache going stale
+    /// independently of watcher delivery.
+    ///
+    /// Skipped when `tab.ref_fingerprint == 0` (no baseline yet — the
+    /// initial state refresh hasn't landed) and when a state refresh
+    /// is already in flight.
+    fn poll_ref_reconciliation(&mut self) {
+        let now = std::time::Instant::now();
+        if now.duration_since(self.last_ref_check).as_secs() < 5 {
+            return;
+        }
+        self.last_ref_check = now;
+
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+        let show_orphans = self.config.show_orphaned_commits;
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        if tab.ref_fingerprint == 0 || tab.state_refresh_rx.is_some() {
+            return;
+        }
+        let fresh = crate::git::ref_fingerprint(tab.repo.git_dir());
+        if fresh != 0 && fresh != tab.ref_fingerprint {
+            tab.reopen_repo_handles();
+            tab.trigger_state_refresh(&proxy, show_orphans);
+        }
+    }
+
+    fn dispatch_watcher_events_at(
+        &mut self,
+        tab_idx: usize,
+        depth: Option<usize>,
+        proxy: &winit::event_loop::EventLoopProxy<()>,
+        show_orphans: bool,
+    ) {
+        // Drain + coalesce in one borrow scope so the dispatch below
+        // can take its own borrow.
+        let max_kind = {
+            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
+                return;
+            };
+            let Some(rx) = tab.watcher_rx.as_ref() else {
+                return;
+            };
+            let mut max_kind: Option<crate::watcher::FsChangeKind> = None;
+            while let Ok(kind) = rx.try_recv() {
+                max_kind = Some(match max_kind {
+                    Some(prev) if prev.priority() >= kind.priority() => prev,
+                    _ => kind,
+                });
+            }
+            max_kind
+        };
+        let Some(kind) = max_kind else { return };
+
+        use crate::watcher::FsChangeKind;
+        match kind {
+            FsChangeKind::WorkingTree => {
+                let (tab_id, repo_workdir, worktree_paths) = {
+                    let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
+                        return;
+                    };
+                    tab.status_dirty = true;
+                    tab.trigger_status_refresh(proxy);
+                    (
+                        tab.id,
+                        tab.repo.workdir().map(|p| p.to_path_buf()),
+                        tab.worktree_order.clone(),
+                    )
+                };
+                // Re-check worktree dirty state — a working-tree edit
+                // may have flipped a worktree's pill.
+                self.dirty_checks_in_flight += crate::git_async::spawn_dirty_checks(
+                    tab_id,
+                    &[],
+                    &worktree_paths,
+                    repo_workdir,
+                    &self.dirty_check_tx,
+                    proxy,
+                );
+            }
+            FsChangeKind::GitMetadata | FsChangeKind::WorktreeStructure => {
+                if let Some(tab) = self.tab_at_mut(tab_idx, depth) {
+                    // Bypass libgit2's refdb cache before triggering
+                    // the refresh — without this the worker would
+                    // still see the pre-event HEAD/refs.
+                    tab.reopen_repo_handles();
+                    tab.trigger_state_refresh(proxy, show_orphans);
+                }
+                // WorktreeStructure additionally needs `watcher_paths_changed`
+                // handling, which falls out automatically: the state
+                // refresh's apply step calls `merge_worktree_views`
+                // which sets the flag, and `poll_state_refresh_at`
+                // calls `update_worktree_watches`.
             }
-            ("revert", ContextTarget::Commit(oid)) => {
-                self.revert(oid);
+        }
+    }
+
+    /// Drain finished `StatusResult`s for every tab + drilled-in level.
+    fn poll_status_refreshes(&mut self) {
+        for tab_idx in 0..self.tabs.len() {
+            self.poll_status_refresh_at(tab_idx, None);
+            let mut d = 0;
+            while let Some(t) = self.tabs.get(tab_idx)
+                && d < t.nav_stack.len()
+            {
+                self.poll_status_refresh_at(tab_idx, Some(d));
+                d += 1;
             }
-            _ => {}
         }
     }
 
-    fn handle_dynamic_commit_context_action(
-        &mut self,
-        action: &str,
-        target: ContextTarget,
-    ) -> bool {
-        if !matches!(target, ContextTarget::Commit(_)) {
-            return false;
+    fn poll_status_refresh_at(&mut self, tab_idx: usize, depth: Option<usize>) {
+        let result = {
+            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
+                return;
+            };
+            let Some(rx) = tab.status_rx.take() else {
+                return;
+            };
+            match rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(std::sync::mpsc::TryRecvError::Empty) => {
+                    tab.status_rx = Some(rx);
+                    None
+                }
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
+            }
+        };
+        let Some(result) = result else { return };
+        let needs_follow_up = if let Some(tab) = self.tab_at_mut(tab_idx, depth) {
+            tab.apply_status_result(result);
+            tab.status_dirty
+        } else {
+            false
+        };
+        if needs_follow_up
+            && let Some(proxy) = self.proxy.clone()
+            && let Some(tab) = self.tab_at_mut(tab_idx, depth)
+        {
+            tab.trigger_status_refresh(&proxy);
         }
-        if let Some(branch) = action.strip_prefix("checkout_branch:") {
-            let branch = branch.to_string();
-            self.run_op("Checkout", move |t| t.repo.checkout_branch(&branch));
-            return true;
+        // Stamp last_status_refresh on the active tab's result so the
+        // 30 s safety net doesn't redundantly fire.
+        if depth.is_none() && tab_idx == self.active_tab {
+            self.last_status_refresh = std::time::Instant::now();
         }
-        if let Some(source) = action.strip_prefix("merge_ref:") {
-            self.merge_branch(source.to_string());
-            return true;
+    }
+
+    /// Drain the global per-entity dirty-check channel and route each
+    /// result back to its originating `RepoTab` by `tab_id`. Stale
+    /// results from closed tabs match no live tab and drop silently.
+    fn poll_dirty_checks(&mut self) {
+        if self.dirty_checks_in_flight == 0 {
+            return;
         }
-        if let Some(base) = action.strip_prefix("rebase_ref:") {
-            self.rebase_onto(base.to_string());
-            return true;
+        loop {
+            match self.dirty_check_rx.try_recv() {
+                Ok(result) => {
+                    self.dirty_checks_in_flight = self.dirty_checks_in_flight.saturating_sub(1);
+                    let target_id = result.tab_id();
+                    if let Some(tab) = self.find_tab_by_id_mut(target_id) {
+                        tab.apply_dirty_check_result(result);
+                    }
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => break,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    // Should never happen — we hold the sender.
+                    self.dirty_checks_in_flight = 0;
+                    break;
+                }
+            }
         }
-        false
     }
 
-    fn handle_settings_route(&mut self, sub: &str) {
-        match sub {
-            "close" => self.active_modal = None,
-            "avatars" => {
-                self.config.avatars_enabled = !self.config.avatars_enabled;
-                self.persist_config();
-            }
-            "shortcut_bar" => {
-                self.shortcut_bar_visible = !self.shortcut_bar_visible;
-                self.config.shortcut_bar_visible = self.shortcut_bar_visible;
-                self.persist_config();
-            }
-            "orphans" => {
-                self.config.show_orphaned_commits = !self.config.show_orphaned_commits;
-                self.refresh_all_repo_state();
-                self.persist_config();
-            }
-            "diff_split" => {
-                self.config.diff_split = !self.config.diff_split;
-                self.persist_config();
-            }
-            "clone" => {
-                // Clone-from-Settings: pre-fill destination with $HOME so
-                // first-time users land in a sensible default location.
-                let form = CloneForm {
-                    dest: std::env::var("HOME").unwrap_or_default(),
-                    ..Default::default()
-                };
-                self.active_modal = Some(ActiveModal::Clone(form));
-            }
-            "tokens" => {
-                self.active_modal = Some(ActiveModal::Token(TokenForm::default()));
+    /// Locate a tab (or drilled-in submodule view) by its stable `id`.
+    /// Walks every tab's `nav_stack` so dirty-check results targeting a
+    /// drilled-in level land on the right frame.
+    fn find_tab_by_id_mut(&mut self, id: u64) -> Option<&mut RepoTab> {
+        for tab in &mut self.tabs {
+            if tab.id == id {
+                return Some(tab);
             }
-            other => {
-                if let Some(scale_str) = other.strip_prefix("row_size:")
-                    && let Ok(scale) = scale_str.parse::<f32>()
-                {
-                    self.config.row_scale = scale;
-                    self.persist_config();
+            for sub in &mut tab.nav_stack {
+                if sub.id == id {
+                    return Some(sub);
                 }
             }
         }
+        None
     }
 
-    fn refresh_all_repo_state(&mut self) {
-        let show_orphans = self.config.show_orphaned_commits;
-        let proxy = self.proxy.clone();
-        for outer in &mut self.tabs {
-            outer.request_state_refresh(proxy.as_ref(), show_orphans);
-            for sub in &mut outer.nav_stack {
-                sub.request_state_refresh(proxy.as_ref(), show_orphans);
-            }
+    /// Address a tab + optional drill-in depth as a single mut ref.
+    /// `depth: None` is the outer tab; `Some(d)` is `nav_stack[d]`.
+    fn tab_at_mut(&mut self, tab_idx: usize, depth: Option<usize>) -> Option<&mut RepoTab> {
+        let outer = self.tabs.get_mut(tab_idx)?;
+        match depth {
+            None => Some(outer),
+            Some(d) => outer.nav_stack.get_mut(d),
         }
     }
 
-    fn persist_config(&mut self) {
-        if let Err(e) = self.config.save() {
-            self.toasts
-                .push(ToastSpec::error(format!("Save settings failed: {e}")));
+    /// Fold any completed Gravatar downloads into the in-memory cache
+    /// so the next render pass can reach for them via `cache.get(email)`.
+    fn drain_avatar_completions(&mut self) {
+        if let Some(cache) = self.avatar_cache.as_mut() {
+            cache.drain_completions();
         }
     }
 
-    fn run_confirm_action(&mut self, action: ConfirmAction) {
-        match action {
-            ConfirmAction::CloseTab(idx) => self.close_tab(idx),
-            ConfirmAction::DeleteBranch(name) => {
-                self.run_op("Delete branch", |t| t.repo.delete_branch(&name));
-            }
-            ConfirmAction::DeleteRemote(name) => {
-                self.run_op("Delete remote", |t| t.repo.delete_remote(&name));
-            }
-            ConfirmAction::DeleteRemoteBranch { remote, branch } => {
-                self.delete_remote_branch(remote, branch);
-            }
-            ConfirmAction::DeleteTag(name) => {
-                self.run_op("Delete tag", |t| t.repo.delete_tag(&name));
-            }
-            ConfirmAction::DropStash(idx) => {
-                self.stash_drop(idx);
-            }
-            ConfirmAction::DiscardFile(path) => {
-                self.run_op("Discard", move |t| t.active_repo().discard_file(&path));
-            }
-            ConfirmAction::DiscardHunk { path, idx } => {
-                self.run_op("Discard hunk", move |t| {
-                    t.active_repo().discard_hunk(&path, idx)
-                });
-            }
-            ConfirmAction::RemoveWorktree { name, path, force } => {
-                self.remove_worktree(name, path, force);
-            }
-            ConfirmAction::ResetToCommit { oid, mode } => {
-                self.run_op(reset_label(mode), move |t| {
-                    t.repo.reset_to_commit(oid, mode)
-                });
+    /// Kick off Gravatar fetches for every author we know about.
+    /// `AvatarCache::request` is idempotent — repeats short-circuit on
+    /// "already requested" — so we just iterate freely each tick.
+    /// The cache is created lazily on the first poll where `proxy`
+    /// is set, since avatars need an event-loop wake to surface
+    /// completions back to the UI.
+    fn request_visible_avatars(&mut self) {
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+        let cache = self
+            .avatar_cache
+            .get_or_insert_with(|| crate::avatar::AvatarCache::new(proxy));
+        for tab in &self.tabs {
+            for c in &tab.commits {
+                cache.request(&c.author_email);
             }
-            ConfirmAction::ForcePush { remote, branch } => {
-                self.force_push(remote, branch);
+            for sub in &tab.nav_stack {
+                for c in &sub.commits {
+                    cache.request(&c.author_email);
+                }
             }
-            ConfirmAction::UpdateSubmodulePin { sm_path } => {
-                self.stage_submodule_pin_update(&sm_path);
+        }
+    }
+
+    /// Drain any completed diff-stats fetches across every tab + level.
+    fn drain_diff_stats(&mut self) {
+        for tab in &mut self.tabs {
+            tab.drain_diff_stats();
+            for sub in &mut tab.nav_stack {
+                sub.drain_diff_stats();
             }
         }
     }
 
-    /// Stage the parent's submodule pointer at `sm_path`, then pop the
-    /// drill-down stack one level so the user lands on the parent
-    /// view with the pointer change ready to commit. `sm_path` is
-    /// relative to the parent's worktree — exactly the form
-    /// `index.add_path` expects.
-    ///
-    /// No-op if not drilled in (no parent to stage against). On
-    /// success we surface a toast naming the submodule + new short
-    /// SHA so the user sees the pointer landed.
-    fn stage_submodule_pin_update(&mut self, sm_path: &str) {
-        let Some(outer) = self.active_mut() else {
+    /// Make local `name` the focused tab's sidebar selection and start
+    /// forecasting whether merging it into HEAD would conflict.
+    fn select_sidebar_branch(&mut self, name: &str) {
+        let proxy = self.proxy.clone();
+        let Some(tab) = self.active_focus_mut() else {
             return;
         };
-        // Capture the new HEAD before we exit (the focused tab carries
-        // it via active_view_tab().active_view()).
-        let new_short = outer
-            .active_view_tab()
-            .active_view()
-            .and_then(|v| v.head_oid)
-            .map(|o| o.to_string()[..7].to_string())
-            .unwrap_or_else(|| "?".to_string());
-        // Pop first so the parent becomes the focused tab. Now stage
-        // through the parent's active worktree's repo.
-        if !outer.exit_submodule() {
+        tab.sidebar.selected = Some(SidebarSelection::Local(name.to_string()));
+        tab.request_conflict_risk(name, proxy.as_ref());
+    }
+
+    /// Fold finished conflict-risk merges for every tab + drilled-in
+    /// level, then keep the selected branch's risk current: a moved
+    /// HEAD or branch tip is a new cache key, so this re-requests once
+    /// per change and is a cache hit otherwise.
+    fn poll_conflict_risk(&mut self) {
+        for tab in &mut self.tabs {
+            tab.drain_conflict_risk();
+            for sub in &mut tab.nav_stack {
+                sub.drain_conflict_risk();
+            }
+        }
+        let proxy = self.proxy.clone();
+        let Some(tab) = self.active_focus_mut() else {
             return;
+        };
+        if let Some(SidebarSelection::Local(name)) = tab.sidebar.selected.clone() {
+            tab.request_conflict_risk(&name, proxy.as_ref());
         }
-        let Some(parent_view) = outer.active_view_tab_mut().active_view_mut() else {
-            self.toasts.push(ToastSpec::error(
-                "Parent view has no active worktree — cannot stage pointer update",
-            ));
+    }
+
+    /// Fold a finished conflict forecast into the Merge / Rebase
+    /// options modal, when it's still open.
+    fn drain_forecast(&mut self) {
+        let Some(rx) = self.forecast_op.as_ref() else {
             return;
         };
-        match parent_view.repo.stage_file(sm_path) {
-            Ok(()) => {
-                // Refresh the parent so the staged pointer change shows
-                // up in its staging well. Refresh on the OUTERMOST
-                // (which is now the focused view since we popped).
-                let proxy = self.proxy.clone();
-                let show_orphans = self.config.show_orphaned_commits;
-                if let Some(outer) = self.active_mut() {
-                    outer.request_state_refresh(proxy.as_ref(), show_orphans);
-                }
-                self.toasts.push(ToastSpec::success(format!(
-                    "Staged {sm_path} \u{2192} {new_short}"
-                )));
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                Err("Forecast worker exited".to_string())
             }
-            Err(e) => {
-                self.toasts
-                    .push(ToastSpec::error(format!("Couldn't stage {sm_path}: {e}")));
+        };
+        self.forecast_op = None;
+        match &mut self.active_modal {
+            Some(ActiveModal::MergeOptions { form, .. }) => form.forecast = Some(result),
+            Some(ActiveModal::RebaseOptions { form, .. }) => form.forecast = Some(result),
+            _ => {}
+        }
+    }
+
+    /// Fold finished insights passes for every tab + drilled-in level.
+    fn drain_insights(&mut self) {
+        for tab in &mut self.tabs {
+            tab.drain_insights();
+            for sub in &mut tab.nav_stack {
+                sub.drain_insights();
             }
         }
     }
 
-    /// `git push --force-with-lease <remote> <branch>`. Reached only via
-    /// the rejected-push Confirm modal, so the user has explicitly opted
-    /// in. Writes through the same `push_op` slot as a regular push, so
-    /// the header progress affordance and the failure-handling path
-    /// don't need a separate code path.
-    fn force_push(&mut self, remote: String, branch: String) {
-        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Push, true) else {
-            return;
-        };
-        let rx = crate::git::push_force_async(wd, remote.clone(), branch.clone(), proxy);
+    /// Fold finished health reports and housekeeping commands for every
+    /// tab + drilled-in level. A finished command refreshes the repo
+    /// state and the report, since gc / prune change both.
+    fn drain_maintenance(&mut self) {
+        let proxy = self.proxy.clone();
+        for idx in 0..self.tabs.len() {
+            let depth = self.tabs[idx].nav_stack.len();
+            for level in std::iter::once(None).chain((0..depth).map(Some)) {
+                let Some(tab) = resolve_tab_mut(&mut self.tabs, idx, level) else {
+                    continue;
+                };
+                let Some(output) = tab.drain_maintenance() else {
+                    continue;
+                };
+                tab.request_state_refresh(proxy.as_ref(), self.config.show_orphaned_commits);
+                tab.request_repo_health(proxy.as_ref());
+                let label = output.task.label();
+                let secs = output.elapsed.as_secs();
+                self.toasts.push(if output.success {
+                    ToastSpec::success(format!("{label} finished in {secs}s"))
+                } else {
+                    ToastSpec::error(format!("{label} failed; see Maintenance for output"))
+                });
+            }
+        }
+    }
+
+    fn open_maintenance_modal(&mut self) {
+        let proxy = self.proxy.clone();
         let Some(tab) = self.active_focus_mut() else {
             return;
         };
-        tab.push_op = Some(TimedOp::new(
-            rx,
-            format!("{branch} \u{2192} {remote} (force)"),
-        ));
-        self.toasts.push(ToastSpec::info(format!(
-            "Force-pushing {branch} to {remote}…"
-        )));
+        tab.request_repo_health(proxy.as_ref());
+        self.active_modal = Some(ActiveModal::Maintenance);
     }
 
-    // -------------------------------------------------------------
-    // Async op infrastructure.
-    //
-    // Each remote op spawns a `git` CLI thread (see `git/async_ops.rs`)
-    // that sends a `RemoteOpResult` back over a channel and wakes the
-    // event loop via `EventLoopProxy`. We poll the channels in
-    // `before_build`; on completion, refresh the tab and surface
-    // success via toast / failure via error modal.
-    // -------------------------------------------------------------
-
-    /// Drain all per-tab async slots plus the app-scoped clone slot;
-    /// called once per frame from `before_build`. Visits every tab and
-    /// every drilled-in level inside it, so background work in a
-    /// non-foreground tab — or on a parent repo while the user has
-    /// drilled into a submodule — still completes cleanly.
-    pub fn poll_async_ops(&mut self) {
-        for idx in 0..self.tabs.len() {
-            // Outermost first.
-            self.poll_async_ops_at(idx, None);
-            // Then each drilled-in submodule level. Re-read depth each
-            // iteration since the outer poll may have refreshed and
-            // exit_submodule could (in principle) shrink the stack.
-            let mut d = 0;
-            while let Some(t) = self.tabs.get(idx)
-                && d < t.nav_stack.len()
-            {
-                self.poll_async_ops_at(idx, Some(d));
-                d += 1;
+    fn handle_maintenance_route(&mut self, key: &str) {
+        if key == "maintenance:refresh" {
+            let proxy = self.proxy.clone();
+            if let Some(tab) = self.active_focus_mut() {
+                tab.request_repo_health(proxy.as_ref());
+            }
+        } else if let Some(task) = key
+            .strip_prefix("maintenance:run:")
+            .and_then(MaintenanceTask::from_key)
+        {
+            if task == MaintenanceTask::Prune {
+                self.active_modal = Some(ActiveModal::Confirm {
+                    title: "Prune unreachable objects?".to_string(),
+                    body: "git prune deletes every loose object no ref, reflog or index \
+                           entry points at, including the dangling objects listed in the \
+                           report. They can't be recovered afterwards."
+                        .to_string(),
+                    ok_label: "Prune".to_string(),
+                    destructive: true,
+                    action: ConfirmAction::RunMaintenance(task),
+                });
+            } else {
+                self.run_maintenance(task);
+            }
+        } else if let Some(sha) = key.strip_prefix("maintenance:copy:") {
+            match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(sha.to_string())) {
+                Ok(()) => self.toasts.push(ToastSpec::info("Copied object id")),
+                Err(e) => self
+                    .toasts
+                    .push(ToastSpec::error(format!("Copy failed: {e}"))),
             }
         }
-        self.poll_clone_op();
-        self.poll_state_refreshes();
-        self.poll_status_refreshes();
-        self.poll_dirty_checks();
-        self.poll_watcher_inits();
-        self.poll_watcher_events();
-        self.poll_status_safety_net();
-        self.poll_ref_reconciliation();
-        self.drain_ci_receivers();
-        self.poll_ci_refresh();
-        self.drain_diff_stats();
-        self.trigger_diff_stats_fetches();
-        self.drain_avatar_completions();
-        self.request_visible_avatars();
     }
 
-    /// Kick off the very first state refresh for any tab that hasn't
-    /// had one attempted yet. Runs every frame from `before_build` —
-    /// the per-tab `state_refresh_attempted` flag short-circuits after
-    /// the first call so this is cheap on subsequent frames.
-    ///
-    /// Tabs constructed before the proxy was set (the startup path
-    /// through `from_paths`) sit in an empty state until the first
-    /// frame where `proxy` is available; that's also the first frame
-    /// this runs. Tabs created later (via `open_repo_path`) trigger
-    /// their refresh inline at construction time and skip this.
-    fn trigger_initial_state_refreshes(&mut self) {
+    fn run_maintenance(&mut self, task: MaintenanceTask) {
         let Some(proxy) = self.proxy.clone() else {
             return;
         };
-        let show_orphans = self.config.show_orphaned_commits;
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        if tab.start_maintenance(task, proxy) {
+            self.toasts
+                .push(ToastSpec::info(format!("Running {}\u{2026}", task.label())));
+        } else {
+            self.toasts.push(ToastSpec::warning(
+                "Another maintenance command is still running",
+            ));
+        }
+    }
+
+    /// Fold finished CI job lists and log downloads for every tab +
+    /// drilled-in level, scrolling a freshly loaded log to its first
+    /// error.
+    fn drain_ci_logs(&mut self) {
+        let proxy = self.proxy.clone();
+        let mut scroll_to = None;
         for tab in &mut self.tabs {
-            if !tab.state_refresh_attempted {
-                tab.trigger_state_refresh(&proxy, show_orphans);
-            }
+            scroll_to = tab.drain_ci_log(proxy.as_ref()).or(scroll_to);
             for sub in &mut tab.nav_stack {
-                if !sub.state_refresh_attempted {
-                    sub.trigger_state_refresh(&proxy, show_orphans);
-                }
+                scroll_to = sub.drain_ci_log(proxy.as_ref()).or(scroll_to);
             }
         }
+        if let Some(row) = scroll_to {
+            self.scroll_requests.push(ScrollRequest::ToRow {
+                list_key: ci_log_view::CI_LOG_LIST_KEY.to_string(),
+                row,
+                align: ScrollAlignment::Visible,
+            });
+        }
     }
 
-    /// Drain finished `RepoStateResult`s for every tab + drilled-in
-    /// level. On a result, fold via [`RepoTab::apply_state_result`],
-    /// surface any errors as toasts, then dispatch the produced effects
-    /// (kick off diff-stats fanout, kick off per-entity dirty checks,
-    /// flag the watcher for path updates).
-    fn poll_state_refreshes(&mut self) {
-        for tab_idx in 0..self.tabs.len() {
-            // Outermost first, then each drilled-in level.
-            self.poll_state_refresh_at(tab_idx, None);
-            let mut d = 0;
-            while let Some(t) = self.tabs.get(tab_idx)
-                && d < t.nav_stack.len()
-            {
-                self.poll_state_refresh_at(tab_idx, Some(d));
-                d += 1;
+    /// Toast finished CI actions for every tab + drilled-in level.
+    fn drain_ci_actions(&mut self) {
+        for tab in &mut self.tabs {
+            let levels = std::iter::once(tab.drain_ci_action())
+                .chain(tab.nav_stack.iter_mut().map(|sub| sub.drain_ci_action()));
+            for (action, result) in levels.flatten() {
+                self.toasts.push(match result {
+                    Ok(()) => ToastSpec::success(action.done_message()),
+                    Err(e) => ToastSpec::error(e),
+                });
             }
         }
     }
 
-    fn poll_state_refresh_at(&mut self, tab_idx: usize, depth: Option<usize>) {
-        let result = {
-            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
-                return;
-            };
-            let Some(rx) = tab.state_refresh_rx.take() else {
-                return;
-            };
-            match rx.try_recv() {
-                Ok(result) => Some(result),
-                Err(std::sync::mpsc::TryRecvError::Empty) => {
-                    // Worker still running — put the receiver back.
-                    tab.state_refresh_rx = Some(rx);
-                    None
+    fn handle_ci_log_route(&mut self, action: &str) {
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        let Some(state) = tab.ci_log.as_mut() else {
+            return;
+        };
+        let mut scroll_to = None;
+        match action {
+            "close" => tab.ci_log = None,
+            "open" => {
+                let url = state
+                    .selected_job()
+                    .and_then(|j| j.url.clone())
+                    .or_else(|| state.checks.get(state.run).and_then(|c| c.url.clone()));
+                if let Some(url) = url {
+                    let _ = std::process::Command::new("xdg-open").arg(&url).spawn();
+                }
+            }
+            "expand_all" => state.collapsed.clear(),
+            "collapse_all" => {
+                if let Some(Ok(log)) = &state.log {
+                    state.collapsed = (0..log.groups.len()).collect();
+                }
+            }
+            "error" => {
+                if let Some(idx) = state
+                    .log
+                    .as_ref()
+                    .and_then(|l| l.as_ref().ok()?.first_error)
+                {
+                    scroll_to = state.reveal_line(idx);
+                }
+            }
+            "next" | "prev" => {
+                let Some(Ok(log)) = state.log.clone() else {
+                    return;
+                };
+                let matches = log.find(&state.search);
+                let cur = state.search_line;
+                let target = if action == "next" {
+                    matches
+                        .iter()
+                        .copied()
+                        .find(|&m| cur.is_none_or(|c| m > c))
+                        .or(matches.first().copied())
+                } else {
+                    matches
+                        .iter()
+                        .rev()
+                        .copied()
+                        .find(|&m| cur.is_none_or(|c| m < c))
+                        .or(matches.last().copied())
+                };
+                if let Some(line) = target {
+                    state.search_line = Some(line);
+                    scroll_to = state.reveal_line(line);
+                }
+            }
+            other => {
+                if let Some(i) = other.strip_prefix("run:").and_then(|i| i.parse().ok()) {
+                    if i < state.checks.len() && i != state.run {
+                        state.run = i;
+                        tab.request_ci_jobs(proxy);
+                    }
+                } else if let Some(id) = other.strip_prefix("job:").and_then(|i| i.parse().ok()) {
+                    if state.job != Some(id) {
+                        tab.select_ci_job(id, proxy);
+                    }
+                } else if let Some(g) = other.strip_prefix("group:").and_then(|g| g.parse().ok())
+                    && !state.collapsed.remove(&g)
+                {
+                    state.collapsed.insert(g);
+                }
+            }
+        }
+        if let Some(row) = scroll_to {
+            self.scroll_requests.push(ScrollRequest::ToRow {
+                list_key: ci_log_view::CI_LOG_LIST_KEY.to_string(),
+                row,
+                align: ScrollAlignment::Visible,
+            });
+        }
+    }
+
+    fn handle_insights_route(&mut self, action: &str) {
+        let proxy = self.proxy.clone();
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        let Some(state) = tab.insights.as_mut() else {
+            return;
+        };
+        match action {
+            "close" => tab.insights = None,
+            "apply" => tab.request_insights(proxy.as_ref()),
+            "range:custom" => {
+                let since = crate::git::parse_day(&state.custom_since);
+                let until = crate::git::parse_day(&state.custom_until);
+                if let (Some(since_day), Some(until_day)) = (since, until)
+                    && since_day <= until_day
+                {
+                    state.range = InsightsRange::Custom {
+                        since_day,
+                        until_day,
+                    };
+                    tab.request_insights(proxy.as_ref());
+                    return;
+                }
+                self.toasts.push(ToastSpec::warning(
+                    "Custom range needs two YYYY-MM-DD dates, oldest first",
+                ));
+            }
+            other => {
+                if let Some(range) = other
+                    .strip_prefix("range:")
+                    .and_then(InsightsRange::from_key)
+                {
+                    state.range = range;
+                    tab.request_insights(proxy.as_ref());
                 }
-                Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
             }
-        };
-        let Some(result) = result else { return };
-
-        // Apply + collect effects in a scoped borrow.
-        let effects = {
-            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
-                return;
-            };
-            tab.apply_state_result(result)
-        };
-
-        for err in effects.errors {
-            self.toasts.push(ToastSpec::error(err));
         }
+    }
 
-        // Diff-stats fetch for the new commit set. The existing
-        // `trigger_diff_stats_fetches` runs every poll anyway, so this
-        // is informational — but kicking it now starts the fetch in
-        // the same frame instead of waiting for the next.
+    /// Kick off a diff-stats fetch on any tab that doesn't already
+    /// have one in flight or completed for its current commit list.
+    /// Idempotent — `trigger_diff_stats_fetch` short-circuits when
+    /// state says it's already covered.
+    fn trigger_diff_stats_fetches(&mut self) {
         let Some(proxy) = self.proxy.clone() else {
             return;
         };
-        if let Some(tab) = self.tab_at_mut(tab_idx, depth) {
+        for tab in &mut self.tabs {
             tab.trigger_diff_stats_fetch(proxy.clone());
+            for sub in &mut tab.nav_stack {
+                sub.trigger_diff_stats_fetch(proxy.clone());
+            }
         }
+    }
 
-        // Per-entity dirty-check fanout — one worker per submodule, one
-        // per worktree. `tab_id` on each result lets the global drain
-        // route back here even after a tab close / reopen.
-        let (tab_id, repo_workdir) = {
-            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
-                return;
-            };
-            (tab.id, tab.repo.workdir().map(|p| p.to_path_buf()))
-        };
-        self.dirty_checks_in_flight += crate::git_async::spawn_dirty_checks(
-            tab_id,
-            &effects.dirty_checks_submodules,
-            &effects.dirty_checks_worktree_paths,
-            repo_workdir,
-            &self.dirty_check_tx,
-            &proxy,
-        );
+    /// Drain CI and pull request fetch receivers for every tab +
+    /// drilled-in level. Quiet when nothing's in flight.
+    fn drain_ci_receivers(&mut self) {
+        for tab in &mut self.tabs {
+            tab.drain_ci_receivers();
+            tab.drain_pull_receivers();
+            for sub in &mut tab.nav_stack {
+                sub.drain_ci_receivers();
+                sub.drain_pull_receivers();
+            }
+        }
+    }
 
-        // Update the watcher's per-worktree watch set if the resolved
-        // worktree list changed, and refresh its submodule exclusion
-        // list so submodules added/removed mid-session are reflected
-        // without rebuilding the watcher.
-        if effects.watcher_paths_changed
-            && let Some(tab) = self.tab_at_mut(tab_idx, depth)
-        {
-            let common_dir = tab.repo.common_dir().to_path_buf();
-            let worktrees = tab.worktrees.clone();
-            let submodule_paths: Vec<PathBuf> = tab
-                .worktree_views
-                .values()
-                .flat_map(|v| v.submodules.iter().map(|sm| v.path.join(&sm.path)))
-                .collect();
-            if let Some(w) = tab.watcher.as_mut() {
-                w.update_worktree_watches(&worktrees, &common_dir);
-                w.update_submodule_paths(submodule_paths);
+    /// Per-tab CI refresh, each forge host on its own interval (see
+    /// `RepoTab::ci_next_due`):
+    /// - 15 s when its runs are Pending or a push completed within
+    ///   the last 5 minutes (so users see CI light up shortly after
+    ///   they push).
+    /// - 5 min otherwise, stretched when the host's rate limit runs low.
+    ///
+    /// Skips hosts with an in-flight fetch to avoid stacking requests
+    /// when the network is slow. The first call kicks off immediately
+    /// (no `last_ci_fetch` yet).
+    fn poll_ci_refresh(&mut self) {
+        use std::time::Instant;
+        let Some(proxy) = self.proxy.clone() else {
+            return;
+        };
+        let now = Instant::now();
+        // Disjoint borrow: tabs and config are separate fields, so we
+        // can hand both into trigger_ci_fetch without `self` at all.
+        let config = &mut self.config;
+        for outer in &mut self.tabs {
+            // Polls one level. Pulled out of the loop body since we
+            // visit both the outermost tab and every drilled-in level
+            // — each one is a fully-independent CI subject.
+            poll_ci_refresh_for(outer, config, &proxy, now);
+            for sub in &mut outer.nav_stack {
+                poll_ci_refresh_for(sub, config, &proxy, now);
             }
         }
+    }
 
-        // Trigger watcher init once we have submodule paths from the
-        // first state-refresh result. Subsequent calls short-circuit
-        // (idempotent on `watcher.is_some() || watcher_init_rx.is_some()`).
-        if let Some(tab) = self.tab_at_mut(tab_idx, depth) {
-            tab.trigger_watcher_init(&proxy);
+    fn poll_clone_op(&mut self) {
+        // Match-and-take: drain the receiver, drop the slot once Ready
+        // or Disconnected, then act on the result. On success the
+        // dialog auto-closes and the new repo opens as a tab; on
+        // failure we surface an Error modal carrying the captured
+        // stderr (the modal's body wraps long messages).
+        let outcome = match &self.clone_op {
+            Some(op) => match op.rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(std::sync::mpsc::TryRecvError::Empty) => None,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    Some(Err("Clone worker disconnected unexpectedly".to_string()))
+                }
+            },
+            None => None,
+        };
+        let Some(outcome) = outcome else { return };
+        self.clone_op = None;
+        match outcome {
+            Ok(path) => {
+                // Dismiss the Clone modal if it's still open. Users can
+                // dismiss it manually mid-clone too — we still open the
+                // tab when the op completes.
+                if matches!(self.active_modal, Some(ActiveModal::Clone(_))) {
+                    self.active_modal = None;
+                }
+                match RepoTab::open(&path) {
+                    Ok(tab) => {
+                        self.tabs.push(tab);
+                        self.active_tab = self.tabs.len() - 1;
+                        self.apply_identity_profile(self.active_tab);
+                        self.toasts
+                            .push(ToastSpec::success(format!("Cloned {}", path.display())));
+                    }
+                    Err(e) => {
+                        self.active_modal = Some(ActiveModal::Error {
+                            title: "Clone succeeded but open failed".to_string(),
+                            body: format!("{}: {e}", path.display()),
+                        });
+                    }
+                }
+            }
+            Err(stderr) => {
+                self.active_modal = Some(ActiveModal::Error {
+                    title: "Clone failed".to_string(),
+                    body: stderr,
+                });
+            }
         }
     }
 
-    /// Drain finished watcher init results for every tab + drilled-in
-    /// level. On success, populate `watcher` + `watcher_rx`; on
-    /// failure, surface a toast and leave the slots `None` (the safety
-    /// nets still cover refresh).
-    fn poll_watcher_inits(&mut self) {
-        for tab_idx in 0..self.tabs.len() {
-            self.poll_watcher_init_at(tab_idx, None);
-            let mut d = 0;
-            while let Some(t) = self.tabs.get(tab_idx)
-                && d < t.nav_stack.len()
-            {
-                self.poll_watcher_init_at(tab_idx, Some(d));
-                d += 1;
+    /// Poll the async ops on either the outermost tab at `idx`
+    /// (`depth = None`) or one specific drilled-in level
+    /// (`depth = Some(d)` indexes into that tab's `nav_stack`).
+    /// Splitting the resolution this way lets us reuse the body for
+    /// every level in the navigation chain without duplicating it.
+    fn poll_async_ops_at(&mut self, idx: usize, depth: Option<usize>) {
+        // Match-and-take pattern: try_recv each slot, take the slot
+        // if Ready/Disconnected so we drop the receiver. We extract
+        // the slot by mem::replace before mutating self further.
+        for kind in [
+            AsyncKind::Fetch,
+            AsyncKind::Pull,
+            AsyncKind::Push,
+            AsyncKind::Mutation,
+        ] {
+            let Some(tab) = resolve_tab_mut(&mut self.tabs, idx, depth) else {
+                return;
+            };
+            let slot = match kind {
+                AsyncKind::Fetch => &mut tab.fetch_op,
+                AsyncKind::Pull => &mut tab.pull_op,
+                AsyncKind::Push => &mut tab.push_op,
+                AsyncKind::Mutation => &mut tab.mutation_op,
+            };
+            let outcome = match slot {
+                Some(op) => match op.rx.try_recv() {
+                    Ok(result) => Some(Ok((std::mem::take(&mut op.label), result))),
+                    Err(std::sync::mpsc::TryRecvError::Empty) => None,
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => Some(Err(())),
+                },
+                None => None,
+            };
+            let Some(outcome) = outcome else { continue };
+            // Clear slot before triggering refresh so a refresh that
+            // happens to inspect the slot sees it empty.
+            match kind {
+                AsyncKind::Fetch => tab.fetch_op = None,
+                AsyncKind::Pull => tab.pull_op = None,
+                AsyncKind::Push => tab.push_op = None,
+                AsyncKind::Mutation => tab.mutation_op = None,
+            }
+            tab.request_state_refresh(self.proxy.as_ref(), self.config.show_orphaned_commits);
+            match outcome {
+                Ok((label, RemoteOpResult { success: true, .. })) => {
+                    self.toasts
+                        .push(ToastSpec::success(format!("{} {}", kind.past(), label)));
+                    // Any of them may move tags, so relist releases.
+                    if !matches!(kind, AsyncKind::Mutation) {
+                        tab.releases_due = true;
+                    }
+                    // Push success: stamp the time so poll_ci_refresh
+                    // boosts to the 15 s cadence, kick off an immediate
+                    // fetch so the new commit's runs surface as soon as
+                    // GitHub/GitLab pick them up, and relist pull requests.
+                    if matches!(kind, AsyncKind::Push) {
+                        tab.last_push_time = Some(std::time::Instant::now());
+                        tab.pulls_due = true;
+                        tab.last_ci_fetch.clear();
+                        if let Some(proxy) = self.proxy.clone() {
+                            tab.trigger_ci_fetch(&mut self.config, proxy);
+                        }
+                        tab.pr_offer = pull_request_offer(tab, &label);
+                    }
+                }
+                Ok((
+                    label,
+                    RemoteOpResult {
+                        success: false,
+                        error,
+                    },
+                )) => {
+                    let (summary, retryable) = classify_git_error(kind.name(), &error);
+                    let body = if summary.is_empty() {
+                        error.clone()
+                    } else {
+                        format!("{summary}\n\n{error}")
+                    };
+                    // Rejected pushes get a Force-push offer rather than a
+                    // dead-end Error modal. The label was set at op kickoff
+                    // as `"<branch> → <remote>"` — split it back so the
+                    // retry hits the same ref.
+                    if matches!(kind, AsyncKind::Push)
+                        && retryable
+                        && let Some((branch, remote)) = label.split_once(" \u{2192} ")
+                    {
+                        self.active_modal = Some(ActiveModal::Confirm {
+                            title: "Push rejected".to_string(),
+                            body: format!(
+                                "{body}\n\n\
+                                 Force push will overwrite remote history. \
+                                 Only do this if you're certain no one else has based work on \
+                                 {branch}."
+                            ),
+                            ok_label: "Force push".to_string(),
+                            destructive: true,
+                            action: ConfirmAction::ForcePush {
+                                remote: remote.to_string(),
+                                branch: branch.to_string(),
+                            },
+                        });
+                    } else {
+                        self.active_modal = Some(ActiveModal::Error {
+                            title: format!("{} failed", kind.name()),
+                            body,
+                        });
+                    }
+                }
+                Err(()) => {
+                    self.active_modal = Some(ActiveModal::Error {
+                        title: format!("{} failed", kind.name()),
+                        body: format!(
+                            "{} terminated unexpectedly (worker thread disconnected)",
+                            kind.name()
+                        ),
+                    });
+                }
             }
         }
+        // AI generation lives in its own slot since the result type
+        // (Result<AiResponse, String>) doesn't fit the AsyncKind /
+        // RemoteOpResult pattern. Drain it after the git ops so the
+        // commit-message draft fold-back happens in the same frame.
+        self.poll_ai_op_at(idx, depth);
+        self.poll_pull_request_ops_at(idx, depth);
+        self.poll_release_ops_at(idx, depth);
     }
 
-    fn poll_watcher_init_at(&mut self, tab_idx: usize, depth: Option<usize>) {
-        let result = {
-            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
-                return;
-            };
-            let Some(rx) = tab.watcher_init_rx.take() else {
-                return;
-            };
-            match rx.try_recv() {
-                Ok(result) => Some(result),
-                Err(std::sync::mpsc::TryRecvError::Empty) => {
-                    tab.watcher_init_rx = Some(rx);
-                    None
-                }
-                Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
-            }
+    /// Drain the Create Pull Request modal's AI draft and create
+    /// request, the review thread fetch and writes, and the selected
+    /// commit's issue lookups. Create results
+    /// land in the modal when it's still open for the same branch; a
+    /// created pull request is recorded on the tab either way.
+    fn poll_pull_request_ops_at(&mut self, idx: usize, depth: Option<usize>) {
+        use std::sync::mpsc::TryRecvError;
+        let proxy = self.proxy.clone();
+        let Some(tab) = resolve_tab_mut(&mut self.tabs, idx, depth) else {
+            return;
         };
-        let Some(result) = result else { return };
-        match result {
-            Ok((watcher, watcher_rx)) => {
-                if let Some(tab) = self.tab_at_mut(tab_idx, depth) {
-                    tab.watcher = Some(watcher);
-                    tab.watcher_rx = Some(watcher_rx);
-                }
+        if let Some(e) = tab.drain_review_receiver() {
+            self.toasts
+                .push(ToastSpec::error(format!("Review threads: {e}")));
+        }
+        let posted = match tab.review_action_rx.as_ref().map(|rx| rx.try_recv()) {
+            Some(Ok(result)) => Some(result),
+            Some(Err(TryRecvError::Disconnected)) => {
+                Some(Err("Worker thread disconnected".to_string()))
             }
-            Err(e) => {
-                self.toasts.push(ToastSpec::error(format!(
-                    "Filesystem watcher failed to initialize: {e}"
-                )));
+            Some(Err(TryRecvError::Empty)) | None => None,
+        };
+        if let Some(result) = &posted {
+            tab.review_action_rx = None;
+            match result {
+                Ok(()) => self.toasts.push(ToastSpec::success("Review updated")),
+                Err(e) => self.toasts.push(ToastSpec::error(e.clone())),
             }
         }
-    }
-
-    /// Drain debounced `FsChangeKind` events for every tab + drilled-in
-    /// level. Coalesces a burst of events to its highest-priority kind
-    /// (so a stream of working-tree edits + a single git-commit event
-    /// dispatches to GitMetadata, not WorkingTree). Per-kind dispatch:
-    ///
-    /// - `WorkingTree` → trigger working-dir status refresh + per-
-    ///   worktree dirty check fanout
-    /// - `GitMetadata` → reopen repo handles (libgit2 cache bypass) +
-    ///   trigger full state refresh
-    /// - `WorktreeStructure` → same as `GitMetadata` plus the next
-    ///   state refresh's `watcher_paths_changed` effect updates the
-    ///   watch set
-    fn poll_watcher_events(&mut self) {
-        let Some(proxy) = self.proxy.clone() else {
-            return;
+        tab.drain_issue_receivers();
+        if let Some(proxy) = proxy {
+            tab.trigger_review_fetch(proxy.clone(), posted.is_some_and(|r| r.is_ok()));
+            tab.request_selected_issues(proxy);
+        }
+        let draft = match tab.pr_draft_rx.as_ref().map(|rx| rx.try_recv()) {
+            Some(Ok(result)) => Some(result),
+            Some(Err(TryRecvError::Disconnected)) => {
+                Some(Err("AI worker thread disconnected".to_string()))
+            }
+            Some(Err(TryRecvError::Empty)) | None => None,
         };
-        let show_orphans = self.config.show_orphaned_commits;
-        for tab_idx in 0..self.tabs.len() {
-            self.dispatch_watcher_events_at(tab_idx, None, &proxy, show_orphans);
-            let mut d = 0;
-            while let Some(t) = self.tabs.get(tab_idx)
-                && d < t.nav_stack.len()
+        if draft.is_some() {
+            tab.pr_draft_rx = None;
+        }
+        let created = match tab.pr_create_rx.as_ref().map(|(_, rx)| rx.try_recv()) {
+            Some(Ok(result)) => tab.pr_create_rx.take().map(|(remote, _)| (remote, result)),
+            Some(Err(TryRecvError::Disconnected)) => tab
+                .pr_create_rx
+                .take()
+                .map(|(remote, _)| (remote, Err("Worker thread disconnected".to_string()))),
+            Some(Err(TryRecvError::Empty)) | None => None,
+        };
+        if let Some((remote, Ok(result))) = &created {
+            if tab
+                .pr_offer
+                .as_ref()
+                .is_some_and(|(branch, _)| *branch == result.pull.source_branch)
             {
-                self.dispatch_watcher_events_at(tab_idx, Some(d), &proxy, show_orphans);
-                d += 1;
+                tab.pr_offer = None;
             }
+            tab.record_created_pull(remote.clone(), result.pull.clone());
         }
-    }
 
-    /// 30 s safety-net status refresh on the active tab. Catches the
-    /// case where a watcher event was dropped (inotify queue overflow,
-    /// filesystem races, NFS-style fakery) by re-querying status
-    /// unconditionally on a long interval. Cheap relative to a full
-    /// state refresh — just the working-dir status walk on the active
-    /// view's repo.
-    ///
-    /// Background tabs aren't covered here; they get refreshed when
-    /// the user switches to them or via their own watcher when one
-    /// fires for that tab. The legacy used the same shape — the
-    /// active tab is what the user is staring at, the safety net's
-    /// job is to keep that tab from going stale silently.
-    fn poll_status_safety_net(&mut self) {
-        let now = std::time::Instant::now();
-        let expired = now.duration_since(self.last_status_refresh).as_secs() >= 30;
-        let Some(proxy) = self.proxy.clone() else {
+        let form = match &mut self.active_modal {
+            Some(ActiveModal::CreatePullRequest { form }) => Some(form),
+            _ => None,
+        };
+        if let Some(result) = draft
+            && let Some(form) = form
+        {
+            form.generating = false;
+            match result {
+                Ok(response) => {
+                    form.title = response.subject;
+                    form.body = response.body;
+                    form.error = None;
+                }
+                Err(e) => form.error = Some(format!("AI generate: {e}")),
+            }
+        }
+        let Some((_, result)) = created else {
             return;
         };
-        if let Some(tab) = self.active_focus_mut() {
-            if expired {
-                tab.status_dirty = true;
+        let form = match &mut self.active_modal {
+            Some(ActiveModal::CreatePullRequest { form }) => Some(form),
+            _ => None,
+        };
+        match (result, form) {
+            (Ok(created), Some(form)) if form.submitting => {
+                form.submitting = false;
+                form.warnings = created.warnings;
+                form.created = Some(created.pull);
             }
-            if tab.status_dirty {
-                tab.trigger_status_refresh(&proxy);
+            (Ok(created), _) => {
+                self.toasts.push(ToastSpec::success(format!(
+                    "Opened {} {}",
+                    created.pull.reference(),
+                    created.pull.url
+                )));
+                for warning in created.warnings {
+                    self.toasts.push(ToastSpec::warning(warning));
+                }
+            }
+            (Err(e), Some(form)) if form.submitting => {
+                form.submitting = false;
+                form.error = Some(e);
             }
+            (Err(e), _) => self.toasts.push(ToastSpec::error(e)),
         }
-        // `trigger_status_refresh` clears the tab's dirty bit only
-        // when it successfully spawns a worker.
     }
 
-    /// 5 s ref_fingerprint reconciliation on the active tab. Cheap
-    /// hash of `git_dir/refs/` content; if it diverges from the last
-    /// cached value (something updated the refdb that the watcher
-    /// didn't surface) → reopen + trigger a full state refresh.
-    /// Belt-and-braces against missed `GitMetadata` events; the
-    /// reopen specifically handles libgit2's refdb cache going stale
-    /// independently of watcher delivery.
-    ///
-    /// Skipped when `tab.ref_fingerprint == 0` (no baseline yet — the
-    /// initial state refresh hasn't landed) and when a state refresh
-    /// is already in flight.
-    fn poll_ref_reconciliation(&mut self) {
-        let now = std::time::Instant::now();
-        if now.duration_since(self.last_ref_check).as_secs() < 5 {
-            return;
-        }
-        self.last_ref_check = now;
-
-        let Some(proxy) = self.proxy.clone() else {
+    /// Drain the release listing and the Create Release modal's create
+    /// request. A created release is recorded on the tab and lands in
+    /// the modal when it's still open for the same tag.
+    fn poll_release_ops_at(&mut self, idx: usize, depth: Option<usize>) {
+        use std::sync::mpsc::TryRecvError;
+        let Some(tab) = resolve_tab_mut(&mut self.tabs, idx, depth) else {
             return;
         };
-        let show_orphans = self.config.show_orphaned_commits;
-        let Some(tab) = self.active_focus_mut() else {
-            return;
+        tab.drain_release_receiver();
+        let created = match tab.release_create_rx.as_ref().map(|(_, rx)| rx.try_recv()) {
+            Some(Ok(result)) => tab.release_create_rx.take().map(|(tag, _)| (tag, result)),
+            Some(Err(TryRecvError::Disconnected)) => tab
+                .release_create_rx
+                .take()
+                .map(|(tag, _)| (tag, Err("Worker thread disconnected".to_string()))),
+            Some(Err(TryRecvError::Empty)) | None => None,
         };
-        if tab.ref_fingerprint == 0 || tab.state_refresh_rx.is_some() {
+        let Some((tag, result)) = created else {
             return;
+        };
+        if let Ok(release) = &result {
+            tab.record_release(release.clone());
         }
-        let fresh = crate::git::ref_fingerprint(tab.repo.git_dir());
-        if fresh != 0 && fresh != tab.ref_fingerprint {
-            tab.reopen_repo_handles();
-            tab.trigger_state_refresh(&proxy, show_orphans);
+        let form = match &mut self.active_modal {
+            Some(ActiveModal::CreateRelease { form }) if form.submitting && form.tag == tag => {
+                Some(form)
+            }
+            _ => None,
+        };
+        match (result, form) {
+            (Ok(release), Some(form)) => {
+                form.submitting = false;
+                form.created = Some(release);
+            }
+            (Ok(release), None) => self.toasts.push(ToastSpec::success(format!(
+                "Released {} {}",
+                release.title(),
+                release.url
+            ))),
+            (Err(e), Some(form)) => {
+                form.submitting = false;
+                form.error = Some(e);
+            }
+            (Err(e), None) => self.toasts.push(ToastSpec::error(e)),
         }
     }
 
-    fn dispatch_watcher_events_at(
-        &mut self,
-        tab_idx: usize,
-        depth: Option<usize>,
-        proxy: &winit::event_loop::EventLoopProxy<()>,
-        show_orphans: bool,
-    ) {
-        // Drain + coalesce in one borrow scope so the dispatch below
-        // can take its own borrow.
-        let max_kind = {
-            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
+    fn poll_ai_op_at(&mut self, idx: usize, depth: Option<usize>) {
+        let (target_path, result) = {
+            let Some(tab) = resolve_tab_mut(&mut self.tabs, idx, depth) else {
                 return;
             };
-            let Some(rx) = tab.watcher_rx.as_ref() else {
+            let Some(op) = tab.ai_op.as_mut() else {
                 return;
             };
-            let mut max_kind: Option<crate::watcher::FsChangeKind> = None;
-            while let Ok(kind) = rx.try_recv() {
-                max_kind = Some(match max_kind {
-                    Some(prev) if prev.priority() >= kind.priority() => prev,
-                    _ => kind,
-                });
+            match op.rx.try_recv() {
+                Ok(result) => (std::mem::take(&mut op.target_path), result),
+                Err(std::sync::mpsc::TryRecvError::Empty) => return,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => (
+                    std::mem::take(&mut op.target_path),
+                    Err("AI worker thread disconnected".to_string()),
+                ),
             }
-            max_kind
         };
-        let Some(kind) = max_kind else { return };
-
-        use crate::watcher::FsChangeKind;
-        match kind {
-            FsChangeKind::WorkingTree => {
-                let (tab_id, repo_workdir, worktree_paths) = {
-                    let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
-                        return;
-                    };
-                    tab.status_dirty = true;
-                    tab.trigger_status_refresh(proxy);
-                    (
-                        tab.id,
-                        tab.repo.workdir().map(|p| p.to_path_buf()),
-                        tab.worktree_order.clone(),
-                    )
-                };
-                // Re-check worktree dirty state — a working-tree edit
-                // may have flipped a worktree's pill.
-                self.dirty_checks_in_flight += crate::git_async::spawn_dirty_checks(
-                    tab_id,
-                    &[],
-                    &worktree_paths,
-                    repo_workdir,
-                    &self.dirty_check_tx,
-                    proxy,
-                );
-            }
-            FsChangeKind::GitMetadata | FsChangeKind::WorktreeStructure => {
-                if let Some(tab) = self.tab_at_mut(tab_idx, depth) {
-                    // Bypass libgit2's refdb cache before triggering
-                    // the refresh — without this the worker would
-                    // still see the pre-event HEAD/refs.
-                    tab.reopen_repo_handles();
-                    tab.trigger_state_refresh(proxy, show_orphans);
+        let Some(tab) = resolve_tab_mut(&mut self.tabs, idx, depth) else {
+            return;
+        };
+        tab.ai_op = None;
+        match result {
+            Ok(response) => {
+                if let Some(view) = tab.worktree_views.get_mut(&target_path) {
+                    view.commit_subject = response.subject;
+                    view.commit_body = response.body;
+                    self.toasts
+                        .push(ToastSpec::success("Generated commit message"));
+                } else {
+                    self.toasts.push(ToastSpec::warning(
+                        "AI result dropped: target worktree no longer present",
+                    ));
                 }
-                // WorktreeStructure additionally needs `watcher_paths_changed`
-                // handling, which falls out automatically: the state
-                // refresh's apply step calls `merge_worktree_views`
-                // which sets the flag, and `poll_state_refresh_at`
-                // calls `update_worktree_watches`.
             }
+            Err(msg) => {
+                self.toasts
+                    .push(ToastSpec::error(format!("AI generate: {msg}")));
+            }
+        }
+    }
+
+    /// Common entry point: bail with a toast if `slot` is occupied or
+    /// if there's no proxy / no remotes. Returns the workdir path for
+    /// the caller to hand to the async function.
+    fn prepare_remote_op(
+        &mut self,
+        kind: AsyncKind,
+        require_remote: bool,
+    ) -> Option<(std::path::PathBuf, winit::event_loop::EventLoopProxy<()>)> {
+        let proxy = match self.proxy.clone() {
+            Some(p) => p,
+            None => {
+                self.toasts.push(ToastSpec::error(format!(
+                    "{} unavailable: event loop proxy missing",
+                    kind.name()
+                )));
+                return None;
+            }
+        };
+        // Operate on the *focused* RepoTab — when the user has drilled
+        // into a submodule, fetch / push / pull target that submodule's
+        // repo, not the outermost one. The op slot also lives on the
+        // focused tab so concurrent parent + child ops don't share a
+        // single slot.
+        let tab = self.active_focus()?;
+        if require_remote && !tab.repo.has_remotes() {
+            self.toasts.push(ToastSpec::error(
+                "No remotes configured for this repository",
+            ));
+            return None;
+        }
+        let busy = match kind {
+            AsyncKind::Fetch => tab.fetch_op.is_some(),
+            AsyncKind::Pull => tab.pull_op.is_some(),
+            AsyncKind::Push => tab.push_op.is_some(),
+            AsyncKind::Mutation => tab.mutation_op.is_some(),
+        };
+        if busy {
+            self.toasts.push(ToastSpec::info(format!(
+                "{} already in progress",
+                kind.name()
+            )));
+            return None;
         }
+        // Run the git CLI in the active worktree's working directory so
+        // ops resolve HEAD against that worktree (push picks up the right
+        // branch, fetch updates the right remote-tracking refs).
+        Some((tab.active_repo().git_command_dir(), proxy))
     }
 
-    /// Drain finished `StatusResult`s for every tab + drilled-in level.
-    fn poll_status_refreshes(&mut self) {
-        for tab_idx in 0..self.tabs.len() {
-            self.poll_status_refresh_at(tab_idx, None);
-            let mut d = 0;
-            while let Some(t) = self.tabs.get(tab_idx)
-                && d < t.nav_stack.len()
-            {
-                self.poll_status_refresh_at(tab_idx, Some(d));
-                d += 1;
-            }
+    fn fetch(&mut self) {
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Fetch, true) else {
+            return;
+        };
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        let remote = tab
+            .repo
+            .default_remote()
+            .unwrap_or_else(|_| "origin".to_string());
+        // Auto-fix missing fetch refspec for bare-cloned remotes — the
+        // old whisper-git pattern. Silent on success since this is a
+        // common state on fresh `git clone --bare` repos.
+        if tab.repo.remote_missing_fetch_refspec(&remote) {
+            let _ = tab.repo.add_default_fetch_refspec(&remote);
         }
+        let rx = crate::git::fetch_remote_async(wd, remote.clone(), proxy);
+        tab.fetch_op = Some(TimedOp::new(rx, format!("from {remote}")));
+        self.toasts
+            .push(ToastSpec::info(format!("Fetching from {remote}…")));
     }
 
-    fn poll_status_refresh_at(&mut self, tab_idx: usize, depth: Option<usize>) {
-        let result = {
-            let Some(tab) = self.tab_at_mut(tab_idx, depth) else {
-                return;
-            };
-            let Some(rx) = tab.status_rx.take() else {
-                return;
-            };
-            match rx.try_recv() {
-                Ok(result) => Some(result),
-                Err(std::sync::mpsc::TryRecvError::Empty) => {
-                    tab.status_rx = Some(rx);
-                    None
-                }
-                Err(std::sync::mpsc::TryRecvError::Disconnected) => None,
-            }
+    fn fetch_remote(&mut self, remote: String) {
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Fetch, true) else {
+            return;
         };
-        let Some(result) = result else { return };
-        let needs_follow_up = if let Some(tab) = self.tab_at_mut(tab_idx, depth) {
-            tab.apply_status_result(result);
-            tab.status_dirty
-        } else {
-            false
+        let Some(tab) = self.active_focus_mut() else {
+            return;
         };
-        if needs_follow_up
-            && let Some(proxy) = self.proxy.clone()
-            && let Some(tab) = self.tab_at_mut(tab_idx, depth)
-        {
-            tab.trigger_status_refresh(&proxy);
-        }
-        // Stamp last_status_refresh on the active tab's result so the
-        // 30 s safety net doesn't redundantly fire.
-        if depth.is_none() && tab_idx == self.active_tab {
-            self.last_status_refresh = std::time::Instant::now();
+        if tab.repo.remote_missing_fetch_refspec(&remote) {
+            let _ = tab.repo.add_default_fetch_refspec(&remote);
         }
+        let rx = crate::git::fetch_remote_async(wd, remote.clone(), proxy);
+        tab.fetch_op = Some(TimedOp::new(rx, format!("from {remote}")));
+        self.toasts
+            .push(ToastSpec::info(format!("Fetching from {remote}…")));
     }
 
-    /// Drain the global per-entity dirty-check channel and route each
-    /// result back to its originating `RepoTab` by `tab_id`. Stale
-    /// results from closed tabs match no live tab and drop silently.
-    fn poll_dirty_checks(&mut self) {
-        if self.dirty_checks_in_flight == 0 {
+    /// Fetch every `refs/notes/*` ref from `remote`. Plain fetches
+    /// leave notes behind, since the default refspec only covers
+    /// branches.
+    fn fetch_notes(&mut self, remote: String) {
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Fetch, true) else {
             return;
-        }
-        loop {
-            match self.dirty_check_rx.try_recv() {
-                Ok(result) => {
-                    self.dirty_checks_in_flight = self.dirty_checks_in_flight.saturating_sub(1);
-                    let target_id = result.tab_id();
-                    if let Some(tab) = self.find_tab_by_id_mut(target_id) {
-                        tab.apply_dirty_check_result(result);
-                    }
-                }
-                Err(std::sync::mpsc::TryRecvError::Empty) => break,
-                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                    // Should never happen — we hold the sender.
-                    self.dirty_checks_in_flight = 0;
-                    break;
-                }
-            }
-        }
+        };
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        let rx = crate::git::fetch_notes_async(wd, remote.clone(), proxy);
+        tab.fetch_op = Some(TimedOp::new(rx, format!("notes from {remote}")));
+        self.toasts
+            .push(ToastSpec::info(format!("Fetching notes from {remote}…")));
     }
 
-    /// Locate a tab (or drilled-in submodule view) by its stable `id`.
-    /// Walks every tab's `nav_stack` so dirty-check results targeting a
-    /// drilled-in level land on the right frame.
-    fn find_tab_by_id_mut(&mut self, id: u64) -> Option<&mut RepoTab> {
-        for tab in &mut self.tabs {
-            if tab.id == id {
-                return Some(tab);
-            }
-            for sub in &mut tab.nav_stack {
-                if sub.id == id {
-                    return Some(sub);
+    fn open_bundle_create_modal(&mut self) {
+        let Some(tab) = self.active_focus() else {
+            return;
+        };
+        let branch = tab.current_branch();
+        let revs = if branch.is_empty() {
+            "--all".to_string()
+        } else {
+            branch.to_string()
+        };
+        self.active_modal = Some(ActiveModal::BundleCreate {
+            form: BundleCreateForm { revs, plan: None },
+        });
+    }
+
+    fn handle_bundle_create_route(&mut self, key: &str) {
+        match key {
+            "bundle_create:check" => {
+                let Some(ActiveModal::BundleCreate { form }) = &self.active_modal else {
+                    return;
+                };
+                let revs: Vec<String> = form.revs.split_whitespace().map(str::to_string).collect();
+                let Some(tab) = self.active_focus() else {
+                    return;
+                };
+                let plan = tab
+                    .active_repo()
+                    .bundle_plan(&revs)
+                    .map_err(|e| format!("{e:#}"));
+                if let Some(ActiveModal::BundleCreate { form }) = &mut self.active_modal {
+                    form.plan = Some(plan);
                 }
             }
+            "bundle_create:execute" => self.create_bundle_from_modal(),
+            _ => {}
         }
-        None
     }
 
-    /// Address a tab + optional drill-in depth as a single mut ref.
-    /// `depth: None` is the outer tab; `Some(d)` is `nav_stack[d]`.
-    fn tab_at_mut(&mut self, tab_idx: usize, depth: Option<usize>) -> Option<&mut RepoTab> {
-        let outer = self.tabs.get_mut(tab_idx)?;
-        match depth {
-            None => Some(outer),
-            Some(d) => outer.nav_stack.get_mut(d),
+    /// Ask where to write the bundle, then run `git bundle create` on
+    /// the mutation slot. Only reachable after a successful Check.
+    fn create_bundle_from_modal(&mut self) {
+        let Some(ActiveModal::BundleCreate { form }) = &self.active_modal else {
+            return;
+        };
+        if !matches!(&form.plan, Some(Ok(plan)) if plan.commit_count > 0) {
+            return;
         }
+        let revs: Vec<String> = form.revs.split_whitespace().map(str::to_string).collect();
+        let file_name = self
+            .active_focus()
+            .map(|t| format!("{}.bundle", t.repo_name))
+            .unwrap_or_else(|| "repo.bundle".to_string());
+        let Some(dest) = rfd::FileDialog::new()
+            .set_title("Save bundle")
+            .set_file_name(file_name)
+            .save_file()
+        else {
+            return;
+        };
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Mutation, false) else {
+            return;
+        };
+        self.active_modal = None;
+        let name = dest
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let rx = crate::git::create_bundle_async(wd, dest, revs, proxy);
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        tab.mutation_op = Some(TimedOp::new(rx, format!("bundle {name}")));
+        self.toasts
+            .push(ToastSpec::info(format!("Writing bundle {name}…")));
     }
 
-    /// Fold any completed Gravatar downloads into the in-memory cache
-    /// so the next render pass can reach for them via `cache.get(email)`.
-    fn drain_avatar_completions(&mut self) {
-        if let Some(cache) = self.avatar_cache.as_mut() {
-            cache.drain_completions();
-        }
+    fn open_bundle_import_picker(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Import bundle")
+            .add_filter("Git bundles", &["bundle"])
+            .add_filter("All files", &["*"])
+            .pick_file()
+        else {
+            return;
+        };
+        let remote = bundle_remote_name(&path);
+        self.open_bundle_import_modal(path, remote);
     }
 
-    /// Kick off Gravatar fetches for every author we know about.
-    /// `AvatarCache::request` is idempotent — repeats short-circuit on
-    /// "already requested" — so we just iterate freely each tick.
-    /// The cache is created lazily on the first poll where `proxy`
-    /// is set, since avatars need an event-loop wake to surface
-    /// completions back to the UI.
-    fn request_visible_avatars(&mut self) {
-        let Some(proxy) = self.proxy.clone() else {
+    /// Re-open a bundle-backed remote's file, e.g. after it was replaced
+    /// with a newer bundle, to check prerequisites before fetching.
+    fn inspect_bundle_remote(&mut self, remote: String) {
+        let Some(url) = self.active_focus().and_then(|t| t.repo.remote_url(&remote)) else {
             return;
         };
-        let cache = self
-            .avatar_cache
-            .get_or_insert_with(|| crate::avatar::AvatarCache::new(proxy));
-        for tab in &self.tabs {
-            for c in &tab.commits {
-                cache.request(&c.author_email);
+        self.open_bundle_import_modal(PathBuf::from(url), remote);
+    }
+
+    fn open_bundle_import_modal(&mut self, path: PathBuf, remote: String) {
+        let Some(tab) = self.active_focus() else {
+            return;
+        };
+        match tab.repo.inspect_bundle(&path) {
+            Ok(info) => {
+                self.active_modal = Some(ActiveModal::BundleImport {
+                    form: BundleImportForm { path, info, remote },
+                });
             }
-            for sub in &tab.nav_stack {
-                for c in &sub.commits {
-                    cache.request(&c.author_email);
-                }
+            Err(e) => {
+                self.active_modal = Some(ActiveModal::Error {
+                    title: "Can't read bundle".to_string(),
+                    body: format!("{e:#}"),
+                });
             }
         }
     }
 
-    /// Drain any completed diff-stats fetches across every tab + level.
-    fn drain_diff_stats(&mut self) {
-        for tab in &mut self.tabs {
-            tab.drain_diff_stats();
-            for sub in &mut tab.nav_stack {
-                sub.drain_diff_stats();
+    /// Register the bundle as a remote (unless a remote of that name
+    /// already points at it) and fetch from it through the normal
+    /// fetch path, so its branches land under `<remote>/…` in the
+    /// sidebar.
+    fn import_bundle_from_modal(&mut self) {
+        let Some(ActiveModal::BundleImport { form }) = &self.active_modal else {
+            return;
+        };
+        let remote = form.remote.trim().to_string();
+        let url = form.path.to_string_lossy().to_string();
+        if remote.is_empty() || !form.info.missing_prerequisites.is_empty() {
+            return;
+        }
+        let proxy = self.proxy.clone();
+        let show_orphans = self.config.show_orphaned_commits;
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        match tab.repo.remote_url(&remote) {
+            Some(existing) if existing == url => {}
+            Some(_) => {
+                self.toasts.push(ToastSpec::warning(format!(
+                    "Remote '{remote}' already exists with a different URL"
+                )));
+                return;
+            }
+            None => {
+                if let Err(e) = tab.repo.add_remote(&remote, &url) {
+                    self.toasts
+                        .push(ToastSpec::error(format!("Add remote failed: {e}")));
+                    return;
+                }
+                tab.request_state_refresh(proxy.as_ref(), show_orphans);
             }
         }
+        self.active_modal = None;
+        self.fetch_remote(remote);
     }
 
-    /// Kick off a diff-stats fetch on any tab that doesn't already
-    /// have one in flight or completed for its current commit list.
-    /// Idempotent — `trigger_diff_stats_fetch` short-circuits when
-    /// state says it's already covered.
-    fn trigger_diff_stats_fetches(&mut self) {
-        let Some(proxy) = self.proxy.clone() else {
+    fn fetch_all(&mut self) {
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Fetch, true) else {
             return;
         };
-        for tab in &mut self.tabs {
-            tab.trigger_diff_stats_fetch(proxy.clone());
-            for sub in &mut tab.nav_stack {
-                sub.trigger_diff_stats_fetch(proxy.clone());
-            }
-        }
+        let rx = crate::git::fetch_all_async(wd, proxy);
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        tab.fetch_op = Some(TimedOp::new(rx, "all remotes".to_string()));
+        self.toasts
+            .push(ToastSpec::info("Fetching all remotes…".to_string()));
     }
 
-    /// Drain CI fetch receivers for every tab + drilled-in level.
-    /// Quiet when nothing's in flight.
-    fn drain_ci_receivers(&mut self) {
-        for tab in &mut self.tabs {
-            tab.drain_ci_receivers();
-            for sub in &mut tab.nav_stack {
-                sub.drain_ci_receivers();
-            }
+    fn push(&mut self) {
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Push, true) else {
+            return;
+        };
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        let remote = tab
+            .repo
+            .default_remote()
+            .unwrap_or_else(|_| "origin".to_string());
+        let branch = tab.current_branch().to_string();
+        if branch.is_empty() {
+            self.toasts
+                .push(ToastSpec::error("Push: HEAD is detached, no branch"));
+            return;
         }
+        let rx = crate::git::push_remote_async(wd, remote.clone(), branch.clone(), proxy);
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        tab.push_op = Some(TimedOp::new(rx, format!("{branch} → {remote}")));
+        self.toasts
+            .push(ToastSpec::info(format!("Pushing {branch} to {remote}…")));
     }
 
-    /// Per-tab CI refresh on a dynamic interval:
-    /// - 15 s when any provider is Pending or a push completed within
-    ///   the last 5 minutes (so users see CI light up shortly after
-    ///   they push).
-    /// - 5 min otherwise.
-    ///
-    /// Skips tabs with an in-flight fetch to avoid stacking requests
-    /// when the network is slow. The first call kicks off immediately
-    /// (no `last_ci_fetch` yet).
-    fn poll_ci_refresh(&mut self) {
-        use std::time::Instant;
-        let Some(proxy) = self.proxy.clone() else {
+    /// Pull the upstream of the current branch — `git pull <remote> <branch>`,
+    /// where `<remote>` is `default_remote()` (the upstream's remote when
+    /// tracking info exists; falls back to `origin`) and `<branch>` is
+    /// the current branch shorthand. Detached HEAD is rejected up-front
+    /// since pull has no source to use.
+    fn pull(&mut self) {
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Pull, true) else {
             return;
         };
-        let now = Instant::now();
-        // Disjoint borrow: tabs and config are separate fields, so we
-        // can hand both into trigger_ci_fetch without `self` at all.
-        let config = &mut self.config;
-        for outer in &mut self.tabs {
-            // Polls one level. Pulled out of the loop body since we
-            // visit both the outermost tab and every drilled-in level
-            // — each one is a fully-independent CI subject.
-            poll_ci_refresh_for(outer, config, &proxy, now);
-            for sub in &mut outer.nav_stack {
-                poll_ci_refresh_for(sub, config, &proxy, now);
-            }
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        let remote = tab
+            .repo
+            .default_remote()
+            .unwrap_or_else(|_| "origin".to_string());
+        let branch = tab.current_branch().to_string();
+        if branch.is_empty() {
+            self.toasts
+                .push(ToastSpec::error("Pull: HEAD is detached, no branch"));
+            return;
         }
+        let rx = crate::git::pull_remote_async(wd, remote.clone(), branch.clone(), proxy);
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        tab.pull_op = Some(TimedOp::new(rx, format!("{remote}/{branch}")));
+        self.toasts
+            .push(ToastSpec::info(format!("Pulling {remote}/{branch}…")));
     }
 
-    fn poll_clone_op(&mut self) {
-        // Match-and-take: drain the receiver, drop the slot once Ready
-        // or Disconnected, then act on the result. On success the
-        // dialog auto-closes and the new repo opens as a tab; on
-        // failure we surface an Error modal carrying the captured
-        // stderr (the modal's body wraps long messages).
-        let outcome = match &self.clone_op {
-            Some(op) => match op.rx.try_recv() {
-                Ok(result) => Some(result),
-                Err(std::sync::mpsc::TryRecvError::Empty) => None,
-                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
-                    Some(Err("Clone worker disconnected unexpectedly".to_string()))
-                }
-            },
-            None => None,
+    fn cherry_pick(&mut self, oid: git2::Oid) {
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Mutation, false) else {
+            return;
         };
-        let Some(outcome) = outcome else { return };
-        self.clone_op = None;
-        match outcome {
-            Ok(path) => {
-                // Dismiss the Clone modal if it's still open. Users can
-                // dismiss it manually mid-clone too — we still open the
-                // tab when the op completes.
-                if matches!(self.active_modal, Some(ActiveModal::Clone(_))) {
-                    self.active_modal = None;
-                }
-                match RepoTab::open(&path) {
-                    Ok(tab) => {
-                        self.tabs.push(tab);
-                        self.active_tab = self.tabs.len() - 1;
-                        self.toasts
-                            .push(ToastSpec::success(format!("Cloned {}", path.display())));
-                    }
-                    Err(e) => {
-                        self.active_modal = Some(ActiveModal::Error {
-                            title: "Clone succeeded but open failed".to_string(),
-                            body: format!("{}: {e}", path.display()),
-                        });
-                    }
-                }
-            }
-            Err(stderr) => {
-                self.active_modal = Some(ActiveModal::Error {
-                    title: "Clone failed".to_string(),
-                    body: stderr,
-                });
-            }
-        }
+        let sha = oid.to_string();
+        let rx = crate::git::cherry_pick_async(wd, sha.clone(), proxy);
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        let short = &sha[..7];
+        tab.mutation_op = Some(TimedOp::new(rx, format!("cherry-pick {short}")));
+        self.toasts
+            .push(ToastSpec::info(format!("Cherry-picking {short}…")));
     }
 
-    /// Poll the async ops on either the outermost tab at `idx`
-    /// (`depth = None`) or one specific drilled-in level
-    /// (`depth = Some(d)` indexes into that tab's `nav_stack`).
-    /// Splitting the resolution this way lets us reuse the body for
-    /// every level in the navigation chain without duplicating it.
-    fn poll_async_ops_at(&mut self, idx: usize, depth: Option<usize>) {
-        // Match-and-take pattern: try_recv each slot, take the slot
-        // if Ready/Disconnected so we drop the receiver. We extract
-        // the slot by mem::replace before mutating self further.
-        for kind in [
-            AsyncKind::Fetch,
-            AsyncKind::Pull,
-            AsyncKind::Push,
-            AsyncKind::Mutation,
-        ] {
-            let Some(tab) = resolve_tab_mut(&mut self.tabs, idx, depth) else {
-                return;
-            };
-            let slot = match kind {
-                AsyncKind::Fetch => &mut tab.fetch_op,
-                AsyncKind::Pull => &mut tab.pull_op,
-                AsyncKind::Push => &mut tab.push_op,
-                AsyncKind::Mutation => &mut tab.mutation_op,
-            };
-            let outcome = match slot {
-                Some(op) => match op.rx.try_recv() {
-                    Ok(result) => Some(Ok((std::mem::take(&mut op.label), result))),
-                    Err(std::sync::mpsc::TryRecvError::Empty) => None,
-                    Err(std::sync::mpsc::TryRecvError::Disconnected) => Some(Err(())),
-                },
-                None => None,
-            };
-            let Some(outcome) = outcome else { continue };
-            // Clear slot before triggering refresh so a refresh that
-            // happens to inspect the slot sees it empty.
-            match kind {
-                AsyncKind::Fetch => tab.fetch_op = None,
-                AsyncKind::Pull => tab.pull_op = None,
-                AsyncKind::Push => tab.push_op = None,
-                AsyncKind::Mutation => tab.mutation_op = None,
-            }
-            tab.request_state_refresh(self.proxy.as_ref(), self.config.show_orphaned_commits);
-            match outcome {
-                Ok((label, RemoteOpResult { success: true, .. })) => {
-                    self.toasts
-                        .push(ToastSpec::success(format!("{} {}", kind.past(), label)));
-                    // Push success: stamp the time so poll_ci_refresh
-                    // boosts to the 15 s cadence, and kick off an
-                    // immediate fetch so the new commit's runs surface
-                    // as soon as GitHub/GitLab pick them up.
-                    if matches!(kind, AsyncKind::Push) {
-                        tab.last_push_time = Some(std::time::Instant::now());
-                        if let Some(proxy) = self.proxy.clone() {
-                            tab.trigger_ci_fetch(&mut self.config, proxy);
-                        }
-                    }
-                }
-                Ok((
-                    label,
-                    RemoteOpResult {
-                        success: false,
-                        error,
-                    },
-                )) => {
-                    let (summary, retryable) = classify_git_error(kind.name(), &error);
-                    let body = if summary.is_empty() {
-                        error.clone()
-                    } else {
-                        format!("{summary}\n\n{error}")
-                    };
-                    // Rejected pushes get a Force-push offer rather than a
-                    // dead-end Error modal. The label was set at op kickoff
-                    // as `"<branch> → <remote>"` — split it back so the
-                    // retry hits the same ref.
-                    if matches!(kind, AsyncKind::Push)
-                        && retryable
-                        && let Some((branch, remote)) = label.split_once(" \u{2192} ")
-                    {
-                        self.active_modal = Some(ActiveModal::Confirm {
-                            title: "Push rejected".to_string(),
-                            body: format!(
-                                "{body}\n\n\
-                                 Force push will overwrite remote history. \
-                                 Only do this if you're certain no one else has based work on \
-                                 {branch}."
-                            ),
-                            ok_label: "Force push".to_string(),
-                            destructive: true,
-                            action: ConfirmAction::ForcePush {
-                                remote: remote.to_string(),
-                                branch: branch.to_string(),
-                            },
-                        });
-                    } else {
-                        self.active_modal = Some(ActiveModal::Error {
-                            title: format!("{} failed", kind.name()),
-                            body,
-                        });
-                    }
-                }
-                Err(()) => {
-                    self.active_modal = Some(ActiveModal::Error {
-                        title: format!("{} failed", kind.name()),
-                        body: format!(
-                            "{} terminated unexpectedly (worker thread disconnected)",
-                            kind.name()
-                        ),
-                    });
+    fn revert(&mut self, oid: git2::Oid) {
+        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Mutation, false) else {
+            return;
+        };
+        let sha = oid.to_string();
+        let rx = crate::git::revert_commit_async(wd, sha.clone(), proxy);
+        let Some(tab) = self.active_focus_mut() else {
+            return;
+        };
+        let short = &sha[..7];
+        tab.mutation_op = Some(TimedOp::new(rx, format!("revert {short}")));
+        self.toasts
+            .push(ToastSpec::info(format!("Reverting {short}…")));
+    }
+
+    /// Restore the worktree copy of `path` to its version at `oid` (or
+    /// `oid`'s first parent), asking first when the copy has local
+    /// changes that would be lost.
+    fn restore_file_at(&mut self, oid: git2::Oid, path: String, parent: bool) {
+        let Some(tab) = self.active_focus() else {
+            return;
+        };
+        let source = if parent {
+            match tab.repo.first_parent(oid) {
+                Ok(p) => p,
+                Err(e) => {
+                    self.toasts
+                        .push(ToastSpec::error(format!("Restore failed: {e:#}")));
+                    return;
                 }
             }
+        } else {
+            oid
+        };
+        match tab.active_repo().path_has_local_changes(&path) {
+            Ok(false) => self.restore_path(source, path),
+            Ok(true) => {
+                let short = &source.to_string()[..7];
+                self.active_modal = Some(ActiveModal::Confirm {
+                    title: "Restore from history".to_string(),
+                    body: format!(
+                        "'{path}' has uncommitted changes. Overwrite the working copy with the version at {short}?"
+                    ),
+                    ok_label: "Restore".to_string(),
+                    destructive: true,
+                    action: ConfirmAction::RestorePath { source, path },
+                });
+            }
+            Err(e) => self
+                .toasts
+                .push(ToastSpec::error(format!("Restore failed: {e:#}"))),
         }
-        // AI generation lives in its own slot since the result type
-        // (Result<AiResponse, String>) doesn't fit the AsyncKind /
-        // RemoteOpResult pattern. Drain it after the git ops so the
-        // commit-message draft fold-back happens in the same frame.
-        self.poll_ai_op_at(idx, depth);
     }
 
-    fn poll_ai_op_at(&mut self, idx: usize, depth: Option<usize>) {
-        let (target_path, result) = {
-            let Some(tab) = resolve_tab_mut(&mut self.tabs, idx, depth) else {
-                return;
-            };
-            let Some(op) = tab.ai_op.as_mut() else {
+    /// [`Self::restore_file_at`] for the file selected in the staging
+    /// well — the history-view commit row entry point.
+    fn restore_selected_file_at(&mut self, oid: git2::Oid, parent: bool) {
+        let path = self
+            .active_focus()
+            .and_then(|t| t.active_view())
+            .and_then(|view| view.selected_diff_file.clone());
+        match path {
+            Some(path) => self.restore_file_at(oid, path, parent),
+            None => self.toasts.push(ToastSpec::warning(
+                "Select a file in the staging well first",
+            )),
+        }
+    }
+
+    fn restore_path(&mut self, source: git2::Oid, path: String) {
+        let label = format!("Restore {path} to {}", &source.to_string()[..7]);
+        self.run_op(&label, move |t| t.active_repo().restore_path(source, &path));
+    }
+
+    /// Write the blob for `path` at `oid` to a file the user picks.
+    fn save_file_at_revision(&mut self, oid: git2::Oid, path: String) {
+        let Some(tab) = self.active_focus() else {
+            return;
+        };
+        let bytes = match tab.repo.file_at_revision(oid, &path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.toasts
+                    .push(ToastSpec::error(format!("Save failed: {e:#}")));
                 return;
-            };
-            match op.rx.try_recv() {
-                Ok(result) => (std::mem::take(&mut op.target_path), result),
-                Err(std::sync::mpsc::TryRecvError::Empty) => return,
-                Err(std::sync::mpsc::TryRecvError::Disconnected) => (
-                    std::mem::take(&mut op.target_path),
-                    Err("AI worker thread disconnected".to_string()),
-                ),
             }
         };
-        let Some(tab) = resolve_tab_mut(&mut self.tabs, idx, depth) else {
+        let file_name = path.rsplit('/').next().unwrap_or(&path).to_string();
+        let Some(dest) = rfd::FileDialog::new()
+            .set_title(format!("Save {path} at {}", &oid.to_string()[..7]))
+            .set_file_name(file_name)
+            .save_file()
+        else {
             return;
         };
-        tab.ai_op = None;
-        match result {
-            Ok(response) => {
-                if let Some(view) = tab.worktree_views.get_mut(&target_path) {
-                    view.commit_subject = response.subject;
-                    view.commit_body = response.body;
-                    self.toasts
-                        .push(ToastSpec::success("Generated commit message"));
+        let toast = match std::fs::write(&dest, bytes) {
+            Ok(()) => ToastSpec::success(format!("Saved {}", dest.display())),
+            Err(e) => ToastSpec::error(format!("Save failed: {e}")),
+        };
+        self.toasts.push(toast);
+    }
+
+    /// Open the ignore-rule dialog for an untracked `path`, seeded with
+    /// the pattern for `kind`.
+    fn open_ignore_modal(
+        &mut self,
+        path: String,
+        kind: IgnoreKind,
+        destination: IgnoreDestination,
+    ) {
+        let pattern = crate::git::ignore_pattern(&path, kind).unwrap_or_default();
+        self.active_modal = Some(ActiveModal::Ignore {
+            form: IgnoreForm {
+                path,
+                pattern,
+                destination,
+                preview: Ok(Vec::new()),
+            },
+        });
+        self.refresh_ignore_preview();
+    }
+
+    /// Recompute which untracked files the Ignore modal's pattern hides.
+    /// Cheap — libgit2 matches the pattern against the staging well's
+    /// cached untracked list without walking the worktree again.
+    fn refresh_ignore_preview(&mut self) {
+        let Some(ActiveModal::Ignore { form }) = &self.active_modal else {
+            return;
+        };
+        let pattern = form.pattern.clone();
+        let Some(tab) = self.active_focus() else {
+            return;
+        };
+        let untracked: Vec<String> = tab
+            .active_view()
+            .map(|v| v.status.untracked.iter().map(|f| f.path.clone()).collect())
+            .unwrap_or_default();
+        let preview = tab
+            .active_repo()
+            .ignore_preview(&pattern, &untracked)
+            .map_err(|e| format!("{e:#}"));
+        if let Some(ActiveModal::Ignore { form }) = &mut self.active_modal {
+            form.preview = preview;
+        }
+    }
+
+    fn add_ignore_rule_from_modal(&mut self) {
+        let Some(ActiveModal::Ignore { form }) = &self.active_modal else {
+            return;
+        };
+        let (pattern, destination) = (form.pattern.trim().to_string(), form.destination);
+        let Some(tab) = self.active_focus() else {
+            return;
+        };
+        match tab.active_repo().add_ignore_pattern(&pattern, destination) {
+            Ok(written) => {
+                self.active_modal = None;
+                let message = match written {
+                    Some(file) => format!("Added {pattern} to {}", file.display()),
+                    None => format!("{pattern} is already in the ignore file"),
+                };
+                self.toasts.push(ToastSpec::success(message));
+                if let (Some(proxy), Some(tab)) = (self.proxy.clone(), self.active_focus_mut()) {
+                    tab.trigger_status_refresh(&proxy);
+                }
+            }
+            Err(e) => self
+                .toasts
+                .push(ToastSpec::error(format!("Ignore failed: {e:#}"))),
+        }
+    }
+
+    /// Toast the rule that ignores `path` — which file, which line,
+    /// which pattern — as reported by `git check-ignore -v`.
+    fn explain_ignored(&mut self, path: String) {
+        let Some(tab) = self.active_focus() else {
+            return;
+        };
+        let toast = match tab.active_repo().ignore_reason(&path) {
+            Ok(Some(reason)) => ToastSpec::info(format!(
+                "{path} is ignored by {}:{} ({})",
+                reason.source, reason.line, reason.pattern
+            )),
+            Ok(None) => ToastSpec::info(format!("{path} is not ignored")),
+            Err(e) => ToastSpec::error(format!("check-ignore failed: {e:#}")),
+        };
+        self.toasts.push(toast);
+    }
+
+    /// Open the note editor for `oid` under `ref_name`, pre-filled with
+    /// the note already stored there (if any).
+    fn open_note_modal(&mut self, oid: git2::Oid, ref_name: String) {
+        let Some(tab) = self.active_focus() else {
+            return;
+        };
+        let existing = tab
+            .repo
+            .notes_for(oid)
+            .into_iter()
+            .find(|note| note.ref_name == ref_name);
+        self.active_modal = Some(ActiveModal::Note {
+            form: NoteForm {
+                oid,
+                ref_name,
+                editing: existing.is_some(),
+                message: existing.map(|note| note.message).unwrap_or_default(),
+            },
+        });
+    }
+
+    fn save_note_from_modal(&mut self) {
+        let Some(ActiveModal::Note { form }) = &self.active_modal else {
+            return;
+        };
+        let ref_name = form.ref_name.trim().to_string();
+        let ref_name = if ref_name.starts_with("refs/") {
+            ref_name
+        } else {
+            format!("refs/notes/{ref_name}")
+        };
+        let (oid, message) = (form.oid, form.message.clone());
+        if message.trim().is_empty() {
+            return;
+        }
+        self.active_modal = None;
+        self.run_op("Save note", move |t| {
+            t.repo.set_note(&ref_name, oid, &message)
+        });
+    }
+
+    fn confirm_remove_note(&mut self, oid: git2::Oid, ref_name: String) {
+        let short = crate::git::short_notes_ref(&ref_name);
+        self.active_modal = Some(ActiveModal::Confirm {
+            title: "Remove note".to_string(),
+            body: format!(
+                "Remove the {short} note from {}? This cannot be undone.",
+                &oid.to_string()[..7]
+            ),
+            ok_label: "Remove".to_string(),
+            destructive: true,
+            action: ConfirmAction::RemoveNote { oid, ref_name },
+        });
+    }
+
+    fn remove_note(&mut self, oid: git2::Oid, ref_name: String) {
+        self.run_op("Remove note", move |t| t.repo.remove_note(&ref_name, oid));
+    }
+
+    /// Open the changelog dialog ending at `to`. The base is the marked
+    /// range base when one is set (shown by tag name if it's tagged),
+    /// else the nearest earlier tag. Generates straight away so the
+    /// preview is ready.
+    fn open_changelog_modal(&mut self, to: String, to_oid: git2::Oid) {
+        let Some(tab) = self.active_focus() else {
+            return;
+        };
+        let from = match tab.patch_range_base.filter(|base| *base != to_oid) {
+            Some(base) => tab
+                .tags
+                .iter()
+                .find(|t| t.oid == base)
+                .map(|t| t.name.clone())
+                .unwrap_or_else(|| base.to_string()[..7].to_string()),
+            None => tab.active_repo().previous_tag(to_oid).unwrap_or_default(),
+        };
+        self.active_modal = Some(ActiveModal::Changelog {
+            form: ChangelogForm {
+                from,
+                to,
+                by_prefix: self.config.changelog_by_prefix,
+                prefixes: self.config.changelog_prefixes.join(", "),
+                result: None,
+                markdown: String::new(),
+            },
+        });
+        self.handle_changelog_route("changelog:generate");
+    }
+
+    /// Generate, copy or save the changelog modal's range. Generating
+    /// also remembers the grouping choice in the config.
+    fn handle_changelog_route(&mut self, key: &str) {
+        let Some(ActiveModal::Changelog { form }) = &self.active_modal else {
+            return;
+        };
+        match key {
+            "changelog:generate" => {
+                let prefixes: Vec<String> = form
+                    .prefixes
+                    .split(',')
+                    .map(|p| p.trim().to_string())
+                    .filter(|p| !p.is_empty())
+                    .collect();
+                let grouping = if form.by_prefix {
+                    ChangelogGrouping::Prefixes(prefixes.clone())
                 } else {
-                    self.toasts.push(ToastSpec::warning(
-                        "AI result dropped: target worktree no longer present",
-                    ));
+                    ChangelogGrouping::Conventional
+                };
+                let (from, to, by_prefix) = (
+                    form.from.clone(),
+                    form.to.trim().to_string(),
+                    form.by_prefix,
+                );
+                let Some(tab) = self.active_focus() else {
+                    return;
+                };
+                let result = tab
+                    .active_repo()
+                    .changelog(&from, &to, &grouping)
+                    .map_err(|e| format!("{e:#}"));
+                let markdown = match &result {
+                    Ok(log) => log.to_markdown(changelog_links(tab).as_ref()),
+                    Err(_) => String::new(),
+                };
+                if let Some(ActiveModal::Changelog { form }) = &mut self.active_modal {
+                    form.result = Some(result);
+                    form.markdown = markdown;
+                }
+                if self.config.changelog_by_prefix != by_prefix
+                    || self.config.changelog_prefixes != prefixes
+                {
+                    self.config.changelog_by_prefix = by_prefix;
+                    self.config.changelog_prefixes = prefixes;
+                    self.persist_config();
                 }
             }
-            Err(msg) => {
-                self.toasts
-                    .push(ToastSpec::error(format!("AI generate: {msg}")));
+            "changelog:copy" => {
+                let markdown = form.markdown.clone();
+                match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(markdown)) {
+                    Ok(()) => {
+                        self.active_modal = None;
+                        self.toasts
+                            .push(ToastSpec::success("Copied changelog to clipboard"));
+                    }
+                    Err(e) => self
+                        .toasts
+                        .push(ToastSpec::error(format!("Clipboard: {e}"))),
+                }
             }
+            "changelog:save" => {
+                let file_name = format!("CHANGELOG-{}.md", form.to.trim().replace('/', "-"));
+                let Some(dest) = rfd::FileDialog::new()
+                    .set_title("Save changelog")
+                    .set_file_name(file_name)
+                    .add_filter("Markdown", &["md"])
+                    .save_file()
+                else {
+                    return;
+                };
+                match std::fs::write(&dest, &form.markdown) {
+                    Ok(()) => {
+                        self.active_modal = None;
+                        self.toasts
+                            .push(ToastSpec::success(format!("Saved {}", dest.display())));
+                    }
+                    Err(e) => self.toasts.push(ToastSpec::error(format!(
+                        "Failed to write {}: {e}",
+                        dest.display()
+                    ))),
+                }
+            }
+            _ => {}
         }
     }
 
-    /// Common entry point: bail with a toast if `slot` is occupied or
-    /// if there's no proxy / no remotes. Returns the workdir path for
-    /// the caller to hand to the async function.
-    fn prepare_remote_op(
-        &mut self,
-        kind: AsyncKind,
-        require_remote: bool,
-    ) -> Option<(std::path::PathBuf, winit::event_loop::EventLoopProxy<()>)> {
-        let proxy = match self.proxy.clone() {
-            Some(p) => p,
-            None => {
-                self.toasts.push(ToastSpec::error(format!(
-                    "{} unavailable: event loop proxy missing",
-                    kind.name()
-                )));
-                return None;
-            }
-        };
-        // Operate on the *focused* RepoTab — when the user has drilled
-        // into a submodule, fetch / push / pull target that submodule's
-        // repo, not the outermost one. The op slot also lives on the
-        // focused tab so concurrent parent + child ops don't share a
-        // single slot.
-        let tab = self.active_focus()?;
-        if require_remote && !tab.repo.has_remotes() {
-            self.toasts.push(ToastSpec::error(
-                "No remotes configured for this repository",
-            ));
-            return None;
+    fn open_repo_config_modal(&mut self) {
+        if self.active_focus().is_none() {
+            return;
         }
-        let busy = match kind {
-            AsyncKind::Fetch => tab.fetch_op.is_some(),
-            AsyncKind::Pull => tab.pull_op.is_some(),
-            AsyncKind::Push => tab.push_op.is_some(),
-            AsyncKind::Mutation => tab.mutation_op.is_some(),
+        self.active_modal = Some(ActiveModal::RepoConfig {
+            form: RepoConfigForm::default(),
+        });
+        self.reload_repo_config_form();
+    }
+
+    /// Re-read the settings modal's values at its current scope,
+    /// dropping unsaved edits.
+    fn reload_repo_config_form(&mut self) {
+        let Some(tab) = self.active_focus() else {
+            return;
         };
-        if busy {
-            self.toasts.push(ToastSpec::info(format!(
-                "{} already in progress",
-                kind.name()
-            )));
-            return None;
+        let repo = tab.active_repo();
+        let Some(ActiveModal::RepoConfig { form }) = &self.active_modal else {
+            return;
+        };
+        let values: Vec<String> = repo
+            .config_values(form.scope)
+            .into_iter()
+            .map(Option::unwrap_or_default)
+            .collect();
+        let effective = repo.effective_config_values();
+        let issue_links: Vec<String> = repo
+            .issue_link_patterns()
+            .iter()
+            .map(IssueLinkPattern::to_line)
+            .collect();
+        let issue_links = issue_links.join("\n");
+        if let Some(ActiveModal::RepoConfig { form }) = &mut self.active_modal {
+            form.loaded = values.clone();
+            form.values = values;
+            form.effective = effective;
+            form.issue_links_loaded = issue_links.clone();
+            form.issue_links = issue_links;
+        }
+    }
+
+    fn handle_repo_config_route(&mut self, key: &str) {
+        if key == "repo_config:save" {
+            self.save_repo_config_form();
+        } else if key == "repo_config:profile_save" {
+            self.save_identity_profile_from_form();
+        } else if let Some(idx) = key
+            .strip_prefix("repo_config:profile_apply:")
+            .and_then(|i| i.parse::<usize>().ok())
+        {
+            let Some(profile) = self.config.identity_profiles.get(idx).cloned() else {
+                return;
+            };
+            let Some(tab) = self.active_focus() else {
+                return;
+            };
+            match tab
+                .active_repo()
+                .set_config_values(ConfigScope::Local, &profile.config_changes())
+            {
+                Ok(()) => {
+                    self.toasts.push(ToastSpec::success(format!(
+                        "Applied identity profile '{}'",
+                        profile.name
+                    )));
+                    self.reload_repo_config_form();
+                }
+                Err(e) => self
+                    .toasts
+                    .push(ToastSpec::error(format!("Apply profile failed: {e:#}"))),
+            }
+        } else if let Some(idx) = key
+            .strip_prefix("repo_config:profile_remove:")
+            .and_then(|i| i.parse::<usize>().ok())
+            && idx < self.config.identity_profiles.len()
+        {
+            self.config.identity_profiles.remove(idx);
+            self.persist_config();
         }
-        // Run the git CLI in the active worktree's working directory so
-        // ops resolve HEAD against that worktree (push picks up the right
-        // branch, fetch updates the right remote-tracking refs).
-        Some((tab.active_repo().git_command_dir(), proxy))
     }
 
-    fn fetch(&mut self) {
-        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Fetch, true) else {
+    /// Write the keys whose values differ from what was loaded. Empty
+    /// values unset the key at the chosen scope.
+    fn save_repo_config_form(&mut self) {
+        let Some(ActiveModal::RepoConfig { form }) = &self.active_modal else {
             return;
         };
-        let Some(tab) = self.active_focus_mut() else {
+        let changes: Vec<(&str, Option<&str>)> = CONFIG_FIELDS
+            .iter()
+            .zip(form.values.iter().zip(&form.loaded))
+            .filter(|(_, (value, loaded))| value.trim() != loaded.trim())
+            .map(|(field, (value, _))| {
+                let value = value.trim();
+                (field.key, (!value.is_empty()).then_some(value))
+            })
+            .collect();
+        let issue_links: Option<Vec<IssueLinkPattern>> =
+            (form.issue_links.trim() != form.issue_links_loaded.trim()).then(|| {
+                form.issue_links
+                    .lines()
+                    .filter_map(IssueLinkPattern::parse)
+                    .collect()
+            });
+        let Some(tab) = self.active_focus() else {
             return;
         };
-        let remote = tab
-            .repo
-            .default_remote()
-            .unwrap_or_else(|_| "origin".to_string());
-        // Auto-fix missing fetch refspec for bare-cloned remotes — the
-        // old whisper-git pattern. Silent on success since this is a
-        // common state on fresh `git clone --bare` repos.
-        if tab.repo.remote_missing_fetch_refspec(&remote) {
-            let _ = tab.repo.add_default_fetch_refspec(&remote);
+        let mut result = tab.active_repo().set_config_values(form.scope, &changes);
+        let relink = issue_links.is_some();
+        if let (Ok(()), Some(patterns)) = (&result, issue_links) {
+            result = tab.active_repo().set_issue_link_patterns(&patterns);
+        }
+        if relink && let Some(tab) = self.active_focus_mut() {
+            tab.refresh_issue_linker();
+        }
+        match result {
+            Ok(()) => {
+                self.active_modal = None;
+                self.toasts
+                    .push(ToastSpec::success("Saved repository settings"));
+            }
+            Err(e) => self
+                .toasts
+                .push(ToastSpec::error(format!("Save settings failed: {e:#}"))),
         }
-        let rx = crate::git::fetch_remote_async(wd, remote.clone(), proxy);
-        tab.fetch_op = Some(TimedOp::new(rx, format!("from {remote}")));
-        self.toasts
-            .push(ToastSpec::info(format!("Fetching from {remote}…")));
     }
 
-    fn fetch_remote(&mut self, remote: String) {
-        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Fetch, true) else {
+    /// Store the form's name / email / signing key as a new identity
+    /// profile (replacing one with the same name).
+    fn save_identity_profile_from_form(&mut self) {
+        let Some(ActiveModal::RepoConfig { form }) = &self.active_modal else {
             return;
         };
-        let Some(tab) = self.active_focus_mut() else {
+        let value = |key: &str| {
+            let idx = CONFIG_FIELDS.iter().position(|f| f.key == key)?;
+            let value = form.values.get(idx)?.trim();
+            if value.is_empty() {
+                form.effective.get(idx)?.clone()
+            } else {
+                Some(value.to_string())
+            }
+        };
+        let name = form.profile_name.trim().to_string();
+        let (Some(user_name), Some(email)) = (value("user.name"), value("user.email")) else {
+            self.toasts.push(ToastSpec::warning(
+                "Set a name and email before saving a profile",
+            ));
             return;
         };
-        if tab.repo.remote_missing_fetch_refspec(&remote) {
-            let _ = tab.repo.add_default_fetch_refspec(&remote);
-        }
-        let rx = crate::git::fetch_remote_async(wd, remote.clone(), proxy);
-        tab.fetch_op = Some(TimedOp::new(rx, format!("from {remote}")));
-        self.toasts
-            .push(ToastSpec::info(format!("Fetching from {remote}…")));
+        if name.is_empty() {
+            self.toasts
+                .push(ToastSpec::warning("Name the profile first"));
+            return;
+        }
+        let profile = crate::config::IdentityProfile {
+            name: name.clone(),
+            user_name,
+            email,
+            signing_key: value("user.signingkey").unwrap_or_default(),
+            host_patterns: form
+                .profile_hosts
+                .split(',')
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty())
+                .collect(),
+        };
+        self.config.identity_profiles.retain(|p| p.name != name);
+        self.config.identity_profiles.push(profile);
+        self.persist_config();
+        if let Some(ActiveModal::RepoConfig { form }) = &mut self.active_modal {
+            form.profile_name.clear();
+            form.profile_hosts.clear();
+        }
+        self.toasts.push(ToastSpec::success(format!(
+            "Saved identity profile '{name}'"
+        )));
     }
 
-    fn fetch_all(&mut self) {
-        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Fetch, true) else {
+    /// Apply the identity profile matching tab `tab_idx`'s remotes to
+    /// its local config, unless the repository already sets its own
+    /// identity. Called for startup tabs and right after a repository
+    /// is opened or cloned.
+    fn apply_identity_profile(&mut self, tab_idx: usize) {
+        let Some(tab) = self.tabs.get(tab_idx) else {
             return;
         };
-        let rx = crate::git::fetch_all_async(wd, proxy);
-        let Some(tab) = self.active_focus_mut() else {
+        if tab.repo.has_local_identity() {
+            return;
+        }
+        let Some(profile) = self
+            .config
+            .identity_profile_for(&tab.repo.remote_urls())
+            .cloned()
+        else {
             return;
         };
-        tab.fetch_op = Some(TimedOp::new(rx, "all remotes".to_string()));
-        self.toasts
-            .push(ToastSpec::info("Fetching all remotes…".to_string()));
+        match tab
+            .repo
+            .set_config_values(ConfigScope::Local, &profile.config_changes())
+        {
+            Ok(()) => self.toasts.push(ToastSpec::info(format!(
+                "Using identity profile '{}' ({})",
+                profile.name, profile.email
+            ))),
+            Err(e) => self
+                .toasts
+                .push(ToastSpec::error(format!("Apply profile failed: {e:#}"))),
+        }
     }
 
-    fn push(&mut self) {
-        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Push, true) else {
+    fn open_patch_export_modal(&mut self, tip: git2::Oid, base: Option<git2::Oid>) {
+        self.active_modal = Some(ActiveModal::PatchExport {
+            form: PatchExportForm {
+                tip,
+                base: base.map(|b| b.to_string()).unwrap_or_default(),
+            },
+        });
+    }
+
+    /// Export the modal's range to the clipboard, a single mbox file,
+    /// or a folder of numbered `.patch` files. Runs `format-patch`
+    /// synchronously — it only reads objects, so it's quick even for
+    /// long ranges. Errors keep the modal open so the base can be fixed.
+    fn handle_patch_export_route(&mut self, key: &str) {
+        let Some(ActiveModal::PatchExport { form }) = &self.active_modal else {
             return;
         };
-        let Some(tab) = self.active_focus_mut() else {
-            return;
+        let base = form.base.trim();
+        let range = if base.is_empty() {
+            PatchRange::Single(form.tip)
+        } else {
+            PatchRange::Since {
+                base: base.to_string(),
+                tip: form.tip,
+            }
         };
-        let remote = tab
-            .repo
-            .default_remote()
-            .unwrap_or_else(|_| "origin".to_string());
-        let branch = tab.current_branch().to_string();
-        if branch.is_empty() {
-            self.toasts
-                .push(ToastSpec::error("Push: HEAD is detached, no branch"));
-            return;
-        }
-        let rx = crate::git::push_remote_async(wd, remote.clone(), branch.clone(), proxy);
-        let Some(tab) = self.active_focus_mut() else {
+        let Some(tab) = self.active_focus() else {
             return;
         };
-        tab.push_op = Some(TimedOp::new(rx, format!("{branch} → {remote}")));
-        self.toasts
-            .push(ToastSpec::info(format!("Pushing {branch} to {remote}…")));
+        let repo = tab.active_repo();
+        let result = match key {
+            "patch_export:copy" => repo.format_patch(&range).and_then(|mbox| {
+                arboard::Clipboard::new()
+                    .and_then(|mut cb| cb.set_text(mbox))
+                    .context("Clipboard")?;
+                Ok("Copied patch to clipboard".to_string())
+            }),
+            "patch_export:save" => {
+                let file_name = match &range {
+                    PatchRange::Single(oid) => format!("{}.patch", &oid.to_string()[..7]),
+                    PatchRange::Since { .. } => format!("{}.mbox", tab.repo_name),
+                };
+                let Some(dest) = rfd::FileDialog::new()
+                    .set_title("Save patch")
+                    .set_file_name(file_name)
+                    .save_file()
+                else {
+                    return;
+                };
+                repo.format_patch(&range).and_then(|mbox| {
+                    std::fs::write(&dest, mbox)
+                        .with_context(|| format!("Failed to write {}", dest.display()))?;
+                    Ok(format!("Saved {}", dest.display()))
+                })
+            }
+            "patch_export:folder" => {
+                let Some(dir) = rfd::FileDialog::new()
+                    .set_title("Export patches to folder")
+                    .pick_folder()
+                else {
+                    return;
+                };
+                repo.format_patch_to_dir(&range, &dir).map(|files| {
+                    format!("Wrote {} patch file(s) to {}", files.len(), dir.display())
+                })
+            }
+            _ => return,
+        };
+        match result {
+            Ok(message) => {
+                self.active_modal = None;
+                self.toasts.push(ToastSpec::success(message));
+            }
+            Err(e) => self
+                .toasts
+                .push(ToastSpec::error(format!("Export failed: {e:#}"))),
+        }
     }
 
-    /// Pull the upstream of the current branch — `git pull <remote> <branch>`,
-    /// where `<remote>` is `default_remote()` (the upstream's remote when
-    /// tracking info exists; falls back to `origin`) and `<branch>` is
-    /// the current branch shorthand. Detached HEAD is rejected up-front
-    /// since pull has no source to use.
-    fn pull(&mut self) {
-        let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Pull, true) else {
+    /// Pick a patch / mailbox file and open the apply dialog with a
+    /// preview of the files it touches. A patch git can't parse fails
+    /// here, before anything in the worktree changes.
+    fn open_patch_apply_picker(&mut self) {
+        let Some(tab) = self.active_focus() else {
             return;
         };
-        let Some(tab) = self.active_focus_mut() else {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Apply patch or mailbox")
+            .add_filter("Patches", &["patch", "diff", "mbox", "eml"])
+            .add_filter("All files", &["*"])
+            .pick_file()
+        else {
             return;
         };
-        let remote = tab
-            .repo
-            .default_remote()
-            .unwrap_or_else(|_| "origin".to_string());
-        let branch = tab.current_branch().to_string();
-        if branch.is_empty() {
-            self.toasts
-                .push(ToastSpec::error("Pull: HEAD is detached, no branch"));
-            return;
+        match tab.active_repo().patch_preview(&path) {
+            Ok(preview) => {
+                let use_am = preview.is_mailbox;
+                self.active_modal = Some(ActiveModal::PatchApply {
+                    form: PatchApplyForm {
+                        path,
+                        preview,
+                        use_am,
+                        three_way: false,
+                    },
+                });
+            }
+            Err(e) => {
+                self.active_modal = Some(ActiveModal::Error {
+                    title: "Can't apply patch".to_string(),
+                    body: format!("{e:#}"),
+                })
+            }
         }
-        let rx = crate::git::pull_remote_async(wd, remote.clone(), branch.clone(), proxy);
-        let Some(tab) = self.active_focus_mut() else {
-            return;
-        };
-        tab.pull_op = Some(TimedOp::new(rx, format!("{remote}/{branch}")));
-        self.toasts
-            .push(ToastSpec::info(format!("Pulling {remote}/{branch}…")));
     }
 
-    fn cherry_pick(&mut self, oid: git2::Oid) {
+    fn handle_patch_apply_route(&mut self, key: &str) {
+        match key {
+            "patch_apply:three_way" => {
+                if let Some(ActiveModal::PatchApply { form }) = &mut self.active_modal {
+                    form.three_way = !form.three_way;
+                }
+            }
+            "patch_apply:execute" => self.apply_patch_from_modal(),
+            // `patch_apply:method:radio:<value>` is folded in `on_event`.
+            _ => {}
+        }
+    }
+
+    /// Run `git am` / `git apply` for the modal's file on the mutation
+    /// slot. If `am` stops on a conflict the worktree enters the
+    /// apply-mailbox state and the staging well shows Continue / Abort.
+    fn apply_patch_from_modal(&mut self) {
+        let Some(ActiveModal::PatchApply { form }) = &self.active_modal else {
+            return;
+        };
+        let (path, use_am, three_way) = (form.path.clone(), form.use_am, form.three_way);
         let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Mutation, false) else {
             return;
         };
-        let sha = oid.to_string();
-        let rx = crate::git::cherry_pick_async(wd, sha.clone(), proxy);
+        self.active_modal = None;
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let rx = crate::git::apply_patch_async(wd, path, use_am, three_way, proxy);
         let Some(tab) = self.active_focus_mut() else {
             return;
         };
-        let short = &sha[..7];
-        tab.mutation_op = Some(TimedOp::new(rx, format!("cherry-pick {short}")));
+        let verb = if use_am { "am" } else { "apply" };
+        tab.mutation_op = Some(TimedOp::new(rx, format!("{verb} {name}")));
         self.toasts
-            .push(ToastSpec::info(format!("Cherry-picking {short}…")));
+            .push(ToastSpec::info(format!("Applying {name}…")));
     }
 
-    fn revert(&mut self, oid: git2::Oid) {
+    /// Continue / skip / abort a stopped `git am` from the staging
+    /// well's in-progress banner.
+    fn am_step(&mut self, action: &str) {
         let Some((wd, proxy)) = self.prepare_remote_op(AsyncKind::Mutation, false) else {
             return;
         };
-        let sha = oid.to_string();
-        let rx = crate::git::revert_commit_async(wd, sha.clone(), proxy);
+        let (rx, label, toast) = match action {
+            "continue" => (
+                crate::git::am_continue_async(wd, proxy),
+                "am --continue",
+                "Continuing patch series…",
+            ),
+            "skip" => (
+                crate::git::am_skip_async(wd, proxy),
+                "am --skip",
+                "Skipping current patch…",
+            ),
+            "abort" => (
+                crate::git::am_abort_async(wd, proxy),
+                "am --abort",
+                "Aborting patch series…",
+            ),
+            _ => return,
+        };
         let Some(tab) = self.active_focus_mut() else {
             return;
         };
-        let short = &sha[..7];
-        tab.mutation_op = Some(TimedOp::new(rx, format!("revert {short}")));
-        self.toasts
-            .push(ToastSpec::info(format!("Reverting {short}…")));
+        tab.mutation_op = Some(TimedOp::new(rx, label.to_string()));
+        self.toasts.push(ToastSpec::info(toast));
     }
 
     fn delete_remote_branch(&mut self, remote: String, branch: String) {
@@ -4078,8 +7490,23 @@ impl WhisperApp {
     }
 
     fn open_merge_options(&mut self, source: String) {
+        // Headless callers have no event loop to drain a worker, so
+        // the forecast runs inline there.
+        let proxy = self.proxy.clone();
+        let mut forecast = None;
+        let mut op = None;
+        if let Some(repo) = self.active_focus().map(|tab| tab.active_repo()) {
+            match proxy {
+                Some(proxy) => op = Some(repo.forecast_merge_async(source.clone(), proxy)),
+                None => forecast = Some(repo.forecast_merge(&source).map_err(|e| format!("{e:#}"))),
+            }
+        }
+        self.forecast_op = op;
         self.active_modal = Some(ActiveModal::MergeOptions {
-            form: MergeForm::default(),
+            form: MergeForm {
+                forecast,
+                ..MergeForm::default()
+            },
             source,
         });
     }
@@ -4239,10 +7666,21 @@ impl WhisperApp {
     }
 
     fn open_rebase_options(&mut self, base: String) {
+        let proxy = self.proxy.clone();
+        let mut forecast = None;
+        let mut op = None;
+        if let Some(repo) = self.active_focus().map(|tab| tab.active_repo()) {
+            match proxy {
+                Some(proxy) => op = Some(repo.forecast_rebase_async(base.clone(), proxy)),
+                None => forecast = Some(repo.forecast_rebase(&base).map_err(|e| format!("{e:#}"))),
+            }
+        }
+        self.forecast_op = op;
         self.active_modal = Some(ActiveModal::RebaseOptions {
             form: RebaseForm {
                 autostash: true,
                 rebase_merges: false,
+                forecast,
             },
             base,
         });
@@ -4449,6 +7887,12 @@ impl WhisperApp {
             self.toasts.push(ToastSpec::warning("No staged changes"));
             return;
         }
+        if !view.repo.has_user_config() {
+            self.toasts
+                .push(ToastSpec::warning("Set user.name and user.email to commit"));
+            self.open_repo_config_modal();
+            return;
+        }
         let message = if view.commit_body.trim().is_empty() {
             view.commit_subject.clone()
         } else {
@@ -4505,6 +7949,26 @@ fn parse_section(key: &str) -> Option<SidebarSection> {
     SidebarSection::ALL.iter().copied().find(|s| s.key() == key)
 }
 
+/// Default remote name for an imported bundle: `bundle-<file stem>`
+/// with anything git would reject in a ref component replaced by `-`.
+fn bundle_remote_name(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stem: String = stem
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("bundle-{}", stem.trim_matches('-'))
+}
+
 fn parse_sidebar_target(route: &str) -> Option<ContextTarget> {
     if route == "section:REMOTE" {
         return Some(ContextTarget::RemoteSection);
@@ -4535,6 +7999,9 @@ fn parse_sidebar_target(route: &str) -> Option<ContextTarget> {
     if let Some(name) = route.strip_prefix("tag:") {
         return Some(ContextTarget::Tag(name.to_string()));
     }
+    if let Some(key) = route.strip_prefix("pr:") {
+        return Some(ContextTarget::PullRequest(key.to_string()));
+    }
     if let Some(idx_str) = route.strip_prefix("stash:")
         && let Ok(idx) = idx_str.parse::<usize>()
     {
@@ -4543,6 +8010,33 @@ fn parse_sidebar_target(route: &str) -> Option<ContextTarget> {
     None
 }
 
+/// Link bases for changelog references, from the `origin` remote's
+/// forge.
+fn changelog_links(tab: &RepoTab) -> Option<ChangelogLinks> {
+    let url = tab.repo.remote_url("origin")?;
+    let (backend, _) = forge::detect(&url)?;
+    backend.changelog_links(&url)
+}
+
+/// `(branch, remote)` to offer a pull request for after the push
+/// labelled `label` (`"<branch> → <remote>"`, maybe with flags). Only
+/// for forge remotes, branches other than the remote's default, and
+/// branches without an open pull request.
+fn pull_request_offer(tab: &RepoTab, label: &str) -> Option<(String, String)> {
+    let (branch, rest) = label.split_once(" \u{2192} ")?;
+    let remote = rest.split(' ').next()?;
+    tab.repo
+        .remote_url(remote)
+        .and_then(|url| remote_forge(&url))
+        .filter(|(p, _)| forge::backend(*p).supports_pull_requests())?;
+    if tab.repo.remote_default_branch(remote).as_deref() == Some(branch)
+        || tab.pull_request_for_branch(branch).is_some()
+    {
+        return None;
+    }
+    Some((branch.to_string(), remote.to_string()))
+}
+
 fn context_menu_layer(state: &ContextMenuState, tab: Option<&RepoTab>) -> El {
     use aetna_core::widgets::popover::{context_menu, menu_item};
     use aetna_core::widgets::separator::separator;
@@ -4551,6 +8045,7 @@ fn context_menu_layer(state: &ContextMenuState, tab: Option<&RepoTab>) -> El {
         ContextTarget::LocalBranch(_) => vec![
             menu_item("Checkout").key("ctx:checkout"),
             menu_item("Create Worktree").key("ctx:create_worktree"),
+            menu_item("Create Pull Request\u{2026}").key("ctx:create_pr"),
             separator(),
             menu_item("Merge into HEAD").key("ctx:merge"),
             menu_item("Merge with options\u{2026}").key("ctx:merge_options"),
@@ -4559,12 +8054,26 @@ fn context_menu_layer(state: &ContextMenuState, tab: Option<&RepoTab>) -> El {
             separator(),
             menu_item("Delete").key("ctx:delete"),
         ],
-        ContextTarget::Remote(remote) => vec![
-            menu_item(format!("Fetch from {remote}")).key("ctx:fetch_remote"),
+        ContextTarget::Remote(remote) => {
+            let mut items = vec![
+                menu_item(format!("Fetch from {remote}")).key("ctx:fetch_remote"),
+                menu_item("Fetch Notes").key("ctx:fetch_notes"),
+            ];
+            if tab.is_some_and(|t| t.bundle_remotes.contains(remote)) {
+                items.push(menu_item("Inspect Bundle\u{2026}").key("ctx:inspect_bundle"));
+            }
+            items.extend([
+                separator(),
+                menu_item("Delete Remote").key("ctx:delete_remote"),
+            ]);
+            items
+        }
+        ContextTarget::RemoteSection => vec![
+            menu_item("Fetch All Remotes").key("ctx:fetch_all"),
             separator(),
-            menu_item("Delete Remote").key("ctx:delete_remote"),
+            menu_item("Create Bundle\u{2026}").key("ctx:create_bundle"),
+            menu_item("Import Bundle\u{2026}").key("ctx:import_bundle"),
         ],
-        ContextTarget::RemoteSection => vec![menu_item("Fetch All Remotes").key("ctx:fetch_all")],
         ContextTarget::RemoteBranch { .. } => vec![
             menu_item("Checkout").key("ctx:checkout"),
             menu_item("Merge into HEAD").key("ctx:merge"),
@@ -4573,7 +8082,28 @@ fn context_menu_layer(state: &ContextMenuState, tab: Option<&RepoTab>) -> El {
             menu_item("Rebase with options\u{2026}").key("ctx:rebase_options"),
             menu_item("Delete").key("ctx:delete"),
         ],
-        ContextTarget::Tag(_) => vec![menu_item("Delete").key("ctx:delete")],
+        ContextTarget::Tag(name) => {
+            let mut items = Vec::new();
+            if tab.is_some_and(|t| t.release(name).is_some()) {
+                items.push(menu_item("Open Release").key("ctx:release_open"));
+            } else if tab.is_some_and(|t| t.releases_supported()) {
+                items.push(menu_item("Create Release\u{2026}").key("ctx:create_release"));
+            }
+            items.extend([
+                menu_item("Generate Changelog\u{2026}").key("ctx:changelog"),
+                menu_item("Set as Range Base").key("ctx:set_patch_base"),
+                separator(),
+                menu_item("Delete").key("ctx:delete"),
+            ]);
+            items
+        }
+        ContextTarget::PullRequest(_) => vec![
+            menu_item("Checkout as Branch").key("ctx:pr_checkout"),
+            menu_item("Checkout in New Worktree").key("ctx:pr_worktree"),
+            separator(),
+            menu_item("Open in Browser").key("ctx:pr_open"),
+            menu_item("Copy URL").key("ctx:pr_copy_url"),
+        ],
         ContextTarget::Stash(_) => vec![
             menu_item("Apply").key("ctx:apply"),
             menu_item("Pop").key("ctx:pop"),
@@ -4586,24 +8116,126 @@ fn context_menu_layer(state: &ContextMenuState, tab: Option<&RepoTab>) -> El {
             menu_item("View Diff").key("ctx:view_diff"),
         ],
         ContextTarget::StagingFile {
+            path,
             staged: false,
             untracked,
-            ..
-        } => vec![
-            menu_item("Stage File").key("ctx:stage"),
-            menu_item("View Diff").key("ctx:view_diff"),
-            menu_item(if *untracked {
-                "Discard File"
-            } else {
-                "Discard Changes"
-            })
-            .key("ctx:discard"),
-        ],
+        } => {
+            let mut items = vec![
+                menu_item("Stage File").key("ctx:stage"),
+                menu_item("View Diff").key("ctx:view_diff"),
+                menu_item(if *untracked {
+                    "Discard File"
+                } else {
+                    "Discard Changes"
+                })
+                .key("ctx:discard"),
+            ];
+            if *untracked {
+                items.extend([separator(), menu_item("Ignore File").key("ctx:ignore_file")]);
+                if let Some(pattern) = crate::git::ignore_pattern(path, IgnoreKind::Extension) {
+                    items.push(
+                        menu_item(format!("Ignore Extension ({pattern})"))
+                            .key("ctx:ignore_extension"),
+                    );
+                }
+                if let Some(pattern) = crate::git::ignore_pattern(path, IgnoreKind::Folder) {
+                    items.push(
+                        menu_item(format!("Ignore Folder ({pattern})")).key("ctx:ignore_folder"),
+                    );
+                }
+                items.push(menu_item("Ignore Locally (.git/info/exclude)").key("ctx:ignore_local"));
+            }
+            items
+        }
+        ContextTarget::CommitFile { path, .. } => {
+            let mut items = vec![
+                menu_item("Restore to This Version").key("ctx:restore_commit"),
+                menu_item("Restore to Parent Version").key("ctx:restore_parent"),
+            ];
+            if let Some((dir, _)) = path.rsplit_once('/') {
+                items.extend([
+                    menu_item(format!("Restore Folder '{dir}/' to This Version"))
+                        .key("ctx:restore_dir_commit"),
+                    menu_item(format!("Restore Folder '{dir}/' to Parent Version"))
+                        .key("ctx:restore_dir_parent"),
+                ]);
+            }
+            items.extend([
+                separator(),
+                menu_item("Save File As\u{2026}").key("ctx:save_at_revision"),
+            ]);
+            items
+        }
+        ContextTarget::Ci { sha, provider } => ci_context_menu_items(sha, *provider, tab),
+        ContextTarget::ReviewLine { new_line, .. } => {
+            vec![menu_item(format!("Comment on Line {new_line}\u{2026}")).key("ctx:review_comment")]
+        }
+        ContextTarget::RepoMenu => {
+            vec![
+                menu_item("Insights").key("ctx:insights"),
+                menu_item("Repository Settings\u{2026}").key("ctx:repo_config"),
+                menu_item("Maintenance\u{2026}").key("ctx:maintenance"),
+                separator(),
+                menu_item("Apply Patch / Mailbox\u{2026}").key("ctx:apply_patch"),
+            ]
+        }
     };
 
     context_menu(SIDEBAR_CTX_KEY, state.pos, items)
 }
 
+/// Menu for one provider's CI runs on a commit. Run actions only show
+/// when some run is in a state they apply to; a manual run always can.
+fn ci_context_menu_items(sha: &str, provider: CiProvider, tab: Option<&RepoTab>) -> Vec<El> {
+    use aetna_core::widgets::popover::menu_item;
+    use aetna_core::widgets::separator::separator;
+
+    let checks = tab.map(|t| ci_checks(t, sha, provider)).unwrap_or_default();
+    let has = |state: CiState| {
+        checks
+            .iter()
+            .any(|c| c.run_id.is_some() && c.state == state)
+    };
+    let mut items = Vec::new();
+    if checks.iter().any(|c| c.run_id.is_some()) {
+        items.push(menu_item("Show Logs").key("ctx:ci_logs"));
+    }
+    if has(CiState::Failure) {
+        items.push(menu_item("Re-run Failed Jobs").key("ctx:ci_rerun_failed"));
+    }
+    if has(CiState::Failure) || has(CiState::Success) {
+        items.push(menu_item("Re-run All Jobs").key("ctx:ci_rerun_all"));
+    }
+    if has(CiState::Pending) {
+        items.push(menu_item("Cancel Running").key("ctx:ci_cancel"));
+    }
+    if !items.is_empty() {
+        items.push(separator());
+    }
+    items.push(
+        menu_item(match provider {
+            CiProvider::GitHub | CiProvider::Gitea | CiProvider::Local => "Run Workflow\u{2026}",
+            CiProvider::GitLab => "Run Pipeline\u{2026}",
+        })
+        .key("ctx:ci_run"),
+    );
+    items
+}
+
+/// `provider`'s checks on commit `sha`, from the per-commit rollups.
+fn ci_checks<'a>(tab: &'a RepoTab, sha: &str, provider: CiProvider) -> &'a [CiCheckStatus] {
+    tab.ci_per_commit
+        .get(sha)
+        .and_then(|r| r.iter().find(|r| r.provider == provider))
+        .map(|r| r.rollup.checks.as_slice())
+        .unwrap_or_default()
+}
+
+/// `name=` lines for a workflow's inputs, for the user to fill in.
+fn seed_inputs(names: &[String]) -> String {
+    names.iter().map(|name| format!("{name}=\n")).collect()
+}
+
 fn commit_context_menu_items(
     oid: git2::Oid,
     tab: Option<&RepoTab>,
@@ -4723,14 +8355,80 @@ fn commit_context_menu_items(
     }
     items.extend([
         separator(),
-        commit_menu_group_toggle("create", expanded_groups, "Create Actions", Some(3)),
+        commit_menu_group_toggle("create", expanded_groups, "Create Actions", Some(4)),
     ]);
     if commit_menu_group_expanded(expanded_groups, "create") {
         items.extend([
             menu_item("Create Branch Here").key("ctx:create_branch"),
             menu_item("Create Worktree Here").key("ctx:create_worktree"),
             menu_item("Create Tag Here").key("ctx:create_tag"),
+            menu_item("Generate Changelog\u{2026}").key("ctx:changelog"),
+        ]);
+    }
+    let range_base = tab.patch_range_base.filter(|base| *base != oid);
+    items.extend([
+        separator(),
+        commit_menu_group_toggle(
+            "patch",
+            expanded_groups,
+            "Patch Actions",
+            Some(if range_base.is_some() { 3 } else { 2 }),
+        ),
+    ]);
+    if commit_menu_group_expanded(expanded_groups, "patch") {
+        items.push(menu_item("Export as Patch\u{2026}").key("ctx:export_patch"));
+        if let Some(base) = range_base {
+            let short = &base.to_string()[..7];
+            items.push(
+                menu_item(format!("Export Range {short}..here\u{2026}"))
+                    .key("ctx:export_patch_range"),
+            );
+        }
+        items.push(menu_item("Set as Patch Range Base").key("ctx:set_patch_base"));
+    }
+    // Restore targets the file selected in the staging well, so a row
+    // in the history view can roll that file back to this commit.
+    if let Some(path) = tab
+        .active_view()
+        .and_then(|view| view.selected_diff_file.as_deref())
+    {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        items.extend([
+            separator(),
+            commit_menu_group_toggle("restore", expanded_groups, "Restore Actions", Some(2)),
         ]);
+        if commit_menu_group_expanded(expanded_groups, "restore") {
+            items.extend([
+                menu_item(format!("Restore '{name}' to This Version"))
+                    .key("ctx:restore_selected_commit"),
+                menu_item(format!("Restore '{name}' to Parent Version"))
+                    .key("ctx:restore_selected_parent"),
+            ]);
+        }
+    }
+    let note_refs = tab.noted_commits.get(&oid).cloned().unwrap_or_default();
+    items.extend([
+        separator(),
+        commit_menu_group_toggle(
+            "notes",
+            expanded_groups,
+            "Note Actions",
+            Some(1 + 2 * note_refs.len()),
+        ),
+    ]);
+    if commit_menu_group_expanded(expanded_groups, "notes") {
+        items.push(menu_item("Add Note\u{2026}").key("ctx:add_note"));
+        for ref_name in &note_refs {
+            let short = crate::git::short_notes_ref(ref_name);
+            items.push(
+                menu_item(format!("Edit Note ({short})\u{2026}"))
+                    .key(format!("ctx:edit_note:{ref_name}")),
+            );
+            items.push(
+                menu_item(format!("Remove Note ({short})"))
+                    .key(format!("ctx:remove_note:{ref_name}")),
+            );
+        }
     }
     items.extend([
         separator(),
@@ -4771,18 +8469,30 @@ fn worktree_context_menu_items(key: &str, tab: Option<&RepoTab>) -> Vec<El> {
     use aetna_core::widgets::popover::menu_item;
     use aetna_core::widgets::separator::separator;
 
+    let linked = tab.and_then(|tab| {
+        tab.worktrees
+            .iter()
+            .find(|worktree| worktree.name == key || worktree.path == key)
+    });
+    if linked.is_some_and(|wt| wt.is_stale) {
+        // Directory is gone: nothing to switch to, only metadata left.
+        return vec![menu_item("Prune Stale Worktrees").key("ctx:prune_worktrees")];
+    }
     let mut items = vec![
         menu_item("Switch Staging").key("ctx:switch_worktree"),
         menu_item("Jump to Branch").key("ctx:jump_to_worktree"),
     ];
-    let can_remove = tab.is_some_and(|tab| {
-        tab.worktrees
-            .iter()
-            .any(|worktree| worktree.name == key || worktree.path == key)
-    });
-    if can_remove {
+    if let Some(wt) = linked {
         items.push(separator());
-        items.push(menu_item("Remove Worktree").key("ctx:remove_worktree"));
+        if wt.is_locked {
+            items.push(menu_item("Unlock Worktree").key("ctx:unlock_worktree"));
+        } else {
+            items.extend([
+                menu_item("Lock Worktree\u{2026}").key("ctx:lock_worktree"),
+                menu_item("Move Worktree\u{2026}").key("ctx:move_worktree"),
+                menu_item("Remove Worktree").key("ctx:remove_worktree"),
+            ]);
+        }
     }
     items
 }
@@ -4868,7 +8578,6 @@ fn tab_bar(app: &WhisperApp) -> El {
 /// provider has reported anything yet — the tab's leading slot stays
 /// empty rather than reserving space for a phantom pip.
 fn tab_ci_pip(tab: &RepoTab, idx: usize) -> Option<El> {
-    use crate::ci::CiState;
     if tab.ci_results.is_empty() {
         return None;
     }
@@ -4882,7 +8591,17 @@ fn tab_ci_pip(tab: &RepoTab, idx: usize) -> Option<El> {
         CiState::Success => (tokens::SUCCESS, "passing"),
         CiState::None => return None,
     };
-    let tip = format!("CI {summary_state}");
+    // Until the first poll lands the pip shows last session's
+    // status, dimmed.
+    let stale = tab.ci_results.iter().all(|r| r.stale);
+    let (color, tip) = if stale {
+        (
+            color.with_alpha(110),
+            format!("CI {summary_state} (last known)"),
+        )
+    } else {
+        (color, format!("CI {summary_state}"))
+    };
     // The pip needs a key for the tooltip to fire (hit-test only
     // returns keyed nodes). Routing `tab_ci:{idx}` back to a tab
     // select keeps the visual area clickable for switching tabs.
@@ -4898,7 +8617,6 @@ fn tab_ci_pip(tab: &RepoTab, idx: usize) -> Option<El> {
 }
 
 fn worst_state(a: crate::ci::CiState, b: crate::ci::CiState) -> crate::ci::CiState {
-    use crate::ci::CiState;
     fn rank(s: CiState) -> u8 {
         match s {
             CiState::Failure => 3,
@@ -5156,6 +8874,14 @@ fn header_bar(active: Option<&RepoTab>, clone_op: Option<&CloneOp>) -> El {
     if fetch_busy {
         fetch_btn = fetch_btn.disabled();
     }
+    // Chevron beside Fetch opens the Fetch picker (remote, --prune,
+    // notes refs).
+    let mut fetch_options_btn = icon_button(IconName::ChevronDown)
+        .key("fetch_options")
+        .tooltip("Fetch with options\u{2026}");
+    if fetch_busy {
+        fetch_options_btn = fetch_options_btn.disabled();
+    }
     let mut pull_btn = button_with_icon(IconName::Download, "Pull")
         .key("pull")
         .tooltip("git pull");
@@ -5210,10 +8936,14 @@ fn header_bar(active: Option<&RepoTab>, clone_op: Option<&CloneOp>) -> El {
     }
     bar_items.push(toolbar_group([
         fetch_btn,
+        fetch_options_btn,
         pull_btn,
         pull_options_btn,
         push_btn,
         push_options_btn,
+        icon_button(IconName::MoreHorizontal)
+            .key("repo_menu")
+            .tooltip("Repository actions"),
         icon_button(IconName::Settings)
             .key("settings")
             .tooltip("Settings"),
@@ -5280,6 +9010,13 @@ fn op_status_lines(active: Option<&RepoTab>, clone_op: Option<&CloneOp>) -> Vec<
             // so don't prefix.
             lines.push(status_row("", &op.label, op.started.elapsed().as_secs()));
         }
+        if let Some(op) = &tab.maintenance.op {
+            lines.push(status_row(
+                "",
+                op.task.label(),
+                op.started.elapsed().as_secs(),
+            ));
+        }
     }
     if let Some(op) = clone_op {
         lines.push(status_row(
@@ -5320,7 +9057,6 @@ fn status_row(verb: &str, label: &str, secs: u64) -> El {
 /// the human-readable summary as tooltip and a click route that opens
 /// the provider URL via `xdg-open`.
 fn ci_badges(tab: &RepoTab) -> Vec<El> {
-    use crate::ci::CiState;
     use crate::widgets::brand_icons;
     if tab.ci_results.is_empty() {
         return Vec::new();
@@ -5335,6 +9071,11 @@ fn ci_badges(tab: &RepoTab) -> Vec<El> {
                 CiState::Pending => (Some(IconName::Activity), tokens::WARNING),
                 CiState::None => (None, tokens::MUTED_FOREGROUND),
             };
+            let color = if result.stale {
+                color.with_alpha(110)
+            } else {
+                color
+            };
             let mut children: Vec<El> = vec![
                 icon(brand_icons::for_provider(result.provider))
                     .icon_size(14.0)
@@ -5366,8 +9107,9 @@ fn ci_badges(tab: &RepoTab) -> Vec<El> {
             if result.status.url.is_some() {
                 badge = badge.key(format!("ci:open:{idx}")).focusable();
             }
+            let stale = if result.stale { " (last known)" } else { "" };
             badge.tooltip(format!(
-                "{} \u{00b7} {}",
+                "{} \u{00b7} {}{stale}",
                 result.provider.short_label(),
                 result.status.summary
             ))
@@ -1,6 +1,7 @@
 //! Unified-diff viewer widget — Github-shaped: colored gutter strip
 //! per line, subtle row-level wash, optional word-level intra-line
-//! highlights, and per-hunk action buttons.
+//! highlights, per-hunk action buttons, and comment threads anchored
+//! under lines.
 //!
 //! Pure data-in: the widget takes a [`DiffData`] value and renders it.
 //! Whisper-git's `diff_view::diff_view(tab)` is the thin adapter that
@@ -58,6 +59,34 @@ pub struct DiffHunkAction {
     pub destructive: bool,
 }
 
+/// Column a [`DiffThread`] is anchored to: old (pre-image) or new
+/// (post-image) line numbers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiffSide {
+    Old,
+    New,
+}
+
+/// A comment thread rendered beneath the line it's anchored to. Threads
+/// with no anchor, or whose line isn't in any hunk shown, are listed
+/// above the first hunk instead.
+#[derive(Clone, Debug)]
+pub struct DiffThread {
+    pub anchor: Option<(DiffSide, u32)>,
+    /// Tags shown in the thread header, e.g. `Outdated`.
+    pub badges: Vec<String>,
+    pub comments: Vec<DiffComment>,
+    pub actions: Vec<DiffHunkAction>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DiffComment {
+    pub author: String,
+    /// Secondary text beside the author, e.g. a timestamp.
+    pub meta: String,
+    pub body: String,
+}
+
 /// Top-level diff payload. `title` is the path; `badge` is the
 /// secondary tag shown in the header (e.g. `staged`, `unstaged`, or a
 /// commit short SHA).
@@ -71,6 +100,14 @@ pub struct DiffData {
     /// `None` hides the toggle (e.g., the host doesn't want a button
     /// because it provides its own UI for switching).
     pub mode_toggle_key: Option<String>,
+    /// File-level buttons rendered in the header next to the badge
+    /// (e.g. restore-to-revision in commit context).
+    pub header_actions: Vec<DiffHunkAction>,
+    pub threads: Vec<DiffThread>,
+    /// When set, line rows are keyed `{prefix}:{old}:{new}` (`-` for
+    /// a missing number) so the host can route clicks on a line. In
+    /// split mode only the side the line belongs to carries the key.
+    pub line_key_prefix: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Default)]
@@ -144,6 +181,7 @@ pub fn diff(data: &DiffData) -> El {
     if let Some(b) = data.badge.as_ref() {
         header_children.push(badge(b.clone()).muted());
     }
+    header_children.extend(data.header_actions.iter().map(action_button));
     if let Some(key) = data.mode_toggle_key.as_ref() {
         header_children.push(mode_toggle_button(data.mode, key));
     }
@@ -154,7 +192,8 @@ pub fn diff(data: &DiffData) -> El {
     let body: El = if data.hunks.is_empty() {
         column([text("(no changes)").caption().muted()]).padding(tokens::SPACE_4)
     } else {
-        let rows = flatten_rows(&data.hunks, data.mode);
+        let rows = flatten_rows(&data.hunks, data.mode, &data.threads);
+        let line_key_prefix = data.line_key_prefix.clone();
         let row_keys: Vec<String> = rows
             .iter()
             .enumerate()
@@ -170,7 +209,7 @@ pub fn diff(data: &DiffData) -> El {
                     .unwrap_or_else(|| format!("diff:row:missing:{i}"))
             },
             move |i| {
-                column([build_diff_row(&rows[i], i)])
+                column([build_diff_row(&rows[i], i, line_key_prefix.as_deref())])
                     .width(Size::Fill(1.0))
                     .padding(Sides {
                         left: 0.0,
@@ -211,40 +250,85 @@ enum DiffRow {
     },
     UnifiedLine(DiffLine),
     SplitPair(PairedRow),
+    Thread(DiffThread),
 }
 
-fn flatten_rows(hunks: &[DiffHunk], mode: DiffMode) -> Vec<DiffRow> {
-    let mut rows = Vec::new();
+/// Flatten hunks into rows, placing each thread after the first line
+/// its anchor matches. Threads that match nothing go first.
+fn flatten_rows(hunks: &[DiffHunk], mode: DiffMode, threads: &[DiffThread]) -> Vec<DiffRow> {
+    let mut placed = vec![false; threads.len()];
+    let mut body = Vec::new();
+    let mut place = |body: &mut Vec<DiffRow>, matches: &dyn Fn(DiffSide, u32) -> bool| {
+        for (t, done) in threads.iter().zip(placed.iter_mut()) {
+            if !*done && t.anchor.is_some_and(|(side, n)| matches(side, n)) {
+                body.push(DiffRow::Thread(t.clone()));
+                *done = true;
+            }
+        }
+    };
     for hunk in hunks {
-        rows.push(DiffRow::HunkHeader {
+        body.push(DiffRow::HunkHeader {
             header: hunk.header.clone(),
             actions: hunk.actions.clone(),
         });
         match mode {
             DiffMode::Unified => {
                 for l in &hunk.lines {
-                    rows.push(DiffRow::UnifiedLine(l.clone()));
+                    body.push(DiffRow::UnifiedLine(l.clone()));
+                    place(&mut body, &|side, n| line_matches(l, side, n));
                 }
             }
             DiffMode::Split => {
                 for p in pair_lines(&hunk.lines) {
-                    rows.push(DiffRow::SplitPair(p));
+                    body.push(DiffRow::SplitPair(p.clone()));
+                    place(&mut body, &|side, n| {
+                        let half = match side {
+                            DiffSide::Old => p.left.as_ref(),
+                            DiffSide::New => p.right.as_ref(),
+                        };
+                        half.is_some_and(|l| line_matches(l, side, n))
+                    });
                 }
             }
         }
     }
+    let mut rows: Vec<DiffRow> = threads
+        .iter()
+        .zip(&placed)
+        .filter(|(_, done)| !**done)
+        .map(|(t, _)| DiffRow::Thread(t.clone()))
+        .collect();
+    rows.extend(body);
     rows
 }
 
-fn build_diff_row(row: &DiffRow, idx: usize) -> El {
+/// Whether `line` is line `n` of `side`. Added lines have no old
+/// number and deleted lines no new one, so each matches one side only.
+fn line_matches(line: &DiffLine, side: DiffSide, n: u32) -> bool {
+    match side {
+        DiffSide::Old => line.kind != DiffLineKind::Addition && line.old_lineno == Some(n),
+        DiffSide::New => line.kind != DiffLineKind::Deletion && line.new_lineno == Some(n),
+    }
+}
+
+fn build_diff_row(row: &DiffRow, idx: usize, line_key_prefix: Option<&str>) -> El {
     let key = format!("diff:row:{idx}");
     match row {
         DiffRow::HunkHeader { header, actions } => hunk_header_row(header, actions).key(key),
-        DiffRow::UnifiedLine(line) => unified_line_row(line).key(key),
-        DiffRow::SplitPair(pair) => split_pair_row(pair).key(key),
+        DiffRow::UnifiedLine(line) => {
+            let key = line_key_prefix.map_or(key, |p| line_route_key(p, line));
+            unified_line_row(line).key(key)
+        }
+        DiffRow::SplitPair(pair) => split_pair_row(pair, line_key_prefix).key(key),
+        DiffRow::Thread(thread) => thread_row(thread).key(key),
     }
 }
 
+fn line_route_key(prefix: &str, line: &DiffLine) -> String {
+    let num = |n: Option<u32>| n.map_or_else(|| "-".to_string(), |n| n.to_string());
+    format!("{prefix}:{}:{}", num(line.old_lineno), num(line.new_lineno))
+}
+
 fn diff_row_key(row: &DiffRow, idx: usize) -> String {
     match row {
         DiffRow::HunkHeader { header, .. } => format!("diff:hunk:{idx}:{header}"),
@@ -262,6 +346,14 @@ fn diff_row_key(row: &DiffRow, idx: usize) -> String {
                 .unwrap_or_else(|| "none".to_string());
             format!("diff:pair:{idx}:{left}:{right}")
         }
+        DiffRow::Thread(thread) => {
+            let first = thread.comments.first();
+            format!(
+                "diff:thread:{idx}:{}:{}",
+                first.map_or("", |c| c.author.as_str()),
+                thread.comments.len()
+            )
+        }
     }
 }
 
@@ -304,16 +396,7 @@ fn hunk_header_row(header: &str, actions: &[DiffHunkAction]) -> El {
     } else {
         children.push(spacer());
     }
-    for act in actions {
-        let mut btn = button(act.label.clone()).key(act.key.clone()).ghost();
-        if act.destructive {
-            btn = btn.destructive();
-        }
-        if let Some(tip) = act.tooltip.as_ref() {
-            btn = btn.tooltip(tip.clone());
-        }
-        children.push(btn);
-    }
+    children.extend(actions.iter().map(action_button));
     row(children)
         .width(Size::Fill(1.0))
         .gap(tokens::SPACE_2)
@@ -322,6 +405,55 @@ fn hunk_header_row(header: &str, actions: &[DiffHunkAction]) -> El {
         .fill(tokens::MUTED)
 }
 
+/// A comment thread indented past the line-number gutter: header with
+/// badges and actions, then each comment's author line and body.
+fn thread_row(thread: &DiffThread) -> El {
+    let mut header: Vec<El> = thread
+        .badges
+        .iter()
+        .map(|b| badge(b.clone()).muted())
+        .collect();
+    header.push(spacer());
+    header.extend(thread.actions.iter().map(action_button));
+    let mut children = vec![row(header).gap(tokens::SPACE_2).align(Align::Center)];
+    for comment in &thread.comments {
+        children.push(
+            column([
+                row([
+                    text(comment.author.clone()).label(),
+                    text(comment.meta.clone()).caption().muted(),
+                ])
+                .gap(tokens::SPACE_2)
+                .align(Align::Center),
+                paragraph(comment.body.clone()),
+            ])
+            .gap(tokens::SPACE_1)
+            .width(Size::Fill(1.0)),
+        );
+    }
+    column([card([card_content(children)
+        .padding(tokens::SPACE_3)
+        .gap(tokens::SPACE_2)])])
+    .width(Size::Fill(1.0))
+    .padding(Sides {
+        left: LINENO_COL_WIDTH * 2.0,
+        right: tokens::SPACE_2,
+        top: tokens::SPACE_1,
+        bottom: tokens::SPACE_1,
+    })
+}
+
+fn action_button(act: &DiffHunkAction) -> El {
+    let mut btn = button(act.label.clone()).key(act.key.clone()).ghost();
+    if act.destructive {
+        btn = btn.destructive();
+    }
+    if let Some(tip) = act.tooltip.as_ref() {
+        btn = btn.tooltip(tip.clone());
+    }
+    btn
+}
+
 /// Parse `@@ -a,b +c,d @@ <context>` into the range chunk
 /// (`@@ -a,b +c,d @@`) and the optional trailing context (e.g.
 /// `fn main()`). The trailing context is what most diff viewers
@@ -400,9 +532,23 @@ fn backgrounds_for(kind: DiffLineKind) -> (Option<Color>, Color) {
 
 /// One row in split mode: [old_lineno | old_content | new_lineno | new_content].
 /// Either side may be `None` (orphan add/delete with no paired counterpart).
-fn split_pair_row(pair: &PairedRow) -> El {
-    let left = side_half(pair.left.as_ref(), Side::Left);
-    let right = side_half(pair.right.as_ref(), Side::Right);
+fn split_pair_row(pair: &PairedRow, line_key_prefix: Option<&str>) -> El {
+    let mut left = side_half(pair.left.as_ref(), Side::Left);
+    let mut right = side_half(pair.right.as_ref(), Side::Right);
+    // Context lines appear on both halves; only the right one is keyed
+    // so the route stays unique.
+    if let Some(prefix) = line_key_prefix {
+        if let Some(l) = pair
+            .left
+            .as_ref()
+            .filter(|l| l.kind == DiffLineKind::Deletion)
+        {
+            left = left.key(line_route_key(prefix, l));
+        }
+        if let Some(r) = pair.right.as_ref() {
+            right = right.key(line_route_key(prefix, r));
+        }
+    }
     row([left, right])
         .align(Align::Stretch)
         .width(Size::Fill(1.0))
@@ -626,6 +772,9 @@ mod tests {
             badge: None,
             mode: DiffMode::default(),
             mode_toggle_key: None,
+            header_actions: Vec::new(),
+            threads: Vec::new(),
+            line_key_prefix: None,
             hunks: vec![
                 DiffHunk {
                     header: "@@ -1,2 +1,3 @@".into(),
@@ -701,6 +850,80 @@ mod tests {
         assert_eq!(pairs[2].right.as_ref().unwrap().content, "new3");
     }
 
+    fn numbered(kind: DiffLineKind, old: Option<u32>, new: Option<u32>) -> DiffLine {
+        DiffLine {
+            old_lineno: old,
+            new_lineno: new,
+            ..line(kind, "")
+        }
+    }
+
+    fn thread(anchor: Option<(DiffSide, u32)>, author: &str) -> DiffThread {
+        DiffThread {
+            anchor,
+            badges: Vec::new(),
+            comments: vec![DiffComment {
+                author: author.into(),
+                meta: String::new(),
+                body: String::new(),
+            }],
+            actions: Vec::new(),
+        }
+    }
+
+    fn row_kinds(rows: &[DiffRow]) -> Vec<String> {
+        rows.iter()
+            .map(|r| match r {
+                DiffRow::HunkHeader { .. } => "hunk".to_string(),
+                DiffRow::UnifiedLine(_) => "line".to_string(),
+                DiffRow::SplitPair(_) => "pair".to_string(),
+                DiffRow::Thread(t) => format!("thread:{}", t.comments[0].author),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flatten_rows_places_threads_under_their_lines() {
+        let hunks = vec![DiffHunk {
+            header: "@@ -1,2 +1,2 @@".into(),
+            actions: Vec::new(),
+            lines: vec![
+                numbered(DiffLineKind::Context, Some(1), Some(1)),
+                numbered(DiffLineKind::Deletion, Some(2), None),
+                numbered(DiffLineKind::Addition, None, Some(2)),
+            ],
+        }];
+        let threads = vec![
+            thread(Some((DiffSide::New, 2)), "new"),
+            thread(Some((DiffSide::Old, 2)), "old"),
+            thread(Some((DiffSide::New, 40)), "elsewhere"),
+            thread(None, "outdated"),
+        ];
+
+        let unified = flatten_rows(&hunks, DiffMode::Unified, &threads);
+        assert_eq!(
+            row_kinds(&unified),
+            [
+                "thread:elsewhere",
+                "thread:outdated",
+                "hunk",
+                "line",
+                "line",
+                "thread:old",
+                "line",
+                "thread:new",
+            ]
+        );
+
+        // The deletion and addition share a split row, so both threads
+        // follow it.
+        let split = flatten_rows(&hunks, DiffMode::Split, &threads);
+        assert_eq!(
+            row_kinds(&split)[2..],
+            ["hunk", "pair", "pair", "thread:new", "thread:old"]
+        );
+    }
+
     #[test]
     fn split_hunk_header_separates_range_and_context() {
         let (range, ctx) = split_hunk_header("@@ -10,5 +12,7 @@ fn main() {");